@@ -4,6 +4,27 @@ mod m20220101_000001_create_table;
 mod m20241209_000002_create_roles;
 mod m20241209_000003_drop_is_admin;
 mod m20251212_151853_create_merchant_site_structure;
+mod m20251215_120000_create_login_challenges;
+mod m20251216_090000_create_token_revocations;
+mod m20251217_100000_create_refresh_tokens;
+mod m20251218_090000_create_permissions;
+mod m20251219_090000_create_merchant_status_history;
+mod m20251220_090000_secure_site_credentials;
+mod m20251221_090000_create_payment_intents;
+mod m20251222_090000_create_ledger;
+mod m20251223_090000_create_traders;
+mod m20251224_090000_add_payment_matching;
+mod m20251225_090000_add_payment_confirmation;
+mod m20251226_090000_create_disputes;
+mod m20251227_090000_create_webhooks;
+mod m20251228_090000_create_idempotency_keys;
+mod m20251229_090000_create_audit_log;
+mod m20251230_090000_add_audit_log_hash_chain;
+mod m20251231_090000_add_users_deleted_at;
+mod m20260101_090000_create_password_history;
+mod m20260102_090000_add_idempotency_key_lease;
+mod m20260103_090000_add_site_credentials_allow_any_ip;
+mod m20260104_090000_add_dispute_adjusted_payment_index;
 
 pub struct Migrator;
 
@@ -15,6 +36,27 @@ impl MigratorTrait for Migrator {
             Box::new(m20241209_000002_create_roles::Migration),
             Box::new(m20241209_000003_drop_is_admin::Migration),
             Box::new(m20251212_151853_create_merchant_site_structure::Migration),
+            Box::new(m20251215_120000_create_login_challenges::Migration),
+            Box::new(m20251216_090000_create_token_revocations::Migration),
+            Box::new(m20251217_100000_create_refresh_tokens::Migration),
+            Box::new(m20251218_090000_create_permissions::Migration),
+            Box::new(m20251219_090000_create_merchant_status_history::Migration),
+            Box::new(m20251220_090000_secure_site_credentials::Migration),
+            Box::new(m20251221_090000_create_payment_intents::Migration),
+            Box::new(m20251222_090000_create_ledger::Migration),
+            Box::new(m20251223_090000_create_traders::Migration),
+            Box::new(m20251224_090000_add_payment_matching::Migration),
+            Box::new(m20251225_090000_add_payment_confirmation::Migration),
+            Box::new(m20251226_090000_create_disputes::Migration),
+            Box::new(m20251227_090000_create_webhooks::Migration),
+            Box::new(m20251228_090000_create_idempotency_keys::Migration),
+            Box::new(m20251229_090000_create_audit_log::Migration),
+            Box::new(m20251230_090000_add_audit_log_hash_chain::Migration),
+            Box::new(m20251231_090000_add_users_deleted_at::Migration),
+            Box::new(m20260101_090000_create_password_history::Migration),
+            Box::new(m20260102_090000_add_idempotency_key_lease::Migration),
+            Box::new(m20260103_090000_add_site_credentials_allow_any_ip::Migration),
+            Box::new(m20260104_090000_add_dispute_adjusted_payment_index::Migration),
         ]
     }
 }
@@ -0,0 +1,71 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Create login challenges table (2nd step of backoffice login)
+        manager
+            .create_table(
+                Table::create()
+                    .table(LoginChallenges::Table)
+                    .if_not_exists()
+                    .col(uuid(LoginChallenges::Id).primary_key())
+                    .col(uuid(LoginChallenges::UserId).not_null())
+                    .col(string(LoginChallenges::CodeHash).not_null())
+                    .col(integer(LoginChallenges::Attempts).not_null().default(0))
+                    .col(timestamp_with_time_zone(LoginChallenges::ExpiresAt).not_null())
+                    .col(timestamp_with_time_zone_null(LoginChallenges::ConsumedAt))
+                    .col(timestamp_with_time_zone(LoginChallenges::CreatedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_login_challenges_user_id")
+                            .from(LoginChallenges::Table, LoginChallenges::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: Add JOIN index
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_login_challenges_user_id")
+                    .table(LoginChallenges::Table)
+                    .col(LoginChallenges::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LoginChallenges::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LoginChallenges {
+    Table,
+    Id,
+    UserId,
+    CodeHash,
+    Attempts,
+    ExpiresAt,
+    ConsumedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,98 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Single revoked tokens (logout), kept until the token expires
+        manager
+            .create_table(
+                Table::create()
+                    .table(RevokedTokens::Table)
+                    .if_not_exists()
+                    .col(uuid(RevokedTokens::Jti).primary_key())
+                    .col(uuid(RevokedTokens::UserId).not_null())
+                    .col(timestamp_with_time_zone(RevokedTokens::ExpiresAt).not_null())
+                    .col(timestamp_with_time_zone(RevokedTokens::RevokedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_revoked_tokens_user_id")
+                            .from(RevokedTokens::Table, RevokedTokens::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: Per-user cut-off, every token issued before it is revoked
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserTokenRevocations::Table)
+                    .if_not_exists()
+                    .col(uuid(UserTokenRevocations::UserId).primary_key())
+                    .col(timestamp_with_time_zone(UserTokenRevocations::RevokedBefore).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_token_revocations_user_id")
+                            .from(UserTokenRevocations::Table, UserTokenRevocations::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 3: Index for purging expired entries
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_revoked_tokens_expires_at")
+                    .table(RevokedTokens::Table)
+                    .col(RevokedTokens::ExpiresAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserTokenRevocations::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(RevokedTokens::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum RevokedTokens {
+    Table,
+    Jti,
+    UserId,
+    ExpiresAt,
+    RevokedAt,
+}
+
+#[derive(DeriveIden)]
+enum UserTokenRevocations {
+    Table,
+    UserId,
+    RevokedBefore,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
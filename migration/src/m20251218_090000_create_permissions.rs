@@ -0,0 +1,195 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const ADMIN_ROLE_ID: &str = "878c19c6-643b-4a57-98f1-a60786a38a92";
+const SUPPORT_ROLE_ID: &str = "e79d6652-5efb-43ae-9565-04b3d3fcfc0f";
+const RISK_ROLE_ID: &str = "48cd5981-0e75-4329-8e1d-57681e8715db";
+const FINANCE_ROLE_ID: &str = "2e457833-9393-4a8f-9c0e-4314e1425312";
+const USER_ROLE_ID: &str = "eec86d00-495c-490c-b151-b9d33672a681";
+
+const PERMISSIONS: &[(&str, &str)] = &[
+    ("users:read", "View backoffice users"),
+    (
+        "users:write",
+        "Create, update and delete backoffice users, revoke their sessions",
+    ),
+    ("roles:read", "View roles and their permissions"),
+    ("roles:write", "Create, update and delete roles"),
+    ("merchants:read", "View merchants and their sites"),
+    (
+        "merchants:write",
+        "Create and update merchants and their sites",
+    ),
+    ("payouts:read", "View payouts"),
+    ("payouts:approve", "Approve or reject payouts"),
+];
+
+/// Grants for the non-admin seeded roles; Admin gets every permission
+const ROLE_GRANTS: &[(&str, &[&str])] = &[
+    (SUPPORT_ROLE_ID, &["users:read", "merchants:read"]),
+    (RISK_ROLE_ID, &["users:read", "merchants:read"]),
+    (
+        FINANCE_ROLE_ID,
+        &[
+            "users:read",
+            "merchants:read",
+            "payouts:read",
+            "payouts:approve",
+        ],
+    ),
+    (USER_ROLE_ID, &["users:read"]),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Create permissions table
+        manager
+            .create_table(
+                Table::create()
+                    .table(Permissions::Table)
+                    .if_not_exists()
+                    .col(uuid(Permissions::PermissionId).primary_key())
+                    .col(string(Permissions::PermissionName).unique_key().not_null())
+                    .col(string_null(Permissions::PermissionDescription))
+                    .col(timestamp_with_time_zone(Permissions::CreatedAt).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: Create role_permissions join table
+        manager
+            .create_table(
+                Table::create()
+                    .table(RolePermissions::Table)
+                    .if_not_exists()
+                    .col(uuid(RolePermissions::RoleId).not_null())
+                    .col(uuid(RolePermissions::PermissionId).not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(RolePermissions::RoleId)
+                            .col(RolePermissions::PermissionId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_role_permissions_role_id")
+                            .from(RolePermissions::Table, RolePermissions::RoleId)
+                            .to(Roles::Table, Roles::RoleId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_role_permissions_permission_id")
+                            .from(RolePermissions::Table, RolePermissions::PermissionId)
+                            .to(Permissions::Table, Permissions::PermissionId)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 3: Seed permissions
+        let now_str = chrono::Utc::now().to_rfc3339();
+        let values = PERMISSIONS
+            .iter()
+            .map(|(name, description)| {
+                format!(
+                    "(gen_random_uuid(), '{}', '{}', '{}')",
+                    name, description, now_str
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",\n                ");
+
+        let insert_permissions = format!(
+            r#"
+            INSERT INTO permissions (permission_id, permission_name, permission_description, created_at)
+            VALUES
+                {}
+            ON CONFLICT (permission_name) DO NOTHING
+            "#,
+            values
+        );
+
+        manager
+            .get_connection()
+            .execute_unprepared(&insert_permissions)
+            .await?;
+
+        // Step 4: Admin gets every permission
+        let grant_admin = format!(
+            r#"
+            INSERT INTO role_permissions (role_id, permission_id)
+            SELECT '{}'::uuid, permission_id FROM permissions
+            ON CONFLICT DO NOTHING
+            "#,
+            ADMIN_ROLE_ID
+        );
+
+        manager
+            .get_connection()
+            .execute_unprepared(&grant_admin)
+            .await?;
+
+        // Step 5: Other seeded roles keep the access they had with hard-coded role lists
+        for (role_id, permissions) in ROLE_GRANTS {
+            let names = permissions
+                .iter()
+                .map(|name| format!("'{}'", name))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let grant = format!(
+                r#"
+                INSERT INTO role_permissions (role_id, permission_id)
+                SELECT '{}'::uuid, permission_id FROM permissions
+                WHERE permission_name IN ({})
+                ON CONFLICT DO NOTHING
+                "#,
+                role_id, names
+            );
+
+            manager.get_connection().execute_unprepared(&grant).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RolePermissions::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Permissions::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Permissions {
+    Table,
+    PermissionId,
+    PermissionName,
+    PermissionDescription,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum RolePermissions {
+    Table,
+    RoleId,
+    PermissionId,
+}
+
+#[derive(DeriveIden)]
+enum Roles {
+    Table,
+    RoleId,
+}
@@ -0,0 +1,129 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: KYB completion date, required before a merchant goes live
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Merchant::Table)
+                    .add_column(timestamp_with_time_zone_null(Merchant::KybVerifiedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: site_credentials.is_active was created as a string column
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE site_credentials ALTER COLUMN is_active TYPE boolean USING is_active::boolean",
+            )
+            .await?;
+
+        // Step 3: Create merchant_status_history table
+        manager
+            .create_table(
+                Table::create()
+                    .table(MerchantStatusHistory::Table)
+                    .if_not_exists()
+                    .col(uuid(MerchantStatusHistory::Id).primary_key())
+                    .col(uuid(MerchantStatusHistory::MerchantId).not_null())
+                    .col(string(MerchantStatusHistory::FromStatus).not_null())
+                    .col(string(MerchantStatusHistory::ToStatus).not_null())
+                    .col(text_null(MerchantStatusHistory::Reason))
+                    .col(uuid_null(MerchantStatusHistory::ChangedBy))
+                    .col(timestamp_with_time_zone(MerchantStatusHistory::ChangedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_merchant_status_history_merchant_id")
+                            .from(
+                                MerchantStatusHistory::Table,
+                                MerchantStatusHistory::MerchantId,
+                            )
+                            .to(Merchant::Table, Merchant::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_merchant_status_history_changed_by")
+                            .from(
+                                MerchantStatusHistory::Table,
+                                MerchantStatusHistory::ChangedBy,
+                            )
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 4: History is always read per merchant in time order
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_merchant_status_history_merchant_id_changed_at")
+                    .table(MerchantStatusHistory::Table)
+                    .col(MerchantStatusHistory::MerchantId)
+                    .col(MerchantStatusHistory::ChangedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MerchantStatusHistory::Table).to_owned())
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE site_credentials ALTER COLUMN is_active TYPE varchar USING is_active::varchar",
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Merchant::Table)
+                    .drop_column(Merchant::KybVerifiedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Merchant {
+    Table,
+    Id,
+    KybVerifiedAt,
+}
+
+#[derive(DeriveIden)]
+enum MerchantStatusHistory {
+    Table,
+    Id,
+    MerchantId,
+    FromStatus,
+    ToStatus,
+    Reason,
+    ChangedBy,
+    ChangedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
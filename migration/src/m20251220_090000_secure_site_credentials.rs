@@ -0,0 +1,85 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Rotation overlap and revocation timestamps
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SiteCredentials::Table)
+                    .add_column(timestamp_with_time_zone_null(SiteCredentials::ExpiresAt))
+                    .add_column(timestamp_with_time_zone_null(SiteCredentials::RevokedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: Secrets were never issued by the application; anything stored so far
+        // is plaintext and cannot be trusted, so revoke it before the column changes meaning
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE site_credentials SET is_active = false, revoked_at = now()")
+            .await?;
+
+        // Step 3: Secret is now stored AES-GCM encrypted; ciphertexts use random nonces
+        // so the uniqueness constraint is meaningless
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE site_credentials DROP CONSTRAINT IF EXISTS site_credentials_secret_key_key",
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SiteCredentials::Table)
+                    .rename_column(
+                        SiteCredentials::SecretKey,
+                        SiteCredentials::EncryptedSecretKey,
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SiteCredentials::Table)
+                    .drop_column(SiteCredentials::ExpiresAt)
+                    .drop_column(SiteCredentials::RevokedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SiteCredentials::Table)
+                    .rename_column(
+                        SiteCredentials::EncryptedSecretKey,
+                        SiteCredentials::SecretKey,
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SiteCredentials {
+    Table,
+    SecretKey,
+    EncryptedSecretKey,
+    ExpiresAt,
+    RevokedAt,
+}
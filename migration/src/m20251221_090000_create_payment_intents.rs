@@ -0,0 +1,173 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const ADMIN_ROLE_ID: &str = "878c19c6-643b-4a57-98f1-a60786a38a92";
+const SUPPORT_ROLE_ID: &str = "e79d6652-5efb-43ae-9565-04b3d3fcfc0f";
+const RISK_ROLE_ID: &str = "48cd5981-0e75-4329-8e1d-57681e8715db";
+const FINANCE_ROLE_ID: &str = "2e457833-9393-4a8f-9c0e-4314e1425312";
+
+const PAYMENTS_READ: &str = "payments:read";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Create payment_intent table
+        manager
+            .create_table(
+                Table::create()
+                    .table(PaymentIntent::Table)
+                    .if_not_exists()
+                    .col(uuid(PaymentIntent::Id).primary_key())
+                    .col(uuid(PaymentIntent::MerchantId).not_null())
+                    .col(uuid(PaymentIntent::SiteId).not_null())
+                    .col(string(PaymentIntent::ExternalOrderId).not_null())
+                    .col(big_integer(PaymentIntent::Amount).not_null())
+                    .col(string_len(PaymentIntent::Currency, 3).not_null())
+                    .col(string(PaymentIntent::Status).not_null())
+                    .col(text_null(PaymentIntent::Description))
+                    .col(timestamp_with_time_zone(PaymentIntent::ExpiresAt).not_null())
+                    .col(timestamp_with_time_zone(PaymentIntent::CreatedAt).not_null())
+                    .col(timestamp_with_time_zone(PaymentIntent::UpdatedAt).not_null())
+                    .check(Expr::col(PaymentIntent::Amount).gt(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_payment_intent_merchant_id")
+                            .from(PaymentIntent::Table, PaymentIntent::MerchantId)
+                            .to(Merchant::Table, Merchant::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_payment_intent_site_id")
+                            .from(PaymentIntent::Table, PaymentIntent::SiteId)
+                            .to(Site::Table, Site::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: A merchant order maps to a single payment per site
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_payment_intent_site_id_external_order_id")
+                    .table(PaymentIntent::Table)
+                    .col(PaymentIntent::SiteId)
+                    .col(PaymentIntent::ExternalOrderId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 3: Backoffice lists are filtered by merchant and sorted by creation time
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_payment_intent_merchant_id_created_at")
+                    .table(PaymentIntent::Table)
+                    .col(PaymentIntent::MerchantId)
+                    .col(PaymentIntent::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 4: The expiry sweep only looks at open intents
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_payment_intent_open_expires_at \
+                 ON payment_intent (expires_at) WHERE status IN ('created', 'pending')",
+            )
+            .await?;
+
+        // Step 5: Seed the payments:read permission for the staff roles
+        let now_str = chrono::Utc::now().to_rfc3339();
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"
+                INSERT INTO permissions (permission_id, permission_name, permission_description, created_at)
+                VALUES (gen_random_uuid(), '{}', 'View payment intents', '{}')
+                ON CONFLICT (permission_name) DO NOTHING
+                "#,
+                PAYMENTS_READ, now_str
+            ))
+            .await?;
+
+        let role_ids = [
+            ADMIN_ROLE_ID,
+            SUPPORT_ROLE_ID,
+            RISK_ROLE_ID,
+            FINANCE_ROLE_ID,
+        ]
+        .iter()
+        .map(|id| format!("'{}'::uuid", id))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"
+                INSERT INTO role_permissions (role_id, permission_id)
+                SELECT roles.role_id, permissions.permission_id
+                FROM roles, permissions
+                WHERE roles.role_id IN ({}) AND permissions.permission_name = '{}'
+                ON CONFLICT DO NOTHING
+                "#,
+                role_ids, PAYMENTS_READ
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                "DELETE FROM permissions WHERE permission_name = '{}'",
+                PAYMENTS_READ
+            ))
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PaymentIntent::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum PaymentIntent {
+    Table,
+    Id,
+    MerchantId,
+    SiteId,
+    ExternalOrderId,
+    Amount,
+    Currency,
+    Status,
+    Description,
+    ExpiresAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Merchant {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Site {
+    Table,
+    Id,
+}
@@ -0,0 +1,284 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const ADMIN_ROLE_ID: &str = "878c19c6-643b-4a57-98f1-a60786a38a92";
+const FINANCE_ROLE_ID: &str = "2e457833-9393-4a8f-9c0e-4314e1425312";
+
+const LEDGER_READ: &str = "ledger:read";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Create ledger_account table
+        manager
+            .create_table(
+                Table::create()
+                    .table(LedgerAccount::Table)
+                    .if_not_exists()
+                    .col(uuid(LedgerAccount::Id).primary_key())
+                    .col(string(LedgerAccount::Kind).not_null())
+                    .col(uuid_null(LedgerAccount::OwnerId))
+                    .col(string_len(LedgerAccount::Currency, 3).not_null())
+                    .col(timestamp_with_time_zone(LedgerAccount::CreatedAt).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: Postings reference (id, currency) so they cannot change the account's currency
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_ledger_account_id_currency")
+                    .table(LedgerAccount::Table)
+                    .col(LedgerAccount::Id)
+                    .col(LedgerAccount::Currency)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 3: One account per kind, owner and currency; system accounts have no owner
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_ledger_account_kind_owner_currency \
+                 ON ledger_account (kind, COALESCE(owner_id, '00000000-0000-0000-0000-000000000000'::uuid), currency)",
+            )
+            .await?;
+
+        // Step 4: Create ledger_journal_entry table
+        manager
+            .create_table(
+                Table::create()
+                    .table(LedgerJournalEntry::Table)
+                    .if_not_exists()
+                    .col(uuid(LedgerJournalEntry::Id).primary_key())
+                    .col(string(LedgerJournalEntry::Kind).not_null())
+                    .col(uuid_null(LedgerJournalEntry::ReferenceId))
+                    .col(text_null(LedgerJournalEntry::Description))
+                    .col(uuid_null(LedgerJournalEntry::CreatedBy))
+                    .col(timestamp_with_time_zone(LedgerJournalEntry::EffectiveAt).not_null())
+                    .col(timestamp_with_time_zone(LedgerJournalEntry::CreatedAt).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 5: A payment is settled and charged at most once
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_ledger_journal_entry_kind_reference_id \
+                 ON ledger_journal_entry (kind, reference_id) WHERE reference_id IS NOT NULL",
+            )
+            .await?;
+
+        // Step 6: Create ledger_posting table; positive amounts debit, negative amounts credit
+        manager
+            .create_table(
+                Table::create()
+                    .table(LedgerPosting::Table)
+                    .if_not_exists()
+                    .col(uuid(LedgerPosting::Id).primary_key())
+                    .col(uuid(LedgerPosting::EntryId).not_null())
+                    .col(uuid(LedgerPosting::AccountId).not_null())
+                    .col(big_integer(LedgerPosting::Amount).not_null())
+                    .col(string_len(LedgerPosting::Currency, 3).not_null())
+                    .col(timestamp_with_time_zone(LedgerPosting::EffectiveAt).not_null())
+                    .check(Expr::col(LedgerPosting::Amount).ne(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_ledger_posting_entry_id")
+                            .from(LedgerPosting::Table, LedgerPosting::EntryId)
+                            .to(LedgerJournalEntry::Table, LedgerJournalEntry::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Restrict),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_ledger_posting_account_id_currency")
+                            .from(LedgerPosting::Table, LedgerPosting::AccountId)
+                            .from(LedgerPosting::Table, LedgerPosting::Currency)
+                            .to(LedgerAccount::Table, LedgerAccount::Id)
+                            .to(LedgerAccount::Table, LedgerAccount::Currency)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 7: Balance queries sum an account's postings up to a point in time
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_ledger_posting_account_id_effective_at")
+                    .table(LedgerPosting::Table)
+                    .col(LedgerPosting::AccountId)
+                    .col(LedgerPosting::EffectiveAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_ledger_posting_entry_id")
+                    .table(LedgerPosting::Table)
+                    .col(LedgerPosting::EntryId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 8: Every entry must sum to zero per currency, checked once the transaction commits
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE OR REPLACE FUNCTION ledger_check_entry_balanced() RETURNS trigger AS $$
+                DECLARE
+                    unbalanced record;
+                BEGIN
+                    SELECT currency, SUM(amount) AS total INTO unbalanced
+                    FROM ledger_posting
+                    WHERE entry_id = NEW.entry_id
+                    GROUP BY currency
+                    HAVING SUM(amount) <> 0
+                    LIMIT 1;
+
+                    IF FOUND THEN
+                        RAISE EXCEPTION 'Journal entry % does not balance in %: %',
+                            NEW.entry_id, unbalanced.currency, unbalanced.total
+                            USING ERRCODE = 'check_violation';
+                    END IF;
+
+                    RETURN NULL;
+                END;
+                $$ LANGUAGE plpgsql;
+
+                CREATE CONSTRAINT TRIGGER trg_ledger_posting_balanced
+                    AFTER INSERT ON ledger_posting
+                    DEFERRABLE INITIALLY DEFERRED
+                    FOR EACH ROW EXECUTE FUNCTION ledger_check_entry_balanced();
+                "#,
+            )
+            .await?;
+
+        // Step 9: The ledger is append-only; corrections are posted as new entries
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE OR REPLACE FUNCTION ledger_reject_mutation() RETURNS trigger AS $$
+                BEGIN
+                    RAISE EXCEPTION '% is append-only', TG_TABLE_NAME
+                        USING ERRCODE = 'restrict_violation';
+                END;
+                $$ LANGUAGE plpgsql;
+
+                CREATE TRIGGER trg_ledger_journal_entry_append_only
+                    BEFORE UPDATE OR DELETE ON ledger_journal_entry
+                    FOR EACH ROW EXECUTE FUNCTION ledger_reject_mutation();
+
+                CREATE TRIGGER trg_ledger_posting_append_only
+                    BEFORE UPDATE OR DELETE ON ledger_posting
+                    FOR EACH ROW EXECUTE FUNCTION ledger_reject_mutation();
+                "#,
+            )
+            .await?;
+
+        // Step 10: Seed the ledger:read permission for Admin and Finance
+        let now_str = chrono::Utc::now().to_rfc3339();
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"
+                INSERT INTO permissions (permission_id, permission_name, permission_description, created_at)
+                VALUES (gen_random_uuid(), '{}', 'View ledger accounts, balances and journal entries', '{}')
+                ON CONFLICT (permission_name) DO NOTHING
+                "#,
+                LEDGER_READ, now_str
+            ))
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"
+                INSERT INTO role_permissions (role_id, permission_id)
+                SELECT roles.role_id, permissions.permission_id
+                FROM roles, permissions
+                WHERE roles.role_id IN ('{}'::uuid, '{}'::uuid) AND permissions.permission_name = '{}'
+                ON CONFLICT DO NOTHING
+                "#,
+                ADMIN_ROLE_ID, FINANCE_ROLE_ID, LEDGER_READ
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                "DELETE FROM permissions WHERE permission_name = '{}'",
+                LEDGER_READ
+            ))
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(LedgerPosting::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(LedgerJournalEntry::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(LedgerAccount::Table).to_owned())
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DROP FUNCTION IF EXISTS ledger_check_entry_balanced(); \
+                 DROP FUNCTION IF EXISTS ledger_reject_mutation();",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum LedgerAccount {
+    Table,
+    Id,
+    Kind,
+    OwnerId,
+    Currency,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum LedgerJournalEntry {
+    Table,
+    Id,
+    Kind,
+    ReferenceId,
+    Description,
+    CreatedBy,
+    EffectiveAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum LedgerPosting {
+    Table,
+    Id,
+    EntryId,
+    AccountId,
+    Amount,
+    Currency,
+    EffectiveAt,
+}
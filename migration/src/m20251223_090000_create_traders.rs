@@ -0,0 +1,167 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const ADMIN_ROLE_ID: &str = "878c19c6-643b-4a57-98f1-a60786a38a92";
+const RISK_ROLE_ID: &str = "48cd5981-0e75-4329-8e1d-57681e8715db";
+const FINANCE_ROLE_ID: &str = "2e457833-9393-4a8f-9c0e-4314e1425312";
+
+const TRADERS_READ: &str = "traders:read";
+const TRADERS_WRITE: &str = "traders:write";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Create trader table
+        manager
+            .create_table(
+                Table::create()
+                    .table(Trader::Table)
+                    .if_not_exists()
+                    .col(uuid(Trader::Id).primary_key())
+                    .col(string(Trader::Name).not_null().unique_key())
+                    .col(string_null(Trader::Contact))
+                    .col(string(Trader::Availability).not_null())
+                    .col(timestamp_with_time_zone(Trader::AvailabilityChangedAt).not_null())
+                    .col(timestamp_with_time_zone(Trader::CreatedAt).not_null())
+                    .col(timestamp_with_time_zone(Trader::UpdatedAt).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: Create trader_requisite table; the full number is only stored encrypted
+        manager
+            .create_table(
+                Table::create()
+                    .table(TraderRequisite::Table)
+                    .if_not_exists()
+                    .col(uuid(TraderRequisite::Id).primary_key())
+                    .col(uuid(TraderRequisite::TraderId).not_null())
+                    .col(string(TraderRequisite::Kind).not_null())
+                    .col(string(TraderRequisite::BankName).not_null())
+                    .col(string(TraderRequisite::HolderName).not_null())
+                    .col(string(TraderRequisite::MaskedNumber).not_null())
+                    .col(text(TraderRequisite::EncryptedNumber).not_null())
+                    .col(string_len(TraderRequisite::Currency, 3).not_null())
+                    .col(big_integer(TraderRequisite::DailyLimit).not_null())
+                    .col(big_integer(TraderRequisite::MonthlyLimit).not_null())
+                    .col(boolean(TraderRequisite::IsActive).not_null())
+                    .col(timestamp_with_time_zone(TraderRequisite::CreatedAt).not_null())
+                    .col(timestamp_with_time_zone(TraderRequisite::UpdatedAt).not_null())
+                    .check(Expr::col(TraderRequisite::DailyLimit).gt(0))
+                    .check(
+                        Expr::col(TraderRequisite::MonthlyLimit)
+                            .gte(Expr::col(TraderRequisite::DailyLimit)),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_trader_requisite_trader_id")
+                            .from(TraderRequisite::Table, TraderRequisite::TraderId)
+                            .to(Trader::Table, Trader::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_trader_requisite_trader_id")
+                    .table(TraderRequisite::Table)
+                    .col(TraderRequisite::TraderId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 3: Seed trader permissions; Finance can see deposits, Risk manages traders
+        let now_str = chrono::Utc::now().to_rfc3339();
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"
+                INSERT INTO permissions (permission_id, permission_name, permission_description, created_at)
+                VALUES
+                    (gen_random_uuid(), '{}', 'View traders, their requisites and deposits', '{}'),
+                    (gen_random_uuid(), '{}', 'Manage traders, requisites, limits and deposits', '{}')
+                ON CONFLICT (permission_name) DO NOTHING
+                "#,
+                TRADERS_READ, now_str, TRADERS_WRITE, now_str
+            ))
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"
+                INSERT INTO role_permissions (role_id, permission_id)
+                SELECT roles.role_id, permissions.permission_id
+                FROM roles, permissions
+                WHERE (roles.role_id IN ('{}'::uuid, '{}'::uuid, '{}'::uuid) AND permissions.permission_name = '{}')
+                   OR (roles.role_id IN ('{}'::uuid, '{}'::uuid) AND permissions.permission_name = '{}')
+                ON CONFLICT DO NOTHING
+                "#,
+                ADMIN_ROLE_ID,
+                RISK_ROLE_ID,
+                FINANCE_ROLE_ID,
+                TRADERS_READ,
+                ADMIN_ROLE_ID,
+                RISK_ROLE_ID,
+                TRADERS_WRITE
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                "DELETE FROM permissions WHERE permission_name IN ('{}', '{}')",
+                TRADERS_READ, TRADERS_WRITE
+            ))
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(TraderRequisite::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Trader::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Trader {
+    Table,
+    Id,
+    Name,
+    Contact,
+    Availability,
+    AvailabilityChangedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum TraderRequisite {
+    Table,
+    Id,
+    TraderId,
+    Kind,
+    BankName,
+    HolderName,
+    MaskedNumber,
+    EncryptedNumber,
+    Currency,
+    DailyLimit,
+    MonthlyLimit,
+    IsActive,
+    CreatedAt,
+    UpdatedAt,
+}
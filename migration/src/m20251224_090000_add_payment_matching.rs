@@ -0,0 +1,127 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Requested payment method and the trader requisite the payment was matched to.
+        // Payments keep their requisite for the limit and dispute history, so neither may be
+        // deleted while referenced
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PaymentIntent::Table)
+                    .add_column(string_null(PaymentIntent::PaymentMethod))
+                    .add_column(uuid_null(PaymentIntent::TraderId))
+                    .add_column(uuid_null(PaymentIntent::RequisiteId))
+                    .add_column(timestamp_with_time_zone_null(PaymentIntent::AssignedAt))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_payment_intent_trader_id")
+                            .from_tbl(PaymentIntent::Table)
+                            .from_col(PaymentIntent::TraderId)
+                            .to_tbl(Trader::Table)
+                            .to_col(Trader::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_payment_intent_requisite_id")
+                            .from_tbl(PaymentIntent::Table)
+                            .from_col(PaymentIntent::RequisiteId)
+                            .to_tbl(TraderRequisite::Table)
+                            .to_col(TraderRequisite::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: Open payments per trader and limit usage per requisite are read on every match
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_payment_intent_trader_id_status")
+                    .table(PaymentIntent::Table)
+                    .col(PaymentIntent::TraderId)
+                    .col(PaymentIntent::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_payment_intent_requisite_id_assigned_at")
+                    .table(PaymentIntent::Table)
+                    .col(PaymentIntent::RequisiteId)
+                    .col(PaymentIntent::AssignedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_payment_intent_requisite_id_assigned_at")
+                    .table(PaymentIntent::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_payment_intent_trader_id_status")
+                    .table(PaymentIntent::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PaymentIntent::Table)
+                    .drop_foreign_key(Alias::new("fk_payment_intent_requisite_id"))
+                    .drop_foreign_key(Alias::new("fk_payment_intent_trader_id"))
+                    .drop_column(PaymentIntent::PaymentMethod)
+                    .drop_column(PaymentIntent::TraderId)
+                    .drop_column(PaymentIntent::RequisiteId)
+                    .drop_column(PaymentIntent::AssignedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum PaymentIntent {
+    Table,
+    PaymentMethod,
+    TraderId,
+    RequisiteId,
+    AssignedAt,
+    Status,
+}
+
+#[derive(DeriveIden)]
+enum Trader {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum TraderRequisite {
+    Table,
+    Id,
+}
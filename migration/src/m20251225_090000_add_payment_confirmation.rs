@@ -0,0 +1,162 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const ADMIN_ROLE_ID: &str = "878c19c6-643b-4a57-98f1-a60786a38a92";
+const SUPPORT_ROLE_ID: &str = "e79d6652-5efb-43ae-9565-04b3d3fcfc0f";
+
+const PAYMENTS_CONFIRM: &str = "payments:confirm";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Confirmation timestamps, the trader's rejection reason and the dispute window
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PaymentIntent::Table)
+                    .add_column(timestamp_with_time_zone_null(PaymentIntent::MarkedPaidAt))
+                    .add_column(timestamp_with_time_zone_null(PaymentIntent::EscalatedAt))
+                    .add_column(timestamp_with_time_zone_null(PaymentIntent::ResolvedAt))
+                    .add_column(timestamp_with_time_zone_null(PaymentIntent::DisputeUntil))
+                    .add_column(text_null(PaymentIntent::RejectionReason))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: The escalation sweep only looks at payments waiting for their trader
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_payment_intent_confirming_marked_paid_at \
+                 ON payment_intent (marked_paid_at) \
+                 WHERE status = 'confirming' AND escalated_at IS NULL",
+            )
+            .await?;
+
+        // Step 3: Create payment_evidence table; the content lives in file storage
+        manager
+            .create_table(
+                Table::create()
+                    .table(PaymentEvidence::Table)
+                    .if_not_exists()
+                    .col(uuid(PaymentEvidence::Id).primary_key())
+                    .col(uuid(PaymentEvidence::PaymentId).not_null())
+                    .col(string(PaymentEvidence::FileName).not_null())
+                    .col(string(PaymentEvidence::ContentType).not_null())
+                    .col(big_integer(PaymentEvidence::SizeBytes).not_null())
+                    .col(string_len(PaymentEvidence::Sha256, 64).not_null())
+                    .col(string(PaymentEvidence::StorageKey).not_null().unique_key())
+                    .col(timestamp_with_time_zone(PaymentEvidence::CreatedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_payment_evidence_payment_id")
+                            .from(PaymentEvidence::Table, PaymentEvidence::PaymentId)
+                            .to(PaymentIntent::Table, PaymentIntent::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_payment_evidence_payment_id")
+                    .table(PaymentEvidence::Table)
+                    .col(PaymentEvidence::PaymentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 4: Seed payments:confirm; Support works the escalation queue
+        let now_str = chrono::Utc::now().to_rfc3339();
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"
+                INSERT INTO permissions (permission_id, permission_name, permission_description, created_at)
+                VALUES (gen_random_uuid(), '{}', 'Confirm or reject payments marked as paid', '{}')
+                ON CONFLICT (permission_name) DO NOTHING
+                "#,
+                PAYMENTS_CONFIRM, now_str
+            ))
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                r#"
+                INSERT INTO role_permissions (role_id, permission_id)
+                SELECT roles.role_id, permissions.permission_id
+                FROM roles, permissions
+                WHERE roles.role_id IN ('{}'::uuid, '{}'::uuid) AND permissions.permission_name = '{}'
+                ON CONFLICT DO NOTHING
+                "#,
+                ADMIN_ROLE_ID, SUPPORT_ROLE_ID, PAYMENTS_CONFIRM
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                "DELETE FROM permissions WHERE permission_name = '{}'",
+                PAYMENTS_CONFIRM
+            ))
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PaymentEvidence::Table).to_owned())
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX IF EXISTS idx_payment_intent_confirming_marked_paid_at")
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PaymentIntent::Table)
+                    .drop_column(PaymentIntent::MarkedPaidAt)
+                    .drop_column(PaymentIntent::EscalatedAt)
+                    .drop_column(PaymentIntent::ResolvedAt)
+                    .drop_column(PaymentIntent::DisputeUntil)
+                    .drop_column(PaymentIntent::RejectionReason)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum PaymentIntent {
+    Table,
+    Id,
+    MarkedPaidAt,
+    EscalatedAt,
+    ResolvedAt,
+    DisputeUntil,
+    RejectionReason,
+}
+
+#[derive(DeriveIden)]
+enum PaymentEvidence {
+    Table,
+    Id,
+    PaymentId,
+    FileName,
+    ContentType,
+    SizeBytes,
+    Sha256,
+    StorageKey,
+    CreatedAt,
+}
@@ -0,0 +1,323 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const ADMIN_ROLE_ID: &str = "878c19c6-643b-4a57-98f1-a60786a38a92";
+const SUPPORT_ROLE_ID: &str = "e79d6652-5efb-43ae-9565-04b3d3fcfc0f";
+const RISK_ROLE_ID: &str = "48cd5981-0e75-4329-8e1d-57681e8715db";
+const FINANCE_ROLE_ID: &str = "2e457833-9393-4a8f-9c0e-4314e1425312";
+
+/// Permission, description and the roles granted it
+const PERMISSIONS: [(&str, &str, &[&str]); 3] = [
+    (
+        "disputes:read",
+        "View disputes, their comments and attachments",
+        &[
+            ADMIN_ROLE_ID,
+            SUPPORT_ROLE_ID,
+            RISK_ROLE_ID,
+            FINANCE_ROLE_ID,
+        ],
+    ),
+    (
+        "disputes:write",
+        "Open, assign and investigate disputes",
+        &[ADMIN_ROLE_ID, SUPPORT_ROLE_ID, RISK_ROLE_ID],
+    ),
+    (
+        "disputes:resolve",
+        "Resolve disputes and post their ledger adjustments",
+        &[ADMIN_ROLE_ID, RISK_ROLE_ID],
+    ),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Create dispute table; amount and parties are copied from the payment
+        manager
+            .create_table(
+                Table::create()
+                    .table(Dispute::Table)
+                    .if_not_exists()
+                    .col(uuid(Dispute::Id).primary_key())
+                    .col(uuid(Dispute::PaymentId).not_null())
+                    .col(uuid(Dispute::MerchantId).not_null())
+                    .col(uuid_null(Dispute::TraderId))
+                    .col(big_integer(Dispute::Amount).not_null())
+                    .col(string_len(Dispute::Currency, 3).not_null())
+                    .col(string_len(Dispute::PaymentStatus, 32).not_null())
+                    .col(text(Dispute::Reason).not_null())
+                    .col(string_len(Dispute::Status, 32).not_null().default("open"))
+                    .col(string_len_null(Dispute::Resolution, 32))
+                    .col(text_null(Dispute::ResolutionNote))
+                    .col(uuid_null(Dispute::AssignedTo))
+                    .col(uuid(Dispute::OpenedBy).not_null())
+                    .col(uuid_null(Dispute::ResolvedBy))
+                    .col(timestamp_with_time_zone_null(Dispute::ResolvedAt))
+                    .col(uuid_null(Dispute::AdjustmentEntryId))
+                    .col(timestamp_with_time_zone(Dispute::CreatedAt).not_null())
+                    .col(timestamp_with_time_zone(Dispute::UpdatedAt).not_null())
+                    .check(Expr::col(Dispute::Amount).gt(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dispute_payment_id")
+                            .from(Dispute::Table, Dispute::PaymentId)
+                            .to(PaymentIntent::Table, PaymentIntent::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dispute_assigned_to")
+                            .from(Dispute::Table, Dispute::AssignedTo)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dispute_adjustment_entry_id")
+                            .from(Dispute::Table, Dispute::AdjustmentEntryId)
+                            .to(LedgerJournalEntry::Table, LedgerJournalEntry::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: One unresolved dispute per payment; the queue is read by status and assignee
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_dispute_unresolved_payment_id \
+                 ON dispute (payment_id) WHERE status <> 'resolved'",
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_dispute_status_created_at")
+                    .table(Dispute::Table)
+                    .col(Dispute::Status)
+                    .col(Dispute::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_dispute_assigned_to")
+                    .table(Dispute::Table)
+                    .col(Dispute::AssignedTo)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 3: Create dispute_comment table for internal notes
+        manager
+            .create_table(
+                Table::create()
+                    .table(DisputeComment::Table)
+                    .if_not_exists()
+                    .col(uuid(DisputeComment::Id).primary_key())
+                    .col(uuid(DisputeComment::DisputeId).not_null())
+                    .col(uuid(DisputeComment::AuthorId).not_null())
+                    .col(text(DisputeComment::Body).not_null())
+                    .col(timestamp_with_time_zone(DisputeComment::CreatedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dispute_comment_dispute_id")
+                            .from(DisputeComment::Table, DisputeComment::DisputeId)
+                            .to(Dispute::Table, Dispute::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_dispute_comment_dispute_id")
+                    .table(DisputeComment::Table)
+                    .col(DisputeComment::DisputeId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 4: Create dispute_attachment table; the content lives in file storage
+        manager
+            .create_table(
+                Table::create()
+                    .table(DisputeAttachment::Table)
+                    .if_not_exists()
+                    .col(uuid(DisputeAttachment::Id).primary_key())
+                    .col(uuid(DisputeAttachment::DisputeId).not_null())
+                    .col(uuid(DisputeAttachment::UploadedBy).not_null())
+                    .col(string(DisputeAttachment::FileName).not_null())
+                    .col(string(DisputeAttachment::ContentType).not_null())
+                    .col(big_integer(DisputeAttachment::SizeBytes).not_null())
+                    .col(string_len(DisputeAttachment::Sha256, 64).not_null())
+                    .col(
+                        string(DisputeAttachment::StorageKey)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(timestamp_with_time_zone(DisputeAttachment::CreatedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dispute_attachment_dispute_id")
+                            .from(DisputeAttachment::Table, DisputeAttachment::DisputeId)
+                            .to(Dispute::Table, Dispute::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_dispute_attachment_dispute_id")
+                    .table(DisputeAttachment::Table)
+                    .col(DisputeAttachment::DisputeId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 5: Seed dispute permissions; Support and Risk work cases, only Risk decides them
+        let now_str = chrono::Utc::now().to_rfc3339();
+        for (name, description, role_ids) in PERMISSIONS {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    r#"
+                    INSERT INTO permissions (permission_id, permission_name, permission_description, created_at)
+                    VALUES (gen_random_uuid(), '{}', '{}', '{}')
+                    ON CONFLICT (permission_name) DO NOTHING
+                    "#,
+                    name, description, now_str
+                ))
+                .await?;
+
+            let role_ids = role_ids
+                .iter()
+                .map(|id| format!("'{}'::uuid", id))
+                .collect::<Vec<_>>()
+                .join(", ");
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    r#"
+                    INSERT INTO role_permissions (role_id, permission_id)
+                    SELECT roles.role_id, permissions.permission_id
+                    FROM roles, permissions
+                    WHERE roles.role_id IN ({}) AND permissions.permission_name = '{}'
+                    ON CONFLICT DO NOTHING
+                    "#,
+                    role_ids, name
+                ))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (name, _, _) in PERMISSIONS {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "DELETE FROM permissions WHERE permission_name = '{}'",
+                    name
+                ))
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(DisputeAttachment::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(DisputeComment::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Dispute::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Dispute {
+    Table,
+    Id,
+    PaymentId,
+    MerchantId,
+    TraderId,
+    Amount,
+    Currency,
+    PaymentStatus,
+    Reason,
+    Status,
+    Resolution,
+    ResolutionNote,
+    AssignedTo,
+    OpenedBy,
+    ResolvedBy,
+    ResolvedAt,
+    AdjustmentEntryId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum DisputeComment {
+    Table,
+    Id,
+    DisputeId,
+    AuthorId,
+    Body,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum DisputeAttachment {
+    Table,
+    Id,
+    DisputeId,
+    UploadedBy,
+    FileName,
+    ContentType,
+    SizeBytes,
+    Sha256,
+    StorageKey,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PaymentIntent {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum LedgerJournalEntry {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
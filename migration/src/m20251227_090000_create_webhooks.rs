@@ -0,0 +1,254 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const ADMIN_ROLE_ID: &str = "878c19c6-643b-4a57-98f1-a60786a38a92";
+const SUPPORT_ROLE_ID: &str = "e79d6652-5efb-43ae-9565-04b3d3fcfc0f";
+
+/// Permission, description and the roles granted it
+const PERMISSIONS: [(&str, &str, &[&str]); 2] = [
+    (
+        "webhooks:read",
+        "View merchant webhook deliveries and their attempts",
+        &[ADMIN_ROLE_ID, SUPPORT_ROLE_ID],
+    ),
+    (
+        "webhooks:redeliver",
+        "Send a merchant webhook again",
+        &[ADMIN_ROLE_ID, SUPPORT_ROLE_ID],
+    ),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Create webhook_delivery table; one row per payment status notification
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookDelivery::Table)
+                    .if_not_exists()
+                    .col(uuid(WebhookDelivery::Id).primary_key())
+                    .col(uuid(WebhookDelivery::SiteId).not_null())
+                    .col(uuid(WebhookDelivery::PaymentId).not_null())
+                    .col(string_len(WebhookDelivery::EventType, 64).not_null())
+                    .col(text(WebhookDelivery::Payload).not_null())
+                    .col(
+                        string_len(WebhookDelivery::Status, 32)
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(integer(WebhookDelivery::Attempts).not_null().default(0))
+                    .col(timestamp_with_time_zone_null(
+                        WebhookDelivery::NextAttemptAt,
+                    ))
+                    .col(timestamp_with_time_zone_null(
+                        WebhookDelivery::LastAttemptAt,
+                    ))
+                    .col(integer_null(WebhookDelivery::LastResponseStatus))
+                    .col(text_null(WebhookDelivery::LastError))
+                    .col(timestamp_with_time_zone_null(WebhookDelivery::DeliveredAt))
+                    .col(timestamp_with_time_zone(WebhookDelivery::CreatedAt).not_null())
+                    .col(timestamp_with_time_zone(WebhookDelivery::UpdatedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webhook_delivery_site_id")
+                            .from(WebhookDelivery::Table, WebhookDelivery::SiteId)
+                            .to(Site::Table, Site::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webhook_delivery_payment_id")
+                            .from(WebhookDelivery::Table, WebhookDelivery::PaymentId)
+                            .to(PaymentIntent::Table, PaymentIntent::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: The dispatcher only scans pending rows; the backoffice reads per site and payment
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE INDEX IF NOT EXISTS idx_webhook_delivery_due \
+                 ON webhook_delivery (next_attempt_at) WHERE status = 'pending'",
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webhook_delivery_site_id_created_at")
+                    .table(WebhookDelivery::Table)
+                    .col(WebhookDelivery::SiteId)
+                    .col(WebhookDelivery::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webhook_delivery_payment_id")
+                    .table(WebhookDelivery::Table)
+                    .col(WebhookDelivery::PaymentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 3: Create webhook_delivery_attempt table, the log of every request sent
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookDeliveryAttempt::Table)
+                    .if_not_exists()
+                    .col(uuid(WebhookDeliveryAttempt::Id).primary_key())
+                    .col(uuid(WebhookDeliveryAttempt::DeliveryId).not_null())
+                    .col(integer(WebhookDeliveryAttempt::AttemptNumber).not_null())
+                    .col(text(WebhookDeliveryAttempt::Url).not_null())
+                    .col(integer_null(WebhookDeliveryAttempt::ResponseStatus))
+                    .col(text_null(WebhookDeliveryAttempt::ResponseBody))
+                    .col(text_null(WebhookDeliveryAttempt::Error))
+                    .col(big_integer(WebhookDeliveryAttempt::DurationMs).not_null())
+                    .col(uuid_null(WebhookDeliveryAttempt::TriggeredBy))
+                    .col(timestamp_with_time_zone(WebhookDeliveryAttempt::AttemptedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webhook_delivery_attempt_delivery_id")
+                            .from(
+                                WebhookDeliveryAttempt::Table,
+                                WebhookDeliveryAttempt::DeliveryId,
+                            )
+                            .to(WebhookDelivery::Table, WebhookDelivery::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webhook_delivery_attempt_delivery_id")
+                    .table(WebhookDeliveryAttempt::Table)
+                    .col(WebhookDeliveryAttempt::DeliveryId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 4: Seed webhook permissions; Support answers merchants asking about callbacks
+        let now_str = chrono::Utc::now().to_rfc3339();
+        for (name, description, role_ids) in PERMISSIONS {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    r#"
+                    INSERT INTO permissions (permission_id, permission_name, permission_description, created_at)
+                    VALUES (gen_random_uuid(), '{}', '{}', '{}')
+                    ON CONFLICT (permission_name) DO NOTHING
+                    "#,
+                    name, description, now_str
+                ))
+                .await?;
+
+            let role_ids = role_ids
+                .iter()
+                .map(|id| format!("'{}'::uuid", id))
+                .collect::<Vec<_>>()
+                .join(", ");
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    r#"
+                    INSERT INTO role_permissions (role_id, permission_id)
+                    SELECT roles.role_id, permissions.permission_id
+                    FROM roles, permissions
+                    WHERE roles.role_id IN ({}) AND permissions.permission_name = '{}'
+                    ON CONFLICT DO NOTHING
+                    "#,
+                    role_ids, name
+                ))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (name, _, _) in PERMISSIONS {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "DELETE FROM permissions WHERE permission_name = '{}'",
+                    name
+                ))
+                .await?;
+        }
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(WebhookDeliveryAttempt::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(WebhookDelivery::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum WebhookDelivery {
+    Table,
+    Id,
+    SiteId,
+    PaymentId,
+    EventType,
+    Payload,
+    Status,
+    Attempts,
+    NextAttemptAt,
+    LastAttemptAt,
+    LastResponseStatus,
+    LastError,
+    DeliveredAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum WebhookDeliveryAttempt {
+    Table,
+    Id,
+    DeliveryId,
+    AttemptNumber,
+    Url,
+    ResponseStatus,
+    ResponseBody,
+    Error,
+    DurationMs,
+    TriggeredBy,
+    AttemptedAt,
+}
+
+#[derive(DeriveIden)]
+enum Site {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum PaymentIntent {
+    Table,
+    Id,
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Create idempotency_keys table; keys are unique per caller, not globally
+        manager
+            .create_table(
+                Table::create()
+                    .table(IdempotencyKeys::Table)
+                    .if_not_exists()
+                    .col(string_len(IdempotencyKeys::Scope, 64).not_null())
+                    .col(string_len(IdempotencyKeys::IdempotencyKey, 255).not_null())
+                    .col(string_len(IdempotencyKeys::RequestFingerprint, 64).not_null())
+                    .col(integer_null(IdempotencyKeys::ResponseStatus))
+                    .col(text_null(IdempotencyKeys::ResponseBody))
+                    .col(timestamp_with_time_zone(IdempotencyKeys::CreatedAt).not_null())
+                    .col(timestamp_with_time_zone(IdempotencyKeys::ExpiresAt).not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(IdempotencyKeys::Scope)
+                            .col(IdempotencyKeys::IdempotencyKey),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: The sweep deletes expired keys
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_idempotency_keys_expires_at")
+                    .table(IdempotencyKeys::Table)
+                    .col(IdempotencyKeys::ExpiresAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IdempotencyKeys::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum IdempotencyKeys {
+    Table,
+    Scope,
+    IdempotencyKey,
+    RequestFingerprint,
+    ResponseStatus,
+    ResponseBody,
+    CreatedAt,
+    ExpiresAt,
+}
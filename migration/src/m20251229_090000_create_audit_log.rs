@@ -0,0 +1,144 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const ADMIN_ROLE_ID: &str = "878c19c6-643b-4a57-98f1-a60786a38a92";
+const RISK_ROLE_ID: &str = "48cd5981-0e75-4329-8e1d-57681e8715db";
+
+/// Permission, description and the roles granted it
+const PERMISSIONS: [(&str, &str, &[&str]); 1] = [(
+    "audit:read",
+    "View the audit log and user activity feeds",
+    &[ADMIN_ROLE_ID, RISK_ROLE_ID],
+)];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Create audit_log table. Actors and entities are not foreign keys;
+        // entries outlive the users and records they mention
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditLog::Table)
+                    .if_not_exists()
+                    .col(uuid(AuditLog::Id).primary_key())
+                    .col(uuid_null(AuditLog::ActorId))
+                    .col(string_len(AuditLog::Action, 32).not_null())
+                    .col(string_len(AuditLog::EntityType, 64).not_null())
+                    .col(uuid(AuditLog::EntityId).not_null())
+                    .col(text(AuditLog::Changes).not_null())
+                    .col(string_len_null(AuditLog::IpAddress, 45))
+                    .col(text_null(AuditLog::UserAgent))
+                    .col(string_len_null(AuditLog::RequestId, 128))
+                    .col(timestamp_with_time_zone(AuditLog::CreatedAt).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: The log is read by actor, by entity and by time range
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_actor_id_created_at")
+                    .table(AuditLog::Table)
+                    .col(AuditLog::ActorId)
+                    .col(AuditLog::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_entity_created_at")
+                    .table(AuditLog::Table)
+                    .col(AuditLog::EntityType)
+                    .col(AuditLog::EntityId)
+                    .col(AuditLog::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_created_at")
+                    .table(AuditLog::Table)
+                    .col(AuditLog::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 3: Seed the audit permission for Admin and Risk
+        let now_str = chrono::Utc::now().to_rfc3339();
+        for (name, description, role_ids) in PERMISSIONS {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    r#"
+                    INSERT INTO permissions (permission_id, permission_name, permission_description, created_at)
+                    VALUES (gen_random_uuid(), '{}', '{}', '{}')
+                    ON CONFLICT (permission_name) DO NOTHING
+                    "#,
+                    name, description, now_str
+                ))
+                .await?;
+
+            let role_ids = role_ids
+                .iter()
+                .map(|id| format!("'{}'::uuid", id))
+                .collect::<Vec<_>>()
+                .join(", ");
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    r#"
+                    INSERT INTO role_permissions (role_id, permission_id)
+                    SELECT roles.role_id, permissions.permission_id
+                    FROM roles, permissions
+                    WHERE roles.role_id IN ({}) AND permissions.permission_name = '{}'
+                    ON CONFLICT DO NOTHING
+                    "#,
+                    role_ids, name
+                ))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (name, _, _) in PERMISSIONS {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    "DELETE FROM permissions WHERE permission_name = '{}'",
+                    name
+                ))
+                .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(AuditLog::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditLog {
+    Table,
+    Id,
+    ActorId,
+    Action,
+    EntityType,
+    EntityId,
+    Changes,
+    IpAddress,
+    UserAgent,
+    RequestId,
+    CreatedAt,
+}
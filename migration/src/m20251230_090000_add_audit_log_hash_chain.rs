@@ -0,0 +1,157 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Chain position and hashes; nullable until existing entries are linked
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AuditLog::Table)
+                    .add_column(big_integer_null(AuditLog::Sequence))
+                    .add_column(string_len_null(AuditLog::PrevHash, 64))
+                    .add_column(string_len_null(AuditLog::Hash, 64))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: Link existing entries in the order they were written. The payload must
+        // match AuditEntry::hash_payload: fields joined by newlines, unset ones empty and
+        // created_at in Unix microseconds
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                DO $$
+                DECLARE
+                    entry RECORD;
+                    next_sequence BIGINT := 0;
+                    previous_hash TEXT := repeat('0', 64);
+                BEGIN
+                    FOR entry IN SELECT * FROM audit_log ORDER BY created_at, id LOOP
+                        next_sequence := next_sequence + 1;
+                        UPDATE audit_log
+                        SET sequence = next_sequence,
+                            prev_hash = previous_hash,
+                            hash = encode(sha256(convert_to(concat_ws(E'\n',
+                                next_sequence::text,
+                                previous_hash,
+                                entry.id::text,
+                                coalesce(entry.actor_id::text, ''),
+                                entry.action,
+                                entry.entity_type,
+                                entry.entity_id::text,
+                                entry.changes,
+                                coalesce(entry.ip_address, ''),
+                                coalesce(entry.user_agent, ''),
+                                coalesce(entry.request_id, ''),
+                                (extract(epoch FROM entry.created_at) * 1000000)::bigint::text
+                            ), 'UTF8')), 'hex')
+                        WHERE id = entry.id
+                        RETURNING hash INTO previous_hash;
+                    END LOOP;
+                END $$;
+                "#,
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AuditLog::Table)
+                    .modify_column(big_integer(AuditLog::Sequence).not_null())
+                    .modify_column(string_len(AuditLog::PrevHash, 64).not_null())
+                    .modify_column(string_len(AuditLog::Hash, 64).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 3: One entry per position; verification walks the log by sequence
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_log_sequence")
+                    .table(AuditLog::Table)
+                    .col(AuditLog::Sequence)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 4: Create audit_checkpoints table
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditCheckpoints::Table)
+                    .if_not_exists()
+                    .col(uuid(AuditCheckpoints::Id).primary_key())
+                    .col(big_integer(AuditCheckpoints::Sequence).not_null())
+                    .col(string_len(AuditCheckpoints::HeadHash, 64).not_null())
+                    .col(string_len(AuditCheckpoints::Signature, 64).not_null())
+                    .col(timestamp_with_time_zone(AuditCheckpoints::CreatedAt).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_checkpoints_sequence")
+                    .table(AuditCheckpoints::Table)
+                    .col(AuditCheckpoints::Sequence)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditCheckpoints::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_audit_log_sequence")
+                    .table(AuditLog::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AuditLog::Table)
+                    .drop_column(AuditLog::Sequence)
+                    .drop_column(AuditLog::PrevHash)
+                    .drop_column(AuditLog::Hash)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditLog {
+    Table,
+    Sequence,
+    PrevHash,
+    Hash,
+}
+
+#[derive(DeriveIden)]
+enum AuditCheckpoints {
+    Table,
+    Id,
+    Sequence,
+    HeadHash,
+    Signature,
+    CreatedAt,
+}
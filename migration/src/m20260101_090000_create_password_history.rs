@@ -0,0 +1,131 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Step 1: Create password_history table (bcrypt hashes of earlier passwords)
+        manager
+            .create_table(
+                Table::create()
+                    .table(PasswordHistory::Table)
+                    .if_not_exists()
+                    .col(uuid(PasswordHistory::Id).primary_key())
+                    .col(uuid(PasswordHistory::UserId).not_null())
+                    .col(string(PasswordHistory::PasswordHash).not_null())
+                    .col(timestamp_with_time_zone(PasswordHistory::CreatedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_password_history_user_id")
+                            .from(PasswordHistory::Table, PasswordHistory::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_password_history_user_id_created_at")
+                    .table(PasswordHistory::Table)
+                    .col(PasswordHistory::UserId)
+                    .col(PasswordHistory::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Step 2: Current passwords start the history
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                INSERT INTO password_history (id, user_id, password_hash, created_at)
+                SELECT gen_random_uuid(), id, password_hash, updated_at FROM users
+                "#,
+            )
+            .await?;
+
+        // Step 3: Create password_reset_tokens table (only SHA-256 of the token is stored)
+        manager
+            .create_table(
+                Table::create()
+                    .table(PasswordResetTokens::Table)
+                    .if_not_exists()
+                    .col(uuid(PasswordResetTokens::Id).primary_key())
+                    .col(uuid(PasswordResetTokens::UserId).not_null())
+                    .col(
+                        string(PasswordResetTokens::TokenHash)
+                            .unique_key()
+                            .not_null(),
+                    )
+                    .col(uuid_null(PasswordResetTokens::RequestedBy))
+                    .col(timestamp_with_time_zone(PasswordResetTokens::ExpiresAt).not_null())
+                    .col(timestamp_with_time_zone_null(PasswordResetTokens::UsedAt))
+                    .col(timestamp_with_time_zone(PasswordResetTokens::CreatedAt).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_password_reset_tokens_user_id")
+                            .from(PasswordResetTokens::Table, PasswordResetTokens::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_password_reset_tokens_user_id")
+                    .table(PasswordResetTokens::Table)
+                    .col(PasswordResetTokens::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PasswordResetTokens::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PasswordHistory::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PasswordHistory {
+    Table,
+    Id,
+    UserId,
+    PasswordHash,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PasswordResetTokens {
+    Table,
+    Id,
+    UserId,
+    TokenHash,
+    RequestedBy,
+    ExpiresAt,
+    UsedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
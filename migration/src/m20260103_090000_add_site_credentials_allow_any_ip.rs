@@ -0,0 +1,44 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Keys without allowed IPs are refused from now on unless an admin opts them out
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SiteCredentials::Table)
+                    .add_column(
+                        boolean(SiteCredentials::AllowAnyIp)
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SiteCredentials::Table)
+                    .drop_column(SiteCredentials::AllowAnyIp)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SiteCredentials {
+    Table,
+    AllowAnyIp,
+}
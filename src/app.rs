@@ -1,7 +1,24 @@
-use crate::common::{dto::ApiResponse, AppState};
-use crate::domains::backoffice::UserApiDoc;
+use crate::common::{
+    dto::ApiResponse,
+    middleware::{audit_context, idempotency, jwt_auth, merchant_auth},
+    AppState,
+};
+use crate::domains::audit::{protected_audit_routes, AuditApiDoc};
+use crate::domains::backoffice::{
+    auth_routes, gateway_routes, protected_auth_routes, protected_merchant_routes,
+    protected_role_routes, protected_user_routes, AuthApiDoc, GatewayApiDoc, MerchantApiDoc,
+    RoleApiDoc, UserApiDoc,
+};
+use crate::domains::disputes::{protected_dispute_routes, DisputeApiDoc};
+use crate::domains::ledger::{protected_ledger_routes, LedgerApiDoc};
+use crate::domains::payments::{
+    gateway_payment_routes, protected_payment_routes, GatewayPaymentApiDoc, PaymentApiDoc,
+};
+use crate::domains::traders::{protected_trader_routes, TraderApiDoc};
+use crate::domains::webhooks::{protected_webhook_routes, WebhookApiDoc};
 use axum::{
     http::{HeaderName, Method, StatusCode},
+    middleware,
     response::{IntoResponse, Response},
     routing::get,
     Json, Router,
@@ -9,6 +26,7 @@ use axum::{
 use std::{str::FromStr, sync::Arc, time::Duration};
 use tower_http::{
     cors::{AllowOrigin, CorsLayer},
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
     trace::TraceLayer,
 };
 use utoipa::OpenApi;
@@ -19,13 +37,32 @@ pub fn create_app(state: Arc<AppState>) -> Router {
 
     Router::new()
         .route("/health", get(health_check))
-        .merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", UserApiDoc::openapi()))
+        .merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", api_doc()))
         .nest("/api/v1", api_routes(Arc::clone(&state)))
         .layer(cors)
+        .layer(PropagateRequestIdLayer::x_request_id())
         .layer(TraceLayer::new_for_http())
+        // Keeps an X-Request-Id sent by the client, otherwise assigns one
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
         .fallback(handler_404)
 }
 
+fn api_doc() -> utoipa::openapi::OpenApi {
+    let mut doc = UserApiDoc::openapi();
+    doc.merge(AuthApiDoc::openapi());
+    doc.merge(RoleApiDoc::openapi());
+    doc.merge(MerchantApiDoc::openapi());
+    doc.merge(GatewayApiDoc::openapi());
+    doc.merge(PaymentApiDoc::openapi());
+    doc.merge(GatewayPaymentApiDoc::openapi());
+    doc.merge(LedgerApiDoc::openapi());
+    doc.merge(TraderApiDoc::openapi());
+    doc.merge(DisputeApiDoc::openapi());
+    doc.merge(WebhookApiDoc::openapi());
+    doc.merge(AuditApiDoc::openapi());
+    doc
+}
+
 fn parse_cors_helper<T: FromStr>(parameters: Vec<String>) -> Vec<T> {
     parameters
         .into_iter()
@@ -61,8 +98,54 @@ fn configure_cors(state: &AppState) -> CorsLayer {
 fn api_routes(state: Arc<AppState>) -> Router {
     use axum::Extension;
 
+    // Routes reachable without a token (login flow, password reset links)
+    let public_routes =
+        Router::new()
+            .merge(auth_routes())
+            .route_layer(middleware::from_fn_with_state(
+                Arc::clone(&state),
+                audit_context,
+            ));
+
+    // Routes behind JWT; Claims are available to role checks and handlers
+    let protected_routes = Router::new()
+        .merge(protected_auth_routes())
+        .merge(protected_user_routes())
+        .merge(protected_role_routes())
+        .merge(protected_merchant_routes())
+        .merge(protected_payment_routes())
+        .merge(protected_ledger_routes())
+        .merge(protected_trader_routes())
+        .merge(protected_dispute_routes())
+        .merge(protected_webhook_routes())
+        .merge(protected_audit_routes())
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            audit_context,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            idempotency,
+        ))
+        .route_layer(middleware::from_fn_with_state(Arc::clone(&state), jwt_auth));
+
+    // Routes for merchant servers; MerchantContext is available to handlers
+    let merchant_routes = Router::new()
+        .merge(gateway_routes())
+        .merge(gateway_payment_routes())
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            idempotency,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            merchant_auth,
+        ));
+
     Router::new()
-        .merge(crate::domains::backoffice::protected_user_routes())
+        .merge(public_routes)
+        .merge(protected_routes)
+        .merge(merchant_routes)
         .layer(Extension(state))
 }
 
@@ -1,11 +1,17 @@
 pub mod app_state;
 pub mod bootstrap;
+pub mod client_ip;
 pub mod config;
 pub mod dto;
 pub mod error;
 pub mod hash_utils;
 pub mod jwt;
+pub mod mailer;
 pub mod middleware;
+pub mod money;
+pub mod request_signature;
+pub mod secret_cipher;
+pub mod storage;
 pub mod time_formater;
 
 pub use app_state::AppState;
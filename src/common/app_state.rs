@@ -2,46 +2,348 @@ use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 
 // Repositories
-use crate::domains::backoffice::domain::repository::UserRepository;
+use crate::domains::audit::domain::repository::{AuditCheckpointRepository, AuditLogRepository};
+use crate::domains::audit::infra::audit_checkpoint_repository::PostgresAuditCheckpointRepository;
+use crate::domains::audit::infra::audit_log_repository::PostgresAuditLogRepository;
+use crate::domains::backoffice::domain::repository::{
+    IdempotencyKeyRepository, LoginChallengeRepository, MerchantRepository,
+    PasswordResetTokenRepository, RefreshTokenRepository, SiteCredentialsRepository,
+    SiteRepository, TokenRevocationRepository, UserRepository,
+};
+use crate::domains::backoffice::infra::idempotency_key_repository::PostgresIdempotencyKeyRepository;
+use crate::domains::backoffice::infra::login_challenge_repository::PostgresLoginChallengeRepository;
+use crate::domains::backoffice::infra::merchant_repository::PostgresMerchantRepository;
+use crate::domains::backoffice::infra::password_reset_token_repository::PostgresPasswordResetTokenRepository;
+use crate::domains::backoffice::infra::refresh_token_repository::PostgresRefreshTokenRepository;
+use crate::domains::backoffice::infra::site_credentials_repository::PostgresSiteCredentialsRepository;
+use crate::domains::backoffice::infra::site_repository::PostgresSiteRepository;
+use crate::domains::backoffice::infra::token_revocation_repository::{
+    InMemoryTokenRevocationRepository, PostgresTokenRevocationRepository,
+};
 use crate::domains::backoffice::infra::user_repository::PostgresUserRepository;
 use crate::domains::backoffice::role::repository::RoleRepository;
 use crate::domains::backoffice::role::repository_impl::PostgresRoleRepository;
+use crate::domains::disputes::domain::repository::DisputeRepository;
+use crate::domains::disputes::infra::dispute_repository::PostgresDisputeRepository;
+use crate::domains::ledger::domain::repository::LedgerRepository;
+use crate::domains::ledger::infra::ledger_repository::PostgresLedgerRepository;
+use crate::domains::payments::domain::repository::{
+    MatchingRepository, PaymentEvidenceRepository, PaymentIntentRepository,
+};
+use crate::domains::payments::infra::matching_repository::PostgresMatchingRepository;
+use crate::domains::payments::infra::payment_evidence_repository::PostgresPaymentEvidenceRepository;
+use crate::domains::payments::infra::payment_intent_repository::PostgresPaymentIntentRepository;
+use crate::domains::traders::domain::repository::TraderRepository;
+use crate::domains::traders::infra::trader_repository::PostgresTraderRepository;
+use crate::domains::webhooks::domain::repository::WebhookRepository;
+use crate::domains::webhooks::infra::webhook_repository::PostgresWebhookRepository;
 
 // User Use Cases
+use crate::domains::backoffice::app::change_password_use_case::ChangePasswordUseCase;
+use crate::domains::backoffice::app::change_user_status_use_case::ChangeUserStatusUseCase;
 use crate::domains::backoffice::app::create_user_use_case::CreateUserUseCase;
 use crate::domains::backoffice::app::delete_user_use_case::DeleteUserUseCase;
 use crate::domains::backoffice::app::get_user_info_use_case::GetUserInfoUseCase;
+use crate::domains::backoffice::app::password_reset_use_case::PasswordResetUseCase;
 use crate::domains::backoffice::app::update_user_use_case::UpdateUserUseCase;
 
+// Role Use Cases
+use crate::domains::backoffice::app::create_role_use_case::CreateRoleUseCase;
+use crate::domains::backoffice::app::delete_role_use_case::DeleteRoleUseCase;
+use crate::domains::backoffice::app::get_role_use_case::GetRoleUseCase;
+use crate::domains::backoffice::app::update_role_use_case::UpdateRoleUseCase;
+
+// Merchant Use Cases
+use crate::domains::backoffice::app::change_merchant_status_use_case::ChangeMerchantStatusUseCase;
+use crate::domains::backoffice::app::create_merchant_use_case::CreateMerchantUseCase;
+use crate::domains::backoffice::app::get_merchant_use_case::GetMerchantUseCase;
+use crate::domains::backoffice::app::update_merchant_use_case::UpdateMerchantUseCase;
+
+// Site Use Cases
+use crate::domains::backoffice::app::change_site_status_use_case::ChangeSiteStatusUseCase;
+use crate::domains::backoffice::app::create_site_use_case::CreateSiteUseCase;
+use crate::domains::backoffice::app::get_site_use_case::GetSiteUseCase;
+use crate::domains::backoffice::app::update_site_use_case::UpdateSiteUseCase;
+
+// Site Credentials Use Cases
+use crate::domains::backoffice::app::get_site_credentials_use_case::GetSiteCredentialsUseCase;
+use crate::domains::backoffice::app::issue_site_credentials_use_case::IssueSiteCredentialsUseCase;
+use crate::domains::backoffice::app::revoke_site_credentials_use_case::RevokeSiteCredentialsUseCase;
+use crate::domains::backoffice::app::update_site_credentials_use_case::UpdateSiteCredentialsUseCase;
+
+// Merchant API Use Cases
+use crate::domains::backoffice::app::authenticate_merchant_use_case::AuthenticateMerchantUseCase;
+
+// Payment Use Cases
+use crate::domains::payments::app::assign_requisite_use_case::AssignRequisiteUseCase;
+use crate::domains::payments::app::confirm_payment_use_case::ConfirmPaymentUseCase;
+use crate::domains::payments::app::create_payment_intent_use_case::CreatePaymentIntentUseCase;
+use crate::domains::payments::app::expire_payment_intents_use_case::ExpirePaymentIntentsUseCase;
+use crate::domains::payments::app::get_payment_intent_use_case::GetPaymentIntentUseCase;
+use crate::domains::payments::app::payment_evidence_use_case::PaymentEvidenceUseCase;
+use crate::domains::payments::domain::matching::{
+    LeastLoadedStrategy, MatchingStrategy, RoundRobinStrategy, SuccessRateStrategy,
+};
+
+// Ledger Use Cases
+use crate::domains::ledger::app::get_ledger_use_case::GetLedgerUseCase;
+use crate::domains::ledger::app::record_payout_use_case::RecordPayoutUseCase;
+use crate::domains::ledger::app::record_settlement_use_case::RecordSettlementUseCase;
+use crate::domains::ledger::app::trader_deposit_use_case::TraderDepositUseCase;
+
+// Trader Use Cases
+use crate::domains::traders::app::create_requisite_use_case::CreateRequisiteUseCase;
+use crate::domains::traders::app::create_trader_use_case::CreateTraderUseCase;
+use crate::domains::traders::app::delete_trader_use_case::DeleteTraderUseCase;
+use crate::domains::traders::app::get_trader_use_case::GetTraderUseCase;
+use crate::domains::traders::app::update_requisite_use_case::UpdateRequisiteUseCase;
+use crate::domains::traders::app::update_trader_use_case::UpdateTraderUseCase;
+
+// Dispute Use Cases
+use crate::domains::disputes::app::dispute_attachment_use_case::DisputeAttachmentUseCase;
+use crate::domains::disputes::app::dispute_comment_use_case::DisputeCommentUseCase;
+use crate::domains::disputes::app::get_dispute_use_case::GetDisputeUseCase;
+use crate::domains::disputes::app::open_dispute_use_case::OpenDisputeUseCase;
+use crate::domains::disputes::app::resolve_dispute_use_case::ResolveDisputeUseCase;
+use crate::domains::disputes::app::update_dispute_use_case::UpdateDisputeUseCase;
+
+// Webhook Use Cases
+use crate::domains::webhooks::app::deliver_webhooks_use_case::DeliverWebhooksUseCase;
+use crate::domains::webhooks::app::get_webhook_delivery_use_case::GetWebhookDeliveryUseCase;
+use crate::domains::webhooks::domain::delivery::RetryPolicy;
+use crate::domains::webhooks::infra::http_webhook_sender::HttpWebhookSender;
+
+// Audit Use Cases
+use crate::domains::audit::app::audit_checkpoint_use_case::AuditCheckpointUseCase;
+use crate::domains::audit::app::get_audit_log_use_case::GetAuditLogUseCase;
+use crate::domains::audit::app::verify_audit_chain_use_case::VerifyAuditChainUseCase;
+
+// Auth Use Cases
+use crate::domains::backoffice::app::login_use_case::LoginUseCase;
+use crate::domains::backoffice::app::logout_use_case::LogoutUseCase;
+use crate::domains::backoffice::app::refresh_token_use_case::RefreshTokenUseCase;
+use crate::domains::backoffice::app::revoke_user_sessions_use_case::RevokeUserSessionsUseCase;
+use crate::domains::backoffice::app::token_issuer::TokenIssuer;
+use crate::domains::backoffice::app::verify_login_use_case::VerifyLoginUseCase;
+
 // Services
+use crate::common::client_ip::ClientIpResolver;
+use crate::common::config::{MatchingStrategyKind, TokenRevocationStore};
 use crate::common::jwt::JwtService;
+use crate::common::mailer::Mailer;
+use crate::common::secret_cipher::SecretCipher;
+use crate::common::storage::{FileStorage, LocalFileStorage};
 use crate::common::Config;
+use crate::domains::backoffice::domain::password_policy::PasswordPolicy;
 
 pub struct AppState {
     pub config: Config,
     pub user_repository: Arc<dyn UserRepository>,
     pub role_repository: Arc<dyn RoleRepository>,
+    pub login_challenge_repository: Arc<dyn LoginChallengeRepository>,
+    pub token_revocation_repository: Arc<dyn TokenRevocationRepository>,
+    pub refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+    pub password_reset_token_repository: Arc<dyn PasswordResetTokenRepository>,
+    pub idempotency_key_repository: Arc<dyn IdempotencyKeyRepository>,
+    pub merchant_repository: Arc<dyn MerchantRepository>,
+    pub site_repository: Arc<dyn SiteRepository>,
+    pub site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+    pub payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+    pub payment_evidence_repository: Arc<dyn PaymentEvidenceRepository>,
+    pub matching_repository: Arc<dyn MatchingRepository>,
+    pub ledger_repository: Arc<dyn LedgerRepository>,
+    pub trader_repository: Arc<dyn TraderRepository>,
+    pub dispute_repository: Arc<dyn DisputeRepository>,
+    pub webhook_repository: Arc<dyn WebhookRepository>,
+    pub audit_log_repository: Arc<dyn AuditLogRepository>,
+    pub audit_checkpoint_repository: Arc<dyn AuditCheckpointRepository>,
+    pub password_policy: Arc<PasswordPolicy>,
     pub jwt_service: Arc<JwtService>,
+    pub secret_cipher: Arc<SecretCipher>,
+    pub client_ip_resolver: Arc<ClientIpResolver>,
+    pub mailer: Arc<dyn Mailer>,
+    pub file_storage: Arc<dyn FileStorage>,
     pub user_get_use_case: Arc<GetUserInfoUseCase>,
     pub user_create_use_case: Arc<CreateUserUseCase>,
     pub user_update_use_case: Arc<UpdateUserUseCase>,
     pub user_delete_use_case: Arc<DeleteUserUseCase>,
+    pub user_change_status_use_case: Arc<ChangeUserStatusUseCase>,
+    pub user_change_password_use_case: Arc<ChangePasswordUseCase>,
+    pub user_password_reset_use_case: Arc<PasswordResetUseCase>,
+    pub role_get_use_case: Arc<GetRoleUseCase>,
+    pub role_create_use_case: Arc<CreateRoleUseCase>,
+    pub role_update_use_case: Arc<UpdateRoleUseCase>,
+    pub role_delete_use_case: Arc<DeleteRoleUseCase>,
+    pub merchant_get_use_case: Arc<GetMerchantUseCase>,
+    pub merchant_create_use_case: Arc<CreateMerchantUseCase>,
+    pub merchant_update_use_case: Arc<UpdateMerchantUseCase>,
+    pub merchant_change_status_use_case: Arc<ChangeMerchantStatusUseCase>,
+    pub site_get_use_case: Arc<GetSiteUseCase>,
+    pub site_create_use_case: Arc<CreateSiteUseCase>,
+    pub site_update_use_case: Arc<UpdateSiteUseCase>,
+    pub site_change_status_use_case: Arc<ChangeSiteStatusUseCase>,
+    pub site_credentials_get_use_case: Arc<GetSiteCredentialsUseCase>,
+    pub site_credentials_issue_use_case: Arc<IssueSiteCredentialsUseCase>,
+    pub site_credentials_revoke_use_case: Arc<RevokeSiteCredentialsUseCase>,
+    pub site_credentials_update_use_case: Arc<UpdateSiteCredentialsUseCase>,
+    pub merchant_authenticate_use_case: Arc<AuthenticateMerchantUseCase>,
+    pub payment_intent_create_use_case: Arc<CreatePaymentIntentUseCase>,
+    pub payment_intent_get_use_case: Arc<GetPaymentIntentUseCase>,
+    pub payment_intent_expire_use_case: Arc<ExpirePaymentIntentsUseCase>,
+    pub payment_intent_assign_use_case: Arc<AssignRequisiteUseCase>,
+    pub payment_confirm_use_case: Arc<ConfirmPaymentUseCase>,
+    pub payment_evidence_use_case: Arc<PaymentEvidenceUseCase>,
+    pub ledger_get_use_case: Arc<GetLedgerUseCase>,
+    pub ledger_settlement_use_case: Arc<RecordSettlementUseCase>,
+    pub ledger_payout_use_case: Arc<RecordPayoutUseCase>,
+    pub ledger_trader_deposit_use_case: Arc<TraderDepositUseCase>,
+    pub trader_get_use_case: Arc<GetTraderUseCase>,
+    pub trader_create_use_case: Arc<CreateTraderUseCase>,
+    pub trader_update_use_case: Arc<UpdateTraderUseCase>,
+    pub trader_delete_use_case: Arc<DeleteTraderUseCase>,
+    pub requisite_create_use_case: Arc<CreateRequisiteUseCase>,
+    pub requisite_update_use_case: Arc<UpdateRequisiteUseCase>,
+    pub dispute_get_use_case: Arc<GetDisputeUseCase>,
+    pub dispute_open_use_case: Arc<OpenDisputeUseCase>,
+    pub dispute_update_use_case: Arc<UpdateDisputeUseCase>,
+    pub dispute_resolve_use_case: Arc<ResolveDisputeUseCase>,
+    pub dispute_comment_use_case: Arc<DisputeCommentUseCase>,
+    pub dispute_attachment_use_case: Arc<DisputeAttachmentUseCase>,
+    pub webhook_deliver_use_case: Arc<DeliverWebhooksUseCase>,
+    pub webhook_get_use_case: Arc<GetWebhookDeliveryUseCase>,
+    pub audit_get_use_case: Arc<GetAuditLogUseCase>,
+    pub audit_verify_use_case: Arc<VerifyAuditChainUseCase>,
+    pub audit_checkpoint_use_case: Arc<AuditCheckpointUseCase>,
+    pub login_use_case: Arc<LoginUseCase>,
+    pub verify_login_use_case: Arc<VerifyLoginUseCase>,
+    pub refresh_token_use_case: Arc<RefreshTokenUseCase>,
+    pub logout_use_case: Arc<LogoutUseCase>,
+    pub revoke_user_sessions_use_case: Arc<RevokeUserSessionsUseCase>,
+}
+
+/// Storage backends used to build `AppState`.
+/// Production uses Postgres; tests can swap single repositories for in-memory ones.
+pub struct Repositories {
+    pub user_repository: Arc<dyn UserRepository>,
+    pub role_repository: Arc<dyn RoleRepository>,
+    pub login_challenge_repository: Arc<dyn LoginChallengeRepository>,
+    pub token_revocation_repository: Arc<dyn TokenRevocationRepository>,
+    pub refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+    pub password_reset_token_repository: Arc<dyn PasswordResetTokenRepository>,
+    pub idempotency_key_repository: Arc<dyn IdempotencyKeyRepository>,
+    pub merchant_repository: Arc<dyn MerchantRepository>,
+    pub site_repository: Arc<dyn SiteRepository>,
+    pub site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+    pub payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+    pub payment_evidence_repository: Arc<dyn PaymentEvidenceRepository>,
+    pub matching_repository: Arc<dyn MatchingRepository>,
+    pub ledger_repository: Arc<dyn LedgerRepository>,
+    pub trader_repository: Arc<dyn TraderRepository>,
+    pub dispute_repository: Arc<dyn DisputeRepository>,
+    pub webhook_repository: Arc<dyn WebhookRepository>,
+    pub audit_log_repository: Arc<dyn AuditLogRepository>,
+    pub audit_checkpoint_repository: Arc<dyn AuditCheckpointRepository>,
+}
+
+impl Repositories {
+    pub fn postgres(db: DatabaseConnection) -> Self {
+        Self {
+            user_repository: Arc::new(PostgresUserRepository::new(db.clone())),
+            role_repository: Arc::new(PostgresRoleRepository::new(db.clone())),
+            login_challenge_repository: Arc::new(PostgresLoginChallengeRepository::new(db.clone())),
+            token_revocation_repository: Arc::new(PostgresTokenRevocationRepository::new(
+                db.clone(),
+            )),
+            refresh_token_repository: Arc::new(PostgresRefreshTokenRepository::new(db.clone())),
+            password_reset_token_repository: Arc::new(PostgresPasswordResetTokenRepository::new(
+                db.clone(),
+            )),
+            idempotency_key_repository: Arc::new(PostgresIdempotencyKeyRepository::new(db.clone())),
+            merchant_repository: Arc::new(PostgresMerchantRepository::new(db.clone())),
+            site_repository: Arc::new(PostgresSiteRepository::new(db.clone())),
+            site_credentials_repository: Arc::new(PostgresSiteCredentialsRepository::new(
+                db.clone(),
+            )),
+            payment_intent_repository: Arc::new(PostgresPaymentIntentRepository::new(db.clone())),
+            payment_evidence_repository: Arc::new(PostgresPaymentEvidenceRepository::new(
+                db.clone(),
+            )),
+            matching_repository: Arc::new(PostgresMatchingRepository::new(db.clone())),
+            ledger_repository: Arc::new(PostgresLedgerRepository::new(db.clone())),
+            trader_repository: Arc::new(PostgresTraderRepository::new(db.clone())),
+            dispute_repository: Arc::new(PostgresDisputeRepository::new(db.clone())),
+            webhook_repository: Arc::new(PostgresWebhookRepository::new(db.clone())),
+            audit_log_repository: Arc::new(PostgresAuditLogRepository::new(db.clone())),
+            audit_checkpoint_repository: Arc::new(PostgresAuditCheckpointRepository::new(db)),
+        }
+    }
 }
 
 impl AppState {
-    pub fn new(db: DatabaseConnection, config: Config) -> Self {
-        let user_repository: Arc<dyn UserRepository> =
-            Arc::new(PostgresUserRepository::new(db.clone()));
+    pub fn new(
+        db: DatabaseConnection,
+        config: Config,
+        mailer: Arc<dyn Mailer>,
+        password_policy: PasswordPolicy,
+    ) -> Self {
+        let mut repositories = Repositories::postgres(db);
 
-        let role_repository: Arc<dyn RoleRepository> = Arc::new(PostgresRoleRepository::new(db));
+        if config.auth.token_revocation_store == TokenRevocationStore::Memory {
+            repositories.token_revocation_repository =
+                Arc::new(InMemoryTokenRevocationRepository::new());
+        }
 
-        let jwt_service = Arc::new(JwtService::new(&config.jwt_secret_key));
+        Self::with_repositories(config, repositories, mailer, password_policy)
+    }
+
+    pub fn with_repositories(
+        config: Config,
+        repositories: Repositories,
+        mailer: Arc<dyn Mailer>,
+        password_policy: PasswordPolicy,
+    ) -> Self {
+        let Repositories {
+            user_repository,
+            role_repository,
+            login_challenge_repository,
+            token_revocation_repository,
+            refresh_token_repository,
+            password_reset_token_repository,
+            idempotency_key_repository,
+            merchant_repository,
+            site_repository,
+            site_credentials_repository,
+            payment_intent_repository,
+            payment_evidence_repository,
+            matching_repository,
+            ledger_repository,
+            trader_repository,
+            dispute_repository,
+            webhook_repository,
+            audit_log_repository,
+            audit_checkpoint_repository,
+        } = repositories;
+
+        let jwt_service = Arc::new(JwtService::with_access_token_ttl(
+            &config.jwt_secret_key,
+            chrono::Duration::minutes(config.auth.access_token_ttl_minutes),
+        ));
+
+        let token_issuer = Arc::new(TokenIssuer::new(
+            Arc::clone(&refresh_token_repository),
+            Arc::clone(&role_repository),
+            Arc::clone(&jwt_service),
+            chrono::Duration::days(config.auth.refresh_token_ttl_days),
+        ));
+
+        let password_policy = Arc::new(password_policy);
 
         let user_get_use_case = Arc::new(GetUserInfoUseCase::new(Arc::clone(&user_repository)));
 
         let user_create_use_case = Arc::new(CreateUserUseCase::new(
             Arc::clone(&user_repository),
             Arc::clone(&role_repository),
+            Arc::clone(&password_policy),
         ));
 
         let user_update_use_case = Arc::new(UpdateUserUseCase::new(
@@ -51,15 +353,334 @@ impl AppState {
 
         let user_delete_use_case = Arc::new(DeleteUserUseCase::new(Arc::clone(&user_repository)));
 
+        let role_get_use_case = Arc::new(GetRoleUseCase::new(Arc::clone(&role_repository)));
+        let role_create_use_case = Arc::new(CreateRoleUseCase::new(Arc::clone(&role_repository)));
+        let role_update_use_case = Arc::new(UpdateRoleUseCase::new(Arc::clone(&role_repository)));
+        let role_delete_use_case = Arc::new(DeleteRoleUseCase::new(Arc::clone(&role_repository)));
+
+        let merchant_get_use_case =
+            Arc::new(GetMerchantUseCase::new(Arc::clone(&merchant_repository)));
+        let merchant_create_use_case =
+            Arc::new(CreateMerchantUseCase::new(Arc::clone(&merchant_repository)));
+        let merchant_update_use_case =
+            Arc::new(UpdateMerchantUseCase::new(Arc::clone(&merchant_repository)));
+        let merchant_change_status_use_case = Arc::new(ChangeMerchantStatusUseCase::new(
+            Arc::clone(&merchant_repository),
+        ));
+
+        // Plain http URLs are fine for local and staging sites
+        let require_https = config.environment.is_production();
+
+        let site_get_use_case = Arc::new(GetSiteUseCase::new(
+            Arc::clone(&merchant_repository),
+            Arc::clone(&site_repository),
+        ));
+        let site_create_use_case = Arc::new(CreateSiteUseCase::new(
+            Arc::clone(&merchant_repository),
+            Arc::clone(&site_repository),
+            require_https,
+        ));
+        let site_update_use_case = Arc::new(UpdateSiteUseCase::new(
+            Arc::clone(&site_repository),
+            require_https,
+        ));
+        let site_change_status_use_case =
+            Arc::new(ChangeSiteStatusUseCase::new(Arc::clone(&site_repository)));
+
+        let secret_cipher = Arc::new(SecretCipher::new(
+            &config.merchant_api.secret_encryption_key,
+        ));
+
+        let site_credentials_get_use_case = Arc::new(GetSiteCredentialsUseCase::new(
+            Arc::clone(&site_repository),
+            Arc::clone(&site_credentials_repository),
+        ));
+        let site_credentials_issue_use_case = Arc::new(IssueSiteCredentialsUseCase::new(
+            Arc::clone(&site_repository),
+            Arc::clone(&site_credentials_repository),
+            Arc::clone(&secret_cipher),
+            chrono::Duration::minutes(config.merchant_api.key_rotation_overlap_minutes),
+        ));
+        let site_credentials_revoke_use_case = Arc::new(RevokeSiteCredentialsUseCase::new(
+            Arc::clone(&site_repository),
+            Arc::clone(&site_credentials_repository),
+        ));
+        let site_credentials_update_use_case = Arc::new(UpdateSiteCredentialsUseCase::new(
+            Arc::clone(&site_repository),
+            Arc::clone(&site_credentials_repository),
+        ));
+
+        let client_ip_resolver = Arc::new(ClientIpResolver::new(
+            config.network.parse_trusted_proxies(),
+        ));
+
+        let merchant_authenticate_use_case = Arc::new(AuthenticateMerchantUseCase::new(
+            Arc::clone(&merchant_repository),
+            Arc::clone(&site_repository),
+            Arc::clone(&site_credentials_repository),
+            Arc::clone(&secret_cipher),
+            chrono::Duration::seconds(config.merchant_api.signature_max_skew_seconds),
+        ));
+
+        let webhook_request_timeout =
+            std::time::Duration::from_secs(config.webhooks.request_timeout_seconds);
+        let webhook_deliver_use_case = Arc::new(DeliverWebhooksUseCase::new(
+            Arc::clone(&webhook_repository),
+            Arc::clone(&site_repository),
+            Arc::clone(&site_credentials_repository),
+            Arc::clone(&secret_cipher),
+            Arc::new(HttpWebhookSender::new(webhook_request_timeout)),
+            RetryPolicy {
+                max_attempts: config.webhooks.max_attempts,
+                initial_backoff: chrono::Duration::seconds(config.webhooks.initial_backoff_seconds),
+                max_backoff: chrono::Duration::seconds(config.webhooks.max_backoff_seconds),
+            },
+            config.webhooks.batch_size,
+            // A claimed delivery stays hidden until its request has surely timed out
+            chrono::Duration::seconds(config.webhooks.request_timeout_seconds as i64 + 60),
+        ));
+        let webhook_get_use_case = Arc::new(GetWebhookDeliveryUseCase::new(Arc::clone(
+            &webhook_repository,
+        )));
+
+        let audit_get_use_case =
+            Arc::new(GetAuditLogUseCase::new(Arc::clone(&audit_log_repository)));
+        let audit_verify_use_case = Arc::new(VerifyAuditChainUseCase::new(
+            Arc::clone(&audit_log_repository),
+            Arc::clone(&audit_checkpoint_repository),
+            &config.audit.checkpoint_signing_key,
+        ));
+        let audit_checkpoint_use_case = Arc::new(AuditCheckpointUseCase::new(
+            Arc::clone(&audit_log_repository),
+            Arc::clone(&audit_checkpoint_repository),
+            &config.audit.checkpoint_signing_key,
+        ));
+
+        let matching_strategy: Arc<dyn MatchingStrategy> = match config.payments.matching_strategy {
+            MatchingStrategyKind::RoundRobin => Arc::new(RoundRobinStrategy),
+            MatchingStrategyKind::LeastLoaded => Arc::new(LeastLoadedStrategy),
+            MatchingStrategyKind::SuccessRate => Arc::new(SuccessRateStrategy),
+        };
+        let payment_intent_assign_use_case = Arc::new(AssignRequisiteUseCase::new(
+            Arc::clone(&payment_intent_repository),
+            Arc::clone(&matching_repository),
+            Arc::clone(&trader_repository),
+            Arc::clone(&secret_cipher),
+            matching_strategy,
+        ));
+        let payment_intent_create_use_case = Arc::new(CreatePaymentIntentUseCase::new(
+            Arc::clone(&payment_intent_repository),
+            Arc::clone(&payment_intent_assign_use_case),
+            chrono::Duration::minutes(config.payments.default_ttl_minutes),
+            chrono::Duration::minutes(config.payments.max_ttl_minutes),
+        ));
+        let payment_intent_get_use_case = Arc::new(GetPaymentIntentUseCase::new(Arc::clone(
+            &payment_intent_repository,
+        )));
+        let payment_intent_expire_use_case = Arc::new(ExpirePaymentIntentsUseCase::new(
+            Arc::clone(&payment_intent_repository),
+        ));
+
+        let ledger_get_use_case = Arc::new(GetLedgerUseCase::new(Arc::clone(&ledger_repository)));
+        let ledger_settlement_use_case = Arc::new(RecordSettlementUseCase::new(
+            Arc::clone(&ledger_repository),
+            config.payments.settlement_fee_bps,
+        ));
+
+        let file_storage: Arc<dyn FileStorage> =
+            Arc::new(LocalFileStorage::new(&config.storage.local_root));
+        let payment_confirm_use_case = Arc::new(ConfirmPaymentUseCase::new(
+            Arc::clone(&payment_intent_repository),
+            Arc::clone(&ledger_settlement_use_case),
+            chrono::Duration::minutes(config.payments.confirmation_timeout_minutes),
+            chrono::Duration::hours(config.payments.dispute_window_hours),
+        ));
+        let payment_evidence_use_case = Arc::new(PaymentEvidenceUseCase::new(
+            Arc::clone(&payment_intent_repository),
+            Arc::clone(&payment_evidence_repository),
+            Arc::clone(&file_storage),
+            config.payments.evidence_max_bytes,
+        ));
+        let ledger_payout_use_case = Arc::new(RecordPayoutUseCase::new(
+            Arc::clone(&ledger_repository),
+            Arc::clone(&merchant_repository),
+        ));
+
+        let ledger_trader_deposit_use_case = Arc::new(TraderDepositUseCase::new(
+            Arc::clone(&ledger_repository),
+            Arc::clone(&trader_repository),
+        ));
+
+        let trader_get_use_case = Arc::new(GetTraderUseCase::new(Arc::clone(&trader_repository)));
+        let trader_create_use_case =
+            Arc::new(CreateTraderUseCase::new(Arc::clone(&trader_repository)));
+        let trader_update_use_case =
+            Arc::new(UpdateTraderUseCase::new(Arc::clone(&trader_repository)));
+        let trader_delete_use_case = Arc::new(DeleteTraderUseCase::new(
+            Arc::clone(&trader_repository),
+            Arc::clone(&ledger_repository),
+        ));
+        let requisite_create_use_case = Arc::new(CreateRequisiteUseCase::new(
+            Arc::clone(&trader_repository),
+            Arc::clone(&secret_cipher),
+        ));
+        let requisite_update_use_case =
+            Arc::new(UpdateRequisiteUseCase::new(Arc::clone(&trader_repository)));
+
+        let dispute_get_use_case =
+            Arc::new(GetDisputeUseCase::new(Arc::clone(&dispute_repository)));
+        let dispute_open_use_case = Arc::new(OpenDisputeUseCase::new(
+            Arc::clone(&dispute_repository),
+            Arc::clone(&payment_intent_repository),
+        ));
+        let dispute_update_use_case = Arc::new(UpdateDisputeUseCase::new(
+            Arc::clone(&dispute_repository),
+            Arc::clone(&user_repository),
+            Arc::clone(&role_repository),
+        ));
+        let dispute_resolve_use_case = Arc::new(ResolveDisputeUseCase::new(
+            Arc::clone(&dispute_repository),
+            Arc::clone(&ledger_repository),
+        ));
+        let dispute_comment_use_case =
+            Arc::new(DisputeCommentUseCase::new(Arc::clone(&dispute_repository)));
+        let dispute_attachment_use_case = Arc::new(DisputeAttachmentUseCase::new(
+            Arc::clone(&dispute_repository),
+            Arc::clone(&file_storage),
+            config.payments.evidence_max_bytes,
+        ));
+
+        let login_use_case = Arc::new(LoginUseCase::new(
+            Arc::clone(&user_repository),
+            Arc::clone(&login_challenge_repository),
+            Arc::clone(&mailer),
+            chrono::Duration::seconds(config.auth.login_code_ttl_seconds),
+        ));
+
+        let verify_login_use_case = Arc::new(VerifyLoginUseCase::new(
+            Arc::clone(&user_repository),
+            Arc::clone(&login_challenge_repository),
+            Arc::clone(&token_issuer),
+            config.auth.login_code_max_attempts,
+        ));
+
+        let refresh_token_use_case = Arc::new(RefreshTokenUseCase::new(
+            Arc::clone(&user_repository),
+            Arc::clone(&refresh_token_repository),
+            Arc::clone(&token_issuer),
+        ));
+
+        let logout_use_case = Arc::new(LogoutUseCase::new(
+            Arc::clone(&token_revocation_repository),
+            Arc::clone(&refresh_token_repository),
+        ));
+
+        let revoke_user_sessions_use_case = Arc::new(RevokeUserSessionsUseCase::new(
+            Arc::clone(&user_repository),
+            Arc::clone(&token_revocation_repository),
+            Arc::clone(&refresh_token_repository),
+        ));
+        let user_change_status_use_case = Arc::new(ChangeUserStatusUseCase::new(
+            Arc::clone(&user_repository),
+            Arc::clone(&revoke_user_sessions_use_case),
+        ));
+        let user_change_password_use_case = Arc::new(ChangePasswordUseCase::new(
+            Arc::clone(&user_repository),
+            Arc::clone(&password_policy),
+        ));
+        let user_password_reset_use_case = Arc::new(PasswordResetUseCase::new(
+            Arc::clone(&user_repository),
+            Arc::clone(&password_reset_token_repository),
+            Arc::clone(&user_change_password_use_case),
+            Arc::clone(&revoke_user_sessions_use_case),
+            Arc::clone(&mailer),
+            chrono::Duration::minutes(config.password.reset_ttl_minutes),
+            config.password.reset_url.clone(),
+        ));
+
         Self {
             config,
             user_repository,
             role_repository,
+            login_challenge_repository,
+            token_revocation_repository,
+            refresh_token_repository,
+            password_reset_token_repository,
+            idempotency_key_repository,
+            merchant_repository,
+            site_repository,
+            site_credentials_repository,
+            payment_intent_repository,
+            payment_evidence_repository,
+            matching_repository,
+            ledger_repository,
+            trader_repository,
+            dispute_repository,
+            webhook_repository,
+            audit_log_repository,
+            audit_checkpoint_repository,
+            password_policy,
             jwt_service,
+            secret_cipher,
+            client_ip_resolver,
+            mailer,
+            file_storage,
             user_get_use_case,
             user_create_use_case,
             user_update_use_case,
             user_delete_use_case,
+            user_change_status_use_case,
+            user_change_password_use_case,
+            user_password_reset_use_case,
+            role_get_use_case,
+            role_create_use_case,
+            role_update_use_case,
+            role_delete_use_case,
+            merchant_get_use_case,
+            merchant_create_use_case,
+            merchant_update_use_case,
+            merchant_change_status_use_case,
+            site_get_use_case,
+            site_create_use_case,
+            site_update_use_case,
+            site_change_status_use_case,
+            site_credentials_get_use_case,
+            site_credentials_issue_use_case,
+            site_credentials_revoke_use_case,
+            site_credentials_update_use_case,
+            merchant_authenticate_use_case,
+            payment_intent_create_use_case,
+            payment_intent_get_use_case,
+            payment_intent_expire_use_case,
+            payment_intent_assign_use_case,
+            payment_confirm_use_case,
+            payment_evidence_use_case,
+            ledger_get_use_case,
+            ledger_settlement_use_case,
+            ledger_payout_use_case,
+            ledger_trader_deposit_use_case,
+            trader_get_use_case,
+            trader_create_use_case,
+            trader_update_use_case,
+            trader_delete_use_case,
+            requisite_create_use_case,
+            requisite_update_use_case,
+            dispute_get_use_case,
+            dispute_open_use_case,
+            dispute_update_use_case,
+            dispute_resolve_use_case,
+            dispute_comment_use_case,
+            dispute_attachment_use_case,
+            webhook_deliver_use_case,
+            webhook_get_use_case,
+            audit_get_use_case,
+            audit_verify_use_case,
+            audit_checkpoint_use_case,
+            login_use_case,
+            verify_login_use_case,
+            refresh_token_use_case,
+            logout_use_case,
+            revoke_user_sessions_use_case,
         }
     }
 }
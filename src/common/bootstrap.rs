@@ -1,4 +1,5 @@
-use crate::common::{AppState, Config};
+use crate::common::{mailer::mailer_from_config, AppState, Config};
+use crate::domains::backoffice::domain::password_policy::PasswordPolicy;
 use color_eyre::eyre::Result;
 use migration::MigratorTrait;
 use sea_orm::DatabaseConnection;
@@ -33,14 +34,86 @@ pub async fn initialize_app() -> Result<Arc<AppState>> {
     migration::Migrator::up(&db, None).await?;
     tracing::info!("Database migrations applied successfully");
 
+    // Login codes and reset links must not end up in production logs
+    let mailer = mailer_from_config(&config.mail, &config.environment)?;
+
+    // Starting without a configured denylist would let breached passwords through
+    let password_policy = PasswordPolicy::from_config(&config.password)?;
+
     tracing::info!("Creating application state...");
-    let state = Arc::new(AppState::new(db, config));
+    let state = Arc::new(AppState::new(db, config, mailer, password_policy));
     tracing::info!("Application state created successfully");
 
     tracing::info!("Application initialization complete");
     Ok(state)
 }
 
+/// Starts the periodic jobs that keep state consistent without a request
+pub fn spawn_background_jobs(state: Arc<AppState>) {
+    let interval =
+        std::time::Duration::from_secs(state.config.payments.expiry_sweep_interval_seconds);
+
+    let webhook_state = Arc::clone(&state);
+    let audit_state = Arc::clone(&state);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(err) = state.payment_intent_expire_use_case.execute().await {
+                tracing::error!("Payment expiry sweep failed: {}", err);
+            }
+            if let Err(err) = state.payment_intent_assign_use_case.assign_waiting().await {
+                tracing::error!("Payment matching sweep failed: {}", err);
+            }
+            if let Err(err) = state.payment_confirm_use_case.escalate_overdue().await {
+                tracing::error!("Payment escalation sweep failed: {}", err);
+            }
+            if let Err(err) = state
+                .idempotency_key_repository
+                .delete_expired(chrono::Utc::now())
+                .await
+            {
+                tracing::error!("Idempotency key sweep failed: {}", err);
+            }
+            if let Err(err) = state
+                .token_revocation_repository
+                .delete_expired(chrono::Utc::now())
+                .await
+            {
+                tracing::error!("Revoked token sweep failed: {}", err);
+            }
+        }
+    });
+
+    // Webhooks run on their own loop so a slow merchant does not hold up the sweeps
+    let webhook_interval =
+        std::time::Duration::from_secs(webhook_state.config.webhooks.dispatch_interval_seconds);
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(webhook_interval);
+        loop {
+            ticker.tick().await;
+            if let Err(err) = webhook_state.webhook_deliver_use_case.dispatch_due().await {
+                tracing::error!("Webhook dispatch failed: {}", err);
+            }
+        }
+    });
+
+    // Signed checkpoints let the audit log be checked against copies kept elsewhere
+    let checkpoint_interval =
+        std::time::Duration::from_secs(audit_state.config.audit.checkpoint_interval_minutes * 60);
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(checkpoint_interval);
+        loop {
+            ticker.tick().await;
+            if let Err(err) = audit_state.audit_checkpoint_use_case.create().await {
+                tracing::error!("Audit checkpoint failed: {}", err);
+            }
+        }
+    });
+}
+
 async fn verify_database_connection(db: &DatabaseConnection) -> Result<()> {
     use sea_orm::ConnectionTrait;
 
@@ -0,0 +1,99 @@
+use axum::http::HeaderMap;
+use ipnet::IpNet;
+use std::net::IpAddr;
+
+pub const FORWARDED_FOR_HEADER: &str = "X-Forwarded-For";
+
+/// Finds the address of the real caller when the service runs behind reverse proxies
+pub struct ClientIpResolver {
+    trusted_proxies: Vec<IpNet>,
+}
+
+impl ClientIpResolver {
+    pub fn new(trusted_proxies: Vec<IpNet>) -> Self {
+        Self { trusted_proxies }
+    }
+
+    fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|proxy| proxy.contains(&ip))
+    }
+
+    /// `peer` is the address of the TCP connection. X-Forwarded-For is only read when
+    /// the peer is a trusted proxy, walking it right to left past further trusted hops,
+    /// so clients cannot spoof their address by sending the header themselves.
+    pub fn resolve(&self, peer: Option<IpAddr>, headers: &HeaderMap) -> Option<IpAddr> {
+        let mut client = peer?.to_canonical();
+        if !self.is_trusted(client) {
+            return Some(client);
+        }
+
+        let hops: Vec<&str> = headers
+            .get_all(FORWARDED_FOR_HEADER)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .collect();
+
+        for hop in hops.into_iter().rev() {
+            let Ok(ip) = hop.parse::<IpAddr>() else {
+                break;
+            };
+            client = ip.to_canonical();
+            if !self.is_trusted(client) {
+                break;
+            }
+        }
+
+        Some(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn resolver() -> ClientIpResolver {
+        ClientIpResolver::new(vec![
+            "10.0.0.0/8".parse().unwrap(),
+            "127.0.0.1/32".parse().unwrap(),
+        ])
+    }
+
+    fn forwarded(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(FORWARDED_FOR_HEADER, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_untrusted_peer_ignores_forwarded_header() {
+        let peer = "203.0.113.7".parse().ok();
+
+        assert_eq!(resolver().resolve(peer, &forwarded("198.51.100.1")), peer);
+    }
+
+    #[test]
+    fn test_trusted_peer_uses_last_untrusted_hop() {
+        let peer = "127.0.0.1".parse().ok();
+        let headers = forwarded("1.1.1.1, 198.51.100.1, 10.0.0.5");
+
+        assert_eq!(
+            resolver().resolve(peer, &headers),
+            "198.51.100.1".parse().ok()
+        );
+        assert_eq!(resolver().resolve(peer, &HeaderMap::new()), peer);
+        assert_eq!(resolver().resolve(None, &headers), None);
+    }
+
+    #[test]
+    fn test_ipv4_mapped_addresses_are_canonical() {
+        let peer = "::ffff:203.0.113.7".parse().ok();
+
+        assert_eq!(
+            resolver().resolve(peer, &HeaderMap::new()),
+            "203.0.113.7".parse().ok()
+        );
+    }
+}
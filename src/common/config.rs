@@ -1,5 +1,6 @@
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{ensure, Result};
 use figment::{providers::Env, Figment};
+use ipnet::IpNet;
 use sea_orm::{ConnectOptions, Database, DatabaseConnection};
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +19,34 @@ pub struct Config {
     pub jwt_secret_key: String,
 
     pub cors: CorsConfig,
+
+    #[serde(default)]
+    pub auth: AuthConfig,
+
+    pub merchant_api: MerchantApiConfig,
+
+    #[serde(default)]
+    pub network: NetworkConfig,
+
+    #[serde(default)]
+    pub payments: PaymentsConfig,
+
+    #[serde(default)]
+    pub storage: StorageConfig,
+
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
+
+    #[serde(default)]
+    pub idempotency: IdempotencyConfig,
+
+    pub audit: AuditConfig,
+
+    #[serde(default)]
+    pub password: PasswordConfig,
+
+    #[serde(default)]
+    pub mail: MailConfig,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -28,6 +57,12 @@ pub enum Environment {
     Production,
 }
 
+impl Environment {
+    pub fn is_production(&self) -> bool {
+        matches!(self, Environment::Production)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct CorsConfig {
     pub allow_origin: String,
@@ -37,6 +72,411 @@ pub struct CorsConfig {
     pub max_age: u64,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AuthConfig {
+    #[serde(default = "default_login_code_ttl_seconds")]
+    pub login_code_ttl_seconds: i64,
+    #[serde(default = "default_login_code_max_attempts")]
+    pub login_code_max_attempts: i32,
+    #[serde(default)]
+    pub token_revocation_store: TokenRevocationStore,
+    #[serde(default = "default_access_token_ttl_minutes")]
+    pub access_token_ttl_minutes: i64,
+    #[serde(default = "default_refresh_token_ttl_days")]
+    pub refresh_token_ttl_days: i64,
+}
+
+/// Backend for the JWT revocation list
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenRevocationStore {
+    #[default]
+    Postgres,
+    Memory,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            login_code_ttl_seconds: default_login_code_ttl_seconds(),
+            login_code_max_attempts: default_login_code_max_attempts(),
+            token_revocation_store: TokenRevocationStore::default(),
+            access_token_ttl_minutes: default_access_token_ttl_minutes(),
+            refresh_token_ttl_days: default_refresh_token_ttl_days(),
+        }
+    }
+}
+
+/// Settings for the merchant-facing API and its credentials
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MerchantApiConfig {
+    /// Passphrase for encrypting stored API secrets; changing it invalidates all of them
+    pub secret_encryption_key: String,
+    /// How long replaced keys keep working after a rotation
+    #[serde(default = "default_key_rotation_overlap_minutes")]
+    pub key_rotation_overlap_minutes: i64,
+    /// Maximum difference between a signed request's timestamp and server time
+    #[serde(default = "default_signature_max_skew_seconds")]
+    pub signature_max_skew_seconds: i64,
+}
+
+/// How the service sits behind reverse proxies
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct NetworkConfig {
+    /// Comma separated addresses or CIDR ranges whose X-Forwarded-For is believed
+    #[serde(default)]
+    pub trusted_proxies: String,
+}
+
+impl NetworkConfig {
+    pub fn parse_trusted_proxies(&self) -> Vec<IpNet> {
+        self.trusted_proxies
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| {
+                let proxy = entry
+                    .parse::<IpNet>()
+                    .or_else(|_| entry.parse::<std::net::IpAddr>().map(IpNet::from));
+                if proxy.is_err() {
+                    tracing::warn!("Ignoring invalid trusted proxy '{}'", entry);
+                }
+                proxy.ok()
+            })
+            .collect()
+    }
+}
+
+/// Settings for merchant payments
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PaymentsConfig {
+    /// Lifetime of a payment when the merchant does not ask for one
+    #[serde(default = "default_payment_ttl_minutes")]
+    pub default_ttl_minutes: i64,
+    /// Longest lifetime a merchant may ask for
+    #[serde(default = "default_payment_max_ttl_minutes")]
+    pub max_ttl_minutes: i64,
+    /// How often overdue payments are expired, waiting payments re-matched
+    /// and unconfirmed ones escalated
+    #[serde(default = "default_expiry_sweep_interval_seconds")]
+    pub expiry_sweep_interval_seconds: u64,
+    /// Platform fee charged on each settled payment, in basis points (150 = 1.5%)
+    #[serde(default)]
+    pub settlement_fee_bps: u32,
+    /// How a payment picks a trader when several can take it
+    #[serde(default)]
+    pub matching_strategy: MatchingStrategyKind,
+    /// How long a trader has to confirm a payment marked as paid before Support takes over
+    #[serde(default = "default_confirmation_timeout_minutes")]
+    pub confirmation_timeout_minutes: i64,
+    /// How long after the trader's decision a dispute can be opened
+    #[serde(default = "default_dispute_window_hours")]
+    pub dispute_window_hours: i64,
+    /// Largest evidence file a merchant can upload
+    #[serde(default = "default_evidence_max_bytes")]
+    pub evidence_max_bytes: usize,
+}
+
+/// Strategy of the matching engine
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchingStrategyKind {
+    #[default]
+    RoundRobin,
+    LeastLoaded,
+    SuccessRate,
+}
+
+impl Default for PaymentsConfig {
+    fn default() -> Self {
+        Self {
+            default_ttl_minutes: default_payment_ttl_minutes(),
+            max_ttl_minutes: default_payment_max_ttl_minutes(),
+            expiry_sweep_interval_seconds: default_expiry_sweep_interval_seconds(),
+            settlement_fee_bps: 0,
+            matching_strategy: MatchingStrategyKind::default(),
+            confirmation_timeout_minutes: default_confirmation_timeout_minutes(),
+            dispute_window_hours: default_dispute_window_hours(),
+            evidence_max_bytes: default_evidence_max_bytes(),
+        }
+    }
+}
+
+/// Where uploaded files are kept
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StorageConfig {
+    /// Root directory of the local file storage
+    #[serde(default = "default_storage_local_root")]
+    pub local_root: String,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            local_root: default_storage_local_root(),
+        }
+    }
+}
+
+/// Settings for payment status webhooks sent to merchants
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WebhooksConfig {
+    /// Attempts made before a delivery is given up as failed
+    #[serde(default = "default_webhook_max_attempts")]
+    pub max_attempts: i32,
+    /// Wait after the first failed attempt; doubled after each further one
+    #[serde(default = "default_webhook_initial_backoff_seconds")]
+    pub initial_backoff_seconds: i64,
+    /// Longest wait between two attempts
+    #[serde(default = "default_webhook_max_backoff_seconds")]
+    pub max_backoff_seconds: i64,
+    /// How long the merchant has to answer a webhook request
+    #[serde(default = "default_webhook_request_timeout_seconds")]
+    pub request_timeout_seconds: u64,
+    /// How often due deliveries are sent
+    #[serde(default = "default_webhook_dispatch_interval_seconds")]
+    pub dispatch_interval_seconds: u64,
+    /// Deliveries sent per dispatch run
+    #[serde(default = "default_webhook_batch_size")]
+    pub batch_size: i64,
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_webhook_max_attempts(),
+            initial_backoff_seconds: default_webhook_initial_backoff_seconds(),
+            max_backoff_seconds: default_webhook_max_backoff_seconds(),
+            request_timeout_seconds: default_webhook_request_timeout_seconds(),
+            dispatch_interval_seconds: default_webhook_dispatch_interval_seconds(),
+            batch_size: default_webhook_batch_size(),
+        }
+    }
+}
+
+/// Settings for the `Idempotency-Key` header of mutating requests
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IdempotencyConfig {
+    /// How long a key and its stored response are kept for replay
+    #[serde(default = "default_idempotency_key_ttl_hours")]
+    pub key_ttl_hours: i64,
+    /// How long a running request holds its key; a retry may take over a key whose
+    /// request died or was cancelled without storing a response
+    #[serde(default = "default_idempotency_lease_seconds")]
+    pub lease_seconds: i64,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            key_ttl_hours: default_idempotency_key_ttl_hours(),
+            lease_seconds: default_idempotency_lease_seconds(),
+        }
+    }
+}
+
+/// Settings for the tamper-evident audit log
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AuditConfig {
+    /// Key for signing checkpoints; keep a copy with the exported checkpoints' verifier
+    pub checkpoint_signing_key: String,
+    /// How often the head of the log is signed
+    #[serde(default = "default_audit_checkpoint_interval_minutes")]
+    pub checkpoint_interval_minutes: u64,
+}
+
+/// Rules for backoffice passwords and the emailed reset links
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PasswordConfig {
+    #[serde(default = "default_password_min_length")]
+    pub min_length: usize,
+    #[serde(default = "default_true")]
+    pub require_lowercase: bool,
+    #[serde(default = "default_true")]
+    pub require_uppercase: bool,
+    #[serde(default = "default_true")]
+    pub require_digit: bool,
+    #[serde(default)]
+    pub require_symbol: bool,
+    /// File of breached passwords, one per line, compared case-insensitively
+    #[serde(default)]
+    pub denylist_path: Option<String>,
+    /// How many of the latest passwords cannot be chosen again; 0 turns the check off
+    #[serde(default = "default_password_history_size")]
+    pub history_size: usize,
+    /// How long an emailed reset link works
+    #[serde(default = "default_password_reset_ttl_minutes")]
+    pub reset_ttl_minutes: i64,
+    /// Page of the backoffice UI that takes the reset token as the `token` query parameter
+    #[serde(default = "default_password_reset_url")]
+    pub reset_url: String,
+}
+
+impl Default for PasswordConfig {
+    fn default() -> Self {
+        Self {
+            min_length: default_password_min_length(),
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: false,
+            denylist_path: None,
+            history_size: default_password_history_size(),
+            reset_ttl_minutes: default_password_reset_ttl_minutes(),
+            reset_url: default_password_reset_url(),
+        }
+    }
+}
+
+/// How backoffice emails (login codes, password reset links) leave the service
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MailConfig {
+    #[serde(default)]
+    pub transport: MailTransport,
+    /// Endpoint of the mail relay used by the `http` transport
+    #[serde(default)]
+    pub http_url: Option<String>,
+    /// Sent to the relay as a Bearer token
+    #[serde(default)]
+    pub http_api_key: Option<String>,
+    #[serde(default = "default_mail_from")]
+    pub from: String,
+}
+
+/// Backend for outgoing email
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MailTransport {
+    /// Writes messages, codes included, to the log; refused in production
+    #[default]
+    Log,
+    /// Posts messages as JSON to an HTTP mail relay
+    Http,
+}
+
+impl Default for MailConfig {
+    fn default() -> Self {
+        Self {
+            transport: MailTransport::default(),
+            http_url: None,
+            http_api_key: None,
+            from: default_mail_from(),
+        }
+    }
+}
+
+fn default_mail_from() -> String {
+    "no-reply@localhost".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_password_min_length() -> usize {
+    12
+}
+
+fn default_password_history_size() -> usize {
+    5
+}
+
+fn default_password_reset_ttl_minutes() -> i64 {
+    60
+}
+
+fn default_password_reset_url() -> String {
+    "http://localhost:3000/reset-password".to_string()
+}
+
+fn default_payment_ttl_minutes() -> i64 {
+    30
+}
+
+fn default_payment_max_ttl_minutes() -> i64 {
+    24 * 60
+}
+
+fn default_expiry_sweep_interval_seconds() -> u64 {
+    60
+}
+
+fn default_confirmation_timeout_minutes() -> i64 {
+    15
+}
+
+fn default_dispute_window_hours() -> i64 {
+    72
+}
+
+fn default_evidence_max_bytes() -> usize {
+    5 * 1024 * 1024
+}
+
+fn default_storage_local_root() -> String {
+    "./data/storage".to_string()
+}
+
+fn default_webhook_max_attempts() -> i32 {
+    10
+}
+
+fn default_webhook_initial_backoff_seconds() -> i64 {
+    30
+}
+
+fn default_webhook_max_backoff_seconds() -> i64 {
+    6 * 60 * 60
+}
+
+fn default_webhook_request_timeout_seconds() -> u64 {
+    10
+}
+
+fn default_webhook_dispatch_interval_seconds() -> u64 {
+    10
+}
+
+fn default_webhook_batch_size() -> i64 {
+    50
+}
+
+fn default_idempotency_key_ttl_hours() -> i64 {
+    24
+}
+
+fn default_idempotency_lease_seconds() -> i64 {
+    60
+}
+
+fn default_audit_checkpoint_interval_minutes() -> u64 {
+    60
+}
+
+fn default_key_rotation_overlap_minutes() -> i64 {
+    24 * 60
+}
+
+fn default_signature_max_skew_seconds() -> i64 {
+    300
+}
+
+fn default_login_code_ttl_seconds() -> i64 {
+    300
+}
+
+fn default_login_code_max_attempts() -> i32 {
+    5
+}
+
+fn default_access_token_ttl_minutes() -> i64 {
+    15
+}
+
+fn default_refresh_token_ttl_days() -> i64 {
+    30
+}
+
 impl CorsConfig {
     pub fn parse_origins(&self) -> Vec<String> {
         self.parse_separator_helper(&self.allow_origin)
@@ -68,10 +508,29 @@ impl Config {
         let config: Config = Figment::new()
             .merge(Env::prefixed("P2P_APP_").split("__"))
             .extract()?;
+        config.validate()?;
 
         Ok(config)
     }
 
+    /// Rejects settings the background jobs cannot run with
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.payments.expiry_sweep_interval_seconds > 0,
+            "payments.expiry_sweep_interval_seconds must be greater than zero"
+        );
+        ensure!(
+            self.webhooks.dispatch_interval_seconds > 0,
+            "webhooks.dispatch_interval_seconds must be greater than zero"
+        );
+        ensure!(
+            self.audit.checkpoint_interval_minutes > 0,
+            "audit.checkpoint_interval_minutes must be greater than zero"
+        );
+
+        Ok(())
+    }
+
     pub fn server_address(&self) -> String {
         format!("{}:{}", self.service_host, self.service_port)
     }
@@ -98,3 +557,52 @@ pub async fn setup_database(config: &Config) -> Result<DatabaseConnection> {
 
     Ok(pool)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config() -> Config {
+        serde_json::from_value(json!({
+            "environment": "development",
+            "database_url": "postgres://localhost/test",
+            "database_max_connections": 1,
+            "database_min_connections": 1,
+            "service_host": "127.0.0.1",
+            "service_port": 3000,
+            "logging_level": "info",
+            "jwt_secret_key": "secret",
+            "cors": {
+                "allow_origin": "*",
+                "allow_methods": "GET",
+                "allow_headers": "content-type",
+                "allow_credentials": false,
+                "max_age": 60
+            },
+            "merchant_api": { "secret_encryption_key": "key" },
+            "audit": { "checkpoint_signing_key": "key" }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_defaults_are_valid() {
+        assert!(config().validate().is_ok());
+    }
+
+    #[test]
+    fn test_zero_intervals_are_rejected() {
+        let mut zero_sweep = config();
+        zero_sweep.payments.expiry_sweep_interval_seconds = 0;
+        assert!(zero_sweep.validate().is_err());
+
+        let mut zero_dispatch = config();
+        zero_dispatch.webhooks.dispatch_interval_seconds = 0;
+        assert!(zero_dispatch.validate().is_err());
+
+        let mut zero_checkpoint = config();
+        zero_checkpoint.audit.checkpoint_interval_minutes = 0;
+        assert!(zero_checkpoint.validate().is_err());
+    }
+}
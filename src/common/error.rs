@@ -32,6 +32,14 @@ pub enum AppError {
 
     #[error("JWT error: {0}")]
     JwtError(String),
+
+    /// Entity cannot move from its current state to the requested one
+    #[error("Invalid state transition: {0}")]
+    InvalidStateTransition(String),
+
+    /// Request clashes with an earlier one, e.g. a reused idempotency key
+    #[error("Conflict: {0}")]
+    Conflict(String),
 }
 
 impl AppError {
@@ -45,6 +53,8 @@ impl AppError {
             AppError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
             AppError::JwtError(_) => StatusCode::UNAUTHORIZED,
+            AppError::InvalidStateTransition(_) => StatusCode::CONFLICT,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
         }
     }
 
@@ -132,6 +142,10 @@ mod tests {
             AppError::ValidationError("test".to_string()).status_code(),
             StatusCode::BAD_REQUEST
         );
+        assert_eq!(
+            AppError::InvalidStateTransition("test".to_string()).status_code(),
+            StatusCode::CONFLICT
+        );
     }
 
     #[test]
@@ -1,5 +1,7 @@
 use crate::common::error::AppError;
 use bcrypt::{hash, verify, DEFAULT_COST};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
 
 pub fn hash_password(password: &str) -> Result<String, AppError> {
     let hashed = hash(password, DEFAULT_COST)?;
@@ -11,6 +13,19 @@ pub fn verify_password(password: &str, hash: &str) -> Result<bool, AppError> {
     Ok(is_valid)
 }
 
+/// SHA-256 of `value`, hex encoded.
+/// For high-entropy secrets (random tokens); use `hash_password` for passwords.
+pub fn sha256_hex(value: &str) -> String {
+    hex::encode(Sha256::digest(value.as_bytes()))
+}
+
+/// Random opaque token of `bytes` bytes, hex encoded
+pub fn generate_token(bytes: usize) -> String {
+    let mut buffer = vec![0u8; bytes];
+    rand::thread_rng().fill_bytes(&mut buffer);
+    hex::encode(buffer)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,4 +51,21 @@ mod tests {
         assert!(verify_password(password, &hash1).unwrap());
         assert!(verify_password(password, &hash2).unwrap());
     }
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_generate_token() {
+        let token1 = generate_token(32);
+        let token2 = generate_token(32);
+
+        assert_eq!(token1.len(), 64);
+        assert_ne!(token1, token2);
+    }
 }
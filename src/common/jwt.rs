@@ -1,8 +1,12 @@
 use crate::common::error::AppError;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+
+/// Default lifetime of access tokens issued by `JwtService::encode_token`
+pub const DEFAULT_ACCESS_TOKEN_TTL_MINUTES: i64 = 15;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String,
@@ -11,27 +15,65 @@ pub struct Claims {
     pub role_name: String,
     pub exp: i64,
     pub iat: i64,
+    /// Unique token ID, used for revocation
+    pub jti: Uuid,
+    /// Login session (refresh token family) the token was issued for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<Uuid>,
+    /// Effective permissions of the role at issue time, e.g. `users:write`
+    #[serde(default)]
+    pub permissions: Vec<String>,
 }
 
 impl Claims {
     pub fn new(user_id: Uuid, role_id: Uuid, role_name: String, expiration_hours: i64) -> Self {
+        Self::with_ttl(
+            user_id,
+            role_id,
+            role_name,
+            Duration::hours(expiration_hours),
+        )
+    }
+
+    pub fn with_ttl(user_id: Uuid, role_id: Uuid, role_name: String, ttl: Duration) -> Self {
         let now = Utc::now();
-        let exp = now + Duration::hours(expiration_hours);
+        let exp = now + ttl;
 
         Self {
             sub: user_id.to_string(),
             user_id,
             role_id,
-            role_name: role_name.clone(),
+            role_name,
             exp: exp.timestamp(),
             iat: now.timestamp(),
+            jti: Uuid::new_v4(),
+            sid: None,
+            permissions: Vec::new(),
         }
     }
 
+    pub fn with_session(mut self, session_id: Uuid) -> Self {
+        self.sid = Some(session_id);
+        self
+    }
+
+    pub fn with_permissions(mut self, permissions: Vec<String>) -> Self {
+        self.permissions = permissions;
+        self
+    }
+
     pub fn is_expired(&self) -> bool {
         Utc::now().timestamp() > self.exp
     }
 
+    pub fn issued_at(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp(self.iat, 0).unwrap_or_default()
+    }
+
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp(self.exp, 0).unwrap_or_default()
+    }
+
     pub fn is_admin(&self) -> bool {
         self.role_name == "Admin"
     }
@@ -39,16 +81,25 @@ impl Claims {
     pub fn has_role(&self, role_name: &str) -> bool {
         self.role_name == role_name
     }
+
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.permissions.iter().any(|p| p == permission)
+    }
 }
 
 pub struct JwtService {
     encoding_key: EncodingKey,
     decoding_key: DecodingKey,
     validation: Validation,
+    access_token_ttl: Duration,
 }
 
 impl JwtService {
     pub fn new(secret: &str) -> Self {
+        Self::with_access_token_ttl(secret, Duration::minutes(DEFAULT_ACCESS_TOKEN_TTL_MINUTES))
+    }
+
+    pub fn with_access_token_ttl(secret: &str, access_token_ttl: Duration) -> Self {
         let encoding_key = EncodingKey::from_secret(secret.as_bytes());
         let decoding_key = DecodingKey::from_secret(secret.as_bytes());
         let validation = Validation::default();
@@ -57,18 +108,31 @@ impl JwtService {
             encoding_key,
             decoding_key,
             validation,
+            access_token_ttl,
         }
     }
 
+    pub fn access_token_ttl(&self) -> Duration {
+        self.access_token_ttl
+    }
+
+    /// Claims for a new access token with the configured lifetime
+    pub fn access_claims(&self, user_id: Uuid, role_id: Uuid, role_name: String) -> Claims {
+        Claims::with_ttl(user_id, role_id, role_name, self.access_token_ttl)
+    }
+
+    pub fn encode_claims(&self, claims: &Claims) -> Result<String, AppError> {
+        let token = encode(&Header::default(), claims, &self.encoding_key)?;
+        Ok(token)
+    }
+
     pub fn encode_token(
         &self,
         user_id: Uuid,
         role_id: Uuid,
         role_name: String,
     ) -> Result<String, AppError> {
-        let claims = Claims::new(user_id, role_id, role_name, 24);
-        let token = encode(&Header::default(), &claims, &self.encoding_key)?;
-        Ok(token)
+        self.encode_claims(&self.access_claims(user_id, role_id, role_name))
     }
 
     pub fn encode_token_with_expiration(
@@ -108,9 +172,8 @@ impl utoipa::Modify for SecurityAddon {
         if let Some(components) = openapi.components.as_mut() {
             use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
 
-            let mut security_scheme = SecurityScheme::ApiKey(
-                ApiKey::Header(ApiKeyValue::new("X-JWT-Token"))
-            );
+            let mut security_scheme =
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-JWT-Token")));
 
             // Add description to the security scheme
             if let SecurityScheme::ApiKey(api_key) = &mut security_scheme {
@@ -186,6 +249,36 @@ mod tests {
         assert!(!claims.is_expired());
     }
 
+    #[test]
+    fn test_access_token_ttl() {
+        let jwt_service =
+            JwtService::with_access_token_ttl("test_secret_key", Duration::minutes(5));
+        let session_id = Uuid::new_v4();
+
+        let claims = jwt_service
+            .access_claims(Uuid::new_v4(), test_role_id(), "User".to_string())
+            .with_session(session_id);
+        assert_eq!(claims.exp - claims.iat, 300);
+
+        let token = jwt_service.encode_claims(&claims).unwrap();
+        let decoded = jwt_service.decode_token(&token).unwrap();
+        assert_eq!(decoded.sid, Some(session_id));
+        assert_eq!(decoded.jti, claims.jti);
+    }
+
+    #[test]
+    fn test_claims_have_unique_jti() {
+        let user_id = Uuid::new_v4();
+        let role_id = test_role_id();
+
+        let first = Claims::new(user_id, role_id, "User".to_string(), 24);
+        let second = Claims::new(user_id, role_id, "User".to_string(), 24);
+
+        assert_ne!(first.jti, second.jti);
+        assert_eq!(first.issued_at().timestamp(), first.iat);
+        assert_eq!(first.expires_at().timestamp(), first.exp);
+    }
+
     #[test]
     fn test_claims_is_admin() {
         let user_id = Uuid::new_v4();
@@ -207,4 +300,18 @@ mod tests {
         assert!(claims.has_role("Support"));
         assert!(!claims.has_role("Admin"));
     }
+
+    #[test]
+    fn test_claims_permissions_round_trip() {
+        let jwt_service = JwtService::new("test_secret_key");
+        let claims = jwt_service
+            .access_claims(Uuid::new_v4(), test_role_id(), "Support".to_string())
+            .with_permissions(vec!["users:read".to_string()]);
+
+        let token = jwt_service.encode_claims(&claims).unwrap();
+        let decoded = jwt_service.decode_token(&token).unwrap();
+
+        assert!(decoded.has_permission("users:read"));
+        assert!(!decoded.has_permission("users:write"));
+    }
 }
@@ -0,0 +1,177 @@
+use crate::common::{
+    config::{Environment, MailConfig, MailTransport},
+    error::AppError,
+};
+use async_trait::async_trait;
+use reqwest::{header::CONTENT_TYPE, Client};
+use std::{sync::Arc, time::Duration};
+
+/// How long the mail relay has to accept a message
+const HTTP_MAILER_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl MailMessage {
+    pub fn new(to: impl Into<String>, subject: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            to: to.into(),
+            subject: subject.into(),
+            body: body.into(),
+        }
+    }
+}
+
+/// Outgoing email transport used by backoffice flows (login codes, notifications)
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, message: MailMessage) -> Result<(), AppError>;
+}
+
+/// Development mailer: writes messages to the log instead of sending them
+pub struct LoggingMailer;
+
+#[async_trait]
+impl Mailer for LoggingMailer {
+    async fn send(&self, message: MailMessage) -> Result<(), AppError> {
+        tracing::info!(
+            "📧 Mail to '{}' | {} | {}",
+            message.to,
+            message.subject,
+            message.body
+        );
+
+        Ok(())
+    }
+}
+
+/// Sends messages through an HTTP mail relay as `{from, to, subject, text}` JSON
+pub struct HttpMailer {
+    client: Client,
+    url: String,
+    api_key: Option<String>,
+    from: String,
+}
+
+impl HttpMailer {
+    pub fn new(url: String, api_key: Option<String>, from: String) -> Self {
+        let client = Client::builder()
+            .timeout(HTTP_MAILER_TIMEOUT)
+            .build()
+            .expect("HTTP client configuration is valid");
+
+        Self {
+            client,
+            url,
+            api_key,
+            from,
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for HttpMailer {
+    async fn send(&self, message: MailMessage) -> Result<(), AppError> {
+        let body = serde_json::json!({
+            "from": self.from,
+            "to": message.to,
+            "subject": message.subject,
+            "text": message.body,
+        });
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_string());
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|err| AppError::InternalError(format!("Mail relay unreachable: {}", err)))?;
+        if !response.status().is_success() {
+            return Err(AppError::InternalError(format!(
+                "Mail relay answered {}",
+                response.status()
+            )));
+        }
+
+        tracing::info!("Mail '{}' sent to '{}'", message.subject, message.to);
+
+        Ok(())
+    }
+}
+
+/// Picks the configured transport. Production refuses the logging mailer,
+/// which would write login codes and reset links to the logs.
+pub fn mailer_from_config(
+    config: &MailConfig,
+    environment: &Environment,
+) -> Result<Arc<dyn Mailer>, AppError> {
+    match config.transport {
+        MailTransport::Log if environment.is_production() => Err(AppError::InternalError(
+            "The log mail transport cannot be used in production, configure the http one"
+                .to_string(),
+        )),
+        MailTransport::Log => Ok(Arc::new(LoggingMailer)),
+        MailTransport::Http => {
+            let url = config.http_url.clone().ok_or_else(|| {
+                AppError::InternalError("The http mail transport needs mail.http_url".to_string())
+            })?;
+
+            Ok(Arc::new(HttpMailer::new(
+                url,
+                config.http_api_key.clone(),
+                config.from.clone(),
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_logging_mailer_sends() {
+        let mailer = LoggingMailer;
+        let message = MailMessage::new("test@example.com", "Subject", "Body");
+
+        assert!(mailer.send(message).await.is_ok());
+    }
+
+    #[test]
+    fn test_production_refuses_logging_mailer() {
+        let config = MailConfig::default();
+
+        assert!(mailer_from_config(&config, &Environment::Development).is_ok());
+        assert!(matches!(
+            mailer_from_config(&config, &Environment::Production),
+            Err(AppError::InternalError(_))
+        ));
+    }
+
+    #[test]
+    fn test_http_mailer_needs_url() {
+        let config = MailConfig {
+            transport: MailTransport::Http,
+            ..MailConfig::default()
+        };
+
+        assert!(mailer_from_config(&config, &Environment::Production).is_err());
+        assert!(mailer_from_config(
+            &MailConfig {
+                http_url: Some("https://mail.example.com/send".to_string()),
+                ..config
+            },
+            &Environment::Production,
+        )
+        .is_ok());
+    }
+}
@@ -1,12 +1,45 @@
 use axum::{
-    extract::{Request, State},
+    body::{self, Body},
+    extract::{ConnectInfo, OriginalUri, Request, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
     middleware::Next,
     response::Response,
 };
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 use uuid::Uuid;
 
-use crate::common::{app_state::AppState, error::AppError, jwt::Claims};
+use crate::common::{
+    app_state::AppState,
+    error::AppError,
+    jwt::Claims,
+    request_signature::{self, API_KEY_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER},
+};
+use crate::domains::{
+    audit::domain::audit_entry::AuditContext,
+    backoffice::domain::{idempotency_key::IdempotencyKey, merchant::MerchantContext},
+};
+
+/// Largest request body accepted for signature verification
+const MAX_SIGNED_BODY_BYTES: usize = 1024 * 1024;
+
+/// Room for the multipart boundaries and headers around an evidence upload
+const MULTIPART_OVERHEAD_BYTES: usize = 64 * 1024;
+
+/// Header clients send to make a mutating request safe to retry
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Set on responses replayed from an earlier request with the same key
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Request ID set on every request and echoed on the response
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Longer user agents are cut in audit entries
+const MAX_USER_AGENT_LEN: usize = 512;
 
 /// JWT Authentication Middleware
 /// Extracts and validates JWT token from X-JWT-Token header
@@ -34,6 +67,38 @@ pub async fn jwt_auth(
         return Err(AppError::Unauthorized("Token has expired".to_string()));
     }
 
+    // Check if token was revoked (logout or forced sign-out)
+    if state
+        .token_revocation_repository
+        .is_revoked(claims.jti, claims.user_id, claims.issued_at())
+        .await?
+    {
+        return Err(AppError::Unauthorized("Token has been revoked".to_string()));
+    }
+
+    // Check if the login session was ended (logout or refresh token reuse)
+    if let Some(session_id) = claims.sid {
+        if state
+            .refresh_token_repository
+            .is_family_revoked(session_id)
+            .await?
+        {
+            return Err(AppError::Unauthorized(
+                "Session has been revoked".to_string(),
+            ));
+        }
+    }
+
+    // Check the user was not deactivated or deleted since the token was issued
+    let is_active = state
+        .user_repository
+        .find_by_id(claims.user_id)
+        .await?
+        .is_some_and(|user| user.is_active());
+    if !is_active {
+        return Err(AppError::Unauthorized("User is inactive".to_string()));
+    }
+
     // Add claims to request extensions for downstream handlers
     request.extensions_mut().insert(claims);
 
@@ -41,6 +106,288 @@ pub async fn jwt_auth(
     Ok(next.run(request).await)
 }
 
+/// Merchant API Authentication Middleware
+/// Verifies the HMAC signature from X-Api-Key, X-Api-Timestamp and X-Api-Signature
+/// and the client IP against the key's allowlist
+/// Adds MerchantContext to request extensions if valid
+pub async fn merchant_auth(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let headers = request.headers();
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| AppError::Unauthorized(format!("Missing {} header", name)))
+    };
+
+    let public_key = header(API_KEY_HEADER)?;
+    let signature = header(SIGNATURE_HEADER)?;
+    let timestamp = header(TIMESTAMP_HEADER)?
+        .parse::<i64>()
+        .map_err(|_| AppError::Unauthorized(format!("Invalid {} header", TIMESTAMP_HEADER)))?;
+
+    // Nested routers see a stripped URI; merchants sign the path they called
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or(request.uri(), |original| &original.0)
+        .path_and_query()
+        .map_or_else(|| "/".to_string(), |path| path.as_str().to_string());
+    let method = request.method().to_string();
+
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let client_ip = state.client_ip_resolver.resolve(peer, headers);
+
+    // The body is signed too, so buffer it and hand the bytes back to the handler
+    let (parts, body) = request.into_parts();
+    let bytes = body::to_bytes(body, max_body_bytes(&state))
+        .await
+        .map_err(|_| AppError::BadRequest("Request body is too large".to_string()))?;
+
+    let payload = request_signature::signing_payload(timestamp, &method, &path, &bytes);
+    let context = state
+        .merchant_authenticate_use_case
+        .execute(&public_key, timestamp, &payload, &signature, client_ip)
+        .await?;
+
+    let mut request = Request::from_parts(parts, Body::from(bytes));
+    request.extensions_mut().insert(context);
+
+    Ok(next.run(request).await)
+}
+
+/// Audit Context Middleware
+/// Runs after `jwt_auth`; adds AuditContext with the acting user, client IP,
+/// user agent and request ID to request extensions for handlers that change records
+pub async fn audit_context(
+    State(state): State<Arc<AppState>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let headers = request.headers();
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+
+    let context = AuditContext {
+        actor_id: request
+            .extensions()
+            .get::<Claims>()
+            .map(|claims| claims.user_id),
+        ip_address: state
+            .client_ip_resolver
+            .resolve(peer, headers)
+            .map(|ip| ip.to_string()),
+        user_agent: header_text(headers, header::USER_AGENT.as_str())
+            .map(|agent| agent.chars().take(MAX_USER_AGENT_LEN).collect()),
+        request_id: header_text(headers, REQUEST_ID_HEADER)
+            .filter(|id| id.len() <= MAX_REQUEST_ID_LEN),
+    };
+    request.extensions_mut().insert(context);
+
+    next.run(request).await
+}
+
+fn header_text(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Idempotency-Key Middleware
+/// Runs after `jwt_auth` or `merchant_auth`; keys are scoped to the authenticated caller.
+/// The first mutating request with a key stores its response, retries with the same
+/// request get that response replayed, and a different request with the key is a conflict.
+/// Requests without the header are passed through unchanged
+pub async fn idempotency(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if !matches!(
+        *request.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    ) {
+        return Ok(next.run(request).await);
+    }
+
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(next.run(request).await);
+    };
+    let key = key
+        .to_str()
+        .ok()
+        .map(str::trim)
+        .filter(|key| !key.is_empty() && key.len() <= MAX_IDEMPOTENCY_KEY_LEN)
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "{} header must be 1 to {} characters",
+                IDEMPOTENCY_KEY_HEADER, MAX_IDEMPOTENCY_KEY_LEN
+            ))
+        })?
+        .to_string();
+
+    let extensions = request.extensions();
+    let scope = if let Some(claims) = extensions.get::<Claims>() {
+        format!("user:{}", claims.user_id)
+    } else if let Some(context) = extensions.get::<MerchantContext>() {
+        format!("site:{}", context.site.id)
+    } else {
+        return Err(AppError::Unauthorized(
+            "Authentication required".to_string(),
+        ));
+    };
+
+    let path = extensions
+        .get::<OriginalUri>()
+        .map_or(request.uri(), |original| &original.0)
+        .path_and_query()
+        .map_or_else(|| "/".to_string(), |path| path.as_str().to_string());
+    let method = request.method().to_string();
+
+    let (parts, body) = request.into_parts();
+    let bytes = body::to_bytes(body, max_body_bytes(&state))
+        .await
+        .map_err(|_| AppError::BadRequest("Request body is too large".to_string()))?;
+    let fingerprint = IdempotencyKey::fingerprint(&method, &path, &bytes);
+
+    let reservation = IdempotencyKey::new(
+        scope.clone(),
+        key.clone(),
+        fingerprint.clone(),
+        chrono::Duration::hours(state.config.idempotency.key_ttl_hours),
+        chrono::Duration::seconds(state.config.idempotency.lease_seconds),
+    );
+    let reserved_at = reservation.created_at;
+    if let Some(existing) = state
+        .idempotency_key_repository
+        .reserve(reservation)
+        .await?
+    {
+        if existing.request_fingerprint != fingerprint {
+            return Err(AppError::Conflict(format!(
+                "Idempotency key '{}' was already used for a different request",
+                key
+            )));
+        }
+
+        let (status, response_body) = existing.response().ok_or_else(|| {
+            AppError::Conflict(format!(
+                "Request with idempotency key '{}' is still being processed",
+                key
+            ))
+        })?;
+
+        tracing::debug!(
+            "Replaying response for idempotency key '{}' of {}",
+            key,
+            scope
+        );
+
+        let mut response = Response::new(Body::from(response_body.to_string()));
+        *response.status_mut() = StatusCode::from_u16(status as u16).unwrap_or(StatusCode::OK);
+        let headers = response.headers_mut();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+        return Ok(response);
+    }
+
+    let reservation = Reservation {
+        state: Arc::clone(&state),
+        scope,
+        key,
+        reserved_at,
+    };
+    let response = next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await;
+
+    // Server errors may be transient, so the key is freed for a retry
+    let (parts, body) = response.into_parts();
+    let stored = match body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            tracing::warn!(
+                "Reading response for idempotency key '{}' failed: {}",
+                reservation.key,
+                err
+            );
+            reservation.release().await?;
+            return Err(AppError::InternalError(
+                "Failed to read response".to_string(),
+            ));
+        }
+    };
+
+    match std::str::from_utf8(&stored) {
+        Ok(text) if !parts.status.is_server_error() => {
+            reservation
+                .complete(parts.status.as_u16() as i32, text.to_string())
+                .await?
+        }
+        _ => reservation.release().await?,
+    }
+
+    Ok(Response::from_parts(parts, Body::from(stored)))
+}
+
+/// Key reserved by a running request. A cancelled request, e.g. when the client
+/// disconnects mid-response, keeps the key until its lease runs out: the handler may
+/// already have committed, so retries get a conflict rather than running it again.
+struct Reservation {
+    state: Arc<AppState>,
+    scope: String,
+    key: String,
+    /// Fences off writes once a retry took over the key after the lease ran out
+    reserved_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Reservation {
+    /// Stores the response for replay; the key is freed when that fails
+    async fn complete(self, status: i32, body: String) -> Result<(), AppError> {
+        let repository = &self.state.idempotency_key_repository;
+        if let Err(err) = repository
+            .complete(&self.scope, &self.key, self.reserved_at, status, body)
+            .await
+        {
+            tracing::warn!(
+                "Storing response for idempotency key '{}' failed: {}",
+                self.key,
+                err
+            );
+            return self.release().await;
+        }
+
+        Ok(())
+    }
+
+    async fn release(self) -> Result<(), AppError> {
+        self.state
+            .idempotency_key_repository
+            .release(&self.scope, &self.key, self.reserved_at)
+            .await
+    }
+}
+
+/// Largest request body buffered by the middlewares; evidence uploads are the largest
+fn max_body_bytes(state: &AppState) -> usize {
+    MAX_SIGNED_BODY_BYTES.max(state.config.payments.evidence_max_bytes + MULTIPART_OVERHEAD_BYTES)
+}
+
 /// Role-Based Authorization Middleware Factory
 /// Checks if authenticated user has one of the required role IDs
 ///
@@ -82,6 +429,45 @@ pub fn require_roles(
     }
 }
 
+/// Permission-Based Authorization Middleware Factory
+/// Checks if the permissions embedded in the authenticated user's token
+/// include `permission`
+///
+/// # Example Usage
+/// ```ignore
+/// use crate::domains::backoffice::role::permission::USERS_WRITE;
+/// Router::new()
+///     .route("/user", post(handler))
+///     .route_layer(axum::middleware::from_fn(require_permission(USERS_WRITE)))
+/// ```
+pub fn require_permission(
+    permission: &'static str,
+) -> impl Fn(
+    Request,
+    Next,
+)
+    -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Response, AppError>> + Send>>
+       + Clone {
+    move |request: Request, next: Next| {
+        Box::pin(async move {
+            // Extract claims from request extensions (added by jwt_auth middleware)
+            let claims = request
+                .extensions()
+                .get::<Claims>()
+                .ok_or_else(|| AppError::Unauthorized("Authentication required".to_string()))?;
+
+            if !claims.has_permission(permission) {
+                return Err(AppError::Forbidden(format!(
+                    "Missing permission: {}",
+                    permission
+                )));
+            }
+
+            Ok(next.run(request).await)
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,6 +485,9 @@ mod tests {
             role_name: "Admin".to_string(),
             exp: Utc::now().timestamp() + 3600,
             iat: Utc::now().timestamp(),
+            jti: Uuid::new_v4(),
+            sid: None,
+            permissions: vec!["users:read".to_string()],
         };
 
         // Test admin role access
@@ -106,5 +495,9 @@ mod tests {
 
         // Test user role does not have admin access
         assert!(user_role_id != claims.role_id);
+
+        // Permission checks use the embedded permission names
+        assert!(claims.has_permission("users:read"));
+        assert!(!claims.has_permission("users:write"));
     }
 }
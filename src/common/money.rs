@@ -0,0 +1,462 @@
+use crate::common::error::AppError;
+use sea_orm::{sea_query::StringLen, DeriveActiveEnum, EnumIter, Iterable};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use utoipa::ToSchema;
+
+/// ISO 4217 currencies the platform settles in.
+/// Stored as the three-letter code in `varchar(3)` columns.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    ToSchema,
+    EnumIter,
+    DeriveActiveEnum,
+)]
+#[serde(rename_all = "UPPERCASE")]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(3))")]
+pub enum Currency {
+    #[sea_orm(string_value = "AZN")]
+    Azn,
+    #[sea_orm(string_value = "BHD")]
+    Bhd,
+    #[sea_orm(string_value = "BYN")]
+    Byn,
+    #[sea_orm(string_value = "CNY")]
+    Cny,
+    #[sea_orm(string_value = "EUR")]
+    Eur,
+    #[sea_orm(string_value = "GBP")]
+    Gbp,
+    #[sea_orm(string_value = "INR")]
+    Inr,
+    #[sea_orm(string_value = "JPY")]
+    Jpy,
+    #[sea_orm(string_value = "KGS")]
+    Kgs,
+    #[sea_orm(string_value = "KRW")]
+    Krw,
+    #[sea_orm(string_value = "KWD")]
+    Kwd,
+    #[sea_orm(string_value = "KZT")]
+    Kzt,
+    #[sea_orm(string_value = "RUB")]
+    Rub,
+    #[sea_orm(string_value = "TRY")]
+    Try,
+    #[sea_orm(string_value = "UAH")]
+    Uah,
+    #[sea_orm(string_value = "USD")]
+    Usd,
+    #[sea_orm(string_value = "UZS")]
+    Uzs,
+}
+
+impl Currency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Currency::Azn => "AZN",
+            Currency::Bhd => "BHD",
+            Currency::Byn => "BYN",
+            Currency::Cny => "CNY",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Inr => "INR",
+            Currency::Jpy => "JPY",
+            Currency::Kgs => "KGS",
+            Currency::Krw => "KRW",
+            Currency::Kwd => "KWD",
+            Currency::Kzt => "KZT",
+            Currency::Rub => "RUB",
+            Currency::Try => "TRY",
+            Currency::Uah => "UAH",
+            Currency::Usd => "USD",
+            Currency::Uzs => "UZS",
+        }
+    }
+
+    /// Number of decimal digits in the minor unit, per ISO 4217
+    pub fn exponent(&self) -> u32 {
+        match self {
+            Currency::Jpy | Currency::Krw => 0,
+            Currency::Bhd | Currency::Kwd => 3,
+            _ => 2,
+        }
+    }
+
+    fn minor_per_major(&self) -> i64 {
+        10_i64.pow(self.exponent())
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = AppError;
+
+    /// Accepts codes in any case, e.g. `usd`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let code = value.trim().to_ascii_uppercase();
+
+        Currency::iter()
+            .find(|currency| currency.as_str() == code)
+            .ok_or_else(|| {
+                AppError::ValidationError(format!(
+                    "'{}' is not a supported ISO 4217 currency",
+                    code
+                ))
+            })
+    }
+}
+
+/// Exact amount of money: integer minor units (cents, kopecks, ...) of one currency.
+///
+/// Arithmetic is checked and never mixes currencies. Serialized as
+/// `{"amount": "1500.00", "currency": "USD"}` with the amount as a decimal string.
+/// Persisted as a `bigint` minor-unit column next to a [`Currency`] column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "MoneyRepr", into = "MoneyRepr")]
+pub struct Money {
+    minor_units: i64,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn new(minor_units: i64, currency: Currency) -> Self {
+        Self {
+            minor_units,
+            currency,
+        }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(0, currency)
+    }
+
+    /// Parses a decimal amount such as `1500`, `1500.5` or `-0.25`.
+    /// More fractional digits than the currency's exponent are rejected, never rounded.
+    pub fn from_decimal(amount: &str, currency: Currency) -> Result<Self, AppError> {
+        let invalid = || {
+            AppError::ValidationError(format!("'{}' is not a valid {} amount", amount, currency))
+        };
+
+        let (negative, digits) = match amount.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, amount),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+        let exponent = currency.exponent() as usize;
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty()
+            || !is_digits(whole)
+            || !is_digits(fraction)
+            || fraction.len() > exponent
+            || (digits.contains('.') && fraction.is_empty())
+        {
+            return Err(invalid());
+        }
+
+        let fraction = format!("{:0<width$}", fraction, width = exponent);
+        let minor_units = whole
+            .parse::<i64>()
+            .ok()
+            .and_then(|whole| whole.checked_mul(currency.minor_per_major()))
+            .and_then(|whole| whole.checked_add(fraction.parse::<i64>().unwrap_or(0)))
+            .ok_or_else(invalid)?;
+
+        Ok(Self::new(
+            if negative { -minor_units } else { minor_units },
+            currency,
+        ))
+    }
+
+    /// Parses user input where both the amount and the currency code are strings
+    pub fn parse(amount: &str, currency: &str) -> Result<Self, AppError> {
+        Self::from_decimal(amount.trim(), currency.parse()?)
+    }
+
+    pub fn minor_units(&self) -> i64 {
+        self.minor_units
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.minor_units == 0
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.minor_units > 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.minor_units < 0
+    }
+
+    /// Amount with exactly `exponent` fractional digits, e.g. `1500.00`
+    pub fn to_decimal_string(&self) -> String {
+        let exponent = self.currency.exponent() as usize;
+        let sign = if self.minor_units < 0 { "-" } else { "" };
+        let absolute = self.minor_units.unsigned_abs();
+
+        if exponent == 0 {
+            return format!("{}{}", sign, absolute);
+        }
+
+        let scale = self.currency.minor_per_major() as u64;
+        format!(
+            "{}{}.{:0width$}",
+            sign,
+            absolute / scale,
+            absolute % scale,
+            width = exponent
+        )
+    }
+
+    pub fn checked_add(&self, other: Money) -> Result<Money, AppError> {
+        self.ensure_same_currency(&other)?;
+
+        self.minor_units
+            .checked_add(other.minor_units)
+            .map(|minor_units| Money::new(minor_units, self.currency))
+            .ok_or_else(|| self.overflow())
+    }
+
+    pub fn checked_sub(&self, other: Money) -> Result<Money, AppError> {
+        self.ensure_same_currency(&other)?;
+
+        self.minor_units
+            .checked_sub(other.minor_units)
+            .map(|minor_units| Money::new(minor_units, self.currency))
+            .ok_or_else(|| self.overflow())
+    }
+
+    pub fn checked_neg(&self) -> Result<Money, AppError> {
+        self.minor_units
+            .checked_neg()
+            .map(|minor_units| Money::new(minor_units, self.currency))
+            .ok_or_else(|| self.overflow())
+    }
+
+    /// Splits into `parts` amounts that differ by at most one minor unit and sum
+    /// back to `self`; the leftover units go to the first parts.
+    pub fn split(&self, parts: usize) -> Result<Vec<Money>, AppError> {
+        self.allocate(&vec![1; parts])
+    }
+
+    /// Splits proportionally to `ratios` without losing minor units, e.g. a fee and a
+    /// net amount with `[150, 9850]`. Rounding leftovers go to the first shares.
+    pub fn allocate(&self, ratios: &[u64]) -> Result<Vec<Money>, AppError> {
+        let total: u128 = ratios.iter().map(|ratio| *ratio as u128).sum();
+        if total == 0 {
+            return Err(AppError::ValidationError(
+                "Money can only be split into a positive number of shares".to_string(),
+            ));
+        }
+
+        let amount = self.minor_units as i128;
+        let mut shares: Vec<i128> = ratios
+            .iter()
+            .map(|ratio| amount * *ratio as i128 / total as i128)
+            .collect();
+
+        let mut leftover = amount - shares.iter().sum::<i128>();
+        let step = leftover.signum();
+        for (share, ratio) in shares.iter_mut().zip(ratios) {
+            if leftover == 0 {
+                break;
+            }
+            if *ratio > 0 {
+                *share += step;
+                leftover -= step;
+            }
+        }
+
+        Ok(shares
+            .into_iter()
+            .map(|share| Money::new(share as i64, self.currency))
+            .collect())
+    }
+
+    fn ensure_same_currency(&self, other: &Money) -> Result<(), AppError> {
+        if self.currency != other.currency {
+            return Err(AppError::ValidationError(format!(
+                "Cannot combine {} with {}",
+                self.currency, other.currency
+            )));
+        }
+        Ok(())
+    }
+
+    fn overflow(&self) -> AppError {
+        AppError::ValidationError(format!("{} amount is out of range", self.currency))
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.to_decimal_string(), self.currency)
+    }
+}
+
+/// Exact amount in one currency; the wire format and OpenAPI schema of [`Money`]
+#[derive(Serialize, Deserialize, ToSchema)]
+#[schema(as = Money)]
+struct MoneyRepr {
+    /// Decimal amount with up to the currency's number of fractional digits
+    #[schema(example = "1500.00")]
+    amount: String,
+    currency: Currency,
+}
+
+impl TryFrom<MoneyRepr> for Money {
+    type Error = AppError;
+
+    fn try_from(repr: MoneyRepr) -> Result<Self, Self::Error> {
+        Money::from_decimal(&repr.amount, repr.currency)
+    }
+}
+
+impl From<Money> for MoneyRepr {
+    fn from(money: Money) -> Self {
+        Self {
+            amount: money.to_decimal_string(),
+            currency: money.currency,
+        }
+    }
+}
+
+impl utoipa::PartialSchema for Money {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        MoneyRepr::schema()
+    }
+}
+
+impl utoipa::ToSchema for Money {
+    fn name() -> std::borrow::Cow<'static, str> {
+        std::borrow::Cow::Borrowed("Money")
+    }
+
+    fn schemas(
+        schemas: &mut Vec<(
+            String,
+            utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+        )>,
+    ) {
+        MoneyRepr::schemas(schemas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usd(minor_units: i64) -> Money {
+        Money::new(minor_units, Currency::Usd)
+    }
+
+    #[test]
+    fn test_parse_and_format_by_exponent() {
+        assert_eq!(Money::parse("1500", "usd").unwrap(), usd(150000));
+        assert_eq!(Money::parse("12.5", "USD").unwrap(), usd(1250));
+        assert_eq!(Money::parse("-0.05", "USD").unwrap(), usd(-5));
+        assert_eq!(usd(-5).to_decimal_string(), "-0.05");
+        assert_eq!(usd(150000).to_string(), "1500.00 USD");
+
+        let yen = Money::parse("1500", "JPY").unwrap();
+        assert_eq!(yen.minor_units(), 1500);
+        assert_eq!(yen.to_decimal_string(), "1500");
+
+        let dinar = Money::parse("1.5", "KWD").unwrap();
+        assert_eq!(dinar.minor_units(), 1500);
+        assert_eq!(dinar.to_decimal_string(), "1.500");
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_amounts() {
+        for amount in [
+            "",
+            "-",
+            ".5",
+            "1.",
+            "1.234",
+            "1e3",
+            "+1",
+            "1,00",
+            " 1",
+            "99999999999999999999",
+        ] {
+            assert!(
+                Money::from_decimal(amount, Currency::Usd).is_err(),
+                "{:?} should be rejected",
+                amount
+            );
+        }
+        assert!(Money::from_decimal("1.0", Currency::Jpy).is_err());
+        assert!(Money::parse("1", "XXX").is_err());
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        assert_eq!(usd(150).checked_add(usd(50)).unwrap(), usd(200));
+        assert_eq!(usd(150).checked_sub(usd(200)).unwrap(), usd(-50));
+        assert_eq!(usd(150).checked_neg().unwrap(), usd(-150));
+
+        let euros = Money::new(50, Currency::Eur);
+        assert!(matches!(
+            usd(150).checked_add(euros),
+            Err(AppError::ValidationError(_))
+        ));
+        assert!(usd(150).checked_sub(euros).is_err());
+        assert!(usd(i64::MAX).checked_add(usd(1)).is_err());
+        assert!(usd(i64::MIN).checked_neg().is_err());
+    }
+
+    #[test]
+    fn test_split_keeps_every_minor_unit() {
+        assert_eq!(usd(100).split(3).unwrap(), vec![usd(34), usd(33), usd(33)]);
+        assert_eq!(
+            usd(-100).split(3).unwrap(),
+            vec![usd(-34), usd(-33), usd(-33)]
+        );
+        assert!(usd(100).split(0).is_err());
+
+        let shares = usd(10001).allocate(&[150, 9850]).unwrap();
+        assert_eq!(shares, vec![usd(151), usd(9850)]);
+
+        let shares = usd(5).allocate(&[0, 1, 1]).unwrap();
+        assert_eq!(shares, vec![usd(0), usd(3), usd(2)]);
+    }
+
+    #[test]
+    fn test_serde_uses_decimal_strings() {
+        let json = serde_json::to_value(usd(150000)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "amount": "1500.00", "currency": "USD" })
+        );
+
+        let money: Money =
+            serde_json::from_value(serde_json::json!({ "amount": "0.1", "currency": "USD" }))
+                .unwrap();
+        assert_eq!(money, usd(10));
+
+        let result = serde_json::from_value::<Money>(
+            serde_json::json!({ "amount": 0.1, "currency": "USD" }),
+        );
+        assert!(result.is_err());
+    }
+}
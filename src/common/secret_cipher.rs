@@ -0,0 +1,90 @@
+use crate::common::error::AppError;
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sha2::{Digest, Sha256};
+
+const NONCE_LEN: usize = 12;
+
+/// AES-256-GCM for secrets the service has to read back, e.g. merchant API secrets
+/// that are needed to verify request signatures or trader card and account numbers.
+/// Use hashing for everything else.
+pub struct SecretCipher {
+    cipher: Aes256Gcm,
+}
+
+impl SecretCipher {
+    /// The 256-bit key is the SHA-256 of the configured passphrase
+    pub fn new(passphrase: &str) -> Self {
+        let key = Sha256::digest(passphrase.as_bytes());
+
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)),
+        }
+    }
+
+    /// Base64 of `nonce || ciphertext`
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, AppError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| AppError::InternalError("Failed to encrypt secret".to_string()))?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&ciphertext);
+
+        Ok(STANDARD.encode(payload))
+    }
+
+    pub fn decrypt(&self, encoded: &str) -> Result<String, AppError> {
+        let payload = STANDARD
+            .decode(encoded)
+            .map_err(|_| AppError::InternalError("Malformed encrypted secret".to_string()))?;
+
+        if payload.len() <= NONCE_LEN {
+            return Err(AppError::InternalError(
+                "Malformed encrypted secret".to_string(),
+            ));
+        }
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| AppError::InternalError("Failed to decrypt secret".to_string()))?;
+
+        String::from_utf8(plaintext)
+            .map_err(|_| AppError::InternalError("Decrypted secret is not UTF-8".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let cipher = SecretCipher::new("passphrase");
+
+        let encrypted = cipher.encrypt("sk_secret").unwrap();
+
+        assert_ne!(encrypted, "sk_secret");
+        assert_ne!(encrypted, cipher.encrypt("sk_secret").unwrap());
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "sk_secret");
+    }
+
+    #[test]
+    fn test_decrypt_with_other_key_fails() {
+        let encrypted = SecretCipher::new("passphrase")
+            .encrypt("sk_secret")
+            .unwrap();
+
+        assert!(SecretCipher::new("other").decrypt(&encrypted).is_err());
+        assert!(SecretCipher::new("passphrase")
+            .decrypt("not-base64!")
+            .is_err());
+    }
+}
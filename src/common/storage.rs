@@ -0,0 +1,111 @@
+use crate::common::error::AppError;
+use async_trait::async_trait;
+use std::{
+    io::ErrorKind,
+    path::{Component, Path, PathBuf},
+};
+
+/// Blob store for uploaded files (payment evidence, dispute attachments).
+/// Keys are relative, `/`-separated paths chosen by the application, never by clients.
+#[async_trait]
+pub trait FileStorage: Send + Sync {
+    async fn put(&self, key: &str, content: &[u8]) -> Result<(), AppError>;
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError>;
+}
+
+/// Stores files under a root directory on the local filesystem
+pub struct LocalFileStorage {
+    root: PathBuf,
+}
+
+impl LocalFileStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Rejects keys that could escape the root directory
+    fn path_for(&self, key: &str) -> Result<PathBuf, AppError> {
+        let relative = Path::new(key);
+        let is_safe = !key.is_empty()
+            && relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if !is_safe {
+            return Err(AppError::InternalError(format!(
+                "Invalid storage key '{}'",
+                key
+            )));
+        }
+
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl FileStorage for LocalFileStorage {
+    async fn put(&self, key: &str, content: &[u8]) -> Result<(), AppError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|err| {
+                AppError::InternalError(format!("Failed to create storage directory: {}", err))
+            })?;
+        }
+
+        tokio::fs::write(&path, content)
+            .await
+            .map_err(|err| AppError::InternalError(format!("Failed to store '{}': {}", key, err)))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError> {
+        let path = self.path_for(key)?;
+
+        tokio::fs::read(&path)
+            .await
+            .map_err(|err| match err.kind() {
+                ErrorKind::NotFound => AppError::NotFound(format!("File '{}' not found", key)),
+                _ => AppError::InternalError(format!("Failed to read '{}': {}", key, err)),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// The directory is removed when the `TempDir` is dropped
+    fn storage() -> (TempDir, LocalFileStorage) {
+        let root = TempDir::with_prefix("storage-").unwrap();
+        let storage = LocalFileStorage::new(root.path());
+        (root, storage)
+    }
+
+    #[tokio::test]
+    async fn test_put_and_get_round_trip() {
+        let (_root, storage) = storage();
+
+        storage
+            .put("payments/1/evidence/2", b"receipt")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            storage.get("payments/1/evidence/2").await.unwrap(),
+            b"receipt"
+        );
+        assert!(matches!(
+            storage.get("payments/1/evidence/3").await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_keys_cannot_escape_root() {
+        let (_root, storage) = storage();
+
+        for key in ["../secret", "/etc/passwd", "a/../../b", ""] {
+            assert!(storage.put(key, b"x").await.is_err(), "{}", key);
+            assert!(storage.get(key).await.is_err(), "{}", key);
+        }
+    }
+}
@@ -1,12 +1,16 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Deserializer, Serializer};
 
+/// RFC 3339 with microseconds, the precision timestamps are stored with, and a `+00:00` offset
+pub fn format(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Micros, false)
+}
+
 pub fn serialize<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    let formatted = date.to_rfc3339();
-    serializer.serialize_str(&formatted)
+    serializer.serialize_str(&format(date))
 }
 
 pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
@@ -19,9 +23,39 @@ where
         .map_err(serde::de::Error::custom)
 }
 
+/// Same format for optional timestamps; `None` stays `null`
+pub mod option {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(date: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match date {
+            Some(date) => super::serialize(date, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(serde::de::Error::custom)
+            })
+            .transpose()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
     use serde::{Deserialize, Serialize};
 
     #[derive(Serialize, Deserialize)]
@@ -30,16 +64,53 @@ mod tests {
         timestamp: DateTime<Utc>,
     }
 
+    #[derive(Serialize, Deserialize)]
+    struct OptionalStruct {
+        #[serde(with = "crate::common::time_formater::option")]
+        timestamp: Option<DateTime<Utc>>,
+    }
+
     #[test]
     fn test_serialize_deserialize() {
         let now = Utc::now();
         let test = TestStruct { timestamp: now };
 
         let json = serde_json::to_string(&test).unwrap();
-        assert!(json.contains(&now.to_rfc3339()));
+        assert!(json.contains(&now.to_rfc3339_opts(SecondsFormat::Micros, false)));
 
         let deserialized: TestStruct = serde_json::from_str(&json).unwrap();
 
         assert_eq!(deserialized.timestamp.timestamp(), now.timestamp());
     }
+
+    #[test]
+    fn test_serialize_fixed_precision_and_offset() {
+        let timestamp = Utc
+            .with_ymd_and_hms(2024, 1, 1, 12, 0, 0)
+            .unwrap()
+            .checked_add_signed(chrono::Duration::nanoseconds(123_456_789))
+            .unwrap();
+
+        let json = serde_json::to_value(TestStruct { timestamp }).unwrap();
+
+        assert_eq!(json["timestamp"], "2024-01-01T12:00:00.123456+00:00");
+    }
+
+    #[test]
+    fn test_optional_timestamp() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+
+        let json = serde_json::to_value(OptionalStruct {
+            timestamp: Some(timestamp),
+        })
+        .unwrap();
+        assert_eq!(json["timestamp"], "2024-01-01T12:00:00.000000+00:00");
+        let deserialized: OptionalStruct = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.timestamp, Some(timestamp));
+
+        let json = serde_json::to_value(OptionalStruct { timestamp: None }).unwrap();
+        assert!(json["timestamp"].is_null());
+        let deserialized: OptionalStruct = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.timestamp, None);
+    }
 }
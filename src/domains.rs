@@ -1 +1,7 @@
+pub mod audit;
 pub mod backoffice;
+pub mod disputes;
+pub mod ledger;
+pub mod payments;
+pub mod traders;
+pub mod webhooks;
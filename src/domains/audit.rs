@@ -0,0 +1,33 @@
+mod api {
+    pub mod audit_handler;
+    pub mod router;
+}
+
+pub mod app {
+    pub mod audit_checkpoint_use_case;
+    pub mod get_audit_log_use_case;
+    pub mod verify_audit_chain_use_case;
+}
+
+pub mod domain {
+    pub mod audit_chain;
+    pub mod audit_checkpoint;
+    pub mod audit_entry;
+    pub mod repository;
+}
+
+pub mod dto {
+    pub mod audit_dto;
+}
+
+pub mod infra {
+    pub mod audit_checkpoint_entity;
+    pub mod audit_checkpoint_repository;
+    pub mod audit_log_entity;
+    pub mod audit_log_repository;
+}
+
+pub use api::router::{protected_audit_routes, AuditApiDoc};
+pub use domain::repository::{AuditCheckpointRepository, AuditLogRepository};
+pub use infra::audit_checkpoint_repository::PostgresAuditCheckpointRepository;
+pub use infra::audit_log_repository::PostgresAuditLogRepository;
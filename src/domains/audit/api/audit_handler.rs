@@ -0,0 +1,160 @@
+use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError};
+use crate::domains::audit::dto::audit_dto::{
+    AuditChainReportResponse, AuditCheckpointResponse, AuditEntryResponse,
+    ListAuditCheckpointsQuery, ListAuditLogQuery, UserActivityQuery,
+};
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/audit",
+    params(ListAuditLogQuery),
+    responses(
+        (status = 200, description = "Page of audit entries, newest first", body = inline(ApiResponse<Vec<AuditEntryResponse>>)),
+        (status = 400, description = "Limit exceeds 100, the time range is empty or a filter is malformed"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Audit",
+    summary = "List audit log",
+    description = "Lists changes to backoffice records filtered by actor, entity and time range. Requires `audit:read`."
+)]
+pub async fn list_audit_log(
+    Extension(state): Extension<Arc<AppState>>,
+    Query(params): Query<ListAuditLogQuery>,
+) -> Result<Json<ApiResponse<Vec<AuditEntryResponse>>>, AppError> {
+    let (limit, offset) = (params.limit, params.offset);
+    let entries = state
+        .audit_get_use_case
+        .list(params.into(), limit, offset)
+        .await?;
+
+    let response: Vec<AuditEntryResponse> =
+        entries.into_iter().map(AuditEntryResponse::from).collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/user/{id}/activity",
+    params(
+        ("id" = Uuid, Path, description = "User whose changes are listed"),
+        UserActivityQuery
+    ),
+    responses(
+        (status = 200, description = "Page of the user's changes, newest first", body = inline(ApiResponse<Vec<AuditEntryResponse>>)),
+        (status = 400, description = "Limit exceeds 100"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Audit",
+    summary = "User activity feed",
+    description = "Lists the changes a backoffice user made. Requires `audit:read`."
+)]
+pub async fn user_activity(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    Query(params): Query<UserActivityQuery>,
+) -> Result<Json<ApiResponse<Vec<AuditEntryResponse>>>, AppError> {
+    let entries = state
+        .audit_get_use_case
+        .user_activity(user_id, params.limit, params.offset)
+        .await?;
+
+    let response: Vec<AuditEntryResponse> =
+        entries.into_iter().map(AuditEntryResponse::from).collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/audit/verify",
+    responses(
+        (status = 200, description = "Result of walking the hash chain; `valid` is false and `broken_link` names the first mismatch when the log was edited", body = inline(ApiResponse<AuditChainReportResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Audit",
+    summary = "Verify audit log integrity",
+    description = "Recomputes the hash of every audit entry in order, checks each against the previous entry's hash and the stored signed checkpoints, and reports the first broken link. Requires `audit:read`."
+)]
+pub async fn verify_audit_chain(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<ApiResponse<AuditChainReportResponse>>, AppError> {
+    let report = state.audit_verify_use_case.execute().await?;
+
+    Ok(Json(ApiResponse::success(report.into())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/audit/checkpoints",
+    params(ListAuditCheckpointsQuery),
+    responses(
+        (status = 200, description = "Page of signed checkpoints, newest first", body = inline(ApiResponse<Vec<AuditCheckpointResponse>>)),
+        (status = 400, description = "Limit exceeds 100"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Audit",
+    summary = "List audit checkpoints",
+    description = "Lists the signed checkpoints (entry count and head hash) taken periodically, for export to storage outside the service. Requires `audit:read`."
+)]
+pub async fn list_audit_checkpoints(
+    Extension(state): Extension<Arc<AppState>>,
+    Query(params): Query<ListAuditCheckpointsQuery>,
+) -> Result<Json<ApiResponse<Vec<AuditCheckpointResponse>>>, AppError> {
+    let checkpoints = state
+        .audit_checkpoint_use_case
+        .list(params.limit, params.offset)
+        .await?;
+
+    let response: Vec<AuditCheckpointResponse> = checkpoints
+        .into_iter()
+        .map(AuditCheckpointResponse::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/audit/checkpoints",
+    responses(
+        (status = 200, description = "Checkpoint of the current head; the latest one when nothing was logged since", body = inline(ApiResponse<AuditCheckpointResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Audit",
+    summary = "Create audit checkpoint",
+    description = "Signs the current entry count and head hash without waiting for the periodic job. Requires `audit:read`."
+)]
+pub async fn create_audit_checkpoint(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<ApiResponse<AuditCheckpointResponse>>, AppError> {
+    let checkpoint = state.audit_checkpoint_use_case.create().await?;
+
+    Ok(Json(ApiResponse::success(checkpoint.into())))
+}
@@ -0,0 +1,57 @@
+use axum::{middleware, routing::get, Router};
+
+use crate::{
+    common::{jwt::SecurityAddon, middleware::require_permission},
+    domains::{
+        audit::{
+            domain::{
+                audit_chain::{BrokenLink, ChainHead},
+                audit_entry::AuditAction,
+            },
+            dto::audit_dto::{
+                AuditChainReportResponse, AuditCheckpointResponse, AuditEntryResponse,
+            },
+        },
+        backoffice::role::permission::AUDIT_READ,
+    },
+};
+
+use utoipa::OpenApi;
+
+use super::audit_handler;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::audit_handler::list_audit_log,
+        super::audit_handler::user_activity,
+        super::audit_handler::verify_audit_chain,
+        super::audit_handler::list_audit_checkpoints,
+        super::audit_handler::create_audit_checkpoint,
+    ),
+    components(schemas(
+        AuditEntryResponse,
+        AuditAction,
+        AuditChainReportResponse,
+        AuditCheckpointResponse,
+        ChainHead,
+        BrokenLink
+    )),
+    tags(
+        (name = "Audit", description = "Who changed which backoffice record, when and from where, in a tamper-evident hash chain")
+    ),
+    modifiers(&SecurityAddon)
+)]
+pub struct AuditApiDoc;
+
+pub fn protected_audit_routes() -> Router {
+    Router::new()
+        .route("/audit", get(audit_handler::list_audit_log))
+        .route("/audit/verify", get(audit_handler::verify_audit_chain))
+        .route(
+            "/audit/checkpoints",
+            get(audit_handler::list_audit_checkpoints).post(audit_handler::create_audit_checkpoint),
+        )
+        .route("/user/{id}/activity", get(audit_handler::user_activity))
+        .route_layer(middleware::from_fn(require_permission(AUDIT_READ)))
+}
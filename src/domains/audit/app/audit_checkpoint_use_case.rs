@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::{
+    common::error::AppError,
+    domains::audit::domain::{
+        audit_checkpoint::AuditCheckpoint,
+        repository::{AuditCheckpointRepository, AuditLogRepository},
+    },
+};
+
+pub struct AuditCheckpointUseCase {
+    audit_log_repository: Arc<dyn AuditLogRepository>,
+    checkpoint_repository: Arc<dyn AuditCheckpointRepository>,
+    signing_key: String,
+}
+
+impl AuditCheckpointUseCase {
+    pub fn new(
+        audit_log_repository: Arc<dyn AuditLogRepository>,
+        checkpoint_repository: Arc<dyn AuditCheckpointRepository>,
+        signing_key: &str,
+    ) -> Self {
+        Self {
+            audit_log_repository,
+            checkpoint_repository,
+            signing_key: signing_key.to_string(),
+        }
+    }
+
+    /// Signs the current head of the log. While nothing was appended since the latest
+    /// checkpoint, that one is returned instead
+    pub async fn create(&self) -> Result<AuditCheckpoint, AppError> {
+        let head = self.audit_log_repository.head().await?;
+
+        if let Some(latest) = self.checkpoint_repository.list(1, 0).await?.pop() {
+            if latest.sequence == head.sequence && latest.head_hash == head.hash {
+                return Ok(latest);
+            }
+        }
+
+        let checkpoint = AuditCheckpoint::sign(head, Utc::now(), &self.signing_key);
+        let checkpoint = self.checkpoint_repository.create(checkpoint).await?;
+
+        // Log shippers keep a copy outside the database
+        tracing::info!(
+            checkpoint_id = %checkpoint.id,
+            sequence = checkpoint.sequence,
+            head_hash = %checkpoint.head_hash,
+            signature = %checkpoint.signature,
+            "Audit checkpoint created"
+        );
+
+        Ok(checkpoint)
+    }
+
+    /// Newest first
+    pub async fn list(&self, limit: i64, offset: i64) -> Result<Vec<AuditCheckpoint>, AppError> {
+        if limit > 100 {
+            return Err(AppError::ValidationError(
+                "Limit cannot exceed 100".to_string(),
+            ));
+        }
+
+        self.checkpoint_repository.list(limit, offset).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::audit::domain::{
+        audit_chain::ChainHead,
+        repository::{MockAuditCheckpointRepository, MockAuditLogRepository},
+    };
+
+    fn head(sequence: i64) -> ChainHead {
+        ChainHead {
+            sequence,
+            hash: format!("{:064x}", sequence),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_signs_current_head() {
+        let mut audit_log = MockAuditLogRepository::new();
+        audit_log.expect_head().returning(|| Ok(head(7)));
+        let mut checkpoints = MockAuditCheckpointRepository::new();
+        checkpoints
+            .expect_list()
+            .returning(|_, _| Ok(vec![AuditCheckpoint::sign(head(5), Utc::now(), "key")]));
+        checkpoints.expect_create().times(1).returning(Ok);
+
+        let checkpoint =
+            AuditCheckpointUseCase::new(Arc::new(audit_log), Arc::new(checkpoints), "key")
+                .create()
+                .await
+                .unwrap();
+
+        assert_eq!(checkpoint.sequence, 7);
+        assert_eq!(checkpoint.head_hash, head(7).hash);
+        assert!(checkpoint.verify("key"));
+    }
+
+    #[tokio::test]
+    async fn test_create_reuses_checkpoint_of_unchanged_head() {
+        let latest = AuditCheckpoint::sign(head(7), Utc::now(), "key");
+        let mut audit_log = MockAuditLogRepository::new();
+        audit_log.expect_head().returning(|| Ok(head(7)));
+        let mut checkpoints = MockAuditCheckpointRepository::new();
+        let stored = latest.clone();
+        checkpoints
+            .expect_list()
+            .returning(move |_, _| Ok(vec![stored.clone()]));
+        checkpoints.expect_create().never();
+
+        let checkpoint =
+            AuditCheckpointUseCase::new(Arc::new(audit_log), Arc::new(checkpoints), "key")
+                .create()
+                .await
+                .unwrap();
+
+        assert_eq!(checkpoint, latest);
+    }
+}
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::audit::domain::{
+        audit_entry::{AuditEntry, AuditFilter},
+        repository::AuditLogRepository,
+    },
+};
+
+pub struct GetAuditLogUseCase {
+    audit_log_repository: Arc<dyn AuditLogRepository>,
+}
+
+impl GetAuditLogUseCase {
+    pub fn new(audit_log_repository: Arc<dyn AuditLogRepository>) -> Self {
+        Self {
+            audit_log_repository,
+        }
+    }
+
+    pub async fn list(
+        &self,
+        filter: AuditFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        tracing::debug!("Listing audit log with {:?}", filter);
+
+        if limit > 100 {
+            return Err(AppError::ValidationError(
+                "Limit cannot exceed 100".to_string(),
+            ));
+        }
+        if let (Some(from), Some(to)) = (filter.from, filter.to) {
+            if from >= to {
+                return Err(AppError::ValidationError(
+                    "'from' must be before 'to'".to_string(),
+                ));
+            }
+        }
+
+        self.audit_log_repository.list(filter, limit, offset).await
+    }
+
+    /// Changes made by the user, newest first. Users deleted since keep their history
+    pub async fn user_activity(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        self.list(
+            AuditFilter {
+                actor_id: Some(user_id),
+                ..AuditFilter::default()
+            },
+            limit,
+            offset,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::audit::domain::repository::MockAuditLogRepository;
+    use chrono::{Duration, Utc};
+
+    #[tokio::test]
+    async fn test_list_rejects_invalid_page_and_range() {
+        let mut repository = MockAuditLogRepository::new();
+        repository.expect_list().never();
+        let use_case = GetAuditLogUseCase::new(Arc::new(repository));
+
+        let result = use_case.list(AuditFilter::default(), 101, 0).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let now = Utc::now();
+        let filter = AuditFilter {
+            from: Some(now),
+            to: Some(now - Duration::hours(1)),
+            ..AuditFilter::default()
+        };
+        let result = use_case.list(filter, 20, 0).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_user_activity_filters_by_actor() {
+        let user_id = Uuid::new_v4();
+        let mut repository = MockAuditLogRepository::new();
+        repository
+            .expect_list()
+            .withf(move |filter, limit, offset| {
+                filter.actor_id == Some(user_id) && *limit == 20 && *offset == 0
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+
+        let entries = GetAuditLogUseCase::new(Arc::new(repository))
+            .user_activity(user_id, 20, 0)
+            .await
+            .unwrap();
+
+        assert!(entries.is_empty());
+    }
+}
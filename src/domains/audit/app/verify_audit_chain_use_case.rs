@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use crate::{
+    common::error::AppError,
+    domains::audit::domain::{
+        audit_chain::{BrokenLink, ChainHead, ChainVerifier},
+        repository::{AuditCheckpointRepository, AuditLogRepository},
+    },
+};
+
+/// Entries read from the log per query while walking the chain
+const VERIFY_BATCH_SIZE: i64 = 500;
+
+/// Outcome of walking the audit log from the first entry to the last
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditChainReport {
+    /// Entries whose links were intact
+    pub entries_checked: i64,
+    pub checkpoints_checked: usize,
+    /// Last entry verified before the walk ended
+    pub head: ChainHead,
+    /// Unset when the whole log is intact
+    pub broken_link: Option<BrokenLink>,
+}
+
+pub struct VerifyAuditChainUseCase {
+    audit_log_repository: Arc<dyn AuditLogRepository>,
+    checkpoint_repository: Arc<dyn AuditCheckpointRepository>,
+    signing_key: String,
+}
+
+impl VerifyAuditChainUseCase {
+    pub fn new(
+        audit_log_repository: Arc<dyn AuditLogRepository>,
+        checkpoint_repository: Arc<dyn AuditCheckpointRepository>,
+        signing_key: &str,
+    ) -> Self {
+        Self {
+            audit_log_repository,
+            checkpoint_repository,
+            signing_key: signing_key.to_string(),
+        }
+    }
+
+    /// Checks every entry against its predecessor and the stored checkpoints, stopping at
+    /// the first broken link
+    pub async fn execute(&self) -> Result<AuditChainReport, AppError> {
+        let checkpoints = self.checkpoint_repository.find_all().await?;
+        let checkpoints_checked = checkpoints.len();
+
+        // A forged checkpoint would vouch for a forged chain
+        if let Some(forged) = checkpoints
+            .iter()
+            .find(|checkpoint| !checkpoint.verify(&self.signing_key))
+        {
+            return Ok(AuditChainReport {
+                entries_checked: 0,
+                checkpoints_checked,
+                head: ChainHead::genesis(),
+                broken_link: Some(BrokenLink {
+                    sequence: forged.sequence,
+                    entry_id: None,
+                    reason: format!("Checkpoint {} has an invalid signature", forged.id),
+                }),
+            });
+        }
+
+        let mut verifier = match ChainVerifier::new(checkpoints) {
+            Ok(verifier) => verifier,
+            Err(broken_link) => {
+                return Ok(AuditChainReport {
+                    entries_checked: 0,
+                    checkpoints_checked,
+                    head: ChainHead::genesis(),
+                    broken_link: Some(broken_link),
+                })
+            }
+        };
+
+        loop {
+            let batch = self
+                .audit_log_repository
+                .list_chain(verifier.head().sequence, VERIFY_BATCH_SIZE)
+                .await?;
+            let done = (batch.len() as i64) < VERIFY_BATCH_SIZE;
+
+            for entry in &batch {
+                if let Err(broken_link) = verifier.check(entry) {
+                    tracing::warn!(
+                        "Audit chain broken at entry {}: {}",
+                        broken_link.sequence,
+                        broken_link.reason
+                    );
+                    return Ok(AuditChainReport {
+                        entries_checked: verifier.checked(),
+                        checkpoints_checked,
+                        head: verifier.head().clone(),
+                        broken_link: Some(broken_link),
+                    });
+                }
+            }
+
+            if done {
+                break;
+            }
+        }
+
+        let entries_checked = verifier.checked();
+        let head = verifier.head().clone();
+        let broken_link = verifier.finish().err();
+        if let Some(broken_link) = &broken_link {
+            tracing::warn!("Audit chain cut short: {}", broken_link.reason);
+        }
+
+        Ok(AuditChainReport {
+            entries_checked,
+            checkpoints_checked,
+            head,
+            broken_link,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::audit::domain::{
+        audit_checkpoint::AuditCheckpoint,
+        audit_entry::{AuditAction, AuditContext, AuditEntry, USER_ENTITY},
+        repository::{MockAuditCheckpointRepository, MockAuditLogRepository},
+    };
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn linked_entry(head: &ChainHead) -> AuditEntry {
+        let mut entry = AuditEntry::new(
+            &AuditContext::default(),
+            AuditAction::Create,
+            USER_ENTITY,
+            Uuid::new_v4(),
+            None,
+            None,
+        );
+        entry.link(head);
+        entry
+    }
+
+    #[tokio::test]
+    async fn test_execute_walks_chain_up_to_checkpoint() {
+        let first = linked_entry(&ChainHead::genesis());
+        let second = linked_entry(&ChainHead {
+            sequence: 1,
+            hash: first.hash.clone(),
+        });
+        let head = ChainHead {
+            sequence: 2,
+            hash: second.hash.clone(),
+        };
+        let checkpoint = AuditCheckpoint::sign(head.clone(), Utc::now(), "key");
+
+        let mut audit_log = MockAuditLogRepository::new();
+        let entries = vec![first, second];
+        audit_log
+            .expect_list_chain()
+            .withf(|after, _| *after == 0)
+            .times(1)
+            .returning(move |_, _| Ok(entries.clone()));
+        let mut checkpoints = MockAuditCheckpointRepository::new();
+        checkpoints
+            .expect_find_all()
+            .returning(move || Ok(vec![checkpoint.clone()]));
+
+        let report =
+            VerifyAuditChainUseCase::new(Arc::new(audit_log), Arc::new(checkpoints), "key")
+                .execute()
+                .await
+                .unwrap();
+
+        assert_eq!(report.broken_link, None);
+        assert_eq!(report.entries_checked, 2);
+        assert_eq!(report.checkpoints_checked, 1);
+        assert_eq!(report.head, head);
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_forged_checkpoint() {
+        let forged = AuditCheckpoint::sign(ChainHead::genesis(), Utc::now(), "other-key");
+        let forged_id = forged.id;
+
+        let mut audit_log = MockAuditLogRepository::new();
+        audit_log.expect_list_chain().never();
+        let mut checkpoints = MockAuditCheckpointRepository::new();
+        checkpoints
+            .expect_find_all()
+            .returning(move || Ok(vec![forged.clone()]));
+
+        let report =
+            VerifyAuditChainUseCase::new(Arc::new(audit_log), Arc::new(checkpoints), "key")
+                .execute()
+                .await
+                .unwrap();
+
+        let broken_link = report.broken_link.unwrap();
+        assert_eq!(
+            broken_link.reason,
+            format!("Checkpoint {} has an invalid signature", forged_id)
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use super::audit_chain::ChainHead;
+use crate::common::error::AppError;
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::{fmt, str::FromStr};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// `entity_type` of backoffice users
+pub const USER_ENTITY: &str = "user";
+
+/// `entity_type` of roles, including their permissions
+pub const ROLE_ENTITY: &str = "role";
+
+/// `entity_type` of merchants
+pub const MERCHANT_ENTITY: &str = "merchant";
+
+/// `entity_type` of merchant sites
+pub const SITE_ENTITY: &str = "site";
+
+/// `entity_type` of site API key pairs
+pub const SITE_CREDENTIALS_ENTITY: &str = "site_credentials";
+
+/// `entity_type` of P2P traders
+pub const TRADER_ENTITY: &str = "trader";
+
+/// `entity_type` of trader requisites; the card or account number is never recorded
+pub const REQUISITE_ENTITY: &str = "requisite";
+
+/// `entity_type` of ledger entries posted from the backoffice, such as deposits and payouts
+pub const JOURNAL_ENTRY_ENTITY: &str = "journal_entry";
+
+/// `entity_type` of payment intents
+pub const PAYMENT_ENTITY: &str = "payment";
+
+/// `entity_type` of disputes
+pub const DISPUTE_ENTITY: &str = "dispute";
+
+/// `entity_type` of dispute comments
+pub const DISPUTE_COMMENT_ENTITY: &str = "dispute_comment";
+
+/// `entity_type` of dispute attachments
+pub const DISPUTE_ATTACHMENT_ENTITY: &str = "dispute_attachment";
+
+/// `entity_type` of webhook deliveries
+pub const WEBHOOK_DELIVERY_ENTITY: &str = "webhook_delivery";
+
+/// `entity_type` of a user's login sessions; the entity ID is the user's
+pub const SESSION_ENTITY: &str = "session";
+
+/// Who makes a change and from where; added to requests by the `audit_context` middleware
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditContext {
+    pub actor_id: Option<Uuid>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub request_id: Option<String>,
+}
+
+/// Change to a backoffice record, written in the same transaction as the change itself
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    /// Unset for changes made by the system
+    pub actor_id: Option<Uuid>,
+    pub action: AuditAction,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    /// Changed fields as `{"field": {"before": .., "after": ..}}`
+    pub changes: Value,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub request_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Position in the chain, starting at 1; assigned when the entry is appended
+    pub sequence: i64,
+    /// `hash` of the entry before this one
+    pub prev_hash: String,
+    /// Hex SHA-256 over the content of this entry and `prev_hash`
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Entry for a change of the entity from `before` to `after`; either is unset
+    /// when the entity is created or deleted
+    pub fn new(
+        context: &AuditContext,
+        action: AuditAction,
+        entity_type: &str,
+        entity_id: Uuid,
+        before: Option<&Value>,
+        after: Option<&Value>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            actor_id: context.actor_id,
+            action,
+            entity_type: entity_type.to_string(),
+            entity_id,
+            changes: Self::diff(before, after),
+            ip_address: context.ip_address.clone(),
+            user_agent: context.user_agent.clone(),
+            request_id: context.request_id.clone(),
+            // Stored with microsecond precision, which the hash has to survive
+            created_at: Utc::now().trunc_subsecs(6),
+            sequence: 0,
+            prev_hash: String::new(),
+            hash: String::new(),
+        }
+    }
+
+    /// Places the entry right after `head` and seals it with its hash
+    pub fn link(&mut self, head: &ChainHead) {
+        self.sequence = head.sequence + 1;
+        self.prev_hash = head.hash.clone();
+        self.hash = self.compute_hash();
+    }
+
+    /// Hash the entry should carry given its current content and `prev_hash`
+    pub fn compute_hash(&self) -> String {
+        hex::encode(Sha256::digest(self.hash_payload().as_bytes()))
+    }
+
+    /// Canonical content: the fields joined by newlines, unset ones as empty strings and
+    /// `created_at` in Unix microseconds. The hash chain migration builds the same string
+    fn hash_payload(&self) -> String {
+        let optional = |value: Option<String>| value.unwrap_or_default();
+
+        [
+            self.sequence.to_string(),
+            self.prev_hash.clone(),
+            self.id.to_string(),
+            optional(self.actor_id.map(|id| id.to_string())),
+            self.action.as_str().to_string(),
+            self.entity_type.clone(),
+            self.entity_id.to_string(),
+            self.changes.to_string(),
+            optional(self.ip_address.clone()),
+            optional(self.user_agent.clone()),
+            optional(self.request_id.clone()),
+            self.created_at.timestamp_micros().to_string(),
+        ]
+        .join("\n")
+    }
+
+    /// Fields of two JSON object snapshots that differ, in field name order
+    pub fn diff(before: Option<&Value>, after: Option<&Value>) -> Value {
+        let empty = Map::new();
+        let before = before.and_then(Value::as_object).unwrap_or(&empty);
+        let after = after.and_then(Value::as_object).unwrap_or(&empty);
+
+        let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+        fields.sort();
+        fields.dedup();
+
+        // A missing field reads as null
+        let value = |snapshot: &Map<String, Value>, field: &str| {
+            snapshot.get(field).cloned().unwrap_or(Value::Null)
+        };
+        let changes: Map<String, Value> = fields
+            .into_iter()
+            .map(|field| (field, value(before, field), value(after, field)))
+            .filter(|(_, before, after)| before != after)
+            .map(|(field, before, after)| {
+                (field.clone(), json!({ "before": before, "after": after }))
+            })
+            .collect();
+
+        Value::Object(changes)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+}
+
+impl AuditAction {
+    /// Value stored in the `action` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+        }
+    }
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AuditAction {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "create" => Ok(AuditAction::Create),
+            "update" => Ok(AuditAction::Update),
+            "delete" => Ok(AuditAction::Delete),
+            other => Err(AppError::InternalError(format!(
+                "Unknown audit action '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Criteria for listing the audit log; `from` is inclusive, `to` exclusive
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub actor_id: Option<Uuid>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<Uuid>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.actor_id
+            .is_none_or(|actor_id| entry.actor_id == Some(actor_id))
+            && self
+                .entity_type
+                .as_ref()
+                .is_none_or(|entity_type| &entry.entity_type == entity_type)
+            && self
+                .entity_id
+                .is_none_or(|entity_id| entry.entity_id == entity_id)
+            && self.from.is_none_or(|from| entry.created_at >= from)
+            && self.to.is_none_or(|to| entry.created_at < to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_keeps_changed_fields_only() {
+        let before = json!({ "username": "dave", "email": "dave@example.com", "is_active": true });
+        let after = json!({ "username": "dave", "email": "d@example.com", "is_active": true });
+
+        assert_eq!(
+            AuditEntry::diff(Some(&before), Some(&after)),
+            json!({ "email": { "before": "dave@example.com", "after": "d@example.com" } })
+        );
+        assert_eq!(AuditEntry::diff(Some(&before), Some(&before)), json!({}));
+    }
+
+    #[test]
+    fn test_diff_of_created_entity_lists_every_field() {
+        let after = json!({ "username": "dave", "role_id": null });
+
+        assert_eq!(
+            AuditEntry::diff(None, Some(&after)),
+            json!({ "username": { "before": null, "after": "dave" } })
+        );
+    }
+
+    #[test]
+    fn test_filter_time_range() {
+        let context = AuditContext {
+            actor_id: Some(Uuid::new_v4()),
+            ..AuditContext::default()
+        };
+        let entry = AuditEntry::new(
+            &context,
+            AuditAction::Delete,
+            USER_ENTITY,
+            Uuid::new_v4(),
+            None,
+            None,
+        );
+
+        let filter = AuditFilter {
+            actor_id: context.actor_id,
+            entity_type: Some(USER_ENTITY.to_string()),
+            from: Some(entry.created_at),
+            to: Some(entry.created_at + chrono::Duration::seconds(1)),
+            ..AuditFilter::default()
+        };
+        assert!(filter.matches(&entry));
+
+        let filter = AuditFilter {
+            to: Some(entry.created_at),
+            ..filter
+        };
+        assert!(!filter.matches(&entry));
+    }
+
+    #[test]
+    fn test_link_seals_entry_after_head() {
+        let mut entry = AuditEntry::new(
+            &AuditContext::default(),
+            AuditAction::Create,
+            USER_ENTITY,
+            Uuid::new_v4(),
+            None,
+            Some(&json!({ "username": "dave" })),
+        );
+        let head = ChainHead {
+            sequence: 4,
+            hash: "ab".repeat(32),
+        };
+
+        entry.link(&head);
+
+        assert_eq!(entry.sequence, 5);
+        assert_eq!(entry.prev_hash, head.hash);
+        assert_eq!(entry.hash, entry.compute_hash());
+
+        let mut edited = entry.clone();
+        edited.changes = json!({ "username": { "before": null, "after": "eve" } });
+        assert_ne!(edited.compute_hash(), entry.hash);
+    }
+}
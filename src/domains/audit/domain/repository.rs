@@ -0,0 +1,68 @@
+use super::{
+    audit_chain::ChainHead,
+    audit_checkpoint::AuditCheckpoint,
+    audit_entry::{AuditEntry, AuditFilter},
+};
+use crate::common::error::AppError;
+use async_trait::async_trait;
+
+/// Read side of the audit log; entries are written by the repositories of the audited
+/// records, inside their own transactions
+#[async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    /// Newest first
+    async fn list(
+        &self,
+        filter: AuditFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditEntry>, AppError>;
+
+    /// Last entry of the chain, the genesis head while the log is empty
+    async fn head(&self) -> Result<ChainHead, AppError>;
+
+    /// Entries after `after_sequence` in chain order
+    async fn list_chain(
+        &self,
+        after_sequence: i64,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>, AppError>;
+}
+
+#[async_trait]
+pub trait AuditCheckpointRepository: Send + Sync {
+    async fn create(&self, checkpoint: AuditCheckpoint) -> Result<AuditCheckpoint, AppError>;
+
+    /// Newest first
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<AuditCheckpoint>, AppError>;
+
+    /// Every checkpoint in sequence order
+    async fn find_all(&self) -> Result<Vec<AuditCheckpoint>, AppError>;
+}
+
+#[cfg(test)]
+use mockall::mock;
+
+#[cfg(test)]
+mock! {
+    pub AuditLogRepository {}
+
+    #[async_trait]
+    impl AuditLogRepository for AuditLogRepository {
+        async fn list(&self, filter: AuditFilter, limit: i64, offset: i64) -> Result<Vec<AuditEntry>, AppError>;
+        async fn head(&self) -> Result<ChainHead, AppError>;
+        async fn list_chain(&self, after_sequence: i64, limit: i64) -> Result<Vec<AuditEntry>, AppError>;
+    }
+}
+
+#[cfg(test)]
+mock! {
+    pub AuditCheckpointRepository {}
+
+    #[async_trait]
+    impl AuditCheckpointRepository for AuditCheckpointRepository {
+        async fn create(&self, checkpoint: AuditCheckpoint) -> Result<AuditCheckpoint, AppError>;
+        async fn list(&self, limit: i64, offset: i64) -> Result<Vec<AuditCheckpoint>, AppError>;
+        async fn find_all(&self) -> Result<Vec<AuditCheckpoint>, AppError>;
+    }
+}
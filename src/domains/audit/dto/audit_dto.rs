@@ -0,0 +1,200 @@
+use crate::domains::audit::app::verify_audit_chain_use_case::AuditChainReport;
+use crate::domains::audit::domain::{
+    audit_chain::{BrokenLink, ChainHead},
+    audit_checkpoint::AuditCheckpoint,
+    audit_entry::{AuditAction, AuditEntry, AuditFilter},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditEntryResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    /// Backoffice user who made the change; unset for changes made by the system
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub actor_id: Option<String>,
+
+    pub action: AuditAction,
+
+    #[schema(example = "user")]
+    pub entity_type: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub entity_id: String,
+
+    /// Changed fields with their values before and after
+    #[schema(value_type = Object, example = json!({"email": {"before": "old@example.com", "after": "new@example.com"}}))]
+    pub changes: Value,
+
+    #[schema(example = "203.0.113.7")]
+    pub ip_address: Option<String>,
+
+    #[schema(example = "Mozilla/5.0")]
+    pub user_agent: Option<String>,
+
+    /// `X-Request-Id` of the request that made the change
+    #[schema(example = "0b6b7b7e-3f4c-4c55-9f8e-8f1f0d8e2a10")]
+    pub request_id: Option<String>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    /// Position in the hash chain, starting at 1
+    #[schema(example = 42)]
+    pub sequence: i64,
+
+    /// Hash of the previous entry
+    #[schema(example = "0000000000000000000000000000000000000000000000000000000000000000")]
+    pub prev_hash: String,
+
+    /// Hex SHA-256 over this entry and `prev_hash`
+    #[schema(example = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08")]
+    pub hash: String,
+}
+
+impl From<AuditEntry> for AuditEntryResponse {
+    fn from(entry: AuditEntry) -> Self {
+        Self {
+            id: entry.id.to_string(),
+            actor_id: entry.actor_id.map(|id| id.to_string()),
+            action: entry.action,
+            entity_type: entry.entity_type,
+            entity_id: entry.entity_id.to_string(),
+            changes: entry.changes,
+            ip_address: entry.ip_address,
+            user_agent: entry.user_agent,
+            request_id: entry.request_id,
+            created_at: entry.created_at,
+            sequence: entry.sequence,
+            prev_hash: entry.prev_hash,
+            hash: entry.hash,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListAuditLogQuery {
+    /// Page size, at most 100
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    pub actor_id: Option<Uuid>,
+    #[param(example = "user")]
+    pub entity_type: Option<String>,
+    pub entity_id: Option<Uuid>,
+    /// Entries at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Entries before this time
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl From<ListAuditLogQuery> for AuditFilter {
+    fn from(query: ListAuditLogQuery) -> Self {
+        Self {
+            actor_id: query.actor_id,
+            entity_type: query.entity_type,
+            entity_id: query.entity_id,
+            from: query.from,
+            to: query.to,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UserActivityQuery {
+    /// Page size, at most 100
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditChainReportResponse {
+    /// Whether every entry and checkpoint matched
+    #[schema(example = true)]
+    pub valid: bool,
+
+    /// Entries verified before the walk ended
+    #[schema(example = 42)]
+    pub entries_checked: i64,
+
+    #[schema(example = 3)]
+    pub checkpoints_checked: usize,
+
+    /// Last verified entry
+    pub head: ChainHead,
+
+    /// First entry or checkpoint that does not match; unset when the log is intact
+    pub broken_link: Option<BrokenLink>,
+}
+
+impl From<AuditChainReport> for AuditChainReportResponse {
+    fn from(report: AuditChainReport) -> Self {
+        Self {
+            valid: report.broken_link.is_none(),
+            entries_checked: report.entries_checked,
+            checkpoints_checked: report.checkpoints_checked,
+            head: report.head,
+            broken_link: report.broken_link,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditCheckpointResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    /// Number of entries covered
+    #[schema(example = 42)]
+    pub sequence: i64,
+
+    /// Hash of the entry at `sequence`
+    #[schema(example = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08")]
+    pub head_hash: String,
+
+    /// Hex HMAC-SHA256 keyed with the checkpoint signing key over
+    /// `{sequence}\n{head_hash}\n{created_at as Unix seconds}`
+    #[schema(example = "5d41402abc4b2a76b9719d911017c592ae2f3b6f1c5e4a7d8e9f0a1b2c3d4e5f")]
+    pub signature: String,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AuditCheckpoint> for AuditCheckpointResponse {
+    fn from(checkpoint: AuditCheckpoint) -> Self {
+        Self {
+            id: checkpoint.id.to_string(),
+            sequence: checkpoint.sequence,
+            head_hash: checkpoint.head_hash,
+            signature: checkpoint.signature,
+            created_at: checkpoint.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListAuditCheckpointsQuery {
+    /// Page size, at most 100
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    20
+}
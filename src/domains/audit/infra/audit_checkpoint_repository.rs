@@ -0,0 +1,70 @@
+use super::audit_checkpoint_entity::{self, Entity as AuditCheckpointEntity};
+use crate::common::error::AppError;
+use crate::domains::audit::domain::{
+    audit_checkpoint::AuditCheckpoint, repository::AuditCheckpointRepository,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, QueryOrder, QuerySelect, Set};
+
+pub struct PostgresAuditCheckpointRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresAuditCheckpointRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(model: audit_checkpoint_entity::Model) -> AuditCheckpoint {
+        AuditCheckpoint {
+            id: model.id,
+            sequence: model.sequence,
+            head_hash: model.head_hash,
+            signature: model.signature,
+            created_at: model.created_at.with_timezone(&Utc),
+        }
+    }
+
+    fn domain_to_active_model(checkpoint: AuditCheckpoint) -> audit_checkpoint_entity::ActiveModel {
+        audit_checkpoint_entity::ActiveModel {
+            id: Set(checkpoint.id),
+            sequence: Set(checkpoint.sequence),
+            head_hash: Set(checkpoint.head_hash),
+            signature: Set(checkpoint.signature),
+            created_at: Set(checkpoint.created_at.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditCheckpointRepository for PostgresAuditCheckpointRepository {
+    async fn create(&self, checkpoint: AuditCheckpoint) -> Result<AuditCheckpoint, AppError> {
+        let model = Self::domain_to_active_model(checkpoint)
+            .insert(&self.db)
+            .await?;
+
+        Ok(Self::entity_to_domain(model))
+    }
+
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<AuditCheckpoint>, AppError> {
+        let models = AuditCheckpointEntity::find()
+            .order_by_desc(audit_checkpoint_entity::Column::Sequence)
+            .order_by_desc(audit_checkpoint_entity::Column::CreatedAt)
+            .limit(limit as u64)
+            .offset(offset as u64)
+            .all(&self.db)
+            .await?;
+
+        Ok(models.into_iter().map(Self::entity_to_domain).collect())
+    }
+
+    async fn find_all(&self) -> Result<Vec<AuditCheckpoint>, AppError> {
+        let models = AuditCheckpointEntity::find()
+            .order_by_asc(audit_checkpoint_entity::Column::Sequence)
+            .all(&self.db)
+            .await?;
+
+        Ok(models.into_iter().map(Self::entity_to_domain).collect())
+    }
+}
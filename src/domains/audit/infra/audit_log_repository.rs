@@ -0,0 +1,164 @@
+use super::audit_log_entity::{self, Entity as AuditLogEntity};
+use crate::common::error::AppError;
+use crate::domains::audit::domain::{
+    audit_chain::ChainHead,
+    audit_entry::{AuditEntry, AuditFilter},
+    repository::AuditLogRepository,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseBackend, DatabaseConnection,
+    EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set, Statement,
+};
+
+/// Key of the transaction-level advisory lock that serializes appends to the chain
+const CHAIN_LOCK_KEY: i64 = 0x6175_6469_745f_6c6f;
+
+pub struct PostgresAuditLogRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresAuditLogRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Links the entry to the head of the chain and writes it on `conn`; repositories of
+    /// audited records pass their transaction. Appends wait for each other until commit, so
+    /// two entries never claim the same place in the chain
+    pub(crate) async fn append<C: ConnectionTrait>(
+        conn: &C,
+        mut entry: AuditEntry,
+    ) -> Result<(), AppError> {
+        conn.execute(Statement::from_string(
+            DatabaseBackend::Postgres,
+            format!("SELECT pg_advisory_xact_lock({})", CHAIN_LOCK_KEY),
+        ))
+        .await?;
+
+        entry.link(&Self::head_on(conn).await?);
+        Self::domain_to_active_model(entry)?.insert(conn).await?;
+
+        Ok(())
+    }
+
+    async fn head_on<C: ConnectionTrait>(conn: &C) -> Result<ChainHead, AppError> {
+        let head = AuditLogEntity::find()
+            .order_by_desc(audit_log_entity::Column::Sequence)
+            .one(conn)
+            .await?
+            .map(|model| ChainHead {
+                sequence: model.sequence,
+                hash: model.hash,
+            })
+            .unwrap_or_else(ChainHead::genesis);
+
+        Ok(head)
+    }
+
+    fn entity_to_domain(model: audit_log_entity::Model) -> Result<AuditEntry, AppError> {
+        Ok(AuditEntry {
+            id: model.id,
+            actor_id: model.actor_id,
+            action: model.action.parse()?,
+            entity_type: model.entity_type,
+            entity_id: model.entity_id,
+            changes: serde_json::from_str(&model.changes).map_err(|err| {
+                AppError::InternalError(format!("Invalid audit changes: {}", err))
+            })?,
+            ip_address: model.ip_address,
+            user_agent: model.user_agent,
+            request_id: model.request_id,
+            created_at: model.created_at.with_timezone(&Utc),
+            sequence: model.sequence,
+            prev_hash: model.prev_hash,
+            hash: model.hash,
+        })
+    }
+
+    fn domain_to_active_model(
+        entry: AuditEntry,
+    ) -> Result<audit_log_entity::ActiveModel, AppError> {
+        Ok(audit_log_entity::ActiveModel {
+            id: Set(entry.id),
+            actor_id: Set(entry.actor_id),
+            action: Set(entry.action.as_str().to_string()),
+            entity_type: Set(entry.entity_type),
+            entity_id: Set(entry.entity_id),
+            changes: Set(serde_json::to_string(&entry.changes).map_err(|err| {
+                AppError::InternalError(format!("Failed to serialize audit changes: {}", err))
+            })?),
+            ip_address: Set(entry.ip_address),
+            user_agent: Set(entry.user_agent),
+            request_id: Set(entry.request_id),
+            created_at: Set(entry.created_at.into()),
+            sequence: Set(entry.sequence),
+            prev_hash: Set(entry.prev_hash),
+            hash: Set(entry.hash),
+        })
+    }
+
+    fn filter_condition(filter: AuditFilter) -> Condition {
+        let mut condition = Condition::all();
+
+        if let Some(actor_id) = filter.actor_id {
+            condition = condition.add(audit_log_entity::Column::ActorId.eq(actor_id));
+        }
+        if let Some(entity_type) = filter.entity_type {
+            condition = condition.add(audit_log_entity::Column::EntityType.eq(entity_type));
+        }
+        if let Some(entity_id) = filter.entity_id {
+            condition = condition.add(audit_log_entity::Column::EntityId.eq(entity_id));
+        }
+        if let Some(from) = filter.from {
+            condition = condition.add(audit_log_entity::Column::CreatedAt.gte(from));
+        }
+        if let Some(to) = filter.to {
+            condition = condition.add(audit_log_entity::Column::CreatedAt.lt(to));
+        }
+
+        condition
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for PostgresAuditLogRepository {
+    async fn list(
+        &self,
+        filter: AuditFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        AuditLogEntity::find()
+            .filter(Self::filter_condition(filter))
+            .order_by_desc(audit_log_entity::Column::CreatedAt)
+            .limit(limit as u64)
+            .offset(offset as u64)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(Self::entity_to_domain)
+            .collect()
+    }
+
+    async fn head(&self) -> Result<ChainHead, AppError> {
+        Self::head_on(&self.db).await
+    }
+
+    async fn list_chain(
+        &self,
+        after_sequence: i64,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        AuditLogEntity::find()
+            .filter(audit_log_entity::Column::Sequence.gt(after_sequence))
+            .order_by_asc(audit_log_entity::Column::Sequence)
+            .limit(limit as u64)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(Self::entity_to_domain)
+            .collect()
+    }
+}
@@ -1,32 +1,113 @@
 mod api {
+    pub mod auth_handler;
+    pub mod gateway_handler;
     pub mod handler;
+    pub mod merchant_handler;
+    pub mod role_handler;
     pub mod router;
+    pub mod site_credentials_handler;
+    pub mod site_handler;
 }
 
 pub mod app {
+    pub mod authenticate_merchant_use_case;
+    pub mod change_merchant_status_use_case;
+    pub mod change_password_use_case;
+    pub mod change_site_status_use_case;
+    pub mod change_user_status_use_case;
+    pub mod create_merchant_use_case;
+    pub mod create_role_use_case;
+    pub mod create_site_use_case;
     pub mod create_user_use_case;
+    pub mod delete_role_use_case;
     pub mod delete_user_use_case;
+    pub mod get_merchant_use_case;
+    pub mod get_role_use_case;
+    pub mod get_site_credentials_use_case;
+    pub mod get_site_use_case;
     pub mod get_user_info_use_case;
+    pub mod issue_site_credentials_use_case;
+    pub mod login_use_case;
+    pub mod logout_use_case;
+    pub mod password_reset_use_case;
+    pub mod refresh_token_use_case;
+    pub mod revoke_site_credentials_use_case;
+    pub mod revoke_user_sessions_use_case;
+    pub mod token_issuer;
+    pub mod update_merchant_use_case;
+    pub mod update_role_use_case;
+    pub mod update_site_credentials_use_case;
+    pub mod update_site_use_case;
     pub mod update_user_use_case;
+    pub mod verify_login_use_case;
 }
 
 pub mod domain {
-    pub mod user;
+    pub mod idempotency_key;
+    pub mod login_challenge;
+    pub mod merchant;
+    pub mod password_policy;
+    pub mod password_reset_token;
+    pub mod refresh_token;
     pub mod repository;
+    pub mod user;
 }
 
 pub mod dto {
+    pub mod auth_dto;
+    pub mod gateway_dto;
+    pub mod merchant_dto;
+    pub mod role_dto;
+    pub mod site_credentials_dto;
+    pub mod site_dto;
     pub mod user_dto;
 }
 
 pub mod infra {
+    pub mod idempotency_key_entity;
+    pub mod idempotency_key_repository;
+    pub mod login_challenge_entity;
+    pub mod login_challenge_repository;
+    pub mod merchant_entity;
+    pub mod merchant_repository;
+    pub mod merchant_status_history_entity;
+    pub mod password_history_entity;
+    pub mod password_reset_token_entity;
+    pub mod password_reset_token_repository;
+    pub mod refresh_token_entity;
+    pub mod refresh_token_repository;
+    pub mod revoked_token_entity;
+    pub mod site_credentials_entity;
+    pub mod site_credentials_repository;
+    pub mod site_entity;
+    pub mod site_repository;
+    pub mod token_revocation_repository;
     pub mod user_entity;
     pub mod user_repository;
+    pub mod user_token_revocation_entity;
 }
 
 pub mod role;
 
-pub use api::router::{protected_user_routes, UserApiDoc};
-pub use domain::repository::UserRepository;
+pub use api::router::{
+    auth_routes, gateway_routes, protected_auth_routes, protected_merchant_routes,
+    protected_role_routes, protected_user_routes, AuthApiDoc, GatewayApiDoc, MerchantApiDoc,
+    RoleApiDoc, UserApiDoc,
+};
+pub use domain::repository::{
+    IdempotencyKeyRepository, LoginChallengeRepository, MerchantRepository,
+    PasswordResetTokenRepository, RefreshTokenRepository, SiteCredentialsRepository,
+    SiteRepository, TokenRevocationRepository, UserRepository,
+};
+pub use infra::idempotency_key_repository::PostgresIdempotencyKeyRepository;
+pub use infra::login_challenge_repository::PostgresLoginChallengeRepository;
+pub use infra::merchant_repository::PostgresMerchantRepository;
+pub use infra::password_reset_token_repository::PostgresPasswordResetTokenRepository;
+pub use infra::refresh_token_repository::PostgresRefreshTokenRepository;
+pub use infra::site_credentials_repository::PostgresSiteCredentialsRepository;
+pub use infra::site_repository::PostgresSiteRepository;
+pub use infra::token_revocation_repository::{
+    InMemoryTokenRevocationRepository, PostgresTokenRevocationRepository,
+};
 pub use infra::user_repository::PostgresUserRepository;
 pub use role::{PostgresRoleRepository, RoleRepository};
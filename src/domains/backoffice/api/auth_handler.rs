@@ -0,0 +1,127 @@
+use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError, jwt::Claims};
+use crate::domains::audit::domain::audit_entry::AuditContext;
+use crate::domains::backoffice::dto::auth_dto::{
+    LoginChallengeResponse, LoginRequest, RefreshTokenRequest, ResetPasswordRequest, TokenResponse,
+    VerifyLoginRequest,
+};
+use axum::{extract::Extension, Json};
+
+use std::sync::Arc;
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Credentials accepted, confirmation code sent by email", body = inline(ApiResponse<LoginChallengeResponse>)),
+        (status = 401, description = "Invalid login or password")
+    ),
+    tag = "Auth",
+    summary = "Login step 1: login/password",
+    description = "Checks the username (or email) and password. On success a one-time 6-digit code is sent to the user's email and a short-lived challenge is returned."
+)]
+pub async fn login(
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<LoginRequest>,
+) -> Result<Json<ApiResponse<LoginChallengeResponse>>, AppError> {
+    let challenge = state.login_use_case.execute(request).await?;
+
+    Ok(Json(ApiResponse::success(LoginChallengeResponse::from(
+        challenge,
+    ))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/verify",
+    request_body = VerifyLoginRequest,
+    responses(
+        (status = 200, description = "Code confirmed, access and refresh tokens issued", body = inline(ApiResponse<TokenResponse>)),
+        (status = 401, description = "Invalid code or expired challenge")
+    ),
+    tag = "Auth",
+    summary = "Login step 2: email code confirmation",
+    description = "Exchanges the challenge ID and the emailed code for a short-lived JWT and a refresh token. The challenge is single use and locked after too many wrong codes."
+)]
+pub async fn verify(
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<VerifyLoginRequest>,
+) -> Result<Json<ApiResponse<TokenResponse>>, AppError> {
+    let tokens = state.verify_login_use_case.execute(request).await?;
+
+    Ok(Json(ApiResponse::success(TokenResponse::from(tokens))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/refresh",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "New access and refresh tokens issued", body = inline(ApiResponse<TokenResponse>)),
+        (status = 401, description = "Invalid, expired or reused refresh token")
+    ),
+    tag = "Auth",
+    summary = "Refresh access token",
+    description = "Exchanges a refresh token for a new token pair. Every refresh token is single use: presenting an already used one revokes the whole login session."
+)]
+pub async fn refresh(
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<RefreshTokenRequest>,
+) -> Result<Json<ApiResponse<TokenResponse>>, AppError> {
+    let tokens = state
+        .refresh_token_use_case
+        .execute(&request.refresh_token)
+        .await?;
+
+    Ok(Json(ApiResponse::success(TokenResponse::from(tokens))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/logout",
+    responses(
+        (status = 200, description = "Token revoked"),
+        (status = 401, description = "Missing, invalid or already revoked JWT token")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Auth",
+    summary = "Logout",
+    description = "Revokes the JWT used for this request and ends its login session, so the session's refresh token stops working as well."
+)]
+pub async fn logout(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state.logout_use_case.execute(&claims, &audit).await?;
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/password-reset",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "Password set; all sessions of the user ended"),
+        (status = 400, description = "New password breaks the password policy"),
+        (status = 401, description = "Unknown, used or expired reset link")
+    ),
+    tag = "Auth",
+    summary = "Reset password",
+    description = "Sets a new password with the token of an emailed reset link. The link works once and the user is signed out everywhere."
+)]
+pub async fn reset_password(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Json(request): Json<ResetPasswordRequest>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .user_password_reset_use_case
+        .complete(request, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(())))
+}
@@ -0,0 +1,28 @@
+use crate::common::{dto::ApiResponse, error::AppError};
+use crate::domains::backoffice::{
+    domain::merchant::MerchantContext, dto::gateway_dto::GatewayIdentityResponse,
+};
+use axum::{extract::Extension, Json};
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/gateway/me",
+    responses(
+        (status = 200, description = "Merchant and site the API key belongs to", body = inline(ApiResponse<GatewayIdentityResponse>)),
+        (status = 401, description = "Missing headers, unknown or revoked key, bad signature or stale timestamp"),
+        (status = 403, description = "Site or merchant is not active")
+    ),
+    security(
+        ("api_key" = [], "api_timestamp" = [], "api_signature" = [])
+    ),
+    tag = "Gateway",
+    summary = "Check merchant API credentials",
+    description = "Returns the merchant and site resolved from a signed request. Use it to verify the signing setup before calling payment endpoints."
+)]
+pub async fn get_gateway_identity(
+    Extension(context): Extension<MerchantContext>,
+) -> Result<Json<ApiResponse<GatewayIdentityResponse>>, AppError> {
+    Ok(Json(ApiResponse::success(GatewayIdentityResponse::from(
+        context,
+    ))))
+}
@@ -1,6 +1,8 @@
 use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError, jwt::Claims};
+use crate::domains::audit::domain::audit_entry::AuditContext;
 use crate::domains::backoffice::dto::user_dto::{
-    CreateUserRequest, ListUsersQuery, UpdateUserRequest, UserResponse,
+    ChangePasswordRequest, CreateUserRequest, ListUsersQuery, PasswordResetResponse,
+    UpdateUserRequest, UserResponse,
 };
 use axum::{
     extract::{Extension, Path, Query},
@@ -73,28 +75,198 @@ pub async fn list_users(
 
 pub async fn create_user(
     Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
     Json(request): Json<CreateUserRequest>,
 ) -> Result<Json<ApiResponse<UserResponse>>, AppError> {
-    let user = state.user_create_use_case.execute(request).await?;
+    let user = state.user_create_use_case.execute(request, &audit).await?;
 
     Ok(Json(ApiResponse::success(UserResponse::from(user))))
 }
 
 pub async fn update_user(
     Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
     Path(user_id): Path<Uuid>,
     Json(request): Json<UpdateUserRequest>,
 ) -> Result<Json<ApiResponse<UserResponse>>, AppError> {
-    let user = state.user_update_use_case.execute(user_id, request).await?;
+    let user = state
+        .user_update_use_case
+        .execute(user_id, request, &audit)
+        .await?;
 
     Ok(Json(ApiResponse::success(UserResponse::from(user))))
 }
 
 pub async fn delete_user(
     Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
     Path(user_id): Path<Uuid>,
 ) -> Result<Json<ApiResponse<()>>, AppError> {
-    state.user_delete_use_case.execute(user_id).await?;
+    state.user_delete_use_case.execute(user_id, &audit).await?;
 
     Ok(Json(ApiResponse::success(())))
 }
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/user/{id}/sessions",
+    params(
+        ("id" = Uuid, Path, description = "User ID whose sessions are revoked")
+    ),
+    responses(
+        (status = 200, description = "All tokens of the user revoked"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Users",
+    summary = "Revoke all sessions of a user",
+    description = "Revokes every JWT issued to the user so far, e.g. when the user is deactivated. Admin only."
+)]
+pub async fn revoke_user_sessions(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .revoke_user_sessions_use_case
+        .execute(user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/user/{id}/deactivate",
+    params(
+        ("id" = Uuid, Path, description = "User ID to deactivate")
+    ),
+    responses(
+        (status = 200, description = "User deactivated and signed out everywhere", body = inline(ApiResponse<UserResponse>)),
+        (status = 400, description = "Users cannot deactivate themselves"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "User is already inactive")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Users",
+    summary = "Deactivate a user",
+    description = "Blocks the user from signing in and revokes all of their sessions. The account and its history are kept. Requires `users:write`."
+)]
+pub async fn deactivate_user(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<UserResponse>>, AppError> {
+    let user = state
+        .user_change_status_use_case
+        .deactivate(user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(UserResponse::from(user))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/user/{id}/activate",
+    params(
+        ("id" = Uuid, Path, description = "User ID to activate")
+    ),
+    responses(
+        (status = 200, description = "User can sign in again", body = inline(ApiResponse<UserResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "User is already active")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Users",
+    summary = "Activate a user",
+    description = "Lets a deactivated user sign in again. Sessions revoked on deactivation stay revoked. Requires `users:write`."
+)]
+pub async fn activate_user(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<UserResponse>>, AppError> {
+    let user = state
+        .user_change_status_use_case
+        .activate(user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(UserResponse::from(user))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/user/me/password",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "Password changed"),
+        (status = 400, description = "Wrong current password, or the new one breaks the password policy"),
+        (status = 401, description = "Missing or invalid JWT token")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Users",
+    summary = "Change own password",
+    description = "Replaces the caller's password after checking the current one. The new password must pass the password policy and differ from the latest ones. Other sessions stay signed in."
+)]
+pub async fn change_own_password(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+    Json(request): Json<ChangePasswordRequest>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .user_change_password_use_case
+        .execute(claims.user_id, request, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/user/{id}/password-reset",
+    params(
+        ("id" = Uuid, Path, description = "User ID whose password is reset")
+    ),
+    responses(
+        (status = 200, description = "Reset link emailed to the user", body = inline(ApiResponse<PasswordResetResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "User is inactive")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Users",
+    summary = "Send a password reset link",
+    description = "Emails the user a one-time link for `POST /api/v1/auth/password-reset`. Links sent before stop working; the current password keeps working until the link is used. Requires `users:write`."
+)]
+pub async fn request_password_reset(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<PasswordResetResponse>>, AppError> {
+    let reset_token = state
+        .user_password_reset_use_case
+        .request(user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(PasswordResetResponse {
+        expires_at: reset_token.expires_at,
+    })))
+}
@@ -0,0 +1,249 @@
+use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError};
+use crate::domains::audit::domain::audit_entry::AuditContext;
+use crate::domains::backoffice::dto::merchant_dto::{
+    ChangeMerchantStatusRequest, CreateMerchantRequest, ListMerchantsQuery, MerchantResponse,
+    MerchantStatusChangeResponse, UpdateMerchantRequest,
+};
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/merchant",
+    params(ListMerchantsQuery),
+    responses(
+        (status = 200, description = "Page of merchants", body = inline(ApiResponse<Vec<MerchantResponse>>)),
+        (status = 400, description = "Limit exceeds 100"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Merchants",
+    summary = "List or search merchants",
+    description = "Lists merchants ordered by name, or searches them by name when `search` is given. Requires `merchants:read`."
+)]
+pub async fn list_merchants(
+    Extension(state): Extension<Arc<AppState>>,
+    Query(params): Query<ListMerchantsQuery>,
+) -> Result<Json<ApiResponse<Vec<MerchantResponse>>>, AppError> {
+    let merchants = if let Some(search_query) = params.search {
+        state
+            .merchant_get_use_case
+            .search(&search_query, Some(params.limit))
+            .await?
+    } else {
+        state
+            .merchant_get_use_case
+            .list(params.limit, params.offset)
+            .await?
+    };
+
+    let response: Vec<MerchantResponse> =
+        merchants.into_iter().map(MerchantResponse::from).collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/merchant/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID to fetch")
+    ),
+    responses(
+        (status = 200, description = "Merchant with its sites", body = inline(ApiResponse<MerchantResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Merchant not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Merchants",
+    summary = "Get merchant by ID",
+    description = "Retrieves a merchant together with its sites. Requires `merchants:read`."
+)]
+pub async fn get_merchant(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(merchant_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<MerchantResponse>>, AppError> {
+    let merchant = state.merchant_get_use_case.execute(merchant_id).await?;
+
+    Ok(Json(ApiResponse::success(MerchantResponse::from(merchant))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/merchant",
+    request_body = CreateMerchantRequest,
+    responses(
+        (status = 200, description = "Merchant created in onboarding", body = inline(ApiResponse<MerchantResponse>)),
+        (status = 400, description = "Empty or duplicate name"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Merchants",
+    summary = "Create merchant",
+    description = "Creates a merchant in `onboarding` status. Requires `merchants:write`."
+)]
+pub async fn create_merchant(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Json(request): Json<CreateMerchantRequest>,
+) -> Result<Json<ApiResponse<MerchantResponse>>, AppError> {
+    let merchant = state
+        .merchant_create_use_case
+        .execute(request, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(MerchantResponse::from(merchant))))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/merchant/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID to update")
+    ),
+    request_body = UpdateMerchantRequest,
+    responses(
+        (status = 200, description = "Merchant updated", body = inline(ApiResponse<MerchantResponse>)),
+        (status = 400, description = "Empty or duplicate name"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Merchant not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Merchants",
+    summary = "Update merchant",
+    description = "Updates name and/or description of a merchant. Requires `merchants:write`."
+)]
+pub async fn update_merchant(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(merchant_id): Path<Uuid>,
+    Json(request): Json<UpdateMerchantRequest>,
+) -> Result<Json<ApiResponse<MerchantResponse>>, AppError> {
+    let merchant = state
+        .merchant_update_use_case
+        .execute(merchant_id, request, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(MerchantResponse::from(merchant))))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/merchant/{id}/status",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID")
+    ),
+    request_body = ChangeMerchantStatusRequest,
+    responses(
+        (status = 200, description = "Status changed", body = inline(ApiResponse<MerchantResponse>)),
+        (status = 400, description = "Reason missing for an active/inactive switch"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Merchant not found"),
+        (status = 409, description = "Transition not allowed from the current status")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Merchants",
+    summary = "Change merchant status",
+    description = "Moves a merchant through its lifecycle. `onboarding` -> `active` requires completed KYB and at least one active site with active credentials; `active` <-> `inactive` requires a reason. Every change is recorded in the status history. Requires `merchants:write`."
+)]
+pub async fn change_merchant_status(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(merchant_id): Path<Uuid>,
+    Json(request): Json<ChangeMerchantStatusRequest>,
+) -> Result<Json<ApiResponse<MerchantResponse>>, AppError> {
+    let merchant = state
+        .merchant_change_status_use_case
+        .execute(merchant_id, request.status, request.reason, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(MerchantResponse::from(merchant))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/merchant/{id}/kyb",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID")
+    ),
+    responses(
+        (status = 200, description = "KYB marked as verified", body = inline(ApiResponse<MerchantResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Merchant not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Merchants",
+    summary = "Mark KYB as verified",
+    description = "Records that the merchant passed KYB checks. Calling it again keeps the original date. Requires `merchants:write`."
+)]
+pub async fn verify_merchant_kyb(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(merchant_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<MerchantResponse>>, AppError> {
+    let merchant = state
+        .merchant_change_status_use_case
+        .verify_kyb(merchant_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(MerchantResponse::from(merchant))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/merchant/{id}/status-history",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID")
+    ),
+    responses(
+        (status = 200, description = "Status changes, newest first", body = inline(ApiResponse<Vec<MerchantStatusChangeResponse>>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Merchant not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Merchants",
+    summary = "Get merchant status history",
+    description = "Lists every status change of a merchant with its reason and author. Requires `merchants:read`."
+)]
+pub async fn get_merchant_status_history(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(merchant_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<MerchantStatusChangeResponse>>>, AppError> {
+    let history = state
+        .merchant_get_use_case
+        .status_history(merchant_id)
+        .await?;
+
+    let response: Vec<MerchantStatusChangeResponse> = history
+        .into_iter()
+        .map(MerchantStatusChangeResponse::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
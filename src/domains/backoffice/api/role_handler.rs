@@ -0,0 +1,157 @@
+use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError};
+use crate::domains::audit::domain::audit_entry::AuditContext;
+use crate::domains::backoffice::dto::role_dto::{
+    CreateRoleRequest, RoleResponse, UpdateRoleRequest,
+};
+use axum::{
+    extract::{Extension, Path},
+    Json,
+};
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/role",
+    responses(
+        (status = 200, description = "All roles with their permissions", body = inline(ApiResponse<Vec<RoleResponse>>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Roles",
+    summary = "List roles",
+    description = "Lists all roles with their granted permissions. Requires `roles:read`."
+)]
+pub async fn list_roles(
+    Extension(state): Extension<Arc<AppState>>,
+) -> Result<Json<ApiResponse<Vec<RoleResponse>>>, AppError> {
+    let roles = state.role_get_use_case.list().await?;
+
+    let response: Vec<RoleResponse> = roles.into_iter().map(RoleResponse::from).collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/role/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Role ID to fetch")
+    ),
+    responses(
+        (status = 200, description = "Role found successfully", body = inline(ApiResponse<RoleResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Role not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Roles",
+    summary = "Get role by ID",
+    description = "Retrieves a role with its granted permissions. Requires `roles:read`."
+)]
+pub async fn get_role(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(role_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<RoleResponse>>, AppError> {
+    let role = state.role_get_use_case.execute(role_id).await?;
+
+    Ok(Json(ApiResponse::success(RoleResponse::from(role))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/role",
+    request_body = CreateRoleRequest,
+    responses(
+        (status = 200, description = "Role created", body = inline(ApiResponse<RoleResponse>)),
+        (status = 400, description = "Empty or duplicate name, or unknown permission"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Roles",
+    summary = "Create role",
+    description = "Creates a custom role and grants it the given permissions. Requires `roles:write`."
+)]
+pub async fn create_role(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Json(request): Json<CreateRoleRequest>,
+) -> Result<Json<ApiResponse<RoleResponse>>, AppError> {
+    let role = state.role_create_use_case.execute(request, &audit).await?;
+
+    Ok(Json(ApiResponse::success(RoleResponse::from(role))))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/role/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Role ID to update")
+    ),
+    request_body = UpdateRoleRequest,
+    responses(
+        (status = 200, description = "Role updated", body = inline(ApiResponse<RoleResponse>)),
+        (status = 400, description = "Empty or duplicate name, or unknown permission"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions or renaming a system role"),
+        (status = 404, description = "Role not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Roles",
+    summary = "Update role",
+    description = "Updates name, description and/or permissions of a role. System roles keep their name but their permissions can be changed. Requires `roles:write`; users pick up new permissions with their next token."
+)]
+pub async fn update_role(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(role_id): Path<Uuid>,
+    Json(request): Json<UpdateRoleRequest>,
+) -> Result<Json<ApiResponse<RoleResponse>>, AppError> {
+    let role = state
+        .role_update_use_case
+        .execute(role_id, request, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(RoleResponse::from(role))))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/role/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Role ID to delete")
+    ),
+    responses(
+        (status = 200, description = "Role deleted"),
+        (status = 400, description = "Role is still assigned to users"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions or deleting a system role"),
+        (status = 404, description = "Role not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Roles",
+    summary = "Delete role",
+    description = "Deletes a custom role that no user references anymore. Requires `roles:write`."
+)]
+pub async fn delete_role(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(role_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state.role_delete_use_case.execute(role_id, &audit).await?;
+
+    Ok(Json(ApiResponse::success(())))
+}
@@ -1,29 +1,55 @@
 use axum::{
     middleware,
-    routing::{delete, get, patch, post},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 
 use crate::{
-    common::{jwt::SecurityAddon, middleware::require_roles},
+    common::{
+        jwt::SecurityAddon, middleware::require_permission,
+        request_signature::MerchantSignatureAddon,
+    },
     domains::backoffice::{
-        dto::user_dto::{RoleInfo, UserResponse},
-        role::model::{
-            admin_role_id, finance_role_id, risk_role_id, support_role_id, user_role_id,
+        domain::merchant::{MerchantStatus, SiteStatus},
+        dto::auth_dto::{
+            LoginChallengeResponse, LoginRequest, RefreshTokenRequest, ResetPasswordRequest,
+            TokenResponse, VerifyLoginRequest,
+        },
+        dto::gateway_dto::GatewayIdentityResponse,
+        dto::merchant_dto::{
+            ChangeMerchantStatusRequest, CreateMerchantRequest, MerchantResponse,
+            MerchantStatusChangeResponse, SiteResponse, UpdateMerchantRequest,
+        },
+        dto::role_dto::{CreateRoleRequest, RoleResponse, UpdateRoleRequest},
+        dto::site_credentials_dto::{
+            IssuedSiteCredentialsResponse, SiteCredentialsResponse, UpdateAllowedIpsRequest,
+        },
+        dto::site_dto::{ChangeSiteStatusRequest, CreateSiteRequest, UpdateSiteRequest},
+        dto::user_dto::{ChangePasswordRequest, PasswordResetResponse, RoleInfo, UserResponse},
+        role::permission::{
+            MERCHANTS_READ, MERCHANTS_WRITE, ROLES_READ, ROLES_WRITE, USERS_READ, USERS_WRITE,
         },
     },
 };
 
 use utoipa::OpenApi;
 
-use super::handler;
+use super::{
+    auth_handler, gateway_handler, handler, merchant_handler, role_handler,
+    site_credentials_handler, site_handler,
+};
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
         super::handler::get_user,
+        super::handler::revoke_user_sessions,
+        super::handler::deactivate_user,
+        super::handler::activate_user,
+        super::handler::change_own_password,
+        super::handler::request_password_reset,
     ),
-    components(schemas(UserResponse, RoleInfo)),
+    components(schemas(UserResponse, RoleInfo, ChangePasswordRequest, PasswordResetResponse)),
     tags(
         (name = "Users", description = "User management endpoints")
     ),
@@ -31,26 +57,221 @@ use super::handler;
 )]
 pub struct UserApiDoc;
 
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::auth_handler::login,
+        super::auth_handler::verify,
+        super::auth_handler::refresh,
+        super::auth_handler::logout,
+        super::auth_handler::reset_password,
+    ),
+    components(schemas(
+        LoginRequest,
+        LoginChallengeResponse,
+        VerifyLoginRequest,
+        RefreshTokenRequest,
+        TokenResponse,
+        ResetPasswordRequest
+    )),
+    tags(
+        (name = "Auth", description = "Backoffice login: password, then email code, then JWT")
+    ),
+    modifiers(&SecurityAddon)
+)]
+pub struct AuthApiDoc;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::role_handler::list_roles,
+        super::role_handler::get_role,
+        super::role_handler::create_role,
+        super::role_handler::update_role,
+        super::role_handler::delete_role,
+    ),
+    components(schemas(RoleResponse, CreateRoleRequest, UpdateRoleRequest)),
+    tags(
+        (name = "Roles", description = "Role and permission management endpoints")
+    ),
+    modifiers(&SecurityAddon)
+)]
+pub struct RoleApiDoc;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::merchant_handler::list_merchants,
+        super::merchant_handler::get_merchant,
+        super::merchant_handler::create_merchant,
+        super::merchant_handler::update_merchant,
+        super::merchant_handler::change_merchant_status,
+        super::merchant_handler::verify_merchant_kyb,
+        super::merchant_handler::get_merchant_status_history,
+        super::site_handler::list_sites,
+        super::site_handler::get_site,
+        super::site_handler::create_site,
+        super::site_handler::update_site,
+        super::site_handler::change_site_status,
+        super::site_credentials_handler::list_site_credentials,
+        super::site_credentials_handler::issue_site_credentials,
+        super::site_credentials_handler::rotate_site_credentials,
+        super::site_credentials_handler::revoke_site_credentials,
+        super::site_credentials_handler::update_site_credentials_allowed_ips,
+    ),
+    components(schemas(
+        MerchantResponse,
+        SiteResponse,
+        MerchantStatus,
+        SiteStatus,
+        CreateMerchantRequest,
+        UpdateMerchantRequest,
+        ChangeMerchantStatusRequest,
+        MerchantStatusChangeResponse,
+        CreateSiteRequest,
+        UpdateSiteRequest,
+        ChangeSiteStatusRequest,
+        SiteCredentialsResponse,
+        IssuedSiteCredentialsResponse,
+        UpdateAllowedIpsRequest
+    )),
+    tags(
+        (name = "Merchants", description = "Merchant management endpoints"),
+        (name = "Sites", description = "Merchant site management endpoints"),
+        (name = "Site credentials", description = "Site API key issuance, rotation and revocation")
+    ),
+    modifiers(&SecurityAddon)
+)]
+pub struct MerchantApiDoc;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(super::gateway_handler::get_gateway_identity),
+    components(schemas(GatewayIdentityResponse)),
+    tags(
+        (name = "Gateway", description = "Merchant-facing API authenticated with signed requests")
+    ),
+    modifiers(&MerchantSignatureAddon)
+)]
+pub struct GatewayApiDoc;
+
+pub fn auth_routes() -> Router {
+    Router::new()
+        .route("/auth/login", post(auth_handler::login))
+        .route("/auth/verify", post(auth_handler::verify))
+        .route("/auth/refresh", post(auth_handler::refresh))
+        .route("/auth/password-reset", post(auth_handler::reset_password))
+}
+
+pub fn protected_auth_routes() -> Router {
+    Router::new().route("/auth/logout", post(auth_handler::logout))
+}
+
 pub fn protected_user_routes() -> Router {
-    // Admin-only routes (create, update, delete users)
-    let admin_routes = Router::new()
+    // Routes that modify users (create, update, delete, revoke sessions, change status,
+    // reset password)
+    let write_routes = Router::new()
         .route("/user", post(handler::create_user))
         .route("/user/{id}", patch(handler::update_user))
         .route("/user/{id}", delete(handler::delete_user))
-        .route_layer(middleware::from_fn(require_roles(vec![admin_role_id()])));
+        .route("/user/{id}/sessions", delete(handler::revoke_user_sessions))
+        .route("/user/{id}/deactivate", post(handler::deactivate_user))
+        .route("/user/{id}/activate", post(handler::activate_user))
+        .route(
+            "/user/{id}/password-reset",
+            post(handler::request_password_reset),
+        )
+        .route_layer(middleware::from_fn(require_permission(USERS_WRITE)));
 
-    // Authenticated user routes (any role can access their own profile)
-    let user_routes = Router::new()
+    let read_routes = Router::new()
         .route("/user", get(handler::list_users))
         .route("/user/{id}", get(handler::get_user))
+        .route_layer(middleware::from_fn(require_permission(USERS_READ)));
+
+    // Any authenticated user can access their own profile
+    let profile_routes = Router::new()
         .route("/user/me", get(handler::get_current_user))
-        .route_layer(middleware::from_fn(require_roles(vec![
-            admin_role_id(),
-            support_role_id(),
-            risk_role_id(),
-            finance_role_id(),
-            user_role_id(),
-        ])));
-
-    Router::new().merge(admin_routes).merge(user_routes)
+        .route("/user/me/password", post(handler::change_own_password));
+
+    Router::new()
+        .merge(write_routes)
+        .merge(read_routes)
+        .merge(profile_routes)
+}
+
+pub fn protected_role_routes() -> Router {
+    let write_routes = Router::new()
+        .route("/role", post(role_handler::create_role))
+        .route("/role/{id}", patch(role_handler::update_role))
+        .route("/role/{id}", delete(role_handler::delete_role))
+        .route_layer(middleware::from_fn(require_permission(ROLES_WRITE)));
+
+    let read_routes = Router::new()
+        .route("/role", get(role_handler::list_roles))
+        .route("/role/{id}", get(role_handler::get_role))
+        .route_layer(middleware::from_fn(require_permission(ROLES_READ)));
+
+    Router::new().merge(write_routes).merge(read_routes)
+}
+
+pub fn protected_merchant_routes() -> Router {
+    let write_routes = Router::new()
+        .route("/merchant", post(merchant_handler::create_merchant))
+        .route("/merchant/{id}", patch(merchant_handler::update_merchant))
+        .route(
+            "/merchant/{id}/status",
+            patch(merchant_handler::change_merchant_status),
+        )
+        .route(
+            "/merchant/{id}/kyb",
+            post(merchant_handler::verify_merchant_kyb),
+        )
+        .route("/merchant/{id}/site", post(site_handler::create_site))
+        .route(
+            "/merchant/{id}/site/{site_id}",
+            patch(site_handler::update_site),
+        )
+        .route(
+            "/merchant/{id}/site/{site_id}/status",
+            patch(site_handler::change_site_status),
+        )
+        .route(
+            "/merchant/{id}/site/{site_id}/credentials",
+            post(site_credentials_handler::issue_site_credentials),
+        )
+        .route(
+            "/merchant/{id}/site/{site_id}/credentials/rotate",
+            post(site_credentials_handler::rotate_site_credentials),
+        )
+        .route(
+            "/merchant/{id}/site/{site_id}/credentials/{credentials_id}/revoke",
+            post(site_credentials_handler::revoke_site_credentials),
+        )
+        .route(
+            "/merchant/{id}/site/{site_id}/credentials/{credentials_id}/allowed-ips",
+            put(site_credentials_handler::update_site_credentials_allowed_ips),
+        )
+        .route_layer(middleware::from_fn(require_permission(MERCHANTS_WRITE)));
+
+    let read_routes = Router::new()
+        .route("/merchant", get(merchant_handler::list_merchants))
+        .route("/merchant/{id}", get(merchant_handler::get_merchant))
+        .route(
+            "/merchant/{id}/status-history",
+            get(merchant_handler::get_merchant_status_history),
+        )
+        .route("/merchant/{id}/site", get(site_handler::list_sites))
+        .route("/merchant/{id}/site/{site_id}", get(site_handler::get_site))
+        .route(
+            "/merchant/{id}/site/{site_id}/credentials",
+            get(site_credentials_handler::list_site_credentials),
+        )
+        .route_layer(middleware::from_fn(require_permission(MERCHANTS_READ)));
+
+    Router::new().merge(write_routes).merge(read_routes)
+}
+
+/// Merchant-facing routes; `merchant_auth` puts a `MerchantContext` in the extensions
+pub fn gateway_routes() -> Router {
+    Router::new().route("/gateway/me", get(gateway_handler::get_gateway_identity))
 }
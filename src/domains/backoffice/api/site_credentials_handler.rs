@@ -0,0 +1,202 @@
+use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError};
+use crate::domains::audit::domain::audit_entry::AuditContext;
+use crate::domains::backoffice::dto::site_credentials_dto::{
+    IssuedSiteCredentialsResponse, SiteCredentialsResponse, UpdateAllowedIpsRequest,
+};
+use axum::{
+    extract::{Extension, Path},
+    Json,
+};
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/merchant/{id}/site/{site_id}/credentials",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID"),
+        ("site_id" = Uuid, Path, description = "Site ID")
+    ),
+    responses(
+        (status = 200, description = "API keys of the site, newest first", body = inline(ApiResponse<Vec<SiteCredentialsResponse>>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Site not found for this merchant")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Site credentials",
+    summary = "List site API keys",
+    description = "Lists current, rotated and revoked API keys of a site. Secrets are never returned. Requires `merchants:read`."
+)]
+pub async fn list_site_credentials(
+    Extension(state): Extension<Arc<AppState>>,
+    Path((merchant_id, site_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<Vec<SiteCredentialsResponse>>>, AppError> {
+    let credentials = state
+        .site_credentials_get_use_case
+        .list(merchant_id, site_id)
+        .await?;
+
+    let response: Vec<SiteCredentialsResponse> = credentials
+        .into_iter()
+        .map(SiteCredentialsResponse::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/merchant/{id}/site/{site_id}/credentials",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID"),
+        ("site_id" = Uuid, Path, description = "Site ID")
+    ),
+    responses(
+        (status = 200, description = "Key pair issued; the secret is shown only once", body = inline(ApiResponse<IssuedSiteCredentialsResponse>)),
+        (status = 400, description = "Site already has an active key"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Site not found for this merchant")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Site credentials",
+    summary = "Issue site API key",
+    description = "Generates the first API key pair of a site. Store the returned `secret_key` right away: only its encrypted form is kept. Requires `merchants:write`."
+)]
+pub async fn issue_site_credentials(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path((merchant_id, site_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<IssuedSiteCredentialsResponse>>, AppError> {
+    let issued = state
+        .site_credentials_issue_use_case
+        .execute(merchant_id, site_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        IssuedSiteCredentialsResponse::from(issued),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/merchant/{id}/site/{site_id}/credentials/rotate",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID"),
+        ("site_id" = Uuid, Path, description = "Site ID")
+    ),
+    responses(
+        (status = 200, description = "New key pair issued; the secret is shown only once", body = inline(ApiResponse<IssuedSiteCredentialsResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Site not found for this merchant")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Site credentials",
+    summary = "Rotate site API key",
+    description = "Issues a new key pair. Previous keys keep working for the configured overlap window so the merchant can switch without downtime. Requires `merchants:write`."
+)]
+pub async fn rotate_site_credentials(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path((merchant_id, site_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<IssuedSiteCredentialsResponse>>, AppError> {
+    let issued = state
+        .site_credentials_issue_use_case
+        .rotate(merchant_id, site_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        IssuedSiteCredentialsResponse::from(issued),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/merchant/{id}/site/{site_id}/credentials/{credentials_id}/revoke",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID"),
+        ("site_id" = Uuid, Path, description = "Site ID"),
+        ("credentials_id" = Uuid, Path, description = "Credentials ID")
+    ),
+    responses(
+        (status = 200, description = "Key revoked", body = inline(ApiResponse<SiteCredentialsResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Site or credentials not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Site credentials",
+    summary = "Revoke site API key",
+    description = "Disables an API key immediately, including keys still inside a rotation overlap. Requires `merchants:write`."
+)]
+pub async fn revoke_site_credentials(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path((merchant_id, site_id, credentials_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<SiteCredentialsResponse>>, AppError> {
+    let credentials = state
+        .site_credentials_revoke_use_case
+        .execute(merchant_id, site_id, credentials_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(SiteCredentialsResponse::from(
+        credentials,
+    ))))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/merchant/{id}/site/{site_id}/credentials/{credentials_id}/allowed-ips",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID"),
+        ("site_id" = Uuid, Path, description = "Site ID"),
+        ("credentials_id" = Uuid, Path, description = "Credentials ID")
+    ),
+    request_body = UpdateAllowedIpsRequest,
+    responses(
+        (status = 200, description = "Allowlist replaced", body = inline(ApiResponse<SiteCredentialsResponse>)),
+        (status = 400, description = "Invalid address or range, or the key is revoked"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Site or credentials not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Site credentials",
+    summary = "Set API key IP allowlist",
+    description = "Replaces the addresses and CIDR ranges allowed to call the merchant API with this key. Requests from other addresses are rejected with 403, and a key with an empty list is rejected everywhere. Setting `allow_any_ip` with an empty list opts the key out of the restriction. Requires `merchants:write`."
+)]
+pub async fn update_site_credentials_allowed_ips(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path((merchant_id, site_id, credentials_id)): Path<(Uuid, Uuid, Uuid)>,
+    Json(payload): Json<UpdateAllowedIpsRequest>,
+) -> Result<Json<ApiResponse<SiteCredentialsResponse>>, AppError> {
+    let credentials = state
+        .site_credentials_update_use_case
+        .update_allowed_ips(
+            merchant_id,
+            site_id,
+            credentials_id,
+            payload.allowed_ips,
+            payload.allow_any_ip,
+            &audit,
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success(SiteCredentialsResponse::from(
+        credentials,
+    ))))
+}
@@ -0,0 +1,181 @@
+use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError};
+use crate::domains::audit::domain::audit_entry::AuditContext;
+use crate::domains::backoffice::dto::{
+    merchant_dto::SiteResponse,
+    site_dto::{ChangeSiteStatusRequest, CreateSiteRequest, UpdateSiteRequest},
+};
+use axum::{
+    extract::{Extension, Path},
+    Json,
+};
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/merchant/{id}/site",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID")
+    ),
+    responses(
+        (status = 200, description = "Sites of the merchant", body = inline(ApiResponse<Vec<SiteResponse>>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Merchant not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Sites",
+    summary = "List merchant sites",
+    description = "Lists the sites of a merchant, oldest first. Requires `merchants:read`."
+)]
+pub async fn list_sites(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(merchant_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<SiteResponse>>>, AppError> {
+    let sites = state.site_get_use_case.list(merchant_id).await?;
+
+    let response: Vec<SiteResponse> = sites.into_iter().map(SiteResponse::from).collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/merchant/{id}/site/{site_id}",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID"),
+        ("site_id" = Uuid, Path, description = "Site ID")
+    ),
+    responses(
+        (status = 200, description = "Site found successfully", body = inline(ApiResponse<SiteResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Site not found for this merchant")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Sites",
+    summary = "Get site by ID",
+    description = "Retrieves a single site of a merchant. Requires `merchants:read`."
+)]
+pub async fn get_site(
+    Extension(state): Extension<Arc<AppState>>,
+    Path((merchant_id, site_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<SiteResponse>>, AppError> {
+    let site = state
+        .site_get_use_case
+        .execute(merchant_id, site_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success(SiteResponse::from(site))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/merchant/{id}/site",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID")
+    ),
+    request_body = CreateSiteRequest,
+    responses(
+        (status = 200, description = "Site created", body = inline(ApiResponse<SiteResponse>)),
+        (status = 400, description = "Invalid URL, redirect on a foreign domain, or duplicate name/url"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Merchant not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Sites",
+    summary = "Create site",
+    description = "Adds an active site to a merchant. All URLs must be absolute (https only in production) and redirect URLs must be on the site's domain or one of its subdomains. Requires `merchants:write`."
+)]
+pub async fn create_site(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(merchant_id): Path<Uuid>,
+    Json(request): Json<CreateSiteRequest>,
+) -> Result<Json<ApiResponse<SiteResponse>>, AppError> {
+    let site = state
+        .site_create_use_case
+        .execute(merchant_id, request, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(SiteResponse::from(site))))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/merchant/{id}/site/{site_id}",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID"),
+        ("site_id" = Uuid, Path, description = "Site ID")
+    ),
+    request_body = UpdateSiteRequest,
+    responses(
+        (status = 200, description = "Site updated", body = inline(ApiResponse<SiteResponse>)),
+        (status = 400, description = "Invalid URL, redirect on a foreign domain, or duplicate name/url"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Site not found for this merchant")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Sites",
+    summary = "Update site",
+    description = "Updates the name and/or URLs of a site. The resulting set of URLs is validated as a whole. Requires `merchants:write`."
+)]
+pub async fn update_site(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path((merchant_id, site_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateSiteRequest>,
+) -> Result<Json<ApiResponse<SiteResponse>>, AppError> {
+    let site = state
+        .site_update_use_case
+        .execute(merchant_id, site_id, request, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(SiteResponse::from(site))))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/merchant/{id}/site/{site_id}/status",
+    params(
+        ("id" = Uuid, Path, description = "Merchant ID"),
+        ("site_id" = Uuid, Path, description = "Site ID")
+    ),
+    request_body = ChangeSiteStatusRequest,
+    responses(
+        (status = 200, description = "Status changed", body = inline(ApiResponse<SiteResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Site not found for this merchant")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Sites",
+    summary = "Activate or deactivate site",
+    description = "Switches a site between `active` and `inactive`. Inactive sites cannot accept payments. Requires `merchants:write`."
+)]
+pub async fn change_site_status(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path((merchant_id, site_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<ChangeSiteStatusRequest>,
+) -> Result<Json<ApiResponse<SiteResponse>>, AppError> {
+    let site = state
+        .site_change_status_use_case
+        .execute(merchant_id, site_id, request.status, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(SiteResponse::from(site))))
+}
@@ -0,0 +1,347 @@
+use std::{net::IpAddr, sync::Arc};
+
+use chrono::{Duration, Utc};
+
+use crate::{
+    common::{error::AppError, request_signature, secret_cipher::SecretCipher},
+    domains::backoffice::domain::{
+        merchant::MerchantContext,
+        repository::{MerchantRepository, SiteCredentialsRepository, SiteRepository},
+    },
+};
+
+pub struct AuthenticateMerchantUseCase {
+    merchant_repository: Arc<dyn MerchantRepository>,
+    site_repository: Arc<dyn SiteRepository>,
+    site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+    secret_cipher: Arc<SecretCipher>,
+    max_skew: Duration,
+}
+
+impl AuthenticateMerchantUseCase {
+    pub fn new(
+        merchant_repository: Arc<dyn MerchantRepository>,
+        site_repository: Arc<dyn SiteRepository>,
+        site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+        secret_cipher: Arc<SecretCipher>,
+        max_skew: Duration,
+    ) -> Self {
+        Self {
+            merchant_repository,
+            site_repository,
+            site_credentials_repository,
+            secret_cipher,
+            max_skew,
+        }
+    }
+
+    /// Resolves the site behind `public_key` and checks `signature` over `payload`.
+    /// `payload` must be built with `request_signature::signing_payload` from `timestamp`.
+    /// `client_ip` is matched against the key's allowlist once the signature is valid.
+    pub async fn execute(
+        &self,
+        public_key: &str,
+        timestamp: i64,
+        payload: &str,
+        signature: &str,
+        client_ip: Option<IpAddr>,
+    ) -> Result<MerchantContext, AppError> {
+        tracing::debug!("Authenticating merchant request with key {}", public_key);
+
+        let now = Utc::now();
+        // The header is untrusted; abs_diff cannot overflow on extreme values
+        if now.timestamp().abs_diff(timestamp) > self.max_skew.num_seconds().unsigned_abs() {
+            return Err(AppError::Unauthorized(
+                "Request timestamp is outside the allowed window".to_string(),
+            ));
+        }
+
+        let credentials = self
+            .site_credentials_repository
+            .find_by_public_key(public_key)
+            .await?
+            .filter(|credentials| credentials.is_usable_at(now))
+            .ok_or_else(|| AppError::Unauthorized("Invalid API key".to_string()))?;
+
+        let secret_key = self
+            .secret_cipher
+            .decrypt(&credentials.encrypted_secret_key)?;
+        if !request_signature::verify(&secret_key, payload, signature) {
+            return Err(AppError::Unauthorized(
+                "Invalid request signature".to_string(),
+            ));
+        }
+
+        if !credentials.allows_ip(client_ip) {
+            tracing::warn!(
+                "Rejected request with key {} from {:?}: not in allowlist",
+                public_key,
+                client_ip
+            );
+            return Err(AppError::Forbidden(
+                "Client IP address is not allowed for this API key".to_string(),
+            ));
+        }
+
+        let site = self
+            .site_repository
+            .find_by_id(credentials.site_id)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Invalid API key".to_string()))?;
+        if !site.is_active() {
+            return Err(AppError::Forbidden("Site is not active".to_string()));
+        }
+
+        let merchant = self
+            .merchant_repository
+            .find_by_id(site.merchant_id)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Invalid API key".to_string()))?;
+        if !merchant.is_active() {
+            return Err(AppError::Forbidden("Merchant is not active".to_string()));
+        }
+
+        tracing::info!(
+            "Merchant {} authenticated for site {}",
+            merchant.id,
+            site.id
+        );
+
+        Ok(MerchantContext {
+            merchant,
+            site,
+            credentials,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::domain::{
+        merchant::{Merchant, MerchantStatus, Site, SiteCredentials},
+        repository::{MockMerchantRepository, MockSiteCredentialsRepository, MockSiteRepository},
+    };
+
+    const SECRET_KEY: &str = "sk_secret";
+
+    struct Fixture {
+        merchant: Merchant,
+        site: Site,
+        credentials: SiteCredentials,
+        cipher: Arc<SecretCipher>,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let cipher = Arc::new(SecretCipher::new("test"));
+            let mut merchant = Merchant::new("Acme".to_string(), None);
+            merchant.status = MerchantStatus::Active;
+            let site = Site::new(
+                merchant.id,
+                "Shop".to_string(),
+                "https://acme.com".to_string(),
+                "https://acme.com/callback".to_string(),
+                "https://acme.com/ok".to_string(),
+                "https://acme.com/fail".to_string(),
+            );
+            let mut credentials = SiteCredentials::new(
+                site.id,
+                "pk_test".to_string(),
+                cipher.encrypt(SECRET_KEY).unwrap(),
+            );
+            // Most tests are not about the allowlist
+            credentials.allow_any_ip = true;
+
+            Self {
+                merchant,
+                site,
+                credentials,
+                cipher,
+            }
+        }
+
+        fn use_case(self) -> AuthenticateMerchantUseCase {
+            let mut credentials_repository = MockSiteCredentialsRepository::new();
+            let credentials = self.credentials;
+            credentials_repository
+                .expect_find_by_public_key()
+                .returning(
+                    move |key| Ok(Some(credentials.clone()).filter(|c| c.public_key == key)),
+                );
+
+            let mut site_repository = MockSiteRepository::new();
+            let site = self.site;
+            site_repository
+                .expect_find_by_id()
+                .returning(move |_| Ok(Some(site.clone())));
+
+            let mut merchant_repository = MockMerchantRepository::new();
+            let merchant = self.merchant;
+            merchant_repository
+                .expect_find_by_id()
+                .returning(move |_| Ok(Some(merchant.clone())));
+
+            AuthenticateMerchantUseCase::new(
+                Arc::new(merchant_repository),
+                Arc::new(site_repository),
+                Arc::new(credentials_repository),
+                self.cipher,
+                Duration::minutes(5),
+            )
+        }
+    }
+
+    fn signed(timestamp: i64, secret_key: &str) -> (String, String) {
+        let payload =
+            request_signature::signing_payload(timestamp, "GET", "/api/v1/gateway/me", b"");
+        let signature = request_signature::sign(secret_key, &payload);
+        (payload, signature)
+    }
+
+    #[tokio::test]
+    async fn test_valid_signature_resolves_context() {
+        let fixture = Fixture::new();
+        let site_id = fixture.site.id;
+        let now = Utc::now().timestamp();
+        let (payload, signature) = signed(now, SECRET_KEY);
+
+        let context = fixture
+            .use_case()
+            .execute("pk_test", now, &payload, &signature, None)
+            .await
+            .unwrap();
+
+        assert_eq!(context.site.id, site_id);
+        assert_eq!(context.merchant.id, context.site.merchant_id);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_signature_and_unknown_key() {
+        let now = Utc::now().timestamp();
+        let (payload, signature) = signed(now, "sk_other");
+
+        let result = Fixture::new()
+            .use_case()
+            .execute("pk_test", now, &payload, &signature, None)
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let (payload, signature) = signed(now, SECRET_KEY);
+        let result = Fixture::new()
+            .use_case()
+            .execute("pk_unknown", now, &payload, &signature, None)
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_timestamp_outside_window() {
+        let stale = Utc::now().timestamp() - 6 * 60;
+        let (payload, signature) = signed(stale, SECRET_KEY);
+
+        let result = Fixture::new()
+            .use_case()
+            .execute("pk_test", stale, &payload, &signature, None)
+            .await;
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_extreme_timestamps() {
+        let use_case = Fixture::new().use_case();
+
+        for timestamp in [i64::MIN, i64::MAX] {
+            let (payload, signature) = signed(timestamp, SECRET_KEY);
+            let result = use_case
+                .execute("pk_test", timestamp, &payload, &signature, None)
+                .await;
+
+            assert!(matches!(result, Err(AppError::Unauthorized(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejects_revoked_key_and_inactive_merchant() {
+        let now = Utc::now().timestamp();
+        let (payload, signature) = signed(now, SECRET_KEY);
+
+        let mut fixture = Fixture::new();
+        fixture.credentials.revoke();
+        let result = fixture
+            .use_case()
+            .execute("pk_test", now, &payload, &signature, None)
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let mut fixture = Fixture::new();
+        fixture.merchant.status = MerchantStatus::Inactive;
+        let result = fixture
+            .use_case()
+            .execute("pk_test", now, &payload, &signature, None)
+            .await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_client_ip_outside_allowlist() {
+        let now = Utc::now().timestamp();
+        let (payload, signature) = signed(now, SECRET_KEY);
+
+        let mut fixture = Fixture::new();
+        fixture
+            .credentials
+            .update_allowed_ips(vec!["203.0.113.0/24".to_string()], false)
+            .unwrap();
+        let use_case = fixture.use_case();
+
+        let result = use_case
+            .execute(
+                "pk_test",
+                now,
+                &payload,
+                &signature,
+                "198.51.100.1".parse().ok(),
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+        let result = use_case
+            .execute("pk_test", now, &payload, &signature, None)
+            .await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+        let result = use_case
+            .execute(
+                "pk_test",
+                now,
+                &payload,
+                &signature,
+                "203.0.113.9".parse().ok(),
+            )
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_every_ip_without_allowlist() {
+        let now = Utc::now().timestamp();
+        let (payload, signature) = signed(now, SECRET_KEY);
+
+        let mut fixture = Fixture::new();
+        fixture.credentials.allow_any_ip = false;
+
+        let result = fixture
+            .use_case()
+            .execute(
+                "pk_test",
+                now,
+                &payload,
+                &signature,
+                "203.0.113.9".parse().ok(),
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+}
@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, MERCHANT_ENTITY},
+        backoffice::domain::{
+            merchant::{Merchant, MerchantStatus},
+            repository::MerchantRepository,
+        },
+    },
+};
+
+pub struct ChangeMerchantStatusUseCase {
+    merchant_repository: Arc<dyn MerchantRepository>,
+}
+
+impl ChangeMerchantStatusUseCase {
+    pub fn new(merchant_repository: Arc<dyn MerchantRepository>) -> Self {
+        Self {
+            merchant_repository,
+        }
+    }
+
+    /// Applies a status transition and records who made it and why
+    pub async fn execute(
+        &self,
+        merchant_id: Uuid,
+        status: MerchantStatus,
+        reason: Option<String>,
+        context: &AuditContext,
+    ) -> Result<Merchant, AppError> {
+        tracing::debug!("Changing status of merchant {} to {}", merchant_id, status);
+
+        let mut merchant = self.find_merchant(merchant_id).await?;
+        let before = merchant.audit_snapshot();
+
+        let has_active_site_with_credentials =
+            if merchant.status == MerchantStatus::Onboarding && status == MerchantStatus::Active {
+                self.merchant_repository
+                    .has_active_site_with_credentials(merchant_id)
+                    .await?
+            } else {
+                false
+            };
+
+        let change = merchant.transition_to(
+            status,
+            reason,
+            has_active_site_with_credentials,
+            context.actor_id,
+        )?;
+        let previous = change.from_status;
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            MERCHANT_ENTITY,
+            merchant.id,
+            Some(&before),
+            Some(&merchant.audit_snapshot()),
+        );
+        let updated_merchant = self
+            .merchant_repository
+            .change_status(merchant, change, audit)
+            .await?;
+
+        tracing::info!(
+            "Merchant {} status changed from {} to {}",
+            merchant_id,
+            previous,
+            status
+        );
+
+        Ok(updated_merchant)
+    }
+
+    /// Marks KYB checks as completed, a precondition for activation
+    pub async fn verify_kyb(
+        &self,
+        merchant_id: Uuid,
+        context: &AuditContext,
+    ) -> Result<Merchant, AppError> {
+        tracing::debug!("Marking KYB as verified for merchant {}", merchant_id);
+
+        let mut merchant = self.find_merchant(merchant_id).await?;
+
+        if merchant.is_kyb_verified() {
+            return Ok(merchant);
+        }
+
+        let before = merchant.audit_snapshot();
+        merchant.verify_kyb();
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            MERCHANT_ENTITY,
+            merchant.id,
+            Some(&before),
+            Some(&merchant.audit_snapshot()),
+        );
+        let updated_merchant = self.merchant_repository.update(merchant, audit).await?;
+
+        tracing::info!("Merchant {} KYB verified", merchant_id);
+
+        Ok(updated_merchant)
+    }
+
+    async fn find_merchant(&self, merchant_id: Uuid) -> Result<Merchant, AppError> {
+        self.merchant_repository
+            .find_by_id(merchant_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Merchant {} not found",
+                merchant_id
+            )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::domain::repository::MockMerchantRepository;
+
+    #[tokio::test]
+    async fn test_change_status_records_history() {
+        let mut merchant = Merchant::new("Acme".to_string(), None);
+        merchant.status = MerchantStatus::Active;
+        let merchant_id = merchant.id;
+        let user_id = Uuid::new_v4();
+
+        let mut merchant_repository = MockMerchantRepository::new();
+        merchant_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(merchant.clone())));
+        merchant_repository
+            .expect_has_active_site_with_credentials()
+            .never();
+        merchant_repository
+            .expect_change_status()
+            .withf(move |merchant, change, audit| {
+                merchant.status == MerchantStatus::Inactive
+                    && change.from_status == MerchantStatus::Active
+                    && change.reason.as_deref() == Some("Fraud review")
+                    && change.changed_by == Some(user_id)
+                    && audit.actor_id == Some(user_id)
+                    && audit.changes["status"]["after"] == "inactive"
+            })
+            .times(1)
+            .returning(|merchant, _, _| Ok(merchant));
+
+        let use_case = ChangeMerchantStatusUseCase::new(Arc::new(merchant_repository));
+
+        let merchant = use_case
+            .execute(
+                merchant_id,
+                MerchantStatus::Inactive,
+                Some("Fraud review".to_string()),
+                &AuditContext {
+                    actor_id: Some(user_id),
+                    ..AuditContext::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(merchant.status, MerchantStatus::Inactive);
+    }
+
+    #[tokio::test]
+    async fn test_activation_without_site_is_rejected() {
+        let mut merchant = Merchant::new("Acme".to_string(), None);
+        merchant.verify_kyb();
+        let merchant_id = merchant.id;
+
+        let mut merchant_repository = MockMerchantRepository::new();
+        merchant_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(merchant.clone())));
+        merchant_repository
+            .expect_has_active_site_with_credentials()
+            .returning(|_| Ok(false));
+        merchant_repository.expect_change_status().never();
+
+        let use_case = ChangeMerchantStatusUseCase::new(Arc::new(merchant_repository));
+
+        let result = use_case
+            .execute(
+                merchant_id,
+                MerchantStatus::Active,
+                None,
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+    }
+}
@@ -0,0 +1,221 @@
+use std::sync::Arc;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    common::{
+        error::AppError,
+        hash_utils::{hash_password, verify_password},
+    },
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, USER_ENTITY},
+        backoffice::{
+            domain::{password_policy::PasswordPolicy, user::User},
+            dto::user_dto::ChangePasswordRequest,
+            UserRepository,
+        },
+    },
+};
+
+/// Sets new passwords that pass the password policy and were not used recently
+pub struct ChangePasswordUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    password_policy: Arc<PasswordPolicy>,
+}
+
+impl ChangePasswordUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        password_policy: Arc<PasswordPolicy>,
+    ) -> Self {
+        Self {
+            user_repository,
+            password_policy,
+        }
+    }
+
+    /// The user changes their own password; other sessions stay signed in
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        request: ChangePasswordRequest,
+        context: &AuditContext,
+    ) -> Result<User, AppError> {
+        tracing::debug!("Changing password of user {}", user_id);
+
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("User {} not found", user_id)))?;
+
+        if !verify_password(&request.current_password, &user.password_hash)? {
+            tracing::warn!("Wrong current password given by user {}", user_id);
+            return Err(AppError::ValidationError(
+                "Current password is incorrect".to_string(),
+            ));
+        }
+
+        self.check(&user, &request.new_password).await?;
+        let user = self
+            .store(user, &request.new_password, "changed", context)
+            .await?;
+
+        tracing::info!("User {} changed their password", user_id);
+
+        Ok(user)
+    }
+
+    /// Checks `new_password` against the policy and the user's password history
+    pub async fn check(&self, user: &User, new_password: &str) -> Result<(), AppError> {
+        self.password_policy.validate(new_password)?;
+
+        let history = self
+            .user_repository
+            .password_history(user.id, self.password_policy.history_size)
+            .await?;
+        self.password_policy.check_reuse(new_password, &history)
+    }
+
+    /// Stores a password that passed `check`. `how` is recorded in the audit entry,
+    /// the password itself never is.
+    pub async fn store(
+        &self,
+        mut user: User,
+        new_password: &str,
+        how: &str,
+        context: &AuditContext,
+    ) -> Result<User, AppError> {
+        user.update_password_hash(hash_password(new_password)?);
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            USER_ENTITY,
+            user.id,
+            None,
+            Some(&json!({ "password": how })),
+        );
+
+        self.user_repository.change_password(user, audit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::config::PasswordConfig,
+        domains::backoffice::{
+            domain::repository::MockUserRepository,
+            role::model::{user_role_id, Role},
+        },
+    };
+    use chrono::Utc;
+
+    const CURRENT_PASSWORD: &str = "Current-Password-1";
+
+    fn user() -> User {
+        let role = Role::new(
+            user_role_id(),
+            "User".to_string(),
+            None,
+            Utc::now(),
+            Utc::now(),
+        );
+        User::new(
+            "walter".to_string(),
+            "walter@example.com".to_string(),
+            hash_password(CURRENT_PASSWORD).unwrap(),
+            role,
+        )
+    }
+
+    fn use_case(repository: MockUserRepository) -> ChangePasswordUseCase {
+        let policy = PasswordPolicy::from_config(&PasswordConfig::default()).unwrap();
+        ChangePasswordUseCase::new(Arc::new(repository), Arc::new(policy))
+    }
+
+    fn request(current_password: &str, new_password: &str) -> ChangePasswordRequest {
+        ChangePasswordRequest {
+            current_password: current_password.to_string(),
+            new_password: new_password.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stores_new_hash_and_audits_without_password() {
+        let user = user();
+        let user_id = user.id;
+        let current_hash = user.password_hash.clone();
+
+        let mut repository = MockUserRepository::new();
+        repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user.clone())));
+        repository
+            .expect_password_history()
+            .withf(move |id, limit| *id == user_id && *limit == 5)
+            .returning(move |_, _| Ok(vec![current_hash.clone()]));
+        repository
+            .expect_change_password()
+            .withf(|user, audit| {
+                verify_password("New-Password-42", &user.password_hash).unwrap()
+                    && audit.changes
+                        == json!({ "password": { "before": null, "after": "changed" } })
+            })
+            .returning(|user, _| Ok(user));
+
+        let changed = use_case(repository)
+            .execute(
+                user_id,
+                request(CURRENT_PASSWORD, "New-Password-42"),
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(changed.id, user_id);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_wrong_current_and_reused_password() {
+        let user = user();
+        let user_id = user.id;
+        let current_hash = user.password_hash.clone();
+
+        let mut repository = MockUserRepository::new();
+        repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user.clone())));
+        repository
+            .expect_password_history()
+            .returning(move |_, _| Ok(vec![current_hash.clone()]));
+        repository.expect_change_password().never();
+        let use_case = use_case(repository);
+
+        let error = use_case
+            .execute(
+                user_id,
+                request("Wrong-Password-1", "New-Password-42"),
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AppError::ValidationError(_)));
+
+        let error = use_case
+            .execute(
+                user_id,
+                request(CURRENT_PASSWORD, CURRENT_PASSWORD),
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Validation error: Password must differ from the last 5 passwords"
+        );
+    }
+}
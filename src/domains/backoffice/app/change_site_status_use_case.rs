@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, SITE_ENTITY},
+        backoffice::{
+            app::get_site_use_case::find_merchant_site,
+            domain::{
+                merchant::{Site, SiteStatus},
+                repository::SiteRepository,
+            },
+        },
+    },
+};
+
+pub struct ChangeSiteStatusUseCase {
+    site_repository: Arc<dyn SiteRepository>,
+}
+
+impl ChangeSiteStatusUseCase {
+    pub fn new(site_repository: Arc<dyn SiteRepository>) -> Self {
+        Self { site_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        merchant_id: Uuid,
+        site_id: Uuid,
+        status: SiteStatus,
+        context: &AuditContext,
+    ) -> Result<Site, AppError> {
+        tracing::debug!("Changing status of site {} to {}", site_id, status);
+
+        let mut site =
+            find_merchant_site(self.site_repository.as_ref(), merchant_id, site_id).await?;
+
+        if site.status == status {
+            return Ok(site);
+        }
+
+        let before = site.audit_snapshot();
+        site.change_status(status);
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            SITE_ENTITY,
+            site.id,
+            Some(&before),
+            Some(&site.audit_snapshot()),
+        );
+        let updated_site = self.site_repository.update(site, audit).await?;
+
+        tracing::info!("Site {} is now {}", site_id, status);
+
+        Ok(updated_site)
+    }
+}
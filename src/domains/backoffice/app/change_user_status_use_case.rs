@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, USER_ENTITY},
+        backoffice::{
+            app::revoke_user_sessions_use_case::RevokeUserSessionsUseCase, domain::user::User,
+            UserRepository,
+        },
+    },
+};
+
+/// Deactivates and reactivates backoffice users without deleting them
+pub struct ChangeUserStatusUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    revoke_user_sessions_use_case: Arc<RevokeUserSessionsUseCase>,
+}
+
+impl ChangeUserStatusUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        revoke_user_sessions_use_case: Arc<RevokeUserSessionsUseCase>,
+    ) -> Self {
+        Self {
+            user_repository,
+            revoke_user_sessions_use_case,
+        }
+    }
+
+    /// Blocks the user from signing in and ends their sessions
+    pub async fn deactivate(
+        &self,
+        user_id: Uuid,
+        context: &AuditContext,
+    ) -> Result<User, AppError> {
+        tracing::debug!("Deactivating user {}", user_id);
+
+        if context.actor_id == Some(user_id) {
+            return Err(AppError::ValidationError(
+                "You cannot deactivate your own account".to_string(),
+            ));
+        }
+
+        let mut user = self.find_user(user_id).await?;
+        if !user.is_active() {
+            return Err(AppError::InvalidStateTransition(format!(
+                "User {} is already inactive",
+                user_id
+            )));
+        }
+
+        let before = user.audit_snapshot();
+        user.deactivate();
+        let user = self.save(user, before, context).await?;
+
+        // Tokens of inactive users are rejected anyway; revoking them keeps them dead
+        // after a reactivation
+        self.revoke_user_sessions_use_case
+            .execute(user_id, context)
+            .await?;
+
+        tracing::info!("User {} deactivated", user_id);
+
+        Ok(user)
+    }
+
+    /// Lets the user sign in again; earlier sessions stay revoked
+    pub async fn activate(&self, user_id: Uuid, context: &AuditContext) -> Result<User, AppError> {
+        tracing::debug!("Activating user {}", user_id);
+
+        let mut user = self.find_user(user_id).await?;
+        if user.is_active() {
+            return Err(AppError::InvalidStateTransition(format!(
+                "User {} is already active",
+                user_id
+            )));
+        }
+
+        let before = user.audit_snapshot();
+        user.activate();
+        let user = self.save(user, before, context).await?;
+
+        tracing::info!("User {} activated", user_id);
+
+        Ok(user)
+    }
+
+    async fn find_user(&self, user_id: Uuid) -> Result<User, AppError> {
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("User {} not found", user_id)))
+    }
+
+    async fn save(
+        &self,
+        user: User,
+        before: serde_json::Value,
+        context: &AuditContext,
+    ) -> Result<User, AppError> {
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            USER_ENTITY,
+            user.id,
+            Some(&before),
+            Some(&user.audit_snapshot()),
+        );
+
+        self.user_repository.update(user, audit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Integration tests would require database setup
+}
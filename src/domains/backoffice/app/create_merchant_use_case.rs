@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, MERCHANT_ENTITY},
+        backoffice::{
+            domain::{merchant::Merchant, repository::MerchantRepository},
+            dto::merchant_dto::CreateMerchantRequest,
+        },
+    },
+};
+
+pub struct CreateMerchantUseCase {
+    merchant_repository: Arc<dyn MerchantRepository>,
+}
+
+impl CreateMerchantUseCase {
+    pub fn new(merchant_repository: Arc<dyn MerchantRepository>) -> Self {
+        Self {
+            merchant_repository,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        request: CreateMerchantRequest,
+        context: &AuditContext,
+    ) -> Result<Merchant, AppError> {
+        tracing::debug!("Creating merchant '{}'", request.name);
+
+        let name = validate_merchant_name(&request.name)?;
+
+        // Check name uniqueness
+        if self.merchant_repository.exists_by_name(&name).await? {
+            return Err(AppError::ValidationError(format!(
+                "Merchant '{}' already exists",
+                name
+            )));
+        }
+
+        let merchant = Merchant::new(name, request.description);
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Create,
+            MERCHANT_ENTITY,
+            merchant.id,
+            None,
+            Some(&merchant.audit_snapshot()),
+        );
+        let merchant = self.merchant_repository.create(merchant, audit).await?;
+
+        tracing::info!("Merchant {} created successfully", merchant.id);
+
+        Ok(merchant)
+    }
+}
+
+/// Trimmed, non-empty merchant name
+pub(crate) fn validate_merchant_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(AppError::ValidationError(
+            "Merchant name cannot be empty".to_string(),
+        ));
+    }
+
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::domain::{
+        merchant::MerchantStatus, repository::MockMerchantRepository,
+    };
+
+    #[tokio::test]
+    async fn test_create_merchant_starts_onboarding() {
+        let mut merchant_repository = MockMerchantRepository::new();
+        merchant_repository
+            .expect_exists_by_name()
+            .returning(|_| Ok(false));
+        merchant_repository
+            .expect_create()
+            .withf(|merchant, audit| {
+                merchant.name == "Acme"
+                    && audit.action == AuditAction::Create
+                    && audit.changes["status"]["after"] == "onboarding"
+            })
+            .times(1)
+            .returning(|merchant, _| Ok(merchant));
+
+        let use_case = CreateMerchantUseCase::new(Arc::new(merchant_repository));
+
+        let merchant = use_case
+            .execute(
+                CreateMerchantRequest {
+                    name: "  Acme ".to_string(),
+                    description: None,
+                },
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(merchant.status, MerchantStatus::Onboarding);
+    }
+
+    #[tokio::test]
+    async fn test_create_merchant_with_duplicate_name() {
+        let mut merchant_repository = MockMerchantRepository::new();
+        merchant_repository
+            .expect_exists_by_name()
+            .returning(|_| Ok(true));
+        merchant_repository.expect_create().never();
+
+        let use_case = CreateMerchantUseCase::new(Arc::new(merchant_repository));
+
+        let result = use_case
+            .execute(
+                CreateMerchantRequest {
+                    name: "Acme".to_string(),
+                    description: None,
+                },
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, ROLE_ENTITY},
+        backoffice::{
+            dto::role_dto::CreateRoleRequest,
+            role::{
+                model::{Role, RoleDetails},
+                repository::RoleRepository,
+            },
+        },
+    },
+};
+
+pub struct CreateRoleUseCase {
+    role_repository: Arc<dyn RoleRepository>,
+}
+
+impl CreateRoleUseCase {
+    pub fn new(role_repository: Arc<dyn RoleRepository>) -> Self {
+        Self { role_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        request: CreateRoleRequest,
+        context: &AuditContext,
+    ) -> Result<RoleDetails, AppError> {
+        tracing::debug!("Creating role '{}'", request.role_name);
+
+        let role_name = validate_role_name(&request.role_name)?;
+
+        // Check name uniqueness
+        if self
+            .role_repository
+            .find_by_name(&role_name)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::ValidationError(format!(
+                "Role '{}' already exists",
+                role_name
+            )));
+        }
+
+        let permissions =
+            validate_permissions(self.role_repository.as_ref(), request.permissions).await?;
+
+        let role = Role::create(role_name, request.role_description);
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Create,
+            ROLE_ENTITY,
+            role.role_id,
+            None,
+            Some(&role.audit_snapshot(&permissions)),
+        );
+        let role = self
+            .role_repository
+            .create(role, permissions.clone(), audit)
+            .await?;
+
+        tracing::info!("Role {} created successfully", role.role_id);
+
+        Ok(RoleDetails { role, permissions })
+    }
+}
+
+/// Trimmed, non-empty role name
+pub(crate) fn validate_role_name(role_name: &str) -> Result<String, AppError> {
+    let role_name = role_name.trim();
+
+    if role_name.is_empty() {
+        return Err(AppError::ValidationError(
+            "Role name cannot be empty".to_string(),
+        ));
+    }
+
+    Ok(role_name.to_string())
+}
+
+/// Sorted, de-duplicated permission names; all of them must exist
+pub(crate) async fn validate_permissions(
+    role_repository: &dyn RoleRepository,
+    mut permissions: Vec<String>,
+) -> Result<Vec<String>, AppError> {
+    permissions.sort();
+    permissions.dedup();
+
+    let known = role_repository.list_permissions().await?;
+
+    if let Some(unknown) = permissions
+        .iter()
+        .find(|name| !known.iter().any(|p| &p.permission_name == *name))
+    {
+        return Err(AppError::ValidationError(format!(
+            "Unknown permission '{}'",
+            unknown
+        )));
+    }
+
+    Ok(permissions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::role::{
+        model::support_role_id, permission::Permission, repository::MockRoleRepository,
+    };
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn known_permissions() -> Vec<Permission> {
+        ["users:read", "users:write"]
+            .into_iter()
+            .map(|name| Permission {
+                permission_id: Uuid::new_v4(),
+                permission_name: name.to_string(),
+                permission_description: None,
+                created_at: Utc::now(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_create_role_with_permissions() {
+        let mut role_repository = MockRoleRepository::new();
+        role_repository
+            .expect_find_by_name()
+            .returning(|_| Ok(None));
+        role_repository
+            .expect_list_permissions()
+            .returning(|| Ok(known_permissions()));
+        role_repository
+            .expect_create()
+            .withf(|role, permissions, audit| {
+                role.role_name == "Auditor"
+                    && permissions == &["users:read".to_string()]
+                    && audit.entity_id == role.role_id
+                    && audit.changes["permissions"]["after"] == serde_json::json!(["users:read"])
+            })
+            .times(1)
+            .returning(|role, _, _| Ok(role));
+
+        let use_case = CreateRoleUseCase::new(Arc::new(role_repository));
+
+        let details = use_case
+            .execute(
+                CreateRoleRequest {
+                    role_name: " Auditor ".to_string(),
+                    role_description: None,
+                    permissions: vec!["users:read".to_string(), "users:read".to_string()],
+                },
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(details.role.role_name, "Auditor");
+        assert!(!details.role.is_system());
+    }
+
+    #[tokio::test]
+    async fn test_create_role_with_duplicate_name() {
+        let mut role_repository = MockRoleRepository::new();
+        role_repository.expect_find_by_name().returning(|_| {
+            Ok(Some(Role::new(
+                support_role_id(),
+                "Support".to_string(),
+                None,
+                Utc::now(),
+                Utc::now(),
+            )))
+        });
+        role_repository.expect_create().never();
+
+        let use_case = CreateRoleUseCase::new(Arc::new(role_repository));
+
+        let result = use_case
+            .execute(
+                CreateRoleRequest {
+                    role_name: "Support".to_string(),
+                    role_description: None,
+                    permissions: Vec::new(),
+                },
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_role_with_unknown_permission() {
+        let mut role_repository = MockRoleRepository::new();
+        role_repository
+            .expect_find_by_name()
+            .returning(|_| Ok(None));
+        role_repository
+            .expect_list_permissions()
+            .returning(|| Ok(known_permissions()));
+        role_repository.expect_create().never();
+
+        let use_case = CreateRoleUseCase::new(Arc::new(role_repository));
+
+        let result = use_case
+            .execute(
+                CreateRoleRequest {
+                    role_name: "Auditor".to_string(),
+                    role_description: None,
+                    permissions: vec!["everything:all".to_string()],
+                },
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
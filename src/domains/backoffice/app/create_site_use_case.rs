@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, SITE_ENTITY},
+        backoffice::{
+            domain::{
+                merchant::Site,
+                repository::{MerchantRepository, SiteRepository},
+            },
+            dto::site_dto::CreateSiteRequest,
+        },
+    },
+};
+
+pub struct CreateSiteUseCase {
+    merchant_repository: Arc<dyn MerchantRepository>,
+    site_repository: Arc<dyn SiteRepository>,
+    require_https: bool,
+}
+
+impl CreateSiteUseCase {
+    pub fn new(
+        merchant_repository: Arc<dyn MerchantRepository>,
+        site_repository: Arc<dyn SiteRepository>,
+        require_https: bool,
+    ) -> Self {
+        Self {
+            merchant_repository,
+            site_repository,
+            require_https,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        merchant_id: Uuid,
+        request: CreateSiteRequest,
+        context: &AuditContext,
+    ) -> Result<Site, AppError> {
+        tracing::debug!(
+            "Creating site '{}' for merchant {}",
+            request.name,
+            merchant_id
+        );
+
+        if self
+            .merchant_repository
+            .find_by_id(merchant_id)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::NotFound(format!(
+                "Merchant {} not found",
+                merchant_id
+            )));
+        }
+
+        let name = validate_site_name(&request.name)?;
+
+        let site = Site::new(
+            merchant_id,
+            name,
+            request.url.trim().to_string(),
+            request.callback_url.trim().to_string(),
+            request.redirect_success_url.trim().to_string(),
+            request.redirect_fail_url.trim().to_string(),
+        );
+        site.validate_urls(self.require_https)?;
+
+        if self.site_repository.exists_by_name(&site.name).await? {
+            return Err(AppError::ValidationError(format!(
+                "Site '{}' already exists",
+                site.name
+            )));
+        }
+
+        if self.site_repository.exists_by_url(&site.url).await? {
+            return Err(AppError::ValidationError(format!(
+                "Site with url '{}' already exists",
+                site.url
+            )));
+        }
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Create,
+            SITE_ENTITY,
+            site.id,
+            None,
+            Some(&site.audit_snapshot()),
+        );
+        let site = self.site_repository.create(site, audit).await?;
+
+        tracing::info!("Site {} created for merchant {}", site.id, merchant_id);
+
+        Ok(site)
+    }
+}
+
+/// Trimmed, non-empty site name
+pub(crate) fn validate_site_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(AppError::ValidationError(
+            "Site name cannot be empty".to_string(),
+        ));
+    }
+
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::domain::{
+        merchant::{Merchant, SiteStatus},
+        repository::{MockMerchantRepository, MockSiteRepository},
+    };
+
+    fn request(url: &str) -> CreateSiteRequest {
+        CreateSiteRequest {
+            name: " Shop ".to_string(),
+            url: url.to_string(),
+            callback_url: format!("{}/callback", url),
+            redirect_success_url: format!("{}/ok", url),
+            redirect_fail_url: format!("{}/fail", url),
+        }
+    }
+
+    fn merchant_repository() -> MockMerchantRepository {
+        let mut merchant_repository = MockMerchantRepository::new();
+        merchant_repository
+            .expect_find_by_id()
+            .returning(|_| Ok(Some(Merchant::new("Acme".to_string(), None))));
+        merchant_repository
+    }
+
+    #[tokio::test]
+    async fn test_create_site() {
+        let mut site_repository = MockSiteRepository::new();
+        site_repository
+            .expect_exists_by_name()
+            .returning(|_| Ok(false));
+        site_repository
+            .expect_exists_by_url()
+            .returning(|_| Ok(false));
+        site_repository
+            .expect_create()
+            .withf(|site, audit| site.name == "Shop" && audit.entity_id == site.id)
+            .times(1)
+            .returning(|site, _| Ok(site));
+
+        let use_case = CreateSiteUseCase::new(
+            Arc::new(merchant_repository()),
+            Arc::new(site_repository),
+            true,
+        );
+
+        let site = use_case
+            .execute(
+                Uuid::new_v4(),
+                request("https://acme.com"),
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(site.status, SiteStatus::Active);
+    }
+
+    #[tokio::test]
+    async fn test_create_site_requires_https() {
+        let mut site_repository = MockSiteRepository::new();
+        site_repository.expect_create().never();
+
+        let use_case = CreateSiteUseCase::new(
+            Arc::new(merchant_repository()),
+            Arc::new(site_repository),
+            true,
+        );
+
+        let result = use_case
+            .execute(
+                Uuid::new_v4(),
+                request("http://acme.com"),
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
@@ -2,29 +2,41 @@ use std::sync::Arc;
 
 use crate::{
     common::{error::AppError, hash_utils::hash_password},
-    domains::backoffice::{
-        domain::user::User, dto::user_dto::CreateUserRequest, role::repository::RoleRepository,
-        UserRepository,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, USER_ENTITY},
+        backoffice::{
+            domain::{password_policy::PasswordPolicy, user::User},
+            dto::user_dto::CreateUserRequest,
+            role::repository::RoleRepository,
+            UserRepository,
+        },
     },
 };
 
 pub struct CreateUserUseCase {
     user_repository: Arc<dyn UserRepository>,
     role_repository: Arc<dyn RoleRepository>,
+    password_policy: Arc<PasswordPolicy>,
 }
 
 impl CreateUserUseCase {
     pub fn new(
         user_repository: Arc<dyn UserRepository>,
         role_repository: Arc<dyn RoleRepository>,
+        password_policy: Arc<PasswordPolicy>,
     ) -> Self {
         Self {
             user_repository,
             role_repository,
+            password_policy,
         }
     }
 
-    pub async fn execute(&self, request: CreateUserRequest) -> Result<User, AppError> {
+    pub async fn execute(
+        &self,
+        request: CreateUserRequest,
+        context: &AuditContext,
+    ) -> Result<User, AppError> {
         tracing::debug!(
             "Creating user '{}' with role_id {}",
             request.username,
@@ -61,14 +73,23 @@ impl CreateUserUseCase {
             )));
         }
 
-        // Hash password
+        // Hash password that passes the policy; a new user has no history to compare with
+        self.password_policy.validate(&request.password)?;
         let password_hash = hash_password(&request.password)?;
 
         // Create user domain model
         let user = User::new(request.username, request.email, password_hash, role);
 
-        // Save to database
-        let created_user = self.user_repository.create(user).await?;
+        // Save to database together with its audit entry
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Create,
+            USER_ENTITY,
+            user.id,
+            None,
+            Some(&user.audit_snapshot()),
+        );
+        let created_user = self.user_repository.create(user, audit).await?;
 
         tracing::info!("User {} created successfully", created_user.id);
 
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, ROLE_ENTITY},
+        backoffice::role::repository::RoleRepository,
+    },
+};
+
+pub struct DeleteRoleUseCase {
+    role_repository: Arc<dyn RoleRepository>,
+}
+
+impl DeleteRoleUseCase {
+    pub fn new(role_repository: Arc<dyn RoleRepository>) -> Self {
+        Self { role_repository }
+    }
+
+    pub async fn execute(&self, role_id: Uuid, context: &AuditContext) -> Result<(), AppError> {
+        tracing::debug!("Deleting role {}", role_id);
+
+        let role = self
+            .role_repository
+            .find_by_id(role_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("Role {} not found", role_id)))?;
+
+        if role.is_system() {
+            return Err(AppError::Forbidden(format!(
+                "System role '{}' cannot be deleted",
+                role.role_name
+            )));
+        }
+
+        // users.role_id is ON DELETE RESTRICT, report it before hitting the FK
+        let users = self.role_repository.count_users(role_id).await?;
+        if users > 0 {
+            return Err(AppError::ValidationError(format!(
+                "Role '{}' is still assigned to {} user(s)",
+                role.role_name, users
+            )));
+        }
+
+        let permissions = self.role_repository.find_permission_names(role_id).await?;
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Delete,
+            ROLE_ENTITY,
+            role_id,
+            Some(&role.audit_snapshot(&permissions)),
+            None,
+        );
+        self.role_repository.delete(role_id, audit).await?;
+
+        tracing::info!("Role {} deleted successfully", role_id);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::role::{
+        model::{admin_role_id, Role},
+        repository::MockRoleRepository,
+    };
+    use chrono::Utc;
+
+    #[tokio::test]
+    async fn test_system_role_cannot_be_deleted() {
+        let mut role_repository = MockRoleRepository::new();
+        role_repository.expect_find_by_id().returning(|_| {
+            Ok(Some(Role::new(
+                admin_role_id(),
+                "Admin".to_string(),
+                None,
+                Utc::now(),
+                Utc::now(),
+            )))
+        });
+        role_repository.expect_delete().never();
+
+        let use_case = DeleteRoleUseCase::new(Arc::new(role_repository));
+
+        let result = use_case
+            .execute(admin_role_id(), &AuditContext::default())
+            .await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_role_in_use_cannot_be_deleted() {
+        let role = Role::create("Auditor".to_string(), None);
+        let role_id = role.role_id;
+
+        let mut role_repository = MockRoleRepository::new();
+        role_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(role.clone())));
+        role_repository.expect_count_users().returning(|_| Ok(2));
+        role_repository.expect_delete().never();
+
+        let use_case = DeleteRoleUseCase::new(Arc::new(role_repository));
+
+        let result = use_case.execute(role_id, &AuditContext::default()).await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
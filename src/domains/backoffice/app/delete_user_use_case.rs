@@ -2,7 +2,13 @@ use std::sync::Arc;
 
 use uuid::Uuid;
 
-use crate::{common::error::AppError, domains::backoffice::UserRepository};
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, USER_ENTITY},
+        backoffice::UserRepository,
+    },
+};
 
 pub struct DeleteUserUseCase {
     user_repository: Arc<dyn UserRepository>,
@@ -13,18 +19,27 @@ impl DeleteUserUseCase {
         Self { user_repository }
     }
 
-    pub async fn execute(&self, user_id: Uuid) -> Result<(), AppError> {
+    pub async fn execute(&self, user_id: Uuid, context: &AuditContext) -> Result<(), AppError> {
         tracing::debug!("Deleting user {}", user_id);
 
         // Verify user exists before attempting deletion
-        let _user = self
+        let user = self
             .user_repository
             .find_by_id(user_id)
             .await?
             .ok_or(AppError::NotFound(format!("User {} not found", user_id)))?;
 
-        // Delete from database
-        self.user_repository.delete(user_id).await?;
+        // Soft-delete together with its audit entry; the row stays for records that
+        // reference the user
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Delete,
+            USER_ENTITY,
+            user.id,
+            Some(&user.audit_snapshot()),
+            None,
+        );
+        self.user_repository.delete(user_id, audit).await?;
 
         tracing::info!("User {} deleted successfully", user_id);
 
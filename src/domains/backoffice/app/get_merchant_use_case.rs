@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::backoffice::domain::{
+        merchant::{Merchant, MerchantStatusChange},
+        repository::MerchantRepository,
+    },
+};
+
+pub struct GetMerchantUseCase {
+    merchant_repository: Arc<dyn MerchantRepository>,
+}
+
+impl GetMerchantUseCase {
+    pub fn new(merchant_repository: Arc<dyn MerchantRepository>) -> Self {
+        Self {
+            merchant_repository,
+        }
+    }
+
+    pub async fn execute(&self, merchant_id: Uuid) -> Result<Merchant, AppError> {
+        tracing::debug!("Fetching merchant {}", merchant_id);
+
+        let merchant = self
+            .merchant_repository
+            .find_by_id(merchant_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Merchant {} not found",
+                merchant_id
+            )))?;
+
+        Ok(merchant)
+    }
+
+    pub async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Merchant>, AppError> {
+        tracing::debug!("Listing merchants with limit={}, offset={}", limit, offset);
+
+        if limit > 100 {
+            return Err(AppError::ValidationError(
+                "Limit cannot exceed 100".to_string(),
+            ));
+        }
+
+        self.merchant_repository.list(limit, offset).await
+    }
+
+    pub async fn search(&self, query: &str, limit: Option<i64>) -> Result<Vec<Merchant>, AppError> {
+        let search_limit = limit.unwrap_or(20);
+
+        if search_limit > 100 {
+            return Err(AppError::ValidationError(
+                "Limit cannot exceed 100".to_string(),
+            ));
+        }
+
+        tracing::debug!(
+            "Searching merchants with query='{}', limit={}",
+            query,
+            search_limit
+        );
+
+        self.merchant_repository.search(query, search_limit).await
+    }
+
+    pub async fn status_history(
+        &self,
+        merchant_id: Uuid,
+    ) -> Result<Vec<MerchantStatusChange>, AppError> {
+        tracing::debug!("Fetching status history of merchant {}", merchant_id);
+
+        self.execute(merchant_id).await?;
+
+        self.merchant_repository.status_history(merchant_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::domain::repository::MockMerchantRepository;
+
+    #[tokio::test]
+    async fn test_list_limit_is_capped() {
+        let mut merchant_repository = MockMerchantRepository::new();
+        merchant_repository.expect_list().never();
+
+        let use_case = GetMerchantUseCase::new(Arc::new(merchant_repository));
+
+        let result = use_case.list(101, 0).await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
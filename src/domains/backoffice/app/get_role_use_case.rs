@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::backoffice::role::{
+        model::{Role, RoleDetails},
+        repository::RoleRepository,
+    },
+};
+
+pub struct GetRoleUseCase {
+    role_repository: Arc<dyn RoleRepository>,
+}
+
+impl GetRoleUseCase {
+    pub fn new(role_repository: Arc<dyn RoleRepository>) -> Self {
+        Self { role_repository }
+    }
+
+    pub async fn execute(&self, role_id: Uuid) -> Result<RoleDetails, AppError> {
+        tracing::debug!("Fetching role {}", role_id);
+
+        let role = self
+            .role_repository
+            .find_by_id(role_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("Role {} not found", role_id)))?;
+
+        self.with_permissions(role).await
+    }
+
+    pub async fn list(&self) -> Result<Vec<RoleDetails>, AppError> {
+        tracing::debug!("Listing roles");
+
+        let roles = self.role_repository.list_all().await?;
+
+        let mut details = Vec::with_capacity(roles.len());
+        for role in roles {
+            details.push(self.with_permissions(role).await?);
+        }
+
+        Ok(details)
+    }
+
+    async fn with_permissions(&self, role: Role) -> Result<RoleDetails, AppError> {
+        let permissions = self
+            .role_repository
+            .find_permission_names(role.role_id)
+            .await?;
+
+        Ok(RoleDetails { role, permissions })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::role::repository::MockRoleRepository;
+
+    #[tokio::test]
+    async fn test_get_missing_role() {
+        let mut role_repository = MockRoleRepository::new();
+        role_repository.expect_find_by_id().returning(|_| Ok(None));
+
+        let use_case = GetRoleUseCase::new(Arc::new(role_repository));
+
+        let result = use_case.execute(Uuid::new_v4()).await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
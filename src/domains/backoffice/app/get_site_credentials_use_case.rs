@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::backoffice::{
+        app::get_site_use_case::find_merchant_site,
+        domain::{
+            merchant::SiteCredentials,
+            repository::{SiteCredentialsRepository, SiteRepository},
+        },
+    },
+};
+
+pub struct GetSiteCredentialsUseCase {
+    site_repository: Arc<dyn SiteRepository>,
+    site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+}
+
+impl GetSiteCredentialsUseCase {
+    pub fn new(
+        site_repository: Arc<dyn SiteRepository>,
+        site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+    ) -> Self {
+        Self {
+            site_repository,
+            site_credentials_repository,
+        }
+    }
+
+    pub async fn list(
+        &self,
+        merchant_id: Uuid,
+        site_id: Uuid,
+    ) -> Result<Vec<SiteCredentials>, AppError> {
+        tracing::debug!("Listing credentials of site {}", site_id);
+
+        find_merchant_site(self.site_repository.as_ref(), merchant_id, site_id).await?;
+
+        self.site_credentials_repository.list_by_site(site_id).await
+    }
+}
+
+/// Credentials of the given site; keys of other sites are reported as missing
+pub(crate) async fn find_site_credentials(
+    site_credentials_repository: &dyn SiteCredentialsRepository,
+    site_id: Uuid,
+    credentials_id: Uuid,
+) -> Result<SiteCredentials, AppError> {
+    site_credentials_repository
+        .find_by_id(credentials_id)
+        .await?
+        .filter(|credentials| credentials.site_id == site_id)
+        .ok_or(AppError::NotFound(format!(
+            "Credentials {} not found",
+            credentials_id
+        )))
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::backoffice::domain::{
+        merchant::Site,
+        repository::{MerchantRepository, SiteRepository},
+    },
+};
+
+pub struct GetSiteUseCase {
+    merchant_repository: Arc<dyn MerchantRepository>,
+    site_repository: Arc<dyn SiteRepository>,
+}
+
+impl GetSiteUseCase {
+    pub fn new(
+        merchant_repository: Arc<dyn MerchantRepository>,
+        site_repository: Arc<dyn SiteRepository>,
+    ) -> Self {
+        Self {
+            merchant_repository,
+            site_repository,
+        }
+    }
+
+    pub async fn execute(&self, merchant_id: Uuid, site_id: Uuid) -> Result<Site, AppError> {
+        tracing::debug!("Fetching site {} of merchant {}", site_id, merchant_id);
+
+        find_merchant_site(self.site_repository.as_ref(), merchant_id, site_id).await
+    }
+
+    pub async fn list(&self, merchant_id: Uuid) -> Result<Vec<Site>, AppError> {
+        tracing::debug!("Listing sites of merchant {}", merchant_id);
+
+        if self
+            .merchant_repository
+            .find_by_id(merchant_id)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::NotFound(format!(
+                "Merchant {} not found",
+                merchant_id
+            )));
+        }
+
+        self.site_repository.list_by_merchant(merchant_id).await
+    }
+}
+
+/// Site that belongs to the given merchant; sites of other merchants are reported as missing
+pub(crate) async fn find_merchant_site(
+    site_repository: &dyn SiteRepository,
+    merchant_id: Uuid,
+    site_id: Uuid,
+) -> Result<Site, AppError> {
+    site_repository
+        .find_by_id(site_id)
+        .await?
+        .filter(|site| site.merchant_id == merchant_id)
+        .ok_or(AppError::NotFound(format!("Site {} not found", site_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::domain::repository::{
+        MockMerchantRepository, MockSiteRepository,
+    };
+
+    #[tokio::test]
+    async fn test_site_of_other_merchant_is_not_found() {
+        let site = Site::new(
+            Uuid::new_v4(),
+            "Shop".to_string(),
+            "https://acme.com".to_string(),
+            "https://acme.com/callback".to_string(),
+            "https://acme.com/ok".to_string(),
+            "https://acme.com/fail".to_string(),
+        );
+        let site_id = site.id;
+
+        let mut site_repository = MockSiteRepository::new();
+        site_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(site.clone())));
+
+        let use_case = GetSiteUseCase::new(
+            Arc::new(MockMerchantRepository::new()),
+            Arc::new(site_repository),
+        );
+
+        let result = use_case.execute(Uuid::new_v4(), site_id).await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,321 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::{
+    common::{error::AppError, hash_utils::generate_token, secret_cipher::SecretCipher},
+    domains::{
+        audit::domain::audit_entry::{
+            AuditAction, AuditContext, AuditEntry, SITE_CREDENTIALS_ENTITY,
+        },
+        backoffice::{
+            app::get_site_use_case::find_merchant_site,
+            domain::{
+                merchant::{IssuedSiteCredentials, SiteCredentials},
+                repository::{SiteCredentialsRepository, SiteRepository},
+            },
+        },
+    },
+};
+
+const PUBLIC_KEY_PREFIX: &str = "pk_";
+const SECRET_KEY_PREFIX: &str = "sk_";
+
+pub struct IssueSiteCredentialsUseCase {
+    site_repository: Arc<dyn SiteRepository>,
+    site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+    secret_cipher: Arc<SecretCipher>,
+    rotation_overlap: Duration,
+}
+
+impl IssueSiteCredentialsUseCase {
+    pub fn new(
+        site_repository: Arc<dyn SiteRepository>,
+        site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+        secret_cipher: Arc<SecretCipher>,
+        rotation_overlap: Duration,
+    ) -> Self {
+        Self {
+            site_repository,
+            site_credentials_repository,
+            secret_cipher,
+            rotation_overlap,
+        }
+    }
+
+    /// First key pair of a site; sites that already have a working key must rotate it
+    pub async fn execute(
+        &self,
+        merchant_id: Uuid,
+        site_id: Uuid,
+        context: &AuditContext,
+    ) -> Result<IssuedSiteCredentials, AppError> {
+        tracing::debug!("Issuing credentials for site {}", site_id);
+
+        find_merchant_site(self.site_repository.as_ref(), merchant_id, site_id).await?;
+
+        let now = Utc::now();
+        let existing = self
+            .site_credentials_repository
+            .list_by_site(site_id)
+            .await?;
+        if existing
+            .iter()
+            .any(|credentials| credentials.is_usable_at(now))
+        {
+            return Err(AppError::ValidationError(format!(
+                "Site {} already has active credentials, rotate them instead",
+                site_id
+            )));
+        }
+
+        let (credentials, secret_key) = self.generate(site_id)?;
+        let audit = Self::created(context, &credentials);
+        let credentials = self
+            .site_credentials_repository
+            .create(credentials, audit)
+            .await?;
+
+        tracing::info!(
+            "Credentials {} issued for site {}",
+            credentials.public_key,
+            site_id
+        );
+
+        Ok(IssuedSiteCredentials {
+            credentials,
+            secret_key,
+        })
+    }
+
+    /// Issues a new key pair; the current ones keep working for the overlap window
+    pub async fn rotate(
+        &self,
+        merchant_id: Uuid,
+        site_id: Uuid,
+        context: &AuditContext,
+    ) -> Result<IssuedSiteCredentials, AppError> {
+        tracing::debug!("Rotating credentials of site {}", site_id);
+
+        find_merchant_site(self.site_repository.as_ref(), merchant_id, site_id).await?;
+
+        let now = Utc::now();
+        let mut audit = Vec::new();
+        let replaced: Vec<SiteCredentials> = self
+            .site_credentials_repository
+            .list_by_site(site_id)
+            .await?
+            .into_iter()
+            .filter(|credentials| credentials.is_usable_at(now))
+            .map(|mut credentials| {
+                let before = credentials.audit_snapshot();
+                credentials.expire_at(now + self.rotation_overlap);
+                audit.push(AuditEntry::new(
+                    context,
+                    AuditAction::Update,
+                    SITE_CREDENTIALS_ENTITY,
+                    credentials.id,
+                    Some(&before),
+                    Some(&credentials.audit_snapshot()),
+                ));
+                credentials
+            })
+            .collect();
+        let replaced_count = replaced.len();
+
+        let (mut credentials, secret_key) = self.generate(site_id)?;
+        // The allowlist belongs to the integration, not to a single key
+        if let Some(previous) = replaced.first() {
+            credentials.allowed_ips = previous.allowed_ips.clone();
+            credentials.allow_any_ip = previous.allow_any_ip;
+        }
+        audit.push(Self::created(context, &credentials));
+
+        let credentials = self
+            .site_credentials_repository
+            .rotate(credentials, replaced, audit)
+            .await?;
+
+        tracing::info!(
+            "Credentials of site {} rotated, {} previous key(s) expire in {} minutes",
+            site_id,
+            replaced_count,
+            self.rotation_overlap.num_minutes()
+        );
+
+        Ok(IssuedSiteCredentials {
+            credentials,
+            secret_key,
+        })
+    }
+
+    fn created(context: &AuditContext, credentials: &SiteCredentials) -> AuditEntry {
+        AuditEntry::new(
+            context,
+            AuditAction::Create,
+            SITE_CREDENTIALS_ENTITY,
+            credentials.id,
+            None,
+            Some(&credentials.audit_snapshot()),
+        )
+    }
+
+    fn generate(&self, site_id: Uuid) -> Result<(SiteCredentials, String), AppError> {
+        let public_key = format!("{}{}", PUBLIC_KEY_PREFIX, generate_token(16));
+        let secret_key = format!("{}{}", SECRET_KEY_PREFIX, generate_token(32));
+        let encrypted_secret_key = self.secret_cipher.encrypt(&secret_key)?;
+
+        Ok((
+            SiteCredentials::new(site_id, public_key, encrypted_secret_key),
+            secret_key,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::domain::{
+        merchant::Site,
+        repository::{MockSiteCredentialsRepository, MockSiteRepository},
+    };
+
+    fn site_repository(site: Site) -> MockSiteRepository {
+        let mut site_repository = MockSiteRepository::new();
+        site_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(site.clone())));
+        site_repository
+    }
+
+    fn site() -> Site {
+        Site::new(
+            Uuid::new_v4(),
+            "Shop".to_string(),
+            "https://acme.com".to_string(),
+            "https://acme.com/callback".to_string(),
+            "https://acme.com/ok".to_string(),
+            "https://acme.com/fail".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_issue_stores_only_encrypted_secret() {
+        let site = site();
+        let (merchant_id, site_id) = (site.merchant_id, site.id);
+        let cipher = Arc::new(SecretCipher::new("test"));
+
+        let mut credentials_repository = MockSiteCredentialsRepository::new();
+        credentials_repository
+            .expect_list_by_site()
+            .returning(|_| Ok(Vec::new()));
+        credentials_repository
+            .expect_create()
+            .withf(|credentials, audit| {
+                !credentials
+                    .encrypted_secret_key
+                    .starts_with(SECRET_KEY_PREFIX)
+                    && audit.entity_id == credentials.id
+                    && audit.changes.get("encrypted_secret_key").is_none()
+            })
+            .times(1)
+            .returning(|credentials, _| Ok(credentials));
+
+        let use_case = IssueSiteCredentialsUseCase::new(
+            Arc::new(site_repository(site)),
+            Arc::new(credentials_repository),
+            Arc::clone(&cipher),
+            Duration::hours(1),
+        );
+
+        let issued = use_case
+            .execute(merchant_id, site_id, &AuditContext::default())
+            .await
+            .unwrap();
+
+        assert!(issued.credentials.public_key.starts_with(PUBLIC_KEY_PREFIX));
+        assert!(issued.secret_key.starts_with(SECRET_KEY_PREFIX));
+        assert_eq!(
+            cipher
+                .decrypt(&issued.credentials.encrypted_secret_key)
+                .unwrap(),
+            issued.secret_key
+        );
+    }
+
+    #[tokio::test]
+    async fn test_issue_when_active_key_exists() {
+        let site = site();
+        let (merchant_id, site_id) = (site.merchant_id, site.id);
+
+        let mut credentials_repository = MockSiteCredentialsRepository::new();
+        credentials_repository
+            .expect_list_by_site()
+            .returning(move |_| {
+                Ok(vec![SiteCredentials::new(
+                    site_id,
+                    "pk_old".to_string(),
+                    "enc".to_string(),
+                )])
+            });
+        credentials_repository.expect_create().never();
+
+        let use_case = IssueSiteCredentialsUseCase::new(
+            Arc::new(site_repository(site)),
+            Arc::new(credentials_repository),
+            Arc::new(SecretCipher::new("test")),
+            Duration::hours(1),
+        );
+
+        let result = use_case
+            .execute(merchant_id, site_id, &AuditContext::default())
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_keeps_old_key_for_overlap() {
+        let site = site();
+        let (merchant_id, site_id) = (site.merchant_id, site.id);
+
+        let mut credentials_repository = MockSiteCredentialsRepository::new();
+        credentials_repository
+            .expect_list_by_site()
+            .returning(move |_| {
+                let mut old =
+                    SiteCredentials::new(site_id, "pk_old".to_string(), "enc".to_string());
+                old.allowed_ips = vec!["10.0.0.0/8".to_string()];
+                Ok(vec![old])
+            });
+        credentials_repository
+            .expect_rotate()
+            .withf(|credentials, replaced, audit| {
+                let expires_at = replaced[0].expires_at.unwrap();
+                credentials.allowed_ips == vec!["10.0.0.0/8".to_string()]
+                    && expires_at > Utc::now() + Duration::minutes(59)
+                    && expires_at <= Utc::now() + Duration::hours(1)
+                    && audit.len() == 2
+                    && audit[0].entity_id == replaced[0].id
+                    && audit[0].changes["expires_at"]["before"].is_null()
+                    && audit[1].action == AuditAction::Create
+            })
+            .times(1)
+            .returning(|credentials, _, _| Ok(credentials));
+
+        let use_case = IssueSiteCredentialsUseCase::new(
+            Arc::new(site_repository(site)),
+            Arc::new(credentials_repository),
+            Arc::new(SecretCipher::new("test")),
+            Duration::hours(1),
+        );
+
+        let issued = use_case
+            .rotate(merchant_id, site_id, &AuditContext::default())
+            .await
+            .unwrap();
+
+        assert_ne!(issued.credentials.public_key, "pk_old");
+    }
+}
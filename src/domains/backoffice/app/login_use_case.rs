@@ -0,0 +1,221 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+use rand::Rng;
+
+use crate::{
+    common::{
+        error::AppError,
+        hash_utils::{hash_password, verify_password},
+        mailer::{MailMessage, Mailer},
+    },
+    domains::backoffice::{
+        domain::{
+            login_challenge::LoginChallenge, repository::LoginChallengeRepository, user::User,
+        },
+        dto::auth_dto::LoginRequest,
+        UserRepository,
+    },
+};
+
+/// 1st login step: checks login/password and emails a one-time code
+pub struct LoginUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    challenge_repository: Arc<dyn LoginChallengeRepository>,
+    mailer: Arc<dyn Mailer>,
+    code_ttl: Duration,
+}
+
+impl LoginUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        challenge_repository: Arc<dyn LoginChallengeRepository>,
+        mailer: Arc<dyn Mailer>,
+        code_ttl: Duration,
+    ) -> Self {
+        Self {
+            user_repository,
+            challenge_repository,
+            mailer,
+            code_ttl,
+        }
+    }
+
+    pub async fn execute(&self, request: LoginRequest) -> Result<LoginChallenge, AppError> {
+        tracing::debug!("Login attempt for '{}'", request.login);
+
+        let user = self
+            .find_user(&request.login)
+            .await?
+            .ok_or_else(Self::invalid_credentials)?;
+
+        if !verify_password(&request.password, &user.password_hash)? {
+            tracing::warn!("Invalid password for user {}", user.id);
+            return Err(Self::invalid_credentials());
+        }
+
+        if !user.is_active() {
+            tracing::warn!("Login attempt for inactive user {}", user.id);
+            return Err(Self::invalid_credentials());
+        }
+
+        // Issue challenge with hashed one-time code
+        let code = generate_code();
+        let challenge = LoginChallenge::new(user.id, hash_password(&code)?, self.code_ttl);
+        let challenge = self.challenge_repository.create(challenge).await?;
+
+        self.mailer
+            .send(MailMessage::new(
+                user.email.clone(),
+                "Your login code",
+                format!(
+                    "Your login confirmation code is {}. It expires in {} minutes.",
+                    code,
+                    self.code_ttl.num_minutes()
+                ),
+            ))
+            .await?;
+
+        tracing::info!(
+            "Login challenge {} issued for user {}",
+            challenge.id,
+            user.id
+        );
+
+        Ok(challenge)
+    }
+
+    async fn find_user(&self, login: &str) -> Result<Option<User>, AppError> {
+        if let Some(user) = self.user_repository.find_by_username(login).await? {
+            return Ok(Some(user));
+        }
+
+        self.user_repository.find_by_email(login).await
+    }
+
+    fn invalid_credentials() -> AppError {
+        AppError::Unauthorized("Invalid login or password".to_string())
+    }
+}
+
+/// 6-digit numeric code, zero padded
+fn generate_code() -> String {
+    format!("{:06}", rand::thread_rng().gen_range(0..1_000_000))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::domain::repository::{
+        MockLoginChallengeRepository, MockUserRepository,
+    };
+    use crate::domains::backoffice::role::model::{user_role_id, Role};
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingMailer {
+        sent: Mutex<Vec<MailMessage>>,
+    }
+
+    #[async_trait]
+    impl Mailer for RecordingMailer {
+        async fn send(&self, message: MailMessage) -> Result<(), AppError> {
+            self.sent.lock().unwrap().push(message);
+            Ok(())
+        }
+    }
+
+    fn create_test_user(password: &str) -> User {
+        let role = Role::new(
+            user_role_id(),
+            "User".to_string(),
+            None,
+            Utc::now(),
+            Utc::now(),
+        );
+
+        User::new(
+            "testuser".to_string(),
+            "test@example.com".to_string(),
+            hash_password(password).unwrap(),
+            role,
+        )
+    }
+
+    #[test]
+    fn test_generate_code_format() {
+        for _ in 0..100 {
+            let code = generate_code();
+            assert_eq!(code.len(), 6);
+            assert!(code.chars().all(|c| c.is_ascii_digit()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_issues_challenge_and_sends_code() {
+        let user = create_test_user("password123");
+        let user_id = user.id;
+
+        let mut user_repository = MockUserRepository::new();
+        user_repository
+            .expect_find_by_username()
+            .returning(move |_| Ok(Some(user.clone())));
+
+        let mut challenge_repository = MockLoginChallengeRepository::new();
+        challenge_repository.expect_create().times(1).returning(Ok);
+
+        let mailer = Arc::new(RecordingMailer::default());
+
+        let use_case = LoginUseCase::new(
+            Arc::new(user_repository),
+            Arc::new(challenge_repository),
+            mailer.clone(),
+            Duration::minutes(5),
+        );
+
+        let challenge = use_case
+            .execute(LoginRequest {
+                login: "testuser".to_string(),
+                password: "password123".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(challenge.user_id, user_id);
+
+        let sent = mailer.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, "test@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_login_with_wrong_password() {
+        let user = create_test_user("password123");
+
+        let mut user_repository = MockUserRepository::new();
+        user_repository
+            .expect_find_by_username()
+            .returning(move |_| Ok(Some(user.clone())));
+
+        let mut challenge_repository = MockLoginChallengeRepository::new();
+        challenge_repository.expect_create().never();
+
+        let use_case = LoginUseCase::new(
+            Arc::new(user_repository),
+            Arc::new(challenge_repository),
+            Arc::new(RecordingMailer::default()),
+            Duration::minutes(5),
+        );
+
+        let result = use_case
+            .execute(LoginRequest {
+                login: "testuser".to_string(),
+                password: "wrong".to_string(),
+            })
+            .await;
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::{
+    common::{error::AppError, jwt::Claims},
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, SESSION_ENTITY},
+        backoffice::domain::repository::{RefreshTokenRepository, TokenRevocationRepository},
+    },
+};
+
+pub struct LogoutUseCase {
+    token_revocation_repository: Arc<dyn TokenRevocationRepository>,
+    refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+}
+
+impl LogoutUseCase {
+    pub fn new(
+        token_revocation_repository: Arc<dyn TokenRevocationRepository>,
+        refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+    ) -> Self {
+        Self {
+            token_revocation_repository,
+            refresh_token_repository,
+        }
+    }
+
+    /// Every token issued at login carries its session, so the audit entry goes with
+    /// ending that session
+    pub async fn execute(&self, claims: &Claims, context: &AuditContext) -> Result<(), AppError> {
+        tracing::debug!("Logging out user {} (token {})", claims.user_id, claims.jti);
+
+        self.token_revocation_repository
+            .revoke_token(claims.jti, claims.user_id, claims.expires_at())
+            .await?;
+
+        // End the login session so its refresh token stops working too
+        if let Some(session_id) = claims.sid {
+            let audit = AuditEntry::new(
+                context,
+                AuditAction::Delete,
+                SESSION_ENTITY,
+                claims.user_id,
+                Some(&json!({ "session_id": session_id })),
+                None,
+            );
+            self.refresh_token_repository
+                .revoke_family(session_id, Some(audit))
+                .await?;
+        }
+
+        tracing::info!("User {} logged out", claims.user_id);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::domain::repository::{
+        MockRefreshTokenRepository, MockTokenRevocationRepository,
+    };
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_logout_revokes_current_token_and_session() {
+        let session_id = Uuid::new_v4();
+        let claims = Claims::new(Uuid::new_v4(), Uuid::new_v4(), "User".to_string(), 24)
+            .with_session(session_id);
+        let jti = claims.jti;
+
+        let mut repository = MockTokenRevocationRepository::new();
+        repository
+            .expect_revoke_token()
+            .withf(move |revoked_jti, _, _| *revoked_jti == jti)
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let mut refresh_token_repository = MockRefreshTokenRepository::new();
+        refresh_token_repository
+            .expect_revoke_family()
+            .withf(move |family_id, audit| {
+                *family_id == session_id
+                    && audit.as_ref().is_some_and(|audit| {
+                        audit.entity_type == SESSION_ENTITY
+                            && audit.changes["session_id"]["before"] == session_id.to_string()
+                    })
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let use_case = LogoutUseCase::new(Arc::new(repository), Arc::new(refresh_token_repository));
+
+        assert!(use_case
+            .execute(&claims, &AuditContext::default())
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+use uuid::Uuid;
+
+use crate::{
+    common::{
+        error::AppError,
+        hash_utils::{generate_token, sha256_hex},
+        mailer::{MailMessage, Mailer},
+    },
+    domains::{
+        audit::domain::audit_entry::AuditContext,
+        backoffice::{
+            app::{
+                change_password_use_case::ChangePasswordUseCase,
+                revoke_user_sessions_use_case::RevokeUserSessionsUseCase,
+            },
+            domain::{
+                password_reset_token::PasswordResetToken, repository::PasswordResetTokenRepository,
+            },
+            dto::auth_dto::ResetPasswordRequest,
+            UserRepository,
+        },
+    },
+};
+
+/// Admin-initiated password reset: the user gets a one-time link by email
+/// and chooses a new password with it
+pub struct PasswordResetUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    reset_token_repository: Arc<dyn PasswordResetTokenRepository>,
+    change_password_use_case: Arc<ChangePasswordUseCase>,
+    revoke_user_sessions_use_case: Arc<RevokeUserSessionsUseCase>,
+    mailer: Arc<dyn Mailer>,
+    token_ttl: Duration,
+    reset_url: String,
+}
+
+impl PasswordResetUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        reset_token_repository: Arc<dyn PasswordResetTokenRepository>,
+        change_password_use_case: Arc<ChangePasswordUseCase>,
+        revoke_user_sessions_use_case: Arc<RevokeUserSessionsUseCase>,
+        mailer: Arc<dyn Mailer>,
+        token_ttl: Duration,
+        reset_url: String,
+    ) -> Self {
+        Self {
+            user_repository,
+            reset_token_repository,
+            change_password_use_case,
+            revoke_user_sessions_use_case,
+            mailer,
+            token_ttl,
+            reset_url,
+        }
+    }
+
+    /// Emails a reset link to the user; links sent before stop working
+    pub async fn request(
+        &self,
+        user_id: Uuid,
+        context: &AuditContext,
+    ) -> Result<PasswordResetToken, AppError> {
+        tracing::debug!("Password reset of user {} requested", user_id);
+
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("User {} not found", user_id)))?;
+
+        if !user.is_active() {
+            return Err(AppError::InvalidStateTransition(format!(
+                "User {} is inactive",
+                user_id
+            )));
+        }
+
+        let token = generate_token(32);
+        let reset_token = PasswordResetToken::new(
+            user.id,
+            sha256_hex(&token),
+            context.actor_id,
+            self.token_ttl,
+        );
+        let reset_token = self.reset_token_repository.create(reset_token).await?;
+
+        self.mailer
+            .send(MailMessage::new(
+                user.email.clone(),
+                "Reset your password",
+                format!(
+                    "An administrator asked for a reset of your password. Open {} to choose \
+                     a new one. The link works once and expires in {} minutes.",
+                    self.reset_link(&token),
+                    self.token_ttl.num_minutes()
+                ),
+            ))
+            .await?;
+
+        tracing::info!(
+            "Password reset link {} sent to user {}",
+            reset_token.id,
+            user.id
+        );
+
+        Ok(reset_token)
+    }
+
+    /// Sets the new password and signs the user out everywhere
+    pub async fn complete(
+        &self,
+        request: ResetPasswordRequest,
+        context: &AuditContext,
+    ) -> Result<(), AppError> {
+        let reset_token = self
+            .reset_token_repository
+            .find_by_hash(&sha256_hex(&request.token))
+            .await?
+            .ok_or_else(Self::invalid_link)?;
+
+        if reset_token.is_used() || reset_token.is_expired() {
+            return Err(Self::invalid_link());
+        }
+
+        let user = self
+            .user_repository
+            .find_by_id(reset_token.user_id)
+            .await?
+            .filter(|user| user.is_active())
+            .ok_or_else(Self::invalid_link)?;
+
+        // The user sets the password, so the change is theirs
+        let context = AuditContext {
+            actor_id: Some(user.id),
+            ..context.clone()
+        };
+
+        // Fail on the policy before spending the link
+        self.change_password_use_case
+            .check(&user, &request.new_password)
+            .await?;
+        if !self
+            .reset_token_repository
+            .mark_used(reset_token.id)
+            .await?
+        {
+            return Err(Self::invalid_link());
+        }
+
+        let user = self
+            .change_password_use_case
+            .store(user, &request.new_password, "reset", &context)
+            .await?;
+        self.revoke_user_sessions_use_case
+            .execute(user.id, &context)
+            .await?;
+
+        tracing::info!(
+            "User {} reset their password with link {}",
+            user.id,
+            reset_token.id
+        );
+
+        Ok(())
+    }
+
+    fn reset_link(&self, token: &str) -> String {
+        let separator = if self.reset_url.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        format!("{}{}token={}", self.reset_url, separator, token)
+    }
+
+    fn invalid_link() -> AppError {
+        AppError::Unauthorized("Invalid or expired reset link".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Integration tests would require database setup
+}
@@ -0,0 +1,221 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::{error::AppError, hash_utils::sha256_hex},
+    domains::backoffice::{
+        app::token_issuer::{IssuedTokens, TokenIssuer},
+        domain::repository::RefreshTokenRepository,
+        UserRepository,
+    },
+};
+
+/// Exchanges a refresh token for a new token pair, rotating the refresh token.
+/// Presenting an already rotated token revokes the whole token family.
+pub struct RefreshTokenUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+    token_issuer: Arc<TokenIssuer>,
+}
+
+impl RefreshTokenUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+        token_issuer: Arc<TokenIssuer>,
+    ) -> Self {
+        Self {
+            user_repository,
+            refresh_token_repository,
+            token_issuer,
+        }
+    }
+
+    pub async fn execute(&self, refresh_token: &str) -> Result<IssuedTokens, AppError> {
+        let token = self
+            .refresh_token_repository
+            .find_by_hash(&sha256_hex(refresh_token))
+            .await?
+            .ok_or_else(Self::invalid_token)?;
+
+        tracing::debug!("Refreshing session {}", token.family_id);
+
+        if token.is_revoked() {
+            return Err(Self::invalid_token());
+        }
+
+        if token.is_rotated() {
+            return self.reject_reuse(token.family_id).await;
+        }
+
+        if token.is_expired() {
+            return Err(Self::invalid_token());
+        }
+
+        // Lost a race with a concurrent refresh of the same token
+        if !self.refresh_token_repository.mark_rotated(token.id).await? {
+            return self.reject_reuse(token.family_id).await;
+        }
+
+        let user = match self.user_repository.find_by_id(token.user_id).await? {
+            Some(user) if user.is_active() => user,
+            _ => {
+                self.refresh_token_repository
+                    .revoke_family(token.family_id, None)
+                    .await?;
+                return Err(Self::invalid_token());
+            }
+        };
+
+        let tokens = self.token_issuer.issue(&user, token.family_id).await?;
+
+        tracing::info!("Session {} of user {} refreshed", token.family_id, user.id);
+
+        Ok(tokens)
+    }
+
+    async fn reject_reuse(&self, family_id: Uuid) -> Result<IssuedTokens, AppError> {
+        tracing::warn!(
+            "Refresh token reuse detected, revoking session {}",
+            family_id
+        );
+
+        self.refresh_token_repository
+            .revoke_family(family_id, None)
+            .await?;
+
+        Err(AppError::Unauthorized(
+            "Refresh token reuse detected, session revoked".to_string(),
+        ))
+    }
+
+    fn invalid_token() -> AppError {
+        AppError::Unauthorized("Invalid or expired refresh token".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::jwt::JwtService;
+    use crate::domains::backoffice::domain::{
+        refresh_token::RefreshToken,
+        repository::{MockRefreshTokenRepository, MockUserRepository},
+        user::User,
+    };
+    use crate::domains::backoffice::role::{
+        model::{user_role_id, Role},
+        repository::MockRoleRepository,
+    };
+    use chrono::{Duration, Utc};
+
+    fn create_test_user() -> User {
+        let role = Role::new(
+            user_role_id(),
+            "User".to_string(),
+            None,
+            Utc::now(),
+            Utc::now(),
+        );
+
+        User::new(
+            "testuser".to_string(),
+            "test@example.com".to_string(),
+            "hash".to_string(),
+            role,
+        )
+    }
+
+    fn create_use_case(
+        user_repository: MockUserRepository,
+        refresh_token_repository: MockRefreshTokenRepository,
+    ) -> RefreshTokenUseCase {
+        let refresh_token_repository = Arc::new(refresh_token_repository);
+        let mut role_repository = MockRoleRepository::new();
+        role_repository
+            .expect_find_permission_names()
+            .returning(|_| Ok(Vec::new()));
+
+        let token_issuer = Arc::new(TokenIssuer::new(
+            refresh_token_repository.clone(),
+            Arc::new(role_repository),
+            Arc::new(JwtService::new("test_secret_key")),
+            Duration::days(30),
+        ));
+
+        RefreshTokenUseCase::new(
+            Arc::new(user_repository),
+            refresh_token_repository,
+            token_issuer,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rotates_token_in_same_family() {
+        let user = create_test_user();
+        let stored = RefreshToken::new(
+            user.id,
+            Uuid::new_v4(),
+            sha256_hex("refresh"),
+            Duration::days(30),
+        );
+        let family_id = stored.family_id;
+
+        let mut refresh_token_repository = MockRefreshTokenRepository::new();
+        refresh_token_repository
+            .expect_find_by_hash()
+            .returning(move |_| Ok(Some(stored.clone())));
+        refresh_token_repository
+            .expect_mark_rotated()
+            .times(1)
+            .returning(|_| Ok(true));
+        refresh_token_repository
+            .expect_create()
+            .withf(move |token| token.family_id == family_id)
+            .times(1)
+            .returning(Ok);
+        refresh_token_repository.expect_revoke_family().never();
+
+        let mut user_repository = MockUserRepository::new();
+        user_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user.clone())));
+
+        let use_case = create_use_case(user_repository, refresh_token_repository);
+
+        let tokens = use_case.execute("refresh").await.unwrap();
+
+        assert_ne!(tokens.refresh_token, "refresh");
+        assert_eq!(tokens.expires_in, 15 * 60);
+    }
+
+    #[tokio::test]
+    async fn test_reused_token_revokes_family() {
+        let mut stored = RefreshToken::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            sha256_hex("refresh"),
+            Duration::days(30),
+        );
+        stored.rotated_at = Some(Utc::now());
+        let family_id = stored.family_id;
+
+        let mut refresh_token_repository = MockRefreshTokenRepository::new();
+        refresh_token_repository
+            .expect_find_by_hash()
+            .returning(move |_| Ok(Some(stored.clone())));
+        refresh_token_repository
+            .expect_revoke_family()
+            .withf(move |id, audit| *id == family_id && audit.is_none())
+            .times(1)
+            .returning(|_, _| Ok(()));
+        refresh_token_repository.expect_create().never();
+
+        let use_case = create_use_case(MockUserRepository::new(), refresh_token_repository);
+
+        let result = use_case.execute("refresh").await;
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+}
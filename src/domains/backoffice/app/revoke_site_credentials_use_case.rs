@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{
+            AuditAction, AuditContext, AuditEntry, SITE_CREDENTIALS_ENTITY,
+        },
+        backoffice::{
+            app::{
+                get_site_credentials_use_case::find_site_credentials,
+                get_site_use_case::find_merchant_site,
+            },
+            domain::{
+                merchant::SiteCredentials,
+                repository::{SiteCredentialsRepository, SiteRepository},
+            },
+        },
+    },
+};
+
+pub struct RevokeSiteCredentialsUseCase {
+    site_repository: Arc<dyn SiteRepository>,
+    site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+}
+
+impl RevokeSiteCredentialsUseCase {
+    pub fn new(
+        site_repository: Arc<dyn SiteRepository>,
+        site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+    ) -> Self {
+        Self {
+            site_repository,
+            site_credentials_repository,
+        }
+    }
+
+    /// Disables the key immediately, ignoring any remaining rotation overlap
+    pub async fn execute(
+        &self,
+        merchant_id: Uuid,
+        site_id: Uuid,
+        credentials_id: Uuid,
+        context: &AuditContext,
+    ) -> Result<SiteCredentials, AppError> {
+        tracing::debug!(
+            "Revoking credentials {} of site {}",
+            credentials_id,
+            site_id
+        );
+
+        find_merchant_site(self.site_repository.as_ref(), merchant_id, site_id).await?;
+        let mut credentials = find_site_credentials(
+            self.site_credentials_repository.as_ref(),
+            site_id,
+            credentials_id,
+        )
+        .await?;
+
+        if credentials.revoked_at.is_some() {
+            return Ok(credentials);
+        }
+
+        let before = credentials.audit_snapshot();
+        credentials.revoke();
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            SITE_CREDENTIALS_ENTITY,
+            credentials.id,
+            Some(&before),
+            Some(&credentials.audit_snapshot()),
+        );
+        let credentials = self
+            .site_credentials_repository
+            .update(credentials, audit)
+            .await?;
+
+        tracing::info!("Credentials {} of site {} revoked", credentials_id, site_id);
+
+        Ok(credentials)
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    common::{error::AppError, time_formater},
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, SESSION_ENTITY},
+        backoffice::{
+            domain::repository::{RefreshTokenRepository, TokenRevocationRepository},
+            UserRepository,
+        },
+    },
+};
+
+/// Signs a user out everywhere by revoking every token issued so far
+pub struct RevokeUserSessionsUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    token_revocation_repository: Arc<dyn TokenRevocationRepository>,
+    refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+}
+
+impl RevokeUserSessionsUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        token_revocation_repository: Arc<dyn TokenRevocationRepository>,
+        refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+    ) -> Self {
+        Self {
+            user_repository,
+            token_revocation_repository,
+            refresh_token_repository,
+        }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, context: &AuditContext) -> Result<(), AppError> {
+        tracing::debug!("Revoking all sessions of user {}", user_id);
+
+        let _user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("User {} not found", user_id)))?;
+
+        let revoked_before = Utc::now();
+        self.token_revocation_repository
+            .revoke_all_for_user(user_id, revoked_before)
+            .await?;
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            SESSION_ENTITY,
+            user_id,
+            None,
+            Some(&json!({ "revoked_before": time_formater::format(&revoked_before) })),
+        );
+        self.refresh_token_repository
+            .revoke_all_for_user(user_id, audit)
+            .await?;
+
+        tracing::info!("All sessions of user {} revoked", user_id);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Integration tests would require database setup
+}
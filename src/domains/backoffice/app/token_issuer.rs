@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+use uuid::Uuid;
+
+use crate::{
+    common::{
+        error::AppError,
+        hash_utils::{generate_token, sha256_hex},
+        jwt::JwtService,
+    },
+    domains::backoffice::{
+        domain::{refresh_token::RefreshToken, repository::RefreshTokenRepository, user::User},
+        role::repository::RoleRepository,
+    },
+};
+
+/// Access + refresh token pair handed to the client
+#[derive(Debug, Clone)]
+pub struct IssuedTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Access token lifetime in seconds
+    pub expires_in: i64,
+}
+
+/// Issues a short-lived access JWT together with an opaque refresh token
+/// for a login session (refresh token family). The role's permissions are
+/// resolved on every issue, so grant changes apply from the next refresh.
+pub struct TokenIssuer {
+    refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+    role_repository: Arc<dyn RoleRepository>,
+    jwt_service: Arc<JwtService>,
+    refresh_token_ttl: Duration,
+}
+
+impl TokenIssuer {
+    pub fn new(
+        refresh_token_repository: Arc<dyn RefreshTokenRepository>,
+        role_repository: Arc<dyn RoleRepository>,
+        jwt_service: Arc<JwtService>,
+        refresh_token_ttl: Duration,
+    ) -> Self {
+        Self {
+            refresh_token_repository,
+            role_repository,
+            jwt_service,
+            refresh_token_ttl,
+        }
+    }
+
+    pub async fn issue(&self, user: &User, family_id: Uuid) -> Result<IssuedTokens, AppError> {
+        let refresh_token = generate_token(32);
+
+        self.refresh_token_repository
+            .create(RefreshToken::new(
+                user.id,
+                family_id,
+                sha256_hex(&refresh_token),
+                self.refresh_token_ttl,
+            ))
+            .await?;
+
+        let permissions = self
+            .role_repository
+            .find_permission_names(user.role.role_id)
+            .await?;
+
+        let claims = self
+            .jwt_service
+            .access_claims(user.id, user.role.role_id, user.role.role_name.clone())
+            .with_session(family_id)
+            .with_permissions(permissions);
+        let access_token = self.jwt_service.encode_claims(&claims)?;
+
+        Ok(IssuedTokens {
+            access_token,
+            refresh_token,
+            expires_in: self.jwt_service.access_token_ttl().num_seconds(),
+        })
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, MERCHANT_ENTITY},
+        backoffice::{
+            app::create_merchant_use_case::validate_merchant_name,
+            domain::{merchant::Merchant, repository::MerchantRepository},
+            dto::merchant_dto::UpdateMerchantRequest,
+        },
+    },
+};
+
+pub struct UpdateMerchantUseCase {
+    merchant_repository: Arc<dyn MerchantRepository>,
+}
+
+impl UpdateMerchantUseCase {
+    pub fn new(merchant_repository: Arc<dyn MerchantRepository>) -> Self {
+        Self {
+            merchant_repository,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        merchant_id: Uuid,
+        request: UpdateMerchantRequest,
+        context: &AuditContext,
+    ) -> Result<Merchant, AppError> {
+        tracing::debug!("Updating merchant {}", merchant_id);
+
+        let mut merchant = self
+            .merchant_repository
+            .find_by_id(merchant_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Merchant {} not found",
+                merchant_id
+            )))?;
+        let before = merchant.audit_snapshot();
+
+        // Update name if provided and different
+        if let Some(new_name) = request.name {
+            let new_name = validate_merchant_name(&new_name)?;
+
+            if new_name != merchant.name {
+                // Check uniqueness
+                if self.merchant_repository.exists_by_name(&new_name).await? {
+                    return Err(AppError::ValidationError(format!(
+                        "Merchant '{}' already exists",
+                        new_name
+                    )));
+                }
+                merchant.update_name(new_name);
+            }
+        }
+
+        if let Some(new_description) = request.description {
+            merchant.update_description(Some(new_description));
+        }
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            MERCHANT_ENTITY,
+            merchant.id,
+            Some(&before),
+            Some(&merchant.audit_snapshot()),
+        );
+        let updated_merchant = self.merchant_repository.update(merchant, audit).await?;
+
+        tracing::info!("Merchant {} updated successfully", updated_merchant.id);
+
+        Ok(updated_merchant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::domain::repository::MockMerchantRepository;
+
+    #[tokio::test]
+    async fn test_update_missing_merchant() {
+        let mut merchant_repository = MockMerchantRepository::new();
+        merchant_repository
+            .expect_find_by_id()
+            .returning(|_| Ok(None));
+
+        let use_case = UpdateMerchantUseCase::new(Arc::new(merchant_repository));
+
+        let result = use_case
+            .execute(
+                Uuid::new_v4(),
+                UpdateMerchantRequest {
+                    name: Some("Acme".to_string()),
+                    description: None,
+                },
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
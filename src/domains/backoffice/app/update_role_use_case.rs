@@ -0,0 +1,248 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, ROLE_ENTITY},
+        backoffice::{
+            app::create_role_use_case::{validate_permissions, validate_role_name},
+            dto::role_dto::UpdateRoleRequest,
+            role::{model::RoleDetails, permission::ROLES_WRITE, repository::RoleRepository},
+        },
+    },
+};
+
+pub struct UpdateRoleUseCase {
+    role_repository: Arc<dyn RoleRepository>,
+}
+
+impl UpdateRoleUseCase {
+    pub fn new(role_repository: Arc<dyn RoleRepository>) -> Self {
+        Self { role_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        role_id: Uuid,
+        request: UpdateRoleRequest,
+        context: &AuditContext,
+    ) -> Result<RoleDetails, AppError> {
+        tracing::debug!("Updating role {}", role_id);
+
+        let mut role = self
+            .role_repository
+            .find_by_id(role_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("Role {} not found", role_id)))?;
+        let current_permissions = self.role_repository.find_permission_names(role_id).await?;
+        let before = role.audit_snapshot(&current_permissions);
+
+        // Update name if provided and different
+        if let Some(new_name) = request.role_name {
+            let new_name = validate_role_name(&new_name)?;
+
+            if new_name != role.role_name {
+                if role.is_system() {
+                    return Err(AppError::Forbidden(format!(
+                        "System role '{}' cannot be renamed",
+                        role.role_name
+                    )));
+                }
+
+                // Check uniqueness
+                if self
+                    .role_repository
+                    .find_by_name(&new_name)
+                    .await?
+                    .is_some()
+                {
+                    return Err(AppError::ValidationError(format!(
+                        "Role '{}' already exists",
+                        new_name
+                    )));
+                }
+
+                role.update_name(new_name);
+            }
+        }
+
+        if let Some(new_description) = request.role_description {
+            role.update_description(Some(new_description));
+        }
+
+        // Validate before saving anything
+        let permissions = match request.permissions {
+            Some(permissions) => {
+                Some(validate_permissions(self.role_repository.as_ref(), permissions).await?)
+            }
+            None => None,
+        };
+
+        // Without it nobody could grant permissions back
+        if role.is_admin()
+            && permissions
+                .as_ref()
+                .is_some_and(|permissions| !permissions.iter().any(|p| p == ROLES_WRITE))
+        {
+            return Err(AppError::Forbidden(format!(
+                "System role '{}' must keep the '{}' permission",
+                role.role_name, ROLES_WRITE
+            )));
+        }
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            ROLE_ENTITY,
+            role_id,
+            Some(&before),
+            Some(&role.audit_snapshot(permissions.as_deref().unwrap_or(&current_permissions))),
+        );
+        let role = self
+            .role_repository
+            .update(role, permissions.clone(), audit)
+            .await?;
+        let permissions = permissions.unwrap_or(current_permissions);
+
+        tracing::info!("Role {} updated successfully", role_id);
+
+        Ok(RoleDetails { role, permissions })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::role::{
+        model::{admin_role_id, risk_role_id, Role},
+        permission::{Permission, USERS_WRITE},
+        repository::MockRoleRepository,
+    };
+    use chrono::Utc;
+
+    fn risk_role() -> Role {
+        Role::new(
+            risk_role_id(),
+            "Risk".to_string(),
+            None,
+            Utc::now(),
+            Utc::now(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_system_role_cannot_be_renamed() {
+        let mut role_repository = MockRoleRepository::new();
+        role_repository
+            .expect_find_by_id()
+            .returning(|_| Ok(Some(risk_role())));
+        role_repository
+            .expect_find_permission_names()
+            .returning(|_| Ok(vec![]));
+        role_repository.expect_update().never();
+
+        let use_case = UpdateRoleUseCase::new(Arc::new(role_repository));
+
+        let result = use_case
+            .execute(
+                risk_role_id(),
+                UpdateRoleRequest {
+                    role_name: Some("Fraud".to_string()),
+                    role_description: None,
+                    permissions: None,
+                },
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_system_role_description_can_change() {
+        let mut role_repository = MockRoleRepository::new();
+        role_repository
+            .expect_find_by_id()
+            .returning(|_| Ok(Some(risk_role())));
+        role_repository
+            .expect_update()
+            .withf(|role, permissions, audit| {
+                role.role_description.as_deref() == Some("Fraud prevention")
+                    && permissions.is_none()
+                    && audit.changes
+                        == serde_json::json!({
+                            "role_description": { "before": null, "after": "Fraud prevention" }
+                        })
+            })
+            .times(1)
+            .returning(|role, _, _| Ok(role));
+        role_repository
+            .expect_find_permission_names()
+            .returning(|_| Ok(vec!["users:read".to_string()]));
+
+        let use_case = UpdateRoleUseCase::new(Arc::new(role_repository));
+
+        let details = use_case
+            .execute(
+                risk_role_id(),
+                UpdateRoleRequest {
+                    role_name: Some("Risk".to_string()),
+                    role_description: Some("Fraud prevention".to_string()),
+                    permissions: None,
+                },
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(details.role.role_name, "Risk");
+        assert_eq!(details.permissions, vec!["users:read"]);
+    }
+
+    #[tokio::test]
+    async fn test_admin_role_keeps_roles_write() {
+        let mut role_repository = MockRoleRepository::new();
+        role_repository.expect_find_by_id().returning(|_| {
+            Ok(Some(Role::new(
+                admin_role_id(),
+                "Admin".to_string(),
+                None,
+                Utc::now(),
+                Utc::now(),
+            )))
+        });
+        role_repository.expect_list_permissions().returning(|| {
+            Ok([ROLES_WRITE, USERS_WRITE]
+                .into_iter()
+                .map(|name| Permission {
+                    permission_id: Uuid::new_v4(),
+                    permission_name: name.to_string(),
+                    permission_description: None,
+                    created_at: Utc::now(),
+                })
+                .collect())
+        });
+        role_repository
+            .expect_find_permission_names()
+            .returning(|_| Ok(vec![ROLES_WRITE.to_string(), USERS_WRITE.to_string()]));
+        role_repository.expect_update().never();
+
+        let use_case = UpdateRoleUseCase::new(Arc::new(role_repository));
+
+        let result = use_case
+            .execute(
+                admin_role_id(),
+                UpdateRoleRequest {
+                    role_name: None,
+                    role_description: None,
+                    permissions: Some(vec![USERS_WRITE.to_string()]),
+                },
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{
+            AuditAction, AuditContext, AuditEntry, SITE_CREDENTIALS_ENTITY,
+        },
+        backoffice::{
+            app::{
+                get_site_credentials_use_case::find_site_credentials,
+                get_site_use_case::find_merchant_site,
+            },
+            domain::{
+                merchant::SiteCredentials,
+                repository::{SiteCredentialsRepository, SiteRepository},
+            },
+        },
+    },
+};
+
+pub struct UpdateSiteCredentialsUseCase {
+    site_repository: Arc<dyn SiteRepository>,
+    site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+}
+
+impl UpdateSiteCredentialsUseCase {
+    pub fn new(
+        site_repository: Arc<dyn SiteRepository>,
+        site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+    ) -> Self {
+        Self {
+            site_repository,
+            site_credentials_repository,
+        }
+    }
+
+    /// Replaces the IP allowlist of a key, or opts the key out of it with `allow_any_ip`
+    pub async fn update_allowed_ips(
+        &self,
+        merchant_id: Uuid,
+        site_id: Uuid,
+        credentials_id: Uuid,
+        allowed_ips: Vec<String>,
+        allow_any_ip: bool,
+        context: &AuditContext,
+    ) -> Result<SiteCredentials, AppError> {
+        tracing::debug!(
+            "Updating allowed IPs of credentials {} of site {}",
+            credentials_id,
+            site_id
+        );
+
+        find_merchant_site(self.site_repository.as_ref(), merchant_id, site_id).await?;
+        let mut credentials = find_site_credentials(
+            self.site_credentials_repository.as_ref(),
+            site_id,
+            credentials_id,
+        )
+        .await?;
+
+        if credentials.revoked_at.is_some() {
+            return Err(AppError::ValidationError(
+                "Revoked credentials cannot be changed".to_string(),
+            ));
+        }
+
+        let before = credentials.audit_snapshot();
+        credentials.update_allowed_ips(allowed_ips, allow_any_ip)?;
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            SITE_CREDENTIALS_ENTITY,
+            credentials.id,
+            Some(&before),
+            Some(&credentials.audit_snapshot()),
+        );
+        let credentials = self
+            .site_credentials_repository
+            .update(credentials, audit)
+            .await?;
+
+        tracing::info!(
+            "Allowed IPs of credentials {} of site {} set to {:?} (any IP: {})",
+            credentials_id,
+            site_id,
+            credentials.allowed_ips,
+            credentials.allow_any_ip
+        );
+
+        Ok(credentials)
+    }
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, SITE_ENTITY},
+        backoffice::{
+            app::{
+                create_site_use_case::validate_site_name, get_site_use_case::find_merchant_site,
+            },
+            domain::{merchant::Site, repository::SiteRepository},
+            dto::site_dto::UpdateSiteRequest,
+        },
+    },
+};
+
+pub struct UpdateSiteUseCase {
+    site_repository: Arc<dyn SiteRepository>,
+    require_https: bool,
+}
+
+impl UpdateSiteUseCase {
+    pub fn new(site_repository: Arc<dyn SiteRepository>, require_https: bool) -> Self {
+        Self {
+            site_repository,
+            require_https,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        merchant_id: Uuid,
+        site_id: Uuid,
+        request: UpdateSiteRequest,
+        context: &AuditContext,
+    ) -> Result<Site, AppError> {
+        tracing::debug!("Updating site {} of merchant {}", site_id, merchant_id);
+
+        let mut site =
+            find_merchant_site(self.site_repository.as_ref(), merchant_id, site_id).await?;
+        let before = site.audit_snapshot();
+
+        if let Some(name) = request.name {
+            let name = validate_site_name(&name)?;
+
+            if name != site.name && self.site_repository.exists_by_name(&name).await? {
+                return Err(AppError::ValidationError(format!(
+                    "Site '{}' already exists",
+                    name
+                )));
+            }
+
+            site.update_name(name);
+        }
+
+        let url = request.url.map(|url| url.trim().to_string());
+        if let Some(url) = &url {
+            if *url != site.url && self.site_repository.exists_by_url(url).await? {
+                return Err(AppError::ValidationError(format!(
+                    "Site with url '{}' already exists",
+                    url
+                )));
+            }
+        }
+
+        site.update_urls(
+            url,
+            request.callback_url.map(|url| url.trim().to_string()),
+            request
+                .redirect_success_url
+                .map(|url| url.trim().to_string()),
+            request.redirect_fail_url.map(|url| url.trim().to_string()),
+        );
+        site.validate_urls(self.require_https)?;
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            SITE_ENTITY,
+            site.id,
+            Some(&before),
+            Some(&site.audit_snapshot()),
+        );
+        let updated_site = self.site_repository.update(site, audit).await?;
+
+        tracing::info!("Site {} updated successfully", site_id);
+
+        Ok(updated_site)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::domain::repository::MockSiteRepository;
+
+    #[tokio::test]
+    async fn test_changing_url_revalidates_redirects() {
+        let merchant_id = Uuid::new_v4();
+        let site = Site::new(
+            merchant_id,
+            "Shop".to_string(),
+            "https://acme.com".to_string(),
+            "https://acme.com/callback".to_string(),
+            "https://acme.com/ok".to_string(),
+            "https://acme.com/fail".to_string(),
+        );
+        let site_id = site.id;
+
+        let mut site_repository = MockSiteRepository::new();
+        site_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(site.clone())));
+        site_repository
+            .expect_exists_by_url()
+            .returning(|_| Ok(false));
+        site_repository.expect_update().never();
+
+        let use_case = UpdateSiteUseCase::new(Arc::new(site_repository), true);
+
+        let result = use_case
+            .execute(
+                merchant_id,
+                site_id,
+                UpdateSiteRequest {
+                    name: None,
+                    url: Some("https://other.com".to_string()),
+                    callback_url: None,
+                    redirect_success_url: None,
+                    redirect_fail_url: None,
+                },
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
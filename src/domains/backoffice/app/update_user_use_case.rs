@@ -4,9 +4,12 @@ use uuid::Uuid;
 
 use crate::{
     common::error::AppError,
-    domains::backoffice::{
-        domain::user::User, dto::user_dto::UpdateUserRequest, role::repository::RoleRepository,
-        UserRepository,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, USER_ENTITY},
+        backoffice::{
+            domain::user::User, dto::user_dto::UpdateUserRequest, role::repository::RoleRepository,
+            UserRepository,
+        },
     },
 };
 
@@ -30,6 +33,7 @@ impl UpdateUserUseCase {
         &self,
         user_id: Uuid,
         request: UpdateUserRequest,
+        context: &AuditContext,
     ) -> Result<User, AppError> {
         tracing::debug!("Updating user {}", user_id);
 
@@ -39,6 +43,7 @@ impl UpdateUserUseCase {
             .find_by_id(user_id)
             .await?
             .ok_or(AppError::NotFound(format!("User {} not found", user_id)))?;
+        let before = user.audit_snapshot();
 
         // Update username if provided and different
         if let Some(new_username) = request.username {
@@ -84,8 +89,16 @@ impl UpdateUserUseCase {
             }
         }
 
-        // Save updated user to database
-        let updated_user = self.user_repository.update(user).await?;
+        // Save updated user to database together with its audit entry
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            USER_ENTITY,
+            user.id,
+            Some(&before),
+            Some(&user.audit_snapshot()),
+        );
+        let updated_user = self.user_repository.update(user, audit).await?;
 
         tracing::info!("User {} updated successfully", updated_user.id);
 
@@ -0,0 +1,269 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::{error::AppError, hash_utils::verify_password},
+    domains::backoffice::{
+        app::token_issuer::{IssuedTokens, TokenIssuer},
+        domain::repository::LoginChallengeRepository,
+        dto::auth_dto::VerifyLoginRequest,
+        UserRepository,
+    },
+};
+
+/// 2nd login step: exchanges challenge + emailed code for a token pair
+/// and starts a new login session
+pub struct VerifyLoginUseCase {
+    user_repository: Arc<dyn UserRepository>,
+    challenge_repository: Arc<dyn LoginChallengeRepository>,
+    token_issuer: Arc<TokenIssuer>,
+    max_attempts: i32,
+}
+
+impl VerifyLoginUseCase {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        challenge_repository: Arc<dyn LoginChallengeRepository>,
+        token_issuer: Arc<TokenIssuer>,
+        max_attempts: i32,
+    ) -> Self {
+        Self {
+            user_repository,
+            challenge_repository,
+            token_issuer,
+            max_attempts,
+        }
+    }
+
+    pub async fn execute(&self, request: VerifyLoginRequest) -> Result<IssuedTokens, AppError> {
+        tracing::debug!("Verifying login challenge {}", request.challenge_id);
+
+        // The attempt is counted before the code is checked, so parallel guesses
+        // cannot all see the same attempt count
+        let challenge = self
+            .challenge_repository
+            .register_attempt(request.challenge_id, self.max_attempts)
+            .await?
+            .ok_or_else(|| {
+                tracing::warn!(
+                    "Login challenge {} is unknown or no longer usable",
+                    request.challenge_id
+                );
+                Self::invalid_challenge()
+            })?;
+
+        if !verify_password(&request.code, &challenge.code_hash)? {
+            tracing::warn!(
+                "Invalid code for login challenge {} (attempt {})",
+                challenge.id,
+                challenge.attempts
+            );
+            return Err(AppError::Unauthorized(
+                "Invalid confirmation code".to_string(),
+            ));
+        }
+
+        if !self.challenge_repository.consume(challenge.id).await? {
+            tracing::warn!("Login challenge {} was already used", challenge.id);
+            return Err(Self::invalid_challenge());
+        }
+
+        let user = self
+            .user_repository
+            .find_by_id(challenge.user_id)
+            .await?
+            .filter(|user| user.is_active())
+            .ok_or_else(Self::invalid_challenge)?;
+
+        let tokens = self.token_issuer.issue(&user, Uuid::new_v4()).await?;
+
+        tracing::info!("User {} logged in", user.id);
+
+        Ok(tokens)
+    }
+
+    fn invalid_challenge() -> AppError {
+        AppError::Unauthorized("Login challenge is invalid or expired".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{hash_utils::hash_password, jwt::JwtService};
+    use crate::domains::backoffice::domain::{
+        login_challenge::LoginChallenge,
+        repository::{
+            MockLoginChallengeRepository, MockRefreshTokenRepository, MockUserRepository,
+        },
+        user::User,
+    };
+    use crate::domains::backoffice::role::{
+        model::{user_role_id, Role},
+        repository::MockRoleRepository,
+    };
+    use chrono::{Duration, Utc};
+
+    fn create_token_issuer(
+        jwt_service: Arc<JwtService>,
+        refresh_token_repository: MockRefreshTokenRepository,
+    ) -> Arc<TokenIssuer> {
+        let mut role_repository = MockRoleRepository::new();
+        role_repository
+            .expect_find_permission_names()
+            .returning(|_| Ok(vec!["users:read".to_string()]));
+
+        Arc::new(TokenIssuer::new(
+            Arc::new(refresh_token_repository),
+            Arc::new(role_repository),
+            jwt_service,
+            Duration::days(30),
+        ))
+    }
+
+    fn create_test_user() -> User {
+        let role = Role::new(
+            user_role_id(),
+            "User".to_string(),
+            None,
+            Utc::now(),
+            Utc::now(),
+        );
+
+        User::new(
+            "testuser".to_string(),
+            "test@example.com".to_string(),
+            "hash".to_string(),
+            role,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_verify_with_valid_code_returns_token() {
+        let user = create_test_user();
+        let challenge = LoginChallenge::new(
+            user.id,
+            hash_password("123456").unwrap(),
+            Duration::minutes(5),
+        );
+        let challenge_id = challenge.id;
+
+        let mut challenge_repository = MockLoginChallengeRepository::new();
+        challenge_repository
+            .expect_register_attempt()
+            .withf(move |id, max_attempts| *id == challenge_id && *max_attempts == 5)
+            .times(1)
+            .returning(move |_, _| {
+                let mut challenge = challenge.clone();
+                challenge.register_attempt();
+                Ok(Some(challenge))
+            });
+        challenge_repository
+            .expect_consume()
+            .withf(move |id| *id == challenge_id)
+            .times(1)
+            .returning(|_| Ok(true));
+
+        let mut user_repository = MockUserRepository::new();
+        user_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user.clone())));
+
+        let mut refresh_token_repository = MockRefreshTokenRepository::new();
+        refresh_token_repository
+            .expect_create()
+            .times(1)
+            .returning(Ok);
+
+        let jwt_service = Arc::new(JwtService::new("test_secret_key"));
+        let use_case = VerifyLoginUseCase::new(
+            Arc::new(user_repository),
+            Arc::new(challenge_repository),
+            create_token_issuer(jwt_service.clone(), refresh_token_repository),
+            5,
+        );
+
+        let tokens = use_case
+            .execute(VerifyLoginRequest {
+                challenge_id,
+                code: "123456".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let claims = jwt_service.decode_token(&tokens.access_token).unwrap();
+        assert!(claims.sid.is_some());
+        assert!(claims.has_permission("users:read"));
+        assert!(!tokens.refresh_token.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verify_with_wrong_code_does_not_consume() {
+        let challenge = LoginChallenge::new(
+            Uuid::new_v4(),
+            hash_password("123456").unwrap(),
+            Duration::minutes(5),
+        );
+        let challenge_id = challenge.id;
+
+        let mut challenge_repository = MockLoginChallengeRepository::new();
+        challenge_repository
+            .expect_register_attempt()
+            .withf(move |id, _| *id == challenge_id)
+            .times(1)
+            .returning(move |_, _| {
+                let mut challenge = challenge.clone();
+                challenge.register_attempt();
+                Ok(Some(challenge))
+            });
+        challenge_repository.expect_consume().never();
+
+        let use_case = VerifyLoginUseCase::new(
+            Arc::new(MockUserRepository::new()),
+            Arc::new(challenge_repository),
+            create_token_issuer(
+                Arc::new(JwtService::new("test_secret_key")),
+                MockRefreshTokenRepository::new(),
+            ),
+            5,
+        );
+
+        let result = use_case
+            .execute(VerifyLoginRequest {
+                challenge_id,
+                code: "000000".to_string(),
+            })
+            .await;
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_used_up_or_consumed_challenge_is_rejected() {
+        let mut challenge_repository = MockLoginChallengeRepository::new();
+        challenge_repository
+            .expect_register_attempt()
+            .times(1)
+            .returning(|_, _| Ok(None));
+
+        let use_case = VerifyLoginUseCase::new(
+            Arc::new(MockUserRepository::new()),
+            Arc::new(challenge_repository),
+            create_token_issuer(
+                Arc::new(JwtService::new("test_secret_key")),
+                MockRefreshTokenRepository::new(),
+            ),
+            5,
+        );
+
+        let result = use_case
+            .execute(VerifyLoginRequest {
+                challenge_id: Uuid::new_v4(),
+                code: "123456".to_string(),
+            })
+            .await;
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+}
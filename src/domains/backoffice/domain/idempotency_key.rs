@@ -0,0 +1,115 @@
+use chrono::{DateTime, Duration, SubsecRound, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Mutating request remembered under the caller's `Idempotency-Key`.
+/// `scope` keeps keys of different callers apart; the response is unset while the
+/// first request is still running, which holds the key until `locked_until`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IdempotencyKey {
+    pub scope: String,
+    pub key: String,
+    pub request_fingerprint: String,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl IdempotencyKey {
+    pub fn new(
+        scope: String,
+        key: String,
+        request_fingerprint: String,
+        ttl: Duration,
+        lease: Duration,
+    ) -> Self {
+        // Whole microseconds, as stored, so `created_at` identifies the reservation
+        let now = Utc::now().trunc_subsecs(6);
+        Self {
+            scope,
+            key,
+            request_fingerprint,
+            response_status: None,
+            response_body: None,
+            locked_until: Some(now + lease),
+            created_at: now,
+            expires_at: now + ttl,
+        }
+    }
+
+    /// SHA-256 of the method, path with query and body, hex encoded
+    pub fn fingerprint(method: &str, path_and_query: &str, body: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(method.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(path_and_query.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(body);
+        hex::encode(hasher.finalize())
+    }
+
+    pub fn is_expired_at(&self, at: DateTime<Utc>) -> bool {
+        self.expires_at <= at
+    }
+
+    /// No response was stored before the lease ran out, e.g. the server stopped
+    /// mid-request; a retry may take the key over
+    pub fn is_abandoned_at(&self, at: DateTime<Utc>) -> bool {
+        self.response_status.is_none() && self.locked_until.is_none_or(|until| until <= at)
+    }
+
+    /// Stored response to replay; `None` while the first request is running
+    pub fn response(&self) -> Option<(i32, &str)> {
+        self.response_status.zip(self.response_body.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_covers_method_path_and_body() {
+        let fingerprint = IdempotencyKey::fingerprint("POST", "/api/v1/user", b"{}");
+
+        assert_eq!(
+            fingerprint,
+            IdempotencyKey::fingerprint("POST", "/api/v1/user", b"{}")
+        );
+        assert_ne!(
+            fingerprint,
+            IdempotencyKey::fingerprint("PATCH", "/api/v1/user", b"{}")
+        );
+        assert_ne!(
+            fingerprint,
+            IdempotencyKey::fingerprint("POST", "/api/v1/role", b"{}")
+        );
+        assert_ne!(
+            fingerprint,
+            IdempotencyKey::fingerprint("POST", "/api/v1/user", b"{ }")
+        );
+    }
+
+    #[test]
+    fn test_response_is_unset_until_completed() {
+        let mut key = IdempotencyKey::new(
+            "user:1".to_string(),
+            "key-1".to_string(),
+            "fingerprint".to_string(),
+            Duration::hours(24),
+            Duration::minutes(1),
+        );
+        assert_eq!(key.response(), None);
+        assert!(!key.is_expired_at(Utc::now()));
+        assert!(key.is_expired_at(Utc::now() + Duration::hours(24)));
+        assert!(!key.is_abandoned_at(Utc::now()));
+        assert!(key.is_abandoned_at(Utc::now() + Duration::minutes(1)));
+
+        key.response_status = Some(200);
+        key.response_body = Some("{}".to_string());
+        assert_eq!(key.response(), Some((200, "{}")));
+        assert!(!key.is_abandoned_at(Utc::now() + Duration::minutes(1)));
+    }
+}
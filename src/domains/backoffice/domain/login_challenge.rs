@@ -0,0 +1,105 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Pending second login step: the user passed the password check
+/// and must now confirm the one-time code sent by email
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LoginChallenge {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub code_hash: String,
+    pub attempts: i32,
+    pub expires_at: DateTime<Utc>,
+    pub consumed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl LoginChallenge {
+    pub fn new(user_id: Uuid, code_hash: String, ttl: Duration) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            code_hash,
+            attempts: 0,
+            expires_at: now + ttl,
+            consumed_at: None,
+            created_at: now,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now() > self.expires_at
+    }
+
+    pub fn is_consumed(&self) -> bool {
+        self.consumed_at.is_some()
+    }
+
+    pub fn attempts_exhausted(&self, max_attempts: i32) -> bool {
+        self.attempts >= max_attempts
+    }
+
+    /// Challenge can still be exchanged for a token
+    pub fn is_usable(&self, max_attempts: i32) -> bool {
+        !self.is_expired() && !self.is_consumed() && !self.attempts_exhausted(max_attempts)
+    }
+
+    /// Every code check counts, right or wrong
+    pub fn register_attempt(&mut self) {
+        self.attempts += 1;
+    }
+
+    pub fn consume(&mut self) {
+        self.consumed_at = Some(Utc::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_challenge(ttl: Duration) -> LoginChallenge {
+        LoginChallenge::new(Uuid::new_v4(), "hash".to_string(), ttl)
+    }
+
+    #[test]
+    fn test_new_challenge_is_usable() {
+        let challenge = create_test_challenge(Duration::minutes(5));
+
+        assert_eq!(challenge.attempts, 0);
+        assert!(!challenge.is_expired());
+        assert!(!challenge.is_consumed());
+        assert!(challenge.is_usable(5));
+    }
+
+    #[test]
+    fn test_expired_challenge() {
+        let challenge = create_test_challenge(Duration::seconds(-1));
+
+        assert!(challenge.is_expired());
+        assert!(!challenge.is_usable(5));
+    }
+
+    #[test]
+    fn test_attempts_exhausted() {
+        let mut challenge = create_test_challenge(Duration::minutes(5));
+
+        challenge.register_attempt();
+        challenge.register_attempt();
+
+        assert!(challenge.is_usable(3));
+        assert!(!challenge.is_usable(2));
+    }
+
+    #[test]
+    fn test_consumed_challenge() {
+        let mut challenge = create_test_challenge(Duration::minutes(5));
+
+        challenge.consume();
+
+        assert!(challenge.is_consumed());
+        assert!(!challenge.is_usable(5));
+    }
+}
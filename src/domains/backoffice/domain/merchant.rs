@@ -1,49 +1,744 @@
+use crate::common::{error::AppError, time_formater};
+use chrono::{DateTime, Utc};
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{fmt, net::IpAddr, str::FromStr};
+use url::Url;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Merchant {
     pub id: Uuid,
     pub name: String,
     pub description: Option<String>,
     pub status: MerchantStatus,
+    pub kyb_verified_at: Option<DateTime<Utc>>,
     pub sites: Vec<Site>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Merchant {
+    /// New merchants start in onboarding without any sites
+    pub fn new(name: String, description: Option<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            description,
+            status: MerchantStatus::Onboarding,
+            kyb_verified_at: None,
+            sites: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == MerchantStatus::Active
+    }
+
+    pub fn update_name(&mut self, name: String) {
+        self.name = name;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn update_description(&mut self, description: Option<String>) {
+        self.description = description;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn is_kyb_verified(&self) -> bool {
+        self.kyb_verified_at.is_some()
+    }
+
+    pub fn verify_kyb(&mut self) {
+        let now = Utc::now();
+        self.kyb_verified_at = Some(now);
+        self.updated_at = now;
+    }
+
+    /// Fields compared in audit entries; sites are audited on their own
+    pub fn audit_snapshot(&self) -> Value {
+        json!({
+            "name": self.name,
+            "description": self.description,
+            "status": self.status,
+            "kyb_verified_at": self.kyb_verified_at.as_ref().map(time_formater::format),
+        })
+    }
+
+    /// Moves the merchant to `target` and returns the change to record.
+    ///
+    /// Allowed transitions:
+    /// - onboarding -> active: KYB verified and at least one active site with credentials
+    /// - active <-> inactive: a reason is mandatory
+    pub fn transition_to(
+        &mut self,
+        target: MerchantStatus,
+        reason: Option<String>,
+        has_active_site_with_credentials: bool,
+        changed_by: Option<Uuid>,
+    ) -> Result<MerchantStatusChange, AppError> {
+        let from = self.status;
+        let reason = reason
+            .map(|reason| reason.trim().to_string())
+            .filter(|reason| !reason.is_empty());
+
+        match (from, target) {
+            (MerchantStatus::Onboarding, MerchantStatus::Active) => {
+                if !self.is_kyb_verified() {
+                    return Err(AppError::InvalidStateTransition(
+                        "KYB checks must be completed before activating the merchant".to_string(),
+                    ));
+                }
+                if !has_active_site_with_credentials {
+                    return Err(AppError::InvalidStateTransition(
+                        "Merchant needs at least one active site with credentials before activation"
+                            .to_string(),
+                    ));
+                }
+            }
+            (MerchantStatus::Active, MerchantStatus::Inactive)
+            | (MerchantStatus::Inactive, MerchantStatus::Active) => {
+                if reason.is_none() {
+                    return Err(AppError::ValidationError(format!(
+                        "A reason is required to move a merchant from {} to {}",
+                        from, target
+                    )));
+                }
+            }
+            _ => {
+                return Err(AppError::InvalidStateTransition(format!(
+                    "Merchant cannot move from {} to {}",
+                    from, target
+                )));
+            }
+        }
+
+        let now = Utc::now();
+        self.status = target;
+        self.updated_at = now;
+
+        Ok(MerchantStatusChange {
+            id: Uuid::new_v4(),
+            merchant_id: self.id,
+            from_status: from,
+            to_status: target,
+            reason,
+            changed_by,
+            changed_at: now,
+        })
+    }
+}
+
+/// Recorded move of a merchant from one status to another
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MerchantStatusChange {
+    pub id: Uuid,
+    pub merchant_id: Uuid,
+    pub from_status: MerchantStatus,
+    pub to_status: MerchantStatus,
+    pub reason: Option<String>,
+    pub changed_by: Option<Uuid>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum MerchantStatus {
     Active,
     Onboarding,
     Inactive,
 }
 
+impl MerchantStatus {
+    /// Value stored in the `status` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MerchantStatus::Active => "active",
+            MerchantStatus::Onboarding => "onboarding",
+            MerchantStatus::Inactive => "inactive",
+        }
+    }
+}
+
+impl fmt::Display for MerchantStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MerchantStatus {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "active" => Ok(MerchantStatus::Active),
+            "onboarding" => Ok(MerchantStatus::Onboarding),
+            "inactive" => Ok(MerchantStatus::Inactive),
+            other => Err(AppError::InternalError(format!(
+                "Unknown merchant status '{}'",
+                other
+            ))),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Site {
     pub id: Uuid,
     pub merchant_id: Uuid,
     pub name: String,
     pub url: String,
+    pub callback_url: String,
+    pub redirect_success_url: String,
+    pub redirect_fail_url: String,
     pub status: SiteStatus,
-    pub redirectSuccessUrl: Option<String>,
-    pub redirectFailureUrl: Option<String>,
-    pub credentials: Option<SiteCredentials>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+impl Site {
+    /// New sites are active right away; payments still need credentials
+    pub fn new(
+        merchant_id: Uuid,
+        name: String,
+        url: String,
+        callback_url: String,
+        redirect_success_url: String,
+        redirect_fail_url: String,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            merchant_id,
+            name,
+            url,
+            callback_url,
+            redirect_success_url,
+            redirect_fail_url,
+            status: SiteStatus::Active,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == SiteStatus::Active
+    }
+
+    pub fn change_status(&mut self, status: SiteStatus) {
+        self.status = status;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn update_name(&mut self, name: String) {
+        self.name = name;
+        self.updated_at = Utc::now();
+    }
+
+    /// Replaces the given URLs; callers re-validate afterwards
+    pub fn update_urls(
+        &mut self,
+        url: Option<String>,
+        callback_url: Option<String>,
+        redirect_success_url: Option<String>,
+        redirect_fail_url: Option<String>,
+    ) {
+        if let Some(url) = url {
+            self.url = url;
+        }
+        if let Some(callback_url) = callback_url {
+            self.callback_url = callback_url;
+        }
+        if let Some(redirect_success_url) = redirect_success_url {
+            self.redirect_success_url = redirect_success_url;
+        }
+        if let Some(redirect_fail_url) = redirect_fail_url {
+            self.redirect_fail_url = redirect_fail_url;
+        }
+        self.updated_at = Utc::now();
+    }
+
+    /// Fields compared in audit entries
+    pub fn audit_snapshot(&self) -> Value {
+        json!({
+            "merchant_id": self.merchant_id,
+            "name": self.name,
+            "url": self.url,
+            "callback_url": self.callback_url,
+            "redirect_success_url": self.redirect_success_url,
+            "redirect_fail_url": self.redirect_fail_url,
+            "status": self.status,
+        })
+    }
+
+    /// Checks that every URL is absolute and that redirects stay on the site's domain.
+    /// Plain http is only accepted when `require_https` is off.
+    pub fn validate_urls(&self, require_https: bool) -> Result<(), AppError> {
+        let site_url = parse_site_url("url", &self.url, require_https)?;
+        parse_site_url("callback_url", &self.callback_url, require_https)?;
+
+        let site_domain = site_domain(&site_url);
+
+        for (field, value) in [
+            ("redirect_success_url", &self.redirect_success_url),
+            ("redirect_fail_url", &self.redirect_fail_url),
+        ] {
+            let redirect = parse_site_url(field, value, require_https)?;
+            let host = redirect.host_str().unwrap_or_default();
+
+            if host != site_domain && !host.ends_with(&format!(".{}", site_domain)) {
+                return Err(AppError::ValidationError(format!(
+                    "{} host '{}' does not match the site domain '{}'",
+                    field, host, site_domain
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_site_url(field: &str, value: &str, require_https: bool) -> Result<Url, AppError> {
+    let url = Url::parse(value)
+        .map_err(|_| AppError::ValidationError(format!("{} must be an absolute URL", field)))?;
+
+    match url.scheme() {
+        "https" => {}
+        "http" if !require_https => {}
+        _ => {
+            return Err(AppError::ValidationError(format!(
+                "{} must use https",
+                field
+            )))
+        }
+    }
+
+    if url.host_str().is_none() {
+        return Err(AppError::ValidationError(format!(
+            "{} must have a host",
+            field
+        )));
+    }
+
+    Ok(url)
+}
+
+/// Host of the site URL without a leading `www.`
+fn site_domain(url: &Url) -> String {
+    let host = url.host_str().unwrap_or_default();
+    host.strip_prefix("www.").unwrap_or(host).to_string()
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum SiteStatus {
     Active,
     Inactive,
 }
 
+impl SiteStatus {
+    /// Value stored in the `status` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SiteStatus::Active => "active",
+            SiteStatus::Inactive => "inactive",
+        }
+    }
+}
+
+impl fmt::Display for SiteStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SiteStatus {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "active" => Ok(SiteStatus::Active),
+            "inactive" => Ok(SiteStatus::Inactive),
+            other => Err(AppError::InternalError(format!(
+                "Unknown site status '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// API key pair of a site. The secret is only kept encrypted and is never returned
+/// after issuance.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SiteCredentials {
     pub id: Uuid,
     pub site_id: Uuid,
-    pub api_key: String,
-    pub api_secret: String,
-    pub AllowedIps: Vec<String>,
+    pub public_key: String,
+    pub encrypted_secret_key: String,
+    pub allowed_ips: Vec<String>,
+    /// Admin opt-out of the allowlist: calls are accepted from any address
+    pub allow_any_ip: bool,
     pub is_active: bool,
+    /// Set on keys replaced by a rotation; they keep working until then
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
-}
\ No newline at end of file
+}
+
+/// Freshly generated key pair; the plaintext secret exists only in this response
+#[derive(Debug, Clone)]
+pub struct IssuedSiteCredentials {
+    pub credentials: SiteCredentials,
+    pub secret_key: String,
+}
+
+/// Caller of the merchant API, resolved from a signed request
+#[derive(Debug, Clone)]
+pub struct MerchantContext {
+    pub merchant: Merchant,
+    pub site: Site,
+    pub credentials: SiteCredentials,
+}
+
+impl SiteCredentials {
+    pub fn new(site_id: Uuid, public_key: String, encrypted_secret_key: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            site_id,
+            public_key,
+            encrypted_secret_key,
+            allowed_ips: Vec::new(),
+            allow_any_ip: false,
+            is_active: true,
+            expires_at: None,
+            revoked_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Fields compared in audit entries; the encrypted secret is left out
+    pub fn audit_snapshot(&self) -> Value {
+        json!({
+            "site_id": self.site_id,
+            "public_key": self.public_key,
+            "allowed_ips": self.allowed_ips,
+            "allow_any_ip": self.allow_any_ip,
+            "is_active": self.is_active,
+            "expires_at": self.expires_at.as_ref().map(time_formater::format),
+            "revoked_at": self.revoked_at.as_ref().map(time_formater::format),
+        })
+    }
+
+    /// Not revoked and, for rotated keys, still inside the overlap window
+    pub fn is_usable_at(&self, at: DateTime<Utc>) -> bool {
+        self.is_active && self.expires_at.is_none_or(|expires_at| expires_at > at)
+    }
+
+    /// Keeps the key working until `at`; an earlier expiry is never extended
+    pub fn expire_at(&mut self, at: DateTime<Utc>) {
+        self.expires_at = Some(self.expires_at.map_or(at, |expires_at| expires_at.min(at)));
+    }
+
+    pub fn revoke(&mut self) {
+        self.is_active = false;
+        self.revoked_at = Some(Utc::now());
+    }
+
+    /// Unless the key is opted out, the caller's address must be known and inside
+    /// one of the entries; an empty allowlist admits nobody
+    pub fn allows_ip(&self, ip: Option<IpAddr>) -> bool {
+        if self.allow_any_ip {
+            return true;
+        }
+
+        let Some(ip) = ip.map(|ip| ip.to_canonical()) else {
+            return false;
+        };
+
+        self.allowed_ips
+            .iter()
+            .filter_map(|entry| parse_ip_entry(entry).ok())
+            .any(|network| network.contains(&ip))
+    }
+
+    /// Replaces the allowlist; `allow_any_ip` lifts it and cannot be combined with entries
+    pub fn update_allowed_ips(
+        &mut self,
+        allowed_ips: Vec<String>,
+        allow_any_ip: bool,
+    ) -> Result<(), AppError> {
+        if allow_any_ip && !allowed_ips.is_empty() {
+            return Err(AppError::ValidationError(
+                "Allowed IPs cannot be listed for a key open to any IP".to_string(),
+            ));
+        }
+        if allowed_ips.len() > MAX_ALLOWED_IPS {
+            return Err(AppError::ValidationError(format!(
+                "At most {} allowed IP entries are supported",
+                MAX_ALLOWED_IPS
+            )));
+        }
+
+        let mut normalized: Vec<String> = Vec::with_capacity(allowed_ips.len());
+        for entry in &allowed_ips {
+            let entry = parse_ip_entry(entry)
+                .map_err(|_| {
+                    AppError::ValidationError(format!(
+                        "'{}' is not an IP address or CIDR range",
+                        entry
+                    ))
+                })?
+                .to_string();
+            if !normalized.contains(&entry) {
+                normalized.push(entry);
+            }
+        }
+
+        self.allowed_ips = normalized;
+        self.allow_any_ip = allow_any_ip;
+        Ok(())
+    }
+}
+
+const MAX_ALLOWED_IPS: usize = 100;
+
+/// Single addresses become /32 or /128 networks; host bits of ranges are cleared
+fn parse_ip_entry(entry: &str) -> Result<IpNet, ipnet::AddrParseError> {
+    let entry = entry.trim();
+
+    entry
+        .parse::<IpNet>()
+        .map(|network| network.trunc())
+        .or_else(|error| {
+            entry
+                .parse::<IpAddr>()
+                .map(|ip| IpNet::from(ip.to_canonical()))
+                .map_err(|_| error)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_ips_match_addresses_and_ranges() {
+        let mut credentials =
+            SiteCredentials::new(Uuid::new_v4(), "pk".to_string(), "enc".to_string());
+        assert!(!credentials.allows_ip("203.0.113.7".parse().ok()));
+        assert!(!credentials.allows_ip(None));
+
+        credentials
+            .update_allowed_ips(
+                vec![
+                    "203.0.113.7".to_string(),
+                    "198.51.100.77/24".to_string(),
+                    "2001:db8::/32".to_string(),
+                    "203.0.113.7".to_string(),
+                ],
+                false,
+            )
+            .unwrap();
+
+        assert_eq!(
+            credentials.allowed_ips,
+            vec!["203.0.113.7/32", "198.51.100.0/24", "2001:db8::/32"]
+        );
+        assert!(credentials.allows_ip("203.0.113.7".parse().ok()));
+        assert!(credentials.allows_ip("::ffff:198.51.100.1".parse().ok()));
+        assert!(credentials.allows_ip("2001:db8::1".parse().ok()));
+        assert!(!credentials.allows_ip("203.0.113.8".parse().ok()));
+        assert!(!credentials.allows_ip(None));
+
+        let result = credentials.update_allowed_ips(vec!["localhost".to_string()], false);
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+        assert_eq!(credentials.allowed_ips.len(), 3);
+    }
+
+    #[test]
+    fn test_allow_any_ip_opts_out_of_allowlist() {
+        let mut credentials =
+            SiteCredentials::new(Uuid::new_v4(), "pk".to_string(), "enc".to_string());
+
+        let result = credentials.update_allowed_ips(vec!["203.0.113.7".to_string()], true);
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+        assert!(!credentials.allow_any_ip);
+
+        credentials.update_allowed_ips(Vec::new(), true).unwrap();
+        assert!(credentials.allows_ip("192.0.2.1".parse().ok()));
+        assert!(credentials.allows_ip(None));
+
+        credentials.update_allowed_ips(Vec::new(), false).unwrap();
+        assert!(!credentials.allows_ip("192.0.2.1".parse().ok()));
+    }
+
+    #[test]
+    fn test_new_merchant_is_onboarding() {
+        let merchant = Merchant::new("Acme".to_string(), None);
+
+        assert_eq!(merchant.status, MerchantStatus::Onboarding);
+        assert!(!merchant.is_active());
+        assert!(merchant.sites.is_empty());
+    }
+
+    #[test]
+    fn test_activation_requires_kyb_and_site() {
+        let mut merchant = Merchant::new("Acme".to_string(), None);
+
+        let result = merchant.transition_to(MerchantStatus::Active, None, true, None);
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+
+        merchant.verify_kyb();
+        let result = merchant.transition_to(MerchantStatus::Active, None, false, None);
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+        assert_eq!(merchant.status, MerchantStatus::Onboarding);
+
+        let change = merchant
+            .transition_to(MerchantStatus::Active, None, true, None)
+            .unwrap();
+        assert_eq!(change.from_status, MerchantStatus::Onboarding);
+        assert_eq!(change.to_status, MerchantStatus::Active);
+        assert!(merchant.is_active());
+    }
+
+    #[test]
+    fn test_active_inactive_requires_reason() {
+        let mut merchant = Merchant::new("Acme".to_string(), None);
+        merchant.status = MerchantStatus::Active;
+
+        let result =
+            merchant.transition_to(MerchantStatus::Inactive, Some("  ".to_string()), true, None);
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let user_id = Uuid::new_v4();
+        let change = merchant
+            .transition_to(
+                MerchantStatus::Inactive,
+                Some("Chargeback ratio".to_string()),
+                false,
+                Some(user_id),
+            )
+            .unwrap();
+        assert_eq!(change.reason.as_deref(), Some("Chargeback ratio"));
+        assert_eq!(change.changed_by, Some(user_id));
+
+        merchant
+            .transition_to(
+                MerchantStatus::Active,
+                Some("Resolved".to_string()),
+                false,
+                None,
+            )
+            .unwrap();
+        assert!(merchant.is_active());
+    }
+
+    #[test]
+    fn test_illegal_transitions() {
+        let mut merchant = Merchant::new("Acme".to_string(), None);
+
+        for target in [MerchantStatus::Onboarding, MerchantStatus::Inactive] {
+            let result = merchant.transition_to(target, Some("reason".to_string()), true, None);
+            assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+        }
+
+        merchant.status = MerchantStatus::Inactive;
+        let result = merchant.transition_to(
+            MerchantStatus::Onboarding,
+            Some("reason".to_string()),
+            true,
+            None,
+        );
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+    }
+
+    fn site(url: &str, success: &str, fail: &str) -> Site {
+        Site::new(
+            Uuid::new_v4(),
+            "Shop".to_string(),
+            url.to_string(),
+            "https://api.acme.com/callback".to_string(),
+            success.to_string(),
+            fail.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_site_urls_on_same_domain() {
+        let site = site(
+            "https://www.acme.com",
+            "https://acme.com/ok",
+            "https://checkout.acme.com/fail",
+        );
+
+        assert!(site.validate_urls(true).is_ok());
+    }
+
+    #[test]
+    fn test_site_redirect_on_foreign_domain() {
+        let site = site(
+            "https://acme.com",
+            "https://acme.com/ok",
+            "https://evil-acme.com/fail",
+        );
+
+        assert!(matches!(
+            site.validate_urls(true),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_site_http_only_outside_production() {
+        let site = site(
+            "http://localhost:3000",
+            "http://localhost:3000/ok",
+            "http://localhost:3000/fail",
+        );
+
+        assert!(site.validate_urls(false).is_ok());
+        assert!(site.validate_urls(true).is_err());
+        assert!(self::site("/relative", "/ok", "/fail")
+            .validate_urls(false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_credentials_usable_until_revoked_or_expired() {
+        let now = Utc::now();
+        let mut credentials =
+            SiteCredentials::new(Uuid::new_v4(), "pk".to_string(), "enc".to_string());
+        assert!(credentials.is_usable_at(now));
+
+        credentials.expire_at(now + chrono::Duration::minutes(10));
+        credentials.expire_at(now + chrono::Duration::hours(1));
+        assert!(credentials.is_usable_at(now + chrono::Duration::minutes(5)));
+        assert!(!credentials.is_usable_at(now + chrono::Duration::minutes(15)));
+
+        credentials.revoke();
+        assert!(!credentials.is_usable_at(now));
+        assert!(credentials.revoked_at.is_some());
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            MerchantStatus::Active,
+            MerchantStatus::Onboarding,
+            MerchantStatus::Inactive,
+        ] {
+            assert_eq!(status.as_str().parse::<MerchantStatus>().unwrap(), status);
+        }
+
+        assert!("deleted".parse::<MerchantStatus>().is_err());
+        assert_eq!(
+            "inactive".parse::<SiteStatus>().unwrap(),
+            SiteStatus::Inactive
+        );
+    }
+}
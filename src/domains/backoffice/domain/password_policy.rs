@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+
+use crate::common::{config::PasswordConfig, error::AppError, hash_utils::verify_password};
+
+/// bcrypt ignores everything after the first 72 bytes
+pub const MAX_PASSWORD_BYTES: usize = 72;
+
+/// Rules a new backoffice password has to pass, whoever sets it
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    pub history_size: usize,
+    /// Breached passwords, lowercased
+    denylist: HashSet<String>,
+}
+
+impl PasswordPolicy {
+    /// Reads the denylist file when one is configured
+    pub fn from_config(config: &PasswordConfig) -> Result<Self, AppError> {
+        let policy = Self {
+            min_length: config.min_length,
+            require_lowercase: config.require_lowercase,
+            require_uppercase: config.require_uppercase,
+            require_digit: config.require_digit,
+            require_symbol: config.require_symbol,
+            history_size: config.history_size,
+            denylist: HashSet::new(),
+        };
+
+        let Some(path) = &config.denylist_path else {
+            return Ok(policy);
+        };
+        let contents = std::fs::read_to_string(path).map_err(|err| {
+            AppError::InternalError(format!("Cannot read password denylist '{}': {}", path, err))
+        })?;
+        let policy = policy.with_denylist(contents.lines());
+        tracing::info!(
+            "Loaded {} breached passwords from '{}'",
+            policy.denylist.len(),
+            path
+        );
+
+        Ok(policy)
+    }
+
+    pub fn with_denylist<'a>(mut self, passwords: impl IntoIterator<Item = &'a str>) -> Self {
+        self.denylist.extend(
+            passwords
+                .into_iter()
+                .map(str::trim)
+                .filter(|password| !password.is_empty())
+                .map(str::to_lowercase),
+        );
+        self
+    }
+
+    /// Checks length, character classes and the denylist
+    pub fn validate(&self, password: &str) -> Result<(), AppError> {
+        let mut unmet = Vec::new();
+        if password.chars().count() < self.min_length {
+            unmet.push(format!("be at least {} characters long", self.min_length));
+        }
+        if password.len() > MAX_PASSWORD_BYTES {
+            unmet.push(format!("be at most {} bytes long", MAX_PASSWORD_BYTES));
+        }
+        if self.require_lowercase && !password.chars().any(char::is_lowercase) {
+            unmet.push("contain a lowercase letter".to_string());
+        }
+        if self.require_uppercase && !password.chars().any(char::is_uppercase) {
+            unmet.push("contain an uppercase letter".to_string());
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            unmet.push("contain a digit".to_string());
+        }
+        if self.require_symbol && password.chars().all(char::is_alphanumeric) {
+            unmet.push("contain a symbol".to_string());
+        }
+        if !unmet.is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "Password must {}",
+                unmet.join(", ")
+            )));
+        }
+
+        if self.denylist.contains(&password.to_lowercase()) {
+            return Err(AppError::ValidationError(
+                "Password appears in a list of breached passwords".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects a password matching one of the user's latest hashes, newest first.
+    /// Only the first `history_size` hashes are compared.
+    pub fn check_reuse(&self, password: &str, recent_hashes: &[String]) -> Result<(), AppError> {
+        for hash in recent_hashes.iter().take(self.history_size) {
+            if verify_password(password, hash)? {
+                return Err(AppError::ValidationError(format!(
+                    "Password must differ from the last {} passwords",
+                    self.history_size
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::hash_utils::hash_password;
+
+    fn policy() -> PasswordPolicy {
+        PasswordPolicy::from_config(&PasswordConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_lists_every_unmet_rule() {
+        let error = policy().validate("short").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Validation error: Password must be at least 12 characters long, \
+             contain an uppercase letter, contain a digit"
+        );
+        assert!(policy().validate("Correct-Horse-42").is_ok());
+        assert!(policy()
+            .validate(&format!("Aa1{}", "x".repeat(MAX_PASSWORD_BYTES)))
+            .is_err());
+    }
+
+    #[test]
+    fn test_symbol_rule_is_optional() {
+        let strict = PasswordPolicy {
+            require_symbol: true,
+            ..policy()
+        };
+
+        assert!(policy().validate("CorrectHorse42").is_ok());
+        assert!(strict.validate("CorrectHorse42").is_err());
+        assert!(strict.validate("Correct Horse 42").is_ok());
+    }
+
+    #[test]
+    fn test_denylist_ignores_case() {
+        let policy = policy().with_denylist(["  Password1234 ", ""]);
+
+        assert!(policy.validate("Password12345").is_ok());
+
+        let error = policy.validate("pAssword1234").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Validation error: Password appears in a list of breached passwords"
+        );
+    }
+
+    #[test]
+    fn test_rejects_recent_passwords_only() {
+        let old = hash_password("Oldest-Password-1").unwrap();
+        let recent = hash_password("Recent-Password-1").unwrap();
+        let policy = PasswordPolicy {
+            history_size: 1,
+            ..policy()
+        };
+        let history = vec![recent, old];
+
+        assert!(policy.check_reuse("Recent-Password-1", &history).is_err());
+        assert!(policy.check_reuse("Oldest-Password-1", &history).is_ok());
+        assert!(PasswordPolicy {
+            history_size: 0,
+            ..policy
+        }
+        .check_reuse("Recent-Password-1", &history)
+        .is_ok());
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// One-time password reset link issued by an admin; only the SHA-256 of the emailed token
+/// is stored
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PasswordResetToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    /// Admin who asked for the reset
+    pub requested_by: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+    /// Set once the token was used or replaced by a newer one
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PasswordResetToken {
+    pub fn new(
+        user_id: Uuid,
+        token_hash: String,
+        requested_by: Option<Uuid>,
+        ttl: Duration,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            token_hash,
+            requested_by,
+            expires_at: now + ttl,
+            used_at: None,
+            created_at: now,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now() > self.expires_at
+    }
+
+    pub fn is_used(&self) -> bool {
+        self.used_at.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_reset_token() {
+        let token = PasswordResetToken::new(
+            Uuid::new_v4(),
+            "hash".to_string(),
+            None,
+            Duration::minutes(60),
+        );
+
+        assert!(!token.is_expired());
+        assert!(!token.is_used());
+
+        let expired = PasswordResetToken::new(
+            Uuid::new_v4(),
+            "hash".to_string(),
+            None,
+            Duration::seconds(-1),
+        );
+        assert!(expired.is_expired());
+    }
+}
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Opaque refresh token as stored: only the SHA-256 of the token is kept.
+/// All tokens rotated from the same login share a `family_id`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub rotated_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RefreshToken {
+    pub fn new(user_id: Uuid, family_id: Uuid, token_hash: String, ttl: Duration) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            family_id,
+            token_hash,
+            expires_at: now + ttl,
+            rotated_at: None,
+            revoked_at: None,
+            created_at: now,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now() > self.expires_at
+    }
+
+    /// Already exchanged for a newer token; presenting it again means reuse
+    pub fn is_rotated(&self) -> bool {
+        self.rotated_at.is_some()
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_refresh_token() {
+        let family_id = Uuid::new_v4();
+        let token = RefreshToken::new(
+            Uuid::new_v4(),
+            family_id,
+            "hash".to_string(),
+            Duration::days(30),
+        );
+
+        assert_eq!(token.family_id, family_id);
+        assert!(!token.is_expired());
+        assert!(!token.is_rotated());
+        assert!(!token.is_revoked());
+    }
+
+    #[test]
+    fn test_expired_refresh_token() {
+        let token = RefreshToken::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "hash".to_string(),
+            Duration::seconds(-1),
+        );
+
+        assert!(token.is_expired());
+    }
+}
@@ -1,29 +1,234 @@
+use super::idempotency_key::IdempotencyKey;
+use super::login_challenge::LoginChallenge;
+use super::merchant::{Merchant, MerchantStatusChange, Site, SiteCredentials};
+use super::password_reset_token::PasswordResetToken;
+use super::refresh_token::RefreshToken;
 use super::user::User;
 use crate::common::error::AppError;
+use crate::domains::audit::domain::audit_entry::AuditEntry;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+/// Lookups skip soft-deleted users
 #[async_trait]
 pub trait UserRepository: Send + Sync {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, AppError>;
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError>;
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError>;
 
+    /// Also true for soft-deleted users, whose username is never handed out again
     async fn exists_by_username(&self, username: &str) -> Result<bool, AppError>;
+    /// Also true for soft-deleted users, whose email is never handed out again
     async fn exists_by_email(&self, email: &str) -> Result<bool, AppError>;
 
-    async fn create(&self, user: User) -> Result<User, AppError>;
-    async fn update(&self, user: User) -> Result<User, AppError>;
-    async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+    /// Changes are stored together with their `audit` entry, or not at all.
+    /// The initial password starts the user's password history.
+    async fn create(&self, user: User, audit: AuditEntry) -> Result<User, AppError>;
+    async fn update(&self, user: User, audit: AuditEntry) -> Result<User, AppError>;
+    /// Stores `user.password_hash` and adds it to the password history
+    async fn change_password(&self, user: User, audit: AuditEntry) -> Result<User, AppError>;
+    /// Soft delete: the user is deactivated and hidden, the row is kept
+    async fn delete(&self, id: Uuid, audit: AuditEntry) -> Result<(), AppError>;
 
     async fn list(&self, limit: i64, offset: i64) -> Result<Vec<User>, AppError>;
     async fn search(&self, query: &str, limit: i64) -> Result<Vec<User>, AppError>;
 
     async fn is_admin(&self, id: Uuid) -> Result<bool, AppError>;
     async fn count(&self) -> Result<i64, AppError>;
+
+    /// Latest `limit` password hashes of the user, newest (the current one) first
+    async fn password_history(&self, user_id: Uuid, limit: usize) -> Result<Vec<String>, AppError>;
+}
+
+#[async_trait]
+pub trait PasswordResetTokenRepository: Send + Sync {
+    /// Stores the token; earlier unused tokens of the same user stop working
+    async fn create(&self, token: PasswordResetToken) -> Result<PasswordResetToken, AppError>;
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<PasswordResetToken>, AppError>;
+
+    /// Marks the token as used. Returns `false` if it was used already,
+    /// so the same link cannot reset the password twice.
+    async fn mark_used(&self, id: Uuid) -> Result<bool, AppError>;
+}
+
+#[async_trait]
+pub trait LoginChallengeRepository: Send + Sync {
+    async fn create(&self, challenge: LoginChallenge) -> Result<LoginChallenge, AppError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<LoginChallenge>, AppError>;
+
+    /// Counts one code check against the challenge. Returns `None` without counting
+    /// if the challenge is unknown, expired, consumed or out of attempts, so
+    /// concurrent guesses cannot exceed `max_attempts`.
+    async fn register_attempt(
+        &self,
+        id: Uuid,
+        max_attempts: i32,
+    ) -> Result<Option<LoginChallenge>, AppError>;
+
+    /// Marks the challenge as consumed. Returns `false` if it already was,
+    /// so a code cannot be exchanged twice.
+    async fn consume(&self, id: Uuid) -> Result<bool, AppError>;
+}
+
+/// Revocation list for issued JWTs (logout and forced sign-out)
+#[async_trait]
+pub trait TokenRevocationRepository: Send + Sync {
+    /// Revoke a single token until it expires
+    async fn revoke_token(
+        &self,
+        jti: Uuid,
+        user_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), AppError>;
+
+    /// Revoke every token of the user issued up to `revoked_before`
+    async fn revoke_all_for_user(
+        &self,
+        user_id: Uuid,
+        revoked_before: DateTime<Utc>,
+    ) -> Result<(), AppError>;
+
+    async fn is_revoked(
+        &self,
+        jti: Uuid,
+        user_id: Uuid,
+        issued_at: DateTime<Utc>,
+    ) -> Result<bool, AppError>;
+
+    /// Forgets single revoked tokens that expired by `at`; they are rejected anyway.
+    /// Returns how many were removed.
+    async fn delete_expired(&self, at: DateTime<Utc>) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+pub trait RefreshTokenRepository: Send + Sync {
+    async fn create(&self, token: RefreshToken) -> Result<RefreshToken, AppError>;
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError>;
+
+    /// Marks the token as rotated. Returns `false` if it was already rotated or revoked,
+    /// so two concurrent refreshes with the same token cannot both succeed.
+    async fn mark_rotated(&self, id: Uuid) -> Result<bool, AppError>;
+
+    /// Ends the login session; `audit` is set when a user ends it rather than the system
+    async fn revoke_family(
+        &self,
+        family_id: Uuid,
+        audit: Option<AuditEntry>,
+    ) -> Result<(), AppError>;
+
+    /// Ends every login session of the user
+    async fn revoke_all_for_user(&self, user_id: Uuid, audit: AuditEntry) -> Result<(), AppError>;
+    async fn is_family_revoked(&self, family_id: Uuid) -> Result<bool, AppError>;
+}
+
+/// Remembered requests of the `Idempotency-Key` middleware
+#[async_trait]
+pub trait IdempotencyKeyRepository: Send + Sync {
+    /// Stores `key` unless an unexpired record with the same scope and key exists,
+    /// in which case that record is returned and nothing is stored.
+    /// Expired and abandoned records are replaced.
+    async fn reserve(&self, key: IdempotencyKey) -> Result<Option<IdempotencyKey>, AppError>;
+
+    /// Saves the response of the request whose reservation was created at `reserved_at`.
+    /// Does nothing once a retry took the key over after the lease ran out.
+    async fn complete(
+        &self,
+        scope: &str,
+        key: &str,
+        reserved_at: DateTime<Utc>,
+        response_status: i32,
+        response_body: String,
+    ) -> Result<(), AppError>;
+
+    /// Forgets the key so the request can be sent again; like `complete`, only while the
+    /// reservation created at `reserved_at` still holds it
+    async fn release(
+        &self,
+        scope: &str,
+        key: &str,
+        reserved_at: DateTime<Utc>,
+    ) -> Result<(), AppError>;
+
+    /// Returns how many records were removed
+    async fn delete_expired(&self, at: DateTime<Utc>) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+pub trait MerchantRepository: Send + Sync {
+    /// Merchant with its sites
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Merchant>, AppError>;
+    async fn exists_by_name(&self, name: &str) -> Result<bool, AppError>;
+
+    /// Changes are stored together with their `audit` entry, or not at all
+    async fn create(&self, merchant: Merchant, audit: AuditEntry) -> Result<Merchant, AppError>;
+    /// Updates the merchant row only, sites are managed separately
+    async fn update(&self, merchant: Merchant, audit: AuditEntry) -> Result<Merchant, AppError>;
+
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Merchant>, AppError>;
+    async fn search(&self, query: &str, limit: i64) -> Result<Vec<Merchant>, AppError>;
+
+    /// Persists the new status, its history entry and `audit` atomically
+    async fn change_status(
+        &self,
+        merchant: Merchant,
+        change: MerchantStatusChange,
+        audit: AuditEntry,
+    ) -> Result<Merchant, AppError>;
+    /// Newest first
+    async fn status_history(
+        &self,
+        merchant_id: Uuid,
+    ) -> Result<Vec<MerchantStatusChange>, AppError>;
+    async fn has_active_site_with_credentials(&self, merchant_id: Uuid) -> Result<bool, AppError>;
 }
 
 // Mock для тестирования (используется в use cases)
+#[async_trait]
+pub trait SiteRepository: Send + Sync {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Site>, AppError>;
+    /// Oldest first
+    async fn list_by_merchant(&self, merchant_id: Uuid) -> Result<Vec<Site>, AppError>;
+
+    async fn exists_by_name(&self, name: &str) -> Result<bool, AppError>;
+    async fn exists_by_url(&self, url: &str) -> Result<bool, AppError>;
+
+    /// Changes are stored together with their `audit` entry, or not at all
+    async fn create(&self, site: Site, audit: AuditEntry) -> Result<Site, AppError>;
+    async fn update(&self, site: Site, audit: AuditEntry) -> Result<Site, AppError>;
+}
+
+#[async_trait]
+pub trait SiteCredentialsRepository: Send + Sync {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<SiteCredentials>, AppError>;
+    async fn find_by_public_key(
+        &self,
+        public_key: &str,
+    ) -> Result<Option<SiteCredentials>, AppError>;
+    /// Newest first, including revoked and expired keys
+    async fn list_by_site(&self, site_id: Uuid) -> Result<Vec<SiteCredentials>, AppError>;
+
+    /// Changes are stored together with their `audit` entry, or not at all
+    async fn create(
+        &self,
+        credentials: SiteCredentials,
+        audit: AuditEntry,
+    ) -> Result<SiteCredentials, AppError>;
+    async fn update(
+        &self,
+        credentials: SiteCredentials,
+        audit: AuditEntry,
+    ) -> Result<SiteCredentials, AppError>;
+    /// Stores the new key pair, the updated expiry of the replaced ones and an `audit`
+    /// entry for each of them atomically
+    async fn rotate(
+        &self,
+        credentials: SiteCredentials,
+        replaced: Vec<SiteCredentials>,
+        audit: Vec<AuditEntry>,
+    ) -> Result<SiteCredentials, AppError>;
+}
+
 #[cfg(test)]
 use mockall::mock;
 
@@ -38,12 +243,132 @@ mock! {
         async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError>;
         async fn exists_by_username(&self, username: &str) -> Result<bool, AppError>;
         async fn exists_by_email(&self, email: &str) -> Result<bool, AppError>;
-        async fn create(&self, user: User) -> Result<User, AppError>;
-        async fn update(&self, user: User) -> Result<User, AppError>;
-        async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+        async fn create(&self, user: User, audit: AuditEntry) -> Result<User, AppError>;
+        async fn update(&self, user: User, audit: AuditEntry) -> Result<User, AppError>;
+        async fn change_password(&self, user: User, audit: AuditEntry) -> Result<User, AppError>;
+        async fn delete(&self, id: Uuid, audit: AuditEntry) -> Result<(), AppError>;
         async fn list(&self, limit: i64, offset: i64) -> Result<Vec<User>, AppError>;
         async fn search(&self, query: &str, limit: i64) -> Result<Vec<User>, AppError>;
         async fn is_admin(&self, id: Uuid) -> Result<bool, AppError>;
         async fn count(&self) -> Result<i64, AppError>;
+        async fn password_history(&self, user_id: Uuid, limit: usize) -> Result<Vec<String>, AppError>;
+    }
+}
+
+#[cfg(test)]
+mock! {
+    pub PasswordResetTokenRepository {}
+
+    #[async_trait]
+    impl PasswordResetTokenRepository for PasswordResetTokenRepository {
+        async fn create(&self, token: PasswordResetToken) -> Result<PasswordResetToken, AppError>;
+        async fn find_by_hash(&self, token_hash: &str) -> Result<Option<PasswordResetToken>, AppError>;
+        async fn mark_used(&self, id: Uuid) -> Result<bool, AppError>;
+    }
+}
+
+#[cfg(test)]
+mock! {
+    pub LoginChallengeRepository {}
+
+    #[async_trait]
+    impl LoginChallengeRepository for LoginChallengeRepository {
+        async fn create(&self, challenge: LoginChallenge) -> Result<LoginChallenge, AppError>;
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<LoginChallenge>, AppError>;
+        async fn register_attempt(
+            &self,
+            id: Uuid,
+            max_attempts: i32,
+        ) -> Result<Option<LoginChallenge>, AppError>;
+        async fn consume(&self, id: Uuid) -> Result<bool, AppError>;
+    }
+}
+
+#[cfg(test)]
+mock! {
+    pub TokenRevocationRepository {}
+
+    #[async_trait]
+    impl TokenRevocationRepository for TokenRevocationRepository {
+        async fn revoke_token(&self, jti: Uuid, user_id: Uuid, expires_at: DateTime<Utc>) -> Result<(), AppError>;
+        async fn revoke_all_for_user(&self, user_id: Uuid, revoked_before: DateTime<Utc>) -> Result<(), AppError>;
+        async fn is_revoked(&self, jti: Uuid, user_id: Uuid, issued_at: DateTime<Utc>) -> Result<bool, AppError>;
+        async fn delete_expired(&self, at: DateTime<Utc>) -> Result<u64, AppError>;
+    }
+}
+
+#[cfg(test)]
+mock! {
+    pub IdempotencyKeyRepository {}
+
+    #[async_trait]
+    impl IdempotencyKeyRepository for IdempotencyKeyRepository {
+        async fn reserve(&self, key: IdempotencyKey) -> Result<Option<IdempotencyKey>, AppError>;
+        async fn complete(&self, scope: &str, key: &str, reserved_at: DateTime<Utc>, response_status: i32, response_body: String) -> Result<(), AppError>;
+        async fn release(&self, scope: &str, key: &str, reserved_at: DateTime<Utc>) -> Result<(), AppError>;
+        async fn delete_expired(&self, at: DateTime<Utc>) -> Result<u64, AppError>;
+    }
+}
+
+#[cfg(test)]
+mock! {
+    pub RefreshTokenRepository {}
+
+    #[async_trait]
+    impl RefreshTokenRepository for RefreshTokenRepository {
+        async fn create(&self, token: RefreshToken) -> Result<RefreshToken, AppError>;
+        async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError>;
+        async fn mark_rotated(&self, id: Uuid) -> Result<bool, AppError>;
+        async fn revoke_family(&self, family_id: Uuid, audit: Option<AuditEntry>) -> Result<(), AppError>;
+        async fn revoke_all_for_user(&self, user_id: Uuid, audit: AuditEntry) -> Result<(), AppError>;
+        async fn is_family_revoked(&self, family_id: Uuid) -> Result<bool, AppError>;
+    }
+}
+
+#[cfg(test)]
+mock! {
+    pub MerchantRepository {}
+
+    #[async_trait]
+    impl MerchantRepository for MerchantRepository {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Merchant>, AppError>;
+        async fn exists_by_name(&self, name: &str) -> Result<bool, AppError>;
+        async fn create(&self, merchant: Merchant, audit: AuditEntry) -> Result<Merchant, AppError>;
+        async fn update(&self, merchant: Merchant, audit: AuditEntry) -> Result<Merchant, AppError>;
+        async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Merchant>, AppError>;
+        async fn search(&self, query: &str, limit: i64) -> Result<Vec<Merchant>, AppError>;
+        async fn change_status(&self, merchant: Merchant, change: MerchantStatusChange, audit: AuditEntry) -> Result<Merchant, AppError>;
+        async fn status_history(&self, merchant_id: Uuid) -> Result<Vec<MerchantStatusChange>, AppError>;
+        async fn has_active_site_with_credentials(&self, merchant_id: Uuid) -> Result<bool, AppError>;
+    }
+}
+
+#[cfg(test)]
+mock! {
+    pub SiteRepository {}
+
+    #[async_trait]
+    impl SiteRepository for SiteRepository {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Site>, AppError>;
+        async fn list_by_merchant(&self, merchant_id: Uuid) -> Result<Vec<Site>, AppError>;
+        async fn exists_by_name(&self, name: &str) -> Result<bool, AppError>;
+        async fn exists_by_url(&self, url: &str) -> Result<bool, AppError>;
+        async fn create(&self, site: Site, audit: AuditEntry) -> Result<Site, AppError>;
+        async fn update(&self, site: Site, audit: AuditEntry) -> Result<Site, AppError>;
+    }
+}
+
+#[cfg(test)]
+mock! {
+    pub SiteCredentialsRepository {}
+
+    #[async_trait]
+    impl SiteCredentialsRepository for SiteCredentialsRepository {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<SiteCredentials>, AppError>;
+        async fn find_by_public_key(&self, public_key: &str) -> Result<Option<SiteCredentials>, AppError>;
+        async fn list_by_site(&self, site_id: Uuid) -> Result<Vec<SiteCredentials>, AppError>;
+        async fn create(&self, credentials: SiteCredentials, audit: AuditEntry) -> Result<SiteCredentials, AppError>;
+        async fn update(&self, credentials: SiteCredentials, audit: AuditEntry) -> Result<SiteCredentials, AppError>;
+        async fn rotate(&self, credentials: SiteCredentials, replaced: Vec<SiteCredentials>, audit: Vec<AuditEntry>) -> Result<SiteCredentials, AppError>;
     }
 }
@@ -1,6 +1,7 @@
 use super::super::role::model::Role;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -13,6 +14,8 @@ pub struct User {
     pub role: Role,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set when the user is deleted; the row stays for the records that reference it
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl User {
@@ -27,11 +30,17 @@ impl User {
             role,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         }
     }
 
+    /// Deleted users count as inactive too
     pub fn is_active(&self) -> bool {
-        self.is_active
+        self.is_active && !self.is_deleted()
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
     }
 
     pub fn is_admin(&self) -> bool {
@@ -61,6 +70,11 @@ impl User {
         self.updated_at = Utc::now();
     }
 
+    pub fn update_password_hash(&mut self, password_hash: String) {
+        self.password_hash = password_hash;
+        self.updated_at = Utc::now();
+    }
+
     pub fn deactivate(&mut self) {
         self.is_active = false;
         self.updated_at = Utc::now();
@@ -70,6 +84,23 @@ impl User {
         self.is_active = true;
         self.updated_at = Utc::now();
     }
+
+    pub fn mark_deleted(&mut self) {
+        let now = Utc::now();
+        self.is_active = false;
+        self.deleted_at = Some(now);
+        self.updated_at = now;
+    }
+
+    /// Fields compared in audit entries; the password hash is left out
+    pub fn audit_snapshot(&self) -> Value {
+        json!({
+            "username": self.username,
+            "email": self.email,
+            "is_active": self.is_active,
+            "role_id": self.role.role_id,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -159,6 +190,25 @@ mod tests {
         assert!(!user.is_active());
     }
 
+    #[test]
+    fn test_deleted_user_is_inactive() {
+        let mut user = User::new(
+            "testuser".to_string(),
+            "test@example.com".to_string(),
+            "hash".to_string(),
+            create_test_user_role(),
+        );
+
+        user.mark_deleted();
+
+        assert!(user.is_deleted());
+        assert!(!user.is_active());
+
+        // Reactivation does not undo a deletion
+        user.activate();
+        assert!(!user.is_active());
+    }
+
     #[test]
     fn test_has_role() {
         let user = User::new(
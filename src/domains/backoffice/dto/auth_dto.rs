@@ -0,0 +1,99 @@
+use crate::domains::backoffice::{
+    app::token_issuer::IssuedTokens, domain::login_challenge::LoginChallenge,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct LoginRequest {
+    /// Username or email
+    #[schema(example = "admin")]
+    pub login: String,
+
+    #[schema(example = "my_secure_password123")]
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct LoginChallengeResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub challenge_id: String,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:05:00Z")]
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<LoginChallenge> for LoginChallengeResponse {
+    fn from(challenge: LoginChallenge) -> Self {
+        Self {
+            challenge_id: challenge.id.to_string(),
+            expires_at: challenge.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct VerifyLoginRequest {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub challenge_id: Uuid,
+
+    #[schema(example = "042137")]
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ResetPasswordRequest {
+    /// Token from the emailed reset link
+    pub token: String,
+
+    #[schema(example = "Correct-Horse-Battery-42")]
+    pub new_password: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct TokenResponse {
+    /// JWT to send in the X-JWT-Token header
+    pub access_token: String,
+
+    /// Opaque single-use token for `POST /api/v1/auth/refresh`
+    pub refresh_token: String,
+
+    /// Access token lifetime in seconds
+    #[schema(example = 900)]
+    pub expires_in: i64,
+}
+
+impl From<IssuedTokens> for TokenResponse {
+    fn from(tokens: IssuedTokens) -> Self {
+        Self {
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
+            expires_in: tokens.expires_in,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_challenge_response_from_challenge() {
+        let challenge =
+            LoginChallenge::new(Uuid::new_v4(), "hash".to_string(), Duration::minutes(5));
+
+        let response = LoginChallengeResponse::from(challenge.clone());
+
+        assert_eq!(response.challenge_id, challenge.id.to_string());
+        assert_eq!(response.expires_at, challenge.expires_at);
+    }
+}
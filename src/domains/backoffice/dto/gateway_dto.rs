@@ -0,0 +1,34 @@
+use crate::domains::backoffice::domain::merchant::MerchantContext;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Identity a signed merchant API request was authenticated as
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GatewayIdentityResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub merchant_id: String,
+
+    #[schema(example = "Acme Corp")]
+    pub merchant_name: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub site_id: String,
+
+    #[schema(example = "Acme Shop")]
+    pub site_name: String,
+
+    #[schema(example = "pk_4f9c2a7d1e8b3c6a5d0f9e8b7a6c5d4e")]
+    pub public_key: String,
+}
+
+impl From<MerchantContext> for GatewayIdentityResponse {
+    fn from(context: MerchantContext) -> Self {
+        Self {
+            merchant_id: context.merchant.id.to_string(),
+            merchant_name: context.merchant.name,
+            site_id: context.site.id.to_string(),
+            site_name: context.site.name,
+            public_key: context.credentials.public_key,
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use crate::domains::backoffice::domain::merchant::{
+    Merchant, MerchantStatus, MerchantStatusChange, Site, SiteStatus,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SiteResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    #[schema(example = "Acme Shop")]
+    pub name: String,
+
+    #[schema(example = "https://shop.acme.com")]
+    pub url: String,
+
+    #[schema(example = "https://shop.acme.com/payments/callback")]
+    pub callback_url: String,
+
+    #[schema(example = "https://shop.acme.com/payments/success")]
+    pub redirect_success_url: String,
+
+    #[schema(example = "https://shop.acme.com/payments/fail")]
+    pub redirect_fail_url: String,
+
+    pub status: SiteStatus,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Site> for SiteResponse {
+    fn from(site: Site) -> Self {
+        Self {
+            id: site.id.to_string(),
+            name: site.name,
+            url: site.url,
+            callback_url: site.callback_url,
+            redirect_success_url: site.redirect_success_url,
+            redirect_fail_url: site.redirect_fail_url,
+            status: site.status,
+            created_at: site.created_at,
+            updated_at: site.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MerchantResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    #[schema(example = "Acme Ltd")]
+    pub name: String,
+
+    #[schema(example = "Online electronics retailer")]
+    pub description: Option<String>,
+
+    pub status: MerchantStatus,
+
+    /// Set once KYB checks are completed; required for activation
+    #[serde(with = "crate::common::time_formater::option")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub kyb_verified_at: Option<DateTime<Utc>>,
+
+    pub sites: Vec<SiteResponse>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Merchant> for MerchantResponse {
+    fn from(merchant: Merchant) -> Self {
+        Self {
+            id: merchant.id.to_string(),
+            name: merchant.name,
+            description: merchant.description,
+            status: merchant.status,
+            kyb_verified_at: merchant.kyb_verified_at,
+            sites: merchant.sites.into_iter().map(SiteResponse::from).collect(),
+            created_at: merchant.created_at,
+            updated_at: merchant.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CreateMerchantRequest {
+    #[schema(example = "Acme Ltd")]
+    pub name: String,
+
+    #[schema(example = "Online electronics retailer")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateMerchantRequest {
+    #[schema(example = "Acme Group")]
+    pub name: Option<String>,
+
+    #[schema(example = "Online electronics retailer")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ChangeMerchantStatusRequest {
+    pub status: MerchantStatus,
+
+    /// Required when moving between active and inactive
+    #[schema(example = "Chargeback ratio above threshold")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MerchantStatusChangeResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    pub from_status: MerchantStatus,
+
+    pub to_status: MerchantStatus,
+
+    #[schema(example = "Chargeback ratio above threshold")]
+    pub reason: Option<String>,
+
+    /// Backoffice user who made the change
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub changed_by: Option<String>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub changed_at: DateTime<Utc>,
+}
+
+impl From<MerchantStatusChange> for MerchantStatusChangeResponse {
+    fn from(change: MerchantStatusChange) -> Self {
+        Self {
+            id: change.id.to_string(),
+            from_status: change.from_status,
+            to_status: change.to_status,
+            reason: change.reason,
+            changed_by: change.changed_by.map(|id| id.to_string()),
+            changed_at: change.changed_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListMerchantsQuery {
+    /// Page size, at most 100
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    /// Substring of the merchant name
+    pub search: Option<String>,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merchant_response_from_merchant() {
+        let merchant = Merchant::new("Acme".to_string(), None);
+
+        let response = MerchantResponse::from(merchant.clone());
+
+        assert_eq!(response.id, merchant.id.to_string());
+        assert_eq!(response.status, MerchantStatus::Onboarding);
+        assert_eq!(
+            serde_json::to_value(&response).unwrap()["status"],
+            "onboarding"
+        );
+    }
+}
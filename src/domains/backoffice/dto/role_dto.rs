@@ -0,0 +1,101 @@
+use crate::domains::backoffice::role::model::RoleDetails;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RoleResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub role_id: String,
+
+    #[schema(example = "Auditor")]
+    pub role_name: String,
+
+    #[schema(example = "Read-only access for external auditors")]
+    pub role_description: Option<String>,
+
+    /// Seeded role that cannot be renamed or deleted
+    #[schema(example = "false")]
+    pub is_system: bool,
+
+    #[schema(example = json!(["users:read", "merchants:read"]))]
+    pub permissions: Vec<String>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<RoleDetails> for RoleResponse {
+    fn from(details: RoleDetails) -> Self {
+        let is_system = details.role.is_system();
+        let role = details.role;
+
+        Self {
+            role_id: role.role_id.to_string(),
+            role_name: role.role_name,
+            role_description: role.role_description,
+            is_system,
+            permissions: details.permissions,
+            created_at: role.created_at,
+            updated_at: role.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CreateRoleRequest {
+    #[schema(example = "Auditor")]
+    pub role_name: String,
+
+    #[schema(example = "Read-only access for external auditors")]
+    pub role_description: Option<String>,
+
+    #[serde(default)]
+    #[schema(example = json!(["users:read", "merchants:read"]))]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateRoleRequest {
+    /// Not allowed for system roles
+    #[schema(example = "Compliance")]
+    pub role_name: Option<String>,
+
+    #[schema(example = "Read-only compliance access")]
+    pub role_description: Option<String>,
+
+    /// Replaces the granted permissions when present
+    #[schema(example = json!(["users:read"]))]
+    pub permissions: Option<Vec<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::backoffice::role::model::{admin_role_id, Role};
+
+    #[test]
+    fn test_role_response_from_details() {
+        let role = Role::new(
+            admin_role_id(),
+            "Admin".to_string(),
+            None,
+            Utc::now(),
+            Utc::now(),
+        );
+
+        let response = RoleResponse::from(RoleDetails {
+            role,
+            permissions: vec!["users:write".to_string()],
+        });
+
+        assert_eq!(response.role_id, admin_role_id().to_string());
+        assert!(response.is_system);
+        assert_eq!(response.permissions, vec!["users:write"]);
+    }
+}
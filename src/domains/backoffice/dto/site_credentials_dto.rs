@@ -0,0 +1,106 @@
+use crate::domains::backoffice::domain::merchant::{IssuedSiteCredentials, SiteCredentials};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SiteCredentialsResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub site_id: String,
+
+    #[schema(example = "pk_4f9c2a7d1e8b3c6a5d0f9e8b7a6c5d4e")]
+    pub public_key: String,
+
+    #[schema(example = json!(["203.0.113.10", "198.51.100.0/24"]))]
+    pub allowed_ips: Vec<String>,
+
+    /// Calls are accepted from any IP; otherwise only from `allowed_ips`
+    #[schema(example = "false")]
+    pub allow_any_ip: bool,
+
+    /// False once revoked
+    #[schema(example = "true")]
+    pub is_active: bool,
+
+    /// Set on keys replaced by a rotation
+    #[serde(with = "crate::common::time_formater::option")]
+    #[schema(example = "2024-01-02T12:00:00Z")]
+    pub expires_at: Option<DateTime<Utc>>,
+
+    #[serde(with = "crate::common::time_formater::option")]
+    #[schema(example = "2024-01-02T12:00:00Z")]
+    pub revoked_at: Option<DateTime<Utc>>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SiteCredentials> for SiteCredentialsResponse {
+    fn from(credentials: SiteCredentials) -> Self {
+        Self {
+            id: credentials.id.to_string(),
+            site_id: credentials.site_id.to_string(),
+            public_key: credentials.public_key,
+            allowed_ips: credentials.allowed_ips,
+            allow_any_ip: credentials.allow_any_ip,
+            is_active: credentials.is_active,
+            expires_at: credentials.expires_at,
+            revoked_at: credentials.revoked_at,
+            created_at: credentials.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IssuedSiteCredentialsResponse {
+    pub credentials: SiteCredentialsResponse,
+
+    /// Shown only in this response; it cannot be retrieved again
+    #[schema(example = "sk_9b8a7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b")]
+    pub secret_key: String,
+}
+
+impl From<IssuedSiteCredentials> for IssuedSiteCredentialsResponse {
+    fn from(issued: IssuedSiteCredentials) -> Self {
+        Self {
+            credentials: SiteCredentialsResponse::from(issued.credentials),
+            secret_key: issued.secret_key,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateAllowedIpsRequest {
+    /// Addresses or CIDR ranges; with an empty list the key is refused everywhere
+    #[schema(example = json!(["203.0.113.10", "198.51.100.0/24"]))]
+    pub allowed_ips: Vec<String>,
+
+    /// Opts the key out of the allowlist; `allowed_ips` must then be empty
+    #[serde(default)]
+    #[schema(example = "false")]
+    pub allow_any_ip: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_response_never_contains_encrypted_secret() {
+        let credentials = SiteCredentials::new(
+            Uuid::new_v4(),
+            "pk_test".to_string(),
+            "encrypted".to_string(),
+        );
+
+        let json = serde_json::to_value(SiteCredentialsResponse::from(credentials)).unwrap();
+
+        assert_eq!(json["public_key"], "pk_test");
+        assert!(!json.to_string().contains("encrypted"));
+    }
+}
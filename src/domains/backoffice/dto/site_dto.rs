@@ -0,0 +1,46 @@
+use crate::domains::backoffice::domain::merchant::SiteStatus;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CreateSiteRequest {
+    #[schema(example = "Acme Shop")]
+    pub name: String,
+
+    /// Public address of the site; redirects must stay on its domain
+    #[schema(example = "https://shop.acme.com")]
+    pub url: String,
+
+    /// Receives payment status notifications
+    #[schema(example = "https://api.acme.com/payments/callback")]
+    pub callback_url: String,
+
+    #[schema(example = "https://shop.acme.com/payments/success")]
+    pub redirect_success_url: String,
+
+    #[schema(example = "https://shop.acme.com/payments/fail")]
+    pub redirect_fail_url: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateSiteRequest {
+    #[schema(example = "Acme Store")]
+    pub name: Option<String>,
+
+    #[schema(example = "https://store.acme.com")]
+    pub url: Option<String>,
+
+    #[schema(example = "https://api.acme.com/payments/callback")]
+    pub callback_url: Option<String>,
+
+    #[schema(example = "https://store.acme.com/payments/success")]
+    pub redirect_success_url: Option<String>,
+
+    #[schema(example = "https://store.acme.com/payments/fail")]
+    pub redirect_fail_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ChangeSiteStatusRequest {
+    pub status: SiteStatus,
+}
@@ -74,6 +74,22 @@ pub struct UpdateUserRequest {
     pub role_id: Option<Uuid>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ChangePasswordRequest {
+    #[schema(example = "my_secure_password123")]
+    pub current_password: String,
+
+    #[schema(example = "Correct-Horse-Battery-42")]
+    pub new_password: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PasswordResetResponse {
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T13:00:00Z")]
+    pub expires_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ListUsersQuery {
     #[serde(default = "default_limit")]
@@ -87,11 +103,6 @@ fn default_limit() -> i64 {
     20
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct DeleteUserRequest {
-    pub password_confirmation: Option<String>,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,155 @@
+use super::idempotency_key_entity::{self, Entity as IdempotencyKeyEntity};
+use crate::common::error::AppError;
+use crate::domains::backoffice::domain::{
+    idempotency_key::IdempotencyKey, repository::IdempotencyKeyRepository,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+};
+
+pub struct PostgresIdempotencyKeyRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresIdempotencyKeyRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(entity: idempotency_key_entity::Model) -> IdempotencyKey {
+        IdempotencyKey {
+            scope: entity.scope,
+            key: entity.idempotency_key,
+            request_fingerprint: entity.request_fingerprint,
+            response_status: entity.response_status,
+            response_body: entity.response_body,
+            locked_until: entity.locked_until.map(Into::into),
+            created_at: entity.created_at.into(),
+            expires_at: entity.expires_at.into(),
+        }
+    }
+
+    fn domain_to_active_model(key: IdempotencyKey) -> idempotency_key_entity::ActiveModel {
+        idempotency_key_entity::ActiveModel {
+            scope: Set(key.scope),
+            idempotency_key: Set(key.key),
+            request_fingerprint: Set(key.request_fingerprint),
+            response_status: Set(key.response_status),
+            response_body: Set(key.response_body),
+            locked_until: Set(key.locked_until.map(Into::into)),
+            created_at: Set(key.created_at.into()),
+            expires_at: Set(key.expires_at.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl IdempotencyKeyRepository for PostgresIdempotencyKeyRepository {
+    async fn reserve(&self, key: IdempotencyKey) -> Result<Option<IdempotencyKey>, AppError> {
+        let scope = key.scope.clone();
+        let idempotency_key = key.key.clone();
+        let now = key.created_at;
+
+        // Concurrent requests race on the primary key; only one of them stores its row.
+        // A request that left no response within its lease gives the key up to a retry.
+        let column = |column| Expr::col((IdempotencyKeyEntity, column));
+        let replaceable = column(idempotency_key_entity::Column::ExpiresAt)
+            .lte(now)
+            .or(column(idempotency_key_entity::Column::ResponseStatus)
+                .is_null()
+                .and(
+                    column(idempotency_key_entity::Column::LockedUntil)
+                        .is_null()
+                        .or(column(idempotency_key_entity::Column::LockedUntil).lte(now)),
+                ));
+        let inserted = IdempotencyKeyEntity::insert(Self::domain_to_active_model(key))
+            .on_conflict(
+                OnConflict::columns([
+                    idempotency_key_entity::Column::Scope,
+                    idempotency_key_entity::Column::IdempotencyKey,
+                ])
+                .update_columns([
+                    idempotency_key_entity::Column::RequestFingerprint,
+                    idempotency_key_entity::Column::ResponseStatus,
+                    idempotency_key_entity::Column::ResponseBody,
+                    idempotency_key_entity::Column::LockedUntil,
+                    idempotency_key_entity::Column::CreatedAt,
+                    idempotency_key_entity::Column::ExpiresAt,
+                ])
+                .action_and_where(replaceable)
+                .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await?;
+
+        if inserted > 0 {
+            return Ok(None);
+        }
+
+        let existing = IdempotencyKeyEntity::find_by_id((scope, idempotency_key.clone()))
+            .one(&self.db)
+            .await?
+            // Released by the request holding it between the insert and this read
+            .ok_or(AppError::Conflict(format!(
+                "Request with idempotency key '{}' is still being processed",
+                idempotency_key
+            )))?;
+
+        Ok(Some(Self::entity_to_domain(existing)))
+    }
+
+    async fn complete(
+        &self,
+        scope: &str,
+        key: &str,
+        reserved_at: DateTime<Utc>,
+        response_status: i32,
+        response_body: String,
+    ) -> Result<(), AppError> {
+        IdempotencyKeyEntity::update_many()
+            .col_expr(
+                idempotency_key_entity::Column::ResponseStatus,
+                Expr::value(response_status),
+            )
+            .col_expr(
+                idempotency_key_entity::Column::ResponseBody,
+                Expr::value(response_body),
+            )
+            .filter(idempotency_key_entity::Column::Scope.eq(scope))
+            .filter(idempotency_key_entity::Column::IdempotencyKey.eq(key))
+            // A takeover replaces created_at, fencing off the request that lost the key
+            .filter(idempotency_key_entity::Column::CreatedAt.eq(reserved_at))
+            .exec(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn release(
+        &self,
+        scope: &str,
+        key: &str,
+        reserved_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        IdempotencyKeyEntity::delete_many()
+            .filter(idempotency_key_entity::Column::Scope.eq(scope))
+            .filter(idempotency_key_entity::Column::IdempotencyKey.eq(key))
+            .filter(idempotency_key_entity::Column::CreatedAt.eq(reserved_at))
+            .exec(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_expired(&self, at: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = IdempotencyKeyEntity::delete_many()
+            .filter(idempotency_key_entity::Column::ExpiresAt.lte(at))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
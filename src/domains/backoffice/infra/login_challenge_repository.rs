@@ -0,0 +1,99 @@
+use super::login_challenge_entity::{self, Entity as LoginChallengeEntity};
+use crate::common::error::AppError;
+use crate::domains::backoffice::domain::{
+    login_challenge::LoginChallenge, repository::LoginChallengeRepository,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    Set,
+};
+use uuid::Uuid;
+
+pub struct PostgresLoginChallengeRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresLoginChallengeRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(entity: login_challenge_entity::Model) -> LoginChallenge {
+        LoginChallenge {
+            id: entity.id,
+            user_id: entity.user_id,
+            code_hash: entity.code_hash,
+            attempts: entity.attempts,
+            expires_at: entity.expires_at.with_timezone(&Utc),
+            consumed_at: entity.consumed_at.map(|at| at.with_timezone(&Utc)),
+            created_at: entity.created_at.with_timezone(&Utc),
+        }
+    }
+
+    fn domain_to_active_model(challenge: LoginChallenge) -> login_challenge_entity::ActiveModel {
+        login_challenge_entity::ActiveModel {
+            id: Set(challenge.id),
+            user_id: Set(challenge.user_id),
+            code_hash: Set(challenge.code_hash),
+            attempts: Set(challenge.attempts),
+            expires_at: Set(challenge.expires_at.into()),
+            consumed_at: Set(challenge.consumed_at.map(Into::into)),
+            created_at: Set(challenge.created_at.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl LoginChallengeRepository for PostgresLoginChallengeRepository {
+    async fn create(&self, challenge: LoginChallenge) -> Result<LoginChallenge, AppError> {
+        let active_model = Self::domain_to_active_model(challenge);
+        let model = active_model.insert(&self.db).await?;
+
+        Ok(Self::entity_to_domain(model))
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<LoginChallenge>, AppError> {
+        let challenge = LoginChallengeEntity::find_by_id(id).one(&self.db).await?;
+
+        Ok(challenge.map(Self::entity_to_domain))
+    }
+
+    async fn register_attempt(
+        &self,
+        id: Uuid,
+        max_attempts: i32,
+    ) -> Result<Option<LoginChallenge>, AppError> {
+        // A single conditional UPDATE: concurrent checks are serialised on the row
+        let challenge = LoginChallengeEntity::update_many()
+            .col_expr(
+                login_challenge_entity::Column::Attempts,
+                Expr::col(login_challenge_entity::Column::Attempts).add(1),
+            )
+            .filter(login_challenge_entity::Column::Id.eq(id))
+            .filter(login_challenge_entity::Column::Attempts.lt(max_attempts))
+            .filter(login_challenge_entity::Column::ConsumedAt.is_null())
+            .filter(login_challenge_entity::Column::ExpiresAt.gt(Utc::now()))
+            .exec_with_returning(&self.db)
+            .await?
+            .into_iter()
+            .next();
+
+        Ok(challenge.map(Self::entity_to_domain))
+    }
+
+    async fn consume(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = LoginChallengeEntity::update_many()
+            .col_expr(
+                login_challenge_entity::Column::ConsumedAt,
+                Expr::value(Utc::now()),
+            )
+            .filter(login_challenge_entity::Column::Id.eq(id))
+            .filter(login_challenge_entity::Column::ConsumedAt.is_null())
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected == 1)
+    }
+}
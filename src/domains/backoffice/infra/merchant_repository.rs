@@ -0,0 +1,242 @@
+use super::merchant_entity::{self, Entity as MerchantEntity};
+use super::merchant_status_history_entity::{self, Entity as MerchantStatusHistoryEntity};
+use super::site_credentials_entity::{self, Entity as SiteCredentialsEntity};
+use super::site_entity::{self, Entity as SiteEntity};
+use crate::common::error::AppError;
+use crate::domains::audit::{
+    domain::audit_entry::AuditEntry, infra::audit_log_repository::PostgresAuditLogRepository,
+};
+use crate::domains::backoffice::domain::{
+    merchant::{Merchant, MerchantStatusChange, Site, SiteStatus},
+    repository::MerchantRepository,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, JoinType,
+    LoaderTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set,
+    TransactionTrait,
+};
+use uuid::Uuid;
+
+pub struct PostgresMerchantRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresMerchantRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub(crate) fn site_to_domain(site: site_entity::Model) -> Result<Site, AppError> {
+        Ok(Site {
+            id: site.id,
+            merchant_id: site.merchant_id,
+            name: site.name,
+            url: site.url,
+            callback_url: site.callback_url,
+            redirect_success_url: site.redirect_success_url,
+            redirect_fail_url: site.redirect_fail_url,
+            status: site.status.parse()?,
+            created_at: site.created_at.with_timezone(&Utc),
+            updated_at: site.updated_at.with_timezone(&Utc),
+        })
+    }
+
+    fn entity_to_domain(
+        merchant: merchant_entity::Model,
+        sites: Vec<site_entity::Model>,
+    ) -> Result<Merchant, AppError> {
+        Ok(Merchant {
+            id: merchant.id,
+            name: merchant.name,
+            description: merchant.description,
+            status: merchant.status.parse()?,
+            kyb_verified_at: merchant.kyb_verified_at.map(|at| at.with_timezone(&Utc)),
+            sites: sites
+                .into_iter()
+                .map(Self::site_to_domain)
+                .collect::<Result<_, _>>()?,
+            created_at: merchant.created_at.with_timezone(&Utc),
+            updated_at: merchant.updated_at.with_timezone(&Utc),
+        })
+    }
+
+    fn domain_to_active_model(merchant: Merchant) -> merchant_entity::ActiveModel {
+        merchant_entity::ActiveModel {
+            id: Set(merchant.id),
+            name: Set(merchant.name),
+            description: Set(merchant.description),
+            status: Set(merchant.status.as_str().to_string()),
+            kyb_verified_at: Set(merchant.kyb_verified_at.map(Into::into)),
+            created_at: Set(merchant.created_at.into()),
+            updated_at: Set(merchant.updated_at.into()),
+        }
+    }
+
+    fn history_to_domain(
+        entry: merchant_status_history_entity::Model,
+    ) -> Result<MerchantStatusChange, AppError> {
+        Ok(MerchantStatusChange {
+            id: entry.id,
+            merchant_id: entry.merchant_id,
+            from_status: entry.from_status.parse()?,
+            to_status: entry.to_status.parse()?,
+            reason: entry.reason,
+            changed_by: entry.changed_by,
+            changed_at: entry.changed_at.with_timezone(&Utc),
+        })
+    }
+
+    /// Attaches the sites of every merchant with a single extra query
+    async fn with_sites(
+        &self,
+        merchants: Vec<merchant_entity::Model>,
+    ) -> Result<Vec<Merchant>, AppError> {
+        let sites = merchants
+            .load_many(
+                SiteEntity::find().order_by_asc(site_entity::Column::CreatedAt),
+                &self.db,
+            )
+            .await?;
+
+        merchants
+            .into_iter()
+            .zip(sites)
+            .map(|(merchant, sites)| Self::entity_to_domain(merchant, sites))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl MerchantRepository for PostgresMerchantRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Merchant>, AppError> {
+        let merchant = MerchantEntity::find_by_id(id).one(&self.db).await?;
+
+        match merchant {
+            Some(merchant) => Ok(self.with_sites(vec![merchant]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    async fn exists_by_name(&self, name: &str) -> Result<bool, AppError> {
+        let count = MerchantEntity::find()
+            .filter(merchant_entity::Column::Name.eq(name))
+            .count(&self.db)
+            .await?;
+
+        Ok(count > 0)
+    }
+
+    async fn create(&self, merchant: Merchant, audit: AuditEntry) -> Result<Merchant, AppError> {
+        let txn = self.db.begin().await?;
+        let model = Self::domain_to_active_model(merchant).insert(&txn).await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        Self::entity_to_domain(model, Vec::new())
+    }
+
+    async fn update(&self, merchant: Merchant, audit: AuditEntry) -> Result<Merchant, AppError> {
+        let sites = merchant.sites.clone();
+        let txn = self.db.begin().await?;
+        let model = Self::domain_to_active_model(merchant).update(&txn).await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        let mut merchant = Self::entity_to_domain(model, Vec::new())?;
+        merchant.sites = sites;
+
+        Ok(merchant)
+    }
+
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Merchant>, AppError> {
+        let merchants = MerchantEntity::find()
+            .order_by_asc(merchant_entity::Column::Name)
+            .limit(limit as u64)
+            .offset(offset as u64)
+            .all(&self.db)
+            .await?;
+
+        self.with_sites(merchants).await
+    }
+
+    async fn search(&self, query: &str, limit: i64) -> Result<Vec<Merchant>, AppError> {
+        let search_pattern = format!("%{}%", query);
+
+        let merchants = MerchantEntity::find()
+            .filter(merchant_entity::Column::Name.like(&search_pattern))
+            .order_by_asc(merchant_entity::Column::Name)
+            .limit(limit as u64)
+            .all(&self.db)
+            .await?;
+
+        self.with_sites(merchants).await
+    }
+
+    async fn change_status(
+        &self,
+        merchant: Merchant,
+        change: MerchantStatusChange,
+        audit: AuditEntry,
+    ) -> Result<Merchant, AppError> {
+        let sites = merchant.sites.clone();
+        let txn = self.db.begin().await?;
+
+        let model = Self::domain_to_active_model(merchant).update(&txn).await?;
+
+        merchant_status_history_entity::ActiveModel {
+            id: Set(change.id),
+            merchant_id: Set(change.merchant_id),
+            from_status: Set(change.from_status.as_str().to_string()),
+            to_status: Set(change.to_status.as_str().to_string()),
+            reason: Set(change.reason),
+            changed_by: Set(change.changed_by),
+            changed_at: Set(change.changed_at.into()),
+        }
+        .insert(&txn)
+        .await?;
+
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        let mut merchant = Self::entity_to_domain(model, Vec::new())?;
+        merchant.sites = sites;
+
+        Ok(merchant)
+    }
+
+    async fn status_history(
+        &self,
+        merchant_id: Uuid,
+    ) -> Result<Vec<MerchantStatusChange>, AppError> {
+        MerchantStatusHistoryEntity::find()
+            .filter(merchant_status_history_entity::Column::MerchantId.eq(merchant_id))
+            .order_by_desc(merchant_status_history_entity::Column::ChangedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(Self::history_to_domain)
+            .collect()
+    }
+
+    async fn has_active_site_with_credentials(&self, merchant_id: Uuid) -> Result<bool, AppError> {
+        let count = SiteCredentialsEntity::find()
+            .join(
+                JoinType::InnerJoin,
+                site_credentials_entity::Relation::Site.def(),
+            )
+            .filter(site_entity::Column::MerchantId.eq(merchant_id))
+            .filter(site_entity::Column::Status.eq(SiteStatus::Active.as_str()))
+            .filter(site_credentials_entity::Column::IsActive.eq(true))
+            .filter(
+                Condition::any()
+                    .add(site_credentials_entity::Column::ExpiresAt.is_null())
+                    .add(site_credentials_entity::Column::ExpiresAt.gt(Utc::now())),
+            )
+            .count(&self.db)
+            .await?;
+
+        Ok(count > 0)
+    }
+}
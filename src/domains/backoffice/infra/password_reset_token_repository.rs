@@ -0,0 +1,91 @@
+use super::password_reset_token_entity::{self, Entity as PasswordResetTokenEntity};
+use crate::common::error::AppError;
+use crate::domains::backoffice::domain::{
+    password_reset_token::PasswordResetToken, repository::PasswordResetTokenRepository,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+pub struct PostgresPasswordResetTokenRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresPasswordResetTokenRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(entity: password_reset_token_entity::Model) -> PasswordResetToken {
+        PasswordResetToken {
+            id: entity.id,
+            user_id: entity.user_id,
+            token_hash: entity.token_hash,
+            requested_by: entity.requested_by,
+            expires_at: entity.expires_at.with_timezone(&Utc),
+            used_at: entity.used_at.map(|at| at.with_timezone(&Utc)),
+            created_at: entity.created_at.with_timezone(&Utc),
+        }
+    }
+
+    fn domain_to_active_model(
+        token: PasswordResetToken,
+    ) -> password_reset_token_entity::ActiveModel {
+        password_reset_token_entity::ActiveModel {
+            id: Set(token.id),
+            user_id: Set(token.user_id),
+            token_hash: Set(token.token_hash),
+            requested_by: Set(token.requested_by),
+            expires_at: Set(token.expires_at.into()),
+            used_at: Set(token.used_at.map(Into::into)),
+            created_at: Set(token.created_at.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for PostgresPasswordResetTokenRepository {
+    async fn create(&self, token: PasswordResetToken) -> Result<PasswordResetToken, AppError> {
+        let txn = self.db.begin().await?;
+        PasswordResetTokenEntity::update_many()
+            .col_expr(
+                password_reset_token_entity::Column::UsedAt,
+                Expr::value(token.created_at),
+            )
+            .filter(password_reset_token_entity::Column::UserId.eq(token.user_id))
+            .filter(password_reset_token_entity::Column::UsedAt.is_null())
+            .exec(&txn)
+            .await?;
+        let model = Self::domain_to_active_model(token).insert(&txn).await?;
+        txn.commit().await?;
+
+        Ok(Self::entity_to_domain(model))
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<PasswordResetToken>, AppError> {
+        let token = PasswordResetTokenEntity::find()
+            .filter(password_reset_token_entity::Column::TokenHash.eq(token_hash))
+            .one(&self.db)
+            .await?;
+
+        Ok(token.map(Self::entity_to_domain))
+    }
+
+    async fn mark_used(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = PasswordResetTokenEntity::update_many()
+            .col_expr(
+                password_reset_token_entity::Column::UsedAt,
+                Expr::value(Utc::now()),
+            )
+            .filter(password_reset_token_entity::Column::Id.eq(id))
+            .filter(password_reset_token_entity::Column::UsedAt.is_null())
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected == 1)
+    }
+}
@@ -0,0 +1,139 @@
+use super::refresh_token_entity::{self, Entity as RefreshTokenEntity};
+use crate::common::error::AppError;
+use crate::domains::{
+    audit::{
+        domain::audit_entry::AuditEntry, infra::audit_log_repository::PostgresAuditLogRepository,
+    },
+    backoffice::domain::{refresh_token::RefreshToken, repository::RefreshTokenRepository},
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+pub struct PostgresRefreshTokenRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresRefreshTokenRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(entity: refresh_token_entity::Model) -> RefreshToken {
+        RefreshToken {
+            id: entity.id,
+            user_id: entity.user_id,
+            family_id: entity.family_id,
+            token_hash: entity.token_hash,
+            expires_at: entity.expires_at.with_timezone(&Utc),
+            rotated_at: entity.rotated_at.map(|at| at.with_timezone(&Utc)),
+            revoked_at: entity.revoked_at.map(|at| at.with_timezone(&Utc)),
+            created_at: entity.created_at.with_timezone(&Utc),
+        }
+    }
+
+    fn domain_to_active_model(token: RefreshToken) -> refresh_token_entity::ActiveModel {
+        refresh_token_entity::ActiveModel {
+            id: Set(token.id),
+            user_id: Set(token.user_id),
+            family_id: Set(token.family_id),
+            token_hash: Set(token.token_hash),
+            expires_at: Set(token.expires_at.into()),
+            rotated_at: Set(token.rotated_at.map(Into::into)),
+            revoked_at: Set(token.revoked_at.map(Into::into)),
+            created_at: Set(token.created_at.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for PostgresRefreshTokenRepository {
+    async fn create(&self, token: RefreshToken) -> Result<RefreshToken, AppError> {
+        let active_model = Self::domain_to_active_model(token);
+        let model = active_model.insert(&self.db).await?;
+
+        Ok(Self::entity_to_domain(model))
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError> {
+        let token = RefreshTokenEntity::find()
+            .filter(refresh_token_entity::Column::TokenHash.eq(token_hash))
+            .one(&self.db)
+            .await?;
+
+        Ok(token.map(Self::entity_to_domain))
+    }
+
+    async fn mark_rotated(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = RefreshTokenEntity::update_many()
+            .col_expr(
+                refresh_token_entity::Column::RotatedAt,
+                Expr::value(Utc::now()),
+            )
+            .filter(refresh_token_entity::Column::Id.eq(id))
+            .filter(refresh_token_entity::Column::RotatedAt.is_null())
+            .filter(refresh_token_entity::Column::RevokedAt.is_null())
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected == 1)
+    }
+
+    async fn revoke_family(
+        &self,
+        family_id: Uuid,
+        audit: Option<AuditEntry>,
+    ) -> Result<(), AppError> {
+        let txn = self.db.begin().await?;
+
+        RefreshTokenEntity::update_many()
+            .col_expr(
+                refresh_token_entity::Column::RevokedAt,
+                Expr::value(Utc::now()),
+            )
+            .filter(refresh_token_entity::Column::FamilyId.eq(family_id))
+            .filter(refresh_token_entity::Column::RevokedAt.is_null())
+            .exec(&txn)
+            .await?;
+        if let Some(audit) = audit {
+            PostgresAuditLogRepository::append(&txn, audit).await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    async fn revoke_all_for_user(&self, user_id: Uuid, audit: AuditEntry) -> Result<(), AppError> {
+        let txn = self.db.begin().await?;
+
+        RefreshTokenEntity::update_many()
+            .col_expr(
+                refresh_token_entity::Column::RevokedAt,
+                Expr::value(Utc::now()),
+            )
+            .filter(refresh_token_entity::Column::UserId.eq(user_id))
+            .filter(refresh_token_entity::Column::RevokedAt.is_null())
+            .exec(&txn)
+            .await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    async fn is_family_revoked(&self, family_id: Uuid) -> Result<bool, AppError> {
+        let revoked = RefreshTokenEntity::find()
+            .filter(refresh_token_entity::Column::FamilyId.eq(family_id))
+            .filter(refresh_token_entity::Column::RevokedAt.is_not_null())
+            .count(&self.db)
+            .await?;
+
+        Ok(revoked > 0)
+    }
+}
@@ -0,0 +1,37 @@
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "site_credentials")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub site_id: Uuid,
+    #[sea_orm(unique)]
+    pub public_key: String,
+    pub encrypted_secret_key: String,
+    pub allowed_ips: Vec<String>,
+    pub allow_any_ip: bool,
+    pub is_active: bool,
+    pub expires_at: Option<DateTimeWithTimeZone>,
+    pub revoked_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::site_entity::Entity",
+        from = "Column::SiteId",
+        to = "super::site_entity::Column::Id"
+    )]
+    Site,
+}
+
+impl Related<super::site_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Site.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
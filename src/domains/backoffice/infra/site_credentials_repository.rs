@@ -0,0 +1,142 @@
+use super::site_credentials_entity::{self, Entity as SiteCredentialsEntity};
+use crate::common::error::AppError;
+use crate::domains::audit::{
+    domain::audit_entry::AuditEntry, infra::audit_log_repository::PostgresAuditLogRepository,
+};
+use crate::domains::backoffice::domain::{
+    merchant::SiteCredentials, repository::SiteCredentialsRepository,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
+use uuid::Uuid;
+
+pub struct PostgresSiteCredentialsRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresSiteCredentialsRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(model: site_credentials_entity::Model) -> SiteCredentials {
+        SiteCredentials {
+            id: model.id,
+            site_id: model.site_id,
+            public_key: model.public_key,
+            encrypted_secret_key: model.encrypted_secret_key,
+            allowed_ips: model.allowed_ips,
+            allow_any_ip: model.allow_any_ip,
+            is_active: model.is_active,
+            expires_at: model.expires_at.map(|at| at.with_timezone(&Utc)),
+            revoked_at: model.revoked_at.map(|at| at.with_timezone(&Utc)),
+            created_at: model.created_at.with_timezone(&Utc),
+        }
+    }
+
+    fn domain_to_active_model(
+        credentials: SiteCredentials,
+    ) -> site_credentials_entity::ActiveModel {
+        site_credentials_entity::ActiveModel {
+            id: Set(credentials.id),
+            site_id: Set(credentials.site_id),
+            public_key: Set(credentials.public_key),
+            encrypted_secret_key: Set(credentials.encrypted_secret_key),
+            allowed_ips: Set(credentials.allowed_ips),
+            allow_any_ip: Set(credentials.allow_any_ip),
+            is_active: Set(credentials.is_active),
+            expires_at: Set(credentials.expires_at.map(Into::into)),
+            revoked_at: Set(credentials.revoked_at.map(Into::into)),
+            created_at: Set(credentials.created_at.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl SiteCredentialsRepository for PostgresSiteCredentialsRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<SiteCredentials>, AppError> {
+        let model = SiteCredentialsEntity::find_by_id(id).one(&self.db).await?;
+
+        Ok(model.map(Self::entity_to_domain))
+    }
+
+    async fn find_by_public_key(
+        &self,
+        public_key: &str,
+    ) -> Result<Option<SiteCredentials>, AppError> {
+        let model = SiteCredentialsEntity::find()
+            .filter(site_credentials_entity::Column::PublicKey.eq(public_key))
+            .one(&self.db)
+            .await?;
+
+        Ok(model.map(Self::entity_to_domain))
+    }
+
+    async fn list_by_site(&self, site_id: Uuid) -> Result<Vec<SiteCredentials>, AppError> {
+        let models = SiteCredentialsEntity::find()
+            .filter(site_credentials_entity::Column::SiteId.eq(site_id))
+            .order_by_desc(site_credentials_entity::Column::CreatedAt)
+            .all(&self.db)
+            .await?;
+
+        Ok(models.into_iter().map(Self::entity_to_domain).collect())
+    }
+
+    async fn create(
+        &self,
+        credentials: SiteCredentials,
+        audit: AuditEntry,
+    ) -> Result<SiteCredentials, AppError> {
+        let txn = self.db.begin().await?;
+        let model = Self::domain_to_active_model(credentials)
+            .insert(&txn)
+            .await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        Ok(Self::entity_to_domain(model))
+    }
+
+    async fn update(
+        &self,
+        credentials: SiteCredentials,
+        audit: AuditEntry,
+    ) -> Result<SiteCredentials, AppError> {
+        let txn = self.db.begin().await?;
+        let model = Self::domain_to_active_model(credentials)
+            .update(&txn)
+            .await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        Ok(Self::entity_to_domain(model))
+    }
+
+    async fn rotate(
+        &self,
+        credentials: SiteCredentials,
+        replaced: Vec<SiteCredentials>,
+        audit: Vec<AuditEntry>,
+    ) -> Result<SiteCredentials, AppError> {
+        let txn = self.db.begin().await?;
+
+        for old in replaced {
+            Self::domain_to_active_model(old).update(&txn).await?;
+        }
+
+        let model = Self::domain_to_active_model(credentials)
+            .insert(&txn)
+            .await?;
+
+        for entry in audit {
+            PostgresAuditLogRepository::append(&txn, entry).await?;
+        }
+        txn.commit().await?;
+
+        Ok(Self::entity_to_domain(model))
+    }
+}
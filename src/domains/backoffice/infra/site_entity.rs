@@ -0,0 +1,46 @@
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "site")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub merchant_id: Uuid,
+    #[sea_orm(unique)]
+    pub name: String,
+    #[sea_orm(unique)]
+    pub url: String,
+    pub callback_url: String,
+    pub redirect_success_url: String,
+    pub redirect_fail_url: String,
+    pub status: String,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::merchant_entity::Entity",
+        from = "Column::MerchantId",
+        to = "super::merchant_entity::Column::Id"
+    )]
+    Merchant,
+    #[sea_orm(has_many = "super::site_credentials_entity::Entity")]
+    SiteCredentials,
+}
+
+impl Related<super::merchant_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Merchant.def()
+    }
+}
+
+impl Related<super::site_credentials_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SiteCredentials.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,96 @@
+use super::merchant_repository::PostgresMerchantRepository;
+use super::site_entity::{self, Entity as SiteEntity};
+use crate::common::error::AppError;
+use crate::domains::audit::{
+    domain::audit_entry::AuditEntry, infra::audit_log_repository::PostgresAuditLogRepository,
+};
+use crate::domains::backoffice::domain::{merchant::Site, repository::SiteRepository};
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+pub struct PostgresSiteRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresSiteRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn domain_to_active_model(site: Site) -> site_entity::ActiveModel {
+        site_entity::ActiveModel {
+            id: Set(site.id),
+            merchant_id: Set(site.merchant_id),
+            name: Set(site.name),
+            url: Set(site.url),
+            callback_url: Set(site.callback_url),
+            redirect_success_url: Set(site.redirect_success_url),
+            redirect_fail_url: Set(site.redirect_fail_url),
+            status: Set(site.status.as_str().to_string()),
+            created_at: Set(site.created_at.into()),
+            updated_at: Set(site.updated_at.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl SiteRepository for PostgresSiteRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Site>, AppError> {
+        SiteEntity::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .map(PostgresMerchantRepository::site_to_domain)
+            .transpose()
+    }
+
+    async fn list_by_merchant(&self, merchant_id: Uuid) -> Result<Vec<Site>, AppError> {
+        SiteEntity::find()
+            .filter(site_entity::Column::MerchantId.eq(merchant_id))
+            .order_by_asc(site_entity::Column::CreatedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(PostgresMerchantRepository::site_to_domain)
+            .collect()
+    }
+
+    async fn exists_by_name(&self, name: &str) -> Result<bool, AppError> {
+        let count = SiteEntity::find()
+            .filter(site_entity::Column::Name.eq(name))
+            .count(&self.db)
+            .await?;
+
+        Ok(count > 0)
+    }
+
+    async fn exists_by_url(&self, url: &str) -> Result<bool, AppError> {
+        let count = SiteEntity::find()
+            .filter(site_entity::Column::Url.eq(url))
+            .count(&self.db)
+            .await?;
+
+        Ok(count > 0)
+    }
+
+    async fn create(&self, site: Site, audit: AuditEntry) -> Result<Site, AppError> {
+        let txn = self.db.begin().await?;
+        let model = Self::domain_to_active_model(site).insert(&txn).await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        PostgresMerchantRepository::site_to_domain(model)
+    }
+
+    async fn update(&self, site: Site, audit: AuditEntry) -> Result<Site, AppError> {
+        let txn = self.db.begin().await?;
+        let model = Self::domain_to_active_model(site).update(&txn).await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        PostgresMerchantRepository::site_to_domain(model)
+    }
+}
@@ -0,0 +1,254 @@
+use super::revoked_token_entity::{self, Entity as RevokedTokenEntity};
+use super::user_token_revocation_entity::{self, Entity as UserTokenRevocationEntity};
+use crate::common::error::AppError;
+use crate::domains::backoffice::domain::repository::TokenRevocationRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, Set,
+};
+use std::{collections::HashMap, sync::Mutex};
+use uuid::Uuid;
+
+pub struct PostgresTokenRevocationRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresTokenRevocationRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl TokenRevocationRepository for PostgresTokenRevocationRepository {
+    async fn revoke_token(
+        &self,
+        jti: Uuid,
+        user_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let active_model = revoked_token_entity::ActiveModel {
+            jti: Set(jti),
+            user_id: Set(user_id),
+            expires_at: Set(expires_at.into()),
+            revoked_at: Set(Utc::now().into()),
+        };
+
+        RevokedTokenEntity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(revoked_token_entity::Column::Jti)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn revoke_all_for_user(
+        &self,
+        user_id: Uuid,
+        revoked_before: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let active_model = user_token_revocation_entity::ActiveModel {
+            user_id: Set(user_id),
+            revoked_before: Set(revoked_before.into()),
+        };
+
+        UserTokenRevocationEntity::insert(active_model)
+            .on_conflict(
+                OnConflict::column(user_token_revocation_entity::Column::UserId)
+                    .update_column(user_token_revocation_entity::Column::RevokedBefore)
+                    .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn is_revoked(
+        &self,
+        jti: Uuid,
+        user_id: Uuid,
+        issued_at: DateTime<Utc>,
+    ) -> Result<bool, AppError> {
+        let revoked_tokens = RevokedTokenEntity::find()
+            .filter(revoked_token_entity::Column::Jti.eq(jti))
+            .count(&self.db)
+            .await?;
+
+        if revoked_tokens > 0 {
+            return Ok(true);
+        }
+
+        let user_revocations = UserTokenRevocationEntity::find()
+            .filter(user_token_revocation_entity::Column::UserId.eq(user_id))
+            .filter(user_token_revocation_entity::Column::RevokedBefore.gte(issued_at))
+            .count(&self.db)
+            .await?;
+
+        Ok(user_revocations > 0)
+    }
+
+    async fn delete_expired(&self, at: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = RevokedTokenEntity::delete_many()
+            .filter(revoked_token_entity::Column::ExpiresAt.lte(at))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
+
+/// Process-local revocation list for development and single-instance setups.
+/// Entries are lost on restart.
+#[derive(Default)]
+pub struct InMemoryTokenRevocationRepository {
+    revoked_tokens: Mutex<HashMap<Uuid, DateTime<Utc>>>,
+    user_revocations: Mutex<HashMap<Uuid, DateTime<Utc>>>,
+}
+
+impl InMemoryTokenRevocationRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TokenRevocationRepository for InMemoryTokenRevocationRepository {
+    async fn revoke_token(
+        &self,
+        jti: Uuid,
+        _user_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let mut revoked_tokens = self.revoked_tokens.lock().unwrap();
+
+        // Expired tokens are rejected anyway, no need to keep them
+        let now = Utc::now();
+        revoked_tokens.retain(|_, expires_at| *expires_at > now);
+        revoked_tokens.insert(jti, expires_at);
+
+        Ok(())
+    }
+
+    async fn revoke_all_for_user(
+        &self,
+        user_id: Uuid,
+        revoked_before: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        self.user_revocations
+            .lock()
+            .unwrap()
+            .insert(user_id, revoked_before);
+
+        Ok(())
+    }
+
+    async fn is_revoked(
+        &self,
+        jti: Uuid,
+        user_id: Uuid,
+        issued_at: DateTime<Utc>,
+    ) -> Result<bool, AppError> {
+        if self.revoked_tokens.lock().unwrap().contains_key(&jti) {
+            return Ok(true);
+        }
+
+        let user_revocations = self.user_revocations.lock().unwrap();
+        Ok(user_revocations
+            .get(&user_id)
+            .is_some_and(|revoked_before| issued_at <= *revoked_before))
+    }
+
+    async fn delete_expired(&self, at: DateTime<Utc>) -> Result<u64, AppError> {
+        let mut revoked_tokens = self.revoked_tokens.lock().unwrap();
+        let before = revoked_tokens.len();
+        revoked_tokens.retain(|_, expires_at| *expires_at > at);
+
+        Ok((before - revoked_tokens.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[tokio::test]
+    async fn test_in_memory_revoke_token() {
+        let repository = InMemoryTokenRevocationRepository::new();
+        let jti = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        assert!(!repository.is_revoked(jti, user_id, now).await.unwrap());
+
+        repository
+            .revoke_token(jti, user_id, now + Duration::hours(1))
+            .await
+            .unwrap();
+
+        assert!(repository.is_revoked(jti, user_id, now).await.unwrap());
+        assert!(!repository
+            .is_revoked(Uuid::new_v4(), user_id, now)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_revoke_all_for_user() {
+        let repository = InMemoryTokenRevocationRepository::new();
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        repository.revoke_all_for_user(user_id, now).await.unwrap();
+
+        let issued_before = now - Duration::minutes(5);
+        let issued_after = now + Duration::seconds(5);
+
+        assert!(repository
+            .is_revoked(Uuid::new_v4(), user_id, issued_before)
+            .await
+            .unwrap());
+        assert!(!repository
+            .is_revoked(Uuid::new_v4(), user_id, issued_after)
+            .await
+            .unwrap());
+        assert!(!repository
+            .is_revoked(Uuid::new_v4(), Uuid::new_v4(), issued_before)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_delete_expired() {
+        let repository = InMemoryTokenRevocationRepository::new();
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+        let expiring = Uuid::new_v4();
+        let lasting = Uuid::new_v4();
+
+        repository
+            .revoke_token(expiring, user_id, now + Duration::minutes(5))
+            .await
+            .unwrap();
+        repository
+            .revoke_token(lasting, user_id, now + Duration::hours(1))
+            .await
+            .unwrap();
+
+        let removed = repository
+            .delete_expired(now + Duration::minutes(10))
+            .await
+            .unwrap();
+
+        assert_eq!(removed, 1);
+        assert!(!repository.is_revoked(expiring, user_id, now).await.unwrap());
+        assert!(repository.is_revoked(lasting, user_id, now).await.unwrap());
+    }
+}
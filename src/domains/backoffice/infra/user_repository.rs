@@ -1,13 +1,18 @@
 use super::super::role::entity::{self as role_entity, Entity as RoleEntity};
+use super::password_history_entity::{self, Entity as PasswordHistoryEntity};
 use super::user_entity::{self, Entity as UserEntity};
 use crate::common::error::AppError;
-use crate::domains::backoffice::domain::{user::User, repository::UserRepository};
+use crate::domains::audit::{
+    domain::audit_entry::AuditEntry, infra::audit_log_repository::PostgresAuditLogRepository,
+};
+use crate::domains::backoffice::domain::{repository::UserRepository, user::User};
 use crate::domains::backoffice::role::model::Role;
 use async_trait::async_trait;
 use chrono::Utc;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
-    QueryOrder, QuerySelect, Set,
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Select, Set,
+    TransactionTrait,
 };
 use uuid::Uuid;
 
@@ -38,6 +43,7 @@ impl PostgresUserRepository {
             role,
             created_at: user_entity.created_at.with_timezone(&Utc),
             updated_at: user_entity.updated_at.with_timezone(&Utc),
+            deleted_at: user_entity.deleted_at.map(|at| at.with_timezone(&Utc)),
         }
     }
 
@@ -51,14 +57,38 @@ impl PostgresUserRepository {
             role_id: Set(user.role.role_id),
             created_at: Set(user.created_at.into()),
             updated_at: Set(user.updated_at.into()),
+            deleted_at: Set(user.deleted_at.map(Into::into)),
+        }
+    }
+
+    /// Users that are not soft-deleted; every lookup starts here
+    fn find_existing() -> Select<UserEntity> {
+        UserEntity::find().filter(user_entity::Column::DeletedAt.is_null())
+    }
+
+    async fn record_password<C: ConnectionTrait>(
+        conn: &C,
+        user_id: Uuid,
+        password_hash: String,
+    ) -> Result<(), AppError> {
+        password_history_entity::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(user_id),
+            password_hash: Set(password_hash),
+            created_at: Set(Utc::now().into()),
         }
+        .insert(conn)
+        .await?;
+
+        Ok(())
     }
 }
 
 #[async_trait]
 impl UserRepository for PostgresUserRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, AppError> {
-        let result = UserEntity::find_by_id(id)
+        let result = Self::find_existing()
+            .filter(user_entity::Column::Id.eq(id))
             .find_also_related(RoleEntity)
             .one(&self.db)
             .await?;
@@ -70,7 +100,7 @@ impl UserRepository for PostgresUserRepository {
     }
 
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError> {
-        let result = UserEntity::find()
+        let result = Self::find_existing()
             .filter(user_entity::Column::Username.eq(username))
             .find_also_related(RoleEntity)
             .one(&self.db)
@@ -83,7 +113,7 @@ impl UserRepository for PostgresUserRepository {
     }
 
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
-        let result = UserEntity::find()
+        let result = Self::find_existing()
             .filter(user_entity::Column::Email.eq(email))
             .find_also_related(RoleEntity)
             .one(&self.db)
@@ -96,6 +126,7 @@ impl UserRepository for PostgresUserRepository {
     }
 
     async fn exists_by_username(&self, username: &str) -> Result<bool, AppError> {
+        // No deleted_at filter: a soft-deleted user's username stays reserved
         let count = UserEntity::find()
             .filter(user_entity::Column::Username.eq(username))
             .count(&self.db)
@@ -105,6 +136,7 @@ impl UserRepository for PostgresUserRepository {
     }
 
     async fn exists_by_email(&self, email: &str) -> Result<bool, AppError> {
+        // No deleted_at filter: a soft-deleted user's email stays reserved
         let count = UserEntity::find()
             .filter(user_entity::Column::Email.eq(email))
             .count(&self.db)
@@ -113,10 +145,16 @@ impl UserRepository for PostgresUserRepository {
         Ok(count > 0)
     }
 
-    async fn create(&self, user: User) -> Result<User, AppError> {
+    async fn create(&self, user: User, audit: AuditEntry) -> Result<User, AppError> {
         let user_id = user.id;
+        let password_hash = user.password_hash.clone();
         let active_model = Self::domain_to_active_model(user);
-        active_model.insert(&self.db).await?;
+
+        let txn = self.db.begin().await?;
+        active_model.insert(&txn).await?;
+        Self::record_password(&txn, user_id, password_hash).await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
 
         // Re-fetch with role
         self.find_by_id(user_id)
@@ -124,10 +162,14 @@ impl UserRepository for PostgresUserRepository {
             .ok_or_else(|| AppError::NotFound(format!("User {} not found after creation", user_id)))
     }
 
-    async fn update(&self, user: User) -> Result<User, AppError> {
+    async fn update(&self, user: User, audit: AuditEntry) -> Result<User, AppError> {
         let user_id = user.id;
         let active_model = Self::domain_to_active_model(user);
-        active_model.update(&self.db).await?;
+
+        let txn = self.db.begin().await?;
+        active_model.update(&txn).await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
 
         // Re-fetch with role
         self.find_by_id(user_id)
@@ -135,18 +177,47 @@ impl UserRepository for PostgresUserRepository {
             .ok_or_else(|| AppError::NotFound(format!("User {} not found after update", user_id)))
     }
 
-    async fn delete(&self, id: Uuid) -> Result<(), AppError> {
-        let result = UserEntity::delete_by_id(id).exec(&self.db).await?;
+    async fn change_password(&self, user: User, audit: AuditEntry) -> Result<User, AppError> {
+        let user_id = user.id;
+        let password_hash = user.password_hash.clone();
+        let active_model = Self::domain_to_active_model(user);
+
+        let txn = self.db.begin().await?;
+        active_model.update(&txn).await?;
+        Self::record_password(&txn, user_id, password_hash).await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        self.find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("User {} not found after update", user_id)))
+    }
+
+    async fn delete(&self, id: Uuid, audit: AuditEntry) -> Result<(), AppError> {
+        let now = Utc::now();
+
+        let txn = self.db.begin().await?;
+        let result = UserEntity::update_many()
+            .col_expr(user_entity::Column::IsActive, Expr::value(false))
+            .col_expr(user_entity::Column::DeletedAt, Expr::value(now))
+            .col_expr(user_entity::Column::UpdatedAt, Expr::value(now))
+            .filter(user_entity::Column::Id.eq(id))
+            .filter(user_entity::Column::DeletedAt.is_null())
+            .exec(&txn)
+            .await?;
 
         if result.rows_affected == 0 {
             return Err(AppError::NotFound(format!("User {} not found", id)));
         }
 
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
         Ok(())
     }
 
     async fn list(&self, limit: i64, offset: i64) -> Result<Vec<User>, AppError> {
-        let results = UserEntity::find()
+        let results = Self::find_existing()
             .find_also_related(RoleEntity)
             .order_by_desc(user_entity::Column::Id)
             .limit(limit as u64)
@@ -163,7 +234,7 @@ impl UserRepository for PostgresUserRepository {
     async fn search(&self, query: &str, limit: i64) -> Result<Vec<User>, AppError> {
         let search_pattern = format!("%{}%", query);
 
-        let results = UserEntity::find()
+        let results = Self::find_existing()
             .find_also_related(RoleEntity)
             .filter(
                 user_entity::Column::Username
@@ -187,9 +258,23 @@ impl UserRepository for PostgresUserRepository {
     }
 
     async fn count(&self) -> Result<i64, AppError> {
-        let count = UserEntity::find().count(&self.db).await?;
+        let count = Self::find_existing().count(&self.db).await?;
         Ok(count as i64)
     }
+
+    async fn password_history(&self, user_id: Uuid, limit: usize) -> Result<Vec<String>, AppError> {
+        let entries = PasswordHistoryEntity::find()
+            .filter(password_history_entity::Column::UserId.eq(user_id))
+            .order_by_desc(password_history_entity::Column::CreatedAt)
+            .limit(limit as u64)
+            .all(&self.db)
+            .await?;
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| entry.password_hash)
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -20,6 +20,9 @@ pub struct Model {
 pub enum Relation {
     #[sea_orm(has_many = "super::super::infra::user_entity::Entity")]
     Users,
+
+    #[sea_orm(has_many = "super::role_permission_entity::Entity")]
+    RolePermissions,
 }
 
 impl Related<super::super::infra::user_entity::Entity> for Entity {
@@ -28,4 +31,10 @@ impl Related<super::super::infra::user_entity::Entity> for Entity {
     }
 }
 
+impl Related<super::role_permission_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RolePermissions.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
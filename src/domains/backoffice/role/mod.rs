@@ -1,12 +1,18 @@
 pub mod entity;
 pub mod model;
+pub mod permission;
+pub mod permission_entity;
 pub mod repository;
 pub mod repository_impl;
+pub mod role_permission_entity;
 
 // Re-export commonly used items
-pub use model::{admin_role_id, finance_role_id, risk_role_id, support_role_id, user_role_id};
 pub use model::{
-    Role, ADMIN_ROLE_ID, FINANCE_ROLE_ID, RISK_ROLE_ID, SUPPORT_ROLE_ID, USER_ROLE_ID,
+    admin_role_id, finance_role_id, risk_role_id, support_role_id, system_role_ids, user_role_id,
 };
+pub use model::{
+    Role, RoleDetails, ADMIN_ROLE_ID, FINANCE_ROLE_ID, RISK_ROLE_ID, SUPPORT_ROLE_ID, USER_ROLE_ID,
+};
+pub use permission::Permission;
 pub use repository::RoleRepository;
 pub use repository_impl::PostgresRoleRepository;
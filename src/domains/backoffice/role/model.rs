@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use uuid::Uuid;
 
 pub const ADMIN_ROLE_ID: &str = "878c19c6-643b-4a57-98f1-a60786a38a92";
@@ -34,6 +35,37 @@ impl Role {
         }
     }
 
+    /// New custom role created through the role API
+    pub fn create(role_name: String, role_description: Option<String>) -> Self {
+        let now = Utc::now();
+        Self::new(Uuid::new_v4(), role_name, role_description, now, now)
+    }
+
+    /// One of the seeded roles the application relies on; these cannot be
+    /// renamed or deleted
+    pub fn is_system(&self) -> bool {
+        system_role_ids().contains(&self.role_id)
+    }
+
+    pub fn update_name(&mut self, role_name: String) {
+        self.role_name = role_name;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn update_description(&mut self, role_description: Option<String>) {
+        self.role_description = role_description;
+        self.updated_at = Utc::now();
+    }
+
+    /// Fields recorded in the audit log, together with the granted permissions
+    pub fn audit_snapshot(&self, permissions: &[String]) -> Value {
+        json!({
+            "role_name": self.role_name,
+            "role_description": self.role_description,
+            "permissions": permissions,
+        })
+    }
+
     // Helper methods to check role type
     pub fn is_admin(&self) -> bool {
         self.role_id == Uuid::parse_str(ADMIN_ROLE_ID).unwrap()
@@ -77,6 +109,23 @@ pub fn user_role_id() -> Uuid {
     Uuid::parse_str(USER_ROLE_ID).unwrap()
 }
 
+pub fn system_role_ids() -> [Uuid; 5] {
+    [
+        admin_role_id(),
+        support_role_id(),
+        risk_role_id(),
+        finance_role_id(),
+        user_role_id(),
+    ]
+}
+
+/// Role together with the names of the permissions granted to it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleDetails {
+    pub role: Role,
+    pub permissions: Vec<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,6 +156,26 @@ mod tests {
         assert!(!user_role.is_admin());
     }
 
+    #[test]
+    fn test_system_roles() {
+        let finance_role = Role::new(
+            finance_role_id(),
+            "Finance".to_string(),
+            None,
+            Utc::now(),
+            Utc::now(),
+        );
+        assert!(finance_role.is_system());
+
+        let mut custom_role = Role::create("Auditor".to_string(), None);
+        assert!(!custom_role.is_system());
+
+        custom_role.update_name("Compliance".to_string());
+        custom_role.update_description(Some("Read-only compliance access".to_string()));
+        assert_eq!(custom_role.role_name, "Compliance");
+        assert!(custom_role.updated_at >= custom_role.created_at);
+    }
+
     #[test]
     fn test_role_constants() {
         assert_eq!(
@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// Permission names as stored in the `permissions` table
+pub const USERS_READ: &str = "users:read";
+pub const USERS_WRITE: &str = "users:write";
+pub const ROLES_READ: &str = "roles:read";
+pub const ROLES_WRITE: &str = "roles:write";
+pub const MERCHANTS_READ: &str = "merchants:read";
+pub const MERCHANTS_WRITE: &str = "merchants:write";
+pub const PAYMENTS_READ: &str = "payments:read";
+pub const PAYMENTS_CONFIRM: &str = "payments:confirm";
+pub const PAYOUTS_READ: &str = "payouts:read";
+pub const PAYOUTS_APPROVE: &str = "payouts:approve";
+pub const LEDGER_READ: &str = "ledger:read";
+pub const TRADERS_READ: &str = "traders:read";
+pub const TRADERS_WRITE: &str = "traders:write";
+pub const DISPUTES_READ: &str = "disputes:read";
+pub const DISPUTES_WRITE: &str = "disputes:write";
+pub const DISPUTES_RESOLVE: &str = "disputes:resolve";
+pub const WEBHOOKS_READ: &str = "webhooks:read";
+pub const WEBHOOKS_REDELIVER: &str = "webhooks:redeliver";
+pub const AUDIT_READ: &str = "audit:read";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Permission {
+    pub permission_id: Uuid,
+    pub permission_name: String,
+    pub permission_description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
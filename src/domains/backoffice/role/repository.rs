@@ -1,5 +1,7 @@
 use super::model::Role;
+use super::permission::Permission;
 use crate::common::error::AppError;
+use crate::domains::audit::domain::audit_entry::AuditEntry;
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -13,4 +15,65 @@ pub trait RoleRepository: Send + Sync {
 
     /// List all roles
     async fn list_all(&self) -> Result<Vec<Role>, AppError>;
+
+    /// Create a new role with the given permissions and its `audit` entry, in one transaction
+    async fn create(
+        &self,
+        role: Role,
+        permission_names: Vec<String>,
+        audit: AuditEntry,
+    ) -> Result<Role, AppError>;
+
+    /// Update name and description of a role and, if given, replace its permissions;
+    /// stored with the `audit` entry in one transaction
+    async fn update(
+        &self,
+        role: Role,
+        permission_names: Option<Vec<String>>,
+        audit: AuditEntry,
+    ) -> Result<Role, AppError>;
+
+    /// Delete a role together with its `audit` entry; fails while users still reference it.
+    /// Soft-deleted users holding the role are moved to the User role first.
+    async fn delete(&self, role_id: Uuid, audit: AuditEntry) -> Result<(), AppError>;
+
+    /// Number of users assigned to a role, not counting deleted ones
+    async fn count_users(&self, role_id: Uuid) -> Result<i64, AppError>;
+
+    /// Names of the permissions granted to a role
+    async fn find_permission_names(&self, role_id: Uuid) -> Result<Vec<String>, AppError>;
+
+    /// List all known permissions
+    async fn list_permissions(&self) -> Result<Vec<Permission>, AppError>;
+}
+
+#[cfg(test)]
+use mockall::mock;
+
+#[cfg(test)]
+mock! {
+    pub RoleRepository {}
+
+    #[async_trait]
+    impl RoleRepository for RoleRepository {
+        async fn find_by_id(&self, role_id: Uuid) -> Result<Option<Role>, AppError>;
+        async fn find_by_name(&self, role_name: &str) -> Result<Option<Role>, AppError>;
+        async fn list_all(&self) -> Result<Vec<Role>, AppError>;
+        async fn create(
+            &self,
+            role: Role,
+            permission_names: Vec<String>,
+            audit: AuditEntry,
+        ) -> Result<Role, AppError>;
+        async fn update(
+            &self,
+            role: Role,
+            permission_names: Option<Vec<String>>,
+            audit: AuditEntry,
+        ) -> Result<Role, AppError>;
+        async fn delete(&self, role_id: Uuid, audit: AuditEntry) -> Result<(), AppError>;
+        async fn count_users(&self, role_id: Uuid) -> Result<i64, AppError>;
+        async fn find_permission_names(&self, role_id: Uuid) -> Result<Vec<String>, AppError>;
+        async fn list_permissions(&self) -> Result<Vec<Permission>, AppError>;
+    }
 }
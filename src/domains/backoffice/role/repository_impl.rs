@@ -1,10 +1,23 @@
 use super::entity::{self, Entity as RoleEntity};
-use super::model::Role;
+use super::model::{user_role_id, Role};
+use super::permission::Permission;
+use super::permission_entity::{self, Entity as PermissionEntity};
 use super::repository::RoleRepository;
+use super::role_permission_entity::{self, Entity as RolePermissionEntity};
 use crate::common::error::AppError;
+use crate::domains::{
+    audit::{
+        domain::audit_entry::AuditEntry, infra::audit_log_repository::PostgresAuditLogRepository,
+    },
+    backoffice::infra::user_entity::{self, Entity as UserEntity},
+};
 use async_trait::async_trait;
 use chrono::Utc;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, JoinType, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+    Set, SqlErr, TransactionTrait,
+};
 use uuid::Uuid;
 
 pub struct PostgresRoleRepository {
@@ -25,6 +38,52 @@ impl PostgresRoleRepository {
             updated_at: entity.updated_at.with_timezone(&Utc),
         }
     }
+
+    fn domain_to_active_model(role: Role) -> entity::ActiveModel {
+        entity::ActiveModel {
+            role_id: Set(role.role_id),
+            role_name: Set(role.role_name),
+            role_description: Set(role.role_description),
+            created_at: Set(role.created_at.into()),
+            updated_at: Set(role.updated_at.into()),
+        }
+    }
+
+    /// Replaces the permissions granted to a role
+    async fn replace_permissions(
+        conn: &impl ConnectionTrait,
+        role_id: Uuid,
+        permission_names: Vec<String>,
+    ) -> Result<(), AppError> {
+        let permissions = PermissionEntity::find()
+            .filter(permission_entity::Column::PermissionName.is_in(permission_names.clone()))
+            .all(conn)
+            .await?;
+
+        if permissions.len() != permission_names.len() {
+            return Err(AppError::ValidationError(
+                "Unknown or duplicate permission".to_string(),
+            ));
+        }
+
+        RolePermissionEntity::delete_many()
+            .filter(role_permission_entity::Column::RoleId.eq(role_id))
+            .exec(conn)
+            .await?;
+
+        if !permissions.is_empty() {
+            RolePermissionEntity::insert_many(permissions.into_iter().map(|permission| {
+                role_permission_entity::ActiveModel {
+                    role_id: Set(role_id),
+                    permission_id: Set(permission.permission_id),
+                }
+            }))
+            .exec(conn)
+            .await?;
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -52,6 +111,118 @@ impl RoleRepository for PostgresRoleRepository {
 
         Ok(roles.into_iter().map(Self::entity_to_domain).collect())
     }
+
+    async fn create(
+        &self,
+        role: Role,
+        permission_names: Vec<String>,
+        audit: AuditEntry,
+    ) -> Result<Role, AppError> {
+        let txn = self.db.begin().await?;
+
+        let model = Self::domain_to_active_model(role).insert(&txn).await?;
+        Self::replace_permissions(&txn, model.role_id, permission_names).await?;
+
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        Ok(Self::entity_to_domain(model))
+    }
+
+    async fn update(
+        &self,
+        role: Role,
+        permission_names: Option<Vec<String>>,
+        audit: AuditEntry,
+    ) -> Result<Role, AppError> {
+        let txn = self.db.begin().await?;
+
+        let model = Self::domain_to_active_model(role).update(&txn).await?;
+        if let Some(permission_names) = permission_names {
+            Self::replace_permissions(&txn, model.role_id, permission_names).await?;
+        }
+
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        Ok(Self::entity_to_domain(model))
+    }
+
+    async fn delete(&self, role_id: Uuid, audit: AuditEntry) -> Result<(), AppError> {
+        let txn = self.db.begin().await?;
+
+        // Deleted users cannot be edited anymore, so nobody else could release the role
+        UserEntity::update_many()
+            .col_expr(user_entity::Column::RoleId, Expr::value(user_role_id()))
+            .filter(user_entity::Column::RoleId.eq(role_id))
+            .filter(user_entity::Column::DeletedAt.is_not_null())
+            .exec(&txn)
+            .await?;
+
+        let result = RoleEntity::delete_by_id(role_id)
+            .exec(&txn)
+            .await
+            .map_err(|err| match err.sql_err() {
+                // users.role_id is ON DELETE RESTRICT
+                Some(SqlErr::ForeignKeyConstraintViolation(_)) => AppError::ValidationError(
+                    format!("Role {} is still assigned to users", role_id),
+                ),
+                _ => AppError::from(err),
+            })?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound(format!("Role {} not found", role_id)));
+        }
+
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    async fn count_users(&self, role_id: Uuid) -> Result<i64, AppError> {
+        let count = UserEntity::find()
+            .filter(user_entity::Column::RoleId.eq(role_id))
+            .filter(user_entity::Column::DeletedAt.is_null())
+            .count(&self.db)
+            .await?;
+
+        Ok(count as i64)
+    }
+
+    async fn find_permission_names(&self, role_id: Uuid) -> Result<Vec<String>, AppError> {
+        let permissions = PermissionEntity::find()
+            .join(
+                JoinType::InnerJoin,
+                permission_entity::Relation::RolePermissions.def(),
+            )
+            .filter(role_permission_entity::Column::RoleId.eq(role_id))
+            .order_by_asc(permission_entity::Column::PermissionName)
+            .all(&self.db)
+            .await?;
+
+        Ok(permissions
+            .into_iter()
+            .map(|permission| permission.permission_name)
+            .collect())
+    }
+
+    async fn list_permissions(&self) -> Result<Vec<Permission>, AppError> {
+        let permissions = PermissionEntity::find()
+            .order_by_asc(permission_entity::Column::PermissionName)
+            .all(&self.db)
+            .await?;
+
+        Ok(permissions
+            .into_iter()
+            .map(|permission| Permission {
+                permission_id: permission.permission_id,
+                permission_name: permission.permission_name,
+                permission_description: permission.permission_description,
+                created_at: permission.created_at.with_timezone(&Utc),
+            })
+            .collect())
+    }
 }
 
 #[cfg(test)]
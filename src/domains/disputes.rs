@@ -0,0 +1,33 @@
+mod api {
+    pub mod dispute_handler;
+    pub mod router;
+}
+
+pub mod app {
+    pub mod dispute_attachment_use_case;
+    pub mod dispute_comment_use_case;
+    pub mod get_dispute_use_case;
+    pub mod open_dispute_use_case;
+    pub mod resolve_dispute_use_case;
+    pub mod update_dispute_use_case;
+}
+
+pub mod domain {
+    pub mod dispute;
+    pub mod repository;
+}
+
+pub mod dto {
+    pub mod dispute_dto;
+}
+
+pub mod infra {
+    pub mod dispute_attachment_entity;
+    pub mod dispute_comment_entity;
+    pub mod dispute_entity;
+    pub mod dispute_repository;
+}
+
+pub use api::router::{protected_dispute_routes, DisputeApiDoc};
+pub use domain::repository::DisputeRepository;
+pub use infra::dispute_repository::PostgresDisputeRepository;
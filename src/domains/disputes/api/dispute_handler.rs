@@ -0,0 +1,442 @@
+use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError, jwt::Claims};
+use crate::domains::audit::domain::audit_entry::AuditContext;
+use crate::domains::disputes::dto::dispute_dto::{
+    AssignDisputeRequest, CreateDisputeCommentRequest, DisputeAttachmentResponse,
+    DisputeCommentResponse, DisputeResponse, ListDisputesQuery, OpenDisputeRequest,
+    ResolveDisputeRequest,
+};
+use axum::{
+    extract::{multipart::MultipartError, Extension, Multipart, Path, Query},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/dispute",
+    params(ListDisputesQuery),
+    responses(
+        (status = 200, description = "Page of disputes, oldest first", body = inline(ApiResponse<Vec<DisputeResponse>>)),
+        (status = 400, description = "Limit exceeds 100 or a filter is malformed"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Disputes",
+    summary = "List disputes",
+    description = "Lists disputes filtered by status, assignee, payment, merchant and trader. `status=open` is the queue of cases nobody has picked up. Requires `disputes:read`."
+)]
+pub async fn list_disputes(
+    Extension(state): Extension<Arc<AppState>>,
+    Query(params): Query<ListDisputesQuery>,
+) -> Result<Json<ApiResponse<Vec<DisputeResponse>>>, AppError> {
+    let (limit, offset) = (params.limit, params.offset);
+    let disputes = state
+        .dispute_get_use_case
+        .list(params.into(), limit, offset)
+        .await?;
+
+    let response: Vec<DisputeResponse> = disputes.into_iter().map(DisputeResponse::from).collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/dispute/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Dispute ID")
+    ),
+    responses(
+        (status = 200, description = "Dispute found", body = inline(ApiResponse<DisputeResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Dispute not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Disputes",
+    summary = "Get dispute",
+    description = "Fetches a dispute by ID. Requires `disputes:read`."
+)]
+pub async fn get_dispute(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(dispute_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<DisputeResponse>>, AppError> {
+    let dispute = state.dispute_get_use_case.execute(dispute_id).await?;
+
+    Ok(Json(ApiResponse::success(DisputeResponse::from(dispute))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/dispute",
+    request_body = OpenDisputeRequest,
+    responses(
+        (status = 200, description = "Dispute opened", body = inline(ApiResponse<DisputeResponse>)),
+        (status = 400, description = "Reason is empty or too long, or the payment already has a dispute in progress"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Payment not found"),
+        (status = 409, description = "Payment is not paid or failed, or its dispute window has closed")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Disputes",
+    summary = "Open dispute",
+    description = "Opens a case on a paid or failed payment before its `dispute_until`. A payment has at most one dispute in progress. Requires `disputes:write`."
+)]
+pub async fn open_dispute(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+    Json(request): Json<OpenDisputeRequest>,
+) -> Result<Json<ApiResponse<DisputeResponse>>, AppError> {
+    let dispute = state
+        .dispute_open_use_case
+        .execute(request, claims.user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(DisputeResponse::from(dispute))))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/dispute/{id}/assignee",
+    params(
+        ("id" = Uuid, Path, description = "Dispute ID")
+    ),
+    request_body = AssignDisputeRequest,
+    responses(
+        (status = 200, description = "Dispute assigned", body = inline(ApiResponse<DisputeResponse>)),
+        (status = 400, description = "Assignee is not an active user with `disputes:write`"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Dispute not found"),
+        (status = 409, description = "Dispute is resolved")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Disputes",
+    summary = "Assign dispute",
+    description = "Hands the case to a backoffice user who can work disputes, or back to the queue with `null`. Requires `disputes:write`."
+)]
+pub async fn assign_dispute(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+    Path(dispute_id): Path<Uuid>,
+    Json(request): Json<AssignDisputeRequest>,
+) -> Result<Json<ApiResponse<DisputeResponse>>, AppError> {
+    let dispute = state
+        .dispute_update_use_case
+        .assign(dispute_id, request.assignee_id, claims.user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(DisputeResponse::from(dispute))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/dispute/{id}/investigate",
+    params(
+        ("id" = Uuid, Path, description = "Dispute ID")
+    ),
+    responses(
+        (status = 200, description = "Dispute under investigation", body = inline(ApiResponse<DisputeResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Dispute not found"),
+        (status = 409, description = "Dispute is not open")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Disputes",
+    summary = "Start investigation",
+    description = "Moves an open dispute to `investigating`. An unassigned case is assigned to the caller. Requires `disputes:write`."
+)]
+pub async fn investigate_dispute(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+    Path(dispute_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<DisputeResponse>>, AppError> {
+    let dispute = state
+        .dispute_update_use_case
+        .start_investigation(dispute_id, claims.user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(DisputeResponse::from(dispute))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/dispute/{id}/resolve",
+    params(
+        ("id" = Uuid, Path, description = "Dispute ID")
+    ),
+    request_body = ResolveDisputeRequest,
+    responses(
+        (status = 200, description = "Dispute resolved and its adjustment posted", body = inline(ApiResponse<DisputeResponse>)),
+        (status = 400, description = "Note is empty or too long, or the payment has no trader to adjust"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Dispute not found"),
+        (status = 409, description = "Dispute is not under investigation")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Disputes",
+    summary = "Resolve dispute",
+    description = "Decides a dispute under investigation for the customer, merchant or trader and posts an `adjustment` ledger entry moving the payment amount. For a paid payment the merchant refunds the customer (treasury) or the trader; for a failed payment the trader's balance pays the customer or the merchant. Deciding for the party the payment outcome already favoured posts nothing. Requires `disputes:resolve`."
+)]
+pub async fn resolve_dispute(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+    Path(dispute_id): Path<Uuid>,
+    Json(request): Json<ResolveDisputeRequest>,
+) -> Result<Json<ApiResponse<DisputeResponse>>, AppError> {
+    let dispute = state
+        .dispute_resolve_use_case
+        .execute(dispute_id, request, claims.user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(DisputeResponse::from(dispute))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/dispute/{id}/comment",
+    params(
+        ("id" = Uuid, Path, description = "Dispute ID")
+    ),
+    responses(
+        (status = 200, description = "Comments on the dispute, oldest first", body = inline(ApiResponse<Vec<DisputeCommentResponse>>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Dispute not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Disputes",
+    summary = "List dispute comments",
+    description = "Lists the internal notes on a dispute. Requires `disputes:read`."
+)]
+pub async fn list_dispute_comments(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(dispute_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<DisputeCommentResponse>>>, AppError> {
+    let comments = state.dispute_comment_use_case.list(dispute_id).await?;
+
+    let response: Vec<DisputeCommentResponse> = comments
+        .into_iter()
+        .map(DisputeCommentResponse::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/dispute/{id}/comment",
+    params(
+        ("id" = Uuid, Path, description = "Dispute ID")
+    ),
+    request_body = CreateDisputeCommentRequest,
+    responses(
+        (status = 200, description = "Comment added", body = inline(ApiResponse<DisputeCommentResponse>)),
+        (status = 400, description = "Comment is empty or too long"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Dispute not found"),
+        (status = 409, description = "Dispute is resolved")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Disputes",
+    summary = "Comment on dispute",
+    description = "Adds an internal note to a dispute that is not resolved. Requires `disputes:write`."
+)]
+pub async fn create_dispute_comment(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+    Path(dispute_id): Path<Uuid>,
+    Json(request): Json<CreateDisputeCommentRequest>,
+) -> Result<Json<ApiResponse<DisputeCommentResponse>>, AppError> {
+    let comment = state
+        .dispute_comment_use_case
+        .add(dispute_id, request.body, claims.user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(DisputeCommentResponse::from(
+        comment,
+    ))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/dispute/{id}/attachment",
+    params(
+        ("id" = Uuid, Path, description = "Dispute ID")
+    ),
+    responses(
+        (status = 200, description = "Attachments of the dispute, oldest first", body = inline(ApiResponse<Vec<DisputeAttachmentResponse>>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Dispute not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Disputes",
+    summary = "List dispute attachments",
+    description = "Lists the files added to a dispute. Requires `disputes:read`."
+)]
+pub async fn list_dispute_attachments(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(dispute_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<DisputeAttachmentResponse>>>, AppError> {
+    let attachments = state.dispute_attachment_use_case.list(dispute_id).await?;
+
+    let response: Vec<DisputeAttachmentResponse> = attachments
+        .into_iter()
+        .map(DisputeAttachmentResponse::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/dispute/{id}/attachment",
+    params(
+        ("id" = Uuid, Path, description = "Dispute ID")
+    ),
+    request_body(
+        content_type = "multipart/form-data",
+        description = "A `file` part with a PNG, JPEG or PDF document"
+    ),
+    responses(
+        (status = 200, description = "Attachment stored", body = inline(ApiResponse<DisputeAttachmentResponse>)),
+        (status = 400, description = "Missing `file` part, unsupported type, empty or too large"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Dispute not found"),
+        (status = 409, description = "Dispute is resolved")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Disputes",
+    summary = "Upload dispute attachment",
+    description = "Adds a file, e.g. a bank statement, to a dispute that is not resolved. Files are limited to the evidence size limit. Requires `disputes:write`."
+)]
+pub async fn upload_dispute_attachment(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+    Path(dispute_id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<Json<ApiResponse<DisputeAttachmentResponse>>, AppError> {
+    let invalid = |err: MultipartError| AppError::BadRequest(err.body_text());
+    let max_bytes = state.config.payments.evidence_max_bytes;
+
+    while let Some(mut field) = multipart.next_field().await.map_err(invalid)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let file_name = field.file_name().unwrap_or_default().to_string();
+        let content_type = field.content_type().unwrap_or_default().to_string();
+
+        // The route has no body limit; stop reading once the file is too large
+        let mut content = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(invalid)? {
+            if content.len() + chunk.len() > max_bytes {
+                return Err(AppError::ValidationError(format!(
+                    "Attachment must be 1 to {} bytes",
+                    max_bytes
+                )));
+            }
+            content.extend_from_slice(&chunk);
+        }
+
+        let attachment = state
+            .dispute_attachment_use_case
+            .upload(
+                dispute_id,
+                &file_name,
+                &content_type,
+                &content,
+                claims.user_id,
+                &audit,
+            )
+            .await?;
+
+        return Ok(Json(ApiResponse::success(DisputeAttachmentResponse::from(
+            attachment,
+        ))));
+    }
+
+    Err(AppError::BadRequest(
+        "Multipart body has no 'file' part".to_string(),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/dispute/{id}/attachment/{attachment_id}",
+    params(
+        ("id" = Uuid, Path, description = "Dispute ID"),
+        ("attachment_id" = Uuid, Path, description = "Attachment ID")
+    ),
+    responses(
+        (status = 200, description = "File content with its original type"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Attachment not found for this dispute")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Disputes",
+    summary = "Download dispute attachment",
+    description = "Downloads a dispute file as an attachment. Requires `disputes:read`."
+)]
+pub async fn download_dispute_attachment(
+    Extension(state): Extension<Arc<AppState>>,
+    Path((dispute_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, AppError> {
+    let (attachment, content) = state
+        .dispute_attachment_use_case
+        .download(dispute_id, attachment_id)
+        .await?;
+
+    // Never rendered inline by the browser
+    Ok((
+        [
+            (header::CONTENT_TYPE, attachment.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", attachment.file_name),
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        content,
+    )
+        .into_response())
+}
@@ -0,0 +1,111 @@
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, patch, post},
+    Router,
+};
+
+use crate::{
+    common::{jwt::SecurityAddon, middleware::require_permission},
+    domains::{
+        backoffice::role::permission::{DISPUTES_READ, DISPUTES_RESOLVE, DISPUTES_WRITE},
+        disputes::{
+            domain::dispute::{DisputeResolution, DisputeStatus},
+            dto::dispute_dto::{
+                AssignDisputeRequest, CreateDisputeCommentRequest, DisputeAttachmentResponse,
+                DisputeCommentResponse, DisputeResponse, OpenDisputeRequest, ResolveDisputeRequest,
+            },
+        },
+        payments::domain::payment_intent::PaymentStatus,
+    },
+};
+
+use utoipa::OpenApi;
+
+use super::dispute_handler;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::dispute_handler::list_disputes,
+        super::dispute_handler::get_dispute,
+        super::dispute_handler::open_dispute,
+        super::dispute_handler::assign_dispute,
+        super::dispute_handler::investigate_dispute,
+        super::dispute_handler::resolve_dispute,
+        super::dispute_handler::list_dispute_comments,
+        super::dispute_handler::create_dispute_comment,
+        super::dispute_handler::list_dispute_attachments,
+        super::dispute_handler::upload_dispute_attachment,
+        super::dispute_handler::download_dispute_attachment,
+    ),
+    components(schemas(
+        DisputeResponse,
+        DisputeCommentResponse,
+        DisputeAttachmentResponse,
+        OpenDisputeRequest,
+        AssignDisputeRequest,
+        ResolveDisputeRequest,
+        CreateDisputeCommentRequest,
+        DisputeStatus,
+        DisputeResolution,
+        PaymentStatus
+    )),
+    tags(
+        (name = "Disputes", description = "Chargeback and complaint cases worked by Support and Risk")
+    ),
+    modifiers(&SecurityAddon)
+)]
+pub struct DisputeApiDoc;
+
+pub fn protected_dispute_routes() -> Router {
+    let resolve_routes = Router::new()
+        .route(
+            "/dispute/{id}/resolve",
+            post(dispute_handler::resolve_dispute),
+        )
+        .route_layer(middleware::from_fn(require_permission(DISPUTES_RESOLVE)));
+
+    let write_routes = Router::new()
+        .route("/dispute", post(dispute_handler::open_dispute))
+        .route(
+            "/dispute/{id}/assignee",
+            patch(dispute_handler::assign_dispute),
+        )
+        .route(
+            "/dispute/{id}/investigate",
+            post(dispute_handler::investigate_dispute),
+        )
+        .route(
+            "/dispute/{id}/comment",
+            post(dispute_handler::create_dispute_comment),
+        )
+        // The handler stops reading once the file exceeds the evidence size limit
+        .route(
+            "/dispute/{id}/attachment",
+            post(dispute_handler::upload_dispute_attachment).layer(DefaultBodyLimit::disable()),
+        )
+        .route_layer(middleware::from_fn(require_permission(DISPUTES_WRITE)));
+
+    let read_routes = Router::new()
+        .route("/dispute", get(dispute_handler::list_disputes))
+        .route("/dispute/{id}", get(dispute_handler::get_dispute))
+        .route(
+            "/dispute/{id}/comment",
+            get(dispute_handler::list_dispute_comments),
+        )
+        .route(
+            "/dispute/{id}/attachment",
+            get(dispute_handler::list_dispute_attachments),
+        )
+        .route(
+            "/dispute/{id}/attachment/{attachment_id}",
+            get(dispute_handler::download_dispute_attachment),
+        )
+        .route_layer(middleware::from_fn(require_permission(DISPUTES_READ)));
+
+    Router::new()
+        .merge(resolve_routes)
+        .merge(write_routes)
+        .merge(read_routes)
+}
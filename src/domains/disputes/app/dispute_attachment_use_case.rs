@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    common::{error::AppError, storage::FileStorage},
+    domains::{
+        audit::domain::audit_entry::{
+            AuditAction, AuditContext, AuditEntry, DISPUTE_ATTACHMENT_ENTITY,
+        },
+        disputes::{
+            app::get_dispute_use_case::find_dispute,
+            domain::{dispute::DisputeAttachment, repository::DisputeRepository},
+        },
+    },
+};
+
+/// Files a case can collect
+const MAX_ATTACHMENTS_PER_DISPUTE: usize = 20;
+
+pub struct DisputeAttachmentUseCase {
+    dispute_repository: Arc<dyn DisputeRepository>,
+    storage: Arc<dyn FileStorage>,
+    max_bytes: usize,
+}
+
+impl DisputeAttachmentUseCase {
+    pub fn new(
+        dispute_repository: Arc<dyn DisputeRepository>,
+        storage: Arc<dyn FileStorage>,
+        max_bytes: usize,
+    ) -> Self {
+        Self {
+            dispute_repository,
+            storage,
+            max_bytes,
+        }
+    }
+
+    /// Adds a file to a case that is not resolved yet
+    pub async fn upload(
+        &self,
+        dispute_id: Uuid,
+        file_name: &str,
+        content_type: &str,
+        content: &[u8],
+        uploaded_by: Uuid,
+        context: &AuditContext,
+    ) -> Result<DisputeAttachment, AppError> {
+        tracing::debug!(
+            "Uploading attachment '{}' ({} bytes) for dispute {}",
+            file_name,
+            content.len(),
+            dispute_id
+        );
+
+        if content.is_empty() || content.len() > self.max_bytes {
+            return Err(AppError::ValidationError(format!(
+                "Attachment must be 1 to {} bytes",
+                self.max_bytes
+            )));
+        }
+
+        let dispute = find_dispute(self.dispute_repository.as_ref(), dispute_id).await?;
+        dispute.ensure_unresolved()?;
+        if self
+            .dispute_repository
+            .list_attachments(dispute_id)
+            .await?
+            .len()
+            >= MAX_ATTACHMENTS_PER_DISPUTE
+        {
+            return Err(AppError::ValidationError(format!(
+                "Dispute {} already has {} attachments",
+                dispute_id, MAX_ATTACHMENTS_PER_DISPUTE
+            )));
+        }
+
+        let attachment = DisputeAttachment::new(
+            dispute_id,
+            uploaded_by,
+            file_name,
+            content_type,
+            content.len() as i64,
+            hex::encode(Sha256::digest(content)),
+        )?;
+
+        // Written first: a stored file without a record is harmless, the reverse is not
+        self.storage.put(&attachment.storage_key, content).await?;
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Create,
+            DISPUTE_ATTACHMENT_ENTITY,
+            attachment.id,
+            None,
+            Some(&attachment.audit_snapshot()),
+        );
+        let attachment = self
+            .dispute_repository
+            .add_attachment(attachment, audit)
+            .await?;
+
+        tracing::info!(
+            "Attachment {} added to dispute {} by {}",
+            attachment.id,
+            dispute_id,
+            uploaded_by
+        );
+
+        Ok(attachment)
+    }
+
+    pub async fn list(&self, dispute_id: Uuid) -> Result<Vec<DisputeAttachment>, AppError> {
+        tracing::debug!("Listing attachments of dispute {}", dispute_id);
+
+        find_dispute(self.dispute_repository.as_ref(), dispute_id).await?;
+        self.dispute_repository.list_attachments(dispute_id).await
+    }
+
+    /// Attachment record with its content
+    pub async fn download(
+        &self,
+        dispute_id: Uuid,
+        attachment_id: Uuid,
+    ) -> Result<(DisputeAttachment, Vec<u8>), AppError> {
+        tracing::debug!(
+            "Downloading attachment {} of dispute {}",
+            attachment_id,
+            dispute_id
+        );
+
+        let attachment = self
+            .dispute_repository
+            .find_attachment(attachment_id)
+            .await?
+            .filter(|attachment| attachment.dispute_id == dispute_id)
+            .ok_or(AppError::NotFound(format!(
+                "Attachment {} not found",
+                attachment_id
+            )))?;
+        let content = self.storage.get(&attachment.storage_key).await?;
+
+        Ok((attachment, content))
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{
+            AuditAction, AuditContext, AuditEntry, DISPUTE_COMMENT_ENTITY,
+        },
+        disputes::{
+            app::get_dispute_use_case::find_dispute,
+            domain::{dispute::DisputeComment, repository::DisputeRepository},
+        },
+    },
+};
+
+const MAX_COMMENT_LEN: usize = 2000;
+
+pub struct DisputeCommentUseCase {
+    dispute_repository: Arc<dyn DisputeRepository>,
+}
+
+impl DisputeCommentUseCase {
+    pub fn new(dispute_repository: Arc<dyn DisputeRepository>) -> Self {
+        Self { dispute_repository }
+    }
+
+    /// Adds an internal note; resolved cases take no more comments
+    pub async fn add(
+        &self,
+        dispute_id: Uuid,
+        body: String,
+        author_id: Uuid,
+        context: &AuditContext,
+    ) -> Result<DisputeComment, AppError> {
+        tracing::debug!("Commenting on dispute {}", dispute_id);
+
+        let body = body.trim().to_string();
+        if body.is_empty() || body.chars().count() > MAX_COMMENT_LEN {
+            return Err(AppError::ValidationError(format!(
+                "Comment must be 1 to {} characters",
+                MAX_COMMENT_LEN
+            )));
+        }
+
+        let dispute = find_dispute(self.dispute_repository.as_ref(), dispute_id).await?;
+        dispute.ensure_unresolved()?;
+
+        let comment = DisputeComment::new(dispute.id, author_id, body);
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Create,
+            DISPUTE_COMMENT_ENTITY,
+            comment.id,
+            None,
+            Some(&comment.audit_snapshot()),
+        );
+        let comment = self.dispute_repository.add_comment(comment, audit).await?;
+
+        tracing::info!(
+            "Comment {} added to dispute {} by {}",
+            comment.id,
+            dispute.id,
+            author_id
+        );
+
+        Ok(comment)
+    }
+
+    pub async fn list(&self, dispute_id: Uuid) -> Result<Vec<DisputeComment>, AppError> {
+        tracing::debug!("Listing comments of dispute {}", dispute_id);
+
+        find_dispute(self.dispute_repository.as_ref(), dispute_id).await?;
+        self.dispute_repository.list_comments(dispute_id).await
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::disputes::domain::{
+        dispute::{Dispute, DisputeFilter},
+        repository::DisputeRepository,
+    },
+};
+
+pub struct GetDisputeUseCase {
+    dispute_repository: Arc<dyn DisputeRepository>,
+}
+
+impl GetDisputeUseCase {
+    pub fn new(dispute_repository: Arc<dyn DisputeRepository>) -> Self {
+        Self { dispute_repository }
+    }
+
+    pub async fn execute(&self, dispute_id: Uuid) -> Result<Dispute, AppError> {
+        tracing::debug!("Fetching dispute {}", dispute_id);
+
+        find_dispute(self.dispute_repository.as_ref(), dispute_id).await
+    }
+
+    pub async fn list(
+        &self,
+        filter: DisputeFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Dispute>, AppError> {
+        tracing::debug!("Listing disputes with {:?}", filter);
+
+        if limit > 100 {
+            return Err(AppError::ValidationError(
+                "Limit cannot exceed 100".to_string(),
+            ));
+        }
+
+        self.dispute_repository.list(filter, limit, offset).await
+    }
+}
+
+pub(crate) async fn find_dispute(
+    dispute_repository: &dyn DisputeRepository,
+    dispute_id: Uuid,
+) -> Result<Dispute, AppError> {
+    dispute_repository
+        .find_by_id(dispute_id)
+        .await?
+        .ok_or(AppError::NotFound(format!(
+            "Dispute {} not found",
+            dispute_id
+        )))
+}
@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, DISPUTE_ENTITY},
+        disputes::{
+            domain::{dispute::Dispute, repository::DisputeRepository},
+            dto::dispute_dto::OpenDisputeRequest,
+        },
+        payments::domain::repository::PaymentIntentRepository,
+    },
+};
+
+const MAX_REASON_LEN: usize = 2000;
+
+pub struct OpenDisputeUseCase {
+    dispute_repository: Arc<dyn DisputeRepository>,
+    payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+}
+
+impl OpenDisputeUseCase {
+    pub fn new(
+        dispute_repository: Arc<dyn DisputeRepository>,
+        payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+    ) -> Self {
+        Self {
+            dispute_repository,
+            payment_intent_repository,
+        }
+    }
+
+    /// Opens a case on a paid or failed payment within its dispute window.
+    /// A payment has at most one unresolved dispute at a time, and none once a dispute
+    /// moved money for it.
+    pub async fn execute(
+        &self,
+        request: OpenDisputeRequest,
+        opened_by: Uuid,
+        context: &AuditContext,
+    ) -> Result<Dispute, AppError> {
+        tracing::debug!("Opening dispute on payment {}", request.payment_id);
+
+        let reason = request.reason.trim().to_string();
+        if reason.is_empty() || reason.chars().count() > MAX_REASON_LEN {
+            return Err(AppError::ValidationError(format!(
+                "Reason must be 1 to {} characters",
+                MAX_REASON_LEN
+            )));
+        }
+
+        let payment = self
+            .payment_intent_repository
+            .find_by_id(request.payment_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Payment {} not found",
+                request.payment_id
+            )))?;
+
+        if let Some(existing) = self
+            .dispute_repository
+            .find_unresolved_by_payment(payment.id)
+            .await?
+        {
+            return Err(AppError::ValidationError(format!(
+                "Payment {} already has dispute {} in progress",
+                payment.id, existing.id
+            )));
+        }
+
+        if let Some(adjusted) = self
+            .dispute_repository
+            .find_adjusted_by_payment(payment.id)
+            .await?
+        {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} was already adjusted by dispute {}",
+                payment.id, adjusted.id
+            )));
+        }
+
+        let dispute = Dispute::open(&payment, reason, opened_by, Utc::now())?;
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Create,
+            DISPUTE_ENTITY,
+            dispute.id,
+            None,
+            Some(&dispute.audit_snapshot()),
+        );
+        let dispute = self.dispute_repository.create(dispute, audit).await?;
+
+        tracing::info!(
+            "Dispute {} opened on payment {} by {}",
+            dispute.id,
+            payment.id,
+            opened_by
+        );
+
+        Ok(dispute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::{Currency, Money};
+    use crate::domains::{
+        disputes::domain::repository::MockDisputeRepository,
+        payments::domain::{
+            payment_intent::PaymentIntent, repository::MockPaymentIntentRepository,
+        },
+    };
+    use chrono::Duration;
+
+    fn paid_payment() -> PaymentIntent {
+        let mut payment = PaymentIntent::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "order-1".to_string(),
+            Money::new(10_000, Currency::Usd),
+            None,
+            None,
+            Duration::minutes(30),
+        );
+        payment
+            .assign(Uuid::new_v4(), Uuid::new_v4(), Utc::now())
+            .unwrap();
+        payment.confirm(Utc::now(), Duration::hours(72)).unwrap();
+        payment
+    }
+
+    fn use_case(
+        payment: PaymentIntent,
+        dispute_repository: MockDisputeRepository,
+    ) -> OpenDisputeUseCase {
+        let mut payment_repository = MockPaymentIntentRepository::new();
+        payment_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(payment.clone())));
+
+        OpenDisputeUseCase::new(Arc::new(dispute_repository), Arc::new(payment_repository))
+    }
+
+    #[tokio::test]
+    async fn test_open_dispute_copies_payment() {
+        let payment = paid_payment();
+        let mut dispute_repository = MockDisputeRepository::new();
+        dispute_repository
+            .expect_find_unresolved_by_payment()
+            .returning(|_| Ok(None));
+        dispute_repository
+            .expect_find_adjusted_by_payment()
+            .returning(|_| Ok(None));
+        dispute_repository
+            .expect_create()
+            .withf(|dispute, audit| {
+                audit.entity_type == DISPUTE_ENTITY && audit.entity_id == dispute.id
+            })
+            .times(1)
+            .returning(|dispute, _| Ok(dispute));
+
+        let dispute = use_case(payment.clone(), dispute_repository)
+            .execute(
+                OpenDisputeRequest {
+                    payment_id: payment.id,
+                    reason: " Customer chargeback ".to_string(),
+                },
+                Uuid::new_v4(),
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(dispute.reason, "Customer chargeback");
+        assert_eq!(dispute.merchant_id, payment.merchant_id);
+        assert_eq!(
+            dispute.trader_id,
+            payment.assignment.map(|assignment| assignment.trader_id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_open_dispute_rejects_duplicates() {
+        let payment = paid_payment();
+        let existing =
+            Dispute::open(&payment, "First".to_string(), Uuid::new_v4(), Utc::now()).unwrap();
+        let mut dispute_repository = MockDisputeRepository::new();
+        dispute_repository
+            .expect_find_unresolved_by_payment()
+            .returning(move |_| Ok(Some(existing.clone())));
+        dispute_repository.expect_create().never();
+
+        let result = use_case(payment.clone(), dispute_repository)
+            .execute(
+                OpenDisputeRequest {
+                    payment_id: payment.id,
+                    reason: "Second".to_string(),
+                },
+                Uuid::new_v4(),
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_open_dispute_rejects_adjusted_payment() {
+        let payment = paid_payment();
+        let mut adjusted =
+            Dispute::open(&payment, "First".to_string(), Uuid::new_v4(), Utc::now()).unwrap();
+        adjusted.adjustment_entry_id = Some(Uuid::new_v4());
+        let mut dispute_repository = MockDisputeRepository::new();
+        dispute_repository
+            .expect_find_unresolved_by_payment()
+            .returning(|_| Ok(None));
+        dispute_repository
+            .expect_find_adjusted_by_payment()
+            .returning(move |_| Ok(Some(adjusted.clone())));
+        dispute_repository.expect_create().never();
+
+        let result = use_case(payment.clone(), dispute_repository)
+            .execute(
+                OpenDisputeRequest {
+                    payment_id: payment.id,
+                    reason: "Second".to_string(),
+                },
+                Uuid::new_v4(),
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+    }
+}
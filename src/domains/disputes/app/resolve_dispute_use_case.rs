@@ -0,0 +1,279 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::AuditContext,
+        disputes::{
+            app::{get_dispute_use_case::find_dispute, update_dispute_use_case::dispute_audit},
+            domain::{
+                dispute::{Dispute, LedgerParty},
+                repository::DisputeRepository,
+            },
+            dto::dispute_dto::ResolveDisputeRequest,
+        },
+        ledger::{
+            app::get_ledger_use_case::open_account,
+            domain::{
+                account::{AccountKind, LedgerAccount},
+                journal_entry::{EntryKind, JournalEntry},
+                repository::LedgerRepository,
+            },
+        },
+    },
+};
+
+const MAX_NOTE_LEN: usize = 2000;
+
+pub struct ResolveDisputeUseCase {
+    dispute_repository: Arc<dyn DisputeRepository>,
+    ledger_repository: Arc<dyn LedgerRepository>,
+}
+
+impl ResolveDisputeUseCase {
+    pub fn new(
+        dispute_repository: Arc<dyn DisputeRepository>,
+        ledger_repository: Arc<dyn LedgerRepository>,
+    ) -> Self {
+        Self {
+            dispute_repository,
+            ledger_repository,
+        }
+    }
+
+    /// Decides a case under investigation and posts the adjustment its outcome calls for;
+    /// see [`Dispute::adjustment`]. The entry is posted in the transaction that closes
+    /// the case, so of concurrent or retried resolutions only the first one counts.
+    pub async fn execute(
+        &self,
+        dispute_id: Uuid,
+        request: ResolveDisputeRequest,
+        resolved_by: Uuid,
+        context: &AuditContext,
+    ) -> Result<Dispute, AppError> {
+        tracing::debug!(
+            "Resolving dispute {} for the {}",
+            dispute_id,
+            request.resolution
+        );
+
+        let note = request.note.trim().to_string();
+        if note.is_empty() || note.chars().count() > MAX_NOTE_LEN {
+            return Err(AppError::ValidationError(format!(
+                "Note must be 1 to {} characters",
+                MAX_NOTE_LEN
+            )));
+        }
+
+        let mut dispute = find_dispute(self.dispute_repository.as_ref(), dispute_id).await?;
+        let before = dispute.audit_snapshot();
+        let adjustment = dispute.adjustment(request.resolution);
+        dispute.resolve(request.resolution, note, resolved_by, None, Utc::now())?;
+
+        let entry = match adjustment {
+            Some((debit, credit)) => Some(self.adjustment_entry(&dispute, debit, credit).await?),
+            None => None,
+        };
+        dispute.adjustment_entry_id = entry.as_ref().map(|entry| entry.id);
+        let audit = dispute_audit(context, &before, &dispute);
+        let dispute = self
+            .dispute_repository
+            .resolve(dispute, entry, audit)
+            .await?;
+
+        tracing::info!(
+            "Dispute {} resolved for the {} by {}",
+            dispute.id,
+            request.resolution,
+            resolved_by
+        );
+
+        Ok(dispute)
+    }
+
+    async fn adjustment_entry(
+        &self,
+        dispute: &Dispute,
+        debit: LedgerParty,
+        credit: LedgerParty,
+    ) -> Result<JournalEntry, AppError> {
+        let debit_account = self.account(dispute, debit).await?;
+        let credit_account = self.account(dispute, credit).await?;
+        JournalEntry::transfer(
+            EntryKind::Adjustment,
+            &debit_account,
+            &credit_account,
+            dispute.amount,
+            Some(dispute.id),
+            Some(format!(
+                "Dispute on payment {} resolved for the {}",
+                dispute.payment_id,
+                dispute
+                    .resolution
+                    .map(|resolution| resolution.as_str())
+                    .unwrap_or_default()
+            )),
+            dispute.resolved_by,
+        )
+    }
+
+    async fn account(
+        &self,
+        dispute: &Dispute,
+        party: LedgerParty,
+    ) -> Result<LedgerAccount, AppError> {
+        let (kind, owner_id) = match party {
+            LedgerParty::Treasury => (AccountKind::Treasury, None),
+            LedgerParty::Merchant => (AccountKind::Merchant, Some(dispute.merchant_id)),
+            LedgerParty::Trader => (
+                AccountKind::Trader,
+                Some(dispute.trader_id.ok_or(AppError::ValidationError(format!(
+                    "Payment {} has no trader to adjust",
+                    dispute.payment_id
+                )))?),
+            ),
+        };
+
+        open_account(
+            self.ledger_repository.as_ref(),
+            kind,
+            owner_id,
+            dispute.amount.currency(),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::{Currency, Money};
+    use crate::domains::{
+        disputes::domain::{
+            dispute::{DisputeResolution, DisputeStatus},
+            repository::MockDisputeRepository,
+        },
+        ledger::domain::repository::MockLedgerRepository,
+        payments::domain::payment_intent::PaymentIntent,
+    };
+    use chrono::Duration;
+
+    fn investigated_dispute() -> Dispute {
+        let mut payment = PaymentIntent::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "order-1".to_string(),
+            Money::new(10_000, Currency::Usd),
+            None,
+            None,
+            Duration::minutes(30),
+        );
+        payment
+            .assign(Uuid::new_v4(), Uuid::new_v4(), Utc::now())
+            .unwrap();
+        payment.confirm(Utc::now(), Duration::hours(72)).unwrap();
+
+        let mut dispute = Dispute::open(
+            &payment,
+            "Chargeback".to_string(),
+            Uuid::new_v4(),
+            Utc::now(),
+        )
+        .unwrap();
+        dispute.start_investigation(Uuid::new_v4()).unwrap();
+        dispute
+    }
+
+    fn use_case(dispute: Dispute, ledger: MockLedgerRepository) -> ResolveDisputeUseCase {
+        let mut dispute_repository = MockDisputeRepository::new();
+        dispute_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(dispute.clone())));
+        dispute_repository
+            .expect_resolve()
+            .withf(|dispute, entry, audit| {
+                dispute.adjustment_entry_id == entry.as_ref().map(|entry| entry.id)
+                    && audit.changes["status"]["after"] == "resolved"
+            })
+            .returning(|dispute, _, _| Ok(dispute));
+
+        ResolveDisputeUseCase::new(Arc::new(dispute_repository), Arc::new(ledger))
+    }
+
+    fn request(resolution: DisputeResolution) -> ResolveDisputeRequest {
+        ResolveDisputeRequest {
+            resolution,
+            note: "Bank confirmed the chargeback".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolution_for_customer_debits_merchant() {
+        let dispute = investigated_dispute();
+        let (dispute_id, resolver) = (dispute.id, Uuid::new_v4());
+        let mut ledger = MockLedgerRepository::new();
+        ledger
+            .expect_find_account_by_owner()
+            .returning(|_, _, _| Ok(None));
+        ledger.expect_create_account().returning(Ok);
+        ledger.expect_post().never();
+
+        let dispute = use_case(dispute, ledger)
+            .execute(
+                dispute_id,
+                request(DisputeResolution::Customer),
+                resolver,
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(dispute.is_resolved());
+        assert_eq!(dispute.resolved_by, Some(resolver));
+        assert!(dispute.adjustment_entry_id.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_resolution_for_merchant_of_paid_payment_posts_nothing() {
+        let dispute = investigated_dispute();
+        let dispute_id = dispute.id;
+        let mut ledger = MockLedgerRepository::new();
+        ledger.expect_post().never();
+
+        let dispute = use_case(dispute, ledger)
+            .execute(
+                dispute_id,
+                request(DisputeResolution::Merchant),
+                Uuid::new_v4(),
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(dispute.resolution, Some(DisputeResolution::Merchant));
+        assert_eq!(dispute.adjustment_entry_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_open_disputes_are_not_resolved() {
+        let mut dispute = investigated_dispute();
+        dispute.status = DisputeStatus::Open;
+        let dispute_id = dispute.id;
+        let mut ledger = MockLedgerRepository::new();
+        ledger.expect_post().never();
+
+        let result = use_case(dispute, ledger)
+            .execute(
+                dispute_id,
+                request(DisputeResolution::Customer),
+                Uuid::new_v4(),
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+    }
+}
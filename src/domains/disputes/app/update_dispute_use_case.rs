@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, DISPUTE_ENTITY},
+        backoffice::{
+            domain::repository::UserRepository,
+            role::{permission::DISPUTES_WRITE, RoleRepository},
+        },
+        disputes::{
+            app::get_dispute_use_case::find_dispute,
+            domain::{dispute::Dispute, repository::DisputeRepository},
+        },
+    },
+};
+
+pub struct UpdateDisputeUseCase {
+    dispute_repository: Arc<dyn DisputeRepository>,
+    user_repository: Arc<dyn UserRepository>,
+    role_repository: Arc<dyn RoleRepository>,
+}
+
+impl UpdateDisputeUseCase {
+    pub fn new(
+        dispute_repository: Arc<dyn DisputeRepository>,
+        user_repository: Arc<dyn UserRepository>,
+        role_repository: Arc<dyn RoleRepository>,
+    ) -> Self {
+        Self {
+            dispute_repository,
+            user_repository,
+            role_repository,
+        }
+    }
+
+    /// Hands the case to an active user who can work disputes, or back to the queue
+    pub async fn assign(
+        &self,
+        dispute_id: Uuid,
+        assignee_id: Option<Uuid>,
+        assigned_by: Uuid,
+        context: &AuditContext,
+    ) -> Result<Dispute, AppError> {
+        tracing::debug!("Assigning dispute {} to {:?}", dispute_id, assignee_id);
+
+        let mut dispute = find_dispute(self.dispute_repository.as_ref(), dispute_id).await?;
+
+        if let Some(assignee_id) = assignee_id {
+            let assignee = self
+                .user_repository
+                .find_by_id(assignee_id)
+                .await?
+                .filter(|user| user.is_active())
+                .ok_or(AppError::ValidationError(format!(
+                    "User {} is not an active backoffice user",
+                    assignee_id
+                )))?;
+            let permissions = self
+                .role_repository
+                .find_permission_names(assignee.role().role_id)
+                .await?;
+            if !permissions.iter().any(|name| name == DISPUTES_WRITE) {
+                return Err(AppError::ValidationError(format!(
+                    "User {} cannot work disputes",
+                    assignee_id
+                )));
+            }
+        }
+
+        let before = dispute.audit_snapshot();
+        dispute.assign(assignee_id)?;
+        let audit = dispute_audit(context, &before, &dispute);
+        let dispute = self.dispute_repository.update(dispute, audit).await?;
+
+        tracing::info!(
+            "Dispute {} assigned to {:?} by {}",
+            dispute.id,
+            dispute.assigned_to,
+            assigned_by
+        );
+
+        Ok(dispute)
+    }
+
+    pub async fn start_investigation(
+        &self,
+        dispute_id: Uuid,
+        user_id: Uuid,
+        context: &AuditContext,
+    ) -> Result<Dispute, AppError> {
+        tracing::debug!("Starting investigation of dispute {}", dispute_id);
+
+        let mut dispute = find_dispute(self.dispute_repository.as_ref(), dispute_id).await?;
+        let before = dispute.audit_snapshot();
+        dispute.start_investigation(user_id)?;
+        let audit = dispute_audit(context, &before, &dispute);
+        let dispute = self.dispute_repository.update(dispute, audit).await?;
+
+        tracing::info!("Dispute {} under investigation by {}", dispute.id, user_id);
+
+        Ok(dispute)
+    }
+}
+
+/// Audit entry of a change to the case from `before`
+pub(crate) fn dispute_audit(
+    context: &AuditContext,
+    before: &Value,
+    dispute: &Dispute,
+) -> AuditEntry {
+    AuditEntry::new(
+        context,
+        AuditAction::Update,
+        DISPUTE_ENTITY,
+        dispute.id,
+        Some(before),
+        Some(&dispute.audit_snapshot()),
+    )
+}
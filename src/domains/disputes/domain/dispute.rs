@@ -0,0 +1,514 @@
+use crate::common::{error::AppError, money::Money, time_formater};
+use crate::domains::payments::domain::{
+    evidence::{sanitize_file_name, ALLOWED_EVIDENCE_TYPES},
+    payment_intent::{PaymentIntent, PaymentStatus},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{fmt, str::FromStr};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Case opened on a paid or failed payment while its dispute window is open.
+/// Support works the case; resolving it moves the payment amount in the ledger.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Dispute {
+    pub id: Uuid,
+    pub payment_id: Uuid,
+    pub merchant_id: Uuid,
+    /// Trader the payment was matched to
+    pub trader_id: Option<Uuid>,
+    /// Payment amount at stake
+    pub amount: Money,
+    /// Payment status when the dispute was opened; `paid` or `failed`
+    pub payment_status: PaymentStatus,
+    pub reason: String,
+    pub status: DisputeStatus,
+    pub resolution: Option<DisputeResolution>,
+    pub resolution_note: Option<String>,
+    /// Backoffice user working the case
+    pub assigned_to: Option<Uuid>,
+    pub opened_by: Uuid,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// Journal entry posted for the resolution, if it moved money
+    pub adjustment_entry_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Dispute {
+    /// Opens a case on `payment`; fails once its dispute window has closed
+    pub fn open(
+        payment: &PaymentIntent,
+        reason: String,
+        opened_by: Uuid,
+        at: DateTime<Utc>,
+    ) -> Result<Self, AppError> {
+        if !payment.is_disputable(at) {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} cannot be disputed",
+                payment.id
+            )));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            payment_id: payment.id,
+            merchant_id: payment.merchant_id,
+            trader_id: payment.assignment.map(|assignment| assignment.trader_id),
+            amount: payment.amount,
+            payment_status: payment.status,
+            reason,
+            status: DisputeStatus::Open,
+            resolution: None,
+            resolution_note: None,
+            assigned_to: None,
+            opened_by,
+            resolved_by: None,
+            resolved_at: None,
+            adjustment_entry_id: None,
+            created_at: at,
+            updated_at: at,
+        })
+    }
+
+    /// Fields compared in audit entries; comments and attachments are audited on their own
+    pub fn audit_snapshot(&self) -> Value {
+        json!({
+            "payment_id": self.payment_id,
+            "reason": self.reason,
+            "status": self.status,
+            "resolution": self.resolution,
+            "resolution_note": self.resolution_note,
+            "assigned_to": self.assigned_to,
+            "resolved_at": self.resolved_at.as_ref().map(time_formater::format),
+            "adjustment_entry_id": self.adjustment_entry_id,
+        })
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        self.status == DisputeStatus::Resolved
+    }
+
+    /// Fails on resolved cases, which are kept as they were closed
+    pub fn ensure_unresolved(&self) -> Result<(), AppError> {
+        if self.is_resolved() {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Dispute {} is resolved",
+                self.id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Hands the case to a backoffice user, or back to the queue with `None`
+    pub fn assign(&mut self, assignee: Option<Uuid>) -> Result<(), AppError> {
+        self.ensure_unresolved()?;
+
+        self.assigned_to = assignee;
+        self.updated_at = Utc::now();
+
+        Ok(())
+    }
+
+    /// Open → investigating; an unassigned case goes to whoever starts it
+    pub fn start_investigation(&mut self, user_id: Uuid) -> Result<(), AppError> {
+        if self.status != DisputeStatus::Open {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Dispute {} is {}; only open disputes can be investigated",
+                self.id, self.status
+            )));
+        }
+
+        self.status = DisputeStatus::Investigating;
+        self.assigned_to.get_or_insert(user_id);
+        self.updated_at = Utc::now();
+
+        Ok(())
+    }
+
+    /// Investigating → resolved in favour of `resolution`
+    pub fn resolve(
+        &mut self,
+        resolution: DisputeResolution,
+        note: String,
+        resolved_by: Uuid,
+        adjustment_entry_id: Option<Uuid>,
+        at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        if self.status != DisputeStatus::Investigating {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Dispute {} is {}; only disputes under investigation can be resolved",
+                self.id, self.status
+            )));
+        }
+
+        self.status = DisputeStatus::Resolved;
+        self.resolution = Some(resolution);
+        self.resolution_note = Some(note);
+        self.resolved_by = Some(resolved_by);
+        self.resolved_at = Some(at);
+        self.adjustment_entry_id = adjustment_entry_id;
+        self.updated_at = at;
+
+        Ok(())
+    }
+
+    /// Ledger movement a resolution calls for, as (debited, credited) party.
+    ///
+    /// | payment | customer             | merchant           | trader             |
+    /// |---------|----------------------|--------------------|--------------------|
+    /// | paid    | merchant → treasury  | –                  | merchant → trader  |
+    /// | failed  | trader → treasury    | trader → merchant  | –                  |
+    ///
+    /// A paid payment was already settled to the merchant: the merchant refunds the
+    /// customer, or the trader who bore a charged-back transfer. A failed payment was
+    /// rejected by the trader: losing the case means the trader kept the money, which is
+    /// taken from its deposit for the customer's refund or the merchant's credit.
+    pub fn adjustment(&self, resolution: DisputeResolution) -> Option<(LedgerParty, LedgerParty)> {
+        match (self.payment_status, resolution) {
+            (PaymentStatus::Paid, DisputeResolution::Customer) => {
+                Some((LedgerParty::Merchant, LedgerParty::Treasury))
+            }
+            (PaymentStatus::Paid, DisputeResolution::Trader) => {
+                Some((LedgerParty::Merchant, LedgerParty::Trader))
+            }
+            (PaymentStatus::Failed, DisputeResolution::Customer) => {
+                Some((LedgerParty::Trader, LedgerParty::Treasury))
+            }
+            (PaymentStatus::Failed, DisputeResolution::Merchant) => {
+                Some((LedgerParty::Trader, LedgerParty::Merchant))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Ledger account a dispute resolution posts to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerParty {
+    /// Platform funds; crediting it pays the customer back
+    Treasury,
+    Merchant,
+    Trader,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DisputeStatus {
+    /// Waiting in the queue
+    Open,
+    /// Being worked by the assignee
+    Investigating,
+    Resolved,
+}
+
+impl DisputeStatus {
+    /// Value stored in the `status` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisputeStatus::Open => "open",
+            DisputeStatus::Investigating => "investigating",
+            DisputeStatus::Resolved => "resolved",
+        }
+    }
+}
+
+impl fmt::Display for DisputeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DisputeStatus {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "open" => Ok(DisputeStatus::Open),
+            "investigating" => Ok(DisputeStatus::Investigating),
+            "resolved" => Ok(DisputeStatus::Resolved),
+            other => Err(AppError::InternalError(format!(
+                "Unknown dispute status '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Party the case was decided for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DisputeResolution {
+    Customer,
+    Merchant,
+    Trader,
+}
+
+impl DisputeResolution {
+    /// Value stored in the `resolution` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisputeResolution::Customer => "customer",
+            DisputeResolution::Merchant => "merchant",
+            DisputeResolution::Trader => "trader",
+        }
+    }
+}
+
+impl fmt::Display for DisputeResolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DisputeResolution {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "customer" => Ok(DisputeResolution::Customer),
+            "merchant" => Ok(DisputeResolution::Merchant),
+            "trader" => Ok(DisputeResolution::Trader),
+            other => Err(AppError::InternalError(format!(
+                "Unknown dispute resolution '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Dispute search criteria; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DisputeFilter {
+    pub status: Option<DisputeStatus>,
+    pub assigned_to: Option<Uuid>,
+    pub payment_id: Option<Uuid>,
+    pub merchant_id: Option<Uuid>,
+    pub trader_id: Option<Uuid>,
+}
+
+impl DisputeFilter {
+    pub fn matches(&self, dispute: &Dispute) -> bool {
+        self.status.is_none_or(|status| dispute.status == status)
+            && self
+                .assigned_to
+                .is_none_or(|user_id| dispute.assigned_to == Some(user_id))
+            && self
+                .payment_id
+                .is_none_or(|payment_id| dispute.payment_id == payment_id)
+            && self
+                .merchant_id
+                .is_none_or(|merchant_id| dispute.merchant_id == merchant_id)
+            && self
+                .trader_id
+                .is_none_or(|trader_id| dispute.trader_id == Some(trader_id))
+    }
+}
+
+/// Internal note on a case; never shown to merchants
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DisputeComment {
+    pub id: Uuid,
+    pub dispute_id: Uuid,
+    pub author_id: Uuid,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DisputeComment {
+    pub fn new(dispute_id: Uuid, author_id: Uuid, body: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            dispute_id,
+            author_id,
+            body,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Fields recorded in the audit entry of the comment
+    pub fn audit_snapshot(&self) -> Value {
+        json!({ "dispute_id": self.dispute_id, "body": self.body })
+    }
+}
+
+/// File added to a case, e.g. a bank statement; the content lives in file storage
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DisputeAttachment {
+    pub id: Uuid,
+    pub dispute_id: Uuid,
+    pub uploaded_by: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Hex SHA-256 of the content
+    pub sha256: String,
+    pub storage_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DisputeAttachment {
+    pub fn new(
+        dispute_id: Uuid,
+        uploaded_by: Uuid,
+        file_name: &str,
+        content_type: &str,
+        size_bytes: i64,
+        sha256: String,
+    ) -> Result<Self, AppError> {
+        if !ALLOWED_EVIDENCE_TYPES.contains(&content_type) {
+            return Err(AppError::ValidationError(format!(
+                "Attachment must be one of {}",
+                ALLOWED_EVIDENCE_TYPES.join(", ")
+            )));
+        }
+
+        let id = Uuid::new_v4();
+
+        Ok(Self {
+            id,
+            dispute_id,
+            uploaded_by,
+            file_name: sanitize_file_name(file_name),
+            content_type: content_type.to_string(),
+            size_bytes,
+            sha256,
+            storage_key: format!("disputes/{}/attachments/{}", dispute_id, id),
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Fields recorded in the audit entry of the upload
+    pub fn audit_snapshot(&self) -> Value {
+        json!({
+            "dispute_id": self.dispute_id,
+            "file_name": self.file_name,
+            "content_type": self.content_type,
+            "size_bytes": self.size_bytes,
+            "sha256": self.sha256,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::Currency;
+    use chrono::Duration;
+
+    fn resolved_payment(status: PaymentStatus) -> PaymentIntent {
+        let mut payment = PaymentIntent::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "order-1".to_string(),
+            Money::new(10_000, Currency::Usd),
+            None,
+            None,
+            Duration::minutes(30),
+        );
+        payment
+            .assign(Uuid::new_v4(), Uuid::new_v4(), Utc::now())
+            .unwrap();
+        payment.mark_paid(Utc::now()).unwrap();
+        match status {
+            PaymentStatus::Paid => payment.confirm(Utc::now(), Duration::hours(72)).unwrap(),
+            _ => payment
+                .reject("No transfer".to_string(), Utc::now(), Duration::hours(72))
+                .unwrap(),
+        }
+        payment
+    }
+
+    #[test]
+    fn test_dispute_workflow() {
+        let payment = resolved_payment(PaymentStatus::Paid);
+        let (opener, investigator) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let mut dispute = Dispute::open(
+            &payment,
+            "Goods not delivered".to_string(),
+            opener,
+            Utc::now(),
+        )
+        .unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Open);
+        assert_eq!(dispute.amount, payment.amount);
+
+        // Cases are investigated before they are decided
+        let result = dispute.resolve(
+            DisputeResolution::Merchant,
+            "Delivered".to_string(),
+            investigator,
+            None,
+            Utc::now(),
+        );
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+
+        dispute.start_investigation(investigator).unwrap();
+        assert_eq!(dispute.assigned_to, Some(investigator));
+        assert!(dispute.start_investigation(investigator).is_err());
+
+        dispute
+            .resolve(
+                DisputeResolution::Merchant,
+                "Delivered".to_string(),
+                investigator,
+                None,
+                Utc::now(),
+            )
+            .unwrap();
+        assert!(dispute.is_resolved());
+        assert!(dispute.assign(Some(opener)).is_err());
+    }
+
+    #[test]
+    fn test_only_payments_in_their_dispute_window_can_be_disputed() {
+        let payment = resolved_payment(PaymentStatus::Paid);
+        let after_window = payment.dispute_until.unwrap() + Duration::seconds(1);
+
+        let result = Dispute::open(&payment, "Late".to_string(), Uuid::new_v4(), after_window);
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+    }
+
+    #[test]
+    fn test_adjustment_follows_the_payment_outcome() {
+        let opener = Uuid::new_v4();
+        let paid = Dispute::open(
+            &resolved_payment(PaymentStatus::Paid),
+            "Chargeback".to_string(),
+            opener,
+            Utc::now(),
+        )
+        .unwrap();
+        let failed = Dispute::open(
+            &resolved_payment(PaymentStatus::Failed),
+            "I paid".to_string(),
+            opener,
+            Utc::now(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            paid.adjustment(DisputeResolution::Customer),
+            Some((LedgerParty::Merchant, LedgerParty::Treasury))
+        );
+        assert_eq!(paid.adjustment(DisputeResolution::Merchant), None);
+        assert_eq!(
+            paid.adjustment(DisputeResolution::Trader),
+            Some((LedgerParty::Merchant, LedgerParty::Trader))
+        );
+        assert_eq!(
+            failed.adjustment(DisputeResolution::Customer),
+            Some((LedgerParty::Trader, LedgerParty::Treasury))
+        );
+        assert_eq!(
+            failed.adjustment(DisputeResolution::Merchant),
+            Some((LedgerParty::Trader, LedgerParty::Merchant))
+        );
+        assert_eq!(failed.adjustment(DisputeResolution::Trader), None);
+    }
+}
@@ -0,0 +1,87 @@
+use super::dispute::{Dispute, DisputeAttachment, DisputeComment, DisputeFilter};
+use crate::common::error::AppError;
+use crate::domains::audit::domain::audit_entry::AuditEntry;
+use crate::domains::ledger::domain::journal_entry::JournalEntry;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait DisputeRepository: Send + Sync {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Dispute>, AppError>;
+
+    /// The payment's dispute that is not resolved yet; there is at most one
+    async fn find_unresolved_by_payment(
+        &self,
+        payment_id: Uuid,
+    ) -> Result<Option<Dispute>, AppError>;
+
+    /// The payment's resolved dispute that posted an adjustment; there is at most one
+    async fn find_adjusted_by_payment(&self, payment_id: Uuid)
+        -> Result<Option<Dispute>, AppError>;
+
+    async fn create(&self, dispute: Dispute, audit: AuditEntry) -> Result<Dispute, AppError>;
+    async fn update(&self, dispute: Dispute, audit: AuditEntry) -> Result<Dispute, AppError>;
+
+    /// Stores the resolved `dispute`, posts its `adjustment` and appends `audit` in one
+    /// transaction.
+    /// Fails with `InvalidStateTransition` unless the stored case is still under
+    /// investigation, so only one of concurrent resolutions goes through, or when an
+    /// earlier dispute on the payment already posted an adjustment.
+    async fn resolve(
+        &self,
+        dispute: Dispute,
+        adjustment: Option<JournalEntry>,
+        audit: AuditEntry,
+    ) -> Result<Dispute, AppError>;
+
+    /// Oldest first, so the queue is worked in order
+    async fn list(
+        &self,
+        filter: DisputeFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Dispute>, AppError>;
+
+    async fn add_comment(
+        &self,
+        comment: DisputeComment,
+        audit: AuditEntry,
+    ) -> Result<DisputeComment, AppError>;
+
+    /// Oldest first
+    async fn list_comments(&self, dispute_id: Uuid) -> Result<Vec<DisputeComment>, AppError>;
+
+    async fn find_attachment(&self, id: Uuid) -> Result<Option<DisputeAttachment>, AppError>;
+    async fn add_attachment(
+        &self,
+        attachment: DisputeAttachment,
+        audit: AuditEntry,
+    ) -> Result<DisputeAttachment, AppError>;
+
+    /// Oldest first
+    async fn list_attachments(&self, dispute_id: Uuid) -> Result<Vec<DisputeAttachment>, AppError>;
+}
+
+#[cfg(test)]
+use mockall::mock;
+
+#[cfg(test)]
+mock! {
+    pub DisputeRepository {}
+
+    #[async_trait]
+    impl DisputeRepository for DisputeRepository {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Dispute>, AppError>;
+        async fn find_unresolved_by_payment(&self, payment_id: Uuid) -> Result<Option<Dispute>, AppError>;
+        async fn find_adjusted_by_payment(&self, payment_id: Uuid) -> Result<Option<Dispute>, AppError>;
+        async fn create(&self, dispute: Dispute, audit: AuditEntry) -> Result<Dispute, AppError>;
+        async fn update(&self, dispute: Dispute, audit: AuditEntry) -> Result<Dispute, AppError>;
+        async fn resolve(&self, dispute: Dispute, adjustment: Option<JournalEntry>, audit: AuditEntry) -> Result<Dispute, AppError>;
+        async fn list(&self, filter: DisputeFilter, limit: i64, offset: i64) -> Result<Vec<Dispute>, AppError>;
+        async fn add_comment(&self, comment: DisputeComment, audit: AuditEntry) -> Result<DisputeComment, AppError>;
+        async fn list_comments(&self, dispute_id: Uuid) -> Result<Vec<DisputeComment>, AppError>;
+        async fn find_attachment(&self, id: Uuid) -> Result<Option<DisputeAttachment>, AppError>;
+        async fn add_attachment(&self, attachment: DisputeAttachment, audit: AuditEntry) -> Result<DisputeAttachment, AppError>;
+        async fn list_attachments(&self, dispute_id: Uuid) -> Result<Vec<DisputeAttachment>, AppError>;
+    }
+}
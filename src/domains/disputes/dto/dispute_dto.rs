@@ -0,0 +1,233 @@
+use crate::common::money::Money;
+use crate::domains::disputes::domain::dispute::{
+    Dispute, DisputeAttachment, DisputeComment, DisputeFilter, DisputeResolution, DisputeStatus,
+};
+use crate::domains::payments::domain::payment_intent::PaymentStatus;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct OpenDisputeRequest {
+    /// Paid or failed payment whose dispute window is still open
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub payment_id: Uuid,
+
+    /// What the complaint is about, at most 2000 characters
+    #[schema(example = "Customer filed a chargeback with their bank")]
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AssignDisputeRequest {
+    /// User who works the case; `null` returns it to the queue
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub assignee_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ResolveDisputeRequest {
+    /// Party the case is decided for
+    pub resolution: DisputeResolution,
+
+    /// Why, at most 2000 characters
+    #[schema(example = "Bank confirmed the chargeback")]
+    pub note: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CreateDisputeCommentRequest {
+    /// Internal note, at most 2000 characters
+    #[schema(example = "Asked the trader for a bank statement")]
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DisputeResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub payment_id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub merchant_id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub trader_id: Option<String>,
+
+    /// Decimal `amount` and `currency` of the payment
+    #[serde(flatten)]
+    pub amount: Money,
+
+    /// Payment status when the dispute was opened
+    pub payment_status: PaymentStatus,
+
+    #[schema(example = "Customer filed a chargeback with their bank")]
+    pub reason: String,
+
+    pub status: DisputeStatus,
+
+    pub resolution: Option<DisputeResolution>,
+
+    #[schema(example = "Bank confirmed the chargeback")]
+    pub resolution_note: Option<String>,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub assigned_to: Option<String>,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub opened_by: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub resolved_by: Option<String>,
+
+    #[serde(with = "crate::common::time_formater::option")]
+    #[schema(example = "2024-01-03T09:00:00Z")]
+    pub resolved_at: Option<DateTime<Utc>>,
+
+    /// Ledger entry posted for the resolution
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub adjustment_entry_id: Option<String>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-02T12:00:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-02T12:00:00Z")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Dispute> for DisputeResponse {
+    fn from(dispute: Dispute) -> Self {
+        Self {
+            id: dispute.id.to_string(),
+            payment_id: dispute.payment_id.to_string(),
+            merchant_id: dispute.merchant_id.to_string(),
+            trader_id: dispute.trader_id.map(|id| id.to_string()),
+            amount: dispute.amount,
+            payment_status: dispute.payment_status,
+            reason: dispute.reason,
+            status: dispute.status,
+            resolution: dispute.resolution,
+            resolution_note: dispute.resolution_note,
+            assigned_to: dispute.assigned_to.map(|id| id.to_string()),
+            opened_by: dispute.opened_by.to_string(),
+            resolved_by: dispute.resolved_by.map(|id| id.to_string()),
+            resolved_at: dispute.resolved_at,
+            adjustment_entry_id: dispute.adjustment_entry_id.map(|id| id.to_string()),
+            created_at: dispute.created_at,
+            updated_at: dispute.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DisputeCommentResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub dispute_id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub author_id: String,
+
+    #[schema(example = "Asked the trader for a bank statement")]
+    pub body: String,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-02T13:00:00Z")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DisputeComment> for DisputeCommentResponse {
+    fn from(comment: DisputeComment) -> Self {
+        Self {
+            id: comment.id.to_string(),
+            dispute_id: comment.dispute_id.to_string(),
+            author_id: comment.author_id.to_string(),
+            body: comment.body,
+            created_at: comment.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DisputeAttachmentResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub dispute_id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub uploaded_by: String,
+
+    #[schema(example = "statement.pdf")]
+    pub file_name: String,
+
+    #[schema(example = "application/pdf")]
+    pub content_type: String,
+
+    #[schema(example = 48213)]
+    pub size_bytes: i64,
+
+    /// Hex SHA-256 of the content
+    #[schema(example = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08")]
+    pub sha256: String,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-02T13:00:00Z")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DisputeAttachment> for DisputeAttachmentResponse {
+    fn from(attachment: DisputeAttachment) -> Self {
+        Self {
+            id: attachment.id.to_string(),
+            dispute_id: attachment.dispute_id.to_string(),
+            uploaded_by: attachment.uploaded_by.to_string(),
+            file_name: attachment.file_name,
+            content_type: attachment.content_type,
+            size_bytes: attachment.size_bytes,
+            sha256: attachment.sha256,
+            created_at: attachment.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListDisputesQuery {
+    /// Page size, at most 100
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    #[param(value_type = Option<String>, example = "open")]
+    pub status: Option<DisputeStatus>,
+    /// Cases worked by this user
+    pub assigned_to: Option<Uuid>,
+    pub payment_id: Option<Uuid>,
+    pub merchant_id: Option<Uuid>,
+    pub trader_id: Option<Uuid>,
+}
+
+impl From<ListDisputesQuery> for DisputeFilter {
+    fn from(query: ListDisputesQuery) -> Self {
+        Self {
+            status: query.status,
+            assigned_to: query.assigned_to,
+            payment_id: query.payment_id,
+            merchant_id: query.merchant_id,
+            trader_id: query.trader_id,
+        }
+    }
+}
+
+fn default_limit() -> i64 {
+    20
+}
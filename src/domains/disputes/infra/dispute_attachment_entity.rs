@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "dispute_attachment")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub dispute_id: Uuid,
+    pub uploaded_by: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    #[sea_orm(unique)]
+    pub storage_key: String,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::dispute_entity::Entity",
+        from = "Column::DisputeId",
+        to = "super::dispute_entity::Column::Id"
+    )]
+    Dispute,
+}
+
+impl Related<super::dispute_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Dispute.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
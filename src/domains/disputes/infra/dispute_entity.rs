@@ -0,0 +1,49 @@
+use crate::common::money::Currency;
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "dispute")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub payment_id: Uuid,
+    pub merchant_id: Uuid,
+    pub trader_id: Option<Uuid>,
+    pub amount: i64,
+    pub currency: Currency,
+    pub payment_status: String,
+    pub reason: String,
+    pub status: String,
+    pub resolution: Option<String>,
+    pub resolution_note: Option<String>,
+    pub assigned_to: Option<Uuid>,
+    pub opened_by: Uuid,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTimeWithTimeZone>,
+    pub adjustment_entry_id: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::dispute_comment_entity::Entity")]
+    DisputeComment,
+    #[sea_orm(has_many = "super::dispute_attachment_entity::Entity")]
+    DisputeAttachment,
+}
+
+impl Related<super::dispute_comment_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DisputeComment.def()
+    }
+}
+
+impl Related<super::dispute_attachment_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DisputeAttachment.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
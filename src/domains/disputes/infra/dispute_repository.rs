@@ -0,0 +1,337 @@
+use super::dispute_attachment_entity::{self, Entity as DisputeAttachmentEntity};
+use super::dispute_comment_entity::{self, Entity as DisputeCommentEntity};
+use super::dispute_entity::{self, Entity as DisputeEntity};
+use crate::common::{error::AppError, money::Money};
+use crate::domains::{
+    audit::{
+        domain::audit_entry::AuditEntry, infra::audit_log_repository::PostgresAuditLogRepository,
+    },
+    disputes::domain::{
+        dispute::{Dispute, DisputeAttachment, DisputeComment, DisputeFilter, DisputeStatus},
+        repository::DisputeRepository,
+    },
+    ledger::{
+        domain::journal_entry::JournalEntry, infra::ledger_repository::PostgresLedgerRepository,
+    },
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, SqlErr, TransactionTrait,
+};
+use uuid::Uuid;
+
+pub struct PostgresDisputeRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresDisputeRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(model: dispute_entity::Model) -> Result<Dispute, AppError> {
+        Ok(Dispute {
+            id: model.id,
+            payment_id: model.payment_id,
+            merchant_id: model.merchant_id,
+            trader_id: model.trader_id,
+            amount: Money::new(model.amount, model.currency),
+            payment_status: model.payment_status.parse()?,
+            reason: model.reason,
+            status: model.status.parse()?,
+            resolution: model.resolution.as_deref().map(str::parse).transpose()?,
+            resolution_note: model.resolution_note,
+            assigned_to: model.assigned_to,
+            opened_by: model.opened_by,
+            resolved_by: model.resolved_by,
+            resolved_at: model.resolved_at.map(|at| at.with_timezone(&Utc)),
+            adjustment_entry_id: model.adjustment_entry_id,
+            created_at: model.created_at.with_timezone(&Utc),
+            updated_at: model.updated_at.with_timezone(&Utc),
+        })
+    }
+
+    fn domain_to_active_model(dispute: Dispute) -> dispute_entity::ActiveModel {
+        dispute_entity::ActiveModel {
+            id: Set(dispute.id),
+            payment_id: Set(dispute.payment_id),
+            merchant_id: Set(dispute.merchant_id),
+            trader_id: Set(dispute.trader_id),
+            amount: Set(dispute.amount.minor_units()),
+            currency: Set(dispute.amount.currency()),
+            payment_status: Set(dispute.payment_status.as_str().to_string()),
+            reason: Set(dispute.reason),
+            status: Set(dispute.status.as_str().to_string()),
+            resolution: Set(dispute
+                .resolution
+                .map(|resolution| resolution.as_str().to_string())),
+            resolution_note: Set(dispute.resolution_note),
+            assigned_to: Set(dispute.assigned_to),
+            opened_by: Set(dispute.opened_by),
+            resolved_by: Set(dispute.resolved_by),
+            resolved_at: Set(dispute.resolved_at.map(Into::into)),
+            adjustment_entry_id: Set(dispute.adjustment_entry_id),
+            created_at: Set(dispute.created_at.into()),
+            updated_at: Set(dispute.updated_at.into()),
+        }
+    }
+
+    fn comment_to_domain(model: dispute_comment_entity::Model) -> DisputeComment {
+        DisputeComment {
+            id: model.id,
+            dispute_id: model.dispute_id,
+            author_id: model.author_id,
+            body: model.body,
+            created_at: model.created_at.with_timezone(&Utc),
+        }
+    }
+
+    fn attachment_to_domain(model: dispute_attachment_entity::Model) -> DisputeAttachment {
+        DisputeAttachment {
+            id: model.id,
+            dispute_id: model.dispute_id,
+            uploaded_by: model.uploaded_by,
+            file_name: model.file_name,
+            content_type: model.content_type,
+            size_bytes: model.size_bytes,
+            sha256: model.sha256,
+            storage_key: model.storage_key,
+            created_at: model.created_at.with_timezone(&Utc),
+        }
+    }
+
+    fn filter_condition(filter: DisputeFilter) -> Condition {
+        let mut condition = Condition::all();
+
+        if let Some(status) = filter.status {
+            condition = condition.add(dispute_entity::Column::Status.eq(status.as_str()));
+        }
+        if let Some(assigned_to) = filter.assigned_to {
+            condition = condition.add(dispute_entity::Column::AssignedTo.eq(assigned_to));
+        }
+        if let Some(payment_id) = filter.payment_id {
+            condition = condition.add(dispute_entity::Column::PaymentId.eq(payment_id));
+        }
+        if let Some(merchant_id) = filter.merchant_id {
+            condition = condition.add(dispute_entity::Column::MerchantId.eq(merchant_id));
+        }
+        if let Some(trader_id) = filter.trader_id {
+            condition = condition.add(dispute_entity::Column::TraderId.eq(trader_id));
+        }
+
+        condition
+    }
+}
+
+#[async_trait]
+impl DisputeRepository for PostgresDisputeRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Dispute>, AppError> {
+        DisputeEntity::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .map(Self::entity_to_domain)
+            .transpose()
+    }
+
+    async fn find_unresolved_by_payment(
+        &self,
+        payment_id: Uuid,
+    ) -> Result<Option<Dispute>, AppError> {
+        DisputeEntity::find()
+            .filter(dispute_entity::Column::PaymentId.eq(payment_id))
+            .filter(dispute_entity::Column::Status.ne(DisputeStatus::Resolved.as_str()))
+            .one(&self.db)
+            .await?
+            .map(Self::entity_to_domain)
+            .transpose()
+    }
+
+    async fn find_adjusted_by_payment(
+        &self,
+        payment_id: Uuid,
+    ) -> Result<Option<Dispute>, AppError> {
+        DisputeEntity::find()
+            .filter(dispute_entity::Column::PaymentId.eq(payment_id))
+            .filter(dispute_entity::Column::AdjustmentEntryId.is_not_null())
+            .one(&self.db)
+            .await?
+            .map(Self::entity_to_domain)
+            .transpose()
+    }
+
+    async fn create(&self, dispute: Dispute, audit: AuditEntry) -> Result<Dispute, AppError> {
+        let payment_id = dispute.payment_id;
+        let txn = self.db.begin().await?;
+
+        let model = Self::domain_to_active_model(dispute)
+            .insert(&txn)
+            .await
+            .map_err(|err| match err.sql_err() {
+                // idx_dispute_unresolved_payment_id closes the race between two openers
+                Some(SqlErr::UniqueConstraintViolation(_)) => AppError::ValidationError(format!(
+                    "Payment {} already has a dispute in progress",
+                    payment_id
+                )),
+                _ => AppError::from(err),
+            })?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+
+        txn.commit().await?;
+
+        Self::entity_to_domain(model)
+    }
+
+    async fn update(&self, dispute: Dispute, audit: AuditEntry) -> Result<Dispute, AppError> {
+        let txn = self.db.begin().await?;
+
+        let model = Self::domain_to_active_model(dispute).update(&txn).await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+
+        txn.commit().await?;
+
+        Self::entity_to_domain(model)
+    }
+
+    async fn resolve(
+        &self,
+        dispute: Dispute,
+        adjustment: Option<JournalEntry>,
+        audit: AuditEntry,
+    ) -> Result<Dispute, AppError> {
+        let dispute_id = dispute.id;
+        let payment_id = dispute.payment_id;
+        let txn = self.db.begin().await?;
+
+        let Some(model) = DisputeEntity::update_many()
+            .set(Self::domain_to_active_model(dispute))
+            .filter(dispute_entity::Column::Id.eq(dispute_id))
+            .filter(dispute_entity::Column::Status.eq(DisputeStatus::Investigating.as_str()))
+            .exec_with_returning(&txn)
+            .await
+            .map_err(|err| match err.sql_err() {
+                // idx_dispute_adjusted_payment_id: a payment is refunded or charged once
+                Some(SqlErr::UniqueConstraintViolation(_)) => {
+                    AppError::InvalidStateTransition(format!(
+                        "Payment {} was already adjusted by an earlier dispute",
+                        payment_id
+                    ))
+                }
+                _ => AppError::from(err),
+            })?
+            .pop()
+        else {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Dispute {} is no longer under investigation",
+                dispute_id
+            )));
+        };
+
+        if let Some(entry) = adjustment {
+            PostgresLedgerRepository::insert_entries(&txn, &[entry]).await?;
+        }
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+
+        txn.commit().await?;
+
+        Self::entity_to_domain(model)
+    }
+
+    async fn list(
+        &self,
+        filter: DisputeFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Dispute>, AppError> {
+        DisputeEntity::find()
+            .filter(Self::filter_condition(filter))
+            .order_by_asc(dispute_entity::Column::CreatedAt)
+            .limit(limit as u64)
+            .offset(offset as u64)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(Self::entity_to_domain)
+            .collect()
+    }
+
+    async fn add_comment(
+        &self,
+        comment: DisputeComment,
+        audit: AuditEntry,
+    ) -> Result<DisputeComment, AppError> {
+        let txn = self.db.begin().await?;
+
+        let model = dispute_comment_entity::ActiveModel {
+            id: Set(comment.id),
+            dispute_id: Set(comment.dispute_id),
+            author_id: Set(comment.author_id),
+            body: Set(comment.body),
+            created_at: Set(comment.created_at.into()),
+        }
+        .insert(&txn)
+        .await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+
+        txn.commit().await?;
+
+        Ok(Self::comment_to_domain(model))
+    }
+
+    async fn list_comments(&self, dispute_id: Uuid) -> Result<Vec<DisputeComment>, AppError> {
+        Ok(DisputeCommentEntity::find()
+            .filter(dispute_comment_entity::Column::DisputeId.eq(dispute_id))
+            .order_by_asc(dispute_comment_entity::Column::CreatedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(Self::comment_to_domain)
+            .collect())
+    }
+
+    async fn find_attachment(&self, id: Uuid) -> Result<Option<DisputeAttachment>, AppError> {
+        Ok(DisputeAttachmentEntity::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .map(Self::attachment_to_domain))
+    }
+
+    async fn add_attachment(
+        &self,
+        attachment: DisputeAttachment,
+        audit: AuditEntry,
+    ) -> Result<DisputeAttachment, AppError> {
+        let txn = self.db.begin().await?;
+
+        let model = dispute_attachment_entity::ActiveModel {
+            id: Set(attachment.id),
+            dispute_id: Set(attachment.dispute_id),
+            uploaded_by: Set(attachment.uploaded_by),
+            file_name: Set(attachment.file_name),
+            content_type: Set(attachment.content_type),
+            size_bytes: Set(attachment.size_bytes),
+            sha256: Set(attachment.sha256),
+            storage_key: Set(attachment.storage_key),
+            created_at: Set(attachment.created_at.into()),
+        }
+        .insert(&txn)
+        .await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+
+        txn.commit().await?;
+
+        Ok(Self::attachment_to_domain(model))
+    }
+
+    async fn list_attachments(&self, dispute_id: Uuid) -> Result<Vec<DisputeAttachment>, AppError> {
+        Ok(DisputeAttachmentEntity::find()
+            .filter(dispute_attachment_entity::Column::DisputeId.eq(dispute_id))
+            .order_by_asc(dispute_attachment_entity::Column::CreatedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(Self::attachment_to_domain)
+            .collect())
+    }
+}
@@ -0,0 +1,32 @@
+mod api {
+    pub mod ledger_handler;
+    pub mod router;
+}
+
+pub mod app {
+    pub mod get_ledger_use_case;
+    pub mod record_payout_use_case;
+    pub mod record_settlement_use_case;
+    pub mod trader_deposit_use_case;
+}
+
+pub mod domain {
+    pub mod account;
+    pub mod journal_entry;
+    pub mod repository;
+}
+
+pub mod dto {
+    pub mod ledger_dto;
+}
+
+pub mod infra {
+    pub mod ledger_account_entity;
+    pub mod ledger_journal_entry_entity;
+    pub mod ledger_posting_entity;
+    pub mod ledger_repository;
+}
+
+pub use api::router::{protected_ledger_routes, LedgerApiDoc};
+pub use domain::repository::LedgerRepository;
+pub use infra::ledger_repository::PostgresLedgerRepository;
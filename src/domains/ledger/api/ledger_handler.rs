@@ -0,0 +1,182 @@
+use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError, jwt::Claims};
+use crate::domains::audit::domain::audit_entry::AuditContext;
+use crate::domains::ledger::dto::ledger_dto::{
+    AccountBalanceQuery, AccountBalanceResponse, CreatePayoutRequest, JournalEntryResponse,
+    LedgerAccountResponse, ListJournalEntriesQuery, ListLedgerAccountsQuery,
+};
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/ledger/account",
+    params(ListLedgerAccountsQuery),
+    responses(
+        (status = 200, description = "Page of ledger accounts, oldest first", body = inline(ApiResponse<Vec<LedgerAccountResponse>>)),
+        (status = 400, description = "Limit exceeds 100 or a filter is malformed"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Ledger",
+    summary = "List ledger accounts",
+    description = "Lists treasury, fee, merchant and trader accounts, filtered by kind, owner and currency. Requires `ledger:read`."
+)]
+pub async fn list_ledger_accounts(
+    Extension(state): Extension<Arc<AppState>>,
+    Query(params): Query<ListLedgerAccountsQuery>,
+) -> Result<Json<ApiResponse<Vec<LedgerAccountResponse>>>, AppError> {
+    let (limit, offset) = (params.limit, params.offset);
+    let accounts = state
+        .ledger_get_use_case
+        .list_accounts(params.into(), limit, offset)
+        .await?;
+
+    let response: Vec<LedgerAccountResponse> = accounts
+        .into_iter()
+        .map(LedgerAccountResponse::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/ledger/account/{id}/balance",
+    params(
+        ("id" = Uuid, Path, description = "Ledger account ID"),
+        AccountBalanceQuery
+    ),
+    responses(
+        (status = 200, description = "Balance at the requested time", body = inline(ApiResponse<AccountBalanceResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Ledger account not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Ledger",
+    summary = "Get account balance",
+    description = "Sums the account's postings effective at or before `as_of` (now by default). Requires `ledger:read`."
+)]
+pub async fn get_ledger_account_balance(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(account_id): Path<Uuid>,
+    Query(params): Query<AccountBalanceQuery>,
+) -> Result<Json<ApiResponse<AccountBalanceResponse>>, AppError> {
+    let balance = state
+        .ledger_get_use_case
+        .balance(account_id, params.as_of)
+        .await?;
+
+    Ok(Json(ApiResponse::success(AccountBalanceResponse::from(
+        balance,
+    ))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/ledger/entry",
+    params(ListJournalEntriesQuery),
+    responses(
+        (status = 200, description = "Page of journal entries, newest first", body = inline(ApiResponse<Vec<JournalEntryResponse>>)),
+        (status = 400, description = "Limit exceeds 100 or a filter is malformed"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Ledger",
+    summary = "List journal entries",
+    description = "Lists journal entries with their postings, filtered by account, kind and reference. Requires `ledger:read`."
+)]
+pub async fn list_journal_entries(
+    Extension(state): Extension<Arc<AppState>>,
+    Query(params): Query<ListJournalEntriesQuery>,
+) -> Result<Json<ApiResponse<Vec<JournalEntryResponse>>>, AppError> {
+    let (limit, offset) = (params.limit, params.offset);
+    let entries = state
+        .ledger_get_use_case
+        .list_entries(params.into(), limit, offset)
+        .await?;
+
+    let response: Vec<JournalEntryResponse> = entries
+        .into_iter()
+        .map(JournalEntryResponse::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/ledger/entry/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Journal entry ID")
+    ),
+    responses(
+        (status = 200, description = "Journal entry found", body = inline(ApiResponse<JournalEntryResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Journal entry not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Ledger",
+    summary = "Get journal entry",
+    description = "Fetches a journal entry with its postings. Requires `ledger:read`."
+)]
+pub async fn get_journal_entry(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(entry_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<JournalEntryResponse>>, AppError> {
+    let entry = state.ledger_get_use_case.entry(entry_id).await?;
+
+    Ok(Json(ApiResponse::success(JournalEntryResponse::from(
+        entry,
+    ))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/ledger/payout",
+    request_body = CreatePayoutRequest,
+    responses(
+        (status = 200, description = "Payout posted", body = inline(ApiResponse<JournalEntryResponse>)),
+        (status = 400, description = "Invalid amount or currency, or the merchant balance is too low"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Merchant not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Ledger",
+    summary = "Record payout",
+    description = "Posts a payout from the merchant's balance to the treasury, recording the approving user. Requires `payouts:approve`."
+)]
+pub async fn create_payout(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+    Json(request): Json<CreatePayoutRequest>,
+) -> Result<Json<ApiResponse<JournalEntryResponse>>, AppError> {
+    let entry = state
+        .ledger_payout_use_case
+        .execute(request, claims.user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(JournalEntryResponse::from(
+        entry,
+    ))))
+}
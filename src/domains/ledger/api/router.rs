@@ -0,0 +1,72 @@
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    common::{
+        jwt::SecurityAddon,
+        middleware::require_permission,
+        money::{Currency, Money},
+    },
+    domains::{
+        backoffice::role::permission::{LEDGER_READ, PAYOUTS_APPROVE},
+        ledger::{
+            domain::{account::AccountKind, journal_entry::EntryKind},
+            dto::ledger_dto::{
+                AccountBalanceResponse, CreatePayoutRequest, JournalEntryResponse,
+                LedgerAccountResponse, PostingResponse,
+            },
+        },
+    },
+};
+
+use utoipa::OpenApi;
+
+use super::ledger_handler;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::ledger_handler::list_ledger_accounts,
+        super::ledger_handler::get_ledger_account_balance,
+        super::ledger_handler::list_journal_entries,
+        super::ledger_handler::get_journal_entry,
+        super::ledger_handler::create_payout,
+    ),
+    components(schemas(
+        LedgerAccountResponse,
+        AccountBalanceResponse,
+        JournalEntryResponse,
+        PostingResponse,
+        CreatePayoutRequest,
+        AccountKind,
+        EntryKind,
+        Currency,
+        Money
+    )),
+    tags(
+        (name = "Ledger", description = "Double-entry ledger of merchant, trader, treasury and fee balances")
+    ),
+    modifiers(&SecurityAddon)
+)]
+pub struct LedgerApiDoc;
+
+pub fn protected_ledger_routes() -> Router {
+    let payout_routes = Router::new()
+        .route("/ledger/payout", post(ledger_handler::create_payout))
+        .route_layer(middleware::from_fn(require_permission(PAYOUTS_APPROVE)));
+
+    let read_routes = Router::new()
+        .route("/ledger/account", get(ledger_handler::list_ledger_accounts))
+        .route(
+            "/ledger/account/{id}/balance",
+            get(ledger_handler::get_ledger_account_balance),
+        )
+        .route("/ledger/entry", get(ledger_handler::list_journal_entries))
+        .route("/ledger/entry/{id}", get(ledger_handler::get_journal_entry))
+        .route_layer(middleware::from_fn(require_permission(LEDGER_READ)));
+
+    Router::new().merge(payout_routes).merge(read_routes)
+}
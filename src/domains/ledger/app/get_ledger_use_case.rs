@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    common::{error::AppError, money::Currency},
+    domains::ledger::domain::{
+        account::{AccountBalance, AccountKind, LedgerAccount, LedgerAccountFilter},
+        journal_entry::{JournalEntry, JournalEntryFilter},
+        repository::LedgerRepository,
+    },
+};
+
+pub struct GetLedgerUseCase {
+    ledger_repository: Arc<dyn LedgerRepository>,
+}
+
+impl GetLedgerUseCase {
+    pub fn new(ledger_repository: Arc<dyn LedgerRepository>) -> Self {
+        Self { ledger_repository }
+    }
+
+    pub async fn list_accounts(
+        &self,
+        filter: LedgerAccountFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LedgerAccount>, AppError> {
+        tracing::debug!("Listing ledger accounts with {:?}", filter);
+
+        validate_limit(limit)?;
+        self.ledger_repository
+            .list_accounts(filter, limit, offset)
+            .await
+    }
+
+    /// Balance including every entry effective at or before `as_of`; now when omitted
+    pub async fn balance(
+        &self,
+        account_id: Uuid,
+        as_of: Option<DateTime<Utc>>,
+    ) -> Result<AccountBalance, AppError> {
+        tracing::debug!("Fetching balance of ledger account {}", account_id);
+
+        let account = self
+            .ledger_repository
+            .find_account(account_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Ledger account {} not found",
+                account_id
+            )))?;
+
+        account_balance(
+            self.ledger_repository.as_ref(),
+            account,
+            as_of.unwrap_or_else(Utc::now),
+        )
+        .await
+    }
+
+    pub async fn list_entries(
+        &self,
+        filter: JournalEntryFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<JournalEntry>, AppError> {
+        tracing::debug!("Listing journal entries with {:?}", filter);
+
+        validate_limit(limit)?;
+        self.ledger_repository
+            .list_entries(filter, limit, offset)
+            .await
+    }
+
+    pub async fn entry(&self, entry_id: Uuid) -> Result<JournalEntry, AppError> {
+        tracing::debug!("Fetching journal entry {}", entry_id);
+
+        self.ledger_repository
+            .find_entry(entry_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Journal entry {} not found",
+                entry_id
+            )))
+    }
+}
+
+/// Finds the account for `kind`, `owner_id` and `currency`, opening it on first use
+pub(crate) async fn open_account(
+    ledger_repository: &dyn LedgerRepository,
+    kind: AccountKind,
+    owner_id: Option<Uuid>,
+    currency: Currency,
+) -> Result<LedgerAccount, AppError> {
+    if kind.has_owner() != owner_id.is_some() {
+        return Err(AppError::InternalError(format!(
+            "Ledger account kind {} {} an owner",
+            kind,
+            if kind.has_owner() {
+                "requires"
+            } else {
+                "cannot have"
+            }
+        )));
+    }
+
+    if let Some(account) = ledger_repository
+        .find_account_by_owner(kind, owner_id, currency)
+        .await?
+    {
+        return Ok(account);
+    }
+
+    match ledger_repository
+        .create_account(LedgerAccount::new(kind, owner_id, currency))
+        .await
+    {
+        Ok(account) => {
+            tracing::info!(
+                "Opened {} ledger account {} in {}",
+                kind,
+                account.id,
+                currency
+            );
+            Ok(account)
+        }
+        // A concurrent posting may have opened the same account first
+        Err(err) => ledger_repository
+            .find_account_by_owner(kind, owner_id, currency)
+            .await?
+            .ok_or(err),
+    }
+}
+
+pub(crate) async fn account_balance(
+    ledger_repository: &dyn LedgerRepository,
+    account: LedgerAccount,
+    as_of: DateTime<Utc>,
+) -> Result<AccountBalance, AppError> {
+    let posted = ledger_repository.posted_sum(account.id, as_of).await?;
+
+    Ok(AccountBalance {
+        balance: account.balance_from_postings(posted)?,
+        account,
+        as_of,
+    })
+}
+
+fn validate_limit(limit: i64) -> Result<(), AppError> {
+    if limit > 100 {
+        return Err(AppError::ValidationError(
+            "Limit cannot exceed 100".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::Money;
+    use crate::domains::ledger::domain::repository::MockLedgerRepository;
+
+    #[tokio::test]
+    async fn test_balance_as_of_uses_normal_side() {
+        let account =
+            LedgerAccount::new(AccountKind::Merchant, Some(Uuid::new_v4()), Currency::Usd);
+        let account_id = account.id;
+        let as_of = Utc::now() - chrono::Duration::days(1);
+
+        let mut repository = MockLedgerRepository::new();
+        repository
+            .expect_find_account()
+            .returning(move |_| Ok(Some(account.clone())));
+        repository
+            .expect_posted_sum()
+            .withf(move |id, at| *id == account_id && *at == as_of)
+            .returning(|_, _| Ok(-9850));
+
+        let balance = GetLedgerUseCase::new(Arc::new(repository))
+            .balance(account_id, Some(as_of))
+            .await
+            .unwrap();
+
+        assert_eq!(balance.balance, Money::new(9850, Currency::Usd));
+        assert_eq!(balance.as_of, as_of);
+    }
+
+    #[tokio::test]
+    async fn test_open_account_reuses_existing_account() {
+        let existing = LedgerAccount::new(AccountKind::Treasury, None, Currency::Eur);
+        let existing_id = existing.id;
+
+        let mut repository = MockLedgerRepository::new();
+        repository
+            .expect_find_account_by_owner()
+            .returning(move |_, _, _| Ok(Some(existing.clone())));
+        repository.expect_create_account().never();
+
+        let account = open_account(&repository, AccountKind::Treasury, None, Currency::Eur)
+            .await
+            .unwrap();
+        assert_eq!(account.id, existing_id);
+
+        let result = open_account(&repository, AccountKind::Merchant, None, Currency::Eur).await;
+        assert!(matches!(result, Err(AppError::InternalError(_))));
+    }
+}
@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::{error::AppError, money::Money},
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, JOURNAL_ENTRY_ENTITY},
+        backoffice::domain::repository::MerchantRepository,
+        ledger::{
+            app::get_ledger_use_case::open_account,
+            domain::{
+                account::AccountKind,
+                journal_entry::{EntryKind, JournalEntry},
+                repository::LedgerRepository,
+            },
+            dto::ledger_dto::CreatePayoutRequest,
+        },
+    },
+};
+
+pub struct RecordPayoutUseCase {
+    ledger_repository: Arc<dyn LedgerRepository>,
+    merchant_repository: Arc<dyn MerchantRepository>,
+}
+
+impl RecordPayoutUseCase {
+    pub fn new(
+        ledger_repository: Arc<dyn LedgerRepository>,
+        merchant_repository: Arc<dyn MerchantRepository>,
+    ) -> Self {
+        Self {
+            ledger_repository,
+            merchant_repository,
+        }
+    }
+
+    /// Pays out part of a merchant's balance: debit merchant, credit treasury.
+    /// The payout can never exceed the merchant's current balance in that currency;
+    /// the repository checks it while holding a lock on the merchant's account.
+    pub async fn execute(
+        &self,
+        request: CreatePayoutRequest,
+        approved_by: Uuid,
+        context: &AuditContext,
+    ) -> Result<JournalEntry, AppError> {
+        tracing::debug!(
+            "Recording payout of {} {} to merchant {}",
+            request.amount,
+            request.currency,
+            request.merchant_id
+        );
+
+        let merchant = self
+            .merchant_repository
+            .find_by_id(request.merchant_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Merchant {} not found",
+                request.merchant_id
+            )))?;
+
+        let amount = Money::parse(&request.amount, &request.currency)?;
+        if !amount.is_positive() {
+            return Err(AppError::ValidationError(
+                "Amount must be greater than zero".to_string(),
+            ));
+        }
+
+        let repository = self.ledger_repository.as_ref();
+        let merchant_account = open_account(
+            repository,
+            AccountKind::Merchant,
+            Some(merchant.id),
+            amount.currency(),
+        )
+        .await?;
+        let treasury =
+            open_account(repository, AccountKind::Treasury, None, amount.currency()).await?;
+
+        let description = request
+            .description
+            .map(|description| description.trim().to_string())
+            .filter(|description| !description.is_empty());
+
+        let entry = JournalEntry::transfer(
+            EntryKind::Payout,
+            &merchant_account,
+            &treasury,
+            amount,
+            Some(Uuid::new_v4()),
+            description,
+            Some(approved_by),
+        )?;
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Create,
+            JOURNAL_ENTRY_ENTITY,
+            entry.id,
+            None,
+            Some(&entry.audit_snapshot()),
+        );
+        let entry = self
+            .ledger_repository
+            .post_if_covered(vec![entry], merchant_account, Some(audit))
+            .await?
+            .pop()
+            .ok_or_else(|| AppError::InternalError("Payout was not posted".to_string()))?;
+
+        tracing::info!(
+            "Payout {} of {} to merchant {} approved by {}",
+            entry.id,
+            amount,
+            merchant.id,
+            approved_by
+        );
+
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::Currency;
+    use crate::domains::{
+        backoffice::domain::{merchant::Merchant, repository::MockMerchantRepository},
+        ledger::domain::repository::MockLedgerRepository,
+    };
+
+    fn request(amount: &str) -> CreatePayoutRequest {
+        CreatePayoutRequest {
+            merchant_id: Uuid::new_v4(),
+            amount: amount.to_string(),
+            currency: "USD".to_string(),
+            description: Some(" Weekly payout ".to_string()),
+        }
+    }
+
+    fn use_case(ledger: MockLedgerRepository) -> RecordPayoutUseCase {
+        let mut merchant_repository = MockMerchantRepository::new();
+        merchant_repository
+            .expect_find_by_id()
+            .returning(|_| Ok(Some(Merchant::new("Acme".to_string(), None))));
+
+        let mut ledger = ledger;
+        ledger
+            .expect_find_account_by_owner()
+            .returning(|_, _, _| Ok(None));
+        ledger.expect_create_account().returning(Ok);
+
+        RecordPayoutUseCase::new(Arc::new(ledger), Arc::new(merchant_repository))
+    }
+
+    #[tokio::test]
+    async fn test_payout_debits_merchant_balance() {
+        let approver = Uuid::new_v4();
+        let mut ledger = MockLedgerRepository::new();
+        ledger.expect_post().never();
+        ledger
+            .expect_post_if_covered()
+            .withf(|entries, account, audit| {
+                account.kind == AccountKind::Merchant
+                    && entries[0].postings[0].account_id == account.id
+                    && audit
+                        .as_ref()
+                        .is_some_and(|audit| audit.entity_id == entries[0].id)
+            })
+            .times(1)
+            .returning(|entries, _, _| Ok(entries));
+
+        let entry = use_case(ledger)
+            .execute(request("100"), approver, &AuditContext::default())
+            .await
+            .unwrap();
+
+        assert_eq!(entry.kind, EntryKind::Payout);
+        assert_eq!(entry.created_by, Some(approver));
+        assert_eq!(entry.description.as_deref(), Some("Weekly payout"));
+        assert_eq!(entry.postings[0].amount, Money::new(10_000, Currency::Usd));
+        assert!(entry.reference_id.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_payout_rejects_non_positive_amounts() {
+        for amount in ["0", "-5"] {
+            let mut ledger = MockLedgerRepository::new();
+            ledger.expect_post_if_covered().never();
+
+            let result = use_case(ledger)
+                .execute(request(amount), Uuid::new_v4(), &AuditContext::default())
+                .await;
+            assert!(matches!(result, Err(AppError::ValidationError(_))));
+        }
+    }
+}
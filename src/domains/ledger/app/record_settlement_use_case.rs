@@ -0,0 +1,301 @@
+use std::sync::Arc;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        ledger::{
+            app::get_ledger_use_case::open_account,
+            domain::{
+                account::AccountKind,
+                journal_entry::{EntryKind, JournalEntry},
+                repository::LedgerRepository,
+            },
+        },
+        payments::domain::payment_intent::{PaymentIntent, PaymentStatus},
+    },
+};
+
+/// Basis points in one whole
+const BPS_SCALE: u64 = 10_000;
+
+pub struct RecordSettlementUseCase {
+    ledger_repository: Arc<dyn LedgerRepository>,
+    fee_bps: u32,
+}
+
+impl RecordSettlementUseCase {
+    pub fn new(ledger_repository: Arc<dyn LedgerRepository>, fee_bps: u32) -> Self {
+        Self {
+            ledger_repository,
+            fee_bps: fee_bps.min(BPS_SCALE as u32),
+        }
+    }
+
+    /// Credits a paid payment to its merchant and charges the platform fee:
+    /// - settlement: debit the assigned trader, who collected the money, and credit the
+    ///   merchant with the full amount
+    /// - fee: debit merchant, credit fees with `fee_bps` of it, rounded up to the minor unit
+    ///
+    /// Settling the same payment again returns the entries posted the first time.
+    pub async fn execute(&self, intent: &PaymentIntent) -> Result<Vec<JournalEntry>, AppError> {
+        tracing::debug!("Settling payment {}", intent.id);
+
+        ensure_paid(intent)?;
+        if let Some(settlement) = self
+            .ledger_repository
+            .find_entry_by_reference(EntryKind::Settlement, intent.id)
+            .await?
+        {
+            let fee = self
+                .ledger_repository
+                .find_entry_by_reference(EntryKind::Fee, intent.id)
+                .await?;
+            return Ok(std::iter::once(settlement).chain(fee).collect());
+        }
+
+        let entries = self.entries(intent).await?;
+        let entries = self.ledger_repository.post(entries, None).await?;
+
+        tracing::info!("Payment {} settled", intent.id);
+
+        Ok(entries)
+    }
+
+    /// The entries `execute` posts, left to the caller to post together with the
+    /// payment's status change
+    pub async fn entries(&self, intent: &PaymentIntent) -> Result<Vec<JournalEntry>, AppError> {
+        ensure_paid(intent)?;
+
+        let assignment = intent.assignment.ok_or_else(|| {
+            AppError::ValidationError(format!(
+                "Payment {} has no trader to settle it against",
+                intent.id
+            ))
+        })?;
+
+        let repository = self.ledger_repository.as_ref();
+        let currency = intent.amount.currency();
+        let trader = open_account(
+            repository,
+            AccountKind::Trader,
+            Some(assignment.trader_id),
+            currency,
+        )
+        .await?;
+        let merchant = open_account(
+            repository,
+            AccountKind::Merchant,
+            Some(intent.merchant_id),
+            currency,
+        )
+        .await?;
+
+        let description = Some(format!("Payment for order '{}'", intent.external_order_id));
+        let mut entries = vec![JournalEntry::transfer(
+            EntryKind::Settlement,
+            &trader,
+            &merchant,
+            intent.amount,
+            Some(intent.id),
+            description.clone(),
+            None,
+        )?];
+
+        let shares = intent
+            .amount
+            .allocate(&[self.fee_bps as u64, BPS_SCALE - self.fee_bps as u64])?;
+        let fee = shares[0];
+        if fee.is_positive() {
+            let fees = open_account(repository, AccountKind::Fees, None, currency).await?;
+            entries.push(JournalEntry::transfer(
+                EntryKind::Fee,
+                &merchant,
+                &fees,
+                fee,
+                Some(intent.id),
+                description,
+                None,
+            )?);
+        }
+
+        tracing::debug!(
+            "Payment {} settles from trader {} to merchant {}: {} with {} fee",
+            intent.id,
+            assignment.trader_id,
+            intent.merchant_id,
+            intent.amount,
+            fee
+        );
+
+        Ok(entries)
+    }
+}
+
+fn ensure_paid(intent: &PaymentIntent) -> Result<(), AppError> {
+    if intent.status != PaymentStatus::Paid {
+        return Err(AppError::ValidationError(format!(
+            "Payment {} is {}; only paid payments are settled",
+            intent.id, intent.status
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::{Currency, Money};
+    use crate::domains::ledger::domain::{
+        account::LedgerAccount, repository::MockLedgerRepository,
+    };
+    use chrono::{Duration, Utc};
+    use std::{collections::HashMap, sync::Mutex};
+    use uuid::Uuid;
+
+    fn paid_intent(minor_units: i64) -> PaymentIntent {
+        let mut intent = PaymentIntent::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "order-1".to_string(),
+            Money::new(minor_units, Currency::Usd),
+            None,
+            None,
+            Duration::minutes(30),
+        );
+        intent
+            .assign(Uuid::new_v4(), Uuid::new_v4(), Utc::now())
+            .unwrap();
+        intent.transition_to(PaymentStatus::Paid).unwrap();
+        intent
+    }
+
+    fn repository() -> MockLedgerRepository {
+        let mut repository = MockLedgerRepository::new();
+        repository
+            .expect_find_entry_by_reference()
+            .returning(|_, _| Ok(None));
+        repository
+            .expect_find_account_by_owner()
+            .returning(|_, _, _| Ok(None));
+        repository.expect_create_account().returning(Ok);
+        repository
+    }
+
+    #[tokio::test]
+    async fn test_settlement_posts_amount_and_fee() {
+        let intent = paid_intent(10_001);
+        let owners = Arc::new(Mutex::new(HashMap::new()));
+        let created = Arc::clone(&owners);
+        let mut repository = MockLedgerRepository::new();
+        repository
+            .expect_find_entry_by_reference()
+            .returning(|_, _| Ok(None));
+        repository
+            .expect_find_account_by_owner()
+            .returning(|_, _, _| Ok(None));
+        repository
+            .expect_create_account()
+            .returning(move |account: LedgerAccount| {
+                created
+                    .lock()
+                    .unwrap()
+                    .insert(account.id, (account.kind, account.owner_id));
+                Ok(account)
+            });
+        repository
+            .expect_post()
+            .withf(|_, audit| audit.is_none())
+            .times(1)
+            .returning(|entries, _| Ok(entries));
+
+        let entries = RecordSettlementUseCase::new(Arc::new(repository), 150)
+            .execute(&intent)
+            .await
+            .unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].kind, EntryKind::Settlement);
+        assert_eq!(entries[0].postings[0].amount.minor_units(), 10_001);
+        assert_eq!(entries[1].kind, EntryKind::Fee);
+        assert_eq!(entries[1].postings[0].amount.minor_units(), 151);
+        assert!(entries
+            .iter()
+            .all(|entry| entry.reference_id == Some(intent.id)));
+        // The trader's account is debited, the merchant's credited and then charged the fee
+        let owners = |account_id| owners.lock().unwrap()[&account_id];
+        assert_eq!(
+            owners(entries[0].postings[0].account_id),
+            (AccountKind::Trader, intent.assignment.map(|a| a.trader_id))
+        );
+        assert_eq!(
+            owners(entries[0].postings[1].account_id),
+            (AccountKind::Merchant, Some(intent.merchant_id))
+        );
+        assert_eq!(
+            entries[0].postings[1].account_id,
+            entries[1].postings[0].account_id
+        );
+    }
+
+    #[tokio::test]
+    async fn test_settlement_without_fee_and_unpaid_payment() {
+        let mut repository = repository();
+        repository
+            .expect_post()
+            .withf(|_, audit| audit.is_none())
+            .times(1)
+            .returning(|entries, _| Ok(entries));
+        let use_case = RecordSettlementUseCase::new(Arc::new(repository), 0);
+
+        let entries = use_case.execute(&paid_intent(500)).await.unwrap();
+        assert_eq!(entries.len(), 1);
+
+        let mut unpaid = paid_intent(500);
+        unpaid.status = PaymentStatus::Pending;
+        let result = use_case.execute(&unpaid).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_settling_twice_returns_existing_entries() {
+        let intent = paid_intent(500);
+        let trader = LedgerAccount::new(
+            AccountKind::Trader,
+            intent.assignment.map(|a| a.trader_id),
+            Currency::Usd,
+        );
+        let merchant = LedgerAccount::new(
+            AccountKind::Merchant,
+            Some(intent.merchant_id),
+            Currency::Usd,
+        );
+        let existing = JournalEntry::transfer(
+            EntryKind::Settlement,
+            &trader,
+            &merchant,
+            intent.amount,
+            Some(intent.id),
+            None,
+            None,
+        )
+        .unwrap();
+        let existing_id = existing.id;
+
+        let mut repository = MockLedgerRepository::new();
+        repository
+            .expect_find_entry_by_reference()
+            .returning(move |kind, _| {
+                Ok(Some(existing.clone()).filter(|_| kind == EntryKind::Settlement))
+            });
+        repository.expect_post().never();
+
+        let entries = RecordSettlementUseCase::new(Arc::new(repository), 150)
+            .execute(&intent)
+            .await
+            .unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, existing_id);
+    }
+}
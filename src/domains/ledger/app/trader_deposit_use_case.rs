@@ -0,0 +1,236 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    common::{error::AppError, money::Money},
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, JOURNAL_ENTRY_ENTITY},
+        ledger::{
+            app::get_ledger_use_case::{account_balance, open_account},
+            domain::{
+                account::{AccountBalance, AccountKind, LedgerAccountFilter},
+                journal_entry::{EntryKind, JournalEntry},
+                repository::LedgerRepository,
+            },
+            dto::ledger_dto::CreateDepositRequest,
+        },
+        traders::domain::repository::TraderRepository,
+    },
+};
+
+/// Currencies a single trader can hold deposits in; one trader account each
+const MAX_DEPOSIT_ACCOUNTS: i64 = 100;
+
+pub struct TraderDepositUseCase {
+    ledger_repository: Arc<dyn LedgerRepository>,
+    trader_repository: Arc<dyn TraderRepository>,
+}
+
+impl TraderDepositUseCase {
+    pub fn new(
+        ledger_repository: Arc<dyn LedgerRepository>,
+        trader_repository: Arc<dyn TraderRepository>,
+    ) -> Self {
+        Self {
+            ledger_repository,
+            trader_repository,
+        }
+    }
+
+    /// Records a security deposit received from a trader: debit treasury, credit trader
+    pub async fn record(
+        &self,
+        trader_id: Uuid,
+        request: CreateDepositRequest,
+        recorded_by: Uuid,
+        context: &AuditContext,
+    ) -> Result<JournalEntry, AppError> {
+        tracing::debug!(
+            "Recording deposit of {} {} from trader {}",
+            request.amount,
+            request.currency,
+            trader_id
+        );
+
+        self.ensure_trader_exists(trader_id).await?;
+
+        let amount = Money::parse(&request.amount, &request.currency)?;
+        if !amount.is_positive() {
+            return Err(AppError::ValidationError(
+                "Amount must be greater than zero".to_string(),
+            ));
+        }
+
+        let repository = self.ledger_repository.as_ref();
+        let treasury =
+            open_account(repository, AccountKind::Treasury, None, amount.currency()).await?;
+        let trader_account = open_account(
+            repository,
+            AccountKind::Trader,
+            Some(trader_id),
+            amount.currency(),
+        )
+        .await?;
+
+        let description = request
+            .description
+            .map(|description| description.trim().to_string())
+            .filter(|description| !description.is_empty());
+
+        let entry = JournalEntry::transfer(
+            EntryKind::Deposit,
+            &treasury,
+            &trader_account,
+            amount,
+            Some(Uuid::new_v4()),
+            description,
+            Some(recorded_by),
+        )?;
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Create,
+            JOURNAL_ENTRY_ENTITY,
+            entry.id,
+            None,
+            Some(&entry.audit_snapshot()),
+        );
+        let entry = self
+            .ledger_repository
+            .post(vec![entry], Some(audit))
+            .await?
+            .pop()
+            .ok_or_else(|| AppError::InternalError("Deposit was not posted".to_string()))?;
+
+        tracing::info!(
+            "Deposit of {} from trader {} recorded by {}",
+            amount,
+            trader_id,
+            recorded_by
+        );
+
+        Ok(entry)
+    }
+
+    /// Current deposit balance of the trader in every currency it has deposited
+    pub async fn balances(&self, trader_id: Uuid) -> Result<Vec<AccountBalance>, AppError> {
+        tracing::debug!("Fetching deposit balances of trader {}", trader_id);
+
+        self.ensure_trader_exists(trader_id).await?;
+
+        let filter = LedgerAccountFilter {
+            kind: Some(AccountKind::Trader),
+            owner_id: Some(trader_id),
+            currency: None,
+        };
+        let accounts = self
+            .ledger_repository
+            .list_accounts(filter, MAX_DEPOSIT_ACCOUNTS, 0)
+            .await?;
+
+        let now = Utc::now();
+        let mut balances = Vec::with_capacity(accounts.len());
+        for account in accounts {
+            balances.push(account_balance(self.ledger_repository.as_ref(), account, now).await?);
+        }
+
+        Ok(balances)
+    }
+
+    async fn ensure_trader_exists(&self, trader_id: Uuid) -> Result<(), AppError> {
+        self.trader_repository
+            .find_by_id(trader_id)
+            .await?
+            .map(|_| ())
+            .ok_or(AppError::NotFound(format!(
+                "Trader {} not found",
+                trader_id
+            )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::Currency;
+    use crate::domains::{
+        ledger::domain::repository::MockLedgerRepository,
+        traders::domain::{repository::MockTraderRepository, trader::Trader},
+    };
+
+    fn trader_repository() -> MockTraderRepository {
+        let mut trader_repository = MockTraderRepository::new();
+        trader_repository
+            .expect_find_by_id()
+            .returning(|_| Ok(Some(Trader::new("Alice".to_string(), None))));
+        trader_repository
+    }
+
+    #[tokio::test]
+    async fn test_deposit_credits_trader_account() {
+        let trader_id = Uuid::new_v4();
+        let recorded_by = Uuid::new_v4();
+
+        let mut ledger_repository = MockLedgerRepository::new();
+        ledger_repository
+            .expect_find_account_by_owner()
+            .returning(|_, _, _| Ok(None));
+        ledger_repository.expect_create_account().returning(Ok);
+        ledger_repository
+            .expect_post()
+            .withf(|entries, audit| {
+                audit.as_ref().is_some_and(|audit| {
+                    audit.entity_type == JOURNAL_ENTRY_ENTITY && audit.entity_id == entries[0].id
+                })
+            })
+            .times(1)
+            .returning(|entries, _| Ok(entries));
+
+        let entry =
+            TraderDepositUseCase::new(Arc::new(ledger_repository), Arc::new(trader_repository()))
+                .record(
+                    trader_id,
+                    CreateDepositRequest {
+                        amount: "1000.00".to_string(),
+                        currency: "USD".to_string(),
+                        description: Some(" ".to_string()),
+                    },
+                    recorded_by,
+                    &AuditContext::default(),
+                )
+                .await
+                .unwrap();
+
+        assert_eq!(entry.kind, EntryKind::Deposit);
+        assert_eq!(entry.created_by, Some(recorded_by));
+        assert_eq!(entry.description, None);
+        // Treasury is debited, the trader's liability account credited
+        assert_eq!(
+            entry.postings[1].amount,
+            Money::new(-100_000, Currency::Usd)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deposit_must_be_positive() {
+        let mut ledger_repository = MockLedgerRepository::new();
+        ledger_repository.expect_post().never();
+
+        let result =
+            TraderDepositUseCase::new(Arc::new(ledger_repository), Arc::new(trader_repository()))
+                .record(
+                    Uuid::new_v4(),
+                    CreateDepositRequest {
+                        amount: "0".to_string(),
+                        currency: "USD".to_string(),
+                        description: None,
+                    },
+                    Uuid::new_v4(),
+                    &AuditContext::default(),
+                )
+                .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,234 @@
+use super::journal_entry::JournalEntry;
+use crate::common::{
+    error::AppError,
+    money::{Currency, Money},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Ledger account holding one currency.
+/// System accounts (treasury, fees) have no owner; merchant and trader accounts do.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LedgerAccount {
+    pub id: Uuid,
+    pub kind: AccountKind,
+    pub owner_id: Option<Uuid>,
+    pub currency: Currency,
+    pub created_at: DateTime<Utc>,
+}
+
+impl LedgerAccount {
+    pub fn new(kind: AccountKind, owner_id: Option<Uuid>, currency: Currency) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            kind,
+            owner_id,
+            currency,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Turns the signed sum of postings (debits positive) into the balance
+    /// as Finance reads it: positive on the account's normal side.
+    pub fn balance_from_postings(&self, posted: i64) -> Result<Money, AppError> {
+        let posted = Money::new(posted, self.currency);
+
+        match self.kind.normal_balance() {
+            NormalBalance::Debit => Ok(posted),
+            NormalBalance::Credit => posted.checked_neg(),
+        }
+    }
+
+    /// Fails unless the balance, `posted` so far, stays at or above zero once
+    /// `entries` are posted too
+    pub fn ensure_covers(&self, posted: i64, entries: &[JournalEntry]) -> Result<(), AppError> {
+        let mut after = Money::new(posted, self.currency);
+        for posting in entries
+            .iter()
+            .flat_map(|entry| entry.postings.iter())
+            .filter(|posting| posting.account_id == self.id)
+        {
+            after = after.checked_add(posting.amount)?;
+        }
+
+        if self
+            .balance_from_postings(after.minor_units())?
+            .is_negative()
+        {
+            return Err(AppError::ValidationError(format!(
+                "Insufficient balance: {} available",
+                self.balance_from_postings(posted)?
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Balance of an account at a point in time
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountBalance {
+    pub account: LedgerAccount,
+    pub balance: Money,
+    pub as_of: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountKind {
+    /// Funds the platform holds; an asset
+    Treasury,
+    /// Fees earned on settled payments; revenue
+    Fees,
+    /// What the platform owes a merchant; a liability
+    Merchant,
+    /// What the platform owes a P2P trader; a liability
+    Trader,
+}
+
+/// Side on which an account's balance grows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormalBalance {
+    Debit,
+    Credit,
+}
+
+impl AccountKind {
+    /// Value stored in the `kind` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountKind::Treasury => "treasury",
+            AccountKind::Fees => "fees",
+            AccountKind::Merchant => "merchant",
+            AccountKind::Trader => "trader",
+        }
+    }
+
+    pub fn normal_balance(&self) -> NormalBalance {
+        match self {
+            AccountKind::Treasury => NormalBalance::Debit,
+            AccountKind::Fees | AccountKind::Merchant | AccountKind::Trader => {
+                NormalBalance::Credit
+            }
+        }
+    }
+
+    /// Merchant and trader accounts belong to the merchant or trader in `owner_id`
+    pub fn has_owner(&self) -> bool {
+        matches!(self, AccountKind::Merchant | AccountKind::Trader)
+    }
+}
+
+impl fmt::Display for AccountKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AccountKind {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "treasury" => Ok(AccountKind::Treasury),
+            "fees" => Ok(AccountKind::Fees),
+            "merchant" => Ok(AccountKind::Merchant),
+            "trader" => Ok(AccountKind::Trader),
+            other => Err(AppError::InternalError(format!(
+                "Unknown ledger account kind '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Account search criteria; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LedgerAccountFilter {
+    pub kind: Option<AccountKind>,
+    pub owner_id: Option<Uuid>,
+    pub currency: Option<Currency>,
+}
+
+impl LedgerAccountFilter {
+    pub fn matches(&self, account: &LedgerAccount) -> bool {
+        self.kind.is_none_or(|kind| account.kind == kind)
+            && self
+                .owner_id
+                .is_none_or(|owner_id| account.owner_id == Some(owner_id))
+            && self
+                .currency
+                .is_none_or(|currency| account.currency == currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::ledger::domain::journal_entry::EntryKind;
+
+    #[test]
+    fn test_balance_follows_normal_side() {
+        let treasury = LedgerAccount::new(AccountKind::Treasury, None, Currency::Usd);
+        let merchant =
+            LedgerAccount::new(AccountKind::Merchant, Some(Uuid::new_v4()), Currency::Usd);
+
+        assert_eq!(
+            treasury.balance_from_postings(500).unwrap(),
+            Money::new(500, Currency::Usd)
+        );
+        assert_eq!(
+            merchant.balance_from_postings(-500).unwrap(),
+            Money::new(500, Currency::Usd)
+        );
+    }
+
+    #[test]
+    fn test_ensure_covers_checks_balance_after_entries() {
+        let treasury = LedgerAccount::new(AccountKind::Treasury, None, Currency::Usd);
+        let merchant =
+            LedgerAccount::new(AccountKind::Merchant, Some(Uuid::new_v4()), Currency::Usd);
+        let payout = |minor_units| {
+            JournalEntry::transfer(
+                EntryKind::Payout,
+                &merchant,
+                &treasury,
+                Money::new(minor_units, Currency::Usd),
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+        };
+
+        // Balance of 100.00 USD
+        assert!(merchant.ensure_covers(-10_000, &[payout(10_000)]).is_ok());
+        // Only postings to the account itself count
+        let other = LedgerAccount::new(AccountKind::Merchant, Some(Uuid::new_v4()), Currency::Usd);
+        assert!(other.ensure_covers(0, &[payout(10_000)]).is_ok());
+
+        let error = merchant
+            .ensure_covers(-10_000, &[payout(6_000), payout(4_001)])
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Validation error: Insufficient balance: 100.00 USD available"
+        );
+    }
+
+    #[test]
+    fn test_kind_round_trips_through_storage_value() {
+        for kind in [
+            AccountKind::Treasury,
+            AccountKind::Fees,
+            AccountKind::Merchant,
+            AccountKind::Trader,
+        ] {
+            assert_eq!(kind.as_str().parse::<AccountKind>().unwrap(), kind);
+        }
+        assert!("cash".parse::<AccountKind>().is_err());
+    }
+}
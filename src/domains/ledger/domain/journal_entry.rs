@@ -0,0 +1,331 @@
+use crate::common::{
+    error::AppError,
+    money::{Currency, Money},
+};
+use crate::domains::ledger::domain::account::LedgerAccount;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{collections::HashMap, fmt, str::FromStr};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Balanced set of postings recorded together. Entries are never changed or
+/// deleted; mistakes are corrected with a new `adjustment` entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JournalEntry {
+    pub id: Uuid,
+    pub kind: EntryKind,
+    /// Payment, payout or dispute the entry was posted for
+    pub reference_id: Option<Uuid>,
+    pub description: Option<String>,
+    /// Backoffice user who posted the entry; `None` for automatic postings
+    pub created_by: Option<Uuid>,
+    /// Point in time the entry counts towards balances
+    pub effective_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub postings: Vec<Posting>,
+}
+
+impl JournalEntry {
+    /// Builds an entry from its postings.
+    /// Fails unless there are at least two postings and they sum to zero in every currency.
+    pub fn new(
+        kind: EntryKind,
+        reference_id: Option<Uuid>,
+        description: Option<String>,
+        created_by: Option<Uuid>,
+        postings: Vec<Posting>,
+    ) -> Result<Self, AppError> {
+        let now = Utc::now();
+        let id = Uuid::new_v4();
+
+        let entry = Self {
+            id,
+            kind,
+            reference_id,
+            description,
+            created_by,
+            effective_at: now,
+            created_at: now,
+            postings: postings
+                .into_iter()
+                .map(|posting| Posting {
+                    entry_id: id,
+                    ..posting
+                })
+                .collect(),
+        };
+        entry.ensure_balanced()?;
+
+        Ok(entry)
+    }
+
+    /// Moves `amount` from `credit` to `debit` in a single two-posting entry
+    pub fn transfer(
+        kind: EntryKind,
+        debit: &LedgerAccount,
+        credit: &LedgerAccount,
+        amount: Money,
+        reference_id: Option<Uuid>,
+        description: Option<String>,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, AppError> {
+        Self::new(
+            kind,
+            reference_id,
+            description,
+            created_by,
+            vec![
+                Posting::debit(debit, amount)?,
+                Posting::credit(credit, amount)?,
+            ],
+        )
+    }
+
+    /// Fields recorded in the audit entry of entries posted from the backoffice
+    pub fn audit_snapshot(&self) -> Value {
+        let postings: Vec<Value> = self
+            .postings
+            .iter()
+            .map(|posting| {
+                json!({
+                    "account_id": posting.account_id,
+                    "amount": posting.amount.to_string(),
+                })
+            })
+            .collect();
+
+        json!({
+            "kind": self.kind,
+            "reference_id": self.reference_id,
+            "description": self.description,
+            "postings": postings,
+        })
+    }
+
+    /// Double-entry invariant: postings sum to zero per currency
+    pub fn ensure_balanced(&self) -> Result<(), AppError> {
+        if self.postings.len() < 2 {
+            return Err(AppError::ValidationError(
+                "Journal entry needs at least two postings".to_string(),
+            ));
+        }
+
+        let mut totals: HashMap<Currency, Money> = HashMap::new();
+        for posting in &self.postings {
+            if posting.amount.is_zero() {
+                return Err(AppError::ValidationError(
+                    "Journal entry postings cannot be zero".to_string(),
+                ));
+            }
+
+            let currency = posting.amount.currency();
+            let total = totals
+                .get(&currency)
+                .copied()
+                .unwrap_or_else(|| Money::zero(currency));
+            totals.insert(currency, total.checked_add(posting.amount)?);
+        }
+
+        if let Some(total) = totals.values().find(|total| !total.is_zero()) {
+            return Err(AppError::ValidationError(format!(
+                "Journal entry does not balance: off by {}",
+                total
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// One side of a journal entry. Positive amounts debit the account, negative amounts credit it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Posting {
+    pub id: Uuid,
+    pub entry_id: Uuid,
+    pub account_id: Uuid,
+    pub amount: Money,
+}
+
+impl Posting {
+    pub fn debit(account: &LedgerAccount, amount: Money) -> Result<Self, AppError> {
+        Self::to_account(account, amount)
+    }
+
+    pub fn credit(account: &LedgerAccount, amount: Money) -> Result<Self, AppError> {
+        Self::to_account(account, amount.checked_neg()?)
+    }
+
+    fn to_account(account: &LedgerAccount, amount: Money) -> Result<Self, AppError> {
+        if amount.currency() != account.currency {
+            return Err(AppError::ValidationError(format!(
+                "Cannot post {} to a {} account",
+                amount.currency(),
+                account.currency
+            )));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            entry_id: Uuid::nil(),
+            account_id: account.id,
+            amount,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    /// Paid payment credited to the merchant
+    Settlement,
+    /// Platform fee charged on a settlement
+    Fee,
+    /// Merchant balance paid out
+    Payout,
+    /// Security deposit paid in by a trader
+    Deposit,
+    /// Manual correction or dispute resolution
+    Adjustment,
+}
+
+impl EntryKind {
+    /// Value stored in the `kind` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryKind::Settlement => "settlement",
+            EntryKind::Fee => "fee",
+            EntryKind::Payout => "payout",
+            EntryKind::Deposit => "deposit",
+            EntryKind::Adjustment => "adjustment",
+        }
+    }
+}
+
+impl fmt::Display for EntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EntryKind {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "settlement" => Ok(EntryKind::Settlement),
+            "fee" => Ok(EntryKind::Fee),
+            "payout" => Ok(EntryKind::Payout),
+            "deposit" => Ok(EntryKind::Deposit),
+            "adjustment" => Ok(EntryKind::Adjustment),
+            other => Err(AppError::InternalError(format!(
+                "Unknown journal entry kind '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Journal search criteria; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JournalEntryFilter {
+    /// Entries with at least one posting to this account
+    pub account_id: Option<Uuid>,
+    pub kind: Option<EntryKind>,
+    pub reference_id: Option<Uuid>,
+}
+
+impl JournalEntryFilter {
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        self.account_id.is_none_or(|account_id| {
+            entry
+                .postings
+                .iter()
+                .any(|posting| posting.account_id == account_id)
+        }) && self.kind.is_none_or(|kind| entry.kind == kind)
+            && self
+                .reference_id
+                .is_none_or(|reference_id| entry.reference_id == Some(reference_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::ledger::domain::account::AccountKind;
+
+    fn account(kind: AccountKind, currency: Currency) -> LedgerAccount {
+        LedgerAccount::new(kind, None, currency)
+    }
+
+    #[test]
+    fn test_transfer_balances_and_links_postings() {
+        let treasury = account(AccountKind::Treasury, Currency::Usd);
+        let merchant = account(AccountKind::Merchant, Currency::Usd);
+
+        let entry = JournalEntry::transfer(
+            EntryKind::Settlement,
+            &treasury,
+            &merchant,
+            Money::new(1000, Currency::Usd),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(entry.postings.len(), 2);
+        assert!(entry.postings.iter().all(|p| p.entry_id == entry.id));
+        assert_eq!(entry.postings[0].amount.minor_units(), 1000);
+        assert_eq!(entry.postings[1].amount.minor_units(), -1000);
+    }
+
+    #[test]
+    fn test_rejects_unbalanced_entries() {
+        let usd = account(AccountKind::Treasury, Currency::Usd);
+        let eur = account(AccountKind::Merchant, Currency::Eur);
+        let usd_merchant = account(AccountKind::Merchant, Currency::Usd);
+
+        let lopsided = JournalEntry::new(
+            EntryKind::Adjustment,
+            None,
+            None,
+            None,
+            vec![
+                Posting::debit(&usd, Money::new(1000, Currency::Usd)).unwrap(),
+                Posting::credit(&usd_merchant, Money::new(999, Currency::Usd)).unwrap(),
+            ],
+        );
+        assert!(matches!(lopsided, Err(AppError::ValidationError(_))));
+
+        // Sums to zero overall, but not within each currency
+        let mixed = JournalEntry::new(
+            EntryKind::Adjustment,
+            None,
+            None,
+            None,
+            vec![
+                Posting::debit(&usd, Money::new(1000, Currency::Usd)).unwrap(),
+                Posting::credit(&eur, Money::new(1000, Currency::Eur)).unwrap(),
+            ],
+        );
+        assert!(mixed.is_err());
+
+        let single = JournalEntry::new(
+            EntryKind::Adjustment,
+            None,
+            None,
+            None,
+            vec![Posting::debit(&usd, Money::zero(Currency::Usd)).unwrap()],
+        );
+        assert!(single.is_err());
+    }
+
+    #[test]
+    fn test_posting_currency_must_match_account() {
+        let eur = account(AccountKind::Merchant, Currency::Eur);
+
+        assert!(Posting::debit(&eur, Money::new(100, Currency::Usd)).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use super::{
+    account::{AccountKind, LedgerAccount, LedgerAccountFilter},
+    journal_entry::{EntryKind, JournalEntry, JournalEntryFilter},
+};
+use crate::common::{error::AppError, money::Currency};
+use crate::domains::audit::domain::audit_entry::AuditEntry;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Append-only storage for accounts and journal entries
+#[async_trait]
+pub trait LedgerRepository: Send + Sync {
+    async fn find_account(&self, id: Uuid) -> Result<Option<LedgerAccount>, AppError>;
+
+    /// `owner_id` is `None` for the treasury and fees accounts
+    async fn find_account_by_owner(
+        &self,
+        kind: AccountKind,
+        owner_id: Option<Uuid>,
+        currency: Currency,
+    ) -> Result<Option<LedgerAccount>, AppError>;
+
+    async fn create_account(&self, account: LedgerAccount) -> Result<LedgerAccount, AppError>;
+
+    async fn list_accounts(
+        &self,
+        filter: LedgerAccountFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LedgerAccount>, AppError>;
+
+    /// Stores all entries with their postings atomically, together with `audit` for
+    /// entries posted from the backoffice
+    async fn post(
+        &self,
+        entries: Vec<JournalEntry>,
+        audit: Option<AuditEntry>,
+    ) -> Result<Vec<JournalEntry>, AppError>;
+
+    /// Like `post`, but fails with a validation error if the entries would take the
+    /// current balance of `account` below zero. The check and the postings share one
+    /// transaction holding a lock on the account, so concurrent calls cannot overdraw it.
+    async fn post_if_covered(
+        &self,
+        entries: Vec<JournalEntry>,
+        account: LedgerAccount,
+        audit: Option<AuditEntry>,
+    ) -> Result<Vec<JournalEntry>, AppError>;
+
+    async fn find_entry(&self, id: Uuid) -> Result<Option<JournalEntry>, AppError>;
+
+    async fn find_entry_by_reference(
+        &self,
+        kind: EntryKind,
+        reference_id: Uuid,
+    ) -> Result<Option<JournalEntry>, AppError>;
+
+    /// Newest effective entries first
+    async fn list_entries(
+        &self,
+        filter: JournalEntryFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<JournalEntry>, AppError>;
+
+    /// Signed sum of the account's postings (debits positive) effective at or before `as_of`
+    async fn posted_sum(&self, account_id: Uuid, as_of: DateTime<Utc>) -> Result<i64, AppError>;
+}
+
+#[cfg(test)]
+use mockall::mock;
+
+#[cfg(test)]
+mock! {
+    pub LedgerRepository {}
+
+    #[async_trait]
+    impl LedgerRepository for LedgerRepository {
+        async fn find_account(&self, id: Uuid) -> Result<Option<LedgerAccount>, AppError>;
+        async fn find_account_by_owner(
+            &self,
+            kind: AccountKind,
+            owner_id: Option<Uuid>,
+            currency: Currency,
+        ) -> Result<Option<LedgerAccount>, AppError>;
+        async fn create_account(&self, account: LedgerAccount) -> Result<LedgerAccount, AppError>;
+        async fn list_accounts(
+            &self,
+            filter: LedgerAccountFilter,
+            limit: i64,
+            offset: i64,
+        ) -> Result<Vec<LedgerAccount>, AppError>;
+        async fn post(
+            &self,
+            entries: Vec<JournalEntry>,
+            audit: Option<AuditEntry>,
+        ) -> Result<Vec<JournalEntry>, AppError>;
+        async fn post_if_covered(
+            &self,
+            entries: Vec<JournalEntry>,
+            account: LedgerAccount,
+            audit: Option<AuditEntry>,
+        ) -> Result<Vec<JournalEntry>, AppError>;
+        async fn find_entry(&self, id: Uuid) -> Result<Option<JournalEntry>, AppError>;
+        async fn find_entry_by_reference(
+            &self,
+            kind: EntryKind,
+            reference_id: Uuid,
+        ) -> Result<Option<JournalEntry>, AppError>;
+        async fn list_entries(
+            &self,
+            filter: JournalEntryFilter,
+            limit: i64,
+            offset: i64,
+        ) -> Result<Vec<JournalEntry>, AppError>;
+        async fn posted_sum(&self, account_id: Uuid, as_of: DateTime<Utc>) -> Result<i64, AppError>;
+    }
+}
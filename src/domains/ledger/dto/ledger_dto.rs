@@ -0,0 +1,226 @@
+use crate::common::money::{Currency, Money};
+use crate::domains::ledger::domain::{
+    account::{AccountBalance, AccountKind, LedgerAccount, LedgerAccountFilter},
+    journal_entry::{EntryKind, JournalEntry, JournalEntryFilter, Posting},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LedgerAccountResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    pub kind: AccountKind,
+
+    /// Merchant or trader the account belongs to; empty for treasury and fees
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub owner_id: Option<String>,
+
+    pub currency: Currency,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<LedgerAccount> for LedgerAccountResponse {
+    fn from(account: LedgerAccount) -> Self {
+        Self {
+            id: account.id.to_string(),
+            kind: account.kind,
+            owner_id: account.owner_id.map(|id| id.to_string()),
+            currency: account.currency,
+            created_at: account.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AccountBalanceResponse {
+    pub account: LedgerAccountResponse,
+
+    /// Balance on the account's normal side: what a merchant or trader is owed,
+    /// fees earned, funds the treasury holds
+    pub balance: Money,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub as_of: DateTime<Utc>,
+}
+
+impl From<AccountBalance> for AccountBalanceResponse {
+    fn from(balance: AccountBalance) -> Self {
+        Self {
+            account: LedgerAccountResponse::from(balance.account),
+            balance: balance.balance,
+            as_of: balance.as_of,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PostingResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub account_id: String,
+
+    /// Positive amounts debit the account, negative amounts credit it
+    #[serde(flatten)]
+    pub amount: Money,
+}
+
+impl From<Posting> for PostingResponse {
+    fn from(posting: Posting) -> Self {
+        Self {
+            id: posting.id.to_string(),
+            account_id: posting.account_id.to_string(),
+            amount: posting.amount,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct JournalEntryResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    pub kind: EntryKind,
+
+    /// Payment for settlements and fees, payout ID for payouts
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub reference_id: Option<String>,
+
+    #[schema(example = "Payment for order 'order-10042'")]
+    pub description: Option<String>,
+
+    /// Backoffice user who posted the entry; empty for automatic postings
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub created_by: Option<String>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub effective_at: DateTime<Utc>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    pub postings: Vec<PostingResponse>,
+}
+
+impl From<JournalEntry> for JournalEntryResponse {
+    fn from(entry: JournalEntry) -> Self {
+        Self {
+            id: entry.id.to_string(),
+            kind: entry.kind,
+            reference_id: entry.reference_id.map(|id| id.to_string()),
+            description: entry.description,
+            created_by: entry.created_by.map(|id| id.to_string()),
+            effective_at: entry.effective_at,
+            created_at: entry.created_at,
+            postings: entry
+                .postings
+                .into_iter()
+                .map(PostingResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CreatePayoutRequest {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub merchant_id: Uuid,
+
+    /// Decimal amount, at most the merchant's balance in `currency`
+    #[schema(example = "250.00")]
+    pub amount: String,
+
+    /// ISO 4217 currency code
+    #[schema(example = "USD")]
+    pub currency: String,
+
+    #[schema(example = "Weekly payout")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CreateDepositRequest {
+    /// Decimal amount received from the trader
+    #[schema(example = "1000.00")]
+    pub amount: String,
+
+    /// ISO 4217 currency code
+    #[schema(example = "USD")]
+    pub currency: String,
+
+    #[schema(example = "Initial security deposit")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListLedgerAccountsQuery {
+    /// Page size, at most 100
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    #[param(value_type = Option<String>, example = "merchant")]
+    pub kind: Option<AccountKind>,
+    pub owner_id: Option<Uuid>,
+    #[param(value_type = Option<String>, example = "USD")]
+    pub currency: Option<Currency>,
+}
+
+impl From<ListLedgerAccountsQuery> for LedgerAccountFilter {
+    fn from(query: ListLedgerAccountsQuery) -> Self {
+        Self {
+            kind: query.kind,
+            owner_id: query.owner_id,
+            currency: query.currency,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AccountBalanceQuery {
+    /// Include entries effective at or before this time; defaults to now
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListJournalEntriesQuery {
+    /// Page size, at most 100
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    /// Entries with a posting to this account
+    pub account_id: Option<Uuid>,
+    #[param(value_type = Option<String>, example = "settlement")]
+    pub kind: Option<EntryKind>,
+    /// Payment or payout the entries were posted for
+    pub reference_id: Option<Uuid>,
+}
+
+impl From<ListJournalEntriesQuery> for JournalEntryFilter {
+    fn from(query: ListJournalEntriesQuery) -> Self {
+        Self {
+            account_id: query.account_id,
+            kind: query.kind,
+            reference_id: query.reference_id,
+        }
+    }
+}
+
+fn default_limit() -> i64 {
+    20
+}
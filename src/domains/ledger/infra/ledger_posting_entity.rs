@@ -0,0 +1,22 @@
+use crate::common::money::Currency;
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "ledger_posting")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub entry_id: Uuid,
+    pub account_id: Uuid,
+    /// Minor units; positive debits, negative credits
+    pub amount: i64,
+    pub currency: Currency,
+    /// Copy of the entry's `effective_at` so balances need no join
+    pub effective_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
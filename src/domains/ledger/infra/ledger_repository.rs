@@ -0,0 +1,352 @@
+use super::{
+    ledger_account_entity::{self, Entity as LedgerAccountEntity},
+    ledger_journal_entry_entity::{self, Entity as LedgerJournalEntryEntity},
+    ledger_posting_entity::{self, Entity as LedgerPostingEntity},
+};
+use crate::common::{
+    error::AppError,
+    money::{Currency, Money},
+};
+use crate::domains::{
+    audit::{
+        domain::audit_entry::AuditEntry, infra::audit_log_repository::PostgresAuditLogRepository,
+    },
+    ledger::domain::{
+        account::{AccountKind, LedgerAccount, LedgerAccountFilter},
+        journal_entry::{EntryKind, JournalEntry, JournalEntryFilter, Posting},
+        repository::LedgerRepository,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    sea_query::{Expr, Query},
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct PostgresLedgerRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresLedgerRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn account_to_domain(model: ledger_account_entity::Model) -> Result<LedgerAccount, AppError> {
+        Ok(LedgerAccount {
+            id: model.id,
+            kind: model.kind.parse()?,
+            owner_id: model.owner_id,
+            currency: model.currency,
+            created_at: model.created_at.with_timezone(&Utc),
+        })
+    }
+
+    fn entry_to_domain(
+        model: ledger_journal_entry_entity::Model,
+        postings: Vec<ledger_posting_entity::Model>,
+    ) -> Result<JournalEntry, AppError> {
+        Ok(JournalEntry {
+            id: model.id,
+            kind: model.kind.parse()?,
+            reference_id: model.reference_id,
+            description: model.description,
+            created_by: model.created_by,
+            effective_at: model.effective_at.with_timezone(&Utc),
+            created_at: model.created_at.with_timezone(&Utc),
+            postings: postings
+                .into_iter()
+                .map(|posting| Posting {
+                    id: posting.id,
+                    entry_id: posting.entry_id,
+                    account_id: posting.account_id,
+                    amount: Money::new(posting.amount, posting.currency),
+                })
+                .collect(),
+        })
+    }
+
+    /// Loads the postings of `entries` in one query, keeping the entries' order
+    async fn with_postings(
+        &self,
+        entries: Vec<ledger_journal_entry_entity::Model>,
+    ) -> Result<Vec<JournalEntry>, AppError> {
+        let entry_ids: Vec<Uuid> = entries.iter().map(|entry| entry.id).collect();
+
+        let mut postings_by_entry: HashMap<Uuid, Vec<ledger_posting_entity::Model>> =
+            HashMap::new();
+        for posting in LedgerPostingEntity::find()
+            .filter(ledger_posting_entity::Column::EntryId.is_in(entry_ids))
+            .all(&self.db)
+            .await?
+        {
+            postings_by_entry
+                .entry(posting.entry_id)
+                .or_default()
+                .push(posting);
+        }
+
+        entries
+            .into_iter()
+            .map(|entry| {
+                let postings = postings_by_entry.remove(&entry.id).unwrap_or_default();
+                Self::entry_to_domain(entry, postings)
+            })
+            .collect()
+    }
+
+    /// Inserts entries with their postings; other repositories use it to post
+    /// within their own transaction
+    pub(crate) async fn insert_entries(
+        conn: &impl ConnectionTrait,
+        entries: &[JournalEntry],
+    ) -> Result<(), AppError> {
+        for entry in entries {
+            ledger_journal_entry_entity::ActiveModel {
+                id: Set(entry.id),
+                kind: Set(entry.kind.as_str().to_string()),
+                reference_id: Set(entry.reference_id),
+                description: Set(entry.description.clone()),
+                created_by: Set(entry.created_by),
+                effective_at: Set(entry.effective_at.into()),
+                created_at: Set(entry.created_at.into()),
+            }
+            .insert(conn)
+            .await?;
+
+            LedgerPostingEntity::insert_many(entry.postings.iter().map(|posting| {
+                ledger_posting_entity::ActiveModel {
+                    id: Set(posting.id),
+                    entry_id: Set(entry.id),
+                    account_id: Set(posting.account_id),
+                    amount: Set(posting.amount.minor_units()),
+                    currency: Set(posting.amount.currency()),
+                    effective_at: Set(entry.effective_at.into()),
+                }
+            }))
+            .exec(conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn sum_postings(
+        conn: &impl ConnectionTrait,
+        account_id: Uuid,
+        as_of: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        // SUM over bigint yields numeric in Postgres; cast back for the tuple decoder
+        let sum = LedgerPostingEntity::find()
+            .select_only()
+            .column_as(Expr::cust("COALESCE(SUM(amount), 0)::bigint"), "posted_sum")
+            .filter(ledger_posting_entity::Column::AccountId.eq(account_id))
+            .filter(ledger_posting_entity::Column::EffectiveAt.lte(as_of))
+            .into_tuple::<i64>()
+            .one(conn)
+            .await?;
+
+        Ok(sum.unwrap_or(0))
+    }
+
+    fn entry_condition(filter: JournalEntryFilter) -> Condition {
+        let mut condition = Condition::all();
+
+        if let Some(account_id) = filter.account_id {
+            condition = condition.add(
+                ledger_journal_entry_entity::Column::Id.in_subquery(
+                    Query::select()
+                        .column(ledger_posting_entity::Column::EntryId)
+                        .from(LedgerPostingEntity)
+                        .and_where(ledger_posting_entity::Column::AccountId.eq(account_id))
+                        .to_owned(),
+                ),
+            );
+        }
+        if let Some(kind) = filter.kind {
+            condition = condition.add(ledger_journal_entry_entity::Column::Kind.eq(kind.as_str()));
+        }
+        if let Some(reference_id) = filter.reference_id {
+            condition =
+                condition.add(ledger_journal_entry_entity::Column::ReferenceId.eq(reference_id));
+        }
+
+        condition
+    }
+}
+
+#[async_trait]
+impl LedgerRepository for PostgresLedgerRepository {
+    async fn find_account(&self, id: Uuid) -> Result<Option<LedgerAccount>, AppError> {
+        LedgerAccountEntity::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .map(Self::account_to_domain)
+            .transpose()
+    }
+
+    async fn find_account_by_owner(
+        &self,
+        kind: AccountKind,
+        owner_id: Option<Uuid>,
+        currency: Currency,
+    ) -> Result<Option<LedgerAccount>, AppError> {
+        let owner_condition = match owner_id {
+            Some(owner_id) => ledger_account_entity::Column::OwnerId.eq(owner_id),
+            None => ledger_account_entity::Column::OwnerId.is_null(),
+        };
+
+        LedgerAccountEntity::find()
+            .filter(ledger_account_entity::Column::Kind.eq(kind.as_str()))
+            .filter(owner_condition)
+            .filter(ledger_account_entity::Column::Currency.eq(currency))
+            .one(&self.db)
+            .await?
+            .map(Self::account_to_domain)
+            .transpose()
+    }
+
+    async fn create_account(&self, account: LedgerAccount) -> Result<LedgerAccount, AppError> {
+        let model = ledger_account_entity::ActiveModel {
+            id: Set(account.id),
+            kind: Set(account.kind.as_str().to_string()),
+            owner_id: Set(account.owner_id),
+            currency: Set(account.currency),
+            created_at: Set(account.created_at.into()),
+        }
+        .insert(&self.db)
+        .await?;
+
+        Self::account_to_domain(model)
+    }
+
+    async fn list_accounts(
+        &self,
+        filter: LedgerAccountFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LedgerAccount>, AppError> {
+        let mut query = LedgerAccountEntity::find();
+        if let Some(kind) = filter.kind {
+            query = query.filter(ledger_account_entity::Column::Kind.eq(kind.as_str()));
+        }
+        if let Some(owner_id) = filter.owner_id {
+            query = query.filter(ledger_account_entity::Column::OwnerId.eq(owner_id));
+        }
+        if let Some(currency) = filter.currency {
+            query = query.filter(ledger_account_entity::Column::Currency.eq(currency));
+        }
+
+        query
+            .order_by_asc(ledger_account_entity::Column::CreatedAt)
+            .limit(limit as u64)
+            .offset(offset as u64)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(Self::account_to_domain)
+            .collect()
+    }
+
+    async fn post(
+        &self,
+        entries: Vec<JournalEntry>,
+        audit: Option<AuditEntry>,
+    ) -> Result<Vec<JournalEntry>, AppError> {
+        // The balance trigger is deferred, so it sees every posting of an entry at commit
+        let txn = self.db.begin().await?;
+
+        Self::insert_entries(&txn, &entries).await?;
+        if let Some(audit) = audit {
+            PostgresAuditLogRepository::append(&txn, audit).await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(entries)
+    }
+
+    async fn post_if_covered(
+        &self,
+        entries: Vec<JournalEntry>,
+        account: LedgerAccount,
+        audit: Option<AuditEntry>,
+    ) -> Result<Vec<JournalEntry>, AppError> {
+        let txn = self.db.begin().await?;
+
+        // Concurrent calls for the account wait here, then see each other's postings
+        LedgerAccountEntity::find_by_id(account.id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Ledger account {} not found", account.id))
+            })?;
+
+        let posted = Self::sum_postings(&txn, account.id, Utc::now()).await?;
+        account.ensure_covers(posted, &entries)?;
+
+        Self::insert_entries(&txn, &entries).await?;
+        if let Some(audit) = audit {
+            PostgresAuditLogRepository::append(&txn, audit).await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(entries)
+    }
+
+    async fn find_entry(&self, id: Uuid) -> Result<Option<JournalEntry>, AppError> {
+        let Some(entry) = LedgerJournalEntryEntity::find_by_id(id)
+            .one(&self.db)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(self.with_postings(vec![entry]).await?.pop())
+    }
+
+    async fn find_entry_by_reference(
+        &self,
+        kind: EntryKind,
+        reference_id: Uuid,
+    ) -> Result<Option<JournalEntry>, AppError> {
+        let Some(entry) = LedgerJournalEntryEntity::find()
+            .filter(ledger_journal_entry_entity::Column::Kind.eq(kind.as_str()))
+            .filter(ledger_journal_entry_entity::Column::ReferenceId.eq(reference_id))
+            .one(&self.db)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(self.with_postings(vec![entry]).await?.pop())
+    }
+
+    async fn list_entries(
+        &self,
+        filter: JournalEntryFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<JournalEntry>, AppError> {
+        let entries = LedgerJournalEntryEntity::find()
+            .filter(Self::entry_condition(filter))
+            .order_by_desc(ledger_journal_entry_entity::Column::EffectiveAt)
+            .order_by_desc(ledger_journal_entry_entity::Column::CreatedAt)
+            .limit(limit as u64)
+            .offset(offset as u64)
+            .all(&self.db)
+            .await?;
+
+        self.with_postings(entries).await
+    }
+
+    async fn posted_sum(&self, account_id: Uuid, as_of: DateTime<Utc>) -> Result<i64, AppError> {
+        Self::sum_postings(&self.db, account_id, as_of).await
+    }
+}
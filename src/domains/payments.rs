@@ -0,0 +1,43 @@
+mod api {
+    pub mod gateway_payment_handler;
+    pub mod payment_handler;
+    pub mod router;
+}
+
+pub mod app {
+    pub mod assign_requisite_use_case;
+    pub mod confirm_payment_use_case;
+    pub mod create_payment_intent_use_case;
+    pub mod expire_payment_intents_use_case;
+    pub mod get_payment_intent_use_case;
+    pub mod payment_evidence_use_case;
+}
+
+pub mod domain {
+    pub mod evidence;
+    pub mod matching;
+    pub mod payment_intent;
+    pub mod repository;
+}
+
+pub mod dto {
+    pub mod payment_intent_dto;
+}
+
+pub mod infra {
+    pub mod matching_repository;
+    pub mod payment_evidence_entity;
+    pub mod payment_evidence_repository;
+    pub mod payment_intent_entity;
+    pub mod payment_intent_repository;
+}
+
+pub use api::router::{
+    gateway_payment_routes, protected_payment_routes, GatewayPaymentApiDoc, PaymentApiDoc,
+};
+pub use domain::repository::{
+    MatchingRepository, PaymentEvidenceRepository, PaymentIntentRepository,
+};
+pub use infra::matching_repository::PostgresMatchingRepository;
+pub use infra::payment_evidence_repository::PostgresPaymentEvidenceRepository;
+pub use infra::payment_intent_repository::PostgresPaymentIntentRepository;
@@ -0,0 +1,262 @@
+use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError};
+use crate::domains::{
+    backoffice::domain::merchant::MerchantContext,
+    payments::dto::payment_intent_dto::{
+        CreatePaymentIntentRequest, PaymentEvidenceResponse, PaymentIntentResponse,
+    },
+};
+use axum::{
+    extract::{multipart::MultipartError, Extension, Multipart, Path},
+    Json,
+};
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/gateway/payment",
+    request_body = CreatePaymentIntentRequest,
+    responses(
+        (status = 200, description = "Payment created", body = inline(ApiResponse<PaymentIntentResponse>)),
+        (status = 400, description = "Invalid amount, currency or expiry, or the order already has a payment"),
+        (status = 401, description = "Missing headers, unknown or revoked key, bad signature or stale timestamp"),
+        (status = 403, description = "Site or merchant is not active, or the client IP is not allowed")
+    ),
+    security(
+        ("api_key" = [], "api_timestamp" = [], "api_signature" = [])
+    ),
+    tag = "Gateway payments",
+    summary = "Create payment",
+    description = "Opens a payment for an order of the site that signed the request and matches it to a trader. A matched payment is `pending` and `pay_to` holds the requisite the customer pays to; a payment no trader can take yet stays `created` until one is found. It expires at `expires_at` unless it is completed first."
+)]
+pub async fn create_payment(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(context): Extension<MerchantContext>,
+    Json(payload): Json<CreatePaymentIntentRequest>,
+) -> Result<Json<ApiResponse<PaymentIntentResponse>>, AppError> {
+    let intent = state
+        .payment_intent_create_use_case
+        .execute(&context, payload)
+        .await?;
+
+    let details = state
+        .payment_intent_assign_use_case
+        .payment_details(&intent)
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        PaymentIntentResponse::from(intent).with_payment_details(details),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/gateway/payment/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Payment ID")
+    ),
+    responses(
+        (status = 200, description = "Payment found", body = inline(ApiResponse<PaymentIntentResponse>)),
+        (status = 401, description = "Missing headers, unknown or revoked key, bad signature or stale timestamp"),
+        (status = 403, description = "Site or merchant is not active, or the client IP is not allowed"),
+        (status = 404, description = "Payment not found for this site")
+    ),
+    security(
+        ("api_key" = [], "api_timestamp" = [], "api_signature" = [])
+    ),
+    tag = "Gateway payments",
+    summary = "Get payment",
+    description = "Fetches a payment of the site that signed the request, with `pay_to` while it is pending."
+)]
+pub async fn get_payment(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(context): Extension<MerchantContext>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<PaymentIntentResponse>>, AppError> {
+    let intent = state
+        .payment_intent_get_use_case
+        .for_site(context.site.id, payment_id)
+        .await?;
+
+    let details = state
+        .payment_intent_assign_use_case
+        .payment_details(&intent)
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        PaymentIntentResponse::from(intent).with_payment_details(details),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/gateway/payment/order/{external_order_id}",
+    params(
+        ("external_order_id" = String, Path, description = "Order reference given at creation")
+    ),
+    responses(
+        (status = 200, description = "Payment found", body = inline(ApiResponse<PaymentIntentResponse>)),
+        (status = 401, description = "Missing headers, unknown or revoked key, bad signature or stale timestamp"),
+        (status = 403, description = "Site or merchant is not active, or the client IP is not allowed"),
+        (status = 404, description = "No payment for this order")
+    ),
+    security(
+        ("api_key" = [], "api_timestamp" = [], "api_signature" = [])
+    ),
+    tag = "Gateway payments",
+    summary = "Get payment by order",
+    description = "Fetches the payment of one of your orders, e.g. after a timeout while creating it."
+)]
+pub async fn get_payment_by_order(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(context): Extension<MerchantContext>,
+    Path(external_order_id): Path<String>,
+) -> Result<Json<ApiResponse<PaymentIntentResponse>>, AppError> {
+    let intent = state
+        .payment_intent_get_use_case
+        .by_external_order_id(context.site.id, &external_order_id)
+        .await?;
+
+    let details = state
+        .payment_intent_assign_use_case
+        .payment_details(&intent)
+        .await?;
+
+    Ok(Json(ApiResponse::success(
+        PaymentIntentResponse::from(intent).with_payment_details(details),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/gateway/payment/{id}/paid",
+    params(
+        ("id" = Uuid, Path, description = "Payment ID")
+    ),
+    responses(
+        (status = 200, description = "Payment waits for the trader's confirmation", body = inline(ApiResponse<PaymentIntentResponse>)),
+        (status = 401, description = "Missing headers, unknown or revoked key, bad signature or stale timestamp"),
+        (status = 403, description = "Site or merchant is not active, or the client IP is not allowed"),
+        (status = 404, description = "Payment not found for this site"),
+        (status = 409, description = "Payment is not pending or has expired")
+    ),
+    security(
+        ("api_key" = [], "api_timestamp" = [], "api_signature" = [])
+    ),
+    tag = "Gateway payments",
+    summary = "Mark payment as paid",
+    description = "Reports that the customer sent the money to `pay_to`. The payment moves to `confirming` and no longer expires; the trader confirms or rejects it, and Support takes over if the trader does not answer in time. Attach a receipt with the evidence endpoint."
+)]
+pub async fn mark_payment_paid(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(context): Extension<MerchantContext>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<PaymentIntentResponse>>, AppError> {
+    let intent = state
+        .payment_confirm_use_case
+        .mark_paid(context.site.id, payment_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success(PaymentIntentResponse::from(
+        intent,
+    ))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/gateway/payment/{id}/cancel",
+    params(
+        ("id" = Uuid, Path, description = "Payment ID")
+    ),
+    responses(
+        (status = 200, description = "Payment cancelled", body = inline(ApiResponse<PaymentIntentResponse>)),
+        (status = 401, description = "Missing headers, unknown or revoked key, bad signature or stale timestamp"),
+        (status = 403, description = "Site or merchant is not active, or the client IP is not allowed"),
+        (status = 404, description = "Payment not found for this site"),
+        (status = 409, description = "Payment is already marked as paid, final or expired")
+    ),
+    security(
+        ("api_key" = [], "api_timestamp" = [], "api_signature" = [])
+    ),
+    tag = "Gateway payments",
+    summary = "Cancel payment",
+    description = "Withdraws a `created` or `pending` payment, for example when the customer abandons the order. The payment becomes `cancelled` and a `payment.cancelled` webhook is sent. Payments the customer already marked as paid wait for the trader instead."
+)]
+pub async fn cancel_payment(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(context): Extension<MerchantContext>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<PaymentIntentResponse>>, AppError> {
+    let intent = state
+        .payment_confirm_use_case
+        .cancel(context.site.id, payment_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success(PaymentIntentResponse::from(
+        intent,
+    ))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/gateway/payment/{id}/evidence",
+    params(
+        ("id" = Uuid, Path, description = "Payment ID")
+    ),
+    request_body(
+        content_type = "multipart/form-data",
+        description = "A `file` part with a PNG, JPEG or PDF receipt"
+    ),
+    responses(
+        (status = 200, description = "Evidence stored", body = inline(ApiResponse<PaymentEvidenceResponse>)),
+        (status = 400, description = "Missing `file` part, unsupported type, empty or too large"),
+        (status = 401, description = "Missing headers, unknown or revoked key, bad signature or stale timestamp"),
+        (status = 403, description = "Site or merchant is not active, or the client IP is not allowed"),
+        (status = 404, description = "Payment not found for this site"),
+        (status = 409, description = "Payment is no longer pending or confirming")
+    ),
+    security(
+        ("api_key" = [], "api_timestamp" = [], "api_signature" = [])
+    ),
+    tag = "Gateway payments",
+    summary = "Upload payment evidence",
+    description = "Attaches proof of the transfer, e.g. a bank receipt, to a pending or confirming payment. The signature covers the raw multipart body."
+)]
+pub async fn upload_payment_evidence(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(context): Extension<MerchantContext>,
+    Path(payment_id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<Json<ApiResponse<PaymentEvidenceResponse>>, AppError> {
+    let invalid = |err: MultipartError| AppError::BadRequest(err.body_text());
+
+    while let Some(field) = multipart.next_field().await.map_err(invalid)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let file_name = field.file_name().unwrap_or_default().to_string();
+        let content_type = field.content_type().unwrap_or_default().to_string();
+        let content = field.bytes().await.map_err(invalid)?;
+
+        let evidence = state
+            .payment_evidence_use_case
+            .upload(
+                context.site.id,
+                payment_id,
+                &file_name,
+                &content_type,
+                &content,
+            )
+            .await?;
+
+        return Ok(Json(ApiResponse::success(PaymentEvidenceResponse::from(
+            evidence,
+        ))));
+    }
+
+    Err(AppError::BadRequest(
+        "Multipart body has no 'file' part".to_string(),
+    ))
+}
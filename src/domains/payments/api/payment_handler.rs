@@ -0,0 +1,234 @@
+use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError, jwt::Claims};
+use crate::domains::audit::domain::audit_entry::AuditContext;
+use crate::domains::payments::dto::payment_intent_dto::{
+    ListPaymentIntentsQuery, PaymentEvidenceResponse, PaymentIntentResponse, RejectPaymentRequest,
+};
+use axum::{
+    extract::{Extension, Path, Query},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/payment",
+    params(ListPaymentIntentsQuery),
+    responses(
+        (status = 200, description = "Page of payments, newest first", body = inline(ApiResponse<Vec<PaymentIntentResponse>>)),
+        (status = 400, description = "Limit exceeds 100 or a filter is malformed"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Payments",
+    summary = "List payments",
+    description = "Lists payments of all merchants, filtered by merchant, site, trader, status, escalation, order reference and creation time. `status=confirming&escalated=true` is the Support queue of payments whose trader did not answer in time. Requires `payments:read`."
+)]
+pub async fn list_payments(
+    Extension(state): Extension<Arc<AppState>>,
+    Query(params): Query<ListPaymentIntentsQuery>,
+) -> Result<Json<ApiResponse<Vec<PaymentIntentResponse>>>, AppError> {
+    let (limit, offset) = (params.limit, params.offset);
+    let intents = state
+        .payment_intent_get_use_case
+        .list(params.into(), limit, offset)
+        .await?;
+
+    let response: Vec<PaymentIntentResponse> = intents
+        .into_iter()
+        .map(PaymentIntentResponse::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/payment/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Payment ID")
+    ),
+    responses(
+        (status = 200, description = "Payment found", body = inline(ApiResponse<PaymentIntentResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Payment not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Payments",
+    summary = "Get payment",
+    description = "Fetches any payment by ID. Requires `payments:read`."
+)]
+pub async fn get_payment(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<PaymentIntentResponse>>, AppError> {
+    let intent = state
+        .payment_intent_get_use_case
+        .execute(payment_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success(PaymentIntentResponse::from(
+        intent,
+    ))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/payment/{id}/confirm",
+    params(
+        ("id" = Uuid, Path, description = "Payment ID")
+    ),
+    responses(
+        (status = 200, description = "Payment paid and settled to the merchant", body = inline(ApiResponse<PaymentIntentResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Payment not found"),
+        (status = 409, description = "Payment is not pending or confirming")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Payments",
+    summary = "Confirm payment",
+    description = "Records that the trader received the money. The payment becomes `paid`, is settled to the merchant's balance and can be disputed until `dispute_until`. Confirming a paid payment again retries a failed settlement. Requires `payments:confirm`."
+)]
+pub async fn confirm_payment(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<PaymentIntentResponse>>, AppError> {
+    let intent = state
+        .payment_confirm_use_case
+        .confirm(payment_id, claims.user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(PaymentIntentResponse::from(
+        intent,
+    ))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/payment/{id}/reject",
+    params(
+        ("id" = Uuid, Path, description = "Payment ID")
+    ),
+    request_body = RejectPaymentRequest,
+    responses(
+        (status = 200, description = "Payment failed", body = inline(ApiResponse<PaymentIntentResponse>)),
+        (status = 400, description = "Reason is empty or too long"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Payment not found"),
+        (status = 409, description = "Payment was not marked as paid")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Payments",
+    summary = "Reject payment",
+    description = "Records that the money never arrived for a payment marked as paid. The payment becomes `failed` and can be disputed until `dispute_until`. Requires `payments:confirm`."
+)]
+pub async fn reject_payment(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+    Path(payment_id): Path<Uuid>,
+    Json(request): Json<RejectPaymentRequest>,
+) -> Result<Json<ApiResponse<PaymentIntentResponse>>, AppError> {
+    let intent = state
+        .payment_confirm_use_case
+        .reject(payment_id, request.reason, claims.user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(PaymentIntentResponse::from(
+        intent,
+    ))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/payment/{id}/evidence",
+    params(
+        ("id" = Uuid, Path, description = "Payment ID")
+    ),
+    responses(
+        (status = 200, description = "Evidence of the payment, oldest first", body = inline(ApiResponse<Vec<PaymentEvidenceResponse>>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Payment not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Payments",
+    summary = "List payment evidence",
+    description = "Lists the files uploaded as proof of the transfer. Requires `payments:read`."
+)]
+pub async fn list_payment_evidence(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<PaymentEvidenceResponse>>>, AppError> {
+    let evidence = state.payment_evidence_use_case.list(payment_id).await?;
+
+    let response: Vec<PaymentEvidenceResponse> = evidence
+        .into_iter()
+        .map(PaymentEvidenceResponse::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/payment/{id}/evidence/{evidence_id}",
+    params(
+        ("id" = Uuid, Path, description = "Payment ID"),
+        ("evidence_id" = Uuid, Path, description = "Evidence ID")
+    ),
+    responses(
+        (status = 200, description = "File content with its original type"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Evidence not found for this payment")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Payments",
+    summary = "Download payment evidence",
+    description = "Downloads an uploaded file as an attachment. Requires `payments:read`."
+)]
+pub async fn download_payment_evidence(
+    Extension(state): Extension<Arc<AppState>>,
+    Path((payment_id, evidence_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, AppError> {
+    let (evidence, content) = state
+        .payment_evidence_use_case
+        .download(payment_id, evidence_id)
+        .await?;
+
+    // Uploaded by merchants, so never rendered inline by the browser
+    Ok((
+        [
+            (header::CONTENT_TYPE, evidence.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", evidence.file_name),
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        content,
+    )
+        .into_response())
+}
@@ -0,0 +1,137 @@
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    common::{
+        jwt::SecurityAddon, middleware::require_permission,
+        request_signature::MerchantSignatureAddon,
+    },
+    domains::{
+        backoffice::role::permission::{PAYMENTS_CONFIRM, PAYMENTS_READ},
+        payments::{
+            domain::payment_intent::PaymentStatus,
+            dto::payment_intent_dto::{
+                CreatePaymentIntentRequest, PaymentDetailsResponse, PaymentEvidenceResponse,
+                PaymentIntentResponse, RejectPaymentRequest,
+            },
+        },
+        traders::domain::requisite::RequisiteKind,
+    },
+};
+
+use utoipa::OpenApi;
+
+use super::{gateway_payment_handler, payment_handler};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::payment_handler::list_payments,
+        super::payment_handler::get_payment,
+        super::payment_handler::confirm_payment,
+        super::payment_handler::reject_payment,
+        super::payment_handler::list_payment_evidence,
+        super::payment_handler::download_payment_evidence,
+    ),
+    components(schemas(
+        PaymentIntentResponse,
+        PaymentDetailsResponse,
+        PaymentEvidenceResponse,
+        RejectPaymentRequest,
+        PaymentStatus,
+        RequisiteKind
+    )),
+    tags(
+        (name = "Payments", description = "Backoffice view of merchant payments and trader confirmations")
+    ),
+    modifiers(&SecurityAddon)
+)]
+pub struct PaymentApiDoc;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::gateway_payment_handler::create_payment,
+        super::gateway_payment_handler::get_payment,
+        super::gateway_payment_handler::get_payment_by_order,
+        super::gateway_payment_handler::mark_payment_paid,
+        super::gateway_payment_handler::cancel_payment,
+        super::gateway_payment_handler::upload_payment_evidence,
+    ),
+    components(schemas(
+        CreatePaymentIntentRequest,
+        PaymentIntentResponse,
+        PaymentDetailsResponse,
+        PaymentEvidenceResponse,
+        PaymentStatus,
+        RequisiteKind
+    )),
+    tags(
+        (name = "Gateway payments", description = "Payments created by merchant sites")
+    ),
+    modifiers(&MerchantSignatureAddon)
+)]
+pub struct GatewayPaymentApiDoc;
+
+pub fn protected_payment_routes() -> Router {
+    let confirm_routes = Router::new()
+        .route(
+            "/payment/{id}/confirm",
+            post(payment_handler::confirm_payment),
+        )
+        .route(
+            "/payment/{id}/reject",
+            post(payment_handler::reject_payment),
+        )
+        .route_layer(middleware::from_fn(require_permission(PAYMENTS_CONFIRM)));
+
+    let read_routes = Router::new()
+        .route("/payment", get(payment_handler::list_payments))
+        .route("/payment/{id}", get(payment_handler::get_payment))
+        .route(
+            "/payment/{id}/evidence",
+            get(payment_handler::list_payment_evidence),
+        )
+        .route(
+            "/payment/{id}/evidence/{evidence_id}",
+            get(payment_handler::download_payment_evidence),
+        )
+        .route_layer(middleware::from_fn(require_permission(PAYMENTS_READ)));
+
+    Router::new().merge(confirm_routes).merge(read_routes)
+}
+
+/// Merchant-facing routes; `merchant_auth` puts a `MerchantContext` in the extensions
+pub fn gateway_payment_routes() -> Router {
+    Router::new()
+        .route(
+            "/gateway/payment",
+            post(gateway_payment_handler::create_payment),
+        )
+        .route(
+            "/gateway/payment/{id}",
+            get(gateway_payment_handler::get_payment),
+        )
+        .route(
+            "/gateway/payment/order/{external_order_id}",
+            get(gateway_payment_handler::get_payment_by_order),
+        )
+        .route(
+            "/gateway/payment/{id}/paid",
+            post(gateway_payment_handler::mark_payment_paid),
+        )
+        .route(
+            "/gateway/payment/{id}/cancel",
+            post(gateway_payment_handler::cancel_payment),
+        )
+        // `merchant_auth` already bounded the body by the evidence size limit
+        .route(
+            "/gateway/payment/{id}/evidence",
+            post(gateway_payment_handler::upload_payment_evidence)
+                .layer(DefaultBodyLimit::disable()),
+        )
+}
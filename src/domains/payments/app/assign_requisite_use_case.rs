@@ -0,0 +1,315 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::{
+    common::{error::AppError, secret_cipher::SecretCipher},
+    domains::{
+        payments::domain::{
+            matching::{MatchingStrategy, PaymentDetails},
+            payment_intent::{PaymentIntent, PaymentIntentFilter, PaymentStatus},
+            repository::{MatchingRepository, PaymentIntentRepository},
+        },
+        traders::domain::repository::TraderRepository,
+    },
+};
+
+/// Payments picked up by one run of the matching sweep
+const MAX_WAITING_BATCH: i64 = 100;
+
+pub struct AssignRequisiteUseCase {
+    payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+    matching_repository: Arc<dyn MatchingRepository>,
+    trader_repository: Arc<dyn TraderRepository>,
+    secret_cipher: Arc<SecretCipher>,
+    strategy: Arc<dyn MatchingStrategy>,
+}
+
+impl AssignRequisiteUseCase {
+    pub fn new(
+        payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+        matching_repository: Arc<dyn MatchingRepository>,
+        trader_repository: Arc<dyn TraderRepository>,
+        secret_cipher: Arc<SecretCipher>,
+        strategy: Arc<dyn MatchingStrategy>,
+    ) -> Self {
+        Self {
+            payment_intent_repository,
+            matching_repository,
+            trader_repository,
+            secret_cipher,
+            strategy,
+        }
+    }
+
+    /// Hands a created payment to the best trader requisite that can take it.
+    /// Returns the payment unchanged when it is already matched or no trader is available;
+    /// the matching sweep tries again later.
+    pub async fn execute(&self, intent: PaymentIntent) -> Result<PaymentIntent, AppError> {
+        tracing::debug!("Matching payment {} of {}", intent.id, intent.amount);
+
+        if intent.status != PaymentStatus::Created || intent.assignment.is_some() {
+            return Ok(intent);
+        }
+
+        let now = Utc::now();
+        let candidates: Vec<_> = self
+            .matching_repository
+            .find_candidates(&intent, now)
+            .await?
+            .into_iter()
+            .filter(|candidate| candidate.can_take(intent.amount))
+            .collect();
+
+        if candidates.is_empty() {
+            tracing::info!("No trader available for payment {}", intent.id);
+            return Ok(intent);
+        }
+
+        let candidate_count = candidates.len();
+        for candidate in self.strategy.rank(candidates) {
+            if let Some(assigned) = self
+                .matching_repository
+                .assign(intent.id, &candidate, now)
+                .await?
+            {
+                tracing::info!(
+                    "Payment {} assigned to requisite {} of trader {}",
+                    assigned.id,
+                    candidate.requisite_id,
+                    candidate.trader_id
+                );
+                return Ok(assigned);
+            }
+
+            tracing::debug!(
+                "Requisite {} was taken before payment {} could be assigned",
+                candidate.requisite_id,
+                intent.id
+            );
+        }
+
+        tracing::info!(
+            "All {} candidates for payment {} were taken",
+            candidate_count,
+            intent.id
+        );
+
+        Ok(intent)
+    }
+
+    /// Retries matching for payments still waiting for a trader; returns how many were assigned
+    pub async fn assign_waiting(&self) -> Result<u64, AppError> {
+        let filter = PaymentIntentFilter {
+            status: Some(PaymentStatus::Created),
+            ..Default::default()
+        };
+        let waiting = self
+            .payment_intent_repository
+            .list(filter, MAX_WAITING_BATCH, 0)
+            .await?;
+
+        let now = Utc::now();
+        let mut assigned = 0;
+        for intent in waiting {
+            if intent.is_due_to_expire(now) {
+                continue;
+            }
+
+            let intent_id = intent.id;
+            match self.execute(intent).await {
+                Ok(intent) if intent.assignment.is_some() => assigned += 1,
+                Ok(_) => {}
+                Err(err) => tracing::warn!("Matching payment {} failed: {}", intent_id, err),
+            }
+        }
+
+        if assigned > 0 {
+            tracing::info!("Assigned {} waiting payments", assigned);
+        }
+
+        Ok(assigned)
+    }
+
+    /// Requisite the customer pays to while the payment is pending
+    pub async fn payment_details(
+        &self,
+        intent: &PaymentIntent,
+    ) -> Result<Option<PaymentDetails>, AppError> {
+        let Some(assignment) = intent.assignment else {
+            return Ok(None);
+        };
+        if intent.status != PaymentStatus::Pending {
+            return Ok(None);
+        }
+
+        let requisite = self
+            .trader_repository
+            .find_requisite(assignment.requisite_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Requisite {} not found",
+                assignment.requisite_id
+            )))?;
+
+        Ok(Some(PaymentDetails {
+            kind: requisite.kind,
+            bank_name: requisite.bank_name,
+            holder_name: requisite.holder_name,
+            number: self.secret_cipher.decrypt(&requisite.encrypted_number)?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::{Currency, Money};
+    use crate::domains::{
+        payments::domain::{
+            matching::{MatchCandidate, RoundRobinStrategy},
+            repository::{MockMatchingRepository, MockPaymentIntentRepository},
+        },
+        traders::domain::{
+            repository::MockTraderRepository,
+            requisite::{Requisite, RequisiteKind},
+        },
+    };
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    fn intent() -> PaymentIntent {
+        PaymentIntent::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "order-1".to_string(),
+            Money::new(5_000, Currency::Usd),
+            None,
+            None,
+            Duration::minutes(30),
+        )
+    }
+
+    fn candidate(deposit: i64) -> MatchCandidate {
+        let usd = |minor_units| Money::new(minor_units, Currency::Usd);
+        MatchCandidate {
+            trader_id: Uuid::new_v4(),
+            requisite_id: Uuid::new_v4(),
+            daily_limit: usd(100_000),
+            monthly_limit: usd(1_000_000),
+            daily_used: usd(0),
+            monthly_used: usd(0),
+            deposit: usd(deposit),
+            exposure: usd(0),
+            open_payments: 0,
+            paid_payments: 0,
+            failed_payments: 0,
+            last_assigned_at: None,
+        }
+    }
+
+    fn use_case(
+        matching_repository: MockMatchingRepository,
+        trader_repository: MockTraderRepository,
+    ) -> AssignRequisiteUseCase {
+        AssignRequisiteUseCase::new(
+            Arc::new(MockPaymentIntentRepository::new()),
+            Arc::new(matching_repository),
+            Arc::new(trader_repository),
+            Arc::new(SecretCipher::new("test-key")),
+            Arc::new(RoundRobinStrategy),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_falls_through_to_next_candidate_when_taken() {
+        let intent = intent();
+        let busy = candidate(50_000);
+        let free = MatchCandidate {
+            last_assigned_at: Some(Utc::now()),
+            ..candidate(50_000)
+        };
+        // Cannot cover the payment with its deposit, never tried
+        let broke = candidate(1_000);
+
+        let mut matching_repository = MockMatchingRepository::new();
+        let candidates = vec![broke.clone(), free.clone(), busy.clone()];
+        matching_repository
+            .expect_find_candidates()
+            .returning(move |_, _| Ok(candidates.clone()));
+        let (busy_id, free_id) = (busy.requisite_id, free.requisite_id);
+        let stored = intent.clone();
+        matching_repository
+            .expect_assign()
+            .times(2)
+            .returning(move |_, candidate, at| {
+                assert_ne!(candidate.requisite_id, broke.requisite_id);
+                if candidate.requisite_id == busy_id {
+                    return Ok(None);
+                }
+                let mut intent = stored.clone();
+                intent.assign(candidate.trader_id, candidate.requisite_id, at)?;
+                Ok(Some(intent))
+            });
+
+        let assigned = use_case(matching_repository, MockTraderRepository::new())
+            .execute(intent)
+            .await
+            .unwrap();
+
+        assert_eq!(assigned.status, PaymentStatus::Pending);
+        assert_eq!(assigned.assignment.unwrap().requisite_id, free_id);
+    }
+
+    #[tokio::test]
+    async fn test_payment_stays_created_without_candidates() {
+        let mut matching_repository = MockMatchingRepository::new();
+        matching_repository
+            .expect_find_candidates()
+            .returning(|_, _| Ok(vec![]));
+        matching_repository.expect_assign().never();
+
+        let intent = use_case(matching_repository, MockTraderRepository::new())
+            .execute(intent())
+            .await
+            .unwrap();
+
+        assert_eq!(intent.status, PaymentStatus::Created);
+        assert!(intent.assignment.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_payment_details_decrypt_the_number() {
+        let cipher = SecretCipher::new("test-key");
+        let requisite = Requisite::new(
+            Uuid::new_v4(),
+            RequisiteKind::Card,
+            "Example Bank".to_string(),
+            "JANE DOE".to_string(),
+            "**** 4242".to_string(),
+            cipher.encrypt("4242424242424242").unwrap(),
+            Money::new(100_000, Currency::Usd),
+            Money::new(1_000_000, Currency::Usd),
+        )
+        .unwrap();
+
+        let mut intent = intent();
+        intent
+            .assign(requisite.trader_id, requisite.id, Utc::now())
+            .unwrap();
+
+        let mut trader_repository = MockTraderRepository::new();
+        trader_repository
+            .expect_find_requisite()
+            .returning(move |_| Ok(Some(requisite.clone())));
+
+        let details = use_case(MockMatchingRepository::new(), trader_repository)
+            .payment_details(&intent)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(details.number, "4242424242424242");
+        assert_eq!(details.holder_name, "JANE DOE");
+    }
+}
@@ -0,0 +1,386 @@
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, PAYMENT_ENTITY},
+        ledger::app::record_settlement_use_case::RecordSettlementUseCase,
+        payments::{
+            app::get_payment_intent_use_case::apply_expiry,
+            domain::{
+                payment_intent::{PaymentIntent, PaymentStatus},
+                repository::PaymentIntentRepository,
+            },
+        },
+    },
+};
+
+const MAX_REJECTION_REASON_LEN: usize = 500;
+
+/// Customer reports a transfer, the trader confirms or rejects it; until the transfer is
+/// reported the merchant may cancel the payment instead
+pub struct ConfirmPaymentUseCase {
+    payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+    settlement_use_case: Arc<RecordSettlementUseCase>,
+    confirmation_timeout: Duration,
+    dispute_window: Duration,
+}
+
+impl ConfirmPaymentUseCase {
+    pub fn new(
+        payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+        settlement_use_case: Arc<RecordSettlementUseCase>,
+        confirmation_timeout: Duration,
+        dispute_window: Duration,
+    ) -> Self {
+        Self {
+            payment_intent_repository,
+            settlement_use_case,
+            confirmation_timeout,
+            dispute_window,
+        }
+    }
+
+    /// Merchant reports that its customer sent the money; payments of other sites are missing
+    pub async fn mark_paid(
+        &self,
+        site_id: Uuid,
+        payment_id: Uuid,
+    ) -> Result<PaymentIntent, AppError> {
+        tracing::debug!("Marking payment {} as paid", payment_id);
+
+        let mut intent = self.find_for_site(site_id, payment_id).await?;
+        intent.mark_paid(Utc::now())?;
+        let intent = self
+            .payment_intent_repository
+            .update_status(intent, None)
+            .await?;
+
+        tracing::info!(
+            "Payment {} marked as paid, waiting for the trader",
+            intent.id
+        );
+
+        Ok(intent)
+    }
+
+    /// Merchant withdraws a created or pending payment; payments of other sites are missing
+    pub async fn cancel(&self, site_id: Uuid, payment_id: Uuid) -> Result<PaymentIntent, AppError> {
+        tracing::debug!("Cancelling payment {}", payment_id);
+
+        let mut intent = self.find_for_site(site_id, payment_id).await?;
+        intent.cancel(Utc::now())?;
+        let intent = self
+            .payment_intent_repository
+            .update_status(intent, None)
+            .await?;
+
+        tracing::info!("Payment {} cancelled by the merchant", intent.id);
+
+        Ok(intent)
+    }
+
+    /// Trader confirms the money arrived; the payment becomes paid and is settled to the
+    /// merchant in one transaction.
+    /// Confirming a paid payment again settles it if that never happened.
+    pub async fn confirm(
+        &self,
+        payment_id: Uuid,
+        confirmed_by: Uuid,
+        context: &AuditContext,
+    ) -> Result<PaymentIntent, AppError> {
+        tracing::debug!("Confirming payment {}", payment_id);
+
+        let mut intent = self.find(payment_id).await?;
+        if intent.status == PaymentStatus::Paid {
+            self.settlement_use_case.execute(&intent).await?;
+            return Ok(intent);
+        }
+
+        let before = intent.audit_snapshot();
+        intent.confirm(Utc::now(), self.dispute_window)?;
+        let settlement = self.settlement_use_case.entries(&intent).await?;
+        let audit = payment_audit(context, &before, &intent);
+        let intent = self
+            .payment_intent_repository
+            .settle(intent, settlement, audit)
+            .await?;
+
+        tracing::info!("Payment {} confirmed by {}", intent.id, confirmed_by);
+
+        Ok(intent)
+    }
+
+    /// Trader reports that the money never arrived
+    pub async fn reject(
+        &self,
+        payment_id: Uuid,
+        reason: String,
+        rejected_by: Uuid,
+        context: &AuditContext,
+    ) -> Result<PaymentIntent, AppError> {
+        tracing::debug!("Rejecting payment {}", payment_id);
+
+        let reason = reason.trim().to_string();
+        if reason.is_empty() || reason.chars().count() > MAX_REJECTION_REASON_LEN {
+            return Err(AppError::ValidationError(format!(
+                "Reason must be 1 to {} characters",
+                MAX_REJECTION_REASON_LEN
+            )));
+        }
+
+        let mut intent = self.find(payment_id).await?;
+        let before = intent.audit_snapshot();
+        intent.reject(reason, Utc::now(), self.dispute_window)?;
+        let audit = payment_audit(context, &before, &intent);
+        let intent = self
+            .payment_intent_repository
+            .update_status(intent, Some(audit))
+            .await?;
+
+        tracing::info!("Payment {} rejected by {}", intent.id, rejected_by);
+
+        Ok(intent)
+    }
+
+    /// Moves payments the traders left unanswered to the Support queue
+    pub async fn escalate_overdue(&self) -> Result<u64, AppError> {
+        let now = Utc::now();
+        let escalated = self
+            .payment_intent_repository
+            .escalate_due(now - self.confirmation_timeout, now)
+            .await?;
+
+        if escalated > 0 {
+            tracing::warn!("Escalated {} unconfirmed payments to Support", escalated);
+        }
+
+        Ok(escalated)
+    }
+
+    async fn find(&self, payment_id: Uuid) -> Result<PaymentIntent, AppError> {
+        let intent = self
+            .payment_intent_repository
+            .find_by_id(payment_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Payment {} not found",
+                payment_id
+            )))?;
+
+        apply_expiry(self.payment_intent_repository.as_ref(), intent).await
+    }
+
+    async fn find_for_site(
+        &self,
+        site_id: Uuid,
+        payment_id: Uuid,
+    ) -> Result<PaymentIntent, AppError> {
+        let intent = self.find(payment_id).await?;
+        if intent.site_id != site_id {
+            return Err(AppError::NotFound(format!(
+                "Payment {} not found",
+                payment_id
+            )));
+        }
+
+        Ok(intent)
+    }
+}
+
+/// Audit entry of a trader decision on the payment
+fn payment_audit(context: &AuditContext, before: &Value, intent: &PaymentIntent) -> AuditEntry {
+    AuditEntry::new(
+        context,
+        AuditAction::Update,
+        PAYMENT_ENTITY,
+        intent.id,
+        Some(before),
+        Some(&intent.audit_snapshot()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::{Currency, Money};
+    use crate::domains::{
+        ledger::domain::{
+            journal_entry::EntryKind,
+            repository::{LedgerRepository, MockLedgerRepository},
+        },
+        payments::domain::repository::MockPaymentIntentRepository,
+    };
+
+    fn pending_intent() -> PaymentIntent {
+        let mut intent = PaymentIntent::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "order-1".to_string(),
+            Money::new(10_000, Currency::Usd),
+            None,
+            None,
+            Duration::minutes(30),
+        );
+        intent
+            .assign(Uuid::new_v4(), Uuid::new_v4(), Utc::now())
+            .unwrap();
+        intent
+    }
+
+    fn use_case(
+        repository: MockPaymentIntentRepository,
+        ledger_repository: MockLedgerRepository,
+    ) -> ConfirmPaymentUseCase {
+        let ledger_repository: Arc<dyn LedgerRepository> = Arc::new(ledger_repository);
+        ConfirmPaymentUseCase::new(
+            Arc::new(repository),
+            Arc::new(RecordSettlementUseCase::new(ledger_repository, 0)),
+            Duration::minutes(15),
+            Duration::hours(72),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_mark_paid_is_scoped_to_the_site() {
+        let intent = pending_intent();
+        let site_id = intent.site_id;
+        let mut repository = MockPaymentIntentRepository::new();
+        repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(intent.clone())));
+        repository
+            .expect_update_status()
+            .withf(|_, audit| audit.is_none())
+            .times(1)
+            .returning(|intent, _| Ok(intent));
+        let use_case = use_case(repository, MockLedgerRepository::new());
+
+        let result = use_case.mark_paid(Uuid::new_v4(), Uuid::new_v4()).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let intent = use_case.mark_paid(site_id, Uuid::new_v4()).await.unwrap();
+        assert_eq!(intent.status, PaymentStatus::Confirming);
+        assert!(intent.marked_paid_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_is_scoped_to_the_site() {
+        let intent = pending_intent();
+        let site_id = intent.site_id;
+        let mut repository = MockPaymentIntentRepository::new();
+        repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(intent.clone())));
+        repository
+            .expect_update_status()
+            .withf(|intent, audit| intent.status == PaymentStatus::Cancelled && audit.is_none())
+            .times(1)
+            .returning(|intent, _| Ok(intent));
+        let use_case = use_case(repository, MockLedgerRepository::new());
+
+        let result = use_case.cancel(Uuid::new_v4(), Uuid::new_v4()).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let intent = use_case.cancel(site_id, Uuid::new_v4()).await.unwrap();
+        assert_eq!(intent.status, PaymentStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_confirm_settles_once() {
+        let mut intent = pending_intent();
+        intent.mark_paid(Utc::now()).unwrap();
+
+        let mut repository = MockPaymentIntentRepository::new();
+        repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(intent.clone())));
+        repository
+            .expect_settle()
+            .withf(|intent, settlement, audit| {
+                settlement.len() == 1
+                    && settlement[0].kind == EntryKind::Settlement
+                    && settlement[0].reference_id == Some(intent.id)
+                    && audit.entity_type == PAYMENT_ENTITY
+                    && audit.entity_id == intent.id
+                    && audit.changes["status"]["after"] == "paid"
+            })
+            .times(1)
+            .returning(|intent, _, _| Ok(intent));
+        repository.expect_update_status().never();
+
+        let mut ledger_repository = MockLedgerRepository::new();
+        ledger_repository
+            .expect_find_account_by_owner()
+            .returning(|_, _, _| Ok(None));
+        ledger_repository.expect_create_account().returning(Ok);
+        ledger_repository.expect_post().never();
+
+        let intent = use_case(repository, ledger_repository)
+            .confirm(Uuid::new_v4(), Uuid::new_v4(), &AuditContext::default())
+            .await
+            .unwrap();
+
+        assert_eq!(intent.status, PaymentStatus::Paid);
+        assert!(intent.dispute_until.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_keeps_payment_unpaid_when_settlement_fails() {
+        let mut intent = pending_intent();
+        intent.mark_paid(Utc::now()).unwrap();
+
+        let mut repository = MockPaymentIntentRepository::new();
+        repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(intent.clone())));
+        repository.expect_settle().never();
+        repository.expect_update_status().never();
+
+        let mut ledger_repository = MockLedgerRepository::new();
+        ledger_repository
+            .expect_find_account_by_owner()
+            .returning(|_, _, _| Err(AppError::InternalError("ledger down".to_string())));
+
+        let result = use_case(repository, ledger_repository)
+            .confirm(Uuid::new_v4(), Uuid::new_v4(), &AuditContext::default())
+            .await;
+
+        assert!(matches!(result, Err(AppError::InternalError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reject_requires_reason_and_marked_payment() {
+        let intent = pending_intent();
+        let mut repository = MockPaymentIntentRepository::new();
+        repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(intent.clone())));
+        repository.expect_update_status().never();
+        let use_case = use_case(repository, MockLedgerRepository::new());
+
+        let result = use_case
+            .reject(
+                Uuid::new_v4(),
+                "  ".to_string(),
+                Uuid::new_v4(),
+                &AuditContext::default(),
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+
+        let result = use_case
+            .reject(
+                Uuid::new_v4(),
+                "No transfer".to_string(),
+                Uuid::new_v4(),
+                &AuditContext::default(),
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+    }
+}
@@ -0,0 +1,281 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+
+use crate::{
+    common::{error::AppError, money::Money},
+    domains::{
+        backoffice::domain::merchant::MerchantContext,
+        payments::{
+            app::assign_requisite_use_case::AssignRequisiteUseCase,
+            domain::{payment_intent::PaymentIntent, repository::PaymentIntentRepository},
+            dto::payment_intent_dto::CreatePaymentIntentRequest,
+        },
+    },
+};
+
+const MAX_EXTERNAL_ORDER_ID_LEN: usize = 255;
+
+pub struct CreatePaymentIntentUseCase {
+    payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+    assign_requisite_use_case: Arc<AssignRequisiteUseCase>,
+    default_ttl: Duration,
+    max_ttl: Duration,
+}
+
+impl CreatePaymentIntentUseCase {
+    pub fn new(
+        payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+        assign_requisite_use_case: Arc<AssignRequisiteUseCase>,
+        default_ttl: Duration,
+        max_ttl: Duration,
+    ) -> Self {
+        Self {
+            payment_intent_repository,
+            assign_requisite_use_case,
+            default_ttl,
+            max_ttl,
+        }
+    }
+
+    /// Opens a payment for the site the request was signed for and matches it to a trader.
+    /// A payment no trader can take yet stays `created` until the matching sweep picks it up.
+    pub async fn execute(
+        &self,
+        context: &MerchantContext,
+        request: CreatePaymentIntentRequest,
+    ) -> Result<PaymentIntent, AppError> {
+        tracing::debug!(
+            "Creating payment for order '{}' of site {}",
+            request.external_order_id,
+            context.site.id
+        );
+
+        let external_order_id = request.external_order_id.trim().to_string();
+        if external_order_id.is_empty() || external_order_id.len() > MAX_EXTERNAL_ORDER_ID_LEN {
+            return Err(AppError::ValidationError(format!(
+                "External order id must be 1 to {} characters",
+                MAX_EXTERNAL_ORDER_ID_LEN
+            )));
+        }
+
+        let amount = Money::parse(&request.amount, &request.currency)?;
+        if !amount.is_positive() {
+            return Err(AppError::ValidationError(
+                "Amount must be greater than zero".to_string(),
+            ));
+        }
+
+        let ttl = self.ttl(request.expires_in_minutes)?;
+
+        if self
+            .payment_intent_repository
+            .find_by_external_order_id(context.site.id, &external_order_id)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::ValidationError(format!(
+                "Payment for order '{}' already exists",
+                external_order_id
+            )));
+        }
+
+        let description = request
+            .description
+            .map(|description| description.trim().to_string())
+            .filter(|description| !description.is_empty());
+
+        let intent = PaymentIntent::new(
+            context.merchant.id,
+            context.site.id,
+            external_order_id,
+            amount,
+            request.payment_method,
+            description,
+            ttl,
+        );
+        let intent = self.payment_intent_repository.create(intent).await?;
+
+        tracing::info!(
+            "Payment {} created for order '{}' of site {}",
+            intent.id,
+            intent.external_order_id,
+            intent.site_id
+        );
+
+        // The payment is stored either way; matching failures are retried by the sweep
+        match self.assign_requisite_use_case.execute(intent.clone()).await {
+            Ok(intent) => Ok(intent),
+            Err(err) => {
+                tracing::warn!("Matching payment {} failed: {}", intent.id, err);
+                Ok(intent)
+            }
+        }
+    }
+
+    fn ttl(&self, expires_in_minutes: Option<i64>) -> Result<Duration, AppError> {
+        let Some(minutes) = expires_in_minutes else {
+            return Ok(self.default_ttl);
+        };
+
+        // Checked before building the duration, which panics on huge values
+        let max_minutes = self.max_ttl.num_minutes();
+        if !(1..=max_minutes).contains(&minutes) {
+            return Err(AppError::ValidationError(format!(
+                "Expiry must be between 1 and {} minutes",
+                max_minutes
+            )));
+        }
+
+        Ok(Duration::minutes(minutes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::Currency;
+    use crate::common::secret_cipher::SecretCipher;
+    use crate::domains::{
+        backoffice::domain::merchant::{Merchant, Site, SiteCredentials},
+        payments::domain::{
+            matching::RoundRobinStrategy,
+            payment_intent::PaymentStatus,
+            repository::{MockMatchingRepository, MockPaymentIntentRepository},
+        },
+        traders::domain::repository::MockTraderRepository,
+    };
+
+    fn context() -> MerchantContext {
+        let merchant = Merchant::new("Acme".to_string(), None);
+        let site = Site::new(
+            merchant.id,
+            "Shop".to_string(),
+            "https://acme.com".to_string(),
+            "https://acme.com/callback".to_string(),
+            "https://acme.com/ok".to_string(),
+            "https://acme.com/fail".to_string(),
+        );
+        let credentials = SiteCredentials::new(site.id, "pk".to_string(), "enc".to_string());
+
+        MerchantContext {
+            merchant,
+            site,
+            credentials,
+        }
+    }
+
+    fn request(amount: &str, currency: &str) -> CreatePaymentIntentRequest {
+        CreatePaymentIntentRequest {
+            external_order_id: " order-1 ".to_string(),
+            amount: amount.to_string(),
+            currency: currency.to_string(),
+            payment_method: None,
+            description: None,
+            expires_in_minutes: None,
+        }
+    }
+
+    fn use_case(repository: MockPaymentIntentRepository) -> CreatePaymentIntentUseCase {
+        // No trader online; payments stay created
+        let mut matching_repository = MockMatchingRepository::new();
+        matching_repository
+            .expect_find_candidates()
+            .returning(|_, _| Ok(vec![]));
+        let repository = Arc::new(repository);
+        let assign_requisite_use_case = AssignRequisiteUseCase::new(
+            repository.clone(),
+            Arc::new(matching_repository),
+            Arc::new(MockTraderRepository::new()),
+            Arc::new(SecretCipher::new("test-key")),
+            Arc::new(RoundRobinStrategy),
+        );
+
+        CreatePaymentIntentUseCase::new(
+            repository,
+            Arc::new(assign_requisite_use_case),
+            Duration::minutes(30),
+            Duration::hours(24),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_create_payment_intent() {
+        let context = context();
+        let mut repository = MockPaymentIntentRepository::new();
+        repository
+            .expect_find_by_external_order_id()
+            .returning(|_, _| Ok(None));
+        repository.expect_create().times(1).returning(Ok);
+
+        let intent = use_case(repository)
+            .execute(&context, request("10.50", "usd"))
+            .await
+            .unwrap();
+
+        assert_eq!(intent.site_id, context.site.id);
+        assert_eq!(intent.merchant_id, context.merchant.id);
+        assert_eq!(intent.external_order_id, "order-1");
+        assert_eq!(intent.amount, Money::new(1050, Currency::Usd));
+        assert_eq!(intent.status, PaymentStatus::Created);
+        assert_eq!(intent.expires_at - intent.created_at, Duration::minutes(30));
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_invalid_input() {
+        let context = context();
+
+        for (request, expires_in_minutes) in [
+            (request("0", "USD"), None),
+            (request("-1.00", "USD"), None),
+            (request("1.001", "USD"), None),
+            (request("1.5", "JPY"), None),
+            (request("100", "US"), None),
+            (request("100", "XXX"), None),
+            (request("100", "USD"), Some(0)),
+            (request("100", "USD"), Some(24 * 60 + 1)),
+            (request("100", "USD"), Some(i64::MAX)),
+            (request("100", "USD"), Some(i64::MIN)),
+        ] {
+            let mut repository = MockPaymentIntentRepository::new();
+            repository.expect_create().never();
+
+            let result = use_case(repository)
+                .execute(
+                    &context,
+                    CreatePaymentIntentRequest {
+                        expires_in_minutes,
+                        ..request
+                    },
+                )
+                .await;
+            assert!(matches!(result, Err(AppError::ValidationError(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_duplicate_order() {
+        let context = context();
+        let existing = PaymentIntent::new(
+            context.merchant.id,
+            context.site.id,
+            "order-1".to_string(),
+            Money::new(1000, Currency::Usd),
+            None,
+            None,
+            Duration::minutes(30),
+        );
+
+        let mut repository = MockPaymentIntentRepository::new();
+        repository
+            .expect_find_by_external_order_id()
+            .returning(move |_, _| Ok(Some(existing.clone())));
+        repository.expect_create().never();
+
+        let result = use_case(repository)
+            .execute(&context, request("10", "USD"))
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::{
+    common::error::AppError, domains::payments::domain::repository::PaymentIntentRepository,
+};
+
+pub struct ExpirePaymentIntentsUseCase {
+    payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+}
+
+impl ExpirePaymentIntentsUseCase {
+    pub fn new(payment_intent_repository: Arc<dyn PaymentIntentRepository>) -> Self {
+        Self {
+            payment_intent_repository,
+        }
+    }
+
+    /// Expires every open payment whose expiry time has passed
+    pub async fn execute(&self) -> Result<u64, AppError> {
+        let expired = self
+            .payment_intent_repository
+            .expire_due(Utc::now())
+            .await?;
+
+        if !expired.is_empty() {
+            tracing::info!("Expired {} overdue payments", expired.len());
+        }
+
+        Ok(expired.len() as u64)
+    }
+}
@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::payments::domain::{
+        payment_intent::{PaymentIntent, PaymentIntentFilter},
+        repository::PaymentIntentRepository,
+    },
+};
+
+pub struct GetPaymentIntentUseCase {
+    payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+}
+
+impl GetPaymentIntentUseCase {
+    pub fn new(payment_intent_repository: Arc<dyn PaymentIntentRepository>) -> Self {
+        Self {
+            payment_intent_repository,
+        }
+    }
+
+    pub async fn execute(&self, payment_id: Uuid) -> Result<PaymentIntent, AppError> {
+        tracing::debug!("Fetching payment {}", payment_id);
+
+        let intent = self
+            .payment_intent_repository
+            .find_by_id(payment_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Payment {} not found",
+                payment_id
+            )))?;
+
+        apply_expiry(self.payment_intent_repository.as_ref(), intent).await
+    }
+
+    /// Payments of other sites are reported as missing
+    pub async fn for_site(
+        &self,
+        site_id: Uuid,
+        payment_id: Uuid,
+    ) -> Result<PaymentIntent, AppError> {
+        let intent = self.execute(payment_id).await?;
+
+        if intent.site_id != site_id {
+            return Err(AppError::NotFound(format!(
+                "Payment {} not found",
+                payment_id
+            )));
+        }
+
+        Ok(intent)
+    }
+
+    pub async fn by_external_order_id(
+        &self,
+        site_id: Uuid,
+        external_order_id: &str,
+    ) -> Result<PaymentIntent, AppError> {
+        tracing::debug!(
+            "Fetching payment for order '{}' of site {}",
+            external_order_id,
+            site_id
+        );
+
+        let intent = self
+            .payment_intent_repository
+            .find_by_external_order_id(site_id, external_order_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Payment for order '{}' not found",
+                external_order_id
+            )))?;
+
+        apply_expiry(self.payment_intent_repository.as_ref(), intent).await
+    }
+
+    pub async fn list(
+        &self,
+        filter: PaymentIntentFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PaymentIntent>, AppError> {
+        tracing::debug!(
+            "Listing payments with {:?}, limit={}, offset={}",
+            filter,
+            limit,
+            offset
+        );
+
+        if limit > 100 {
+            return Err(AppError::ValidationError(
+                "Limit cannot exceed 100".to_string(),
+            ));
+        }
+
+        // The sweep may lag behind; filters must not see overdue payments as open
+        self.payment_intent_repository
+            .expire_due(Utc::now())
+            .await?;
+
+        let intents = self
+            .payment_intent_repository
+            .list(filter, limit, offset)
+            .await?;
+
+        Ok(intents)
+    }
+}
+
+/// Persists the expiry of an overdue intent instead of waiting for the next sweep
+pub(crate) async fn apply_expiry(
+    payment_intent_repository: &dyn PaymentIntentRepository,
+    mut intent: PaymentIntent,
+) -> Result<PaymentIntent, AppError> {
+    if !intent.expire_if_due(Utc::now()) {
+        return Ok(intent);
+    }
+
+    tracing::info!("Payment {} expired", intent.id);
+
+    payment_intent_repository.update_status(intent, None).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::{Currency, Money};
+    use crate::domains::payments::domain::{
+        payment_intent::PaymentStatus, repository::MockPaymentIntentRepository,
+    };
+    use chrono::Duration;
+
+    fn intent(ttl: Duration) -> PaymentIntent {
+        PaymentIntent::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "order-1".to_string(),
+            Money::new(1000, Currency::Usd),
+            None,
+            None,
+            ttl,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_overdue_payment_is_expired_on_read() {
+        let overdue = intent(Duration::minutes(-1));
+        let id = overdue.id;
+
+        let mut repository = MockPaymentIntentRepository::new();
+        repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(overdue.clone())));
+        repository
+            .expect_update_status()
+            .withf(|intent, audit| intent.status == PaymentStatus::Expired && audit.is_none())
+            .times(1)
+            .returning(|intent, _| Ok(intent));
+
+        let intent = GetPaymentIntentUseCase::new(Arc::new(repository))
+            .execute(id)
+            .await
+            .unwrap();
+
+        assert_eq!(intent.status, PaymentStatus::Expired);
+    }
+
+    #[tokio::test]
+    async fn test_other_site_payment_is_not_found() {
+        let intent = intent(Duration::minutes(30));
+        let id = intent.id;
+
+        let mut repository = MockPaymentIntentRepository::new();
+        repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(intent.clone())));
+
+        let result = GetPaymentIntentUseCase::new(Arc::new(repository))
+            .for_site(Uuid::new_v4(), id)
+            .await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_limit_is_capped() {
+        let mut repository = MockPaymentIntentRepository::new();
+        repository.expect_list().never();
+
+        let result = GetPaymentIntentUseCase::new(Arc::new(repository))
+            .list(PaymentIntentFilter::default(), 101, 0)
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,295 @@
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    common::{error::AppError, storage::FileStorage},
+    domains::payments::domain::{
+        evidence::PaymentEvidence,
+        payment_intent::{PaymentIntent, PaymentStatus},
+        repository::{PaymentEvidenceRepository, PaymentIntentRepository},
+    },
+};
+
+/// Files a payment can collect before it is considered spam
+const MAX_EVIDENCE_PER_PAYMENT: usize = 10;
+
+pub struct PaymentEvidenceUseCase {
+    payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+    evidence_repository: Arc<dyn PaymentEvidenceRepository>,
+    storage: Arc<dyn FileStorage>,
+    max_bytes: usize,
+}
+
+impl PaymentEvidenceUseCase {
+    pub fn new(
+        payment_intent_repository: Arc<dyn PaymentIntentRepository>,
+        evidence_repository: Arc<dyn PaymentEvidenceRepository>,
+        storage: Arc<dyn FileStorage>,
+        max_bytes: usize,
+    ) -> Self {
+        Self {
+            payment_intent_repository,
+            evidence_repository,
+            storage,
+            max_bytes,
+        }
+    }
+
+    /// Attaches proof of the transfer to an open payment of the site
+    pub async fn upload(
+        &self,
+        site_id: Uuid,
+        payment_id: Uuid,
+        file_name: &str,
+        content_type: &str,
+        content: &[u8],
+    ) -> Result<PaymentEvidence, AppError> {
+        tracing::debug!(
+            "Uploading evidence '{}' ({} bytes) for payment {}",
+            file_name,
+            content.len(),
+            payment_id
+        );
+
+        if content.is_empty() || content.len() > self.max_bytes {
+            return Err(AppError::ValidationError(format!(
+                "Evidence must be 1 to {} bytes",
+                self.max_bytes
+            )));
+        }
+
+        let intent = self.find(payment_id).await?;
+        if intent.site_id != site_id {
+            return Err(AppError::NotFound(format!(
+                "Payment {} not found",
+                payment_id
+            )));
+        }
+        if !matches!(
+            intent.status,
+            PaymentStatus::Pending | PaymentStatus::Confirming
+        ) {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} is {}; evidence is only accepted while it is pending or confirming",
+                intent.id, intent.status
+            )));
+        }
+        if self
+            .evidence_repository
+            .list_by_payment(payment_id)
+            .await?
+            .len()
+            >= MAX_EVIDENCE_PER_PAYMENT
+        {
+            return Err(AppError::ValidationError(format!(
+                "Payment {} already has {} evidence files",
+                payment_id, MAX_EVIDENCE_PER_PAYMENT
+            )));
+        }
+
+        let evidence = PaymentEvidence::new(
+            payment_id,
+            file_name,
+            content_type,
+            content.len() as i64,
+            hex::encode(Sha256::digest(content)),
+        )?;
+
+        // Written first: a stored file without a record is harmless, the reverse is not
+        self.storage.put(&evidence.storage_key, content).await?;
+        let evidence = self.evidence_repository.create(evidence).await?;
+
+        tracing::info!(
+            "Evidence {} uploaded for payment {}",
+            evidence.id,
+            payment_id
+        );
+
+        Ok(evidence)
+    }
+
+    pub async fn list(&self, payment_id: Uuid) -> Result<Vec<PaymentEvidence>, AppError> {
+        tracing::debug!("Listing evidence of payment {}", payment_id);
+
+        self.find(payment_id).await?;
+        self.evidence_repository.list_by_payment(payment_id).await
+    }
+
+    /// Evidence record with its content
+    pub async fn download(
+        &self,
+        payment_id: Uuid,
+        evidence_id: Uuid,
+    ) -> Result<(PaymentEvidence, Vec<u8>), AppError> {
+        tracing::debug!(
+            "Downloading evidence {} of payment {}",
+            evidence_id,
+            payment_id
+        );
+
+        let evidence = self
+            .evidence_repository
+            .find_by_id(evidence_id)
+            .await?
+            .filter(|evidence| evidence.payment_id == payment_id)
+            .ok_or(AppError::NotFound(format!(
+                "Evidence {} not found",
+                evidence_id
+            )))?;
+        let content = self.storage.get(&evidence.storage_key).await?;
+
+        Ok((evidence, content))
+    }
+
+    async fn find(&self, payment_id: Uuid) -> Result<PaymentIntent, AppError> {
+        self.payment_intent_repository
+            .find_by_id(payment_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Payment {} not found",
+                payment_id
+            )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::{Currency, Money};
+    use crate::domains::payments::domain::repository::{
+        MockPaymentEvidenceRepository, MockPaymentIntentRepository,
+    };
+    use async_trait::async_trait;
+    use chrono::{Duration, Utc};
+    use std::{collections::HashMap, sync::Mutex};
+
+    #[derive(Default)]
+    struct MemoryStorage {
+        files: Mutex<HashMap<String, Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl FileStorage for MemoryStorage {
+        async fn put(&self, key: &str, content: &[u8]) -> Result<(), AppError> {
+            self.files
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), content.to_vec());
+            Ok(())
+        }
+
+        async fn get(&self, key: &str) -> Result<Vec<u8>, AppError> {
+            self.files
+                .lock()
+                .unwrap()
+                .get(key)
+                .cloned()
+                .ok_or(AppError::NotFound(format!("File '{}' not found", key)))
+        }
+    }
+
+    fn pending_intent() -> PaymentIntent {
+        let mut intent = PaymentIntent::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "order-1".to_string(),
+            Money::new(10_000, Currency::Usd),
+            None,
+            None,
+            Duration::minutes(30),
+        );
+        intent
+            .assign(Uuid::new_v4(), Uuid::new_v4(), Utc::now())
+            .unwrap();
+        intent
+    }
+
+    fn use_case(
+        intent: PaymentIntent,
+        evidence_repository: MockPaymentEvidenceRepository,
+        storage: Arc<MemoryStorage>,
+    ) -> PaymentEvidenceUseCase {
+        let mut repository = MockPaymentIntentRepository::new();
+        repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(intent.clone())));
+
+        PaymentEvidenceUseCase::new(
+            Arc::new(repository),
+            Arc::new(evidence_repository),
+            storage,
+            1024,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_upload_stores_content_and_record() {
+        let intent = pending_intent();
+        let (site_id, payment_id) = (intent.site_id, intent.id);
+        let mut evidence_repository = MockPaymentEvidenceRepository::new();
+        evidence_repository
+            .expect_list_by_payment()
+            .returning(|_| Ok(vec![]));
+        evidence_repository.expect_create().times(1).returning(Ok);
+        let storage = Arc::new(MemoryStorage::default());
+
+        let evidence = use_case(intent, evidence_repository, storage.clone())
+            .upload(site_id, payment_id, "receipt.png", "image/png", b"png")
+            .await
+            .unwrap();
+
+        assert_eq!(evidence.size_bytes, 3);
+        assert_eq!(evidence.sha256, hex::encode(Sha256::digest(b"png")));
+        assert_eq!(
+            storage.get(&evidence.storage_key).await.unwrap(),
+            b"png".to_vec()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_bad_files_and_closed_payments() {
+        let intent = pending_intent();
+        let (site_id, payment_id) = (intent.site_id, intent.id);
+        let mut evidence_repository = MockPaymentEvidenceRepository::new();
+        evidence_repository
+            .expect_list_by_payment()
+            .returning(|_| Ok(vec![]));
+        evidence_repository.expect_create().never();
+        let use_case = use_case(
+            intent.clone(),
+            evidence_repository,
+            Arc::new(MemoryStorage::default()),
+        );
+
+        for (content, content_type) in [
+            (vec![], "image/png"),
+            (vec![0; 1025], "image/png"),
+            (vec![0; 10], "text/html"),
+        ] {
+            let result = use_case
+                .upload(site_id, payment_id, "a", content_type, &content)
+                .await;
+            assert!(matches!(result, Err(AppError::ValidationError(_))));
+        }
+
+        let result = use_case
+            .upload(Uuid::new_v4(), payment_id, "a.png", "image/png", b"png")
+            .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let mut intent = intent;
+        intent.transition_to(PaymentStatus::Cancelled).unwrap();
+        let mut evidence_repository = MockPaymentEvidenceRepository::new();
+        evidence_repository.expect_create().never();
+        let result = self::use_case(
+            intent,
+            evidence_repository,
+            Arc::new(MemoryStorage::default()),
+        )
+        .upload(site_id, payment_id, "a.png", "image/png", b"png")
+        .await;
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+    }
+}
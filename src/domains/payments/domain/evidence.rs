@@ -0,0 +1,108 @@
+use crate::common::error::AppError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// File types accepted as proof of a transfer: receipt screenshots and bank statements
+pub const ALLOWED_EVIDENCE_TYPES: [&str; 3] = ["image/png", "image/jpeg", "application/pdf"];
+
+const MAX_FILE_NAME_LEN: usize = 255;
+
+/// Proof of a transfer uploaded for a payment, e.g. a receipt image.
+/// The content lives in file storage under `storage_key`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PaymentEvidence {
+    pub id: Uuid,
+    pub payment_id: Uuid,
+    /// Name of the uploaded file, without any directory part
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Hex SHA-256 of the content
+    pub sha256: String,
+    pub storage_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PaymentEvidence {
+    pub fn new(
+        payment_id: Uuid,
+        file_name: &str,
+        content_type: &str,
+        size_bytes: i64,
+        sha256: String,
+    ) -> Result<Self, AppError> {
+        if !ALLOWED_EVIDENCE_TYPES.contains(&content_type) {
+            return Err(AppError::ValidationError(format!(
+                "Evidence must be one of {}",
+                ALLOWED_EVIDENCE_TYPES.join(", ")
+            )));
+        }
+
+        let file_name = sanitize_file_name(file_name);
+        let id = Uuid::new_v4();
+
+        Ok(Self {
+            id,
+            payment_id,
+            file_name,
+            content_type: content_type.to_string(),
+            size_bytes,
+            sha256,
+            storage_key: format!("payments/{}/evidence/{}", payment_id, id),
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// Keeps the last path segment of a client-supplied name and drops control characters,
+/// so it is safe to echo in a `Content-Disposition` header
+pub(crate) fn sanitize_file_name(file_name: &str) -> String {
+    let name: String = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(MAX_FILE_NAME_LEN)
+        .collect();
+    let name = name.trim();
+
+    if name.is_empty() {
+        "evidence".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_evidence_validates_type_and_name() {
+        let payment_id = Uuid::new_v4();
+
+        let evidence = PaymentEvidence::new(
+            payment_id,
+            "C:\\Users\\jane\\receipt\n\".png",
+            "image/png",
+            10,
+            "ab".to_string(),
+        )
+        .unwrap();
+        assert_eq!(evidence.file_name, "receipt.png");
+        assert_eq!(
+            evidence.storage_key,
+            format!("payments/{}/evidence/{}", payment_id, evidence.id)
+        );
+
+        let evidence =
+            PaymentEvidence::new(payment_id, "../", "application/pdf", 10, "ab".to_string())
+                .unwrap();
+        assert_eq!(evidence.file_name, "evidence");
+
+        let result = PaymentEvidence::new(payment_id, "a.html", "text/html", 10, "ab".to_string());
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
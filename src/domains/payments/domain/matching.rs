@@ -0,0 +1,266 @@
+use crate::common::money::Money;
+use crate::domains::traders::domain::requisite::RequisiteKind;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+/// Active requisite of an online trader that could receive a payment,
+/// with the usage figures the strategies rank on. All amounts are in the payment currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchCandidate {
+    pub trader_id: Uuid,
+    pub requisite_id: Uuid,
+    pub daily_limit: Money,
+    pub monthly_limit: Money,
+    /// Open and paid payments assigned to the requisite since the start of the day (UTC)
+    pub daily_used: Money,
+    /// Same as `daily_used`, since the start of the month (UTC)
+    pub monthly_used: Money,
+    /// Trader's security deposit balance
+    pub deposit: Money,
+    /// Trader's open payments (pending or confirming) and paid ones not settled yet;
+    /// the deposit has to cover them all
+    pub exposure: Money,
+    /// Number of open payments of the trader
+    pub open_payments: i64,
+    pub paid_payments: i64,
+    /// Payments of the trader that failed or expired after being assigned
+    pub failed_payments: i64,
+    pub last_assigned_at: Option<DateTime<Utc>>,
+}
+
+impl MatchCandidate {
+    /// Whether both limits of the requisite and the trader's free deposit leave room for `amount`
+    pub fn can_take(&self, amount: Money) -> bool {
+        let fits = |used: Money, cap: Money| {
+            used.checked_add(amount)
+                .is_ok_and(|total| total.minor_units() <= cap.minor_units())
+        };
+
+        fits(self.daily_used, self.daily_limit)
+            && fits(self.monthly_used, self.monthly_limit)
+            && fits(self.exposure, self.deposit)
+    }
+
+    /// Share of the trader's finished payments that were paid.
+    /// Smoothed so a trader without history starts at one half instead of zero.
+    pub fn success_rate(&self) -> f64 {
+        (self.paid_payments + 1) as f64 / (self.paid_payments + self.failed_payments + 2) as f64
+    }
+
+    /// Share of the daily limit already used, between 0 and 1
+    fn daily_usage(&self) -> f64 {
+        self.daily_used.minor_units() as f64 / self.daily_limit.minor_units().max(1) as f64
+    }
+}
+
+/// Where the customer of a matched payment sends the money
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentDetails {
+    pub kind: RequisiteKind,
+    pub bank_name: String,
+    pub holder_name: String,
+    /// Full card or account number, decrypted for the customer
+    pub number: String,
+}
+
+/// Decides which trader gets a payment when several can take it
+pub trait MatchingStrategy: Send + Sync {
+    /// Orders the candidates from most to least preferred. The engine assigns the first one
+    /// it can lock and falls through to the next when a candidate is taken concurrently.
+    fn rank(&self, candidates: Vec<MatchCandidate>) -> Vec<MatchCandidate>;
+}
+
+/// Trader that has waited longest since its last payment goes first; never-assigned traders lead.
+/// Requisites of the same trader are ordered by the least used daily limit.
+pub struct RoundRobinStrategy;
+
+impl MatchingStrategy for RoundRobinStrategy {
+    fn rank(&self, mut candidates: Vec<MatchCandidate>) -> Vec<MatchCandidate> {
+        candidates.sort_by(|a, b| {
+            // None sorts before Some
+            a.last_assigned_at
+                .cmp(&b.last_assigned_at)
+                .then_with(|| compare_daily_usage(a, b))
+        });
+        candidates
+    }
+}
+
+/// Trader with the fewest pending payments goes first, then the least used daily limit
+pub struct LeastLoadedStrategy;
+
+impl MatchingStrategy for LeastLoadedStrategy {
+    fn rank(&self, mut candidates: Vec<MatchCandidate>) -> Vec<MatchCandidate> {
+        candidates.sort_by(|a, b| {
+            a.open_payments
+                .cmp(&b.open_payments)
+                .then_with(|| compare_daily_usage(a, b))
+        });
+        candidates
+    }
+}
+
+/// Random order weighted by success rate: a trader paying out twice as reliably
+/// is twice as likely to come first, while weaker traders still get some traffic.
+pub struct SuccessRateStrategy;
+
+impl SuccessRateStrategy {
+    /// Weighted shuffle (Efraimidis-Spirakis): sort by `u^(1/weight)` with `u` uniform in (0, 1)
+    pub fn rank_with<R: Rng>(
+        &self,
+        candidates: Vec<MatchCandidate>,
+        rng: &mut R,
+    ) -> Vec<MatchCandidate> {
+        let mut keyed: Vec<(f64, MatchCandidate)> = candidates
+            .into_iter()
+            .map(|candidate| {
+                let u: f64 = rng.gen_range(f64::EPSILON..1.0);
+                (u.powf(1.0 / candidate.success_rate()), candidate)
+            })
+            .collect();
+
+        keyed.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        keyed.into_iter().map(|(_, candidate)| candidate).collect()
+    }
+}
+
+impl MatchingStrategy for SuccessRateStrategy {
+    fn rank(&self, candidates: Vec<MatchCandidate>) -> Vec<MatchCandidate> {
+        self.rank_with(candidates, &mut rand::thread_rng())
+    }
+}
+
+fn compare_daily_usage(a: &MatchCandidate, b: &MatchCandidate) -> Ordering {
+    a.daily_usage()
+        .partial_cmp(&b.daily_usage())
+        .unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::Currency;
+    use chrono::Duration;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn usd(minor_units: i64) -> Money {
+        Money::new(minor_units, Currency::Usd)
+    }
+
+    fn candidate() -> MatchCandidate {
+        MatchCandidate {
+            trader_id: Uuid::new_v4(),
+            requisite_id: Uuid::new_v4(),
+            daily_limit: usd(10_000),
+            monthly_limit: usd(100_000),
+            daily_used: usd(0),
+            monthly_used: usd(0),
+            deposit: usd(50_000),
+            exposure: usd(0),
+            open_payments: 0,
+            paid_payments: 0,
+            failed_payments: 0,
+            last_assigned_at: None,
+        }
+    }
+
+    #[test]
+    fn test_can_take_respects_limits_and_deposit() {
+        let candidate = MatchCandidate {
+            daily_used: usd(6_000),
+            monthly_used: usd(90_000),
+            exposure: usd(45_000),
+            ..candidate()
+        };
+
+        assert!(candidate.can_take(usd(4_000)));
+        // Daily limit
+        assert!(!candidate.can_take(usd(4_001)));
+        // Monthly limit
+        let candidate = MatchCandidate {
+            monthly_used: usd(97_000),
+            ..candidate
+        };
+        assert!(!candidate.can_take(usd(3_500)));
+        // Free deposit
+        let candidate = MatchCandidate {
+            exposure: usd(48_000),
+            ..candidate
+        };
+        assert!(!candidate.can_take(usd(2_500)));
+        assert!(candidate.can_take(usd(2_000)));
+    }
+
+    #[test]
+    fn test_round_robin_prefers_longest_idle_trader() {
+        let now = Utc::now();
+        let recent = MatchCandidate {
+            last_assigned_at: Some(now),
+            ..candidate()
+        };
+        let idle = MatchCandidate {
+            last_assigned_at: Some(now - Duration::minutes(10)),
+            ..candidate()
+        };
+        let fresh = candidate();
+
+        let ranked = RoundRobinStrategy.rank(vec![recent.clone(), idle.clone(), fresh.clone()]);
+
+        assert_eq!(ranked, vec![fresh, idle, recent]);
+    }
+
+    #[test]
+    fn test_least_loaded_prefers_fewest_pending_then_usage() {
+        let busy = MatchCandidate {
+            open_payments: 3,
+            ..candidate()
+        };
+        let used = MatchCandidate {
+            open_payments: 1,
+            daily_used: usd(5_000),
+            ..candidate()
+        };
+        let idle = MatchCandidate {
+            open_payments: 1,
+            daily_used: usd(1_000),
+            ..candidate()
+        };
+
+        let ranked = LeastLoadedStrategy.rank(vec![busy.clone(), used.clone(), idle.clone()]);
+
+        assert_eq!(ranked, vec![idle, used, busy]);
+    }
+
+    #[test]
+    fn test_success_rate_favours_reliable_traders() {
+        let reliable = MatchCandidate {
+            paid_payments: 98,
+            failed_payments: 0,
+            ..candidate()
+        };
+        let unreliable = MatchCandidate {
+            paid_payments: 0,
+            failed_payments: 98,
+            ..candidate()
+        };
+        assert_eq!(reliable.success_rate(), 0.99);
+        assert_eq!(unreliable.success_rate(), 0.01);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let reliable_first = (0..200)
+            .filter(|_| {
+                let ranked = SuccessRateStrategy
+                    .rank_with(vec![unreliable.clone(), reliable.clone()], &mut rng);
+                ranked[0].trader_id == reliable.trader_id
+            })
+            .count();
+
+        assert!(
+            reliable_first > 180,
+            "reliable first {} times",
+            reliable_first
+        );
+    }
+}
@@ -0,0 +1,543 @@
+use crate::common::{error::AppError, money::Money, time_formater};
+use crate::domains::traders::domain::requisite::RequisiteKind;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{fmt, str::FromStr};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Request of a merchant site to collect `amount` for one of its orders
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PaymentIntent {
+    pub id: Uuid,
+    pub merchant_id: Uuid,
+    pub site_id: Uuid,
+    /// Order reference in the merchant's system, unique per site
+    pub external_order_id: String,
+    pub amount: Money,
+    /// Kind of requisite the customer wants to pay to; any kind when unset
+    pub payment_method: Option<RequisiteKind>,
+    pub status: PaymentStatus,
+    pub description: Option<String>,
+    /// Trader requisite the customer pays to, once the payment is matched
+    pub assignment: Option<RequisiteAssignment>,
+    /// When the customer reported the transfer as sent
+    pub marked_paid_at: Option<DateTime<Utc>>,
+    /// When the payment went to the Support queue for lack of a trader decision
+    pub escalated_at: Option<DateTime<Utc>>,
+    /// When the trader confirmed or rejected the transfer
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// Disputes can be opened on the payment until then
+    pub dispute_until: Option<DateTime<Utc>>,
+    pub rejection_reason: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct RequisiteAssignment {
+    pub trader_id: Uuid,
+    pub requisite_id: Uuid,
+    pub assigned_at: DateTime<Utc>,
+}
+
+impl PaymentIntent {
+    /// New intents wait in `created` until they are picked up or expire
+    pub fn new(
+        merchant_id: Uuid,
+        site_id: Uuid,
+        external_order_id: String,
+        amount: Money,
+        payment_method: Option<RequisiteKind>,
+        description: Option<String>,
+        ttl: Duration,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            merchant_id,
+            site_id,
+            external_order_id,
+            amount,
+            payment_method,
+            status: PaymentStatus::Created,
+            description,
+            assignment: None,
+            marked_paid_at: None,
+            escalated_at: None,
+            resolved_at: None,
+            dispute_until: None,
+            rejection_reason: None,
+            expires_at: now + ttl,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Fields recorded in audit entries of trader decisions
+    pub fn audit_snapshot(&self) -> Value {
+        json!({
+            "status": self.status,
+            "resolved_at": self.resolved_at.as_ref().map(time_formater::format),
+            "dispute_until": self.dispute_until.as_ref().map(time_formater::format),
+            "rejection_reason": self.rejection_reason,
+        })
+    }
+
+    /// Allowed transitions:
+    /// - created -> pending, expired, cancelled
+    /// - pending -> confirming, paid, failed, expired, cancelled
+    /// - confirming -> paid, failed
+    ///
+    /// Final statuses never change again.
+    pub fn transition_to(&mut self, target: PaymentStatus) -> Result<(), AppError> {
+        use PaymentStatus::*;
+
+        let allowed = matches!(
+            (self.status, target),
+            (Created, Pending | Expired | Cancelled)
+                | (Pending, Confirming | Paid | Failed | Expired | Cancelled)
+                | (Confirming, Paid | Failed)
+        );
+        if !allowed {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment cannot move from {} to {}",
+                self.status, target
+            )));
+        }
+
+        self.status = target;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Hands the payment to a trader requisite, moving it from `created` to `pending`.
+    /// A payment is matched at most once and never after its expiry time.
+    pub fn assign(
+        &mut self,
+        trader_id: Uuid,
+        requisite_id: Uuid,
+        at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        if self.assignment.is_some() {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} is already assigned to a requisite",
+                self.id
+            )));
+        }
+        if self.is_due_to_expire(at) {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} has expired",
+                self.id
+            )));
+        }
+        if self.status != PaymentStatus::Created {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} is {}; only created payments are assigned",
+                self.id, self.status
+            )));
+        }
+
+        self.transition_to(PaymentStatus::Pending)?;
+        self.assignment = Some(RequisiteAssignment {
+            trader_id,
+            requisite_id,
+            assigned_at: at,
+        });
+        self.updated_at = at;
+
+        Ok(())
+    }
+
+    /// Customer reports the transfer as sent; the payment waits for the trader from now on
+    /// and no longer expires
+    pub fn mark_paid(&mut self, at: DateTime<Utc>) -> Result<(), AppError> {
+        if self.is_due_to_expire(at) {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} has expired",
+                self.id
+            )));
+        }
+        if self.status != PaymentStatus::Pending {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} is {}; only pending payments can be marked as paid",
+                self.id, self.status
+            )));
+        }
+
+        self.transition_to(PaymentStatus::Confirming)?;
+        self.marked_paid_at = Some(at);
+        self.updated_at = at;
+
+        Ok(())
+    }
+
+    /// Merchant withdraws a payment the customer has not reported as paid yet
+    pub fn cancel(&mut self, at: DateTime<Utc>) -> Result<(), AppError> {
+        if self.is_due_to_expire(at) {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} has expired",
+                self.id
+            )));
+        }
+        if !matches!(self.status, PaymentStatus::Created | PaymentStatus::Pending) {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} is {}; only created and pending payments can be cancelled",
+                self.id, self.status
+            )));
+        }
+
+        self.transition_to(PaymentStatus::Cancelled)?;
+        self.updated_at = at;
+
+        Ok(())
+    }
+
+    /// Trader confirms the money arrived, with or without the customer marking it paid
+    pub fn confirm(&mut self, at: DateTime<Utc>, dispute_window: Duration) -> Result<(), AppError> {
+        if self.status == PaymentStatus::Pending && self.is_due_to_expire(at) {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} has expired",
+                self.id
+            )));
+        }
+
+        self.transition_to(PaymentStatus::Paid)?;
+        self.resolve(at, dispute_window);
+
+        Ok(())
+    }
+
+    /// Trader reports that the money reported as sent never arrived
+    pub fn reject(
+        &mut self,
+        reason: String,
+        at: DateTime<Utc>,
+        dispute_window: Duration,
+    ) -> Result<(), AppError> {
+        if self.status != PaymentStatus::Confirming {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} is {}; only payments marked as paid can be rejected",
+                self.id, self.status
+            )));
+        }
+
+        self.transition_to(PaymentStatus::Failed)?;
+        self.rejection_reason = Some(reason);
+        self.resolve(at, dispute_window);
+
+        Ok(())
+    }
+
+    fn resolve(&mut self, at: DateTime<Utc>, dispute_window: Duration) {
+        self.resolved_at = Some(at);
+        self.dispute_until = Some(at + dispute_window);
+        self.updated_at = at;
+    }
+
+    /// Sends a payment the trader left unanswered for `timeout` to the Support queue;
+    /// returns whether it changed
+    pub fn escalate_if_due(&mut self, timeout: Duration, at: DateTime<Utc>) -> bool {
+        let due = self.status == PaymentStatus::Confirming
+            && self.escalated_at.is_none()
+            && self
+                .marked_paid_at
+                .is_some_and(|marked_paid_at| marked_paid_at + timeout <= at);
+        if due {
+            self.escalated_at = Some(at);
+            self.updated_at = at;
+        }
+
+        due
+    }
+
+    /// Whether a dispute can still be opened on the payment
+    pub fn is_disputable(&self, at: DateTime<Utc>) -> bool {
+        self.dispute_until.is_some_and(|until| at < until)
+    }
+
+    /// Only payments the customer may still be paying expire; a reported transfer waits
+    /// for the trader
+    pub fn is_due_to_expire(&self, at: DateTime<Utc>) -> bool {
+        matches!(self.status, PaymentStatus::Created | PaymentStatus::Pending)
+            && self.expires_at <= at
+    }
+
+    /// Moves an open intent past its expiry time to `expired`; returns whether it changed
+    pub fn expire_if_due(&mut self, at: DateTime<Utc>) -> bool {
+        if !self.is_due_to_expire(at) {
+            return false;
+        }
+
+        self.status = PaymentStatus::Expired;
+        self.updated_at = at;
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PaymentStatus {
+    Created,
+    Pending,
+    /// Customer reported the transfer; waiting for the trader to confirm it
+    Confirming,
+    Paid,
+    Failed,
+    Expired,
+    Cancelled,
+}
+
+impl PaymentStatus {
+    /// Value stored in the `status` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::Created => "created",
+            PaymentStatus::Pending => "pending",
+            PaymentStatus::Confirming => "confirming",
+            PaymentStatus::Paid => "paid",
+            PaymentStatus::Failed => "failed",
+            PaymentStatus::Expired => "expired",
+            PaymentStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn is_final(&self) -> bool {
+        !matches!(
+            self,
+            PaymentStatus::Created | PaymentStatus::Pending | PaymentStatus::Confirming
+        )
+    }
+}
+
+impl fmt::Display for PaymentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PaymentStatus {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "created" => Ok(PaymentStatus::Created),
+            "pending" => Ok(PaymentStatus::Pending),
+            "confirming" => Ok(PaymentStatus::Confirming),
+            "paid" => Ok(PaymentStatus::Paid),
+            "failed" => Ok(PaymentStatus::Failed),
+            "expired" => Ok(PaymentStatus::Expired),
+            "cancelled" => Ok(PaymentStatus::Cancelled),
+            other => Err(AppError::InternalError(format!(
+                "Unknown payment status '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Backoffice search criteria; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PaymentIntentFilter {
+    pub merchant_id: Option<Uuid>,
+    pub site_id: Option<Uuid>,
+    pub status: Option<PaymentStatus>,
+    pub external_order_id: Option<String>,
+    /// Payments assigned to this trader
+    pub trader_id: Option<Uuid>,
+    /// Payments in (or out of) the Support queue
+    pub escalated: Option<bool>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+}
+
+impl PaymentIntentFilter {
+    pub fn matches(&self, intent: &PaymentIntent) -> bool {
+        self.merchant_id.is_none_or(|id| intent.merchant_id == id)
+            && self.site_id.is_none_or(|id| intent.site_id == id)
+            && self.status.is_none_or(|status| intent.status == status)
+            && self
+                .external_order_id
+                .as_ref()
+                .is_none_or(|id| &intent.external_order_id == id)
+            && self.trader_id.is_none_or(|id| {
+                intent
+                    .assignment
+                    .is_some_and(|assignment| assignment.trader_id == id)
+            })
+            && self
+                .escalated
+                .is_none_or(|escalated| intent.escalated_at.is_some() == escalated)
+            && self
+                .created_from
+                .is_none_or(|from| intent.created_at >= from)
+            && self.created_to.is_none_or(|to| intent.created_at < to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::Currency;
+
+    fn intent() -> PaymentIntent {
+        PaymentIntent::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "order-1".to_string(),
+            Money::new(10_000, Currency::Usd),
+            None,
+            None,
+            Duration::minutes(30),
+        )
+    }
+
+    #[test]
+    fn test_lifecycle_to_paid() {
+        let mut intent = intent();
+        assert_eq!(intent.status, PaymentStatus::Created);
+
+        intent.transition_to(PaymentStatus::Pending).unwrap();
+        intent.transition_to(PaymentStatus::Paid).unwrap();
+
+        assert!(intent.status.is_final());
+        let result = intent.transition_to(PaymentStatus::Cancelled);
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+        assert_eq!(intent.status, PaymentStatus::Paid);
+    }
+
+    #[test]
+    fn test_created_cannot_be_paid_directly() {
+        let mut intent = intent();
+
+        let result = intent.transition_to(PaymentStatus::Paid);
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+
+        let result = intent.transition_to(PaymentStatus::Failed);
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+    }
+
+    #[test]
+    fn test_assign_moves_to_pending_once() {
+        let mut intent = intent();
+        let (trader_id, requisite_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        intent.assign(trader_id, requisite_id, Utc::now()).unwrap();
+        assert_eq!(intent.status, PaymentStatus::Pending);
+        assert_eq!(intent.assignment.unwrap().requisite_id, requisite_id);
+
+        let result = intent.assign(trader_id, Uuid::new_v4(), Utc::now());
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+        assert_eq!(intent.assignment.unwrap().requisite_id, requisite_id);
+    }
+
+    #[test]
+    fn test_expired_or_cancelled_cannot_be_assigned() {
+        let mut intent = intent();
+        let result = intent.assign(Uuid::new_v4(), Uuid::new_v4(), intent.expires_at);
+        assert!(result.is_err());
+
+        intent.transition_to(PaymentStatus::Cancelled).unwrap();
+        let result = intent.assign(Uuid::new_v4(), Uuid::new_v4(), Utc::now());
+        assert!(result.is_err());
+        assert!(intent.assignment.is_none());
+    }
+
+    #[test]
+    fn test_marked_paid_waits_for_trader_without_expiring() {
+        let mut intent = intent();
+        let result = intent.mark_paid(Utc::now());
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+
+        intent
+            .assign(Uuid::new_v4(), Uuid::new_v4(), Utc::now())
+            .unwrap();
+        let marked_at = Utc::now();
+        intent.mark_paid(marked_at).unwrap();
+        assert_eq!(intent.status, PaymentStatus::Confirming);
+        assert!(!intent.expire_if_due(intent.expires_at + Duration::hours(1)));
+
+        let timeout = Duration::minutes(15);
+        assert!(!intent.escalate_if_due(timeout, marked_at + Duration::minutes(14)));
+        assert!(intent.escalate_if_due(timeout, marked_at + timeout));
+        assert!(!intent.escalate_if_due(timeout, marked_at + Duration::hours(1)));
+        assert_eq!(intent.escalated_at, Some(marked_at + timeout));
+    }
+
+    #[test]
+    fn test_cancel_before_the_customer_reports_the_transfer() {
+        let mut created = intent();
+        let result = created.cancel(created.expires_at);
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+
+        created.cancel(Utc::now()).unwrap();
+        assert_eq!(created.status, PaymentStatus::Cancelled);
+        assert!(created.status.is_final());
+
+        let mut pending = intent();
+        pending
+            .assign(Uuid::new_v4(), Uuid::new_v4(), Utc::now())
+            .unwrap();
+        let mut marked = pending.clone();
+        pending.cancel(Utc::now()).unwrap();
+        assert_eq!(pending.status, PaymentStatus::Cancelled);
+
+        marked.mark_paid(Utc::now()).unwrap();
+        let result = marked.cancel(Utc::now());
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+        assert_eq!(marked.status, PaymentStatus::Confirming);
+    }
+
+    #[test]
+    fn test_trader_decision_opens_dispute_window() {
+        let window = Duration::hours(72);
+        let mut intent = intent();
+        intent
+            .assign(Uuid::new_v4(), Uuid::new_v4(), Utc::now())
+            .unwrap();
+
+        // Nothing was reported as sent yet
+        let result = intent.reject("No transfer".to_string(), Utc::now(), window);
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+
+        intent.mark_paid(Utc::now()).unwrap();
+        let rejected_at = Utc::now();
+        intent
+            .reject("No transfer".to_string(), rejected_at, window)
+            .unwrap();
+        assert_eq!(intent.status, PaymentStatus::Failed);
+        assert_eq!(intent.rejection_reason.as_deref(), Some("No transfer"));
+        assert!(intent.is_disputable(rejected_at + Duration::hours(71)));
+        assert!(!intent.is_disputable(rejected_at + window));
+
+        let result = intent.confirm(Utc::now(), window);
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+    }
+
+    #[test]
+    fn test_expire_if_due() {
+        let mut intent = intent();
+
+        assert!(!intent.expire_if_due(Utc::now()));
+        assert!(intent.expire_if_due(intent.expires_at));
+        assert_eq!(intent.status, PaymentStatus::Expired);
+        assert!(!intent.expire_if_due(intent.expires_at + Duration::hours(1)));
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            PaymentStatus::Created,
+            PaymentStatus::Pending,
+            PaymentStatus::Confirming,
+            PaymentStatus::Paid,
+            PaymentStatus::Failed,
+            PaymentStatus::Expired,
+            PaymentStatus::Cancelled,
+        ] {
+            assert_eq!(status.as_str().parse::<PaymentStatus>().unwrap(), status);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use super::{
+    evidence::PaymentEvidence,
+    matching::MatchCandidate,
+    payment_intent::{PaymentIntent, PaymentIntentFilter},
+};
+use crate::common::error::AppError;
+use crate::domains::audit::domain::audit_entry::AuditEntry;
+use crate::domains::ledger::domain::journal_entry::JournalEntry;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[async_trait]
+pub trait PaymentIntentRepository: Send + Sync {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PaymentIntent>, AppError>;
+    async fn find_by_external_order_id(
+        &self,
+        site_id: Uuid,
+        external_order_id: &str,
+    ) -> Result<Option<PaymentIntent>, AppError>;
+
+    async fn create(&self, intent: PaymentIntent) -> Result<PaymentIntent, AppError>;
+
+    /// Stores a status change and queues its `payment.{status}` webhook in one transaction,
+    /// together with `audit` for changes made from the backoffice
+    async fn update_status(
+        &self,
+        intent: PaymentIntent,
+        audit: Option<AuditEntry>,
+    ) -> Result<PaymentIntent, AppError>;
+
+    /// Like `update_status` for a payment that became paid, posting its `settlement`
+    /// entries in the same transaction
+    async fn settle(
+        &self,
+        intent: PaymentIntent,
+        settlement: Vec<JournalEntry>,
+        audit: AuditEntry,
+    ) -> Result<PaymentIntent, AppError>;
+
+    /// Newest first
+    async fn list(
+        &self,
+        filter: PaymentIntentFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PaymentIntent>, AppError>;
+
+    /// Marks every open intent with `expires_at <= at` as expired and queues their webhooks
+    /// in the same transaction; returns the intents it changed
+    async fn expire_due(&self, at: DateTime<Utc>) -> Result<Vec<PaymentIntent>, AppError>;
+
+    /// Sets `escalated_at = at` on confirming intents marked paid at or before
+    /// `marked_paid_before` and not escalated yet; returns how many changed
+    async fn escalate_due(
+        &self,
+        marked_paid_before: DateTime<Utc>,
+        at: DateTime<Utc>,
+    ) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+pub trait PaymentEvidenceRepository: Send + Sync {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PaymentEvidence>, AppError>;
+    async fn create(&self, evidence: PaymentEvidence) -> Result<PaymentEvidence, AppError>;
+
+    /// Oldest first
+    async fn list_by_payment(&self, payment_id: Uuid) -> Result<Vec<PaymentEvidence>, AppError>;
+}
+
+/// Storage side of the matching engine
+#[async_trait]
+pub trait MatchingRepository: Send + Sync {
+    /// Active requisites of online traders in the payment's currency, and of its payment
+    /// method when set, with usage as of `at`. Limits and deposit are checked by the caller.
+    async fn find_candidates(
+        &self,
+        intent: &PaymentIntent,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<MatchCandidate>, AppError>;
+
+    /// Assigns the payment to the candidate's requisite in one transaction:
+    /// - locks the trader row, skipping it when another assignment holds the lock
+    /// - locks the payment row, so it is assigned at most once
+    /// - re-reads the candidate and checks it can still take the amount
+    /// - queues the `payment.pending` webhook
+    ///
+    /// Returns `None` when the trader is busy, offline or out of limits or deposit.
+    async fn assign(
+        &self,
+        intent_id: Uuid,
+        candidate: &MatchCandidate,
+        at: DateTime<Utc>,
+    ) -> Result<Option<PaymentIntent>, AppError>;
+}
+
+#[cfg(test)]
+use mockall::mock;
+
+#[cfg(test)]
+mock! {
+    pub PaymentIntentRepository {}
+
+    #[async_trait]
+    impl PaymentIntentRepository for PaymentIntentRepository {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<PaymentIntent>, AppError>;
+        async fn find_by_external_order_id(&self, site_id: Uuid, external_order_id: &str) -> Result<Option<PaymentIntent>, AppError>;
+        async fn create(&self, intent: PaymentIntent) -> Result<PaymentIntent, AppError>;
+        async fn update_status(&self, intent: PaymentIntent, audit: Option<AuditEntry>) -> Result<PaymentIntent, AppError>;
+        async fn settle(&self, intent: PaymentIntent, settlement: Vec<JournalEntry>, audit: AuditEntry) -> Result<PaymentIntent, AppError>;
+        async fn list(&self, filter: PaymentIntentFilter, limit: i64, offset: i64) -> Result<Vec<PaymentIntent>, AppError>;
+        async fn expire_due(&self, at: DateTime<Utc>) -> Result<Vec<PaymentIntent>, AppError>;
+        async fn escalate_due(&self, marked_paid_before: DateTime<Utc>, at: DateTime<Utc>) -> Result<u64, AppError>;
+    }
+}
+
+#[cfg(test)]
+mock! {
+    pub PaymentEvidenceRepository {}
+
+    #[async_trait]
+    impl PaymentEvidenceRepository for PaymentEvidenceRepository {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<PaymentEvidence>, AppError>;
+        async fn create(&self, evidence: PaymentEvidence) -> Result<PaymentEvidence, AppError>;
+        async fn list_by_payment(&self, payment_id: Uuid) -> Result<Vec<PaymentEvidence>, AppError>;
+    }
+}
+
+#[cfg(test)]
+mock! {
+    pub MatchingRepository {}
+
+    #[async_trait]
+    impl MatchingRepository for MatchingRepository {
+        async fn find_candidates(&self, intent: &PaymentIntent, at: DateTime<Utc>) -> Result<Vec<MatchCandidate>, AppError>;
+        async fn assign(&self, intent_id: Uuid, candidate: &MatchCandidate, at: DateTime<Utc>) -> Result<Option<PaymentIntent>, AppError>;
+    }
+}
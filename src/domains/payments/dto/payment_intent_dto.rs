@@ -0,0 +1,299 @@
+use crate::common::money::Money;
+use crate::domains::payments::domain::{
+    evidence::PaymentEvidence,
+    matching::PaymentDetails,
+    payment_intent::{PaymentIntent, PaymentIntentFilter, PaymentStatus},
+};
+use crate::domains::traders::domain::requisite::RequisiteKind;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CreatePaymentIntentRequest {
+    /// Order reference in your system; one payment per order and site
+    #[schema(example = "order-10042")]
+    pub external_order_id: String,
+
+    /// Decimal amount with at most the currency's number of fractional digits
+    #[schema(example = "1500.00")]
+    pub amount: String,
+
+    /// ISO 4217 currency code
+    #[schema(example = "USD")]
+    pub currency: String,
+
+    /// Payment method the customer chose; any trader requisite in the currency when omitted
+    #[serde(default)]
+    #[schema(example = "card")]
+    pub payment_method: Option<RequisiteKind>,
+
+    #[schema(example = "Order #10042")]
+    pub description: Option<String>,
+
+    /// Minutes until the payment expires; the service default applies when omitted
+    #[schema(example = 30)]
+    pub expires_in_minutes: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaymentIntentResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub merchant_id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub site_id: String,
+
+    #[schema(example = "order-10042")]
+    pub external_order_id: String,
+
+    /// Decimal `amount` and `currency`
+    #[serde(flatten)]
+    pub amount: Money,
+
+    #[schema(example = "card")]
+    pub payment_method: Option<RequisiteKind>,
+
+    pub status: PaymentStatus,
+
+    #[schema(example = "Order #10042")]
+    pub description: Option<String>,
+
+    /// Trader the payment was matched to
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub trader_id: Option<String>,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub requisite_id: Option<String>,
+
+    #[serde(with = "crate::common::time_formater::option")]
+    #[schema(example = "2024-01-01T12:00:05Z")]
+    pub assigned_at: Option<DateTime<Utc>>,
+
+    /// Where the customer sends the money; shown to the merchant while the payment is pending
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pay_to: Option<PaymentDetailsResponse>,
+
+    /// When the transfer was reported; the payment waits for its trader from then on
+    #[serde(with = "crate::common::time_formater::option")]
+    #[schema(example = "2024-01-01T12:10:00Z")]
+    pub marked_paid_at: Option<DateTime<Utc>>,
+
+    /// When the trader's confirmation timed out and Support took over
+    #[serde(with = "crate::common::time_formater::option")]
+    #[schema(example = "2024-01-01T12:25:00Z")]
+    pub escalated_at: Option<DateTime<Utc>>,
+
+    /// When the payment was confirmed or rejected
+    #[serde(with = "crate::common::time_formater::option")]
+    #[schema(example = "2024-01-01T12:15:00Z")]
+    pub resolved_at: Option<DateTime<Utc>>,
+
+    /// Disputes can be opened until this time
+    #[serde(with = "crate::common::time_formater::option")]
+    #[schema(example = "2024-01-04T12:15:00Z")]
+    pub dispute_until: Option<DateTime<Utc>>,
+
+    /// Why the trader rejected the payment
+    #[schema(example = "No transfer received")]
+    pub rejection_reason: Option<String>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:30:00Z")]
+    pub expires_at: DateTime<Utc>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PaymentIntentResponse {
+    pub fn with_payment_details(mut self, details: Option<PaymentDetails>) -> Self {
+        self.pay_to = details.map(PaymentDetailsResponse::from);
+        self
+    }
+}
+
+impl From<PaymentIntent> for PaymentIntentResponse {
+    fn from(intent: PaymentIntent) -> Self {
+        Self {
+            id: intent.id.to_string(),
+            merchant_id: intent.merchant_id.to_string(),
+            site_id: intent.site_id.to_string(),
+            external_order_id: intent.external_order_id,
+            amount: intent.amount,
+            payment_method: intent.payment_method,
+            status: intent.status,
+            description: intent.description,
+            trader_id: intent
+                .assignment
+                .map(|assignment| assignment.trader_id.to_string()),
+            requisite_id: intent
+                .assignment
+                .map(|assignment| assignment.requisite_id.to_string()),
+            assigned_at: intent.assignment.map(|assignment| assignment.assigned_at),
+            pay_to: None,
+            marked_paid_at: intent.marked_paid_at,
+            escalated_at: intent.escalated_at,
+            resolved_at: intent.resolved_at,
+            dispute_until: intent.dispute_until,
+            rejection_reason: intent.rejection_reason,
+            expires_at: intent.expires_at,
+            created_at: intent.created_at,
+            updated_at: intent.updated_at,
+        }
+    }
+}
+
+/// Requisite the customer pays to
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaymentDetailsResponse {
+    pub kind: RequisiteKind,
+
+    #[schema(example = "Example Bank")]
+    pub bank_name: String,
+
+    #[schema(example = "JANE DOE")]
+    pub holder_name: String,
+
+    /// Full card or account number
+    #[schema(example = "4242424242424242")]
+    pub number: String,
+}
+
+impl From<PaymentDetails> for PaymentDetailsResponse {
+    fn from(details: PaymentDetails) -> Self {
+        Self {
+            kind: details.kind,
+            bank_name: details.bank_name,
+            holder_name: details.holder_name,
+            number: details.number,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct RejectPaymentRequest {
+    /// Why the payment is rejected, at most 500 characters
+    #[schema(example = "No transfer received")]
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaymentEvidenceResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub payment_id: String,
+
+    #[schema(example = "receipt.png")]
+    pub file_name: String,
+
+    #[schema(example = "image/png")]
+    pub content_type: String,
+
+    #[schema(example = 48213)]
+    pub size_bytes: i64,
+
+    /// Hex SHA-256 of the content
+    #[schema(example = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08")]
+    pub sha256: String,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:10:00Z")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PaymentEvidence> for PaymentEvidenceResponse {
+    fn from(evidence: PaymentEvidence) -> Self {
+        Self {
+            id: evidence.id.to_string(),
+            payment_id: evidence.payment_id.to_string(),
+            file_name: evidence.file_name,
+            content_type: evidence.content_type,
+            size_bytes: evidence.size_bytes,
+            sha256: evidence.sha256,
+            created_at: evidence.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListPaymentIntentsQuery {
+    /// Page size, at most 100
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    pub merchant_id: Option<Uuid>,
+    pub site_id: Option<Uuid>,
+    #[param(value_type = Option<String>, example = "pending")]
+    pub status: Option<PaymentStatus>,
+    /// Payments matched to this trader
+    pub trader_id: Option<Uuid>,
+    /// Payments escalated to Support; with `status=confirming` this is the Support queue
+    pub escalated: Option<bool>,
+    pub external_order_id: Option<String>,
+    /// Created at or after this time
+    pub created_from: Option<DateTime<Utc>>,
+    /// Created before this time
+    pub created_to: Option<DateTime<Utc>>,
+}
+
+impl From<ListPaymentIntentsQuery> for PaymentIntentFilter {
+    fn from(query: ListPaymentIntentsQuery) -> Self {
+        Self {
+            merchant_id: query.merchant_id,
+            site_id: query.site_id,
+            status: query.status,
+            trader_id: query.trader_id,
+            escalated: query.escalated,
+            external_order_id: query.external_order_id,
+            created_from: query.created_from,
+            created_to: query.created_to,
+        }
+    }
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::Currency;
+    use chrono::Duration;
+
+    #[test]
+    fn test_payment_intent_response_from_intent() {
+        let intent = PaymentIntent::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "order-1".to_string(),
+            Money::new(2500, Currency::Eur),
+            None,
+            None,
+            Duration::minutes(30),
+        );
+
+        let json = serde_json::to_value(PaymentIntentResponse::from(intent.clone())).unwrap();
+
+        assert_eq!(json["id"], intent.id.to_string());
+        assert_eq!(json["amount"], "25.00");
+        assert_eq!(json["currency"], "EUR");
+        assert_eq!(json["status"], "created");
+        assert_eq!(json["trader_id"], serde_json::Value::Null);
+        assert!(json.get("pay_to").is_none());
+    }
+}
@@ -0,0 +1,230 @@
+use super::{
+    payment_intent_entity::Entity as PaymentIntentEntity,
+    payment_intent_repository::PostgresPaymentIntentRepository,
+};
+use crate::common::{
+    error::AppError,
+    money::{Currency, Money},
+};
+use crate::domains::{
+    payments::domain::{
+        matching::MatchCandidate, payment_intent::PaymentIntent, repository::MatchingRepository,
+    },
+    traders::{
+        domain::trader::TraderAvailability,
+        infra::trader_entity::{self, Entity as TraderEntity},
+    },
+    webhooks::infra::webhook_repository::PostgresWebhookRepository,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Utc};
+use sea_orm::{
+    sea_query::{LockBehavior, LockType},
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
+    FromQueryResult, QueryFilter, QuerySelect, Statement, TransactionTrait, Value,
+};
+use uuid::Uuid;
+
+/// Requisites of online traders with their usage. Amounts are summed in SQL so a candidate
+/// read inside the assignment transaction sees every payment committed before the trader lock.
+/// Paid payments count as exposure until their settlement is posted; from then on the
+/// settlement has taken the amount off the trader's deposit.
+/// Sums over bigint yield numeric in Postgres; they are cast back for the row decoder.
+const CANDIDATES_SQL: &str = r#"
+SELECT
+    r.id AS requisite_id,
+    r.trader_id,
+    r.daily_limit,
+    r.monthly_limit,
+    COALESCE((
+        SELECT SUM(p.amount) FROM payment_intent p
+        WHERE p.requisite_id = r.id AND p.status IN ('pending', 'confirming', 'paid') AND p.assigned_at >= $2
+    ), 0)::bigint AS daily_used,
+    COALESCE((
+        SELECT SUM(p.amount) FROM payment_intent p
+        WHERE p.requisite_id = r.id AND p.status IN ('pending', 'confirming', 'paid') AND p.assigned_at >= $3
+    ), 0)::bigint AS monthly_used,
+    COALESCE((
+        SELECT -SUM(lp.amount) FROM ledger_posting lp
+        JOIN ledger_account la ON la.id = lp.account_id
+        WHERE la.kind = 'trader' AND la.owner_id = r.trader_id AND la.currency = $1
+            AND lp.effective_at <= $4
+    ), 0)::bigint AS deposit,
+    COALESCE((
+        SELECT SUM(p.amount) FROM payment_intent p
+        WHERE p.trader_id = r.trader_id AND p.currency = $1
+            AND (p.status IN ('pending', 'confirming') OR (p.status = 'paid' AND NOT EXISTS (
+                SELECT 1 FROM ledger_journal_entry e
+                WHERE e.kind = 'settlement' AND e.reference_id = p.id AND e.effective_at <= $4
+            )))
+    ), 0)::bigint AS exposure,
+    (
+        SELECT COUNT(*) FROM payment_intent p
+        WHERE p.trader_id = r.trader_id AND p.status IN ('pending', 'confirming')
+    ) AS open_payments,
+    (
+        SELECT COUNT(*) FROM payment_intent p
+        WHERE p.trader_id = r.trader_id AND p.status = 'paid'
+    ) AS paid_payments,
+    (
+        SELECT COUNT(*) FROM payment_intent p
+        WHERE p.trader_id = r.trader_id AND p.status IN ('failed', 'expired')
+    ) AS failed_payments,
+    (
+        SELECT MAX(p.assigned_at) FROM payment_intent p WHERE p.trader_id = r.trader_id
+    ) AS last_assigned_at
+FROM trader_requisite r
+JOIN trader t ON t.id = r.trader_id
+WHERE t.availability = 'online' AND r.is_active AND r.currency = $1
+"#;
+
+#[derive(Debug, FromQueryResult)]
+struct CandidateRow {
+    requisite_id: Uuid,
+    trader_id: Uuid,
+    daily_limit: i64,
+    monthly_limit: i64,
+    daily_used: i64,
+    monthly_used: i64,
+    deposit: i64,
+    exposure: i64,
+    open_payments: i64,
+    paid_payments: i64,
+    failed_payments: i64,
+    last_assigned_at: Option<DateTime<Utc>>,
+}
+
+pub struct PostgresMatchingRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresMatchingRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn row_to_candidate(row: CandidateRow, currency: Currency) -> MatchCandidate {
+        let money = |minor_units| Money::new(minor_units, currency);
+        MatchCandidate {
+            trader_id: row.trader_id,
+            requisite_id: row.requisite_id,
+            daily_limit: money(row.daily_limit),
+            monthly_limit: money(row.monthly_limit),
+            daily_used: money(row.daily_used),
+            monthly_used: money(row.monthly_used),
+            deposit: money(row.deposit),
+            exposure: money(row.exposure),
+            open_payments: row.open_payments,
+            paid_payments: row.paid_payments,
+            failed_payments: row.failed_payments,
+            last_assigned_at: row.last_assigned_at,
+        }
+    }
+
+    async fn query_candidates<C: ConnectionTrait>(
+        conn: &C,
+        intent: &PaymentIntent,
+        requisite_id: Option<Uuid>,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<MatchCandidate>, AppError> {
+        let currency = intent.amount.currency();
+        let day_start = at.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let month_start = day_start.with_day(1).unwrap();
+
+        let mut sql = CANDIDATES_SQL.to_string();
+        let mut values: Vec<Value> = vec![
+            currency.as_str().into(),
+            day_start.into(),
+            month_start.into(),
+            at.into(),
+        ];
+        if let Some(kind) = intent.payment_method {
+            values.push(kind.as_str().into());
+            sql.push_str(&format!(" AND r.kind = ${}", values.len()));
+        }
+        if let Some(requisite_id) = requisite_id {
+            values.push(requisite_id.into());
+            sql.push_str(&format!(" AND r.id = ${}", values.len()));
+        }
+
+        let rows = CandidateRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            values,
+        ))
+        .all(conn)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Self::row_to_candidate(row, currency))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl MatchingRepository for PostgresMatchingRepository {
+    async fn find_candidates(
+        &self,
+        intent: &PaymentIntent,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<MatchCandidate>, AppError> {
+        Self::query_candidates(&self.db, intent, None, at).await
+    }
+
+    async fn assign(
+        &self,
+        intent_id: Uuid,
+        candidate: &MatchCandidate,
+        at: DateTime<Utc>,
+    ) -> Result<Option<PaymentIntent>, AppError> {
+        let txn = self.db.begin().await?;
+
+        // Serialises assignments per trader; a concurrent assignment holding the lock
+        // makes the engine move on to the next candidate instead of waiting
+        let trader = TraderEntity::find_by_id(candidate.trader_id)
+            .filter(trader_entity::Column::Availability.eq(TraderAvailability::Online.as_str()))
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .one(&txn)
+            .await?;
+        if trader.is_none() {
+            return Ok(None);
+        }
+
+        let Some(model) = PaymentIntentEntity::find_by_id(intent_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+        else {
+            return Err(AppError::NotFound(format!(
+                "Payment {} not found",
+                intent_id
+            )));
+        };
+        let mut intent = PostgresPaymentIntentRepository::entity_to_domain(model)?;
+
+        // Usage may have changed since the candidate was ranked
+        let fits = Self::query_candidates(&txn, &intent, Some(candidate.requisite_id), at)
+            .await?
+            .into_iter()
+            .any(|current| {
+                current.trader_id == candidate.trader_id && current.can_take(intent.amount)
+            });
+        if !fits {
+            return Ok(None);
+        }
+
+        intent.assign(candidate.trader_id, candidate.requisite_id, at)?;
+        let intent = PostgresPaymentIntentRepository::entity_to_domain(
+            PostgresPaymentIntentRepository::domain_to_active_model(intent)
+                .update(&txn)
+                .await?,
+        )?;
+        PostgresWebhookRepository::insert_payment_events(&txn, std::slice::from_ref(&intent), at)
+            .await?;
+
+        txn.commit().await?;
+
+        Ok(Some(intent))
+    }
+}
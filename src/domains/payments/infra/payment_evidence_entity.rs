@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "payment_evidence")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub payment_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    #[sea_orm(unique)]
+    pub storage_key: String,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::payment_intent_entity::Entity",
+        from = "Column::PaymentId",
+        to = "super::payment_intent_entity::Column::Id"
+    )]
+    PaymentIntent,
+}
+
+impl Related<super::payment_intent_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PaymentIntent.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,76 @@
+use super::payment_evidence_entity::{self, Entity as PaymentEvidenceEntity};
+use crate::common::error::AppError;
+use crate::domains::payments::domain::{
+    evidence::PaymentEvidence, repository::PaymentEvidenceRepository,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use uuid::Uuid;
+
+pub struct PostgresPaymentEvidenceRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresPaymentEvidenceRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(model: payment_evidence_entity::Model) -> PaymentEvidence {
+        PaymentEvidence {
+            id: model.id,
+            payment_id: model.payment_id,
+            file_name: model.file_name,
+            content_type: model.content_type,
+            size_bytes: model.size_bytes,
+            sha256: model.sha256,
+            storage_key: model.storage_key,
+            created_at: model.created_at.with_timezone(&Utc),
+        }
+    }
+
+    fn domain_to_active_model(evidence: PaymentEvidence) -> payment_evidence_entity::ActiveModel {
+        payment_evidence_entity::ActiveModel {
+            id: Set(evidence.id),
+            payment_id: Set(evidence.payment_id),
+            file_name: Set(evidence.file_name),
+            content_type: Set(evidence.content_type),
+            size_bytes: Set(evidence.size_bytes),
+            sha256: Set(evidence.sha256),
+            storage_key: Set(evidence.storage_key),
+            created_at: Set(evidence.created_at.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl PaymentEvidenceRepository for PostgresPaymentEvidenceRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PaymentEvidence>, AppError> {
+        Ok(PaymentEvidenceEntity::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .map(Self::entity_to_domain))
+    }
+
+    async fn create(&self, evidence: PaymentEvidence) -> Result<PaymentEvidence, AppError> {
+        let model = Self::domain_to_active_model(evidence)
+            .insert(&self.db)
+            .await?;
+
+        Ok(Self::entity_to_domain(model))
+    }
+
+    async fn list_by_payment(&self, payment_id: Uuid) -> Result<Vec<PaymentEvidence>, AppError> {
+        Ok(PaymentEvidenceEntity::find()
+            .filter(payment_evidence_entity::Column::PaymentId.eq(payment_id))
+            .order_by_asc(payment_evidence_entity::Column::CreatedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(Self::entity_to_domain)
+            .collect())
+    }
+}
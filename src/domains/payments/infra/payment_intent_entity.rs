@@ -0,0 +1,34 @@
+use crate::common::money::Currency;
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "payment_intent")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub merchant_id: Uuid,
+    pub site_id: Uuid,
+    pub external_order_id: String,
+    pub amount: i64,
+    pub currency: Currency,
+    pub payment_method: Option<String>,
+    pub status: String,
+    pub description: Option<String>,
+    pub trader_id: Option<Uuid>,
+    pub requisite_id: Option<Uuid>,
+    pub assigned_at: Option<DateTimeWithTimeZone>,
+    pub marked_paid_at: Option<DateTimeWithTimeZone>,
+    pub escalated_at: Option<DateTimeWithTimeZone>,
+    pub resolved_at: Option<DateTimeWithTimeZone>,
+    pub dispute_until: Option<DateTimeWithTimeZone>,
+    pub rejection_reason: Option<String>,
+    pub expires_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
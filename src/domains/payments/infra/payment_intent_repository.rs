@@ -0,0 +1,277 @@
+use super::payment_intent_entity::{self, Entity as PaymentIntentEntity};
+use crate::common::{error::AppError, money::Money};
+use crate::domains::{
+    audit::{
+        domain::audit_entry::AuditEntry, infra::audit_log_repository::PostgresAuditLogRepository,
+    },
+    ledger::{
+        domain::journal_entry::JournalEntry, infra::ledger_repository::PostgresLedgerRepository,
+    },
+    payments::domain::{
+        payment_intent::{PaymentIntent, PaymentIntentFilter, PaymentStatus, RequisiteAssignment},
+        repository::PaymentIntentRepository,
+    },
+    webhooks::infra::webhook_repository::PostgresWebhookRepository,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+pub struct PostgresPaymentIntentRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresPaymentIntentRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub(super) fn entity_to_domain(
+        model: payment_intent_entity::Model,
+    ) -> Result<PaymentIntent, AppError> {
+        let assignment = match (model.trader_id, model.requisite_id, model.assigned_at) {
+            (Some(trader_id), Some(requisite_id), Some(assigned_at)) => Some(RequisiteAssignment {
+                trader_id,
+                requisite_id,
+                assigned_at: assigned_at.with_timezone(&Utc),
+            }),
+            _ => None,
+        };
+
+        Ok(PaymentIntent {
+            id: model.id,
+            merchant_id: model.merchant_id,
+            site_id: model.site_id,
+            external_order_id: model.external_order_id,
+            amount: Money::new(model.amount, model.currency),
+            payment_method: model
+                .payment_method
+                .as_deref()
+                .map(str::parse)
+                .transpose()?,
+            status: model.status.parse()?,
+            description: model.description,
+            assignment,
+            marked_paid_at: model.marked_paid_at.map(|at| at.with_timezone(&Utc)),
+            escalated_at: model.escalated_at.map(|at| at.with_timezone(&Utc)),
+            resolved_at: model.resolved_at.map(|at| at.with_timezone(&Utc)),
+            dispute_until: model.dispute_until.map(|at| at.with_timezone(&Utc)),
+            rejection_reason: model.rejection_reason,
+            expires_at: model.expires_at.with_timezone(&Utc),
+            created_at: model.created_at.with_timezone(&Utc),
+            updated_at: model.updated_at.with_timezone(&Utc),
+        })
+    }
+
+    pub(super) fn domain_to_active_model(
+        intent: PaymentIntent,
+    ) -> payment_intent_entity::ActiveModel {
+        payment_intent_entity::ActiveModel {
+            id: Set(intent.id),
+            merchant_id: Set(intent.merchant_id),
+            site_id: Set(intent.site_id),
+            external_order_id: Set(intent.external_order_id),
+            amount: Set(intent.amount.minor_units()),
+            currency: Set(intent.amount.currency()),
+            payment_method: Set(intent.payment_method.map(|kind| kind.as_str().to_string())),
+            status: Set(intent.status.as_str().to_string()),
+            description: Set(intent.description),
+            trader_id: Set(intent.assignment.map(|assignment| assignment.trader_id)),
+            requisite_id: Set(intent.assignment.map(|assignment| assignment.requisite_id)),
+            assigned_at: Set(intent
+                .assignment
+                .map(|assignment| assignment.assigned_at.into())),
+            marked_paid_at: Set(intent.marked_paid_at.map(Into::into)),
+            escalated_at: Set(intent.escalated_at.map(Into::into)),
+            resolved_at: Set(intent.resolved_at.map(Into::into)),
+            dispute_until: Set(intent.dispute_until.map(Into::into)),
+            rejection_reason: Set(intent.rejection_reason),
+            expires_at: Set(intent.expires_at.into()),
+            created_at: Set(intent.created_at.into()),
+            updated_at: Set(intent.updated_at.into()),
+        }
+    }
+
+    fn filter_condition(filter: PaymentIntentFilter) -> Condition {
+        let mut condition = Condition::all();
+
+        if let Some(merchant_id) = filter.merchant_id {
+            condition = condition.add(payment_intent_entity::Column::MerchantId.eq(merchant_id));
+        }
+        if let Some(site_id) = filter.site_id {
+            condition = condition.add(payment_intent_entity::Column::SiteId.eq(site_id));
+        }
+        if let Some(status) = filter.status {
+            condition = condition.add(payment_intent_entity::Column::Status.eq(status.as_str()));
+        }
+        if let Some(trader_id) = filter.trader_id {
+            condition = condition.add(payment_intent_entity::Column::TraderId.eq(trader_id));
+        }
+        if let Some(escalated) = filter.escalated {
+            condition = condition.add(if escalated {
+                payment_intent_entity::Column::EscalatedAt.is_not_null()
+            } else {
+                payment_intent_entity::Column::EscalatedAt.is_null()
+            });
+        }
+        if let Some(external_order_id) = filter.external_order_id {
+            condition =
+                condition.add(payment_intent_entity::Column::ExternalOrderId.eq(external_order_id));
+        }
+        if let Some(created_from) = filter.created_from {
+            condition = condition.add(payment_intent_entity::Column::CreatedAt.gte(created_from));
+        }
+        if let Some(created_to) = filter.created_to {
+            condition = condition.add(payment_intent_entity::Column::CreatedAt.lt(created_to));
+        }
+
+        condition
+    }
+}
+
+#[async_trait]
+impl PaymentIntentRepository for PostgresPaymentIntentRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PaymentIntent>, AppError> {
+        PaymentIntentEntity::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .map(Self::entity_to_domain)
+            .transpose()
+    }
+
+    async fn find_by_external_order_id(
+        &self,
+        site_id: Uuid,
+        external_order_id: &str,
+    ) -> Result<Option<PaymentIntent>, AppError> {
+        PaymentIntentEntity::find()
+            .filter(payment_intent_entity::Column::SiteId.eq(site_id))
+            .filter(payment_intent_entity::Column::ExternalOrderId.eq(external_order_id))
+            .one(&self.db)
+            .await?
+            .map(Self::entity_to_domain)
+            .transpose()
+    }
+
+    async fn create(&self, intent: PaymentIntent) -> Result<PaymentIntent, AppError> {
+        let model = Self::domain_to_active_model(intent)
+            .insert(&self.db)
+            .await?;
+
+        Self::entity_to_domain(model)
+    }
+
+    async fn update_status(
+        &self,
+        intent: PaymentIntent,
+        audit: Option<AuditEntry>,
+    ) -> Result<PaymentIntent, AppError> {
+        let txn = self.db.begin().await?;
+
+        let intent =
+            Self::entity_to_domain(Self::domain_to_active_model(intent).update(&txn).await?)?;
+        PostgresWebhookRepository::insert_payment_events(
+            &txn,
+            std::slice::from_ref(&intent),
+            Utc::now(),
+        )
+        .await?;
+        if let Some(audit) = audit {
+            PostgresAuditLogRepository::append(&txn, audit).await?;
+        }
+
+        txn.commit().await?;
+
+        Ok(intent)
+    }
+
+    async fn settle(
+        &self,
+        intent: PaymentIntent,
+        settlement: Vec<JournalEntry>,
+        audit: AuditEntry,
+    ) -> Result<PaymentIntent, AppError> {
+        // The balance trigger is deferred, so it sees every posting of an entry at commit
+        let txn = self.db.begin().await?;
+
+        let intent =
+            Self::entity_to_domain(Self::domain_to_active_model(intent).update(&txn).await?)?;
+        PostgresLedgerRepository::insert_entries(&txn, &settlement).await?;
+        PostgresWebhookRepository::insert_payment_events(
+            &txn,
+            std::slice::from_ref(&intent),
+            Utc::now(),
+        )
+        .await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+
+        txn.commit().await?;
+
+        Ok(intent)
+    }
+
+    async fn list(
+        &self,
+        filter: PaymentIntentFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PaymentIntent>, AppError> {
+        PaymentIntentEntity::find()
+            .filter(Self::filter_condition(filter))
+            .order_by_desc(payment_intent_entity::Column::CreatedAt)
+            .limit(limit as u64)
+            .offset(offset as u64)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(Self::entity_to_domain)
+            .collect()
+    }
+
+    async fn expire_due(&self, at: DateTime<Utc>) -> Result<Vec<PaymentIntent>, AppError> {
+        let txn = self.db.begin().await?;
+
+        let expired = PaymentIntentEntity::update_many()
+            .col_expr(
+                payment_intent_entity::Column::Status,
+                Expr::value(PaymentStatus::Expired.as_str()),
+            )
+            .col_expr(payment_intent_entity::Column::UpdatedAt, Expr::value(at))
+            .filter(payment_intent_entity::Column::Status.is_in([
+                PaymentStatus::Created.as_str(),
+                PaymentStatus::Pending.as_str(),
+            ]))
+            .filter(payment_intent_entity::Column::ExpiresAt.lte(at))
+            .exec_with_returning(&txn)
+            .await?
+            .into_iter()
+            .map(Self::entity_to_domain)
+            .collect::<Result<Vec<_>, _>>()?;
+        PostgresWebhookRepository::insert_payment_events(&txn, &expired, at).await?;
+
+        txn.commit().await?;
+
+        Ok(expired)
+    }
+
+    async fn escalate_due(
+        &self,
+        marked_paid_before: DateTime<Utc>,
+        at: DateTime<Utc>,
+    ) -> Result<u64, AppError> {
+        let result = PaymentIntentEntity::update_many()
+            .col_expr(payment_intent_entity::Column::EscalatedAt, Expr::value(at))
+            .col_expr(payment_intent_entity::Column::UpdatedAt, Expr::value(at))
+            .filter(payment_intent_entity::Column::Status.eq(PaymentStatus::Confirming.as_str()))
+            .filter(payment_intent_entity::Column::EscalatedAt.is_null())
+            .filter(payment_intent_entity::Column::MarkedPaidAt.lte(marked_paid_before))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,33 @@
+mod api {
+    pub mod router;
+    pub mod trader_handler;
+}
+
+pub mod app {
+    pub mod create_requisite_use_case;
+    pub mod create_trader_use_case;
+    pub mod delete_trader_use_case;
+    pub mod get_trader_use_case;
+    pub mod update_requisite_use_case;
+    pub mod update_trader_use_case;
+}
+
+pub mod domain {
+    pub mod repository;
+    pub mod requisite;
+    pub mod trader;
+}
+
+pub mod dto {
+    pub mod trader_dto;
+}
+
+pub mod infra {
+    pub mod trader_entity;
+    pub mod trader_repository;
+    pub mod trader_requisite_entity;
+}
+
+pub use api::router::{protected_trader_routes, TraderApiDoc};
+pub use domain::repository::TraderRepository;
+pub use infra::trader_repository::PostgresTraderRepository;
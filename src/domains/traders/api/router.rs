@@ -0,0 +1,96 @@
+use axum::{
+    middleware,
+    routing::{get, patch, post},
+    Router,
+};
+
+use crate::{
+    common::{jwt::SecurityAddon, middleware::require_permission, money::Money},
+    domains::{
+        backoffice::role::permission::{TRADERS_READ, TRADERS_WRITE},
+        ledger::dto::ledger_dto::CreateDepositRequest,
+        traders::{
+            domain::{requisite::RequisiteKind, trader::TraderAvailability},
+            dto::trader_dto::{
+                ChangeTraderAvailabilityRequest, CreateRequisiteRequest, CreateTraderRequest,
+                RequisiteResponse, TraderResponse, UpdateRequisiteRequest, UpdateTraderRequest,
+            },
+        },
+    },
+};
+
+use utoipa::OpenApi;
+
+use super::trader_handler;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::trader_handler::list_traders,
+        super::trader_handler::get_trader,
+        super::trader_handler::create_trader,
+        super::trader_handler::update_trader,
+        super::trader_handler::delete_trader,
+        super::trader_handler::change_trader_availability,
+        super::trader_handler::create_requisite,
+        super::trader_handler::update_requisite,
+        super::trader_handler::delete_requisite,
+        super::trader_handler::get_trader_deposits,
+        super::trader_handler::create_trader_deposit,
+    ),
+    components(schemas(
+        TraderResponse,
+        RequisiteResponse,
+        CreateTraderRequest,
+        UpdateTraderRequest,
+        ChangeTraderAvailabilityRequest,
+        CreateRequisiteRequest,
+        UpdateRequisiteRequest,
+        CreateDepositRequest,
+        TraderAvailability,
+        RequisiteKind,
+        Money
+    )),
+    tags(
+        (name = "Traders", description = "P2P traders, their payment requisites, turnover limits and security deposits")
+    ),
+    modifiers(&SecurityAddon)
+)]
+pub struct TraderApiDoc;
+
+pub fn protected_trader_routes() -> Router {
+    let write_routes = Router::new()
+        .route("/trader", post(trader_handler::create_trader))
+        .route(
+            "/trader/{id}",
+            patch(trader_handler::update_trader).delete(trader_handler::delete_trader),
+        )
+        .route(
+            "/trader/{id}/availability",
+            patch(trader_handler::change_trader_availability),
+        )
+        .route(
+            "/trader/{id}/requisite",
+            post(trader_handler::create_requisite),
+        )
+        .route(
+            "/trader/{id}/requisite/{requisite_id}",
+            patch(trader_handler::update_requisite).delete(trader_handler::delete_requisite),
+        )
+        .route(
+            "/trader/{id}/deposit",
+            post(trader_handler::create_trader_deposit),
+        )
+        .route_layer(middleware::from_fn(require_permission(TRADERS_WRITE)));
+
+    let read_routes = Router::new()
+        .route("/trader", get(trader_handler::list_traders))
+        .route("/trader/{id}", get(trader_handler::get_trader))
+        .route(
+            "/trader/{id}/deposit",
+            get(trader_handler::get_trader_deposits),
+        )
+        .route_layer(middleware::from_fn(require_permission(TRADERS_READ)));
+
+    Router::new().merge(write_routes).merge(read_routes)
+}
@@ -0,0 +1,391 @@
+use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError, jwt::Claims};
+use crate::domains::audit::domain::audit_entry::AuditContext;
+use crate::domains::ledger::dto::ledger_dto::{
+    AccountBalanceResponse, CreateDepositRequest, JournalEntryResponse,
+};
+use crate::domains::traders::dto::trader_dto::{
+    ChangeTraderAvailabilityRequest, CreateRequisiteRequest, CreateTraderRequest, ListTradersQuery,
+    RequisiteResponse, TraderResponse, UpdateRequisiteRequest, UpdateTraderRequest,
+};
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/trader",
+    params(ListTradersQuery),
+    responses(
+        (status = 200, description = "Page of traders ordered by name", body = inline(ApiResponse<Vec<TraderResponse>>)),
+        (status = 400, description = "Limit exceeds 100"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Traders",
+    summary = "List traders",
+    description = "Lists traders with their requisites, optionally only online or offline ones. Requires `traders:read`."
+)]
+pub async fn list_traders(
+    Extension(state): Extension<Arc<AppState>>,
+    Query(params): Query<ListTradersQuery>,
+) -> Result<Json<ApiResponse<Vec<TraderResponse>>>, AppError> {
+    let (limit, offset) = (params.limit, params.offset);
+    let traders = state
+        .trader_get_use_case
+        .list(params.into(), limit, offset)
+        .await?;
+
+    let response: Vec<TraderResponse> = traders.into_iter().map(TraderResponse::from).collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/trader/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Trader ID to fetch")
+    ),
+    responses(
+        (status = 200, description = "Trader with its requisites", body = inline(ApiResponse<TraderResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Trader not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Traders",
+    summary = "Get trader by ID",
+    description = "Retrieves a trader together with its masked requisites. Requires `traders:read`."
+)]
+pub async fn get_trader(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(trader_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<TraderResponse>>, AppError> {
+    let trader = state.trader_get_use_case.execute(trader_id).await?;
+
+    Ok(Json(ApiResponse::success(TraderResponse::from(trader))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/trader",
+    request_body = CreateTraderRequest,
+    responses(
+        (status = 200, description = "Trader created offline", body = inline(ApiResponse<TraderResponse>)),
+        (status = 400, description = "Empty or duplicate name"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Traders",
+    summary = "Create trader",
+    description = "Creates an offline trader without requisites. Requires `traders:write`."
+)]
+pub async fn create_trader(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Json(request): Json<CreateTraderRequest>,
+) -> Result<Json<ApiResponse<TraderResponse>>, AppError> {
+    let trader = state
+        .trader_create_use_case
+        .execute(request, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(TraderResponse::from(trader))))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/trader/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Trader ID to update")
+    ),
+    request_body = UpdateTraderRequest,
+    responses(
+        (status = 200, description = "Trader updated", body = inline(ApiResponse<TraderResponse>)),
+        (status = 400, description = "Empty or duplicate name"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Trader not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Traders",
+    summary = "Update trader",
+    description = "Updates name and/or contact of a trader; a blank contact clears it. Requires `traders:write`."
+)]
+pub async fn update_trader(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(trader_id): Path<Uuid>,
+    Json(request): Json<UpdateTraderRequest>,
+) -> Result<Json<ApiResponse<TraderResponse>>, AppError> {
+    let trader = state
+        .trader_update_use_case
+        .execute(trader_id, request, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(TraderResponse::from(trader))))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/trader/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Trader ID to delete")
+    ),
+    responses(
+        (status = 200, description = "Trader and its requisites deleted"),
+        (status = 400, description = "Trader has ledger history"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Trader not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Traders",
+    summary = "Delete trader",
+    description = "Deletes a trader together with its requisites. Traders that ever held a deposit cannot be deleted; take them offline instead. Requires `traders:write`."
+)]
+pub async fn delete_trader(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(trader_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .trader_delete_use_case
+        .execute(trader_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/trader/{id}/availability",
+    params(
+        ("id" = Uuid, Path, description = "Trader ID")
+    ),
+    request_body = ChangeTraderAvailabilityRequest,
+    responses(
+        (status = 200, description = "Availability changed", body = inline(ApiResponse<TraderResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Trader not found"),
+        (status = 409, description = "Trader has no active requisite to go online with")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Traders",
+    summary = "Set trader online or offline",
+    description = "Switches a trader online or offline. Going online requires at least one active requisite. Requires `traders:write`."
+)]
+pub async fn change_trader_availability(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(trader_id): Path<Uuid>,
+    Json(request): Json<ChangeTraderAvailabilityRequest>,
+) -> Result<Json<ApiResponse<TraderResponse>>, AppError> {
+    let trader = state
+        .trader_update_use_case
+        .change_availability(trader_id, request.availability, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(TraderResponse::from(trader))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/trader/{id}/requisite",
+    params(
+        ("id" = Uuid, Path, description = "Trader ID")
+    ),
+    request_body = CreateRequisiteRequest,
+    responses(
+        (status = 200, description = "Requisite added", body = inline(ApiResponse<RequisiteResponse>)),
+        (status = 400, description = "Malformed number, empty names, or invalid limits"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Trader not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Traders",
+    summary = "Add requisite",
+    description = "Adds an active card or account requisite with daily and monthly turnover limits. The number is stored encrypted and only returned masked. Requires `traders:write`."
+)]
+pub async fn create_requisite(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path(trader_id): Path<Uuid>,
+    Json(request): Json<CreateRequisiteRequest>,
+) -> Result<Json<ApiResponse<RequisiteResponse>>, AppError> {
+    let requisite = state
+        .requisite_create_use_case
+        .execute(trader_id, request, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(RequisiteResponse::from(
+        requisite,
+    ))))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/trader/{id}/requisite/{requisite_id}",
+    params(
+        ("id" = Uuid, Path, description = "Trader ID"),
+        ("requisite_id" = Uuid, Path, description = "Requisite ID to update")
+    ),
+    request_body = UpdateRequisiteRequest,
+    responses(
+        (status = 200, description = "Requisite updated", body = inline(ApiResponse<RequisiteResponse>)),
+        (status = 400, description = "Empty names or invalid limits"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Trader or requisite not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Traders",
+    summary = "Update requisite",
+    description = "Updates names, limits or the active flag of a requisite. Limits are read in the requisite's currency. Deactivating the last active requisite takes the trader offline. Requires `traders:write`."
+)]
+pub async fn update_requisite(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path((trader_id, requisite_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateRequisiteRequest>,
+) -> Result<Json<ApiResponse<RequisiteResponse>>, AppError> {
+    let requisite = state
+        .requisite_update_use_case
+        .execute(trader_id, requisite_id, request, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(RequisiteResponse::from(
+        requisite,
+    ))))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/trader/{id}/requisite/{requisite_id}",
+    params(
+        ("id" = Uuid, Path, description = "Trader ID"),
+        ("requisite_id" = Uuid, Path, description = "Requisite ID to delete")
+    ),
+    responses(
+        (status = 200, description = "Requisite deleted"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Trader or requisite not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Traders",
+    summary = "Delete requisite",
+    description = "Deletes a requisite. Deleting the last active requisite takes the trader offline. Requires `traders:write`."
+)]
+pub async fn delete_requisite(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(audit): Extension<AuditContext>,
+    Path((trader_id, requisite_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .requisite_update_use_case
+        .delete(trader_id, requisite_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/trader/{id}/deposit",
+    params(
+        ("id" = Uuid, Path, description = "Trader ID")
+    ),
+    responses(
+        (status = 200, description = "Deposit balance per currency", body = inline(ApiResponse<Vec<AccountBalanceResponse>>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Trader not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Traders",
+    summary = "Get deposit balances",
+    description = "Current security deposit of the trader in every currency it has deposited, read from its ledger accounts. Requires `traders:read`."
+)]
+pub async fn get_trader_deposits(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(trader_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<AccountBalanceResponse>>>, AppError> {
+    let balances = state
+        .ledger_trader_deposit_use_case
+        .balances(trader_id)
+        .await?;
+
+    let response: Vec<AccountBalanceResponse> = balances
+        .into_iter()
+        .map(AccountBalanceResponse::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/trader/{id}/deposit",
+    params(
+        ("id" = Uuid, Path, description = "Trader ID")
+    ),
+    request_body = CreateDepositRequest,
+    responses(
+        (status = 200, description = "Deposit posted", body = inline(ApiResponse<JournalEntryResponse>)),
+        (status = 400, description = "Invalid amount or currency"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Trader not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Traders",
+    summary = "Record deposit",
+    description = "Posts a security deposit received from the trader to the treasury and the trader's ledger account, recording the user who entered it. Requires `traders:write`."
+)]
+pub async fn create_trader_deposit(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+    Path(trader_id): Path<Uuid>,
+    Json(request): Json<CreateDepositRequest>,
+) -> Result<Json<ApiResponse<JournalEntryResponse>>, AppError> {
+    let entry = state
+        .ledger_trader_deposit_use_case
+        .record(trader_id, request, claims.user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(JournalEntryResponse::from(
+        entry,
+    ))))
+}
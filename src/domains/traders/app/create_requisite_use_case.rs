@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::{error::AppError, money::Money, secret_cipher::SecretCipher},
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, REQUISITE_ENTITY},
+        traders::{
+            app::get_trader_use_case::find_trader,
+            domain::{
+                repository::TraderRepository,
+                requisite::{mask_number, Requisite},
+            },
+            dto::trader_dto::CreateRequisiteRequest,
+        },
+    },
+};
+
+pub struct CreateRequisiteUseCase {
+    trader_repository: Arc<dyn TraderRepository>,
+    secret_cipher: Arc<SecretCipher>,
+}
+
+impl CreateRequisiteUseCase {
+    pub fn new(
+        trader_repository: Arc<dyn TraderRepository>,
+        secret_cipher: Arc<SecretCipher>,
+    ) -> Self {
+        Self {
+            trader_repository,
+            secret_cipher,
+        }
+    }
+
+    /// Adds an active requisite; the number is encrypted before it is stored
+    pub async fn execute(
+        &self,
+        trader_id: Uuid,
+        request: CreateRequisiteRequest,
+        context: &AuditContext,
+    ) -> Result<Requisite, AppError> {
+        tracing::debug!("Adding {} requisite to trader {}", request.kind, trader_id);
+
+        find_trader(self.trader_repository.as_ref(), trader_id).await?;
+
+        let number = request.kind.normalize_number(&request.number)?;
+        let daily_limit = Money::parse(&request.daily_limit, &request.currency)?;
+        let monthly_limit = Money::parse(&request.monthly_limit, &request.currency)?;
+
+        let requisite = Requisite::new(
+            trader_id,
+            request.kind,
+            validate_required("Bank name", &request.bank_name)?,
+            validate_required("Holder name", &request.holder_name)?,
+            mask_number(&number),
+            self.secret_cipher.encrypt(&number)?,
+            daily_limit,
+            monthly_limit,
+        )?;
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Create,
+            REQUISITE_ENTITY,
+            requisite.id,
+            None,
+            Some(&requisite.audit_snapshot()),
+        );
+        let requisite = self
+            .trader_repository
+            .create_requisite(requisite, audit)
+            .await?;
+
+        tracing::info!("Requisite {} added to trader {}", requisite.id, trader_id);
+
+        Ok(requisite)
+    }
+}
+
+/// Trimmed, non-empty value of the `field` text field
+pub(crate) fn validate_required(field: &str, value: &str) -> Result<String, AppError> {
+    let value = value.trim();
+
+    if value.is_empty() {
+        return Err(AppError::ValidationError(format!(
+            "{} cannot be empty",
+            field
+        )));
+    }
+
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::Currency;
+    use crate::domains::traders::domain::{
+        repository::MockTraderRepository, requisite::RequisiteKind, trader::Trader,
+    };
+
+    fn request(number: &str) -> CreateRequisiteRequest {
+        CreateRequisiteRequest {
+            kind: RequisiteKind::Card,
+            number: number.to_string(),
+            bank_name: " Example Bank ".to_string(),
+            holder_name: "JANE DOE".to_string(),
+            currency: "usd".to_string(),
+            daily_limit: "5000".to_string(),
+            monthly_limit: "50000.00".to_string(),
+        }
+    }
+
+    fn trader_repository(creates: usize) -> MockTraderRepository {
+        let mut trader_repository = MockTraderRepository::new();
+        trader_repository
+            .expect_find_by_id()
+            .returning(|_| Ok(Some(Trader::new("Alice".to_string(), None))));
+        trader_repository
+            .expect_create_requisite()
+            .withf(|requisite, audit| {
+                audit.entity_id == requisite.id
+                    && audit.changes["masked_number"]["after"] == "**** 4242"
+                    && !audit.changes.to_string().contains("4242424242424242")
+            })
+            .times(creates)
+            .returning(|requisite, _| Ok(requisite));
+        trader_repository
+    }
+
+    #[tokio::test]
+    async fn test_number_is_stored_encrypted_and_masked() {
+        let cipher = Arc::new(SecretCipher::new("test"));
+        let use_case = CreateRequisiteUseCase::new(Arc::new(trader_repository(1)), cipher.clone());
+
+        let requisite = use_case
+            .execute(
+                Uuid::new_v4(),
+                request("4242 4242 4242 4242"),
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(requisite.masked_number, "**** 4242");
+        assert_eq!(requisite.bank_name, "Example Bank");
+        assert_ne!(requisite.encrypted_number, "4242424242424242");
+        assert_eq!(
+            cipher.decrypt(&requisite.encrypted_number).unwrap(),
+            "4242424242424242"
+        );
+        assert_eq!(requisite.daily_limit, Money::new(500_000, Currency::Usd));
+        assert!(requisite.is_active);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_card_number_is_rejected() {
+        let use_case = CreateRequisiteUseCase::new(
+            Arc::new(trader_repository(0)),
+            Arc::new(SecretCipher::new("test")),
+        );
+
+        let result = use_case
+            .execute(
+                Uuid::new_v4(),
+                request("4242 4242 4242 4241"),
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
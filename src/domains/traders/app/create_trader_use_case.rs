@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, TRADER_ENTITY},
+        traders::{
+            domain::{repository::TraderRepository, trader::Trader},
+            dto::trader_dto::CreateTraderRequest,
+        },
+    },
+};
+
+pub struct CreateTraderUseCase {
+    trader_repository: Arc<dyn TraderRepository>,
+}
+
+impl CreateTraderUseCase {
+    pub fn new(trader_repository: Arc<dyn TraderRepository>) -> Self {
+        Self { trader_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        request: CreateTraderRequest,
+        context: &AuditContext,
+    ) -> Result<Trader, AppError> {
+        tracing::debug!("Creating trader '{}'", request.name);
+
+        let name = validate_trader_name(&request.name)?;
+
+        if self.trader_repository.exists_by_name(&name).await? {
+            return Err(AppError::ValidationError(format!(
+                "Trader '{}' already exists",
+                name
+            )));
+        }
+
+        let trader = Trader::new(name, normalize_contact(request.contact));
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Create,
+            TRADER_ENTITY,
+            trader.id,
+            None,
+            Some(&trader.audit_snapshot()),
+        );
+        let trader = self.trader_repository.create(trader, audit).await?;
+
+        tracing::info!("Trader {} created successfully", trader.id);
+
+        Ok(trader)
+    }
+}
+
+/// Trimmed, non-empty trader name
+pub(crate) fn validate_trader_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(AppError::ValidationError(
+            "Trader name cannot be empty".to_string(),
+        ));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Trimmed contact; blank values clear it
+pub(crate) fn normalize_contact(contact: Option<String>) -> Option<String> {
+    contact
+        .map(|contact| contact.trim().to_string())
+        .filter(|contact| !contact.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::traders::domain::{
+        repository::MockTraderRepository, trader::TraderAvailability,
+    };
+
+    #[tokio::test]
+    async fn test_create_trader_starts_offline() {
+        let mut trader_repository = MockTraderRepository::new();
+        trader_repository
+            .expect_exists_by_name()
+            .returning(|_| Ok(false));
+        trader_repository
+            .expect_create()
+            .withf(|trader, audit| {
+                trader.name == "Alice"
+                    && trader.contact.is_none()
+                    && audit.entity_type == TRADER_ENTITY
+                    && audit.entity_id == trader.id
+            })
+            .times(1)
+            .returning(|trader, _| Ok(trader));
+
+        let use_case = CreateTraderUseCase::new(Arc::new(trader_repository));
+
+        let trader = use_case
+            .execute(
+                CreateTraderRequest {
+                    name: " Alice ".to_string(),
+                    contact: Some("  ".to_string()),
+                },
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(trader.availability, TraderAvailability::Offline);
+    }
+
+    #[tokio::test]
+    async fn test_create_trader_rejects_duplicate_name() {
+        let mut trader_repository = MockTraderRepository::new();
+        trader_repository
+            .expect_exists_by_name()
+            .returning(|_| Ok(true));
+        trader_repository.expect_create().never();
+
+        let use_case = CreateTraderUseCase::new(Arc::new(trader_repository));
+
+        let result = use_case
+            .execute(
+                CreateTraderRequest {
+                    name: "Alice".to_string(),
+                    contact: None,
+                },
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, TRADER_ENTITY},
+        ledger::domain::{
+            account::{AccountKind, LedgerAccountFilter},
+            repository::LedgerRepository,
+        },
+        traders::{app::get_trader_use_case::find_trader, domain::repository::TraderRepository},
+    },
+};
+
+pub struct DeleteTraderUseCase {
+    trader_repository: Arc<dyn TraderRepository>,
+    ledger_repository: Arc<dyn LedgerRepository>,
+}
+
+impl DeleteTraderUseCase {
+    pub fn new(
+        trader_repository: Arc<dyn TraderRepository>,
+        ledger_repository: Arc<dyn LedgerRepository>,
+    ) -> Self {
+        Self {
+            trader_repository,
+            ledger_repository,
+        }
+    }
+
+    /// Deletes a trader with its requisites. Traders with a ledger account are kept
+    /// so their deposits and postings stay attributable.
+    pub async fn execute(&self, trader_id: Uuid, context: &AuditContext) -> Result<(), AppError> {
+        tracing::debug!("Deleting trader {}", trader_id);
+
+        let trader = find_trader(self.trader_repository.as_ref(), trader_id).await?;
+
+        let filter = LedgerAccountFilter {
+            kind: Some(AccountKind::Trader),
+            owner_id: Some(trader_id),
+            currency: None,
+        };
+        if !self
+            .ledger_repository
+            .list_accounts(filter, 1, 0)
+            .await?
+            .is_empty()
+        {
+            return Err(AppError::ValidationError(format!(
+                "Trader {} has ledger history and cannot be deleted",
+                trader_id
+            )));
+        }
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Delete,
+            TRADER_ENTITY,
+            trader_id,
+            Some(&trader.audit_snapshot()),
+            None,
+        );
+        self.trader_repository.delete(trader_id, audit).await?;
+
+        tracing::info!("Trader {} deleted successfully", trader_id);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::Currency;
+    use crate::domains::{
+        ledger::domain::{account::LedgerAccount, repository::MockLedgerRepository},
+        traders::domain::{repository::MockTraderRepository, trader::Trader},
+    };
+
+    fn trader_repository(deletes: usize) -> MockTraderRepository {
+        let mut trader_repository = MockTraderRepository::new();
+        trader_repository
+            .expect_find_by_id()
+            .returning(|_| Ok(Some(Trader::new("Alice".to_string(), None))));
+        trader_repository
+            .expect_delete()
+            .times(deletes)
+            .returning(|_, _| Ok(()));
+        trader_repository
+    }
+
+    #[tokio::test]
+    async fn test_delete_trader_without_ledger_history() {
+        let mut ledger_repository = MockLedgerRepository::new();
+        ledger_repository
+            .expect_list_accounts()
+            .returning(|_, _, _| Ok(Vec::new()));
+
+        let use_case =
+            DeleteTraderUseCase::new(Arc::new(trader_repository(1)), Arc::new(ledger_repository));
+
+        use_case
+            .execute(Uuid::new_v4(), &AuditContext::default())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_trader_with_deposit_is_kept() {
+        let mut ledger_repository = MockLedgerRepository::new();
+        ledger_repository
+            .expect_list_accounts()
+            .returning(|filter, _, _| {
+                Ok(vec![LedgerAccount::new(
+                    AccountKind::Trader,
+                    filter.owner_id,
+                    Currency::Usd,
+                )])
+            });
+
+        let use_case =
+            DeleteTraderUseCase::new(Arc::new(trader_repository(0)), Arc::new(ledger_repository));
+
+        let result = use_case
+            .execute(Uuid::new_v4(), &AuditContext::default())
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
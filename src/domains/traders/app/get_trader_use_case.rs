@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::traders::domain::{
+        repository::TraderRepository,
+        requisite::Requisite,
+        trader::{Trader, TraderFilter},
+    },
+};
+
+pub struct GetTraderUseCase {
+    trader_repository: Arc<dyn TraderRepository>,
+}
+
+impl GetTraderUseCase {
+    pub fn new(trader_repository: Arc<dyn TraderRepository>) -> Self {
+        Self { trader_repository }
+    }
+
+    pub async fn execute(&self, trader_id: Uuid) -> Result<Trader, AppError> {
+        tracing::debug!("Fetching trader {}", trader_id);
+
+        find_trader(self.trader_repository.as_ref(), trader_id).await
+    }
+
+    pub async fn list(
+        &self,
+        filter: TraderFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Trader>, AppError> {
+        tracing::debug!("Listing traders with {:?}", filter);
+
+        if limit > 100 {
+            return Err(AppError::ValidationError(
+                "Limit cannot exceed 100".to_string(),
+            ));
+        }
+
+        self.trader_repository.list(filter, limit, offset).await
+    }
+}
+
+pub(crate) async fn find_trader(
+    trader_repository: &dyn TraderRepository,
+    trader_id: Uuid,
+) -> Result<Trader, AppError> {
+    trader_repository
+        .find_by_id(trader_id)
+        .await?
+        .ok_or(AppError::NotFound(format!(
+            "Trader {} not found",
+            trader_id
+        )))
+}
+
+/// Requisite `requisite_id`, unless it belongs to another trader
+pub(crate) async fn find_trader_requisite(
+    trader_repository: &dyn TraderRepository,
+    trader_id: Uuid,
+    requisite_id: Uuid,
+) -> Result<Requisite, AppError> {
+    trader_repository
+        .find_requisite(requisite_id)
+        .await?
+        .filter(|requisite| requisite.trader_id == trader_id)
+        .ok_or(AppError::NotFound(format!(
+            "Requisite {} not found",
+            requisite_id
+        )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::{Currency, Money};
+    use crate::domains::traders::domain::{
+        repository::MockTraderRepository, requisite::RequisiteKind,
+    };
+
+    #[tokio::test]
+    async fn test_requisite_of_other_trader_is_not_found() {
+        let requisite = Requisite::new(
+            Uuid::new_v4(),
+            RequisiteKind::Card,
+            "Bank".to_string(),
+            "Jane Doe".to_string(),
+            "**** 4242".to_string(),
+            "ciphertext".to_string(),
+            Money::new(100, Currency::Usd),
+            Money::new(1000, Currency::Usd),
+        )
+        .unwrap();
+        let requisite_id = requisite.id;
+
+        let mut trader_repository = MockTraderRepository::new();
+        trader_repository
+            .expect_find_requisite()
+            .returning(move |_| Ok(Some(requisite.clone())));
+
+        let result = find_trader_requisite(&trader_repository, Uuid::new_v4(), requisite_id).await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::{error::AppError, money::Money},
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, REQUISITE_ENTITY},
+        traders::{
+            app::{
+                create_requisite_use_case::validate_required,
+                get_trader_use_case::{find_trader, find_trader_requisite},
+                update_trader_use_case::trader_audit,
+            },
+            domain::{
+                repository::TraderRepository, requisite::Requisite, trader::TraderAvailability,
+            },
+            dto::trader_dto::UpdateRequisiteRequest,
+        },
+    },
+};
+
+pub struct UpdateRequisiteUseCase {
+    trader_repository: Arc<dyn TraderRepository>,
+}
+
+impl UpdateRequisiteUseCase {
+    pub fn new(trader_repository: Arc<dyn TraderRepository>) -> Self {
+        Self { trader_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        trader_id: Uuid,
+        requisite_id: Uuid,
+        request: UpdateRequisiteRequest,
+        context: &AuditContext,
+    ) -> Result<Requisite, AppError> {
+        tracing::debug!(
+            "Updating requisite {} of trader {}",
+            requisite_id,
+            trader_id
+        );
+
+        let mut requisite =
+            find_trader_requisite(self.trader_repository.as_ref(), trader_id, requisite_id).await?;
+        let before = requisite.audit_snapshot();
+
+        if let Some(bank_name) = request.bank_name {
+            requisite.update_bank_name(validate_required("Bank name", &bank_name)?);
+        }
+        if let Some(holder_name) = request.holder_name {
+            requisite.update_holder_name(validate_required("Holder name", &holder_name)?);
+        }
+
+        let currency = requisite.currency();
+        let daily_limit = request
+            .daily_limit
+            .map(|amount| Money::from_decimal(&amount, currency))
+            .transpose()?;
+        let monthly_limit = request
+            .monthly_limit
+            .map(|amount| Money::from_decimal(&amount, currency))
+            .transpose()?;
+        if daily_limit.is_some() || monthly_limit.is_some() {
+            requisite.update_limits(daily_limit, monthly_limit)?;
+        }
+
+        if let Some(is_active) = request.is_active {
+            requisite.set_active(is_active);
+        }
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Update,
+            REQUISITE_ENTITY,
+            requisite.id,
+            Some(&before),
+            Some(&requisite.audit_snapshot()),
+        );
+        let requisite = self
+            .trader_repository
+            .update_requisite(requisite, audit)
+            .await?;
+        self.take_offline_if_unreachable(trader_id, context).await?;
+
+        tracing::info!("Requisite {} updated successfully", requisite_id);
+
+        Ok(requisite)
+    }
+
+    pub async fn delete(
+        &self,
+        trader_id: Uuid,
+        requisite_id: Uuid,
+        context: &AuditContext,
+    ) -> Result<(), AppError> {
+        tracing::debug!(
+            "Deleting requisite {} of trader {}",
+            requisite_id,
+            trader_id
+        );
+
+        let requisite =
+            find_trader_requisite(self.trader_repository.as_ref(), trader_id, requisite_id).await?;
+
+        let audit = AuditEntry::new(
+            context,
+            AuditAction::Delete,
+            REQUISITE_ENTITY,
+            requisite_id,
+            Some(&requisite.audit_snapshot()),
+            None,
+        );
+        self.trader_repository
+            .delete_requisite(requisite_id, audit)
+            .await?;
+        self.take_offline_if_unreachable(trader_id, context).await?;
+
+        tracing::info!("Requisite {} deleted successfully", requisite_id);
+
+        Ok(())
+    }
+
+    /// An online trader needs an active requisite to receive payments on
+    async fn take_offline_if_unreachable(
+        &self,
+        trader_id: Uuid,
+        context: &AuditContext,
+    ) -> Result<(), AppError> {
+        let mut trader = find_trader(self.trader_repository.as_ref(), trader_id).await?;
+
+        if trader.is_online() && !trader.has_active_requisite() {
+            let before = trader.audit_snapshot();
+            trader.set_availability(TraderAvailability::Offline)?;
+            let audit = trader_audit(context, &before, &trader);
+            self.trader_repository.update(trader, audit).await?;
+
+            tracing::info!(
+                "Trader {} went offline: no active requisites left",
+                trader_id
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::Currency;
+    use crate::domains::traders::domain::{
+        repository::MockTraderRepository, requisite::RequisiteKind, trader::Trader,
+    };
+
+    fn online_trader_with_requisite() -> (Trader, Requisite) {
+        let mut trader = Trader::new("Alice".to_string(), None);
+        let requisite = Requisite::new(
+            trader.id,
+            RequisiteKind::Card,
+            "Bank".to_string(),
+            "Jane Doe".to_string(),
+            "**** 4242".to_string(),
+            "ciphertext".to_string(),
+            Money::new(10_000, Currency::Usd),
+            Money::new(100_000, Currency::Usd),
+        )
+        .unwrap();
+        trader.requisites.push(requisite.clone());
+        trader.set_availability(TraderAvailability::Online).unwrap();
+
+        (trader, requisite)
+    }
+
+    #[tokio::test]
+    async fn test_deactivating_last_requisite_takes_trader_offline() {
+        let (trader, requisite) = online_trader_with_requisite();
+        let (trader_id, requisite_id) = (trader.id, requisite.id);
+
+        let mut trader_repository = MockTraderRepository::new();
+        trader_repository
+            .expect_find_requisite()
+            .returning(move |_| Ok(Some(requisite.clone())));
+        trader_repository
+            .expect_update_requisite()
+            .withf(|requisite, audit| {
+                audit.entity_id == requisite.id
+                    && audit.changes
+                        == serde_json::json!({ "is_active": { "before": true, "after": false } })
+            })
+            .returning(|requisite, _| Ok(requisite));
+        trader_repository.expect_find_by_id().returning(move |_| {
+            let mut trader = trader.clone();
+            trader.requisites[0].set_active(false);
+            Ok(Some(trader))
+        });
+        trader_repository
+            .expect_update()
+            .withf(|trader, audit| !trader.is_online() && audit.entity_id == trader.id)
+            .times(1)
+            .returning(|trader, _| Ok(trader));
+
+        let requisite = UpdateRequisiteUseCase::new(Arc::new(trader_repository))
+            .execute(
+                trader_id,
+                requisite_id,
+                UpdateRequisiteRequest {
+                    bank_name: None,
+                    holder_name: None,
+                    daily_limit: None,
+                    monthly_limit: None,
+                    is_active: Some(false),
+                },
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(!requisite.is_active);
+    }
+
+    #[tokio::test]
+    async fn test_limits_are_parsed_in_requisite_currency() {
+        let (_, requisite) = online_trader_with_requisite();
+        let (trader_id, requisite_id) = (requisite.trader_id, requisite.id);
+
+        let mut trader_repository = MockTraderRepository::new();
+        trader_repository
+            .expect_find_requisite()
+            .returning(move |_| Ok(Some(requisite.clone())));
+        trader_repository.expect_update_requisite().never();
+
+        let result = UpdateRequisiteUseCase::new(Arc::new(trader_repository))
+            .execute(
+                trader_id,
+                requisite_id,
+                UpdateRequisiteRequest {
+                    bank_name: None,
+                    holder_name: None,
+                    daily_limit: Some("2000.00".to_string()),
+                    monthly_limit: None,
+                    is_active: None,
+                },
+                &AuditContext::default(),
+            )
+            .await;
+
+        // 2000.00 USD is above the 1000.00 USD monthly limit
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
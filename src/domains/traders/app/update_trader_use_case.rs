@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::{
+        audit::domain::audit_entry::{AuditAction, AuditContext, AuditEntry, TRADER_ENTITY},
+        traders::{
+            app::{
+                create_trader_use_case::{normalize_contact, validate_trader_name},
+                get_trader_use_case::find_trader,
+            },
+            domain::{
+                repository::TraderRepository,
+                trader::{Trader, TraderAvailability},
+            },
+            dto::trader_dto::UpdateTraderRequest,
+        },
+    },
+};
+
+pub struct UpdateTraderUseCase {
+    trader_repository: Arc<dyn TraderRepository>,
+}
+
+impl UpdateTraderUseCase {
+    pub fn new(trader_repository: Arc<dyn TraderRepository>) -> Self {
+        Self { trader_repository }
+    }
+
+    pub async fn execute(
+        &self,
+        trader_id: Uuid,
+        request: UpdateTraderRequest,
+        context: &AuditContext,
+    ) -> Result<Trader, AppError> {
+        tracing::debug!("Updating trader {}", trader_id);
+
+        let mut trader = find_trader(self.trader_repository.as_ref(), trader_id).await?;
+        let before = trader.audit_snapshot();
+
+        if let Some(name) = request.name {
+            let name = validate_trader_name(&name)?;
+
+            if name != trader.name && self.trader_repository.exists_by_name(&name).await? {
+                return Err(AppError::ValidationError(format!(
+                    "Trader '{}' already exists",
+                    name
+                )));
+            }
+
+            trader.update_name(name);
+        }
+
+        if request.contact.is_some() {
+            trader.update_contact(normalize_contact(request.contact));
+        }
+
+        let audit = trader_audit(context, &before, &trader);
+        let trader = self.trader_repository.update(trader, audit).await?;
+
+        tracing::info!("Trader {} updated successfully", trader_id);
+
+        Ok(trader)
+    }
+
+    pub async fn change_availability(
+        &self,
+        trader_id: Uuid,
+        availability: TraderAvailability,
+        context: &AuditContext,
+    ) -> Result<Trader, AppError> {
+        tracing::debug!("Setting trader {} {}", trader_id, availability);
+
+        let mut trader = find_trader(self.trader_repository.as_ref(), trader_id).await?;
+        let before = trader.audit_snapshot();
+        trader.set_availability(availability)?;
+
+        let audit = trader_audit(context, &before, &trader);
+        let trader = self.trader_repository.update(trader, audit).await?;
+
+        tracing::info!("Trader {} is now {}", trader_id, availability);
+
+        Ok(trader)
+    }
+}
+
+/// Update entry for a change of `trader` from its `before` snapshot
+pub(crate) fn trader_audit(context: &AuditContext, before: &Value, trader: &Trader) -> AuditEntry {
+    AuditEntry::new(
+        context,
+        AuditAction::Update,
+        TRADER_ENTITY,
+        trader.id,
+        Some(before),
+        Some(&trader.audit_snapshot()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domains::traders::domain::repository::MockTraderRepository;
+
+    #[tokio::test]
+    async fn test_trader_without_requisites_cannot_go_online() {
+        let trader = Trader::new("Alice".to_string(), None);
+        let trader_id = trader.id;
+
+        let mut trader_repository = MockTraderRepository::new();
+        trader_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(trader.clone())));
+        trader_repository.expect_update().never();
+
+        let result = UpdateTraderUseCase::new(Arc::new(trader_repository))
+            .change_availability(
+                trader_id,
+                TraderAvailability::Online,
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+    }
+}
@@ -0,0 +1,68 @@
+use super::requisite::Requisite;
+use super::trader::{Trader, TraderFilter};
+use crate::common::error::AppError;
+use crate::domains::audit::domain::audit_entry::AuditEntry;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait TraderRepository: Send + Sync {
+    /// Trader with its requisites, oldest first
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Trader>, AppError>;
+    /// Ordered by name
+    async fn list(
+        &self,
+        filter: TraderFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Trader>, AppError>;
+
+    async fn exists_by_name(&self, name: &str) -> Result<bool, AppError>;
+
+    /// Changes are stored together with their `audit` entry, or not at all
+    async fn create(&self, trader: Trader, audit: AuditEntry) -> Result<Trader, AppError>;
+    /// Stores the trader's own fields; requisites are saved separately
+    async fn update(&self, trader: Trader, audit: AuditEntry) -> Result<Trader, AppError>;
+    /// Removes the trader together with its requisites
+    async fn delete(&self, id: Uuid, audit: AuditEntry) -> Result<(), AppError>;
+
+    async fn find_requisite(&self, id: Uuid) -> Result<Option<Requisite>, AppError>;
+    async fn create_requisite(
+        &self,
+        requisite: Requisite,
+        audit: AuditEntry,
+    ) -> Result<Requisite, AppError>;
+    async fn update_requisite(
+        &self,
+        requisite: Requisite,
+        audit: AuditEntry,
+    ) -> Result<Requisite, AppError>;
+    async fn delete_requisite(&self, id: Uuid, audit: AuditEntry) -> Result<(), AppError>;
+}
+
+#[cfg(test)]
+use mockall::mock;
+
+#[cfg(test)]
+mock! {
+    pub TraderRepository {}
+
+    #[async_trait]
+    impl TraderRepository for TraderRepository {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Trader>, AppError>;
+        async fn list(
+            &self,
+            filter: TraderFilter,
+            limit: i64,
+            offset: i64,
+        ) -> Result<Vec<Trader>, AppError>;
+        async fn exists_by_name(&self, name: &str) -> Result<bool, AppError>;
+        async fn create(&self, trader: Trader, audit: AuditEntry) -> Result<Trader, AppError>;
+        async fn update(&self, trader: Trader, audit: AuditEntry) -> Result<Trader, AppError>;
+        async fn delete(&self, id: Uuid, audit: AuditEntry) -> Result<(), AppError>;
+        async fn find_requisite(&self, id: Uuid) -> Result<Option<Requisite>, AppError>;
+        async fn create_requisite(&self, requisite: Requisite, audit: AuditEntry) -> Result<Requisite, AppError>;
+        async fn update_requisite(&self, requisite: Requisite, audit: AuditEntry) -> Result<Requisite, AppError>;
+        async fn delete_requisite(&self, id: Uuid, audit: AuditEntry) -> Result<(), AppError>;
+    }
+}
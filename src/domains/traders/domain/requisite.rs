@@ -0,0 +1,319 @@
+use crate::common::{
+    error::AppError,
+    money::{Currency, Money},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{fmt, str::FromStr};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Card or bank account a trader receives customer transfers on.
+/// The number is only kept encrypted; listings show the masked form.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Requisite {
+    pub id: Uuid,
+    pub trader_id: Uuid,
+    pub kind: RequisiteKind,
+    pub bank_name: String,
+    pub holder_name: String,
+    /// Last four characters of the number, e.g. `**** 4242`
+    pub masked_number: String,
+    pub encrypted_number: String,
+    /// Most the requisite may receive per calendar day (UTC)
+    pub daily_limit: Money,
+    /// Most the requisite may receive per calendar month (UTC)
+    pub monthly_limit: Money,
+    /// Inactive requisites are kept for history but never matched
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Requisite {
+    /// New requisites are active right away. Both limits are in the requisite's currency.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        trader_id: Uuid,
+        kind: RequisiteKind,
+        bank_name: String,
+        holder_name: String,
+        masked_number: String,
+        encrypted_number: String,
+        daily_limit: Money,
+        monthly_limit: Money,
+    ) -> Result<Self, AppError> {
+        validate_limits(daily_limit, monthly_limit)?;
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            trader_id,
+            kind,
+            bank_name,
+            holder_name,
+            masked_number,
+            encrypted_number,
+            daily_limit,
+            monthly_limit,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Audited fields; only the masked number is recorded
+    pub fn audit_snapshot(&self) -> Value {
+        json!({
+            "trader_id": self.trader_id,
+            "kind": self.kind,
+            "bank_name": self.bank_name,
+            "holder_name": self.holder_name,
+            "masked_number": self.masked_number,
+            "daily_limit": self.daily_limit.to_string(),
+            "monthly_limit": self.monthly_limit.to_string(),
+            "is_active": self.is_active,
+        })
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.daily_limit.currency()
+    }
+
+    pub fn update_bank_name(&mut self, bank_name: String) {
+        self.bank_name = bank_name;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn update_holder_name(&mut self, holder_name: String) {
+        self.holder_name = holder_name;
+        self.updated_at = Utc::now();
+    }
+
+    /// Replaces either limit; the result must still be positive with daily <= monthly
+    pub fn update_limits(
+        &mut self,
+        daily_limit: Option<Money>,
+        monthly_limit: Option<Money>,
+    ) -> Result<(), AppError> {
+        let daily_limit = daily_limit.unwrap_or(self.daily_limit);
+        let monthly_limit = monthly_limit.unwrap_or(self.monthly_limit);
+        validate_limits(daily_limit, monthly_limit)?;
+
+        if daily_limit.currency() != self.currency() {
+            return Err(AppError::ValidationError(format!(
+                "Limits must be in {}, the requisite's currency",
+                self.currency()
+            )));
+        }
+
+        self.daily_limit = daily_limit;
+        self.monthly_limit = monthly_limit;
+        self.updated_at = Utc::now();
+
+        Ok(())
+    }
+
+    pub fn set_active(&mut self, is_active: bool) {
+        self.is_active = is_active;
+        self.updated_at = Utc::now();
+    }
+}
+
+fn validate_limits(daily_limit: Money, monthly_limit: Money) -> Result<(), AppError> {
+    if daily_limit.currency() != monthly_limit.currency() {
+        return Err(AppError::ValidationError(
+            "Daily and monthly limits must be in the same currency".to_string(),
+        ));
+    }
+
+    if !daily_limit.is_positive() || !monthly_limit.is_positive() {
+        return Err(AppError::ValidationError(
+            "Turnover limits must be positive".to_string(),
+        ));
+    }
+
+    if daily_limit.minor_units() > monthly_limit.minor_units() {
+        return Err(AppError::ValidationError(format!(
+            "Daily limit {} exceeds monthly limit {}",
+            daily_limit, monthly_limit
+        )));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RequisiteKind {
+    /// Bank card, identified by its PAN
+    Card,
+    /// Bank account, identified by its account number or IBAN
+    Account,
+}
+
+impl RequisiteKind {
+    /// Value stored in the `kind` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RequisiteKind::Card => "card",
+            RequisiteKind::Account => "account",
+        }
+    }
+
+    /// Strips spaces and dashes and checks the number's format:
+    /// - card: 13 to 19 digits with a valid Luhn check digit
+    /// - account: 5 to 34 letters or digits, uppercased
+    pub fn normalize_number(&self, number: &str) -> Result<String, AppError> {
+        let number: String = number
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .collect::<String>()
+            .to_ascii_uppercase();
+
+        match self {
+            RequisiteKind::Card => {
+                if !(13..=19).contains(&number.len()) || !number.chars().all(|c| c.is_ascii_digit())
+                {
+                    return Err(AppError::ValidationError(
+                        "Card number must be 13 to 19 digits".to_string(),
+                    ));
+                }
+                if !passes_luhn(&number) {
+                    return Err(AppError::ValidationError(
+                        "Card number fails the checksum".to_string(),
+                    ));
+                }
+            }
+            RequisiteKind::Account => {
+                if !(5..=34).contains(&number.len())
+                    || !number.chars().all(|c| c.is_ascii_alphanumeric())
+                {
+                    return Err(AppError::ValidationError(
+                        "Account number must be 5 to 34 letters or digits".to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(number)
+    }
+}
+
+impl fmt::Display for RequisiteKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RequisiteKind {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "card" => Ok(RequisiteKind::Card),
+            "account" => Ok(RequisiteKind::Account),
+            other => Err(AppError::InternalError(format!(
+                "Unknown requisite kind '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Masked display form of a normalized number: `**** ` and its last four characters
+pub fn mask_number(number: &str) -> String {
+    let visible = number.len().saturating_sub(4);
+    format!("**** {}", &number[visible..])
+}
+
+fn passes_luhn(digits: &str) -> bool {
+    let sum: u32 = digits
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(index, digit)| {
+            if index % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                digit
+            }
+        })
+        .sum();
+
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_card_number() {
+        let number = RequisiteKind::Card
+            .normalize_number("4242 4242-4242 4242")
+            .unwrap();
+        assert_eq!(number, "4242424242424242");
+        assert_eq!(mask_number(&number), "**** 4242");
+
+        assert!(RequisiteKind::Card
+            .normalize_number("4242 4242 4242 4241")
+            .is_err());
+        assert!(RequisiteKind::Card.normalize_number("4242").is_err());
+        assert!(RequisiteKind::Card
+            .normalize_number("4242abcd42424242")
+            .is_err());
+    }
+
+    #[test]
+    fn test_normalize_account_number() {
+        let number = RequisiteKind::Account
+            .normalize_number("gb82 west 1234 5698 7654 32")
+            .unwrap();
+        assert_eq!(number, "GB82WEST12345698765432");
+
+        assert!(RequisiteKind::Account.normalize_number("123").is_err());
+        assert!(RequisiteKind::Account
+            .normalize_number("1234/5678")
+            .is_err());
+    }
+
+    #[test]
+    fn test_limits_must_be_positive_and_ordered() {
+        let requisite = |daily: i64, monthly: i64| {
+            Requisite::new(
+                Uuid::new_v4(),
+                RequisiteKind::Card,
+                "Bank".to_string(),
+                "Jane Doe".to_string(),
+                "**** 4242".to_string(),
+                "ciphertext".to_string(),
+                Money::new(daily, Currency::Eur),
+                Money::new(monthly, Currency::Eur),
+            )
+        };
+
+        assert!(requisite(0, 100).is_err());
+        assert!(requisite(200, 100).is_err());
+
+        let mut requisite = requisite(100, 100).unwrap();
+        assert!(requisite
+            .update_limits(None, Some(Money::new(50, Currency::Eur)))
+            .is_err());
+        assert!(requisite
+            .update_limits(Some(Money::new(50, Currency::Usd)), None)
+            .is_err());
+        requisite
+            .update_limits(Some(Money::new(50, Currency::Eur)), None)
+            .unwrap();
+        assert_eq!(requisite.daily_limit.minor_units(), 50);
+        assert_eq!(requisite.monthly_limit.minor_units(), 100);
+    }
+}
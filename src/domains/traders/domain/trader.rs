@@ -0,0 +1,197 @@
+use crate::common::error::AppError;
+use crate::domains::traders::domain::requisite::Requisite;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{fmt, str::FromStr};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// P2P counterparty that receives customer transfers on its requisites.
+/// The security deposit lives in the ledger as the trader's `trader` accounts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Trader {
+    pub id: Uuid,
+    pub name: String,
+    /// Telegram handle, phone or e-mail used by operators to reach the trader
+    pub contact: Option<String>,
+    pub availability: TraderAvailability,
+    pub availability_changed_at: DateTime<Utc>,
+    pub requisites: Vec<Requisite>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Trader {
+    /// New traders start offline without any requisites
+    pub fn new(name: String, contact: Option<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            contact,
+            availability: TraderAvailability::Offline,
+            availability_changed_at: now,
+            requisites: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Audited fields of the trader itself; requisites are audited on their own
+    pub fn audit_snapshot(&self) -> Value {
+        json!({
+            "name": self.name,
+            "contact": self.contact,
+            "availability": self.availability,
+        })
+    }
+
+    pub fn is_online(&self) -> bool {
+        self.availability == TraderAvailability::Online
+    }
+
+    pub fn has_active_requisite(&self) -> bool {
+        self.requisites.iter().any(|requisite| requisite.is_active)
+    }
+
+    pub fn update_name(&mut self, name: String) {
+        self.name = name;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn update_contact(&mut self, contact: Option<String>) {
+        self.contact = contact;
+        self.updated_at = Utc::now();
+    }
+
+    /// Switches the trader online or offline; setting the current value is a no-op.
+    /// Going online needs at least one active requisite to receive transfers on.
+    pub fn set_availability(&mut self, target: TraderAvailability) -> Result<(), AppError> {
+        if self.availability == target {
+            return Ok(());
+        }
+
+        if target == TraderAvailability::Online && !self.has_active_requisite() {
+            return Err(AppError::InvalidStateTransition(
+                "Trader needs at least one active requisite before going online".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        self.availability = target;
+        self.availability_changed_at = now;
+        self.updated_at = now;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TraderAvailability {
+    /// Accepting new payments
+    Online,
+    Offline,
+}
+
+impl TraderAvailability {
+    /// Value stored in the `availability` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TraderAvailability::Online => "online",
+            TraderAvailability::Offline => "offline",
+        }
+    }
+}
+
+impl fmt::Display for TraderAvailability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TraderAvailability {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "online" => Ok(TraderAvailability::Online),
+            "offline" => Ok(TraderAvailability::Offline),
+            other => Err(AppError::InternalError(format!(
+                "Unknown trader availability '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Trader search criteria; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TraderFilter {
+    pub availability: Option<TraderAvailability>,
+}
+
+impl TraderFilter {
+    pub fn matches(&self, trader: &Trader) -> bool {
+        self.availability
+            .is_none_or(|availability| trader.availability == availability)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::{Currency, Money};
+    use crate::domains::traders::domain::requisite::RequisiteKind;
+
+    fn requisite(trader_id: Uuid) -> Requisite {
+        Requisite::new(
+            trader_id,
+            RequisiteKind::Card,
+            "Bank".to_string(),
+            "Jane Doe".to_string(),
+            "**** 4242".to_string(),
+            "ciphertext".to_string(),
+            Money::new(100_000, Currency::Usd),
+            Money::new(1_000_000, Currency::Usd),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_going_online_needs_an_active_requisite() {
+        let mut trader = Trader::new("Alice".to_string(), None);
+
+        let result = trader.set_availability(TraderAvailability::Online);
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+
+        let mut inactive = requisite(trader.id);
+        inactive.set_active(false);
+        trader.requisites.push(inactive);
+        assert!(trader.set_availability(TraderAvailability::Online).is_err());
+
+        trader.requisites.push(requisite(trader.id));
+        trader.set_availability(TraderAvailability::Online).unwrap();
+        assert!(trader.is_online());
+
+        // Going offline is always allowed
+        trader.requisites.clear();
+        trader
+            .set_availability(TraderAvailability::Offline)
+            .unwrap();
+        assert!(!trader.is_online());
+    }
+
+    #[test]
+    fn test_setting_same_availability_keeps_timestamp() {
+        let mut trader = Trader::new("Alice".to_string(), None);
+        let changed_at = trader.availability_changed_at;
+
+        trader
+            .set_availability(TraderAvailability::Offline)
+            .unwrap();
+
+        assert_eq!(trader.availability_changed_at, changed_at);
+    }
+}
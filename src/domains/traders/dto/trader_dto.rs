@@ -0,0 +1,203 @@
+use crate::common::money::{Currency, Money};
+use crate::domains::traders::domain::{
+    requisite::{Requisite, RequisiteKind},
+    trader::{Trader, TraderAvailability, TraderFilter},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RequisiteResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    pub kind: RequisiteKind,
+
+    #[schema(example = "Example Bank")]
+    pub bank_name: String,
+
+    #[schema(example = "JANE DOE")]
+    pub holder_name: String,
+
+    /// Last four characters of the card or account number
+    #[schema(example = "**** 4242")]
+    pub masked_number: String,
+
+    pub currency: Currency,
+
+    /// Most the requisite may receive per calendar day (UTC)
+    pub daily_limit: Money,
+
+    /// Most the requisite may receive per calendar month (UTC)
+    pub monthly_limit: Money,
+
+    pub is_active: bool,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Requisite> for RequisiteResponse {
+    fn from(requisite: Requisite) -> Self {
+        Self {
+            id: requisite.id.to_string(),
+            kind: requisite.kind,
+            bank_name: requisite.bank_name,
+            holder_name: requisite.holder_name,
+            masked_number: requisite.masked_number,
+            currency: requisite.daily_limit.currency(),
+            daily_limit: requisite.daily_limit,
+            monthly_limit: requisite.monthly_limit,
+            is_active: requisite.is_active,
+            created_at: requisite.created_at,
+            updated_at: requisite.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TraderResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    #[schema(example = "Alice")]
+    pub name: String,
+
+    #[schema(example = "@alice_p2p")]
+    pub contact: Option<String>,
+
+    pub availability: TraderAvailability,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub availability_changed_at: DateTime<Utc>,
+
+    pub requisites: Vec<RequisiteResponse>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:00:00Z")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Trader> for TraderResponse {
+    fn from(trader: Trader) -> Self {
+        Self {
+            id: trader.id.to_string(),
+            name: trader.name,
+            contact: trader.contact,
+            availability: trader.availability,
+            availability_changed_at: trader.availability_changed_at,
+            requisites: trader
+                .requisites
+                .into_iter()
+                .map(RequisiteResponse::from)
+                .collect(),
+            created_at: trader.created_at,
+            updated_at: trader.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CreateTraderRequest {
+    #[schema(example = "Alice")]
+    pub name: String,
+
+    /// Telegram handle, phone or e-mail
+    #[schema(example = "@alice_p2p")]
+    pub contact: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateTraderRequest {
+    #[schema(example = "Alice Smith")]
+    pub name: Option<String>,
+
+    #[schema(example = "@alice_p2p")]
+    pub contact: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ChangeTraderAvailabilityRequest {
+    pub availability: TraderAvailability,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CreateRequisiteRequest {
+    pub kind: RequisiteKind,
+
+    /// Card number or account number/IBAN; spaces and dashes are ignored
+    #[schema(example = "4242 4242 4242 4242")]
+    pub number: String,
+
+    #[schema(example = "Example Bank")]
+    pub bank_name: String,
+
+    #[schema(example = "JANE DOE")]
+    pub holder_name: String,
+
+    /// ISO 4217 currency code the requisite receives
+    #[schema(example = "USD")]
+    pub currency: String,
+
+    /// Decimal amount in `currency`
+    #[schema(example = "5000.00")]
+    pub daily_limit: String,
+
+    /// Decimal amount in `currency`, at least the daily limit
+    #[schema(example = "50000.00")]
+    pub monthly_limit: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateRequisiteRequest {
+    #[schema(example = "Example Bank")]
+    pub bank_name: Option<String>,
+
+    #[schema(example = "JANE DOE")]
+    pub holder_name: Option<String>,
+
+    /// Decimal amount in the requisite's currency
+    #[schema(example = "7500.00")]
+    pub daily_limit: Option<String>,
+
+    /// Decimal amount in the requisite's currency
+    #[schema(example = "75000.00")]
+    pub monthly_limit: Option<String>,
+
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListTradersQuery {
+    /// Page size, at most 100
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    #[param(value_type = Option<String>, example = "online")]
+    pub availability: Option<TraderAvailability>,
+}
+
+impl From<ListTradersQuery> for TraderFilter {
+    fn from(query: ListTradersQuery) -> Self {
+        Self {
+            availability: query.availability,
+        }
+    }
+}
+
+fn default_limit() -> i64 {
+    20
+}
@@ -0,0 +1,30 @@
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "trader")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    #[sea_orm(unique)]
+    pub name: String,
+    pub contact: Option<String>,
+    pub availability: String,
+    pub availability_changed_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::trader_requisite_entity::Entity")]
+    TraderRequisite,
+}
+
+impl Related<super::trader_requisite_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TraderRequisite.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,254 @@
+use super::trader_entity::{self, Entity as TraderEntity};
+use super::trader_requisite_entity::{self, Entity as TraderRequisiteEntity};
+use crate::common::{error::AppError, money::Money};
+use crate::domains::{
+    audit::{
+        domain::audit_entry::AuditEntry, infra::audit_log_repository::PostgresAuditLogRepository,
+    },
+    traders::domain::{
+        repository::TraderRepository,
+        requisite::Requisite,
+        trader::{Trader, TraderFilter},
+    },
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, LoaderTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, SqlErr, TransactionTrait,
+};
+use uuid::Uuid;
+
+pub struct PostgresTraderRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresTraderRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn requisite_to_domain(model: trader_requisite_entity::Model) -> Result<Requisite, AppError> {
+        Ok(Requisite {
+            id: model.id,
+            trader_id: model.trader_id,
+            kind: model.kind.parse()?,
+            bank_name: model.bank_name,
+            holder_name: model.holder_name,
+            masked_number: model.masked_number,
+            encrypted_number: model.encrypted_number,
+            daily_limit: Money::new(model.daily_limit, model.currency),
+            monthly_limit: Money::new(model.monthly_limit, model.currency),
+            is_active: model.is_active,
+            created_at: model.created_at.with_timezone(&Utc),
+            updated_at: model.updated_at.with_timezone(&Utc),
+        })
+    }
+
+    fn requisite_to_active_model(requisite: Requisite) -> trader_requisite_entity::ActiveModel {
+        trader_requisite_entity::ActiveModel {
+            id: Set(requisite.id),
+            trader_id: Set(requisite.trader_id),
+            kind: Set(requisite.kind.as_str().to_string()),
+            bank_name: Set(requisite.bank_name),
+            holder_name: Set(requisite.holder_name),
+            masked_number: Set(requisite.masked_number),
+            encrypted_number: Set(requisite.encrypted_number),
+            currency: Set(requisite.daily_limit.currency()),
+            daily_limit: Set(requisite.daily_limit.minor_units()),
+            monthly_limit: Set(requisite.monthly_limit.minor_units()),
+            is_active: Set(requisite.is_active),
+            created_at: Set(requisite.created_at.into()),
+            updated_at: Set(requisite.updated_at.into()),
+        }
+    }
+
+    fn entity_to_domain(
+        trader: trader_entity::Model,
+        requisites: Vec<trader_requisite_entity::Model>,
+    ) -> Result<Trader, AppError> {
+        Ok(Trader {
+            id: trader.id,
+            name: trader.name,
+            contact: trader.contact,
+            availability: trader.availability.parse()?,
+            availability_changed_at: trader.availability_changed_at.with_timezone(&Utc),
+            requisites: requisites
+                .into_iter()
+                .map(Self::requisite_to_domain)
+                .collect::<Result<_, _>>()?,
+            created_at: trader.created_at.with_timezone(&Utc),
+            updated_at: trader.updated_at.with_timezone(&Utc),
+        })
+    }
+
+    fn domain_to_active_model(trader: Trader) -> trader_entity::ActiveModel {
+        trader_entity::ActiveModel {
+            id: Set(trader.id),
+            name: Set(trader.name),
+            contact: Set(trader.contact),
+            availability: Set(trader.availability.as_str().to_string()),
+            availability_changed_at: Set(trader.availability_changed_at.into()),
+            created_at: Set(trader.created_at.into()),
+            updated_at: Set(trader.updated_at.into()),
+        }
+    }
+
+    /// Attaches the requisites of every trader with a single extra query
+    async fn with_requisites(
+        &self,
+        traders: Vec<trader_entity::Model>,
+    ) -> Result<Vec<Trader>, AppError> {
+        let requisites = traders
+            .load_many(
+                TraderRequisiteEntity::find()
+                    .order_by_asc(trader_requisite_entity::Column::CreatedAt),
+                &self.db,
+            )
+            .await?;
+
+        traders
+            .into_iter()
+            .zip(requisites)
+            .map(|(trader, requisites)| Self::entity_to_domain(trader, requisites))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl TraderRepository for PostgresTraderRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Trader>, AppError> {
+        let trader = TraderEntity::find_by_id(id).one(&self.db).await?;
+
+        match trader {
+            Some(trader) => Ok(self.with_requisites(vec![trader]).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    async fn list(
+        &self,
+        filter: TraderFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Trader>, AppError> {
+        let mut query = TraderEntity::find();
+        if let Some(availability) = filter.availability {
+            query = query.filter(trader_entity::Column::Availability.eq(availability.as_str()));
+        }
+
+        let traders = query
+            .order_by_asc(trader_entity::Column::Name)
+            .limit(limit as u64)
+            .offset(offset as u64)
+            .all(&self.db)
+            .await?;
+
+        self.with_requisites(traders).await
+    }
+
+    async fn exists_by_name(&self, name: &str) -> Result<bool, AppError> {
+        let count = TraderEntity::find()
+            .filter(trader_entity::Column::Name.eq(name))
+            .count(&self.db)
+            .await?;
+
+        Ok(count > 0)
+    }
+
+    async fn create(&self, trader: Trader, audit: AuditEntry) -> Result<Trader, AppError> {
+        let txn = self.db.begin().await?;
+        let model = Self::domain_to_active_model(trader).insert(&txn).await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        Self::entity_to_domain(model, Vec::new())
+    }
+
+    async fn update(&self, trader: Trader, audit: AuditEntry) -> Result<Trader, AppError> {
+        let requisites = trader.requisites.clone();
+        let txn = self.db.begin().await?;
+        let model = Self::domain_to_active_model(trader).update(&txn).await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        Ok(Trader {
+            requisites,
+            ..Self::entity_to_domain(model, Vec::new())?
+        })
+    }
+
+    async fn delete(&self, id: Uuid, audit: AuditEntry) -> Result<(), AppError> {
+        let txn = self.db.begin().await?;
+        TraderEntity::delete_by_id(id)
+            .exec(&txn)
+            .await
+            .map_err(|err| match err.sql_err() {
+                // payment_intent.trader_id and requisite_id are ON DELETE RESTRICT
+                Some(SqlErr::ForeignKeyConstraintViolation(_)) => AppError::ValidationError(
+                    format!("Trader {} has payments and cannot be deleted", id),
+                ),
+                _ => AppError::from(err),
+            })?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    async fn find_requisite(&self, id: Uuid) -> Result<Option<Requisite>, AppError> {
+        TraderRequisiteEntity::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .map(Self::requisite_to_domain)
+            .transpose()
+    }
+
+    async fn create_requisite(
+        &self,
+        requisite: Requisite,
+        audit: AuditEntry,
+    ) -> Result<Requisite, AppError> {
+        let txn = self.db.begin().await?;
+        let model = Self::requisite_to_active_model(requisite)
+            .insert(&txn)
+            .await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        Self::requisite_to_domain(model)
+    }
+
+    async fn update_requisite(
+        &self,
+        requisite: Requisite,
+        audit: AuditEntry,
+    ) -> Result<Requisite, AppError> {
+        let txn = self.db.begin().await?;
+        let model = Self::requisite_to_active_model(requisite)
+            .update(&txn)
+            .await?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        Self::requisite_to_domain(model)
+    }
+
+    async fn delete_requisite(&self, id: Uuid, audit: AuditEntry) -> Result<(), AppError> {
+        let txn = self.db.begin().await?;
+        TraderRequisiteEntity::delete_by_id(id)
+            .exec(&txn)
+            .await
+            .map_err(|err| match err.sql_err() {
+                // payment_intent.requisite_id is ON DELETE RESTRICT
+                Some(SqlErr::ForeignKeyConstraintViolation(_)) => AppError::ValidationError(
+                    format!("Requisite {} has payments; deactivate it instead", id),
+                ),
+                _ => AppError::from(err),
+            })?;
+        PostgresAuditLogRepository::append(&txn, audit).await?;
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
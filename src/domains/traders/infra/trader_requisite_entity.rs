@@ -0,0 +1,40 @@
+use crate::common::money::Currency;
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "trader_requisite")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub trader_id: Uuid,
+    pub kind: String,
+    pub bank_name: String,
+    pub holder_name: String,
+    pub masked_number: String,
+    pub encrypted_number: String,
+    pub currency: Currency,
+    pub daily_limit: i64,
+    pub monthly_limit: i64,
+    pub is_active: bool,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::trader_entity::Entity",
+        from = "Column::TraderId",
+        to = "super::trader_entity::Column::Id"
+    )]
+    Trader,
+}
+
+impl Related<super::trader_entity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Trader.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,31 @@
+mod api {
+    pub mod router;
+    pub mod webhook_handler;
+}
+
+pub mod app {
+    pub mod deliver_webhooks_use_case;
+    pub mod get_webhook_delivery_use_case;
+}
+
+pub mod domain {
+    pub mod delivery;
+    pub mod repository;
+    pub mod sender;
+}
+
+pub mod dto {
+    pub mod webhook_dto;
+}
+
+pub mod infra {
+    pub mod http_webhook_sender;
+    pub mod webhook_attempt_entity;
+    pub mod webhook_delivery_entity;
+    pub mod webhook_repository;
+}
+
+pub use api::router::{protected_webhook_routes, WebhookApiDoc};
+pub use domain::repository::WebhookRepository;
+pub use domain::sender::WebhookSender;
+pub use infra::webhook_repository::PostgresWebhookRepository;
@@ -0,0 +1,62 @@
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{
+    common::{jwt::SecurityAddon, middleware::require_permission},
+    domains::{
+        backoffice::role::permission::{WEBHOOKS_READ, WEBHOOKS_REDELIVER},
+        webhooks::{
+            domain::delivery::DeliveryStatus,
+            dto::webhook_dto::{WebhookAttemptResponse, WebhookDeliveryResponse},
+        },
+    },
+};
+
+use utoipa::OpenApi;
+
+use super::webhook_handler;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::webhook_handler::list_webhook_deliveries,
+        super::webhook_handler::get_webhook_delivery,
+        super::webhook_handler::list_webhook_attempts,
+        super::webhook_handler::redeliver_webhook,
+    ),
+    components(schemas(WebhookDeliveryResponse, WebhookAttemptResponse, DeliveryStatus)),
+    tags(
+        (name = "Webhooks", description = "Payment status notifications sent to merchant callback URLs")
+    ),
+    modifiers(&SecurityAddon)
+)]
+pub struct WebhookApiDoc;
+
+pub fn protected_webhook_routes() -> Router {
+    let redeliver_routes = Router::new()
+        .route(
+            "/webhook/delivery/{id}/redeliver",
+            post(webhook_handler::redeliver_webhook),
+        )
+        .route_layer(middleware::from_fn(require_permission(WEBHOOKS_REDELIVER)));
+
+    let read_routes = Router::new()
+        .route(
+            "/webhook/delivery",
+            get(webhook_handler::list_webhook_deliveries),
+        )
+        .route(
+            "/webhook/delivery/{id}",
+            get(webhook_handler::get_webhook_delivery),
+        )
+        .route(
+            "/webhook/delivery/{id}/attempt",
+            get(webhook_handler::list_webhook_attempts),
+        )
+        .route_layer(middleware::from_fn(require_permission(WEBHOOKS_READ)));
+
+    Router::new().merge(redeliver_routes).merge(read_routes)
+}
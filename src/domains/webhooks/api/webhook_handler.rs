@@ -0,0 +1,144 @@
+use crate::common::{app_state::AppState, dto::ApiResponse, error::AppError, jwt::Claims};
+use crate::domains::audit::domain::audit_entry::AuditContext;
+use crate::domains::webhooks::dto::webhook_dto::{
+    ListWebhookDeliveriesQuery, WebhookAttemptResponse, WebhookDeliveryResponse,
+};
+use axum::{
+    extract::{Extension, Path, Query},
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhook/delivery",
+    params(ListWebhookDeliveriesQuery),
+    responses(
+        (status = 200, description = "Page of webhook deliveries, newest first", body = inline(ApiResponse<Vec<WebhookDeliveryResponse>>)),
+        (status = 400, description = "Limit exceeds 100 or a filter is malformed"),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Webhooks",
+    summary = "List webhook deliveries",
+    description = "Lists merchant notifications filtered by site, payment and status. `status=failed` lists the ones that ran out of attempts. Requires `webhooks:read`."
+)]
+pub async fn list_webhook_deliveries(
+    Extension(state): Extension<Arc<AppState>>,
+    Query(params): Query<ListWebhookDeliveriesQuery>,
+) -> Result<Json<ApiResponse<Vec<WebhookDeliveryResponse>>>, AppError> {
+    let (limit, offset) = (params.limit, params.offset);
+    let deliveries = state
+        .webhook_get_use_case
+        .list(params.into(), limit, offset)
+        .await?;
+
+    let response: Vec<WebhookDeliveryResponse> = deliveries
+        .into_iter()
+        .map(WebhookDeliveryResponse::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhook/delivery/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Webhook delivery ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook delivery found", body = inline(ApiResponse<WebhookDeliveryResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Webhook delivery not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Webhooks",
+    summary = "Get webhook delivery",
+    description = "Fetches a webhook delivery with its payload. Requires `webhooks:read`."
+)]
+pub async fn get_webhook_delivery(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(delivery_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<WebhookDeliveryResponse>>, AppError> {
+    let delivery = state.webhook_get_use_case.execute(delivery_id).await?;
+
+    Ok(Json(ApiResponse::success(WebhookDeliveryResponse::from(
+        delivery,
+    ))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhook/delivery/{id}/attempt",
+    params(
+        ("id" = Uuid, Path, description = "Webhook delivery ID")
+    ),
+    responses(
+        (status = 200, description = "Attempts, oldest first", body = inline(ApiResponse<Vec<WebhookAttemptResponse>>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Webhook delivery not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Webhooks",
+    summary = "List webhook attempts",
+    description = "Lists every request sent for the delivery with the response status, the start of the response body or the transport error. Requires `webhooks:read`."
+)]
+pub async fn list_webhook_attempts(
+    Extension(state): Extension<Arc<AppState>>,
+    Path(delivery_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<WebhookAttemptResponse>>>, AppError> {
+    let attempts = state.webhook_get_use_case.attempts(delivery_id).await?;
+
+    let response: Vec<WebhookAttemptResponse> = attempts
+        .into_iter()
+        .map(WebhookAttemptResponse::from)
+        .collect();
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhook/delivery/{id}/redeliver",
+    params(
+        ("id" = Uuid, Path, description = "Webhook delivery ID")
+    ),
+    responses(
+        (status = 200, description = "Attempt sent; the delivery shows its outcome", body = inline(ApiResponse<WebhookDeliveryResponse>)),
+        (status = 401, description = "Missing or invalid JWT token"),
+        (status = 403, description = "Insufficient permissions"),
+        (status = 404, description = "Webhook delivery not found")
+    ),
+    security(
+        ("jwt_token" = [])
+    ),
+    tag = "Webhooks",
+    summary = "Redeliver webhook",
+    description = "Sends the same payload to the site's current callback URL right away, whatever the delivery status. Success marks the delivery as delivered; a failure is logged without restarting automatic retries of a failed delivery. Requires `webhooks:redeliver`."
+)]
+pub async fn redeliver_webhook(
+    Extension(state): Extension<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Extension(audit): Extension<AuditContext>,
+    Path(delivery_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<WebhookDeliveryResponse>>, AppError> {
+    let delivery = state
+        .webhook_deliver_use_case
+        .redeliver(delivery_id, claims.user_id, &audit)
+        .await?;
+
+    Ok(Json(ApiResponse::success(WebhookDeliveryResponse::from(
+        delivery,
+    ))))
+}
@@ -0,0 +1,433 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    common::{
+        error::AppError,
+        request_signature::{self, API_KEY_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER},
+        secret_cipher::SecretCipher,
+    },
+    domains::{
+        audit::domain::audit_entry::{
+            AuditAction, AuditContext, AuditEntry, WEBHOOK_DELIVERY_ENTITY,
+        },
+        backoffice::domain::repository::{SiteCredentialsRepository, SiteRepository},
+        webhooks::domain::{
+            delivery::{
+                DeliveryStatus, RetryPolicy, WebhookAttempt, WebhookDelivery, WEBHOOK_EVENT_HEADER,
+                WEBHOOK_ID_HEADER,
+            },
+            repository::WebhookRepository,
+            sender::{WebhookRequest, WebhookSender},
+        },
+    },
+};
+
+/// Longest response body kept on an attempt
+const MAX_LOGGED_RESPONSE_CHARS: usize = 1000;
+
+/// Sends queued webhooks to merchant callback URLs and retries failed ones
+pub struct DeliverWebhooksUseCase {
+    webhook_repository: Arc<dyn WebhookRepository>,
+    site_repository: Arc<dyn SiteRepository>,
+    site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+    secret_cipher: Arc<SecretCipher>,
+    sender: Arc<dyn WebhookSender>,
+    retry_policy: RetryPolicy,
+    batch_size: i64,
+    /// How long a claimed delivery is hidden from other dispatchers while it is sent
+    lease: Duration,
+}
+
+impl DeliverWebhooksUseCase {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        webhook_repository: Arc<dyn WebhookRepository>,
+        site_repository: Arc<dyn SiteRepository>,
+        site_credentials_repository: Arc<dyn SiteCredentialsRepository>,
+        secret_cipher: Arc<SecretCipher>,
+        sender: Arc<dyn WebhookSender>,
+        retry_policy: RetryPolicy,
+        batch_size: i64,
+        lease: Duration,
+    ) -> Self {
+        Self {
+            webhook_repository,
+            site_repository,
+            site_credentials_repository,
+            secret_cipher,
+            sender,
+            retry_policy,
+            batch_size,
+            lease,
+        }
+    }
+
+    /// Sends every pending webhook whose attempt is due; returns how many got through
+    pub async fn dispatch_due(&self) -> Result<u64, AppError> {
+        let now = Utc::now();
+        let due = self
+            .webhook_repository
+            .claim_due(now, self.batch_size, now + self.lease)
+            .await?;
+
+        let mut delivered = 0;
+        for delivery in due {
+            let delivery_id = delivery.id;
+            match self.attempt(delivery, None, None).await {
+                Ok(delivery) if delivery.status == DeliveryStatus::Delivered => delivered += 1,
+                Ok(_) => {}
+                Err(err) => tracing::error!("Webhook {} attempt failed: {}", delivery_id, err),
+            }
+        }
+
+        if delivered > 0 {
+            tracing::info!("Delivered {} webhooks", delivered);
+        }
+
+        Ok(delivered)
+    }
+
+    /// Sends the webhook again right away, whatever its status.
+    /// A failed redelivery does not restart automatic retries of a finished delivery.
+    pub async fn redeliver(
+        &self,
+        delivery_id: Uuid,
+        triggered_by: Uuid,
+        context: &AuditContext,
+    ) -> Result<WebhookDelivery, AppError> {
+        tracing::debug!("Redelivering webhook {}", delivery_id);
+
+        let delivery = self
+            .webhook_repository
+            .find_by_id(delivery_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Webhook delivery {} not found",
+                delivery_id
+            )))?;
+
+        let delivery = self
+            .attempt(delivery, Some(triggered_by), Some(context))
+            .await?;
+
+        tracing::info!(
+            "Webhook {} redelivered by {}: {}",
+            delivery.id,
+            triggered_by,
+            delivery.status
+        );
+
+        Ok(delivery)
+    }
+
+    /// Sends one attempt and logs it; problems on our side, such as a site without an
+    /// active key, are logged as failed attempts too. Manual attempts are audited.
+    async fn attempt(
+        &self,
+        mut delivery: WebhookDelivery,
+        triggered_by: Option<Uuid>,
+        context: Option<&AuditContext>,
+    ) -> Result<WebhookDelivery, AppError> {
+        let started_at = Utc::now();
+        let before = delivery.audit_snapshot();
+
+        let mut attempt = WebhookAttempt {
+            id: Uuid::new_v4(),
+            delivery_id: delivery.id,
+            attempt_number: delivery.attempts + 1,
+            url: String::new(),
+            response_status: None,
+            response_body: None,
+            error: None,
+            duration_ms: 0,
+            triggered_by,
+            attempted_at: started_at,
+        };
+
+        match self.build_request(&delivery, started_at).await {
+            Ok(request) => {
+                attempt.url = request.url.clone();
+                match self.sender.send(request).await {
+                    Ok(response) => {
+                        attempt.response_status = Some(i32::from(response.status));
+                        attempt.response_body = Some(
+                            response
+                                .body
+                                .chars()
+                                .take(MAX_LOGGED_RESPONSE_CHARS)
+                                .collect(),
+                        );
+                    }
+                    Err(err) => attempt.error = Some(err),
+                }
+            }
+            Err((url, err)) => {
+                attempt.url = url;
+                attempt.error = Some(err);
+            }
+        }
+
+        let finished_at = Utc::now();
+        attempt.duration_ms = (finished_at - started_at).num_milliseconds();
+        attempt.attempted_at = finished_at;
+
+        if !attempt.is_success() {
+            tracing::warn!(
+                "Webhook {} attempt {} to {} failed: status {:?}, error {:?}",
+                delivery.id,
+                attempt.attempt_number,
+                attempt.url,
+                attempt.response_status,
+                attempt.error
+            );
+        }
+
+        delivery.record_attempt(&attempt, &self.retry_policy);
+        let audit = context.map(|context| {
+            AuditEntry::new(
+                context,
+                AuditAction::Update,
+                WEBHOOK_DELIVERY_ENTITY,
+                delivery.id,
+                Some(&before),
+                Some(&delivery.audit_snapshot()),
+            )
+        });
+        self.webhook_repository
+            .record_attempt(delivery, attempt, audit)
+            .await
+    }
+
+    /// POST of the payload to the site's current callback URL, signed like merchant API
+    /// requests with the site's newest usable key. Errors carry the URL when it is known.
+    async fn build_request(
+        &self,
+        delivery: &WebhookDelivery,
+        at: DateTime<Utc>,
+    ) -> Result<WebhookRequest, (String, String)> {
+        let internal = |err: AppError| (String::new(), err.to_string());
+
+        let site = self
+            .site_repository
+            .find_by_id(delivery.site_id)
+            .await
+            .map_err(internal)?
+            .ok_or_else(|| (String::new(), "Site not found".to_string()))?;
+        let url = site.callback_url.clone();
+
+        if !site.is_active() {
+            return Err((url, "Site is not active".to_string()));
+        }
+
+        let credentials = self
+            .site_credentials_repository
+            .list_by_site(site.id)
+            .await
+            .map_err(|err| (url.clone(), err.to_string()))?
+            .into_iter()
+            .find(|credentials| credentials.is_usable_at(at))
+            .ok_or_else(|| (url.clone(), "Site has no active API key".to_string()))?;
+        let secret_key = self
+            .secret_cipher
+            .decrypt(&credentials.encrypted_secret_key)
+            .map_err(|err| (url.clone(), err.to_string()))?;
+
+        let parsed = Url::parse(&url).map_err(|err| (url.clone(), err.to_string()))?;
+        let path = match parsed.query() {
+            Some(query) => format!("{}?{}", parsed.path(), query),
+            None => parsed.path().to_string(),
+        };
+
+        let timestamp = at.timestamp();
+        let payload = request_signature::signing_payload(
+            timestamp,
+            "POST",
+            &path,
+            delivery.payload.as_bytes(),
+        );
+
+        Ok(WebhookRequest {
+            url,
+            headers: vec![
+                (API_KEY_HEADER, credentials.public_key),
+                (TIMESTAMP_HEADER, timestamp.to_string()),
+                (
+                    SIGNATURE_HEADER,
+                    request_signature::sign(&secret_key, &payload),
+                ),
+                (WEBHOOK_ID_HEADER, delivery.id.to_string()),
+                (WEBHOOK_EVENT_HEADER, delivery.event_type.clone()),
+            ],
+            body: delivery.payload.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::{Currency, Money};
+    use crate::domains::{
+        backoffice::domain::{
+            merchant::{Site, SiteCredentials, SiteStatus},
+            repository::{MockSiteCredentialsRepository, MockSiteRepository},
+        },
+        payments::domain::payment_intent::PaymentIntent,
+        webhooks::domain::{
+            repository::MockWebhookRepository,
+            sender::{MockWebhookSender, WebhookResponse},
+        },
+    };
+
+    const SECRET_KEY: &str = "sk_test_secret";
+
+    fn site() -> Site {
+        Site::new(
+            Uuid::new_v4(),
+            "Shop".to_string(),
+            "https://shop.example.com".to_string(),
+            "https://shop.example.com/hooks?v=1".to_string(),
+            "https://shop.example.com/ok".to_string(),
+            "https://shop.example.com/fail".to_string(),
+        )
+    }
+
+    fn delivery(site: &Site) -> WebhookDelivery {
+        let payment = PaymentIntent::new(
+            site.merchant_id,
+            site.id,
+            "order-1".to_string(),
+            Money::new(10_000, Currency::Usd),
+            None,
+            None,
+            Duration::minutes(30),
+        );
+
+        WebhookDelivery::for_payment(&payment, Utc::now()).unwrap()
+    }
+
+    fn use_case(
+        site: Site,
+        delivery: WebhookDelivery,
+        sender: MockWebhookSender,
+    ) -> DeliverWebhooksUseCase {
+        let cipher = Arc::new(SecretCipher::new("test_encryption_key"));
+        let credentials = SiteCredentials::new(
+            site.id,
+            "pk_test".to_string(),
+            cipher.encrypt(SECRET_KEY).unwrap(),
+        );
+
+        let mut webhook_repository = MockWebhookRepository::new();
+        webhook_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(delivery.clone())));
+        webhook_repository
+            .expect_record_attempt()
+            .withf(|delivery, _, audit| {
+                audit.as_ref().is_some_and(|audit| {
+                    audit.entity_type == WEBHOOK_DELIVERY_ENTITY && audit.entity_id == delivery.id
+                })
+            })
+            .times(1)
+            .returning(|delivery, _, _| Ok(delivery));
+
+        let mut site_repository = MockSiteRepository::new();
+        site_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(site.clone())));
+
+        let mut site_credentials_repository = MockSiteCredentialsRepository::new();
+        site_credentials_repository
+            .expect_list_by_site()
+            .returning(move |_| Ok(vec![credentials.clone()]));
+
+        DeliverWebhooksUseCase::new(
+            Arc::new(webhook_repository),
+            Arc::new(site_repository),
+            Arc::new(site_credentials_repository),
+            cipher,
+            Arc::new(sender),
+            RetryPolicy {
+                max_attempts: 3,
+                initial_backoff: Duration::seconds(30),
+                max_backoff: Duration::hours(1),
+            },
+            10,
+            Duration::minutes(1),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_request_is_signed_with_site_secret() {
+        let site = site();
+        let delivery = delivery(&site);
+        let delivery_id = delivery.id;
+
+        let mut sender = MockWebhookSender::new();
+        sender.expect_send().times(1).returning(|request| {
+            let header = |name: &str| {
+                request
+                    .headers
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.clone())
+                    .unwrap()
+            };
+            let payload = request_signature::signing_payload(
+                header(TIMESTAMP_HEADER).parse().unwrap(),
+                "POST",
+                "/hooks?v=1",
+                request.body.as_bytes(),
+            );
+
+            assert_eq!(request.url, "https://shop.example.com/hooks?v=1");
+            assert_eq!(header(API_KEY_HEADER), "pk_test");
+            assert_eq!(header(WEBHOOK_EVENT_HEADER), "payment.created");
+            assert!(request_signature::verify(
+                SECRET_KEY,
+                &payload,
+                &header(SIGNATURE_HEADER)
+            ));
+
+            Ok(WebhookResponse {
+                status: 200,
+                body: "ok".to_string(),
+            })
+        });
+
+        let delivery = use_case(site, delivery, sender)
+            .redeliver(delivery_id, Uuid::new_v4(), &AuditContext::default())
+            .await
+            .unwrap();
+
+        assert_eq!(delivery.status, DeliveryStatus::Delivered);
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.last_response_status, Some(200));
+    }
+
+    #[tokio::test]
+    async fn test_inactive_site_is_logged_as_failed_attempt() {
+        let mut site = site();
+        site.change_status(SiteStatus::Inactive);
+        let delivery = delivery(&site);
+        let delivery_id = delivery.id;
+
+        let mut sender = MockWebhookSender::new();
+        sender.expect_send().never();
+
+        let delivery = use_case(site, delivery, sender)
+            .redeliver(delivery_id, Uuid::new_v4(), &AuditContext::default())
+            .await
+            .unwrap();
+
+        assert_eq!(delivery.status, DeliveryStatus::Pending);
+        assert_eq!(delivery.attempts, 1);
+        assert!(delivery.next_attempt_at.is_some());
+        assert_eq!(delivery.last_error.as_deref(), Some("Site is not active"));
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+    common::error::AppError,
+    domains::webhooks::domain::{
+        delivery::{WebhookAttempt, WebhookDelivery, WebhookDeliveryFilter},
+        repository::WebhookRepository,
+    },
+};
+
+pub struct GetWebhookDeliveryUseCase {
+    webhook_repository: Arc<dyn WebhookRepository>,
+}
+
+impl GetWebhookDeliveryUseCase {
+    pub fn new(webhook_repository: Arc<dyn WebhookRepository>) -> Self {
+        Self { webhook_repository }
+    }
+
+    pub async fn execute(&self, delivery_id: Uuid) -> Result<WebhookDelivery, AppError> {
+        tracing::debug!("Fetching webhook delivery {}", delivery_id);
+
+        self.webhook_repository
+            .find_by_id(delivery_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Webhook delivery {} not found",
+                delivery_id
+            )))
+    }
+
+    pub async fn list(
+        &self,
+        filter: WebhookDeliveryFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        tracing::debug!("Listing webhook deliveries with {:?}", filter);
+
+        if limit > 100 {
+            return Err(AppError::ValidationError(
+                "Limit cannot exceed 100".to_string(),
+            ));
+        }
+
+        self.webhook_repository.list(filter, limit, offset).await
+    }
+
+    /// Attempt log of the delivery, oldest first
+    pub async fn attempts(&self, delivery_id: Uuid) -> Result<Vec<WebhookAttempt>, AppError> {
+        let delivery = self.execute(delivery_id).await?;
+
+        self.webhook_repository.list_attempts(delivery.id).await
+    }
+}
@@ -0,0 +1,360 @@
+use crate::common::{error::AppError, money::Money, time_formater};
+use crate::domains::payments::domain::payment_intent::{PaymentIntent, PaymentStatus};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{fmt, str::FromStr};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Delivery ID; the same on every attempt, so merchants can drop duplicates
+pub const WEBHOOK_ID_HEADER: &str = "X-Webhook-Id";
+/// Event type of the payload, e.g. `payment.paid`
+pub const WEBHOOK_EVENT_HEADER: &str = "X-Webhook-Event";
+
+/// Notification of a payment status change for the site's `callback_url`.
+/// The payload is fixed when the event happens; every attempt sends the same body.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub site_id: Uuid,
+    pub payment_id: Uuid,
+    pub event_type: String,
+    /// JSON body sent to the merchant
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    /// When the dispatcher sends the next automatic attempt; unset once finished
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub last_response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body of a payment event
+#[derive(Serialize)]
+struct PaymentEvent<'a> {
+    id: Uuid,
+    #[serde(rename = "type")]
+    event_type: &'a str,
+    #[serde(with = "crate::common::time_formater")]
+    created_at: DateTime<Utc>,
+    data: PaymentEventData<'a>,
+}
+
+#[derive(Serialize)]
+struct PaymentEventData<'a> {
+    id: Uuid,
+    site_id: Uuid,
+    external_order_id: &'a str,
+    #[serde(flatten)]
+    amount: Money,
+    status: PaymentStatus,
+    rejection_reason: Option<&'a str>,
+    #[serde(with = "crate::common::time_formater::option")]
+    marked_paid_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::common::time_formater::option")]
+    resolved_at: Option<DateTime<Utc>>,
+    #[serde(with = "crate::common::time_formater")]
+    expires_at: DateTime<Utc>,
+    #[serde(with = "crate::common::time_formater")]
+    updated_at: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    /// Fields recorded in audit entries of manual redeliveries
+    pub fn audit_snapshot(&self) -> Value {
+        json!({
+            "status": self.status,
+            "attempts": self.attempts,
+            "next_attempt_at": self.next_attempt_at.as_ref().map(time_formater::format),
+            "last_response_status": self.last_response_status,
+            "last_error": self.last_error,
+            "delivered_at": self.delivered_at.as_ref().map(time_formater::format),
+        })
+    }
+
+    /// `payment.{status}` event with a snapshot of the payment, due right away
+    pub fn for_payment(payment: &PaymentIntent, at: DateTime<Utc>) -> Result<Self, AppError> {
+        let id = Uuid::new_v4();
+        let event_type = format!("payment.{}", payment.status);
+
+        let payload = serde_json::to_string(&PaymentEvent {
+            id,
+            event_type: &event_type,
+            created_at: at,
+            data: PaymentEventData {
+                id: payment.id,
+                site_id: payment.site_id,
+                external_order_id: &payment.external_order_id,
+                amount: payment.amount,
+                status: payment.status,
+                rejection_reason: payment.rejection_reason.as_deref(),
+                marked_paid_at: payment.marked_paid_at,
+                resolved_at: payment.resolved_at,
+                expires_at: payment.expires_at,
+                updated_at: payment.updated_at,
+            },
+        })
+        .map_err(|err| AppError::InternalError(format!("Cannot encode webhook: {}", err)))?;
+
+        Ok(Self {
+            id,
+            site_id: payment.site_id,
+            payment_id: payment.id,
+            event_type,
+            payload,
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: Some(at),
+            last_attempt_at: None,
+            last_response_status: None,
+            last_error: None,
+            delivered_at: None,
+            created_at: at,
+            updated_at: at,
+        })
+    }
+
+    /// Counts `attempt` and decides what happens next:
+    /// - a 2xx response delivers the webhook
+    /// - a pending delivery is retried with backoff until `policy.max_attempts` is reached
+    /// - manual attempts on finished deliveries only log the outcome
+    pub fn record_attempt(&mut self, attempt: &WebhookAttempt, policy: &RetryPolicy) {
+        let at = attempt.attempted_at;
+
+        self.attempts += 1;
+        self.last_attempt_at = Some(at);
+        self.last_response_status = attempt.response_status;
+        self.last_error = attempt.error.clone();
+        self.updated_at = at;
+
+        if attempt.is_success() {
+            self.status = DeliveryStatus::Delivered;
+            self.delivered_at = Some(at);
+            self.next_attempt_at = None;
+            return;
+        }
+
+        if self.status != DeliveryStatus::Pending {
+            return;
+        }
+
+        if self.attempts >= policy.max_attempts {
+            self.status = DeliveryStatus::Failed;
+            self.next_attempt_at = None;
+        } else {
+            self.next_attempt_at = Some(at + policy.backoff(self.attempts));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    /// Waiting for its next attempt
+    Pending,
+    Delivered,
+    /// Gave up after the maximum number of attempts
+    Failed,
+}
+
+impl DeliveryStatus {
+    /// Value stored in the `status` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Delivered => "delivered",
+            DeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+impl fmt::Display for DeliveryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DeliveryStatus {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "pending" => Ok(DeliveryStatus::Pending),
+            "delivered" => Ok(DeliveryStatus::Delivered),
+            "failed" => Ok(DeliveryStatus::Failed),
+            other => Err(AppError::InternalError(format!(
+                "Unknown webhook delivery status '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// One request to the callback URL, as logged
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookAttempt {
+    pub id: Uuid,
+    pub delivery_id: Uuid,
+    /// 1 for the first attempt
+    pub attempt_number: i32,
+    pub url: String,
+    /// Unset when no response came back
+    pub response_status: Option<i32>,
+    /// Start of the response body
+    pub response_body: Option<String>,
+    /// Why the request could not be sent or got no response
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    /// Backoffice user who asked for a redelivery; unset for automatic attempts
+    pub triggered_by: Option<Uuid>,
+    pub attempted_at: DateTime<Utc>,
+}
+
+impl WebhookAttempt {
+    pub fn is_success(&self) -> bool {
+        self.response_status
+            .is_some_and(|status| (200..300).contains(&status))
+    }
+}
+
+/// Exponential backoff between automatic attempts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: i32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Wait after the `attempts`-th failure: the initial backoff doubled each time, capped
+    pub fn backoff(&self, attempts: i32) -> Duration {
+        let doublings = attempts.saturating_sub(1).clamp(0, 30) as u32;
+        let backoff = self
+            .initial_backoff
+            .checked_mul(2_i32.pow(doublings))
+            .unwrap_or(self.max_backoff);
+
+        backoff.min(self.max_backoff)
+    }
+}
+
+/// Backoffice search criteria; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WebhookDeliveryFilter {
+    pub site_id: Option<Uuid>,
+    pub payment_id: Option<Uuid>,
+    pub status: Option<DeliveryStatus>,
+}
+
+impl WebhookDeliveryFilter {
+    pub fn matches(&self, delivery: &WebhookDelivery) -> bool {
+        self.site_id
+            .is_none_or(|site_id| delivery.site_id == site_id)
+            && self
+                .payment_id
+                .is_none_or(|payment_id| delivery.payment_id == payment_id)
+            && self.status.is_none_or(|status| delivery.status == status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::money::Currency;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::seconds(30),
+            max_backoff: Duration::minutes(1),
+        }
+    }
+
+    fn delivery() -> WebhookDelivery {
+        let payment = PaymentIntent::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "order-1".to_string(),
+            Money::new(10_050, Currency::Usd),
+            None,
+            None,
+            Duration::minutes(30),
+        );
+
+        WebhookDelivery::for_payment(&payment, Utc::now()).unwrap()
+    }
+
+    fn attempt(delivery: &WebhookDelivery, response_status: Option<i32>) -> WebhookAttempt {
+        WebhookAttempt {
+            id: Uuid::new_v4(),
+            delivery_id: delivery.id,
+            attempt_number: delivery.attempts + 1,
+            url: "https://shop.example.com/callback".to_string(),
+            response_status,
+            response_body: None,
+            error: response_status
+                .is_none()
+                .then(|| "connection refused".to_string()),
+            duration_ms: 5,
+            triggered_by: None,
+            attempted_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_payload_describes_payment() {
+        let delivery = delivery();
+        let payload: serde_json::Value = serde_json::from_str(&delivery.payload).unwrap();
+
+        assert_eq!(delivery.event_type, "payment.created");
+        assert_eq!(payload["id"], delivery.id.to_string());
+        assert_eq!(payload["type"], "payment.created");
+        assert_eq!(payload["data"]["id"], delivery.payment_id.to_string());
+        assert_eq!(payload["data"]["amount"], "100.50");
+        assert_eq!(payload["data"]["currency"], "USD");
+        assert_eq!(payload["data"]["status"], "created");
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let policy = policy();
+
+        assert_eq!(policy.backoff(1), Duration::seconds(30));
+        assert_eq!(policy.backoff(2), Duration::seconds(60));
+        assert_eq!(policy.backoff(3), Duration::seconds(60));
+        assert_eq!(policy.backoff(i32::MAX), Duration::seconds(60));
+    }
+
+    #[test]
+    fn test_failures_are_retried_until_max_attempts() {
+        let policy = policy();
+        let mut delivery = delivery();
+
+        let first = attempt(&delivery, Some(500));
+        delivery.record_attempt(&first, &policy);
+        assert_eq!(delivery.status, DeliveryStatus::Pending);
+        assert_eq!(
+            delivery.next_attempt_at,
+            Some(first.attempted_at + Duration::seconds(30))
+        );
+
+        delivery.record_attempt(&attempt(&delivery, None), &policy);
+        assert_eq!(delivery.status, DeliveryStatus::Pending);
+        assert_eq!(delivery.last_error.as_deref(), Some("connection refused"));
+
+        delivery.record_attempt(&attempt(&delivery, Some(404)), &policy);
+        assert_eq!(delivery.status, DeliveryStatus::Failed);
+        assert_eq!(delivery.attempts, 3);
+        assert!(delivery.next_attempt_at.is_none());
+
+        // A manual redelivery can still get it through
+        delivery.record_attempt(&attempt(&delivery, Some(204)), &policy);
+        assert_eq!(delivery.status, DeliveryStatus::Delivered);
+        assert!(delivery.delivered_at.is_some());
+    }
+}
@@ -0,0 +1,59 @@
+use super::delivery::{WebhookAttempt, WebhookDelivery, WebhookDeliveryFilter};
+use crate::common::error::AppError;
+use crate::domains::audit::domain::audit_entry::AuditEntry;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<WebhookDelivery>, AppError>;
+    async fn create(&self, delivery: WebhookDelivery) -> Result<WebhookDelivery, AppError>;
+
+    /// Newest first
+    async fn list(
+        &self,
+        filter: WebhookDeliveryFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<WebhookDelivery>, AppError>;
+
+    /// Takes up to `limit` pending deliveries due at `at`, oldest due first, and moves
+    /// their next attempt to `lease_until` so no other dispatcher picks them up meanwhile
+    async fn claim_due(
+        &self,
+        at: DateTime<Utc>,
+        limit: i64,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Vec<WebhookDelivery>, AppError>;
+
+    /// Stores the attempt and the delivery it updated atomically, together with `audit`
+    /// for attempts triggered from the backoffice
+    async fn record_attempt(
+        &self,
+        delivery: WebhookDelivery,
+        attempt: WebhookAttempt,
+        audit: Option<AuditEntry>,
+    ) -> Result<WebhookDelivery, AppError>;
+
+    /// Oldest first
+    async fn list_attempts(&self, delivery_id: Uuid) -> Result<Vec<WebhookAttempt>, AppError>;
+}
+
+#[cfg(test)]
+use mockall::mock;
+
+#[cfg(test)]
+mock! {
+    pub WebhookRepository {}
+
+    #[async_trait]
+    impl WebhookRepository for WebhookRepository {
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<WebhookDelivery>, AppError>;
+        async fn create(&self, delivery: WebhookDelivery) -> Result<WebhookDelivery, AppError>;
+        async fn list(&self, filter: WebhookDeliveryFilter, limit: i64, offset: i64) -> Result<Vec<WebhookDelivery>, AppError>;
+        async fn claim_due(&self, at: DateTime<Utc>, limit: i64, lease_until: DateTime<Utc>) -> Result<Vec<WebhookDelivery>, AppError>;
+        async fn record_attempt(&self, delivery: WebhookDelivery, attempt: WebhookAttempt, audit: Option<AuditEntry>) -> Result<WebhookDelivery, AppError>;
+        async fn list_attempts(&self, delivery_id: Uuid) -> Result<Vec<WebhookAttempt>, AppError>;
+    }
+}
@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+
+/// Signed POST to a merchant's callback URL
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookRequest {
+    pub url: String,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Transport for webhook requests
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    /// Any HTTP response is `Ok`, whatever its status. `Err` describes why no response
+    /// came back (connection refused, timeout, ...) and is logged on the attempt.
+    async fn send(&self, request: WebhookRequest) -> Result<WebhookResponse, String>;
+}
+
+#[cfg(test)]
+use mockall::mock;
+
+#[cfg(test)]
+mock! {
+    pub WebhookSender {}
+
+    #[async_trait]
+    impl WebhookSender for WebhookSender {
+        async fn send(&self, request: WebhookRequest) -> Result<WebhookResponse, String>;
+    }
+}
@@ -0,0 +1,164 @@
+use crate::domains::webhooks::domain::delivery::{
+    DeliveryStatus, WebhookAttempt, WebhookDelivery, WebhookDeliveryFilter,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDeliveryResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub site_id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub payment_id: String,
+
+    #[schema(example = "payment.paid")]
+    pub event_type: String,
+
+    /// JSON body sent to the callback URL
+    #[schema(
+        example = r#"{"id":"550e8400-e29b-41d4-a716-446655440000","type":"payment.paid","created_at":"2024-01-01T12:15:00Z","data":{}}"#
+    )]
+    pub payload: String,
+
+    pub status: DeliveryStatus,
+
+    #[schema(example = 2)]
+    pub attempts: i32,
+
+    /// When the next automatic attempt is sent
+    #[serde(with = "crate::common::time_formater::option")]
+    #[schema(example = "2024-01-01T12:16:00Z")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+
+    #[serde(with = "crate::common::time_formater::option")]
+    #[schema(example = "2024-01-01T12:15:30Z")]
+    pub last_attempt_at: Option<DateTime<Utc>>,
+
+    #[schema(example = 503)]
+    pub last_response_status: Option<i32>,
+
+    #[schema(example = "operation timed out")]
+    pub last_error: Option<String>,
+
+    #[serde(with = "crate::common::time_formater::option")]
+    #[schema(example = "2024-01-01T12:16:00Z")]
+    pub delivered_at: Option<DateTime<Utc>>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:15:00Z")]
+    pub created_at: DateTime<Utc>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:15:30Z")]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id.to_string(),
+            site_id: delivery.site_id.to_string(),
+            payment_id: delivery.payment_id.to_string(),
+            event_type: delivery.event_type,
+            payload: delivery.payload,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at,
+            last_attempt_at: delivery.last_attempt_at,
+            last_response_status: delivery.last_response_status,
+            last_error: delivery.last_error,
+            delivered_at: delivery.delivered_at,
+            created_at: delivery.created_at,
+            updated_at: delivery.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookAttemptResponse {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub delivery_id: String,
+
+    #[schema(example = 1)]
+    pub attempt_number: i32,
+
+    #[schema(example = "https://shop.example.com/callback")]
+    pub url: String,
+
+    /// Unset when no response came back
+    #[schema(example = 503)]
+    pub response_status: Option<i32>,
+
+    /// Start of the response body
+    #[schema(example = "Service Unavailable")]
+    pub response_body: Option<String>,
+
+    /// Why the request could not be sent or got no response
+    #[schema(example = "operation timed out")]
+    pub error: Option<String>,
+
+    #[schema(example = 184)]
+    pub duration_ms: i64,
+
+    /// Backoffice user who asked for the redelivery; unset for automatic attempts
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub triggered_by: Option<String>,
+
+    #[serde(with = "crate::common::time_formater")]
+    #[schema(example = "2024-01-01T12:15:30Z")]
+    pub attempted_at: DateTime<Utc>,
+}
+
+impl From<WebhookAttempt> for WebhookAttemptResponse {
+    fn from(attempt: WebhookAttempt) -> Self {
+        Self {
+            id: attempt.id.to_string(),
+            delivery_id: attempt.delivery_id.to_string(),
+            attempt_number: attempt.attempt_number,
+            url: attempt.url,
+            response_status: attempt.response_status,
+            response_body: attempt.response_body,
+            error: attempt.error,
+            duration_ms: attempt.duration_ms,
+            triggered_by: attempt.triggered_by.map(|id| id.to_string()),
+            attempted_at: attempt.attempted_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListWebhookDeliveriesQuery {
+    /// Page size, at most 100
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    pub site_id: Option<Uuid>,
+    pub payment_id: Option<Uuid>,
+    #[param(value_type = Option<String>, example = "failed")]
+    pub status: Option<DeliveryStatus>,
+}
+
+impl From<ListWebhookDeliveriesQuery> for WebhookDeliveryFilter {
+    fn from(query: ListWebhookDeliveriesQuery) -> Self {
+        Self {
+            site_id: query.site_id,
+            payment_id: query.payment_id,
+            status: query.status,
+        }
+    }
+}
+
+fn default_limit() -> i64 {
+    20
+}
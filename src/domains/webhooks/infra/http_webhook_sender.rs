@@ -0,0 +1,58 @@
+use crate::domains::webhooks::domain::sender::{WebhookRequest, WebhookResponse, WebhookSender};
+use async_trait::async_trait;
+use reqwest::{header::CONTENT_TYPE, redirect::Policy, Client};
+use std::time::Duration;
+
+/// Response bytes read per attempt
+const MAX_RESPONSE_BYTES: usize = 4096;
+
+/// Sends webhooks over HTTP(S). Redirects are not followed: the signed request is only
+/// ever sent to the URL the merchant configured.
+pub struct HttpWebhookSender {
+    client: Client,
+}
+
+impl HttpWebhookSender {
+    pub fn new(timeout: Duration) -> Self {
+        let client = Client::builder()
+            .timeout(timeout)
+            .redirect(Policy::none())
+            .build()
+            .expect("HTTP client configuration is valid");
+
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl WebhookSender for HttpWebhookSender {
+    async fn send(&self, request: WebhookRequest) -> Result<WebhookResponse, String> {
+        let mut builder = self
+            .client
+            .post(&request.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(request.body);
+        for (name, value) in request.headers {
+            builder = builder.header(name, value);
+        }
+
+        let mut response = builder.send().await.map_err(|err| err.to_string())?;
+        let status = response.status().as_u16();
+
+        // The body is only logged: read the start of it, and a body that cannot be
+        // read does not fail the attempt
+        let mut body = Vec::new();
+        while body.len() < MAX_RESPONSE_BYTES {
+            match response.chunk().await {
+                Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+                _ => break,
+            }
+        }
+        body.truncate(MAX_RESPONSE_BYTES);
+
+        Ok(WebhookResponse {
+            status,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        })
+    }
+}
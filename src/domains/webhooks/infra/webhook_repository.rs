@@ -0,0 +1,249 @@
+use super::webhook_attempt_entity::{self, Entity as WebhookAttemptEntity};
+use super::webhook_delivery_entity::{self, Entity as WebhookDeliveryEntity};
+use crate::common::error::AppError;
+use crate::domains::{
+    audit::{
+        domain::audit_entry::AuditEntry, infra::audit_log_repository::PostgresAuditLogRepository,
+    },
+    payments::domain::payment_intent::PaymentIntent,
+    webhooks::domain::{
+        delivery::{DeliveryStatus, WebhookAttempt, WebhookDelivery, WebhookDeliveryFilter},
+        repository::WebhookRepository,
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    sea_query::{Expr, LockBehavior, LockType},
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+pub struct PostgresWebhookRepository {
+    db: DatabaseConnection,
+}
+
+impl PostgresWebhookRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    fn entity_to_domain(
+        model: webhook_delivery_entity::Model,
+    ) -> Result<WebhookDelivery, AppError> {
+        Ok(WebhookDelivery {
+            id: model.id,
+            site_id: model.site_id,
+            payment_id: model.payment_id,
+            event_type: model.event_type,
+            payload: model.payload,
+            status: model.status.parse()?,
+            attempts: model.attempts,
+            next_attempt_at: model.next_attempt_at.map(|at| at.with_timezone(&Utc)),
+            last_attempt_at: model.last_attempt_at.map(|at| at.with_timezone(&Utc)),
+            last_response_status: model.last_response_status,
+            last_error: model.last_error,
+            delivered_at: model.delivered_at.map(|at| at.with_timezone(&Utc)),
+            created_at: model.created_at.with_timezone(&Utc),
+            updated_at: model.updated_at.with_timezone(&Utc),
+        })
+    }
+
+    fn domain_to_active_model(delivery: WebhookDelivery) -> webhook_delivery_entity::ActiveModel {
+        webhook_delivery_entity::ActiveModel {
+            id: Set(delivery.id),
+            site_id: Set(delivery.site_id),
+            payment_id: Set(delivery.payment_id),
+            event_type: Set(delivery.event_type),
+            payload: Set(delivery.payload),
+            status: Set(delivery.status.as_str().to_string()),
+            attempts: Set(delivery.attempts),
+            next_attempt_at: Set(delivery.next_attempt_at.map(Into::into)),
+            last_attempt_at: Set(delivery.last_attempt_at.map(Into::into)),
+            last_response_status: Set(delivery.last_response_status),
+            last_error: Set(delivery.last_error),
+            delivered_at: Set(delivery.delivered_at.map(Into::into)),
+            created_at: Set(delivery.created_at.into()),
+            updated_at: Set(delivery.updated_at.into()),
+        }
+    }
+
+    /// Queues a `payment.{status}` webhook per payment; the payment repositories call it in
+    /// the transaction that stores the new status, so no status change goes unnotified
+    pub(crate) async fn insert_payment_events(
+        conn: &impl ConnectionTrait,
+        payments: &[PaymentIntent],
+        at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        if payments.is_empty() {
+            return Ok(());
+        }
+
+        let deliveries = payments
+            .iter()
+            .map(|payment| {
+                WebhookDelivery::for_payment(payment, at).map(Self::domain_to_active_model)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        WebhookDeliveryEntity::insert_many(deliveries)
+            .exec(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    fn attempt_to_domain(model: webhook_attempt_entity::Model) -> WebhookAttempt {
+        WebhookAttempt {
+            id: model.id,
+            delivery_id: model.delivery_id,
+            attempt_number: model.attempt_number,
+            url: model.url,
+            response_status: model.response_status,
+            response_body: model.response_body,
+            error: model.error,
+            duration_ms: model.duration_ms,
+            triggered_by: model.triggered_by,
+            attempted_at: model.attempted_at.with_timezone(&Utc),
+        }
+    }
+
+    fn filter_condition(filter: WebhookDeliveryFilter) -> Condition {
+        let mut condition = Condition::all();
+
+        if let Some(site_id) = filter.site_id {
+            condition = condition.add(webhook_delivery_entity::Column::SiteId.eq(site_id));
+        }
+        if let Some(payment_id) = filter.payment_id {
+            condition = condition.add(webhook_delivery_entity::Column::PaymentId.eq(payment_id));
+        }
+        if let Some(status) = filter.status {
+            condition = condition.add(webhook_delivery_entity::Column::Status.eq(status.as_str()));
+        }
+
+        condition
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for PostgresWebhookRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<WebhookDelivery>, AppError> {
+        WebhookDeliveryEntity::find_by_id(id)
+            .one(&self.db)
+            .await?
+            .map(Self::entity_to_domain)
+            .transpose()
+    }
+
+    async fn create(&self, delivery: WebhookDelivery) -> Result<WebhookDelivery, AppError> {
+        let model = Self::domain_to_active_model(delivery)
+            .insert(&self.db)
+            .await?;
+
+        Self::entity_to_domain(model)
+    }
+
+    async fn list(
+        &self,
+        filter: WebhookDeliveryFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        WebhookDeliveryEntity::find()
+            .filter(Self::filter_condition(filter))
+            .order_by_desc(webhook_delivery_entity::Column::CreatedAt)
+            .limit(limit as u64)
+            .offset(offset as u64)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(Self::entity_to_domain)
+            .collect()
+    }
+
+    async fn claim_due(
+        &self,
+        at: DateTime<Utc>,
+        limit: i64,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        let txn = self.db.begin().await?;
+
+        // Rows another dispatcher is claiming right now are skipped, not waited for
+        let due = WebhookDeliveryEntity::find()
+            .filter(webhook_delivery_entity::Column::Status.eq(DeliveryStatus::Pending.as_str()))
+            .filter(webhook_delivery_entity::Column::NextAttemptAt.lte(at))
+            .order_by_asc(webhook_delivery_entity::Column::NextAttemptAt)
+            .limit(limit as u64)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .all(&txn)
+            .await?;
+
+        if due.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<Uuid> = due.iter().map(|model| model.id).collect();
+        WebhookDeliveryEntity::update_many()
+            .col_expr(
+                webhook_delivery_entity::Column::NextAttemptAt,
+                Expr::value(lease_until),
+            )
+            .filter(webhook_delivery_entity::Column::Id.is_in(ids))
+            .exec(&txn)
+            .await?;
+
+        txn.commit().await?;
+
+        due.into_iter()
+            .map(|mut model| {
+                model.next_attempt_at = Some(lease_until.into());
+                Self::entity_to_domain(model)
+            })
+            .collect()
+    }
+
+    async fn record_attempt(
+        &self,
+        delivery: WebhookDelivery,
+        attempt: WebhookAttempt,
+        audit: Option<AuditEntry>,
+    ) -> Result<WebhookDelivery, AppError> {
+        let txn = self.db.begin().await?;
+
+        webhook_attempt_entity::ActiveModel {
+            id: Set(attempt.id),
+            delivery_id: Set(attempt.delivery_id),
+            attempt_number: Set(attempt.attempt_number),
+            url: Set(attempt.url),
+            response_status: Set(attempt.response_status),
+            response_body: Set(attempt.response_body),
+            error: Set(attempt.error),
+            duration_ms: Set(attempt.duration_ms),
+            triggered_by: Set(attempt.triggered_by),
+            attempted_at: Set(attempt.attempted_at.into()),
+        }
+        .insert(&txn)
+        .await?;
+
+        let model = Self::domain_to_active_model(delivery).update(&txn).await?;
+        if let Some(audit) = audit {
+            PostgresAuditLogRepository::append(&txn, audit).await?;
+        }
+
+        txn.commit().await?;
+
+        Self::entity_to_domain(model)
+    }
+
+    async fn list_attempts(&self, delivery_id: Uuid) -> Result<Vec<WebhookAttempt>, AppError> {
+        Ok(WebhookAttemptEntity::find()
+            .filter(webhook_attempt_entity::Column::DeliveryId.eq(delivery_id))
+            .order_by_asc(webhook_attempt_entity::Column::AttemptedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(Self::attempt_to_domain)
+            .collect())
+    }
+}
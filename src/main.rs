@@ -1,11 +1,13 @@
 use color_eyre::eyre::Result;
 use p2p_payment::{app::create_app, common::bootstrap};
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let state = bootstrap::initialize_app().await?;
 
+    bootstrap::spawn_background_jobs(Arc::clone(&state));
+
     let app = create_app(Arc::clone(&state));
 
     let addr = state.config.server_address();
@@ -14,7 +16,12 @@ async fn main() -> Result<()> {
     tracing::info!("📚 API docs available at http://{}/docs", addr);
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    // Peer addresses feed client IP resolution for merchant API allowlists
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -0,0 +1,2496 @@
+#![allow(dead_code)]
+
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use chrono::{DateTime, Datelike, Utc};
+use http_body_util::BodyExt;
+use p2p_payment::{
+    app::create_app,
+    common::{
+        app_state::Repositories,
+        config::{
+            AuditConfig, AuthConfig, CorsConfig, Environment, IdempotencyConfig, MailConfig,
+            MerchantApiConfig, NetworkConfig, PasswordConfig, PaymentsConfig, StorageConfig,
+            WebhooksConfig,
+        },
+        error::AppError,
+        hash_utils::hash_password,
+        mailer::{MailMessage, Mailer},
+        money::{Currency, Money},
+        request_signature::{self, API_KEY_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER},
+    },
+    domains::audit::{
+        domain::{
+            audit_chain::ChainHead,
+            audit_checkpoint::AuditCheckpoint,
+            audit_entry::{AuditEntry, AuditFilter},
+        },
+        AuditCheckpointRepository, AuditLogRepository,
+    },
+    domains::backoffice::{
+        domain::{
+            idempotency_key::IdempotencyKey,
+            login_challenge::LoginChallenge,
+            merchant::{Merchant, MerchantStatusChange, Site, SiteCredentials},
+            password_policy::PasswordPolicy,
+            password_reset_token::PasswordResetToken,
+            refresh_token::RefreshToken,
+            user::User,
+        },
+        role::{
+            admin_role_id, finance_role_id, risk_role_id, support_role_id, user_role_id,
+            Permission, Role,
+        },
+        IdempotencyKeyRepository, InMemoryTokenRevocationRepository, LoginChallengeRepository,
+        MerchantRepository, PasswordResetTokenRepository, RefreshTokenRepository, RoleRepository,
+        SiteCredentialsRepository, SiteRepository, UserRepository,
+    },
+    domains::disputes::{
+        domain::dispute::{
+            Dispute, DisputeAttachment, DisputeComment, DisputeFilter, DisputeStatus,
+        },
+        DisputeRepository,
+    },
+    domains::ledger::{
+        domain::{
+            account::{AccountKind, LedgerAccount, LedgerAccountFilter},
+            journal_entry::{EntryKind, JournalEntry, JournalEntryFilter},
+        },
+        LedgerRepository,
+    },
+    domains::payments::{
+        domain::{
+            evidence::PaymentEvidence,
+            matching::MatchCandidate,
+            payment_intent::{PaymentIntent, PaymentIntentFilter, PaymentStatus},
+        },
+        MatchingRepository, PaymentEvidenceRepository, PaymentIntentRepository,
+    },
+    domains::traders::{
+        domain::{
+            requisite::Requisite,
+            trader::{Trader, TraderAvailability, TraderFilter},
+        },
+        TraderRepository,
+    },
+    domains::webhooks::{
+        domain::delivery::{
+            DeliveryStatus, WebhookAttempt, WebhookDelivery, WebhookDeliveryFilter,
+        },
+        WebhookRepository,
+    },
+    AppState, Config,
+};
+use sea_orm::DatabaseConnection;
+use serde_json::{json, Value};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+use tempfile::TempDir;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+pub const TEST_PASSWORD: &str = "Test-Password-42";
+
+pub fn test_config(storage_root: &Path) -> Config {
+    Config {
+        environment: Environment::Development,
+        database_url: "postgres://localhost/test".to_string(),
+        database_max_connections: 1,
+        database_min_connections: 1,
+        service_host: "localhost".to_string(),
+        service_port: 8080,
+        logging_level: "INFO".to_string(),
+        jwt_secret_key: "test_secret_key".to_string(),
+        cors: CorsConfig {
+            allow_origin: "http://localhost:3000".to_string(),
+            allow_methods: "GET,POST,PATCH,DELETE".to_string(),
+            allow_headers: "content-type,x-jwt-token".to_string(),
+            allow_credentials: false,
+            max_age: 3600,
+        },
+        auth: AuthConfig::default(),
+        merchant_api: MerchantApiConfig {
+            secret_encryption_key: "test_encryption_key".to_string(),
+            key_rotation_overlap_minutes: 60,
+            signature_max_skew_seconds: 300,
+        },
+        network: NetworkConfig {
+            trusted_proxies: "10.0.0.0/8".to_string(),
+        },
+        payments: PaymentsConfig {
+            settlement_fee_bps: 150,
+            ..PaymentsConfig::default()
+        },
+        storage: StorageConfig {
+            local_root: storage_root.to_string_lossy().into_owned(),
+        },
+        // Failed webhooks are due again right away so tests can drive retries
+        webhooks: WebhooksConfig {
+            max_attempts: 3,
+            initial_backoff_seconds: 0,
+            max_backoff_seconds: 0,
+            request_timeout_seconds: 5,
+            ..WebhooksConfig::default()
+        },
+        idempotency: IdempotencyConfig::default(),
+        audit: AuditConfig {
+            checkpoint_signing_key: "test_checkpoint_key".to_string(),
+            checkpoint_interval_minutes: 60,
+        },
+        password: PasswordConfig {
+            history_size: 3,
+            reset_url: "https://backoffice.test/reset-password".to_string(),
+            ..PasswordConfig::default()
+        },
+        mail: MailConfig::default(),
+    }
+}
+
+pub fn seeded_roles() -> Vec<Role> {
+    [
+        (admin_role_id(), "Admin"),
+        (support_role_id(), "Support"),
+        (risk_role_id(), "Risk"),
+        (finance_role_id(), "Finance"),
+        (user_role_id(), "User"),
+    ]
+    .into_iter()
+    .map(|(id, name)| Role::new(id, name.to_string(), None, Utc::now(), Utc::now()))
+    .collect()
+}
+
+/// Role grants as seeded by the permissions migration
+pub fn seeded_permissions(role_id: Uuid) -> Vec<String> {
+    let permissions: &[&str] = if role_id == admin_role_id() {
+        &[
+            "audit:read",
+            "disputes:read",
+            "disputes:resolve",
+            "disputes:write",
+            "ledger:read",
+            "merchants:read",
+            "merchants:write",
+            "payments:confirm",
+            "payments:read",
+            "payouts:approve",
+            "payouts:read",
+            "roles:read",
+            "roles:write",
+            "traders:read",
+            "traders:write",
+            "users:read",
+            "users:write",
+            "webhooks:read",
+            "webhooks:redeliver",
+        ]
+    } else if role_id == finance_role_id() {
+        &[
+            "disputes:read",
+            "ledger:read",
+            "merchants:read",
+            "payments:read",
+            "payouts:approve",
+            "payouts:read",
+            "traders:read",
+            "users:read",
+        ]
+    } else if role_id == risk_role_id() {
+        &[
+            "audit:read",
+            "disputes:read",
+            "disputes:resolve",
+            "disputes:write",
+            "merchants:read",
+            "payments:read",
+            "traders:read",
+            "traders:write",
+            "users:read",
+        ]
+    } else if role_id == support_role_id() {
+        &[
+            "disputes:read",
+            "disputes:write",
+            "merchants:read",
+            "payments:confirm",
+            "payments:read",
+            "users:read",
+            "webhooks:read",
+            "webhooks:redeliver",
+        ]
+    } else {
+        &["users:read"]
+    };
+
+    permissions.iter().map(|p| p.to_string()).collect()
+}
+
+/// Users are stored together with their audit entries and password history,
+/// like the Postgres repository
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    users: Mutex<HashMap<Uuid, User>>,
+    /// Password hashes per user, oldest first
+    password_history: Mutex<HashMap<Uuid, Vec<String>>>,
+    pub audit_log: Arc<InMemoryAuditLogRepository>,
+}
+
+impl InMemoryUserRepository {
+    /// Stores a user without an audit entry, as test fixtures do
+    pub fn insert(&self, user: User) -> User {
+        self.users.lock().unwrap().insert(user.id, user.clone());
+        user
+    }
+
+    pub fn record_password(&self, user_id: Uuid, password_hash: &str) {
+        self.password_history
+            .lock()
+            .unwrap()
+            .entry(user_id)
+            .or_default()
+            .push(password_hash.to_string());
+    }
+
+    /// Stored row, soft-deleted or not
+    pub fn stored(&self, id: Uuid) -> Option<User> {
+        self.users.lock().unwrap().get(&id).cloned()
+    }
+
+    fn existing(&self) -> Vec<User> {
+        self.users
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|u| !u.is_deleted())
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, AppError> {
+        Ok(self.existing().into_iter().find(|u| u.id == id))
+    }
+
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError> {
+        Ok(self.existing().into_iter().find(|u| u.username == username))
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
+        Ok(self.existing().into_iter().find(|u| u.email == email))
+    }
+
+    async fn exists_by_username(&self, username: &str) -> Result<bool, AppError> {
+        let users = self.users.lock().unwrap();
+        Ok(users.values().any(|u| u.username == username))
+    }
+
+    async fn exists_by_email(&self, email: &str) -> Result<bool, AppError> {
+        let users = self.users.lock().unwrap();
+        Ok(users.values().any(|u| u.email == email))
+    }
+
+    async fn create(&self, user: User, audit: AuditEntry) -> Result<User, AppError> {
+        self.record_password(user.id, &user.password_hash);
+        self.audit_log.append(audit);
+        Ok(self.insert(user))
+    }
+
+    async fn update(&self, user: User, audit: AuditEntry) -> Result<User, AppError> {
+        self.audit_log.append(audit);
+        Ok(self.insert(user))
+    }
+
+    async fn change_password(&self, user: User, audit: AuditEntry) -> Result<User, AppError> {
+        self.record_password(user.id, &user.password_hash);
+        self.audit_log.append(audit);
+        Ok(self.insert(user))
+    }
+
+    async fn delete(&self, id: Uuid, audit: AuditEntry) -> Result<(), AppError> {
+        let mut user = self
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("User {} not found", id)))?;
+        user.mark_deleted();
+        self.insert(user);
+        self.audit_log.append(audit);
+        Ok(())
+    }
+
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<User>, AppError> {
+        Ok(self
+            .existing()
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn search(&self, query: &str, limit: i64) -> Result<Vec<User>, AppError> {
+        Ok(self
+            .existing()
+            .into_iter()
+            .filter(|u| u.username.contains(query) || u.email.contains(query))
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn is_admin(&self, id: Uuid) -> Result<bool, AppError> {
+        Ok(self
+            .find_by_id(id)
+            .await?
+            .map(|u| u.is_admin())
+            .unwrap_or(false))
+    }
+
+    async fn count(&self) -> Result<i64, AppError> {
+        Ok(self.existing().len() as i64)
+    }
+
+    async fn password_history(&self, user_id: Uuid, limit: usize) -> Result<Vec<String>, AppError> {
+        let history = self.password_history.lock().unwrap();
+        Ok(history
+            .get(&user_id)
+            .map(|hashes| hashes.iter().rev().take(limit).cloned().collect())
+            .unwrap_or_default())
+    }
+}
+
+pub struct InMemoryRoleRepository {
+    roles: Mutex<Vec<Role>>,
+    permissions: Mutex<HashMap<Uuid, Vec<String>>>,
+    users: Arc<InMemoryUserRepository>,
+}
+
+impl InMemoryRoleRepository {
+    pub fn new(users: Arc<InMemoryUserRepository>) -> Self {
+        let roles = seeded_roles();
+        let permissions = roles
+            .iter()
+            .map(|r| (r.role_id, seeded_permissions(r.role_id)))
+            .collect();
+
+        Self {
+            roles: Mutex::new(roles),
+            permissions: Mutex::new(permissions),
+            users,
+        }
+    }
+}
+
+#[async_trait]
+impl RoleRepository for InMemoryRoleRepository {
+    async fn find_by_id(&self, role_id: Uuid) -> Result<Option<Role>, AppError> {
+        let roles = self.roles.lock().unwrap();
+        Ok(roles.iter().find(|r| r.role_id == role_id).cloned())
+    }
+
+    async fn find_by_name(&self, role_name: &str) -> Result<Option<Role>, AppError> {
+        let roles = self.roles.lock().unwrap();
+        Ok(roles.iter().find(|r| r.role_name == role_name).cloned())
+    }
+
+    async fn list_all(&self) -> Result<Vec<Role>, AppError> {
+        Ok(self.roles.lock().unwrap().clone())
+    }
+
+    async fn create(
+        &self,
+        role: Role,
+        permission_names: Vec<String>,
+        audit: AuditEntry,
+    ) -> Result<Role, AppError> {
+        self.users.audit_log.append(audit);
+        self.roles.lock().unwrap().push(role.clone());
+        self.permissions
+            .lock()
+            .unwrap()
+            .insert(role.role_id, permission_names);
+        Ok(role)
+    }
+
+    async fn update(
+        &self,
+        role: Role,
+        permission_names: Option<Vec<String>>,
+        audit: AuditEntry,
+    ) -> Result<Role, AppError> {
+        let mut roles = self.roles.lock().unwrap();
+        let existing = roles
+            .iter_mut()
+            .find(|r| r.role_id == role.role_id)
+            .ok_or_else(|| AppError::NotFound("Record not found".to_string()))?;
+        *existing = role.clone();
+        self.users.audit_log.append(audit);
+        if let Some(permission_names) = permission_names {
+            self.permissions
+                .lock()
+                .unwrap()
+                .insert(role.role_id, permission_names);
+        }
+        Ok(role)
+    }
+
+    async fn delete(&self, role_id: Uuid, audit: AuditEntry) -> Result<(), AppError> {
+        let fallback = self.find_by_id(user_role_id()).await?.unwrap();
+        for user in self.users.users.lock().unwrap().values_mut() {
+            if user.role.role_id == role_id && user.deleted_at.is_some() {
+                user.role = fallback.clone();
+            }
+        }
+        self.users.audit_log.append(audit);
+        self.roles.lock().unwrap().retain(|r| r.role_id != role_id);
+        self.permissions.lock().unwrap().remove(&role_id);
+        Ok(())
+    }
+
+    async fn count_users(&self, role_id: Uuid) -> Result<i64, AppError> {
+        let users = self.users.users.lock().unwrap();
+        Ok(users
+            .values()
+            .filter(|u| u.role.role_id == role_id && u.deleted_at.is_none())
+            .count() as i64)
+    }
+
+    async fn find_permission_names(&self, role_id: Uuid) -> Result<Vec<String>, AppError> {
+        let permissions = self.permissions.lock().unwrap();
+        Ok(permissions.get(&role_id).cloned().unwrap_or_default())
+    }
+
+    async fn list_permissions(&self) -> Result<Vec<Permission>, AppError> {
+        Ok(seeded_permissions(admin_role_id())
+            .into_iter()
+            .map(|name| Permission {
+                permission_id: Uuid::new_v4(),
+                permission_name: name,
+                permission_description: None,
+                created_at: Utc::now(),
+            })
+            .collect())
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryLoginChallengeRepository {
+    challenges: Mutex<HashMap<Uuid, LoginChallenge>>,
+}
+
+#[async_trait]
+impl LoginChallengeRepository for InMemoryLoginChallengeRepository {
+    async fn create(&self, challenge: LoginChallenge) -> Result<LoginChallenge, AppError> {
+        self.challenges
+            .lock()
+            .unwrap()
+            .insert(challenge.id, challenge.clone());
+        Ok(challenge)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<LoginChallenge>, AppError> {
+        Ok(self.challenges.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn register_attempt(
+        &self,
+        id: Uuid,
+        max_attempts: i32,
+    ) -> Result<Option<LoginChallenge>, AppError> {
+        let mut challenges = self.challenges.lock().unwrap();
+        let Some(challenge) = challenges
+            .get_mut(&id)
+            .filter(|challenge| challenge.is_usable(max_attempts))
+        else {
+            return Ok(None);
+        };
+        challenge.register_attempt();
+        Ok(Some(challenge.clone()))
+    }
+
+    async fn consume(&self, id: Uuid) -> Result<bool, AppError> {
+        let mut challenges = self.challenges.lock().unwrap();
+        match challenges.get_mut(&id) {
+            Some(challenge) if !challenge.is_consumed() => {
+                challenge.consume();
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryRefreshTokenRepository {
+    tokens: Mutex<HashMap<Uuid, RefreshToken>>,
+    pub audit_log: Arc<InMemoryAuditLogRepository>,
+}
+
+impl InMemoryRefreshTokenRepository {
+    pub fn new(audit_log: Arc<InMemoryAuditLogRepository>) -> Self {
+        Self {
+            audit_log,
+            ..Self::default()
+        }
+    }
+
+    fn revoke_where(&self, predicate: impl Fn(&RefreshToken) -> bool) {
+        let now = Utc::now();
+        for token in self.tokens.lock().unwrap().values_mut() {
+            if predicate(token) && token.revoked_at.is_none() {
+                token.revoked_at = Some(now);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for InMemoryRefreshTokenRepository {
+    async fn create(&self, token: RefreshToken) -> Result<RefreshToken, AppError> {
+        self.tokens.lock().unwrap().insert(token.id, token.clone());
+        Ok(token)
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError> {
+        Ok(self
+            .tokens
+            .lock()
+            .unwrap()
+            .values()
+            .find(|t| t.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn mark_rotated(&self, id: Uuid) -> Result<bool, AppError> {
+        let mut tokens = self.tokens.lock().unwrap();
+        match tokens.get_mut(&id) {
+            Some(token) if token.rotated_at.is_none() && token.revoked_at.is_none() => {
+                token.rotated_at = Some(Utc::now());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn revoke_family(
+        &self,
+        family_id: Uuid,
+        audit: Option<AuditEntry>,
+    ) -> Result<(), AppError> {
+        self.revoke_where(|t| t.family_id == family_id);
+        if let Some(audit) = audit {
+            self.audit_log.append(audit);
+        }
+        Ok(())
+    }
+
+    async fn revoke_all_for_user(&self, user_id: Uuid, audit: AuditEntry) -> Result<(), AppError> {
+        self.revoke_where(|t| t.user_id == user_id);
+        self.audit_log.append(audit);
+        Ok(())
+    }
+
+    async fn is_family_revoked(&self, family_id: Uuid) -> Result<bool, AppError> {
+        Ok(self
+            .tokens
+            .lock()
+            .unwrap()
+            .values()
+            .any(|t| t.family_id == family_id && t.revoked_at.is_some()))
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryPasswordResetTokenRepository {
+    pub tokens: Mutex<HashMap<Uuid, PasswordResetToken>>,
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for InMemoryPasswordResetTokenRepository {
+    async fn create(&self, token: PasswordResetToken) -> Result<PasswordResetToken, AppError> {
+        let mut tokens = self.tokens.lock().unwrap();
+        for earlier in tokens.values_mut() {
+            if earlier.user_id == token.user_id && earlier.used_at.is_none() {
+                earlier.used_at = Some(token.created_at);
+            }
+        }
+        tokens.insert(token.id, token.clone());
+        Ok(token)
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<PasswordResetToken>, AppError> {
+        Ok(self
+            .tokens
+            .lock()
+            .unwrap()
+            .values()
+            .find(|t| t.token_hash == token_hash)
+            .cloned())
+    }
+
+    async fn mark_used(&self, id: Uuid) -> Result<bool, AppError> {
+        let mut tokens = self.tokens.lock().unwrap();
+        match tokens.get_mut(&id) {
+            Some(token) if token.used_at.is_none() => {
+                token.used_at = Some(Utc::now());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryMerchantRepository {
+    pub merchants: Mutex<HashMap<Uuid, Merchant>>,
+    pub status_history: Mutex<Vec<MerchantStatusChange>>,
+    /// Merchants that own an active site with active credentials
+    pub with_active_credentials: Mutex<HashSet<Uuid>>,
+    pub audit_log: Arc<InMemoryAuditLogRepository>,
+}
+
+impl InMemoryMerchantRepository {
+    pub fn new(audit_log: Arc<InMemoryAuditLogRepository>) -> Self {
+        Self {
+            audit_log,
+            ..Self::default()
+        }
+    }
+
+    fn store(&self, merchant: Merchant) -> Merchant {
+        self.merchants
+            .lock()
+            .unwrap()
+            .insert(merchant.id, merchant.clone());
+        merchant
+    }
+}
+
+#[async_trait]
+impl MerchantRepository for InMemoryMerchantRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Merchant>, AppError> {
+        Ok(self.merchants.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn exists_by_name(&self, name: &str) -> Result<bool, AppError> {
+        let merchants = self.merchants.lock().unwrap();
+        Ok(merchants.values().any(|m| m.name == name))
+    }
+
+    async fn create(&self, merchant: Merchant, audit: AuditEntry) -> Result<Merchant, AppError> {
+        self.audit_log.append(audit);
+        Ok(self.store(merchant))
+    }
+
+    async fn update(&self, merchant: Merchant, audit: AuditEntry) -> Result<Merchant, AppError> {
+        self.audit_log.append(audit);
+        Ok(self.store(merchant))
+    }
+
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Merchant>, AppError> {
+        let mut merchants: Vec<Merchant> =
+            self.merchants.lock().unwrap().values().cloned().collect();
+        merchants.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(merchants
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn search(&self, query: &str, limit: i64) -> Result<Vec<Merchant>, AppError> {
+        let mut merchants: Vec<Merchant> = self
+            .merchants
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|m| m.name.contains(query))
+            .cloned()
+            .collect();
+        merchants.sort_by(|a, b| a.name.cmp(&b.name));
+        merchants.truncate(limit as usize);
+        Ok(merchants)
+    }
+
+    async fn change_status(
+        &self,
+        merchant: Merchant,
+        change: MerchantStatusChange,
+        audit: AuditEntry,
+    ) -> Result<Merchant, AppError> {
+        self.status_history.lock().unwrap().push(change);
+        self.update(merchant, audit).await
+    }
+
+    async fn status_history(
+        &self,
+        merchant_id: Uuid,
+    ) -> Result<Vec<MerchantStatusChange>, AppError> {
+        Ok(self
+            .status_history
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|change| change.merchant_id == merchant_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn has_active_site_with_credentials(&self, merchant_id: Uuid) -> Result<bool, AppError> {
+        Ok(self
+            .with_active_credentials
+            .lock()
+            .unwrap()
+            .contains(&merchant_id))
+    }
+}
+
+#[derive(Default)]
+pub struct InMemorySiteRepository {
+    pub sites: Mutex<HashMap<Uuid, Site>>,
+    pub audit_log: Arc<InMemoryAuditLogRepository>,
+}
+
+impl InMemorySiteRepository {
+    pub fn new(audit_log: Arc<InMemoryAuditLogRepository>) -> Self {
+        Self {
+            audit_log,
+            ..Self::default()
+        }
+    }
+}
+
+#[async_trait]
+impl SiteRepository for InMemorySiteRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Site>, AppError> {
+        Ok(self.sites.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn list_by_merchant(&self, merchant_id: Uuid) -> Result<Vec<Site>, AppError> {
+        let mut sites: Vec<Site> = self
+            .sites
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|s| s.merchant_id == merchant_id)
+            .cloned()
+            .collect();
+        sites.sort_by_key(|s| s.created_at);
+        Ok(sites)
+    }
+
+    async fn exists_by_name(&self, name: &str) -> Result<bool, AppError> {
+        Ok(self.sites.lock().unwrap().values().any(|s| s.name == name))
+    }
+
+    async fn exists_by_url(&self, url: &str) -> Result<bool, AppError> {
+        Ok(self.sites.lock().unwrap().values().any(|s| s.url == url))
+    }
+
+    async fn create(&self, site: Site, audit: AuditEntry) -> Result<Site, AppError> {
+        self.audit_log.append(audit);
+        self.sites.lock().unwrap().insert(site.id, site.clone());
+        Ok(site)
+    }
+
+    async fn update(&self, site: Site, audit: AuditEntry) -> Result<Site, AppError> {
+        self.create(site, audit).await
+    }
+}
+
+#[derive(Default)]
+pub struct InMemorySiteCredentialsRepository {
+    pub credentials: Mutex<HashMap<Uuid, SiteCredentials>>,
+    pub audit_log: Arc<InMemoryAuditLogRepository>,
+}
+
+impl InMemorySiteCredentialsRepository {
+    pub fn new(audit_log: Arc<InMemoryAuditLogRepository>) -> Self {
+        Self {
+            audit_log,
+            ..Self::default()
+        }
+    }
+
+    fn store(&self, credentials: SiteCredentials) -> SiteCredentials {
+        self.credentials
+            .lock()
+            .unwrap()
+            .insert(credentials.id, credentials.clone());
+        credentials
+    }
+}
+
+#[async_trait]
+impl SiteCredentialsRepository for InMemorySiteCredentialsRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<SiteCredentials>, AppError> {
+        Ok(self.credentials.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_by_public_key(
+        &self,
+        public_key: &str,
+    ) -> Result<Option<SiteCredentials>, AppError> {
+        Ok(self
+            .credentials
+            .lock()
+            .unwrap()
+            .values()
+            .find(|c| c.public_key == public_key)
+            .cloned())
+    }
+
+    async fn list_by_site(&self, site_id: Uuid) -> Result<Vec<SiteCredentials>, AppError> {
+        let mut credentials: Vec<SiteCredentials> = self
+            .credentials
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|c| c.site_id == site_id)
+            .cloned()
+            .collect();
+        credentials.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+        Ok(credentials)
+    }
+
+    async fn create(
+        &self,
+        credentials: SiteCredentials,
+        audit: AuditEntry,
+    ) -> Result<SiteCredentials, AppError> {
+        self.audit_log.append(audit);
+        Ok(self.store(credentials))
+    }
+
+    async fn update(
+        &self,
+        credentials: SiteCredentials,
+        audit: AuditEntry,
+    ) -> Result<SiteCredentials, AppError> {
+        self.create(credentials, audit).await
+    }
+
+    async fn rotate(
+        &self,
+        credentials: SiteCredentials,
+        replaced: Vec<SiteCredentials>,
+        audit: Vec<AuditEntry>,
+    ) -> Result<SiteCredentials, AppError> {
+        for old in replaced {
+            self.store(old);
+        }
+        for entry in audit {
+            self.audit_log.append(entry);
+        }
+        Ok(self.store(credentials))
+    }
+}
+
+/// Status changes queue their webhooks into `webhooks`, as the Postgres repository does
+/// within its transaction
+pub struct InMemoryPaymentIntentRepository {
+    pub intents: Mutex<HashMap<Uuid, PaymentIntent>>,
+    webhooks: Arc<InMemoryWebhookRepository>,
+    ledger: Arc<InMemoryLedgerRepository>,
+    audit_log: Arc<InMemoryAuditLogRepository>,
+}
+
+impl InMemoryPaymentIntentRepository {
+    pub fn new(
+        webhooks: Arc<InMemoryWebhookRepository>,
+        ledger: Arc<InMemoryLedgerRepository>,
+        audit_log: Arc<InMemoryAuditLogRepository>,
+    ) -> Self {
+        Self {
+            intents: Mutex::new(HashMap::new()),
+            webhooks,
+            ledger,
+            audit_log,
+        }
+    }
+
+    fn queue_webhook(&self, intent: &PaymentIntent, at: DateTime<Utc>) -> Result<(), AppError> {
+        let delivery = WebhookDelivery::for_payment(intent, at)?;
+        self.webhooks
+            .deliveries
+            .lock()
+            .unwrap()
+            .insert(delivery.id, delivery);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PaymentIntentRepository for InMemoryPaymentIntentRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PaymentIntent>, AppError> {
+        Ok(self.intents.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_by_external_order_id(
+        &self,
+        site_id: Uuid,
+        external_order_id: &str,
+    ) -> Result<Option<PaymentIntent>, AppError> {
+        Ok(self
+            .intents
+            .lock()
+            .unwrap()
+            .values()
+            .find(|i| i.site_id == site_id && i.external_order_id == external_order_id)
+            .cloned())
+    }
+
+    async fn create(&self, intent: PaymentIntent) -> Result<PaymentIntent, AppError> {
+        self.intents
+            .lock()
+            .unwrap()
+            .insert(intent.id, intent.clone());
+        Ok(intent)
+    }
+
+    async fn update_status(
+        &self,
+        intent: PaymentIntent,
+        audit: Option<AuditEntry>,
+    ) -> Result<PaymentIntent, AppError> {
+        self.queue_webhook(&intent, Utc::now())?;
+        if let Some(audit) = audit {
+            self.audit_log.append(audit);
+        }
+        self.create(intent).await
+    }
+
+    async fn settle(
+        &self,
+        intent: PaymentIntent,
+        settlement: Vec<JournalEntry>,
+        audit: AuditEntry,
+    ) -> Result<PaymentIntent, AppError> {
+        // Nothing is stored unless the whole settlement posts
+        self.ledger.post(settlement, None).await?;
+        self.update_status(intent, Some(audit)).await
+    }
+
+    async fn list(
+        &self,
+        filter: PaymentIntentFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<PaymentIntent>, AppError> {
+        let mut intents: Vec<PaymentIntent> = self
+            .intents
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|i| filter.matches(i))
+            .cloned()
+            .collect();
+        intents.sort_by_key(|i| std::cmp::Reverse(i.created_at));
+        Ok(intents
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn expire_due(&self, at: DateTime<Utc>) -> Result<Vec<PaymentIntent>, AppError> {
+        let expired: Vec<PaymentIntent> = self
+            .intents
+            .lock()
+            .unwrap()
+            .values_mut()
+            .filter_map(|i| i.expire_if_due(at).then(|| i.clone()))
+            .collect();
+        for intent in &expired {
+            self.queue_webhook(intent, at)?;
+        }
+        Ok(expired)
+    }
+
+    async fn escalate_due(
+        &self,
+        marked_paid_before: DateTime<Utc>,
+        at: DateTime<Utc>,
+    ) -> Result<u64, AppError> {
+        let mut intents = self.intents.lock().unwrap();
+        Ok(intents
+            .values_mut()
+            .map(|i| i.escalate_if_due(at - marked_paid_before, at))
+            .filter(|escalated| *escalated)
+            .count() as u64)
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryPaymentEvidenceRepository {
+    pub evidence: Mutex<Vec<PaymentEvidence>>,
+}
+
+#[async_trait]
+impl PaymentEvidenceRepository for InMemoryPaymentEvidenceRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PaymentEvidence>, AppError> {
+        Ok(self
+            .evidence
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|e| e.id == id)
+            .cloned())
+    }
+
+    async fn create(&self, evidence: PaymentEvidence) -> Result<PaymentEvidence, AppError> {
+        self.evidence.lock().unwrap().push(evidence.clone());
+        Ok(evidence)
+    }
+
+    async fn list_by_payment(&self, payment_id: Uuid) -> Result<Vec<PaymentEvidence>, AppError> {
+        Ok(self
+            .evidence
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.payment_id == payment_id)
+            .cloned()
+            .collect())
+    }
+}
+
+pub struct InMemoryDisputeRepository {
+    pub disputes: Mutex<HashMap<Uuid, Dispute>>,
+    pub comments: Mutex<Vec<DisputeComment>>,
+    pub attachments: Mutex<Vec<DisputeAttachment>>,
+    /// Adjustments of resolved disputes are posted here
+    ledger: Arc<InMemoryLedgerRepository>,
+    audit_log: Arc<InMemoryAuditLogRepository>,
+}
+
+impl InMemoryDisputeRepository {
+    pub fn new(
+        ledger: Arc<InMemoryLedgerRepository>,
+        audit_log: Arc<InMemoryAuditLogRepository>,
+    ) -> Self {
+        Self {
+            disputes: Mutex::default(),
+            comments: Mutex::default(),
+            attachments: Mutex::default(),
+            ledger,
+            audit_log,
+        }
+    }
+
+    fn store(&self, dispute: &Dispute) {
+        self.disputes
+            .lock()
+            .unwrap()
+            .insert(dispute.id, dispute.clone());
+    }
+}
+
+#[async_trait]
+impl DisputeRepository for InMemoryDisputeRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Dispute>, AppError> {
+        Ok(self.disputes.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_unresolved_by_payment(
+        &self,
+        payment_id: Uuid,
+    ) -> Result<Option<Dispute>, AppError> {
+        Ok(self
+            .disputes
+            .lock()
+            .unwrap()
+            .values()
+            .find(|d| d.payment_id == payment_id && d.status != DisputeStatus::Resolved)
+            .cloned())
+    }
+
+    async fn find_adjusted_by_payment(
+        &self,
+        payment_id: Uuid,
+    ) -> Result<Option<Dispute>, AppError> {
+        Ok(self
+            .disputes
+            .lock()
+            .unwrap()
+            .values()
+            .find(|d| d.payment_id == payment_id && d.adjustment_entry_id.is_some())
+            .cloned())
+    }
+
+    async fn create(&self, dispute: Dispute, audit: AuditEntry) -> Result<Dispute, AppError> {
+        self.store(&dispute);
+        self.audit_log.append(audit);
+        Ok(dispute)
+    }
+
+    async fn update(&self, dispute: Dispute, audit: AuditEntry) -> Result<Dispute, AppError> {
+        self.store(&dispute);
+        self.audit_log.append(audit);
+        Ok(dispute)
+    }
+
+    async fn resolve(
+        &self,
+        dispute: Dispute,
+        adjustment: Option<JournalEntry>,
+        audit: AuditEntry,
+    ) -> Result<Dispute, AppError> {
+        if let Some(entry) = &adjustment {
+            entry.ensure_balanced()?;
+        }
+
+        let mut disputes = self.disputes.lock().unwrap();
+        match disputes.get(&dispute.id) {
+            Some(stored) if stored.status == DisputeStatus::Investigating => {}
+            _ => {
+                return Err(AppError::InvalidStateTransition(format!(
+                    "Dispute {} is no longer under investigation",
+                    dispute.id
+                )))
+            }
+        }
+        // Mirrors idx_dispute_adjusted_payment_id
+        if dispute.adjustment_entry_id.is_some()
+            && disputes.values().any(|d| {
+                d.payment_id == dispute.payment_id
+                    && d.id != dispute.id
+                    && d.adjustment_entry_id.is_some()
+            })
+        {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Payment {} was already adjusted by an earlier dispute",
+                dispute.payment_id
+            )));
+        }
+        disputes.insert(dispute.id, dispute.clone());
+        self.ledger.entries.lock().unwrap().extend(adjustment);
+        self.audit_log.append(audit);
+        Ok(dispute)
+    }
+
+    async fn list(
+        &self,
+        filter: DisputeFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Dispute>, AppError> {
+        let mut disputes: Vec<Dispute> = self
+            .disputes
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|d| filter.matches(d))
+            .cloned()
+            .collect();
+        disputes.sort_by_key(|d| d.created_at);
+        Ok(disputes
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn add_comment(
+        &self,
+        comment: DisputeComment,
+        audit: AuditEntry,
+    ) -> Result<DisputeComment, AppError> {
+        self.comments.lock().unwrap().push(comment.clone());
+        self.audit_log.append(audit);
+        Ok(comment)
+    }
+
+    async fn list_comments(&self, dispute_id: Uuid) -> Result<Vec<DisputeComment>, AppError> {
+        Ok(self
+            .comments
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| c.dispute_id == dispute_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_attachment(&self, id: Uuid) -> Result<Option<DisputeAttachment>, AppError> {
+        Ok(self
+            .attachments
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|a| a.id == id)
+            .cloned())
+    }
+
+    async fn add_attachment(
+        &self,
+        attachment: DisputeAttachment,
+        audit: AuditEntry,
+    ) -> Result<DisputeAttachment, AppError> {
+        self.attachments.lock().unwrap().push(attachment.clone());
+        self.audit_log.append(audit);
+        Ok(attachment)
+    }
+
+    async fn list_attachments(&self, dispute_id: Uuid) -> Result<Vec<DisputeAttachment>, AppError> {
+        Ok(self
+            .attachments
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|a| a.dispute_id == dispute_id)
+            .cloned()
+            .collect())
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryWebhookRepository {
+    pub deliveries: Mutex<HashMap<Uuid, WebhookDelivery>>,
+    pub attempts: Mutex<Vec<WebhookAttempt>>,
+    pub audit_log: Arc<InMemoryAuditLogRepository>,
+}
+
+impl InMemoryWebhookRepository {
+    pub fn new(audit_log: Arc<InMemoryAuditLogRepository>) -> Self {
+        Self {
+            audit_log,
+            ..Self::default()
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for InMemoryWebhookRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<WebhookDelivery>, AppError> {
+        Ok(self.deliveries.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn create(&self, delivery: WebhookDelivery) -> Result<WebhookDelivery, AppError> {
+        self.deliveries
+            .lock()
+            .unwrap()
+            .insert(delivery.id, delivery.clone());
+        Ok(delivery)
+    }
+
+    async fn list(
+        &self,
+        filter: WebhookDeliveryFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        let mut deliveries: Vec<WebhookDelivery> = self
+            .deliveries
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|d| filter.matches(d))
+            .cloned()
+            .collect();
+        deliveries.sort_by_key(|d| std::cmp::Reverse(d.created_at));
+        Ok(deliveries
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn claim_due(
+        &self,
+        at: DateTime<Utc>,
+        limit: i64,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        let mut deliveries = self.deliveries.lock().unwrap();
+        let mut due: Vec<&mut WebhookDelivery> = deliveries
+            .values_mut()
+            .filter(|d| {
+                d.status == DeliveryStatus::Pending && d.next_attempt_at.is_some_and(|n| n <= at)
+            })
+            .collect();
+        due.sort_by_key(|d| d.next_attempt_at);
+
+        Ok(due
+            .into_iter()
+            .take(limit as usize)
+            .map(|d| {
+                let claimed = d.clone();
+                d.next_attempt_at = Some(lease_until);
+                claimed
+            })
+            .collect())
+    }
+
+    async fn record_attempt(
+        &self,
+        delivery: WebhookDelivery,
+        attempt: WebhookAttempt,
+        audit: Option<AuditEntry>,
+    ) -> Result<WebhookDelivery, AppError> {
+        self.attempts.lock().unwrap().push(attempt);
+        if let Some(audit) = audit {
+            self.audit_log.append(audit);
+        }
+        self.create(delivery).await
+    }
+
+    async fn list_attempts(&self, delivery_id: Uuid) -> Result<Vec<WebhookAttempt>, AppError> {
+        Ok(self
+            .attempts
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|a| a.delivery_id == delivery_id)
+            .cloned()
+            .collect())
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryLedgerRepository {
+    pub accounts: Mutex<Vec<LedgerAccount>>,
+    pub entries: Mutex<Vec<JournalEntry>>,
+    pub audit_log: Arc<InMemoryAuditLogRepository>,
+}
+
+impl InMemoryLedgerRepository {
+    pub fn new(audit_log: Arc<InMemoryAuditLogRepository>) -> Self {
+        Self {
+            audit_log,
+            ..Self::default()
+        }
+    }
+}
+
+#[async_trait]
+impl LedgerRepository for InMemoryLedgerRepository {
+    async fn find_account(&self, id: Uuid) -> Result<Option<LedgerAccount>, AppError> {
+        Ok(self
+            .accounts
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|a| a.id == id)
+            .cloned())
+    }
+
+    async fn find_account_by_owner(
+        &self,
+        kind: AccountKind,
+        owner_id: Option<Uuid>,
+        currency: Currency,
+    ) -> Result<Option<LedgerAccount>, AppError> {
+        Ok(self
+            .accounts
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|a| a.kind == kind && a.owner_id == owner_id && a.currency == currency)
+            .cloned())
+    }
+
+    async fn create_account(&self, account: LedgerAccount) -> Result<LedgerAccount, AppError> {
+        self.accounts.lock().unwrap().push(account.clone());
+        Ok(account)
+    }
+
+    async fn list_accounts(
+        &self,
+        filter: LedgerAccountFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LedgerAccount>, AppError> {
+        Ok(self
+            .accounts
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|a| filter.matches(a))
+            .skip(offset as usize)
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn post(
+        &self,
+        entries: Vec<JournalEntry>,
+        audit: Option<AuditEntry>,
+    ) -> Result<Vec<JournalEntry>, AppError> {
+        // Mirrors the database trigger
+        for entry in &entries {
+            entry.ensure_balanced()?;
+        }
+        self.entries.lock().unwrap().extend(entries.iter().cloned());
+        if let Some(audit) = audit {
+            self.audit_log.append(audit);
+        }
+        Ok(entries)
+    }
+
+    async fn post_if_covered(
+        &self,
+        entries: Vec<JournalEntry>,
+        account: LedgerAccount,
+        audit: Option<AuditEntry>,
+    ) -> Result<Vec<JournalEntry>, AppError> {
+        for entry in &entries {
+            entry.ensure_balanced()?;
+        }
+        let mut stored = self.entries.lock().unwrap();
+        let posted = stored
+            .iter()
+            .filter(|e| e.effective_at <= Utc::now())
+            .flat_map(|e| e.postings.iter())
+            .filter(|p| p.account_id == account.id)
+            .map(|p| p.amount.minor_units())
+            .sum();
+        account.ensure_covers(posted, &entries)?;
+        stored.extend(entries.iter().cloned());
+        if let Some(audit) = audit {
+            self.audit_log.append(audit);
+        }
+        Ok(entries)
+    }
+
+    async fn find_entry(&self, id: Uuid) -> Result<Option<JournalEntry>, AppError> {
+        Ok(self
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|e| e.id == id)
+            .cloned())
+    }
+
+    async fn find_entry_by_reference(
+        &self,
+        kind: EntryKind,
+        reference_id: Uuid,
+    ) -> Result<Option<JournalEntry>, AppError> {
+        Ok(self
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|e| e.kind == kind && e.reference_id == Some(reference_id))
+            .cloned())
+    }
+
+    async fn list_entries(
+        &self,
+        filter: JournalEntryFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<JournalEntry>, AppError> {
+        let mut entries: Vec<JournalEntry> = self
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| filter.matches(e))
+            .cloned()
+            .collect();
+        entries.reverse();
+        Ok(entries
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn posted_sum(&self, account_id: Uuid, as_of: DateTime<Utc>) -> Result<i64, AppError> {
+        Ok(self
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.effective_at <= as_of)
+            .flat_map(|e| e.postings.iter())
+            .filter(|p| p.account_id == account_id)
+            .map(|p| p.amount.minor_units())
+            .sum())
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryTraderRepository {
+    pub traders: Mutex<HashMap<Uuid, Trader>>,
+    /// Oldest first
+    pub requisites: Mutex<Vec<Requisite>>,
+    pub audit_log: Arc<InMemoryAuditLogRepository>,
+}
+
+impl InMemoryTraderRepository {
+    pub fn new(audit_log: Arc<InMemoryAuditLogRepository>) -> Self {
+        Self {
+            audit_log,
+            ..Self::default()
+        }
+    }
+
+    fn store(&self, trader: &Trader) {
+        self.traders
+            .lock()
+            .unwrap()
+            .insert(trader.id, trader.clone());
+    }
+
+    fn with_requisites(&self, trader: Trader) -> Trader {
+        let requisites = self
+            .requisites
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.trader_id == trader.id)
+            .cloned()
+            .collect();
+        Trader {
+            requisites,
+            ..trader
+        }
+    }
+}
+
+#[async_trait]
+impl TraderRepository for InMemoryTraderRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Trader>, AppError> {
+        let trader = self.traders.lock().unwrap().get(&id).cloned();
+        Ok(trader.map(|t| self.with_requisites(t)))
+    }
+
+    async fn list(
+        &self,
+        filter: TraderFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Trader>, AppError> {
+        let mut traders: Vec<Trader> = self
+            .traders
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|t| filter.matches(t))
+            .cloned()
+            .collect();
+        traders.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(traders
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|t| self.with_requisites(t))
+            .collect())
+    }
+
+    async fn exists_by_name(&self, name: &str) -> Result<bool, AppError> {
+        Ok(self
+            .traders
+            .lock()
+            .unwrap()
+            .values()
+            .any(|t| t.name == name))
+    }
+
+    async fn create(&self, trader: Trader, audit: AuditEntry) -> Result<Trader, AppError> {
+        self.store(&trader);
+        self.audit_log.append(audit);
+        Ok(trader)
+    }
+
+    async fn update(&self, trader: Trader, audit: AuditEntry) -> Result<Trader, AppError> {
+        self.store(&trader);
+        self.audit_log.append(audit);
+        Ok(trader)
+    }
+
+    async fn delete(&self, id: Uuid, audit: AuditEntry) -> Result<(), AppError> {
+        self.traders.lock().unwrap().remove(&id);
+        self.requisites
+            .lock()
+            .unwrap()
+            .retain(|r| r.trader_id != id);
+        self.audit_log.append(audit);
+        Ok(())
+    }
+
+    async fn find_requisite(&self, id: Uuid) -> Result<Option<Requisite>, AppError> {
+        Ok(self
+            .requisites
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|r| r.id == id)
+            .cloned())
+    }
+
+    async fn create_requisite(
+        &self,
+        requisite: Requisite,
+        audit: AuditEntry,
+    ) -> Result<Requisite, AppError> {
+        self.requisites.lock().unwrap().push(requisite.clone());
+        self.audit_log.append(audit);
+        Ok(requisite)
+    }
+
+    async fn update_requisite(
+        &self,
+        requisite: Requisite,
+        audit: AuditEntry,
+    ) -> Result<Requisite, AppError> {
+        let mut requisites = self.requisites.lock().unwrap();
+        if let Some(existing) = requisites.iter_mut().find(|r| r.id == requisite.id) {
+            *existing = requisite.clone();
+        }
+        self.audit_log.append(audit);
+        Ok(requisite)
+    }
+
+    async fn delete_requisite(&self, id: Uuid, audit: AuditEntry) -> Result<(), AppError> {
+        self.requisites.lock().unwrap().retain(|r| r.id != id);
+        self.audit_log.append(audit);
+        Ok(())
+    }
+}
+
+/// Computes candidates from the other in-memory repositories.
+/// Assignments run one at a time, standing in for the trader row lock.
+pub struct InMemoryMatchingRepository {
+    payments: Arc<InMemoryPaymentIntentRepository>,
+    traders: Arc<InMemoryTraderRepository>,
+    ledger: Arc<InMemoryLedgerRepository>,
+    assign_lock: Mutex<()>,
+}
+
+impl InMemoryMatchingRepository {
+    pub fn new(
+        payments: Arc<InMemoryPaymentIntentRepository>,
+        traders: Arc<InMemoryTraderRepository>,
+        ledger: Arc<InMemoryLedgerRepository>,
+    ) -> Self {
+        Self {
+            payments,
+            traders,
+            ledger,
+            assign_lock: Mutex::new(()),
+        }
+    }
+
+    fn candidates(&self, intent: &PaymentIntent, at: DateTime<Utc>) -> Vec<MatchCandidate> {
+        let currency = intent.amount.currency();
+        let day_start = at.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let month_start = day_start.with_day(1).unwrap();
+        let money = |minor_units| Money::new(minor_units, currency);
+
+        let online: Vec<Uuid> = self
+            .traders
+            .traders
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|t| t.availability == TraderAvailability::Online)
+            .map(|t| t.id)
+            .collect();
+        let requisites: Vec<Requisite> = self
+            .traders
+            .requisites
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.is_active && online.contains(&r.trader_id))
+            .filter(|r| r.daily_limit.currency() == currency)
+            .filter(|r| intent.payment_method.is_none_or(|kind| r.kind == kind))
+            .cloned()
+            .collect();
+        let intents: Vec<PaymentIntent> = self
+            .payments
+            .intents
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|i| i.assignment.is_some())
+            .cloned()
+            .collect();
+
+        let settled: Vec<Uuid> = self
+            .ledger
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.kind == EntryKind::Settlement && e.effective_at <= at)
+            .filter_map(|e| e.reference_id)
+            .collect();
+
+        requisites
+            .into_iter()
+            .map(|r| {
+                let of_trader = || {
+                    intents
+                        .iter()
+                        .filter(|i| i.assignment.unwrap().trader_id == r.trader_id)
+                };
+                let used_since = |since: DateTime<Utc>| {
+                    intents
+                        .iter()
+                        .filter(|i| {
+                            let assignment = i.assignment.unwrap();
+                            assignment.requisite_id == r.id
+                                && assignment.assigned_at >= since
+                                && matches!(
+                                    i.status,
+                                    PaymentStatus::Pending
+                                        | PaymentStatus::Confirming
+                                        | PaymentStatus::Paid
+                                )
+                        })
+                        .map(|i| i.amount.minor_units())
+                        .sum()
+                };
+                let count = |statuses: &[PaymentStatus]| {
+                    of_trader().filter(|i| statuses.contains(&i.status)).count() as i64
+                };
+                let deposit = self
+                    .ledger
+                    .accounts
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|a| {
+                        a.kind == AccountKind::Trader
+                            && a.owner_id == Some(r.trader_id)
+                            && a.currency == currency
+                    })
+                    .map(|a| a.id)
+                    .map_or(0, |account_id| {
+                        -self
+                            .ledger
+                            .entries
+                            .lock()
+                            .unwrap()
+                            .iter()
+                            .filter(|e| e.effective_at <= at)
+                            .flat_map(|e| e.postings.iter())
+                            .filter(|p| p.account_id == account_id)
+                            .map(|p| p.amount.minor_units())
+                            .sum::<i64>()
+                    });
+
+                MatchCandidate {
+                    trader_id: r.trader_id,
+                    requisite_id: r.id,
+                    daily_limit: r.daily_limit,
+                    monthly_limit: r.monthly_limit,
+                    daily_used: money(used_since(day_start)),
+                    monthly_used: money(used_since(month_start)),
+                    deposit: money(deposit),
+                    exposure: money(
+                        of_trader()
+                            .filter(|i| {
+                                i.amount.currency() == currency
+                                    && match i.status {
+                                        PaymentStatus::Pending | PaymentStatus::Confirming => true,
+                                        PaymentStatus::Paid => !settled.contains(&i.id),
+                                        _ => false,
+                                    }
+                            })
+                            .map(|i| i.amount.minor_units())
+                            .sum(),
+                    ),
+                    open_payments: count(&[PaymentStatus::Pending, PaymentStatus::Confirming]),
+                    paid_payments: count(&[PaymentStatus::Paid]),
+                    failed_payments: count(&[PaymentStatus::Failed, PaymentStatus::Expired]),
+                    last_assigned_at: of_trader().map(|i| i.assignment.unwrap().assigned_at).max(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl MatchingRepository for InMemoryMatchingRepository {
+    async fn find_candidates(
+        &self,
+        intent: &PaymentIntent,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<MatchCandidate>, AppError> {
+        Ok(self.candidates(intent, at))
+    }
+
+    async fn assign(
+        &self,
+        intent_id: Uuid,
+        candidate: &MatchCandidate,
+        at: DateTime<Utc>,
+    ) -> Result<Option<PaymentIntent>, AppError> {
+        let _guard = self.assign_lock.lock().unwrap();
+
+        let Some(mut intent) = self
+            .payments
+            .intents
+            .lock()
+            .unwrap()
+            .get(&intent_id)
+            .cloned()
+        else {
+            return Err(AppError::NotFound(format!(
+                "Payment {} not found",
+                intent_id
+            )));
+        };
+        let fits = self.candidates(&intent, at).into_iter().any(|current| {
+            current.requisite_id == candidate.requisite_id && current.can_take(intent.amount)
+        });
+        if !fits {
+            return Ok(None);
+        }
+
+        intent.assign(candidate.trader_id, candidate.requisite_id, at)?;
+        self.payments.queue_webhook(&intent, at)?;
+        self.payments
+            .intents
+            .lock()
+            .unwrap()
+            .insert(intent.id, intent.clone());
+        Ok(Some(intent))
+    }
+}
+
+#[derive(Default)]
+pub struct RecordingMailer {
+    pub sent: Mutex<Vec<MailMessage>>,
+    /// While set, sending never finishes, leaving the request in flight
+    pub stalled: AtomicBool,
+}
+
+impl RecordingMailer {
+    /// Last 6-digit code mailed to `email`
+    pub fn last_code_for(&self, email: &str) -> Option<String> {
+        self.sent
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|m| m.to == email)
+            .and_then(|m| {
+                m.body
+                    .split(|c: char| !c.is_ascii_digit())
+                    .find(|part| part.len() == 6)
+                    .map(str::to_string)
+            })
+    }
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, message: MailMessage) -> Result<(), AppError> {
+        if self.stalled.load(Ordering::SeqCst) {
+            std::future::pending::<()>().await;
+        }
+        self.sent.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+/// Entries in chain order
+#[derive(Default)]
+pub struct InMemoryAuditLogRepository {
+    pub entries: Mutex<Vec<AuditEntry>>,
+}
+
+impl InMemoryAuditLogRepository {
+    /// Links the entry to the last one, like the Postgres repository
+    pub fn append(&self, mut entry: AuditEntry) {
+        let mut entries = self.entries.lock().unwrap();
+        entry.link(&Self::head_of(&entries));
+        entries.push(entry);
+    }
+
+    fn head_of(entries: &[AuditEntry]) -> ChainHead {
+        entries
+            .last()
+            .map(|entry| ChainHead {
+                sequence: entry.sequence,
+                hash: entry.hash.clone(),
+            })
+            .unwrap_or_else(ChainHead::genesis)
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for InMemoryAuditLogRepository {
+    async fn list(
+        &self,
+        filter: AuditFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        let mut entries: Vec<AuditEntry> = self
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| filter.matches(e))
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+        Ok(entries
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn head(&self) -> Result<ChainHead, AppError> {
+        Ok(Self::head_of(&self.entries.lock().unwrap()))
+    }
+
+    async fn list_chain(
+        &self,
+        after_sequence: i64,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>, AppError> {
+        let mut entries: Vec<AuditEntry> = self
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.sequence > after_sequence)
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| e.sequence);
+        entries.truncate(limit as usize);
+        Ok(entries)
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryAuditCheckpointRepository {
+    pub checkpoints: Mutex<Vec<AuditCheckpoint>>,
+}
+
+#[async_trait]
+impl AuditCheckpointRepository for InMemoryAuditCheckpointRepository {
+    async fn create(&self, checkpoint: AuditCheckpoint) -> Result<AuditCheckpoint, AppError> {
+        self.checkpoints.lock().unwrap().push(checkpoint.clone());
+        Ok(checkpoint)
+    }
+
+    async fn list(&self, limit: i64, offset: i64) -> Result<Vec<AuditCheckpoint>, AppError> {
+        let mut checkpoints = self.find_all().await?;
+        checkpoints.reverse();
+        Ok(checkpoints
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn find_all(&self) -> Result<Vec<AuditCheckpoint>, AppError> {
+        let mut checkpoints = self.checkpoints.lock().unwrap().clone();
+        checkpoints.sort_by_key(|c| c.sequence);
+        Ok(checkpoints)
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryIdempotencyKeyRepository {
+    pub keys: Mutex<HashMap<(String, String), IdempotencyKey>>,
+}
+
+#[async_trait]
+impl IdempotencyKeyRepository for InMemoryIdempotencyKeyRepository {
+    async fn reserve(&self, key: IdempotencyKey) -> Result<Option<IdempotencyKey>, AppError> {
+        let mut keys = self.keys.lock().unwrap();
+        let id = (key.scope.clone(), key.key.clone());
+
+        if let Some(existing) = keys.get(&id) {
+            if !existing.is_expired_at(key.created_at) && !existing.is_abandoned_at(key.created_at)
+            {
+                return Ok(Some(existing.clone()));
+            }
+        }
+
+        keys.insert(id, key);
+        Ok(None)
+    }
+
+    async fn complete(
+        &self,
+        scope: &str,
+        key: &str,
+        reserved_at: DateTime<Utc>,
+        response_status: i32,
+        response_body: String,
+    ) -> Result<(), AppError> {
+        if let Some(existing) = self
+            .keys
+            .lock()
+            .unwrap()
+            .get_mut(&(scope.to_string(), key.to_string()))
+            .filter(|existing| existing.created_at == reserved_at)
+        {
+            existing.response_status = Some(response_status);
+            existing.response_body = Some(response_body);
+        }
+        Ok(())
+    }
+
+    async fn release(
+        &self,
+        scope: &str,
+        key: &str,
+        reserved_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let mut keys = self.keys.lock().unwrap();
+        let id = (scope.to_string(), key.to_string());
+        if keys
+            .get(&id)
+            .is_some_and(|existing| existing.created_at == reserved_at)
+        {
+            keys.remove(&id);
+        }
+        Ok(())
+    }
+
+    async fn delete_expired(&self, at: DateTime<Utc>) -> Result<u64, AppError> {
+        let mut keys = self.keys.lock().unwrap();
+        let before = keys.len();
+        keys.retain(|_, key| !key.is_expired_at(at));
+        Ok((before - keys.len()) as u64)
+    }
+}
+
+/// Full application router backed by in-memory repositories
+pub struct TestApp {
+    pub router: Router,
+    pub state: Arc<AppState>,
+    pub users: Arc<InMemoryUserRepository>,
+    pub merchants: Arc<InMemoryMerchantRepository>,
+    pub payments: Arc<InMemoryPaymentIntentRepository>,
+    pub ledger: Arc<InMemoryLedgerRepository>,
+    pub traders: Arc<InMemoryTraderRepository>,
+    pub webhooks: Arc<InMemoryWebhookRepository>,
+    pub idempotency_keys: Arc<InMemoryIdempotencyKeyRepository>,
+    pub mailer: Arc<RecordingMailer>,
+    pub audit_checkpoints: Arc<InMemoryAuditCheckpointRepository>,
+    pub password_reset_tokens: Arc<InMemoryPasswordResetTokenRepository>,
+    /// Uploaded files; removed together with the app
+    _storage: TempDir,
+}
+
+/// Active merchant with one site and an issued API key
+pub struct MerchantSite {
+    pub merchant_id: String,
+    pub site_id: String,
+    pub credentials_uri: String,
+    pub public_key: String,
+    pub secret_key: String,
+}
+
+impl TestApp {
+    pub fn new() -> Self {
+        let users = Arc::new(InMemoryUserRepository::default());
+        let merchants = Arc::new(InMemoryMerchantRepository::new(users.audit_log.clone()));
+        let webhooks = Arc::new(InMemoryWebhookRepository::new(users.audit_log.clone()));
+        let ledger = Arc::new(InMemoryLedgerRepository::new(users.audit_log.clone()));
+        let payments = Arc::new(InMemoryPaymentIntentRepository::new(
+            webhooks.clone(),
+            ledger.clone(),
+            users.audit_log.clone(),
+        ));
+        let traders = Arc::new(InMemoryTraderRepository::new(users.audit_log.clone()));
+        let idempotency_keys = Arc::new(InMemoryIdempotencyKeyRepository::default());
+        let mailer = Arc::new(RecordingMailer::default());
+        let audit_checkpoints = Arc::new(InMemoryAuditCheckpointRepository::default());
+        let password_reset_tokens = Arc::new(InMemoryPasswordResetTokenRepository::default());
+
+        // Anything not replaced here fails fast with a connection error
+        let mut repositories = Repositories::postgres(DatabaseConnection::default());
+        repositories.user_repository = users.clone();
+        repositories.role_repository = Arc::new(InMemoryRoleRepository::new(users.clone()));
+        repositories.login_challenge_repository =
+            Arc::new(InMemoryLoginChallengeRepository::default());
+        repositories.token_revocation_repository =
+            Arc::new(InMemoryTokenRevocationRepository::new());
+        repositories.refresh_token_repository =
+            Arc::new(InMemoryRefreshTokenRepository::new(users.audit_log.clone()));
+        repositories.password_reset_token_repository = password_reset_tokens.clone();
+        repositories.idempotency_key_repository = idempotency_keys.clone();
+        repositories.merchant_repository = merchants.clone();
+        repositories.site_repository =
+            Arc::new(InMemorySiteRepository::new(users.audit_log.clone()));
+        repositories.site_credentials_repository = Arc::new(
+            InMemorySiteCredentialsRepository::new(users.audit_log.clone()),
+        );
+        repositories.payment_intent_repository = payments.clone();
+        repositories.payment_evidence_repository =
+            Arc::new(InMemoryPaymentEvidenceRepository::default());
+        repositories.ledger_repository = ledger.clone();
+        repositories.trader_repository = traders.clone();
+        repositories.dispute_repository = Arc::new(InMemoryDisputeRepository::new(
+            ledger.clone(),
+            users.audit_log.clone(),
+        ));
+        repositories.webhook_repository = webhooks.clone();
+        repositories.audit_log_repository = users.audit_log.clone();
+        repositories.audit_checkpoint_repository = audit_checkpoints.clone();
+        repositories.matching_repository = Arc::new(InMemoryMatchingRepository::new(
+            payments.clone(),
+            traders.clone(),
+            ledger.clone(),
+        ));
+
+        let storage = TempDir::with_prefix("p2p-payment-test-").expect("temporary storage");
+        let config = test_config(storage.path());
+        let password_policy = PasswordPolicy::from_config(&config.password).unwrap();
+        let state = Arc::new(AppState::with_repositories(
+            config,
+            repositories,
+            mailer.clone(),
+            password_policy,
+        ));
+
+        Self {
+            router: create_app(Arc::clone(&state)),
+            state,
+            users,
+            merchants,
+            payments,
+            ledger,
+            traders,
+            webhooks,
+            idempotency_keys,
+            mailer,
+            audit_checkpoints,
+            password_reset_tokens,
+            _storage: storage,
+        }
+    }
+
+    pub async fn create_user(&self, username: &str, role_id: Uuid) -> User {
+        let role = seeded_roles()
+            .into_iter()
+            .find(|r| r.role_id == role_id)
+            .expect("seeded role");
+
+        let user = User::new(
+            username.to_string(),
+            format!("{}@example.com", username),
+            hash_password(TEST_PASSWORD).unwrap(),
+            role,
+        );
+        self.users.record_password(user.id, &user.password_hash);
+
+        self.users.insert(user)
+    }
+
+    /// Runs the two-step login and returns the `data` of the verify response
+    pub async fn login(&self, user: &User) -> Value {
+        let (status, body) = self
+            .post(
+                "/api/v1/auth/login",
+                None,
+                serde_json::json!({ "login": user.username, "password": TEST_PASSWORD }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK);
+        let challenge_id = body["data"]["challenge_id"].clone();
+        let code = self.mailer.last_code_for(&user.email).expect("code mailed");
+
+        let (status, body) = self
+            .post(
+                "/api/v1/auth/verify",
+                None,
+                serde_json::json!({ "challenge_id": challenge_id, "code": code }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK);
+
+        body["data"].clone()
+    }
+
+    /// Onboards `name` through the admin API up to an active merchant with a signing key
+    pub async fn onboard_merchant(&self, token: &str, name: &str) -> MerchantSite {
+        let slug = name.to_lowercase();
+
+        let (_, body) = self
+            .post("/api/v1/merchant", Some(token), json!({ "name": name }))
+            .await;
+        let merchant_id = body["data"]["id"].as_str().unwrap().to_string();
+
+        let (_, body) = self
+            .post(
+                &format!("/api/v1/merchant/{}/site", merchant_id),
+                Some(token),
+                json!({
+                    "name": format!("{} Shop", name),
+                    "url": format!("https://shop.{}.com", slug),
+                    "callback_url": format!("https://shop.{}.com/callback", slug),
+                    "redirect_success_url": format!("https://shop.{}.com/ok", slug),
+                    "redirect_fail_url": format!("https://shop.{}.com/fail", slug),
+                }),
+            )
+            .await;
+        let site_id = body["data"]["id"].as_str().unwrap().to_string();
+
+        let credentials_uri = format!(
+            "/api/v1/merchant/{}/site/{}/credentials",
+            merchant_id, site_id
+        );
+        let (_, body) = self.post(&credentials_uri, Some(token), json!({})).await;
+        let public_key = body["data"]["credentials"]["public_key"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let secret_key = body["data"]["secret_key"].as_str().unwrap().to_string();
+
+        // Test requests carry no client address, so the key is opted out of the allowlist
+        let credentials_id = body["data"]["credentials"]["id"].as_str().unwrap();
+        let (status, _) = self
+            .send(build_request(
+                "PUT",
+                &format!("{}/{}/allowed-ips", credentials_uri, credentials_id),
+                Some(token),
+                Some(json!({ "allowed_ips": [], "allow_any_ip": true })),
+            ))
+            .await;
+        assert_eq!(status, StatusCode::OK);
+
+        self.post(
+            &format!("/api/v1/merchant/{}/kyb", merchant_id),
+            Some(token),
+            json!({}),
+        )
+        .await;
+        self.merchants
+            .with_active_credentials
+            .lock()
+            .unwrap()
+            .insert(Uuid::parse_str(&merchant_id).unwrap());
+        let (status, _) = self
+            .patch(
+                &format!("/api/v1/merchant/{}/status", merchant_id),
+                Some(token),
+                json!({ "status": "active" }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK);
+
+        MerchantSite {
+            merchant_id,
+            site_id,
+            credentials_uri,
+            public_key,
+            secret_key,
+        }
+    }
+
+    /// Sends a merchant API request signed with the site's key
+    pub async fn signed(
+        &self,
+        site: &MerchantSite,
+        method: &str,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        self.send(signed_request(
+            method,
+            uri,
+            &site.public_key,
+            &site.secret_key,
+            body,
+        ))
+        .await
+    }
+
+    /// Sends a signed merchant API request with a raw body, e.g. a multipart upload
+    pub async fn signed_raw(
+        &self,
+        site: &MerchantSite,
+        method: &str,
+        uri: &str,
+        content_type: &str,
+        body: Vec<u8>,
+    ) -> (StatusCode, Value) {
+        let timestamp = Utc::now().timestamp();
+        let payload = request_signature::signing_payload(timestamp, method, uri, &body);
+
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(API_KEY_HEADER, &site.public_key)
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                request_signature::sign(&site.secret_key, &payload),
+            )
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body))
+            .unwrap();
+
+        self.send(request).await
+    }
+
+    /// GET returning the raw response, for non-JSON bodies
+    pub async fn get_raw(
+        &self,
+        uri: &str,
+        token: &str,
+    ) -> (StatusCode, axum::http::HeaderMap, Vec<u8>) {
+        let response = self
+            .router
+            .clone()
+            .oneshot(build_request("GET", uri, Some(token), None))
+            .await
+            .unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+
+        (status, headers, bytes.to_vec())
+    }
+
+    pub fn token_for(&self, user: &User) -> String {
+        let claims = self
+            .state
+            .jwt_service
+            .access_claims(user.id, user.role.role_id, user.role.role_name.clone())
+            .with_permissions(seeded_permissions(user.role.role_id));
+
+        self.state.jwt_service.encode_claims(&claims).unwrap()
+    }
+
+    pub async fn send(&self, request: Request<Body>) -> (StatusCode, Value) {
+        let response = self.router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+
+        (status, body)
+    }
+
+    pub async fn get(&self, uri: &str, token: Option<&str>) -> (StatusCode, Value) {
+        self.send(build_request("GET", uri, token, None)).await
+    }
+
+    pub async fn post(&self, uri: &str, token: Option<&str>, body: Value) -> (StatusCode, Value) {
+        self.send(build_request("POST", uri, token, Some(body)))
+            .await
+    }
+
+    pub async fn patch(&self, uri: &str, token: Option<&str>, body: Value) -> (StatusCode, Value) {
+        self.send(build_request("PATCH", uri, token, Some(body)))
+            .await
+    }
+
+    pub async fn delete(&self, uri: &str, token: Option<&str>) -> (StatusCode, Value) {
+        self.send(build_request("DELETE", uri, token, None)).await
+    }
+
+    /// POST of a body built by `multipart_file`
+    pub async fn post_multipart(
+        &self,
+        uri: &str,
+        token: &str,
+        (content_type, body): (String, Vec<u8>),
+    ) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("X-JWT-Token", token)
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body))
+            .unwrap();
+
+        self.send(request).await
+    }
+}
+
+pub fn build_request(
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> Request<Body> {
+    let mut builder = Request::builder().method(method).uri(uri);
+
+    if let Some(token) = token {
+        builder = builder.header("X-JWT-Token", token);
+    }
+
+    match body {
+        Some(body) => builder
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+        None => builder.body(Body::empty()).unwrap(),
+    }
+}
+
+/// `multipart/form-data` body with a single `file` part; returns the content type and body
+pub fn multipart_file(file_name: &str, content_type: &str, content: &[u8]) -> (String, Vec<u8>) {
+    let boundary = format!("boundary-{}", Uuid::new_v4().simple());
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+        boundary, file_name, content_type
+    )
+    .into_bytes();
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    (format!("multipart/form-data; boundary={}", boundary), body)
+}
+
+/// Merchant API request signed with `secret_key` at the current time
+pub fn signed_request(
+    method: &str,
+    uri: &str,
+    public_key: &str,
+    secret_key: &str,
+    body: Option<Value>,
+) -> Request<Body> {
+    signed_request_at(
+        method,
+        uri,
+        public_key,
+        secret_key,
+        Utc::now().timestamp(),
+        body,
+    )
+}
+
+pub fn signed_request_at(
+    method: &str,
+    uri: &str,
+    public_key: &str,
+    secret_key: &str,
+    timestamp: i64,
+    body: Option<Value>,
+) -> Request<Body> {
+    let body = body.map(|body| body.to_string()).unwrap_or_default();
+    let payload = request_signature::signing_payload(timestamp, method, uri, body.as_bytes());
+
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(API_KEY_HEADER, public_key)
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(
+            SIGNATURE_HEADER,
+            request_signature::sign(secret_key, &payload),
+        )
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
@@ -0,0 +1,299 @@
+mod common;
+
+use axum::http::{header, StatusCode};
+use chrono::{Duration, Utc};
+use common::{build_request, TestApp, TEST_PASSWORD};
+use p2p_payment::common::request_signature;
+use p2p_payment::domains::backoffice::role::{
+    admin_role_id, risk_role_id, support_role_id, user_role_id,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+/// Creates `dave` as `token`; returns the new user's ID
+async fn create_dave(app: &TestApp, token: &str) -> String {
+    let (status, body) = app
+        .post(
+            "/api/v1/user",
+            Some(token),
+            json!({
+                "username": "dave",
+                "email": "dave@example.com",
+                "password": TEST_PASSWORD,
+                "role_id": user_role_id()
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    body["data"]["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_user_changes_are_audited() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let user_id = create_dave(&app, &token).await;
+
+    let mut request = build_request(
+        "PATCH",
+        &format!("/api/v1/user/{}", user_id),
+        Some(&token),
+        Some(json!({ "email": "d@example.com" })),
+    );
+    request
+        .headers_mut()
+        .insert("X-Request-Id", "req-42".parse().unwrap());
+    request
+        .headers_mut()
+        .insert(header::USER_AGENT, "backoffice-ui/1.0".parse().unwrap());
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-request-id"], "req-42");
+
+    // Rejected changes leave no entry
+    let (status, _) = app
+        .patch(
+            &format!("/api/v1/user/{}", user_id),
+            Some(&token),
+            json!({ "username": "admin" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = app
+        .delete(&format!("/api/v1/user/{}", user_id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app
+        .get(
+            &format!("/api/v1/audit?entity_type=user&entity_id={}", user_id),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body["data"].as_array().unwrap();
+    let actions: Vec<&str> = entries
+        .iter()
+        .map(|e| e["action"].as_str().unwrap())
+        .collect();
+    assert_eq!(actions, ["delete", "update", "create"]);
+    assert!(entries
+        .iter()
+        .all(|e| e["actor_id"] == admin.id.to_string() && e["entity_id"] == user_id));
+
+    let update = &entries[1];
+    assert_eq!(
+        update["changes"],
+        json!({ "email": { "before": "dave@example.com", "after": "d@example.com" } })
+    );
+    assert_eq!(update["request_id"], "req-42");
+    assert_eq!(update["user_agent"], "backoffice-ui/1.0");
+
+    let created = &entries[2]["changes"];
+    assert_eq!(created["username"]["after"], "dave");
+    assert_eq!(created["role_id"]["after"], user_role_id().to_string());
+    assert!(created.get("password_hash").is_none());
+    // Every request gets an ID even when the client sends none
+    assert!(entries[2]["request_id"].as_str().is_some());
+
+    assert_eq!(entries[0]["changes"]["email"]["before"], "d@example.com");
+    assert_eq!(entries[0]["changes"]["email"]["after"], Value::Null);
+}
+
+#[tokio::test]
+async fn test_audit_filters_and_user_activity_feed() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let other_admin = app.create_user("admin2", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let user_id = create_dave(&app, &token).await;
+    let (status, _) = app
+        .patch(
+            &format!("/api/v1/user/{}", user_id),
+            Some(&app.token_for(&other_admin)),
+            json!({ "username": "david" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = app
+        .get(
+            &format!("/api/v1/audit?actor_id={}", other_admin.id),
+            Some(&token),
+        )
+        .await;
+    let entries = body["data"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["action"], "update");
+
+    let (_, body) = app
+        .get(&format!("/api/v1/user/{}/activity", admin.id), Some(&token))
+        .await;
+    let entries = body["data"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["action"], "create");
+
+    // UTC with a `Z` suffix needs no escaping in the query string
+    let hour_ago = (Utc::now() - Duration::hours(1))
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string();
+    let (_, body) = app
+        .get(&format!("/api/v1/audit?to={}", hour_ago), Some(&token))
+        .await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 0);
+    let (_, body) = app
+        .get(&format!("/api/v1/audit?from={}", hour_ago), Some(&token))
+        .await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+
+    let (status, _) = app.get("/api/v1/audit?limit=101", Some(&token)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_audit_routes_require_permission() {
+    let app = TestApp::new();
+    let support = app.create_user("support", support_role_id()).await;
+    let risk = app.create_user("risk", risk_role_id()).await;
+
+    let (status, _) = app
+        .get("/api/v1/audit", Some(&app.token_for(&support)))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = app
+        .get(
+            &format!("/api/v1/user/{}/activity", support.id),
+            Some(&app.token_for(&support)),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = app.get("/api/v1/audit", Some(&app.token_for(&risk))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!([]));
+}
+
+#[tokio::test]
+async fn test_verify_reports_first_edited_entry() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let user_id = create_dave(&app, &token).await;
+    let (status, _) = app
+        .patch(
+            &format!("/api/v1/user/{}", user_id),
+            Some(&token),
+            json!({ "email": "d@example.com" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app.get("/api/v1/audit/verify", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["valid"], true);
+    assert_eq!(body["data"]["entries_checked"], 2);
+    assert_eq!(body["data"]["head"]["sequence"], 2);
+    assert_eq!(body["data"]["broken_link"], Value::Null);
+
+    let (_, body) = app.get("/api/v1/audit", Some(&token)).await;
+    let newest = &body["data"][0];
+    let oldest = &body["data"][1];
+    assert_eq!(oldest["sequence"], 1);
+    assert_eq!(newest["prev_hash"], oldest["hash"]);
+
+    // Someone with database access rewrites the first entry
+    let first_id = {
+        let mut entries = app.users.audit_log.entries.lock().unwrap();
+        entries[0].changes = json!({ "email": { "before": null, "after": "x@example.com" } });
+        entries[0].id
+    };
+
+    let (status, body) = app.get("/api/v1/audit/verify", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["valid"], false);
+    assert_eq!(body["data"]["entries_checked"], 0);
+    assert_eq!(
+        body["data"]["broken_link"],
+        json!({
+            "sequence": 1,
+            "entry_id": first_id,
+            "reason": "Entry content does not match its hash"
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_checkpoints_are_signed_and_detect_truncation() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    create_dave(&app, &token).await;
+
+    let (status, body) = app
+        .post("/api/v1/audit/checkpoints", Some(&token), json!({}))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let checkpoint = body["data"].clone();
+    assert_eq!(checkpoint["sequence"], 1);
+    let created_at =
+        chrono::DateTime::parse_from_rfc3339(checkpoint["created_at"].as_str().unwrap()).unwrap();
+    let payload = format!(
+        "{}\n{}\n{}",
+        checkpoint["sequence"],
+        checkpoint["head_hash"].as_str().unwrap(),
+        created_at.timestamp()
+    );
+    assert!(request_signature::verify(
+        "test_checkpoint_key",
+        &payload,
+        checkpoint["signature"].as_str().unwrap()
+    ));
+
+    // Nothing new was logged, so the same checkpoint comes back
+    let (_, body) = app
+        .post("/api/v1/audit/checkpoints", Some(&token), json!({}))
+        .await;
+    assert_eq!(body["data"]["id"], checkpoint["id"]);
+
+    let (status, body) = app.get("/api/v1/audit/checkpoints", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!([checkpoint]));
+
+    // An emptied log is a valid chain on its own; only the checkpoint shows entries are gone
+    app.users.audit_log.entries.lock().unwrap().clear();
+
+    let (_, body) = app.get("/api/v1/audit/verify", Some(&token)).await;
+    assert_eq!(body["data"]["valid"], false);
+    assert_eq!(body["data"]["checkpoints_checked"], 1);
+    assert_eq!(body["data"]["broken_link"]["sequence"], 1);
+    assert_eq!(
+        body["data"]["broken_link"]["reason"],
+        format!(
+            "Log ends after entry 0 but checkpoint {} covers 1 entries",
+            checkpoint["id"].as_str().unwrap()
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_integrity_routes_require_audit_permission() {
+    let app = TestApp::new();
+    let support = app.create_user("sam", support_role_id()).await;
+    let token = app.token_for(&support);
+
+    let (status, _) = app.get("/api/v1/audit/verify", Some(&token)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = app
+        .post("/api/v1/audit/checkpoints", Some(&token), json!({}))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let risk = app.create_user("rita", risk_role_id()).await;
+    let (status, _) = app
+        .get("/api/v1/audit/checkpoints", Some(&app.token_for(&risk)))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
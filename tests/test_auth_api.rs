@@ -0,0 +1,421 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TEST_PASSWORD};
+use futures::future::join_all;
+use p2p_payment::domains::backoffice::role::{admin_role_id, support_role_id, user_role_id};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_health_is_public() {
+    let app = TestApp::new();
+
+    let (status, body) = app.get("/health", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "Ok");
+}
+
+#[tokio::test]
+async fn test_protected_route_requires_token() {
+    let app = TestApp::new();
+
+    let (status, body) = app.get("/api/v1/user/me", None).await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["status"], 401);
+}
+
+#[tokio::test]
+async fn test_protected_route_rejects_invalid_token() {
+    let app = TestApp::new();
+
+    let (status, _) = app.get("/api/v1/user/me", Some("invalid.token.here")).await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_claims_reach_handler() {
+    let app = TestApp::new();
+    let user = app.create_user("alice", user_role_id()).await;
+    let token = app.token_for(&user);
+
+    let (status, body) = app.get("/api/v1/user/me", Some(&token)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["id"], user.id.to_string());
+    assert_eq!(body["data"]["username"], "alice");
+}
+
+#[tokio::test]
+async fn test_admin_route_forbidden_for_user_role() {
+    let app = TestApp::new();
+    let user = app.create_user("bob", user_role_id()).await;
+    let token = app.token_for(&user);
+
+    let (status, _) = app
+        .post(
+            "/api/v1/user",
+            Some(&token),
+            json!({
+                "username": "mallory",
+                "email": "mallory@example.com",
+                "password": TEST_PASSWORD,
+                "role_id": admin_role_id()
+            }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_admin_can_fetch_user() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let user = app.create_user("carol", user_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let (status, body) = app
+        .get(&format!("/api/v1/user/{}", user.id), Some(&token))
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["username"], "carol");
+}
+
+#[tokio::test]
+async fn test_two_step_login_issues_working_token() {
+    let app = TestApp::new();
+    let user = app.create_user("dave", user_role_id()).await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/auth/login",
+            None,
+            json!({ "login": "dave", "password": TEST_PASSWORD }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let challenge_id = body["data"]["challenge_id"].as_str().unwrap().to_string();
+
+    let code = app.mailer.last_code_for(&user.email).expect("code mailed");
+
+    let (status, body) = app
+        .post(
+            "/api/v1/auth/verify",
+            None,
+            json!({ "challenge_id": challenge_id, "code": code }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let token = body["data"]["access_token"].as_str().unwrap().to_string();
+
+    let (status, body) = app.get("/api/v1/user/me", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["username"], "dave");
+
+    // Challenge is single use
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/verify",
+            None,
+            json!({ "challenge_id": challenge_id, "code": code }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_parallel_guesses_are_capped_by_max_attempts() {
+    let app = TestApp::new();
+    let user = app.create_user("dora", user_role_id()).await;
+
+    let (_, body) = app
+        .post(
+            "/api/v1/auth/login",
+            None,
+            json!({ "login": "dora", "password": TEST_PASSWORD }),
+        )
+        .await;
+    let challenge_id = body["data"]["challenge_id"].as_str().unwrap().to_string();
+    let code = app.mailer.last_code_for(&user.email).expect("code mailed");
+    let wrong_code = if code == "000000" { "111111" } else { "000000" };
+
+    // Login codes allow 5 attempts by default
+    let guesses = (0..10).map(|_| {
+        app.post(
+            "/api/v1/auth/verify",
+            None,
+            json!({ "challenge_id": challenge_id, "code": wrong_code }),
+        )
+    });
+    let responses = join_all(guesses).await;
+    let checked = responses
+        .iter()
+        .filter(|(_, body)| body["message"] == "Unauthorized: Invalid confirmation code")
+        .count();
+    assert_eq!(checked, 5);
+
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/verify",
+            None,
+            json!({ "challenge_id": challenge_id, "code": code }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_login_with_wrong_password() {
+    let app = TestApp::new();
+    app.create_user("erin", user_role_id()).await;
+
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/login",
+            None,
+            json!({ "login": "erin", "password": "wrong" }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(app.mailer.sent.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_logout_revokes_token() {
+    let app = TestApp::new();
+    let user = app.create_user("frank", user_role_id()).await;
+    let token = app.token_for(&user);
+    let other_token = app.token_for(&user);
+
+    let (status, _) = app
+        .post("/api/v1/auth/logout", Some(&token), json!({}))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app.get("/api/v1/user/me", Some(&token)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["message"], "Unauthorized: Token has been revoked");
+
+    // Other sessions of the same user stay valid
+    let (status, _) = app.get("/api/v1/user/me", Some(&other_token)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_logout_requires_token() {
+    let app = TestApp::new();
+
+    let (status, _) = app.post("/api/v1/auth/logout", None, json!({})).await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_admin_revokes_all_sessions_of_user() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let user = app.create_user("grace", user_role_id()).await;
+    let admin_token = app.token_for(&admin);
+    let user_token = app.token_for(&user);
+
+    let (status, _) = app
+        .delete(
+            &format!("/api/v1/user/{}/sessions", user.id),
+            Some(&admin_token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app.get("/api/v1/user/me", Some(&user_token)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = app.get("/api/v1/user/me", Some(&admin_token)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = app
+        .get(
+            &format!("/api/v1/audit?entity_type=session&entity_id={}", user.id),
+            Some(&admin_token),
+        )
+        .await;
+    let entries = body["data"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["action"], "update");
+    assert_eq!(entries[0]["actor_id"], admin.id.to_string());
+}
+
+#[tokio::test]
+async fn test_revoke_sessions_is_admin_only() {
+    let app = TestApp::new();
+    let user = app.create_user("heidi", user_role_id()).await;
+    let token = app.token_for(&user);
+
+    let (status, _) = app
+        .delete(&format!("/api/v1/user/{}/sessions", user.id), Some(&token))
+        .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_refresh_rotates_tokens() {
+    let app = TestApp::new();
+    let user = app.create_user("ivan", user_role_id()).await;
+    let tokens = app.login(&user).await;
+    let refresh_token = tokens["refresh_token"].as_str().unwrap();
+
+    let (status, body) = app
+        .post(
+            "/api/v1/auth/refresh",
+            None,
+            json!({ "refresh_token": refresh_token }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(body["data"]["refresh_token"], refresh_token);
+    assert_eq!(body["data"]["expires_in"], 15 * 60);
+
+    let access_token = body["data"]["access_token"].as_str().unwrap();
+    let (status, _) = app.get("/api/v1/user/me", Some(access_token)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_refresh_token_reuse_revokes_session() {
+    let app = TestApp::new();
+    let user = app.create_user("judy", user_role_id()).await;
+    let tokens = app.login(&user).await;
+    let refresh_token = tokens["refresh_token"].as_str().unwrap();
+
+    let (status, body) = app
+        .post(
+            "/api/v1/auth/refresh",
+            None,
+            json!({ "refresh_token": refresh_token }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let rotated = body["data"].clone();
+
+    // Replaying the old token kills the whole session
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/refresh",
+            None,
+            json!({ "refresh_token": refresh_token }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, body) = app
+        .get("/api/v1/user/me", rotated["access_token"].as_str())
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["message"], "Unauthorized: Session has been revoked");
+
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/refresh",
+            None,
+            json!({ "refresh_token": rotated["refresh_token"] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_logout_revokes_refresh_token() {
+    let app = TestApp::new();
+    let user = app.create_user("kate", user_role_id()).await;
+    let tokens = app.login(&user).await;
+
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/logout",
+            tokens["access_token"].as_str(),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/refresh",
+            None,
+            json!({ "refresh_token": tokens["refresh_token"] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let (_, body) = app
+        .get(
+            &format!("/api/v1/audit?entity_type=session&entity_id={}", user.id),
+            Some(&app.token_for(&admin)),
+        )
+        .await;
+    let entries = body["data"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["action"], "delete");
+    assert_eq!(entries[0]["actor_id"], user.id.to_string());
+}
+
+#[tokio::test]
+async fn test_login_embeds_role_permissions() {
+    let app = TestApp::new();
+    let user = app.create_user("leo", support_role_id()).await;
+    let tokens = app.login(&user).await;
+
+    let claims = app
+        .state
+        .jwt_service
+        .decode_token(tokens["access_token"].as_str().unwrap())
+        .unwrap();
+
+    assert!(claims.has_permission("users:read"));
+    assert!(!claims.has_permission("users:write"));
+}
+
+#[tokio::test]
+async fn test_permission_gates_user_routes() {
+    let app = TestApp::new();
+    let support = app.create_user("mia", support_role_id()).await;
+    let other = app.create_user("ned", user_role_id()).await;
+    let token = app.token_for(&support);
+
+    let (status, _) = app
+        .get(&format!("/api/v1/user/{}", other.id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app
+        .delete(&format!("/api/v1/user/{}", other.id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(
+        body["message"],
+        "Forbidden: Missing permission: users:write"
+    );
+}
+
+#[tokio::test]
+async fn test_profile_needs_no_permission() {
+    let app = TestApp::new();
+    let user = app.create_user("olga", user_role_id()).await;
+    let token = app
+        .state
+        .jwt_service
+        .encode_token(user.id, user.role.role_id, user.role.role_name.clone())
+        .unwrap();
+
+    let (status, _) = app.get("/api/v1/user/me", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app.get("/api/v1/user", Some(&token)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
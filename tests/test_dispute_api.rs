@@ -0,0 +1,593 @@
+mod common;
+
+use axum::http::{header, StatusCode};
+use common::{multipart_file, MerchantSite, TestApp};
+use p2p_payment::domains::backoffice::role::{
+    admin_role_id, finance_role_id, risk_role_id, support_role_id,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Online trader with one USD card and a 1000 USD deposit; returns its ID
+async fn online_trader(app: &TestApp, token: &str) -> String {
+    let (_, body) = app
+        .post("/api/v1/trader", Some(token), json!({ "name": "Alice" }))
+        .await;
+    let trader_id = body["data"]["id"].as_str().unwrap().to_string();
+    let trader_uri = format!("/api/v1/trader/{}", trader_id);
+
+    let (status, _) = app
+        .post(
+            &format!("{}/requisite", trader_uri),
+            Some(token),
+            json!({
+                "kind": "card",
+                "number": "4242 4242 4242 4242",
+                "bank_name": "Example Bank",
+                "holder_name": "JANE DOE",
+                "currency": "USD",
+                "daily_limit": "5000",
+                "monthly_limit": "50000"
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .post(
+            &format!("{}/deposit", trader_uri),
+            Some(token),
+            json!({ "amount": "1000", "currency": "USD" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .patch(
+            &format!("{}/availability", trader_uri),
+            Some(token),
+            json!({ "availability": "online" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    trader_id
+}
+
+/// Payment of 100 USD marked as paid and then confirmed or rejected by the trader
+async fn decided_payment(app: &TestApp, token: &str, site: &MerchantSite, confirm: bool) -> String {
+    let (status, body) = app
+        .signed(
+            site,
+            "POST",
+            "/api/v1/gateway/payment",
+            Some(json!({ "external_order_id": "order-1", "amount": "100", "currency": "USD" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let payment_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, _) = app
+        .signed(
+            site,
+            "POST",
+            &format!("/api/v1/gateway/payment/{}/paid", payment_id),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = if confirm {
+        app.post(
+            &format!("/api/v1/payment/{}/confirm", payment_id),
+            Some(token),
+            json!({}),
+        )
+        .await
+    } else {
+        app.post(
+            &format!("/api/v1/payment/{}/reject", payment_id),
+            Some(token),
+            json!({ "reason": "No transfer received" }),
+        )
+        .await
+    };
+    assert_eq!(status, StatusCode::OK);
+
+    payment_id
+}
+
+async fn balance(app: &TestApp, token: &str, kind: &str, owner_id: &str) -> Value {
+    let (_, body) = app
+        .get(
+            &format!("/api/v1/ledger/account?kind={}&owner_id={}", kind, owner_id),
+            Some(token),
+        )
+        .await;
+    let account_id = body["data"][0]["id"].as_str().unwrap();
+
+    let (_, body) = app
+        .get(
+            &format!("/api/v1/ledger/account/{}/balance", account_id),
+            Some(token),
+        )
+        .await;
+    body["data"]["balance"]["amount"].clone()
+}
+
+#[tokio::test]
+async fn test_dispute_resolved_for_customer_refunds_from_merchant() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let support = app.create_user("sam", support_role_id()).await;
+    let risk = app.create_user("rita", risk_role_id()).await;
+    let admin_token = app.token_for(&admin);
+    let support_token = app.token_for(&support);
+    let risk_token = app.token_for(&risk);
+    let site = app.onboard_merchant(&admin_token, "Acme").await;
+    online_trader(&app, &admin_token).await;
+    let payment_id = decided_payment(&app, &admin_token, &site, true).await;
+    assert_eq!(
+        balance(&app, &admin_token, "merchant", &site.merchant_id).await,
+        "98.50"
+    );
+
+    // Support opens the case and hands it to Risk
+    let (status, body) = app
+        .post(
+            "/api/v1/dispute",
+            Some(&support_token),
+            json!({ "payment_id": payment_id, "reason": "Customer chargeback" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "open");
+    assert_eq!(body["data"]["amount"], "100.00");
+    assert_eq!(body["data"]["payment_status"], "paid");
+    let dispute_uri = format!("/api/v1/dispute/{}", body["data"]["id"].as_str().unwrap());
+
+    let (status, _) = app
+        .post(
+            "/api/v1/dispute",
+            Some(&support_token),
+            json!({ "payment_id": payment_id, "reason": "Again" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = app
+        .patch(
+            &format!("{}/assignee", dispute_uri),
+            Some(&support_token),
+            json!({ "assignee_id": risk.id }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["assigned_to"], risk.id.to_string());
+
+    let (_, body) = app
+        .get(
+            &format!("/api/v1/dispute?status=open&assigned_to={}", risk.id),
+            Some(&risk_token),
+        )
+        .await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    let (status, body) = app
+        .post(
+            &format!("{}/investigate", dispute_uri),
+            Some(&risk_token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "investigating");
+
+    // Notes and files collected while investigating
+    let (status, body) = app
+        .post(
+            &format!("{}/comment", dispute_uri),
+            Some(&support_token),
+            json!({ "body": "Bank confirmed the chargeback" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["author_id"], support.id.to_string());
+
+    let (status, body) = app
+        .post_multipart(
+            &format!("{}/attachment", dispute_uri),
+            &support_token,
+            multipart_file("statement.pdf", "application/pdf", b"%PDF statement"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["size_bytes"], 14);
+    let attachment_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, headers, content) = app
+        .get_raw(
+            &format!("{}/attachment/{}", dispute_uri, attachment_id),
+            &risk_token,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content, b"%PDF statement");
+    assert_eq!(headers[header::CONTENT_TYPE], "application/pdf");
+
+    // Only Risk decides cases
+    let resolution = json!({ "resolution": "customer", "note": "Chargeback upheld" });
+    let (status, _) = app
+        .post(
+            &format!("{}/resolve", dispute_uri),
+            Some(&support_token),
+            resolution.clone(),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = app
+        .post(
+            &format!("{}/resolve", dispute_uri),
+            Some(&risk_token),
+            resolution.clone(),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "resolved");
+    assert_eq!(body["data"]["resolution"], "customer");
+    assert_eq!(body["data"]["resolved_by"], risk.id.to_string());
+    let entry_id = body["data"]["adjustment_entry_id"].as_str().unwrap();
+
+    let (_, body) = app
+        .get(
+            &format!("/api/v1/ledger/entry/{}", entry_id),
+            Some(&admin_token),
+        )
+        .await;
+    assert_eq!(body["data"]["kind"], "adjustment");
+    assert_eq!(
+        balance(&app, &admin_token, "merchant", &site.merchant_id).await,
+        "-1.50"
+    );
+
+    // Resolved cases are closed
+    let (status, _) = app
+        .post(
+            &format!("{}/resolve", dispute_uri),
+            Some(&risk_token),
+            resolution,
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = app
+        .post(
+            &format!("{}/comment", dispute_uri),
+            Some(&support_token),
+            json!({ "body": "Late note" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (_, body) = app
+        .get(&format!("{}/comment", dispute_uri), Some(&risk_token))
+        .await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    let dispute_id = dispute_uri.rsplit('/').next().unwrap();
+    let (_, body) = app
+        .get(
+            &format!("/api/v1/audit?entity_type=dispute&entity_id={}", dispute_id),
+            Some(&admin_token),
+        )
+        .await;
+    let entries = body["data"].as_array().unwrap();
+    assert_eq!(entries[0]["action"], "update");
+    assert_eq!(entries[0]["actor_id"], risk.id.to_string());
+    assert_eq!(entries[0]["changes"]["status"]["after"], "resolved");
+    assert_eq!(entries.last().unwrap()["action"], "create");
+}
+
+#[tokio::test]
+async fn test_dispute_on_failed_payment_resolved_for_merchant_charges_trader() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+    let trader_id = online_trader(&app, &token).await;
+    let payment_id = decided_payment(&app, &token, &site, false).await;
+    assert_eq!(balance(&app, &token, "trader", &trader_id).await, "1000.00");
+
+    let (status, body) = app
+        .post(
+            "/api/v1/dispute",
+            Some(&token),
+            json!({ "payment_id": payment_id, "reason": "Customer has a bank receipt" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["trader_id"], trader_id);
+    let dispute_uri = format!("/api/v1/dispute/{}", body["data"]["id"].as_str().unwrap());
+
+    // Cases are investigated before they are decided
+    let resolution = json!({ "resolution": "merchant", "note": "Trader kept the transfer" });
+    let (status, _) = app
+        .post(
+            &format!("{}/resolve", dispute_uri),
+            Some(&token),
+            resolution.clone(),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, body) = app
+        .post(
+            &format!("{}/investigate", dispute_uri),
+            Some(&token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["assigned_to"], admin.id.to_string());
+
+    let (status, _) = app
+        .post(
+            &format!("{}/resolve", dispute_uri),
+            Some(&token),
+            resolution,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert_eq!(balance(&app, &token, "trader", &trader_id).await, "900.00");
+    assert_eq!(
+        balance(&app, &token, "merchant", &site.merchant_id).await,
+        "100.00"
+    );
+}
+
+#[tokio::test]
+async fn test_concurrent_resolutions_post_one_matching_adjustment() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+    let trader_id = online_trader(&app, &token).await;
+    let payment_id = decided_payment(&app, &token, &site, false).await;
+
+    let (_, body) = app
+        .post(
+            "/api/v1/dispute",
+            Some(&token),
+            json!({ "payment_id": payment_id, "reason": "Customer has a bank receipt" }),
+        )
+        .await;
+    let dispute_uri = format!("/api/v1/dispute/{}", body["data"]["id"].as_str().unwrap());
+    let (status, _) = app
+        .post(
+            &format!("{}/investigate", dispute_uri),
+            Some(&token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let resolve_uri = format!("{}/resolve", dispute_uri);
+    let (for_merchant, for_customer) = tokio::join!(
+        app.post(
+            &resolve_uri,
+            Some(&token),
+            json!({ "resolution": "merchant", "note": "Trader kept the transfer" }),
+        ),
+        app.post(
+            &resolve_uri,
+            Some(&token),
+            json!({ "resolution": "customer", "note": "Refund the customer" }),
+        ),
+    );
+    let mut statuses = [for_merchant.0, for_customer.0];
+    statuses.sort();
+    assert_eq!(statuses, [StatusCode::OK, StatusCode::CONFLICT]);
+
+    let (_, dispute) = app.get(&dispute_uri, Some(&token)).await;
+    let (_, entries) = app
+        .get(
+            &format!(
+                "/api/v1/ledger/entry?kind=adjustment&reference_id={}",
+                dispute["data"]["id"].as_str().unwrap()
+            ),
+            Some(&token),
+        )
+        .await;
+    let entries = entries["data"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["id"], dispute["data"]["adjustment_entry_id"]);
+
+    // The trader pays either way; the merchant is credited only if it won
+    assert_eq!(balance(&app, &token, "trader", &trader_id).await, "900.00");
+    if dispute["data"]["resolution"] == "merchant" {
+        assert_eq!(
+            balance(&app, &token, "merchant", &site.merchant_id).await,
+            "100.00"
+        );
+    } else {
+        let (_, accounts) = app
+            .get(
+                &format!(
+                    "/api/v1/ledger/account?kind=merchant&owner_id={}",
+                    site.merchant_id
+                ),
+                Some(&token),
+            )
+            .await;
+        assert_eq!(accounts["data"], json!([]));
+    }
+}
+
+/// Opens, investigates and resolves a dispute; returns the status of the resolution
+async fn settle_dispute(
+    app: &TestApp,
+    token: &str,
+    payment_id: &str,
+    resolution: &str,
+) -> StatusCode {
+    let (status, body) = app
+        .post(
+            "/api/v1/dispute",
+            Some(token),
+            json!({ "payment_id": payment_id, "reason": "Customer chargeback" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let dispute_uri = format!("/api/v1/dispute/{}", body["data"]["id"].as_str().unwrap());
+
+    let (status, _) = app
+        .post(
+            &format!("{}/investigate", dispute_uri),
+            Some(token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .post(
+            &format!("{}/resolve", dispute_uri),
+            Some(token),
+            json!({ "resolution": resolution, "note": "Decided" }),
+        )
+        .await;
+    status
+}
+
+#[tokio::test]
+async fn test_payment_is_refunded_at_most_once() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+    online_trader(&app, &token).await;
+    let payment_id = decided_payment(&app, &token, &site, true).await;
+
+    // Deciding for the merchant moves nothing, the case can be reopened
+    assert_eq!(
+        settle_dispute(&app, &token, &payment_id, "merchant").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        settle_dispute(&app, &token, &payment_id, "customer").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        balance(&app, &token, "merchant", &site.merchant_id).await,
+        "-1.50"
+    );
+
+    // The refund is final
+    let (status, _) = app
+        .post(
+            "/api/v1/dispute",
+            Some(&token),
+            json!({ "payment_id": payment_id, "reason": "Second chargeback" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (_, entries) = app
+        .get("/api/v1/ledger/entry?kind=adjustment", Some(&token))
+        .await;
+    assert_eq!(entries["data"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        balance(&app, &token, "merchant", &site.merchant_id).await,
+        "-1.50"
+    );
+}
+
+#[tokio::test]
+async fn test_disputes_require_permission_and_a_disputable_payment() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let finance = app.create_user("fiona", finance_role_id()).await;
+    let admin_token = app.token_for(&admin);
+    let finance_token = app.token_for(&finance);
+    let site = app.onboard_merchant(&admin_token, "Acme").await;
+
+    // Finance reads disputes but does not work them
+    let (status, body) = app.get("/api/v1/dispute", Some(&finance_token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!([]));
+
+    let (status, _) = app
+        .post(
+            "/api/v1/dispute",
+            Some(&finance_token),
+            json!({ "payment_id": Uuid::new_v4(), "reason": "Chargeback" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Payments still waiting for the customer cannot be disputed
+    let (_, body) = app
+        .signed(
+            &site,
+            "POST",
+            "/api/v1/gateway/payment",
+            Some(json!({ "external_order_id": "order-1", "amount": "100", "currency": "USD" })),
+        )
+        .await;
+    let (status, _) = app
+        .post(
+            "/api/v1/dispute",
+            Some(&admin_token),
+            json!({ "payment_id": body["data"]["id"], "reason": "Chargeback" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    online_trader(&app, &admin_token).await;
+    let (_, body) = app
+        .signed(
+            &site,
+            "POST",
+            "/api/v1/gateway/payment",
+            Some(json!({ "external_order_id": "order-2", "amount": "100", "currency": "USD" })),
+        )
+        .await;
+    let payment_id = body["data"]["id"].as_str().unwrap().to_string();
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/payment/{}/confirm", payment_id),
+            Some(&admin_token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = app
+        .post(
+            "/api/v1/dispute",
+            Some(&admin_token),
+            json!({ "payment_id": payment_id, "reason": "Chargeback" }),
+        )
+        .await;
+    let dispute_uri = format!("/api/v1/dispute/{}", body["data"]["id"].as_str().unwrap());
+
+    // Cases go to users who can work them
+    let (status, _) = app
+        .patch(
+            &format!("{}/assignee", dispute_uri),
+            Some(&admin_token),
+            json!({ "assignee_id": finance.id }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = app
+        .get(
+            &format!("/api/v1/dispute/{}", Uuid::new_v4()),
+            Some(&finance_token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,239 @@
+mod common;
+
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{Request, StatusCode},
+};
+use chrono::Utc;
+use common::{signed_request, signed_request_at, TestApp};
+use p2p_payment::domains::backoffice::role::admin_role_id;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+
+const ME_URI: &str = "/api/v1/gateway/me";
+
+/// Returns the credentials URI of an active merchant's site together with
+/// the issued `(public_key, secret_key)`
+async fn issued_keys(app: &TestApp, token: &str) -> (String, String, String) {
+    let site = app.onboard_merchant(token, "Acme").await;
+
+    (site.credentials_uri, site.public_key, site.secret_key)
+}
+
+/// Pretends the request arrived over a TCP connection from `peer`
+fn from_peer(mut request: Request<Body>, peer: &str) -> Request<Body> {
+    let peer: SocketAddr = format!("{}:40000", peer).parse().unwrap();
+    request.extensions_mut().insert(ConnectInfo(peer));
+    request
+}
+
+fn issued(body: &Value) -> (String, String) {
+    (
+        body["data"]["credentials"]["public_key"]
+            .as_str()
+            .unwrap()
+            .to_string(),
+        body["data"]["secret_key"].as_str().unwrap().to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_signed_request_resolves_site() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let (_, public_key, secret_key) = issued_keys(&app, &token).await;
+
+    let (status, body) = app
+        .send(signed_request(
+            "GET",
+            ME_URI,
+            &public_key,
+            &secret_key,
+            None,
+        ))
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["merchant_name"], "Acme");
+    assert_eq!(body["data"]["site_name"], "Acme Shop");
+    assert_eq!(body["data"]["public_key"], public_key);
+}
+
+#[tokio::test]
+async fn test_rejects_missing_headers_bad_signature_and_stale_timestamp() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let (_, public_key, secret_key) = issued_keys(&app, &token).await;
+
+    // Backoffice JWTs are not accepted on the merchant API
+    let (status, _) = app.get(ME_URI, Some(&token)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = app
+        .send(signed_request("GET", ME_URI, &public_key, "sk_wrong", None))
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = app
+        .send(signed_request(
+            "GET",
+            ME_URI,
+            "pk_unknown",
+            &secret_key,
+            None,
+        ))
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let stale = Utc::now().timestamp() - 10 * 60;
+    let (status, _) = app
+        .send(signed_request_at(
+            "GET",
+            ME_URI,
+            &public_key,
+            &secret_key,
+            stale,
+            None,
+        ))
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_revoked_key_and_inactive_merchant_are_rejected() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let (uri, public_key, secret_key) = issued_keys(&app, &token).await;
+
+    // Rotated keys keep working during the overlap window
+    let (_, body) = app
+        .post(&format!("{}/rotate", uri), Some(&token), json!({}))
+        .await;
+    let (new_public_key, new_secret_key) = issued(&body);
+    let (status, _) = app
+        .send(signed_request(
+            "GET",
+            ME_URI,
+            &public_key,
+            &secret_key,
+            None,
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = app.get(&uri, Some(&token)).await;
+    let old_id = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["public_key"] == public_key.as_str())
+        .unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    app.post(
+        &format!("{}/{}/revoke", uri, old_id),
+        Some(&token),
+        json!({}),
+    )
+    .await;
+    let (status, _) = app
+        .send(signed_request(
+            "GET",
+            ME_URI,
+            &public_key,
+            &secret_key,
+            None,
+        ))
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let merchant_id = uri.split('/').nth(4).unwrap().to_string();
+    let (status, _) = app
+        .patch(
+            &format!("/api/v1/merchant/{}/status", merchant_id),
+            Some(&token),
+            json!({ "status": "inactive", "reason": "Chargeback review" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = app
+        .send(signed_request(
+            "GET",
+            ME_URI,
+            &new_public_key,
+            &new_secret_key,
+            None,
+        ))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_allowlist_rejects_other_client_ips() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let (uri, public_key, secret_key) = issued_keys(&app, &token).await;
+
+    let (_, body) = app.get(&uri, Some(&token)).await;
+    let credentials_id = body["data"][0]["id"].as_str().unwrap().to_string();
+    let (status, body) = app
+        .send(common::build_request(
+            "PUT",
+            &format!("{}/{}/allowed-ips", uri, credentials_id),
+            Some(&token),
+            Some(json!({ "allowed_ips": ["203.0.113.7", "198.51.100.0/24"] })),
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"]["allowed_ips"],
+        json!(["203.0.113.7/32", "198.51.100.0/24"])
+    );
+
+    let request = || signed_request("GET", ME_URI, &public_key, &secret_key, None);
+
+    let (status, _) = app.send(from_peer(request(), "198.51.100.20")).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app.send(from_peer(request(), "192.0.2.1")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Without a known peer address the allowlist cannot be satisfied
+    let (status, _) = app.send(request()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // X-Forwarded-For counts only when set by a trusted proxy
+    let mut forwarded = request();
+    forwarded
+        .headers_mut()
+        .insert("X-Forwarded-For", "203.0.113.7".parse().unwrap());
+    let (status, _) = app.send(from_peer(forwarded, "10.1.2.3")).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let mut spoofed = request();
+    spoofed
+        .headers_mut()
+        .insert("X-Forwarded-For", "203.0.113.7".parse().unwrap());
+    let (status, _) = app.send(from_peer(spoofed, "192.0.2.1")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // An empty allowlist admits nobody unless the key is opted out
+    let (status, body) = app
+        .send(common::build_request(
+            "PUT",
+            &format!("{}/{}/allowed-ips", uri, credentials_id),
+            Some(&token),
+            Some(json!({ "allowed_ips": [] })),
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["allow_any_ip"], false);
+    let (status, _) = app.send(from_peer(request(), "198.51.100.20")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,365 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use common::{build_request, signed_request, TestApp, TEST_PASSWORD};
+use http_body_util::BodyExt;
+use p2p_payment::{
+    common::middleware::{IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER},
+    domains::backoffice::{
+        domain::idempotency_key::IdempotencyKey,
+        role::{admin_role_id, user_role_id},
+        UserRepository,
+    },
+};
+use serde_json::{json, Value};
+use std::sync::atomic::Ordering;
+use tower::ServiceExt;
+
+fn with_key(mut request: Request<Body>, key: &str) -> Request<Body> {
+    request
+        .headers_mut()
+        .insert(IDEMPOTENCY_KEY_HEADER, key.parse().unwrap());
+    request
+}
+
+/// Sends the request; returns the status, body and whether the response was replayed
+async fn send(app: &TestApp, request: Request<Body>) -> (StatusCode, Value, bool) {
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let replayed = response
+        .headers()
+        .get(IDEMPOTENT_REPLAYED_HEADER)
+        .is_some_and(|value| value == "true");
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        replayed,
+    )
+}
+
+fn new_user(username: &str) -> Value {
+    json!({
+        "username": username,
+        "email": format!("{}@example.com", username),
+        "password": TEST_PASSWORD,
+        "role_id": user_role_id()
+    })
+}
+
+#[tokio::test]
+async fn test_retried_create_user_replays_stored_response() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let request = || {
+        with_key(
+            build_request("POST", "/api/v1/user", Some(&token), Some(new_user("dave"))),
+            "create-dave",
+        )
+    };
+
+    let (status, first, replayed) = send(&app, request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!replayed);
+
+    let (status, retry, replayed) = send(&app, request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(replayed);
+    assert_eq!(retry, first);
+
+    // Without the key the same request runs again and trips the uniqueness check
+    let (status, _) = app
+        .post("/api/v1/user", Some(&token), new_user("dave"))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let users = app.users.list(100, 0).await.unwrap();
+    assert_eq!(users.iter().filter(|u| u.username == "dave").count(), 1);
+}
+
+#[tokio::test]
+async fn test_reused_key_with_different_body_is_conflict() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let (status, _, _) = send(
+        &app,
+        with_key(
+            build_request("POST", "/api/v1/user", Some(&token), Some(new_user("dave"))),
+            "key-1",
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body, replayed) = send(
+        &app,
+        with_key(
+            build_request("POST", "/api/v1/user", Some(&token), Some(new_user("erin"))),
+            "key-1",
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(!replayed);
+    assert_eq!(body["status"], 409);
+
+    let (status, _, _) = send(
+        &app,
+        with_key(
+            build_request("POST", "/api/v1/user", Some(&token), Some(new_user("erin"))),
+            &"k".repeat(256),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_failed_requests_and_expired_keys_can_be_retried() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let other_admin = app.create_user("admin2", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    // Error responses below 500 are stored and replayed like any other
+    let (status, _, _) = send(
+        &app,
+        with_key(
+            build_request(
+                "POST",
+                "/api/v1/user",
+                Some(&token),
+                Some(new_user("admin")),
+            ),
+            "key-1",
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _, replayed) = send(
+        &app,
+        with_key(
+            build_request(
+                "POST",
+                "/api/v1/user",
+                Some(&token),
+                Some(new_user("admin")),
+            ),
+            "key-1",
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(replayed);
+
+    // Keys belong to the caller; another user may use the same one
+    let (status, _, replayed) = send(
+        &app,
+        with_key(
+            build_request(
+                "POST",
+                "/api/v1/user",
+                Some(&app.token_for(&other_admin)),
+                Some(new_user("dave")),
+            ),
+            "key-1",
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!replayed);
+
+    for key in app.idempotency_keys.keys.lock().unwrap().values_mut() {
+        key.expires_at = Utc::now() - Duration::minutes(1);
+    }
+
+    // An expired key is reserved anew
+    let (status, _, replayed) = send(
+        &app,
+        with_key(
+            build_request("POST", "/api/v1/user", Some(&token), Some(new_user("erin"))),
+            "key-1",
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!replayed);
+
+    let removed = app
+        .state
+        .idempotency_key_repository
+        .delete_expired(Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(removed, 1);
+}
+
+#[tokio::test]
+async fn test_cancelled_and_abandoned_requests_hold_their_key_until_the_lease_runs_out() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let user = app.create_user("frank", user_role_id()).await;
+    let path = format!("/api/v1/user/{}/password-reset", user.id);
+    let request = |key: &str| {
+        with_key(
+            build_request("POST", &path, Some(&token), Some(json!({}))),
+            key,
+        )
+    };
+    let mails_to_user = || {
+        app.mailer
+            .sent
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| m.to == user.email)
+            .count()
+    };
+    let expire_leases = || {
+        for key in app.idempotency_keys.keys.lock().unwrap().values_mut() {
+            if key.response().is_none() {
+                key.locked_until = Some(Utc::now() - Duration::seconds(1));
+            }
+        }
+    };
+
+    // The client gives up while the reset link is being mailed; the handler may
+    // already have done its work, so a retry must not run it again yet
+    app.mailer.stalled.store(true, Ordering::SeqCst);
+    let cancelled = tokio::time::timeout(
+        std::time::Duration::from_millis(100),
+        send(&app, request("reset-1")),
+    )
+    .await;
+    assert!(cancelled.is_err());
+    app.mailer.stalled.store(false, Ordering::SeqCst);
+
+    let (status, body, _) = send(&app, request("reset-1")).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        body["message"],
+        "Conflict: Request with idempotency key 'reset-1' is still being processed"
+    );
+    assert_eq!(app.idempotency_keys.keys.lock().unwrap().len(), 1);
+
+    expire_leases();
+    let (status, _, replayed) = send(&app, request("reset-1")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!replayed);
+    assert_eq!(mails_to_user(), 1);
+
+    // A request that died without storing a response, e.g. on a server restart
+    let abandoned = IdempotencyKey::new(
+        format!("user:{}", admin.id),
+        "reset-2".to_string(),
+        IdempotencyKey::fingerprint("POST", &path, b"{}"),
+        Duration::hours(24),
+        Duration::minutes(1),
+    );
+    app.idempotency_keys
+        .keys
+        .lock()
+        .unwrap()
+        .insert((abandoned.scope.clone(), abandoned.key.clone()), abandoned);
+
+    let (status, _, _) = send(&app, request("reset-2")).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    expire_leases();
+    let (status, _, replayed) = send(&app, request("reset-2")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!replayed);
+    assert_eq!(mails_to_user(), 2);
+}
+
+#[tokio::test]
+async fn test_request_that_lost_its_key_cannot_overwrite_the_retry() {
+    let app = TestApp::new();
+    let repository = &app.state.idempotency_key_repository;
+    let reservation = |lease| {
+        IdempotencyKey::new(
+            "user:1".to_string(),
+            "key-1".to_string(),
+            "fingerprint".to_string(),
+            Duration::hours(24),
+            lease,
+        )
+    };
+
+    // The original request outlives its lease and a retry takes the key over
+    let slow = reservation(Duration::zero());
+    let slow_reserved_at = slow.created_at;
+    assert!(repository.reserve(slow).await.unwrap().is_none());
+    tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    let retry = reservation(Duration::minutes(1));
+    let retry_reserved_at = retry.created_at;
+    assert!(repository.reserve(retry).await.unwrap().is_none());
+
+    // The slow request finishes last; neither its response nor its release sticks
+    repository
+        .complete("user:1", "key-1", slow_reserved_at, 500, "{}".to_string())
+        .await
+        .unwrap();
+    repository
+        .release("user:1", "key-1", slow_reserved_at)
+        .await
+        .unwrap();
+    let stored = repository
+        .reserve(reservation(Duration::minutes(1)))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.created_at, retry_reserved_at);
+    assert_eq!(stored.response(), None);
+
+    repository
+        .complete("user:1", "key-1", retry_reserved_at, 201, "{}".to_string())
+        .await
+        .unwrap();
+    let stored = repository
+        .reserve(reservation(Duration::minutes(1)))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.response(), Some((201, "{}")));
+}
+
+#[tokio::test]
+async fn test_retried_gateway_payment_is_not_created_twice() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let site = app.onboard_merchant(&app.token_for(&admin), "Acme").await;
+    let payment = json!({ "external_order_id": "order-1", "amount": "100", "currency": "USD" });
+    // Each retry is signed again with a fresh timestamp
+    let request = || {
+        with_key(
+            signed_request(
+                "POST",
+                "/api/v1/gateway/payment",
+                &site.public_key,
+                &site.secret_key,
+                Some(payment.clone()),
+            ),
+            "order-1-attempt",
+        )
+    };
+
+    let (status, first, replayed) = send(&app, request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!replayed);
+
+    let (status, retry, replayed) = send(&app, request()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(replayed);
+    assert_eq!(retry["data"]["id"], first["data"]["id"]);
+    assert_eq!(app.payments.intents.lock().unwrap().len(), 1);
+}
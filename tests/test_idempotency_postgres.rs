@@ -0,0 +1,85 @@
+//! Runs the lease handling of `PostgresIdempotencyKeyRepository` against a real database.
+//! Set `P2P_APP_TEST_DATABASE_URL` to a scratch database and run with `--ignored`;
+//! every key is scoped to a fresh ID, so the database can be reused between runs.
+
+use chrono::Duration;
+use migration::MigratorTrait;
+use p2p_payment::domains::backoffice::{
+    domain::idempotency_key::IdempotencyKey, IdempotencyKeyRepository,
+    PostgresIdempotencyKeyRepository,
+};
+use sea_orm::{Database, DatabaseConnection};
+use uuid::Uuid;
+
+const DATABASE_URL_VAR: &str = "P2P_APP_TEST_DATABASE_URL";
+
+async fn connect() -> DatabaseConnection {
+    let url = std::env::var(DATABASE_URL_VAR)
+        .unwrap_or_else(|_| panic!("{} must point to a scratch database", DATABASE_URL_VAR));
+    let db = Database::connect(url).await.unwrap();
+    migration::Migrator::up(&db, None).await.unwrap();
+    db
+}
+
+#[tokio::test]
+#[ignore = "requires P2P_APP_TEST_DATABASE_URL"]
+async fn test_request_that_lost_its_key_cannot_overwrite_the_retry() {
+    let repository = PostgresIdempotencyKeyRepository::new(connect().await);
+    let scope = format!("user:{}", Uuid::new_v4());
+    let reservation = |lease| {
+        IdempotencyKey::new(
+            scope.clone(),
+            "key-1".to_string(),
+            "fingerprint".to_string(),
+            Duration::hours(24),
+            lease,
+        )
+    };
+
+    // The original request outlives its lease and a retry takes the key over
+    let slow = reservation(Duration::zero());
+    let slow_reserved_at = slow.created_at;
+    assert!(repository.reserve(slow).await.unwrap().is_none());
+    tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    let retry = reservation(Duration::minutes(1));
+    let retry_reserved_at = retry.created_at;
+    assert!(repository.reserve(retry).await.unwrap().is_none());
+
+    // The slow request finishes last; neither its response nor its release sticks
+    repository
+        .complete(&scope, "key-1", slow_reserved_at, 500, "{}".to_string())
+        .await
+        .unwrap();
+    repository
+        .release(&scope, "key-1", slow_reserved_at)
+        .await
+        .unwrap();
+    let stored = repository
+        .reserve(reservation(Duration::minutes(1)))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.created_at, retry_reserved_at);
+    assert_eq!(stored.response(), None);
+
+    repository
+        .complete(&scope, "key-1", retry_reserved_at, 201, "{}".to_string())
+        .await
+        .unwrap();
+    let stored = repository
+        .reserve(reservation(Duration::minutes(1)))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.response(), Some((201, "{}")));
+
+    repository
+        .release(&scope, "key-1", retry_reserved_at)
+        .await
+        .unwrap();
+    assert!(repository
+        .reserve(reservation(Duration::minutes(1)))
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,320 @@
+mod common;
+
+use axum::http::StatusCode;
+use chrono::{SecondsFormat, Utc};
+use common::{MerchantSite, TestApp};
+use futures::future::join_all;
+use p2p_payment::domains::{
+    backoffice::role::{admin_role_id, finance_role_id, support_role_id},
+    payments::domain::payment_intent::PaymentStatus,
+};
+use serde_json::{json, Value};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Creates a payment through the gateway, assigns it to `trader_id`, marks it paid and settles it
+async fn settled_payment(
+    app: &TestApp,
+    site: &MerchantSite,
+    trader_id: Uuid,
+    order: &str,
+    amount: &str,
+) -> Uuid {
+    let (status, body) = app
+        .signed(
+            site,
+            "POST",
+            "/api/v1/gateway/payment",
+            Some(json!({ "external_order_id": order, "amount": amount, "currency": "USD" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let payment_id: Uuid = body["data"]["id"].as_str().unwrap().parse().unwrap();
+
+    let intent = {
+        let mut intents = app.payments.intents.lock().unwrap();
+        let intent = intents.get_mut(&payment_id).unwrap();
+        intent
+            .assign(trader_id, Uuid::new_v4(), Utc::now())
+            .unwrap();
+        intent.transition_to(PaymentStatus::Paid).unwrap();
+        intent.clone()
+    };
+    app.state
+        .ledger_settlement_use_case
+        .execute(&intent)
+        .await
+        .unwrap();
+
+    payment_id
+}
+
+async fn account(app: &TestApp, token: &str, query: &str) -> Value {
+    let (status, body) = app
+        .get(&format!("/api/v1/ledger/account?{}", query), Some(token))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let accounts = body["data"].as_array().unwrap();
+    assert_eq!(accounts.len(), 1, "{}", query);
+    accounts[0].clone()
+}
+
+async fn balance(app: &TestApp, token: &str, account_id: &str, as_of: Option<&str>) -> Value {
+    let uri = match as_of {
+        Some(as_of) => format!(
+            "/api/v1/ledger/account/{}/balance?as_of={}",
+            account_id, as_of
+        ),
+        None => format!("/api/v1/ledger/account/{}/balance", account_id),
+    };
+    let (status, body) = app.get(&uri, Some(token)).await;
+    assert_eq!(status, StatusCode::OK);
+    body["data"]["balance"].clone()
+}
+
+#[tokio::test]
+async fn test_settlement_credits_merchant_net_of_fee() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let finance = app.create_user("fiona", finance_role_id()).await;
+    let token = app.token_for(&finance);
+    let site = app.onboard_merchant(&app.token_for(&admin), "Acme").await;
+
+    let payment_id = settled_payment(&app, &site, Uuid::new_v4(), "order-1", "100.00").await;
+    // Settling again must not post twice
+    let intent = app.payments.intents.lock().unwrap()[&payment_id].clone();
+    app.state
+        .ledger_settlement_use_case
+        .execute(&intent)
+        .await
+        .unwrap();
+
+    let merchant = account(
+        &app,
+        &token,
+        &format!("kind=merchant&owner_id={}", site.merchant_id),
+    )
+    .await;
+    let merchant_account_id = merchant["id"].as_str().unwrap();
+    assert_eq!(merchant["currency"], "USD");
+    assert_eq!(
+        balance(&app, &token, merchant_account_id, None).await,
+        json!({ "amount": "98.50", "currency": "USD" })
+    );
+
+    let fees = account(&app, &token, "kind=fees").await;
+    assert_eq!(
+        balance(&app, &token, fees["id"].as_str().unwrap(), None).await["amount"],
+        "1.50"
+    );
+
+    let (status, body) = app
+        .get(
+            &format!("/api/v1/ledger/entry?reference_id={}", payment_id),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body["data"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["kind"], "fee");
+    assert_eq!(entries[1]["kind"], "settlement");
+    assert_eq!(entries[1]["postings"][0]["amount"], "100.00");
+    assert_eq!(entries[1]["postings"][1]["amount"], "-100.00");
+
+    let entry_id = entries[1]["id"].as_str().unwrap();
+    let (status, body) = app
+        .get(&format!("/api/v1/ledger/entry/{}", entry_id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["reference_id"], payment_id.to_string());
+}
+
+#[tokio::test]
+async fn test_settlement_debits_the_collecting_trader() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let admin_token = app.token_for(&admin);
+    let finance = app.create_user("fiona", finance_role_id()).await;
+    let token = app.token_for(&finance);
+    let site = app.onboard_merchant(&admin_token, "Acme").await;
+
+    let (_, body) = app
+        .post(
+            "/api/v1/trader",
+            Some(&admin_token),
+            json!({ "name": "Alice" }),
+        )
+        .await;
+    let trader_id: Uuid = body["data"]["id"].as_str().unwrap().parse().unwrap();
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/trader/{}/deposit", trader_id),
+            Some(&admin_token),
+            json!({ "amount": "500", "currency": "USD" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    settled_payment(&app, &site, trader_id, "order-1", "200.00").await;
+
+    // The customer paid the trader: the platform received nothing new
+    let treasury = account(&app, &token, "kind=treasury&currency=USD").await;
+    assert_eq!(treasury["owner_id"], Value::Null);
+    assert_eq!(
+        balance(&app, &token, treasury["id"].as_str().unwrap(), None).await["amount"],
+        "500.00"
+    );
+    let trader = account(&app, &token, &format!("kind=trader&owner_id={}", trader_id)).await;
+    assert_eq!(
+        balance(&app, &token, trader["id"].as_str().unwrap(), None).await["amount"],
+        "300.00"
+    );
+    let merchant = account(
+        &app,
+        &token,
+        &format!("kind=merchant&owner_id={}", site.merchant_id),
+    )
+    .await;
+    assert_eq!(
+        balance(&app, &token, merchant["id"].as_str().unwrap(), None).await["amount"],
+        "197.00"
+    );
+}
+
+#[tokio::test]
+async fn test_payout_and_balance_as_of() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let finance = app.create_user("fiona", finance_role_id()).await;
+    let token = app.token_for(&finance);
+    let site = app.onboard_merchant(&app.token_for(&admin), "Acme").await;
+
+    settled_payment(&app, &site, Uuid::new_v4(), "order-1", "100.00").await;
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let before_payout = Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true);
+    tokio::time::sleep(Duration::from_millis(5)).await;
+
+    let (status, body) = app
+        .post(
+            "/api/v1/ledger/payout",
+            Some(&token),
+            json!({
+                "merchant_id": site.merchant_id,
+                "amount": "50",
+                "currency": "usd",
+                "description": "Weekly payout"
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["kind"], "payout");
+    assert_eq!(body["data"]["created_by"], finance.id.to_string());
+
+    let merchant = account(
+        &app,
+        &token,
+        &format!("kind=merchant&owner_id={}", site.merchant_id),
+    )
+    .await;
+    let merchant_account_id = merchant["id"].as_str().unwrap();
+    assert_eq!(
+        balance(&app, &token, merchant_account_id, None).await["amount"],
+        "48.50"
+    );
+    assert_eq!(
+        balance(&app, &token, merchant_account_id, Some(&before_payout)).await["amount"],
+        "98.50"
+    );
+
+    // Cannot pay out more than the balance
+    let (status, _) = app
+        .post(
+            "/api/v1/ledger/payout",
+            Some(&token),
+            json!({ "merchant_id": site.merchant_id, "amount": "48.51", "currency": "USD" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = app
+        .get(
+            &format!("/api/v1/ledger/entry?account_id={}", merchant_account_id),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let kinds: Vec<&str> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, vec!["payout", "fee", "settlement"]);
+}
+
+#[tokio::test]
+async fn test_concurrent_payouts_cannot_overdraw() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let finance = app.create_user("fiona", finance_role_id()).await;
+    let token = app.token_for(&finance);
+    let site = app.onboard_merchant(&app.token_for(&admin), "Acme").await;
+
+    // Balance of 98.50 after the fee: two of three 40.00 payouts fit
+    settled_payment(&app, &site, Uuid::new_v4(), "order-1", "100.00").await;
+
+    let payouts = (0..3).map(|_| {
+        app.post(
+            "/api/v1/ledger/payout",
+            Some(&token),
+            json!({ "merchant_id": site.merchant_id, "amount": "40", "currency": "USD" }),
+        )
+    });
+    let statuses: Vec<StatusCode> = join_all(payouts)
+        .await
+        .into_iter()
+        .map(|(status, _)| status)
+        .collect();
+    assert_eq!(statuses.iter().filter(|s| **s == StatusCode::OK).count(), 2);
+    assert!(statuses.contains(&StatusCode::BAD_REQUEST));
+
+    let merchant = account(
+        &app,
+        &token,
+        &format!("kind=merchant&owner_id={}", site.merchant_id),
+    )
+    .await;
+    assert_eq!(
+        balance(&app, &token, merchant["id"].as_str().unwrap(), None).await["amount"],
+        "18.50"
+    );
+}
+
+#[tokio::test]
+async fn test_ledger_routes_require_permission() {
+    let app = TestApp::new();
+    let support = app.create_user("sam", support_role_id()).await;
+    let token = app.token_for(&support);
+
+    let (status, _) = app.get("/api/v1/ledger/account", Some(&token)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = app
+        .post(
+            "/api/v1/ledger/payout",
+            Some(&token),
+            json!({ "merchant_id": Uuid::new_v4(), "amount": "1", "currency": "USD" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let finance = app.create_user("fiona", finance_role_id()).await;
+    let (status, _) = app
+        .get(
+            &format!("/api/v1/ledger/entry/{}", Uuid::new_v4()),
+            Some(&app.token_for(&finance)),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
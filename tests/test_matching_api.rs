@@ -0,0 +1,185 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{MerchantSite, TestApp};
+use futures::future::join_all;
+use p2p_payment::domains::backoffice::role::admin_role_id;
+use serde_json::{json, Value};
+
+/// Online trader with one USD card (5000 a day) and a deposit
+async fn online_trader(app: &TestApp, token: &str, name: &str, deposit: &str) -> String {
+    let (status, body) = app
+        .post("/api/v1/trader", Some(token), json!({ "name": name }))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let trader_id = body["data"]["id"].as_str().unwrap().to_string();
+    let trader_uri = format!("/api/v1/trader/{}", trader_id);
+
+    let (status, _) = app
+        .post(
+            &format!("{}/requisite", trader_uri),
+            Some(token),
+            json!({
+                "kind": "card",
+                "number": "4242 4242 4242 4242",
+                "bank_name": "Example Bank",
+                "holder_name": "JANE DOE",
+                "currency": "USD",
+                "daily_limit": "5000",
+                "monthly_limit": "50000"
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .post(
+            &format!("{}/deposit", trader_uri),
+            Some(token),
+            json!({ "amount": deposit, "currency": "USD" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .patch(
+            &format!("{}/availability", trader_uri),
+            Some(token),
+            json!({ "availability": "online" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    trader_id
+}
+
+async fn create_payment(app: &TestApp, site: &MerchantSite, payment: Value) -> Value {
+    let (status, body) = app
+        .signed(site, "POST", "/api/v1/gateway/payment", Some(payment))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    body["data"].clone()
+}
+
+fn payment(order: &str, amount: &str) -> Value {
+    json!({ "external_order_id": order, "amount": amount, "currency": "USD" })
+}
+
+#[tokio::test]
+async fn test_payment_is_matched_to_an_online_trader() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+    let trader_id = online_trader(&app, &token, "Alice", "1000").await;
+
+    let matched = create_payment(&app, &site, payment("order-1", "100")).await;
+    assert_eq!(matched["status"], "pending");
+    assert_eq!(matched["trader_id"], trader_id);
+    assert_eq!(matched["pay_to"]["kind"], "card");
+    assert_eq!(matched["pay_to"]["number"], "4242424242424242");
+    assert_eq!(matched["pay_to"]["holder_name"], "JANE DOE");
+
+    let (status, body) = app
+        .signed(
+            &site,
+            "GET",
+            &format!(
+                "/api/v1/gateway/payment/{}",
+                matched["id"].as_str().unwrap()
+            ),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["pay_to"], matched["pay_to"]);
+
+    // 900 of the deposit is left
+    let waiting = create_payment(&app, &site, payment("order-2", "950")).await;
+    assert_eq!(waiting["status"], "created");
+    assert_eq!(waiting["trader_id"], Value::Null);
+    assert!(waiting.get("pay_to").is_none());
+
+    // No trader takes bank transfers
+    let waiting = create_payment(
+        &app,
+        &site,
+        json!({
+            "external_order_id": "order-3",
+            "amount": "10",
+            "currency": "USD",
+            "payment_method": "account"
+        }),
+    )
+    .await;
+    assert_eq!(waiting["status"], "created");
+
+    let (status, body) = app
+        .get(
+            &format!("/api/v1/payment?trader_id={}", trader_id),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let payments = body["data"].as_array().unwrap();
+    assert_eq!(payments.len(), 1);
+    assert_eq!(payments[0]["id"], matched["id"]);
+}
+
+#[tokio::test]
+async fn test_waiting_payments_are_matched_by_the_sweep() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+
+    let waiting = create_payment(&app, &site, payment("order-1", "100")).await;
+    assert_eq!(waiting["status"], "created");
+
+    online_trader(&app, &token, "Alice", "1000").await;
+    let assigned = app
+        .state
+        .payment_intent_assign_use_case
+        .assign_waiting()
+        .await
+        .unwrap();
+    assert_eq!(assigned, 1);
+
+    let (status, body) = app
+        .signed(&site, "GET", "/api/v1/gateway/payment/order/order-1", None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "pending");
+    assert_eq!(body["data"]["pay_to"]["number"], "4242424242424242");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_payments_never_exceed_the_deposit() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+    let alice = online_trader(&app, &token, "Alice", "1000").await;
+    let bob = online_trader(&app, &token, "Bob", "600").await;
+
+    let payments = join_all(
+        (0..10).map(|n| create_payment(&app, &site, payment(&format!("order-{}", n), "300"))),
+    )
+    .await;
+
+    let pending: Vec<&Value> = payments
+        .iter()
+        .filter(|p| p["status"] == "pending")
+        .collect();
+    let taken_by = |trader_id: &str| {
+        pending
+            .iter()
+            .filter(|p| p["trader_id"] == trader_id)
+            .count()
+    };
+
+    // 3 x 300 fit into 1000, 2 x 300 into 600
+    assert_eq!(taken_by(&alice), 3);
+    assert_eq!(taken_by(&bob), 2);
+    assert_eq!(pending.len(), 5);
+}
@@ -0,0 +1,337 @@
+//! Runs the candidate query and assignment of `PostgresMatchingRepository` against a real
+//! database. Set `P2P_APP_TEST_DATABASE_URL` to a scratch database and run with
+//! `--ignored`; migrations are applied and every row is created with fresh IDs, so the
+//! database can be reused between runs.
+
+use chrono::{Duration, Utc};
+use migration::MigratorTrait;
+use p2p_payment::{
+    common::money::{Currency, Money},
+    domains::payments::{
+        domain::payment_intent::PaymentIntent, MatchingRepository, PaymentIntentRepository,
+        PostgresMatchingRepository, PostgresPaymentIntentRepository,
+    },
+};
+use sea_orm::{
+    ConnectionTrait, Database, DatabaseConnection, DbBackend, Statement, TransactionTrait, Value,
+};
+use uuid::Uuid;
+
+const DATABASE_URL_VAR: &str = "P2P_APP_TEST_DATABASE_URL";
+
+async fn connect() -> DatabaseConnection {
+    let url = std::env::var(DATABASE_URL_VAR)
+        .unwrap_or_else(|_| panic!("{} must point to a scratch database", DATABASE_URL_VAR));
+    let db = Database::connect(url).await.unwrap();
+    migration::Migrator::up(&db, None).await.unwrap();
+    db
+}
+
+async fn execute<C: ConnectionTrait>(db: &C, sql: &str, values: Vec<Value>) {
+    db.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        sql,
+        values,
+    ))
+    .await
+    .unwrap();
+}
+
+/// Ledger entry moving `amount` USD cents from `credit` to `debit`
+async fn post_entry(
+    db: &DatabaseConnection,
+    kind: &str,
+    reference_id: Uuid,
+    debit: Uuid,
+    credit: Uuid,
+    amount: i64,
+) {
+    let entry_id = Uuid::new_v4();
+    let now = Utc::now();
+    // Entries are checked for balance at commit, so the postings go in one transaction
+    let txn = db.begin().await.unwrap();
+    execute(
+        &txn,
+        "INSERT INTO ledger_journal_entry (id, kind, reference_id, effective_at, created_at) \
+         VALUES ($1, $2, $3, $4, $4)",
+        vec![
+            entry_id.into(),
+            kind.into(),
+            reference_id.into(),
+            now.into(),
+        ],
+    )
+    .await;
+    for (account_id, amount) in [(debit, amount), (credit, -amount)] {
+        execute(
+            &txn,
+            "INSERT INTO ledger_posting (id, entry_id, account_id, amount, currency, effective_at) \
+             VALUES ($1, $2, $3, $4, 'USD', $5)",
+            vec![
+                Uuid::new_v4().into(),
+                entry_id.into(),
+                account_id.into(),
+                amount.into(),
+                now.into(),
+            ],
+        )
+        .await;
+    }
+    txn.commit().await.unwrap();
+}
+
+async fn create_account(db: &DatabaseConnection, kind: &str, owner_id: Option<Uuid>) -> Uuid {
+    let id = Uuid::new_v4();
+    execute(
+        db,
+        "INSERT INTO ledger_account (id, kind, owner_id, currency, created_at) \
+         VALUES ($1, $2, $3, 'USD', $4)",
+        vec![id.into(), kind.into(), owner_id.into(), Utc::now().into()],
+    )
+    .await;
+    id
+}
+
+/// The USD treasury account, shared by every run
+async fn treasury_account(db: &DatabaseConnection) -> Uuid {
+    execute(
+        db,
+        "INSERT INTO ledger_account (id, kind, owner_id, currency, created_at) \
+         VALUES ($1, 'treasury', NULL, 'USD', $2) ON CONFLICT DO NOTHING",
+        vec![Uuid::new_v4().into(), Utc::now().into()],
+    )
+    .await;
+    db.query_one(Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT id FROM ledger_account WHERE kind = 'treasury' AND owner_id IS NULL \
+         AND currency = 'USD'",
+    ))
+    .await
+    .unwrap()
+    .unwrap()
+    .try_get("", "id")
+    .unwrap()
+}
+
+/// Online trader with one USD card, owned by a fresh merchant and site
+struct Fixture {
+    merchant_id: Uuid,
+    site_id: Uuid,
+    trader_id: Uuid,
+    requisite_id: Uuid,
+    /// The trader's ledger account, holding `deposit` USD cents
+    trader_account: Uuid,
+}
+
+impl Fixture {
+    async fn new(db: &DatabaseConnection, deposit: i64) -> Self {
+        let now = Utc::now();
+        let (merchant_id, site_id, trader_id, requisite_id) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+
+        execute(
+            db,
+            "INSERT INTO merchant (id, name, status, created_at, updated_at) \
+             VALUES ($1, $2, 'active', $3, $3)",
+            vec![
+                merchant_id.into(),
+                format!("Acme {}", merchant_id).into(),
+                now.into(),
+            ],
+        )
+        .await;
+        let site_url = format!("https://{}.example.com", site_id);
+        execute(
+            db,
+            "INSERT INTO site (id, merchant_id, name, url, callback_url, redirect_success_url, \
+             redirect_fail_url, status, created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, $4, $4, $4, 'active', $5, $5)",
+            vec![
+                site_id.into(),
+                merchant_id.into(),
+                format!("Shop {}", site_id).into(),
+                site_url.into(),
+                now.into(),
+            ],
+        )
+        .await;
+        execute(
+            db,
+            "INSERT INTO trader (id, name, availability, availability_changed_at, created_at, \
+             updated_at) VALUES ($1, $2, 'online', $3, $3, $3)",
+            vec![
+                trader_id.into(),
+                format!("Alice {}", trader_id).into(),
+                now.into(),
+            ],
+        )
+        .await;
+        execute(
+            db,
+            "INSERT INTO trader_requisite (id, trader_id, kind, bank_name, holder_name, \
+             masked_number, encrypted_number, currency, daily_limit, monthly_limit, is_active, \
+             created_at, updated_at) VALUES ($1, $2, 'card', 'Example Bank', 'JANE DOE', \
+             '**** 4242', 'unused', 'USD', 500000, 5000000, true, $3, $3)",
+            vec![requisite_id.into(), trader_id.into(), now.into()],
+        )
+        .await;
+
+        let treasury = treasury_account(db).await;
+        let trader_account = create_account(db, "trader", Some(trader_id)).await;
+        post_entry(
+            db,
+            "deposit",
+            Uuid::new_v4(),
+            treasury,
+            trader_account,
+            deposit,
+        )
+        .await;
+
+        Self {
+            merchant_id,
+            site_id,
+            trader_id,
+            requisite_id,
+            trader_account,
+        }
+    }
+
+    fn intent(&self, minor_units: i64) -> PaymentIntent {
+        PaymentIntent::new(
+            self.merchant_id,
+            self.site_id,
+            Uuid::new_v4().to_string(),
+            Money::new(minor_units, Currency::Usd),
+            None,
+            None,
+            Duration::minutes(30),
+        )
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires P2P_APP_TEST_DATABASE_URL"]
+async fn test_paid_payments_count_against_the_deposit_until_settled() {
+    let db = connect().await;
+    let now = Utc::now();
+    // 500 USD deposit
+    let Fixture {
+        merchant_id,
+        site_id,
+        trader_id,
+        requisite_id,
+        trader_account: trader,
+    } = Fixture::new(&db, 50_000).await;
+
+    // 200 USD the trader collected, not settled yet
+    let paid_id = Uuid::new_v4();
+    execute(
+        &db,
+        "INSERT INTO payment_intent (id, merchant_id, site_id, external_order_id, amount, \
+         currency, status, expires_at, created_at, updated_at, trader_id, requisite_id, \
+         assigned_at) VALUES ($1, $2, $3, $4, 20000, 'USD', 'paid', $5, $5, $5, $6, $7, $5)",
+        vec![
+            paid_id.into(),
+            merchant_id.into(),
+            site_id.into(),
+            paid_id.to_string().into(),
+            now.into(),
+            trader_id.into(),
+            requisite_id.into(),
+        ],
+    )
+    .await;
+
+    let repository = PostgresMatchingRepository::new(db.clone());
+    let intent = PaymentIntent::new(
+        merchant_id,
+        site_id,
+        "order-2".to_string(),
+        Money::new(45_000, Currency::Usd),
+        None,
+        None,
+        Duration::minutes(30),
+    );
+    let candidate = |at| {
+        let repository = &repository;
+        let intent = &intent;
+        async move {
+            repository
+                .find_candidates(intent, at)
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|candidate| candidate.requisite_id == requisite_id)
+                .unwrap()
+        }
+    };
+
+    let before = candidate(Utc::now()).await;
+    assert_eq!(before.deposit.minor_units(), 50_000);
+    assert_eq!(before.exposure.minor_units(), 20_000);
+    assert!(!before.can_take(intent.amount));
+    assert!(before.can_take(Money::new(30_000, Currency::Usd)));
+
+    // Settling takes the amount off the deposit instead
+    let merchant = create_account(&db, "merchant", Some(merchant_id)).await;
+    post_entry(&db, "settlement", paid_id, trader, merchant, 20_000).await;
+
+    let after = candidate(Utc::now()).await;
+    assert_eq!(after.deposit.minor_units(), 30_000);
+    assert_eq!(after.exposure.minor_units(), 0);
+    assert!(!after.can_take(intent.amount));
+    assert!(after.can_take(Money::new(30_000, Currency::Usd)));
+}
+
+#[tokio::test]
+#[ignore = "requires P2P_APP_TEST_DATABASE_URL"]
+async fn test_concurrent_assignments_do_not_overcommit_the_deposit() {
+    let db = connect().await;
+    // 500 USD deposit, two payments of 300 USD
+    let fixture = Fixture::new(&db, 50_000).await;
+    let payments = PostgresPaymentIntentRepository::new(db.clone());
+    let first = payments.create(fixture.intent(30_000)).await.unwrap();
+    let second = payments.create(fixture.intent(30_000)).await.unwrap();
+
+    let repository = PostgresMatchingRepository::new(db.clone());
+    let now = Utc::now();
+    let candidate = repository
+        .find_candidates(&first, now)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|candidate| candidate.requisite_id == fixture.requisite_id)
+        .unwrap();
+    assert!(candidate.can_take(first.amount));
+
+    let (first, second) = tokio::join!(
+        repository.assign(first.id, &candidate, now),
+        repository.assign(second.id, &candidate, now),
+    );
+    let assigned: Vec<PaymentIntent> = [first.unwrap(), second.unwrap()]
+        .into_iter()
+        .flatten()
+        .collect();
+    assert_eq!(assigned.len(), 1);
+    assert_eq!(
+        assigned[0]
+            .assignment
+            .map(|assignment| assignment.trader_id),
+        Some(fixture.trader_id)
+    );
+
+    let after = repository
+        .find_candidates(&fixture.intent(1), Utc::now())
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|candidate| candidate.requisite_id == fixture.requisite_id)
+        .unwrap();
+    assert_eq!(after.exposure.minor_units(), 30_000);
+    assert!(after.exposure.minor_units() <= after.deposit.minor_units());
+}
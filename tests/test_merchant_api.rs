@@ -0,0 +1,243 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use p2p_payment::domains::backoffice::role::{admin_role_id, support_role_id};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_create_and_fetch_merchant() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let (status, body) = app
+        .post(
+            "/api/v1/merchant",
+            Some(&token),
+            json!({ "name": "Acme", "description": "Electronics" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "onboarding");
+    assert_eq!(body["data"]["sites"], json!([]));
+    let merchant_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, body) = app
+        .get(&format!("/api/v1/merchant/{}", merchant_id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["name"], "Acme");
+
+    let (status, _) = app
+        .post("/api/v1/merchant", Some(&token), json!({ "name": "Acme" }))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_update_merchant_and_reject_illegal_transition() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let (_, body) = app
+        .post("/api/v1/merchant", Some(&token), json!({ "name": "Acme" }))
+        .await;
+    let merchant_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, body) = app
+        .patch(
+            &format!("/api/v1/merchant/{}", merchant_id),
+            Some(&token),
+            json!({ "name": "Acme Group" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["name"], "Acme Group");
+
+    let (status, body) = app
+        .patch(
+            &format!("/api/v1/merchant/{}/status", merchant_id),
+            Some(&token),
+            json!({ "status": "inactive", "reason": "Closed" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["data"], json!(null));
+}
+
+#[tokio::test]
+async fn test_merchant_changes_are_audited() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let (_, body) = app
+        .post("/api/v1/merchant", Some(&token), json!({ "name": "Acme" }))
+        .await;
+    let merchant_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    app.patch(
+        &format!("/api/v1/merchant/{}", merchant_id),
+        Some(&token),
+        json!({ "name": "Acme Group" }),
+    )
+    .await;
+    let (status, _) = app
+        .patch(
+            &format!("/api/v1/merchant/{}/status", merchant_id),
+            Some(&token),
+            json!({ "status": "inactive", "reason": "Closed" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, body) = app
+        .get(
+            &format!(
+                "/api/v1/audit?entity_type=merchant&entity_id={}",
+                merchant_id
+            ),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body["data"].as_array().unwrap();
+    let actions: Vec<&str> = entries
+        .iter()
+        .map(|e| e["action"].as_str().unwrap())
+        .collect();
+    assert_eq!(actions, ["update", "create"]);
+    assert!(entries
+        .iter()
+        .all(|e| e["actor_id"] == admin.id.to_string()));
+    assert_eq!(
+        entries[0]["changes"]["name"],
+        json!({ "before": "Acme", "after": "Acme Group" })
+    );
+}
+
+#[tokio::test]
+async fn test_merchant_lifecycle_records_history() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let (_, body) = app
+        .post("/api/v1/merchant", Some(&token), json!({ "name": "Acme" }))
+        .await;
+    let merchant_id = body["data"]["id"].as_str().unwrap().to_string();
+    let status_uri = format!("/api/v1/merchant/{}/status", merchant_id);
+
+    // KYB is not done yet
+    let (status, _) = app
+        .patch(&status_uri, Some(&token), json!({ "status": "active" }))
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, body) = app
+        .post(
+            &format!("/api/v1/merchant/{}/kyb", merchant_id),
+            Some(&token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    // Same format as the other timestamps of the response
+    let kyb_verified_at = body["data"]["kyb_verified_at"].as_str().unwrap();
+    assert!(kyb_verified_at.ends_with("+00:00"));
+    assert_eq!(
+        kyb_verified_at.len(),
+        body["data"]["created_at"].as_str().unwrap().len()
+    );
+
+    // Still no active site with credentials
+    let (status, _) = app
+        .patch(&status_uri, Some(&token), json!({ "status": "active" }))
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    app.merchants
+        .with_active_credentials
+        .lock()
+        .unwrap()
+        .insert(merchant_id.parse().unwrap());
+
+    let (status, body) = app
+        .patch(&status_uri, Some(&token), json!({ "status": "active" }))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "active");
+
+    let (status, _) = app
+        .patch(&status_uri, Some(&token), json!({ "status": "inactive" }))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = app
+        .patch(
+            &status_uri,
+            Some(&token),
+            json!({ "status": "inactive", "reason": "Chargeback ratio" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app
+        .get(
+            &format!("/api/v1/merchant/{}/status-history", merchant_id),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let history = body["data"].as_array().unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0]["from_status"], "active");
+    assert_eq!(history[0]["to_status"], "inactive");
+    assert_eq!(history[0]["reason"], "Chargeback ratio");
+    assert_eq!(history[0]["changed_by"], admin.id.to_string());
+    assert_eq!(history[1]["from_status"], "onboarding");
+}
+
+#[tokio::test]
+async fn test_list_and_search_merchants() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    for name in ["Beta Shop", "Acme", "Acme Travel"] {
+        app.post("/api/v1/merchant", Some(&token), json!({ "name": name }))
+            .await;
+    }
+
+    let (status, body) = app.get("/api/v1/merchant?limit=2", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    let names: Vec<_> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["name"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(names, vec!["Acme", "Acme Travel"]);
+
+    let (status, body) = app.get("/api/v1/merchant?search=Shop", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(body["data"][0]["name"], "Beta Shop");
+}
+
+#[tokio::test]
+async fn test_merchant_write_requires_permission() {
+    let app = TestApp::new();
+    let support = app.create_user("sam", support_role_id()).await;
+    let token = app.token_for(&support);
+
+    let (status, _) = app.get("/api/v1/merchant", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .post("/api/v1/merchant", Some(&token), json!({ "name": "Acme" }))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,335 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TEST_PASSWORD};
+use p2p_payment::domains::backoffice::role::{admin_role_id, support_role_id, user_role_id};
+use serde_json::json;
+
+/// Token of the last reset link mailed to `email`
+fn reset_token_for(app: &TestApp, email: &str) -> String {
+    let sent = app.mailer.sent.lock().unwrap();
+    let message = sent
+        .iter()
+        .rev()
+        .find(|m| m.to == email && m.subject == "Reset your password")
+        .expect("reset link mailed");
+    let (_, rest) = message
+        .body
+        .split_once("https://backoffice.test/reset-password?token=")
+        .expect("link in body");
+
+    rest.chars().take_while(char::is_ascii_hexdigit).collect()
+}
+
+#[tokio::test]
+async fn test_user_changes_own_password() {
+    let app = TestApp::new();
+    let user = app.create_user("trent", user_role_id()).await;
+    let token = app.token_for(&user);
+    let change =
+        |current: &str, new: &str| json!({ "current_password": current, "new_password": new });
+
+    let (status, body) = app
+        .post(
+            "/api/v1/user/me/password",
+            Some(&token),
+            change("Wrong-Password-1", "Brand-New-Password-1"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["message"],
+        "Validation error: Current password is incorrect"
+    );
+
+    let (status, body) = app
+        .post(
+            "/api/v1/user/me/password",
+            Some(&token),
+            change(TEST_PASSWORD, "tooshort"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["message"],
+        "Validation error: Password must be at least 12 characters long, \
+         contain an uppercase letter, contain a digit"
+    );
+
+    let (status, body) = app
+        .post(
+            "/api/v1/user/me/password",
+            Some(&token),
+            change(TEST_PASSWORD, TEST_PASSWORD),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["message"],
+        "Validation error: Password must differ from the last 3 passwords"
+    );
+
+    let (status, _) = app
+        .post(
+            "/api/v1/user/me/password",
+            Some(&token),
+            change(TEST_PASSWORD, "Brand-New-Password-1"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let login = |password: &str| json!({ "login": "trent", "password": password });
+    let (status, _) = app
+        .post("/api/v1/auth/login", None, login(TEST_PASSWORD))
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = app
+        .post("/api/v1/auth/login", None, login("Brand-New-Password-1"))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The session used for the change keeps working
+    let (status, _) = app.get("/api/v1/user/me", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let stored = app.users.stored(user.id).unwrap();
+    let entries = app.users.audit_log.entries.lock().unwrap();
+    let entry = entries.last().unwrap();
+    assert_eq!(entry.actor_id, Some(user.id));
+    assert_eq!(
+        entry.changes,
+        json!({ "password": { "before": null, "after": "changed" } })
+    );
+    assert!(!entry.changes.to_string().contains(&stored.password_hash));
+}
+
+#[tokio::test]
+async fn test_only_the_latest_passwords_are_remembered() {
+    let app = TestApp::new();
+    let user = app.create_user("ursula", user_role_id()).await;
+    let token = app.token_for(&user);
+
+    // The test policy remembers 3 passwords: the initial one drops out after 3 changes
+    let passwords = [
+        TEST_PASSWORD,
+        "Second-Password-2",
+        "Third-Password-3",
+        "Fourth-Password-4",
+    ];
+    for pair in passwords.windows(2) {
+        let (status, _) = app
+            .post(
+                "/api/v1/user/me/password",
+                Some(&token),
+                json!({ "current_password": pair[0], "new_password": pair[1] }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, _) = app
+        .post(
+            "/api/v1/user/me/password",
+            Some(&token),
+            json!({ "current_password": passwords[3], "new_password": passwords[1] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = app
+        .post(
+            "/api/v1/user/me/password",
+            Some(&token),
+            json!({ "current_password": passwords[3], "new_password": passwords[0] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_new_users_need_a_policy_password() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let new_user = |password: &str| {
+        json!({
+            "username": "victor",
+            "email": "victor@example.com",
+            "password": password,
+            "role_id": user_role_id()
+        })
+    };
+
+    let (status, body) = app
+        .post("/api/v1/user", Some(&token), new_user("alllowercase12"))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["message"],
+        "Validation error: Password must contain an uppercase letter"
+    );
+
+    let (status, body) = app
+        .post("/api/v1/user", Some(&token), new_user("Victor-Password-1"))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The initial password already counts as used
+    let victor = app
+        .users
+        .stored(body["data"]["id"].as_str().unwrap().parse().unwrap())
+        .unwrap();
+    let (status, _) = app
+        .post(
+            "/api/v1/user/me/password",
+            Some(&app.token_for(&victor)),
+            json!({
+                "current_password": "Victor-Password-1",
+                "new_password": "Victor-Password-1"
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_admin_reset_link_sets_password_once() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let admin_token = app.token_for(&admin);
+    let user = app.create_user("wendy", user_role_id()).await;
+    let user_token = app.token_for(&user);
+    let reset_path = format!("/api/v1/user/{}/password-reset", user.id);
+
+    let support = app.create_user("sam", support_role_id()).await;
+    let (status, _) = app
+        .post(&reset_path, Some(&app.token_for(&support)), json!({}))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = app.post(&reset_path, Some(&admin_token), json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]["expires_at"].is_string());
+    let first_token = reset_token_for(&app, &user.email);
+
+    // A second link replaces the first one
+    let (status, _) = app.post(&reset_path, Some(&admin_token), json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    let reset_token = reset_token_for(&app, &user.email);
+    assert_ne!(first_token, reset_token);
+
+    let reset = |token: &str, password: &str| json!({ "token": token, "new_password": password });
+    let (status, body) = app
+        .post(
+            "/api/v1/auth/password-reset",
+            None,
+            reset(&first_token, "Wendy-Password-1"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        body["message"],
+        "Unauthorized: Invalid or expired reset link"
+    );
+
+    // A rejected password does not spend the link
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/password-reset",
+            None,
+            reset(&reset_token, TEST_PASSWORD),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/password-reset",
+            None,
+            reset(&reset_token, "Wendy-Password-1"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/password-reset",
+            None,
+            reset(&reset_token, "Wendy-Password-2"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Signed out everywhere; the new password works
+    let (status, _) = app.get("/api/v1/user/me", Some(&user_token)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/login",
+            None,
+            json!({ "login": "wendy", "password": "Wendy-Password-1" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let entries = app.users.audit_log.entries.lock().unwrap();
+    let entry = entries
+        .iter()
+        .rfind(|entry| entry.entity_type == "user")
+        .unwrap();
+    assert_eq!(entry.actor_id, Some(user.id));
+    assert_eq!(
+        entry.changes,
+        json!({ "password": { "before": null, "after": "reset" } })
+    );
+    let sessions = entries.last().unwrap();
+    assert_eq!(sessions.entity_type, "session");
+    assert_eq!(sessions.entity_id, user.id);
+}
+
+#[tokio::test]
+async fn test_reset_links_expire_and_skip_inactive_users() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let admin_token = app.token_for(&admin);
+    let user = app.create_user("xavier", user_role_id()).await;
+    let reset_path = format!("/api/v1/user/{}/password-reset", user.id);
+
+    let (status, _) = app.post(&reset_path, Some(&admin_token), json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    let reset_token = reset_token_for(&app, &user.email);
+
+    for token in app
+        .password_reset_tokens
+        .tokens
+        .lock()
+        .unwrap()
+        .values_mut()
+    {
+        token.expires_at = chrono::Utc::now() - chrono::Duration::seconds(1);
+    }
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/password-reset",
+            None,
+            json!({ "token": reset_token, "new_password": "Xavier-Password-1" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let mut stored = user.clone();
+    stored.deactivate();
+    app.users.insert(stored);
+
+    let (status, _) = app.post(&reset_path, Some(&admin_token), json!({})).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/user/{}/password-reset", uuid::Uuid::new_v4()),
+            Some(&admin_token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
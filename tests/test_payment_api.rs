@@ -0,0 +1,222 @@
+mod common;
+
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use common::TestApp;
+use p2p_payment::domains::backoffice::role::{admin_role_id, support_role_id, user_role_id};
+use serde_json::json;
+use uuid::Uuid;
+
+fn payment(order: &str, amount: &str) -> serde_json::Value {
+    json!({
+        "external_order_id": order,
+        "amount": amount,
+        "currency": "usd",
+        "description": "Order",
+    })
+}
+
+#[tokio::test]
+async fn test_merchant_creates_and_fetches_payment() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let site = app.onboard_merchant(&app.token_for(&admin), "Acme").await;
+
+    let (status, body) = app
+        .signed(
+            &site,
+            "POST",
+            "/api/v1/gateway/payment",
+            Some(payment("order-1", "1500.00")),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "created");
+    assert_eq!(body["data"]["currency"], "USD");
+    assert_eq!(body["data"]["site_id"], site.site_id);
+    let payment_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, body) = app
+        .signed(
+            &site,
+            "GET",
+            &format!("/api/v1/gateway/payment/{}", payment_id),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["amount"], "1500.00");
+
+    let (status, body) = app
+        .signed(&site, "GET", "/api/v1/gateway/payment/order/order-1", None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["id"], payment_id);
+
+    // One payment per order
+    let (status, _) = app
+        .signed(
+            &site,
+            "POST",
+            "/api/v1/gateway/payment",
+            Some(payment("order-1", "1")),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_merchant_cannot_see_other_site_payments() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let acme = app.onboard_merchant(&token, "Acme").await;
+    let globex = app.onboard_merchant(&token, "Globex").await;
+
+    let (_, body) = app
+        .signed(
+            &acme,
+            "POST",
+            "/api/v1/gateway/payment",
+            Some(payment("order-1", "10")),
+        )
+        .await;
+    let payment_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, _) = app
+        .signed(
+            &globex,
+            "GET",
+            &format!("/api/v1/gateway/payment/{}", payment_id),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Order references are scoped to the site
+    let (status, _) = app
+        .signed(
+            &globex,
+            "POST",
+            "/api/v1/gateway/payment",
+            Some(payment("order-1", "10")),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_backoffice_lists_and_filters_payments() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let acme = app.onboard_merchant(&token, "Acme").await;
+    let globex = app.onboard_merchant(&token, "Globex").await;
+
+    for (site, order) in [(&acme, "a-1"), (&acme, "a-2"), (&globex, "g-1")] {
+        let (status, _) = app
+            .signed(
+                site,
+                "POST",
+                "/api/v1/gateway/payment",
+                Some(payment(order, "10")),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let support = app.create_user("support", support_role_id()).await;
+    let support_token = app.token_for(&support);
+
+    let (status, body) = app.get("/api/v1/payment", Some(&support_token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 3);
+
+    let (_, body) = app
+        .get(
+            &format!("/api/v1/payment?merchant_id={}", acme.merchant_id),
+            Some(&support_token),
+        )
+        .await;
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+
+    let (_, body) = app
+        .get(
+            &format!(
+                "/api/v1/payment?site_id={}&external_order_id=g-1&status=created",
+                globex.site_id
+            ),
+            Some(&support_token),
+        )
+        .await;
+    let payments = body["data"].as_array().unwrap();
+    assert_eq!(payments.len(), 1);
+    let payment_id = payments[0]["id"].as_str().unwrap().to_string();
+
+    let (_, body) = app
+        .get("/api/v1/payment?status=paid", Some(&support_token))
+        .await;
+    assert!(body["data"].as_array().unwrap().is_empty());
+
+    let (status, body) = app
+        .get(
+            &format!("/api/v1/payment/{}", payment_id),
+            Some(&support_token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["external_order_id"], "g-1");
+
+    let (status, _) = app
+        .get(&format!("/api/v1/payment/{}", Uuid::new_v4()), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let user = app.create_user("viewer", user_role_id()).await;
+    let (status, _) = app
+        .get("/api/v1/payment", Some(&app.token_for(&user)))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_overdue_payment_reads_as_expired() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+
+    let (_, body) = app
+        .signed(
+            &site,
+            "POST",
+            "/api/v1/gateway/payment",
+            Some(payment("order-1", "10")),
+        )
+        .await;
+    let payment_id = Uuid::parse_str(body["data"]["id"].as_str().unwrap()).unwrap();
+
+    app.payments
+        .intents
+        .lock()
+        .unwrap()
+        .get_mut(&payment_id)
+        .unwrap()
+        .expires_at = Utc::now() - Duration::minutes(1);
+
+    let (_, body) = app
+        .get("/api/v1/payment?status=created", Some(&token))
+        .await;
+    assert!(body["data"].as_array().unwrap().is_empty());
+
+    let (status, body) = app
+        .signed(
+            &site,
+            "GET",
+            &format!("/api/v1/gateway/payment/{}", payment_id),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "expired");
+}
@@ -0,0 +1,402 @@
+mod common;
+
+use axum::http::{header, StatusCode};
+use chrono::{Duration, Utc};
+use common::{multipart_file, MerchantSite, TestApp};
+use p2p_payment::domains::backoffice::role::{admin_role_id, finance_role_id, support_role_id};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Online trader with one USD card and a deposit large enough for the tests
+async fn online_trader(app: &TestApp, token: &str) {
+    let (_, body) = app
+        .post("/api/v1/trader", Some(token), json!({ "name": "Alice" }))
+        .await;
+    let trader_uri = format!("/api/v1/trader/{}", body["data"]["id"].as_str().unwrap());
+
+    let (status, _) = app
+        .post(
+            &format!("{}/requisite", trader_uri),
+            Some(token),
+            json!({
+                "kind": "card",
+                "number": "4242 4242 4242 4242",
+                "bank_name": "Example Bank",
+                "holder_name": "JANE DOE",
+                "currency": "USD",
+                "daily_limit": "5000",
+                "monthly_limit": "50000"
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .post(
+            &format!("{}/deposit", trader_uri),
+            Some(token),
+            json!({ "amount": "1000", "currency": "USD" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .patch(
+            &format!("{}/availability", trader_uri),
+            Some(token),
+            json!({ "availability": "online" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+/// Matched payment of 100 USD, marked as paid by the merchant
+async fn marked_payment(app: &TestApp, site: &MerchantSite, order: &str) -> String {
+    let (status, body) = app
+        .signed(
+            site,
+            "POST",
+            "/api/v1/gateway/payment",
+            Some(json!({ "external_order_id": order, "amount": "100", "currency": "USD" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "pending");
+    let payment_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, body) = app
+        .signed(
+            site,
+            "POST",
+            &format!("/api/v1/gateway/payment/{}/paid", payment_id),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "confirming");
+    assert!(body["data"]["marked_paid_at"].is_string());
+
+    payment_id
+}
+
+async fn merchant_balance(app: &TestApp, token: &str, site: &MerchantSite) -> Value {
+    let (_, body) = app
+        .get(
+            &format!(
+                "/api/v1/ledger/account?kind=merchant&owner_id={}",
+                site.merchant_id
+            ),
+            Some(token),
+        )
+        .await;
+    let Some(account) = body["data"].as_array().unwrap().first() else {
+        return Value::Null;
+    };
+
+    let (_, body) = app
+        .get(
+            &format!(
+                "/api/v1/ledger/account/{}/balance",
+                account["id"].as_str().unwrap()
+            ),
+            Some(token),
+        )
+        .await;
+    body["data"]["balance"]["amount"].clone()
+}
+
+#[tokio::test]
+async fn test_confirmed_payment_with_evidence_is_settled() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+    online_trader(&app, &token).await;
+    let payment_id = marked_payment(&app, &site, "order-1").await;
+
+    let (content_type, body) = multipart_file("receipt.png", "image/png", b"\x89PNG receipt");
+    let (status, body) = app
+        .signed_raw(
+            &site,
+            "POST",
+            &format!("/api/v1/gateway/payment/{}/evidence", payment_id),
+            &content_type,
+            body,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["file_name"], "receipt.png");
+    assert_eq!(body["data"]["size_bytes"], 12);
+    let evidence_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, body) = app
+        .get(
+            &format!("/api/v1/payment/{}/evidence", payment_id),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    let (status, headers, content) = app
+        .get_raw(
+            &format!("/api/v1/payment/{}/evidence/{}", payment_id, evidence_id),
+            &token,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content, b"\x89PNG receipt");
+    assert_eq!(headers[header::CONTENT_TYPE], "image/png");
+    assert_eq!(
+        headers[header::CONTENT_DISPOSITION],
+        "attachment; filename=\"receipt.png\""
+    );
+
+    // Evidence is scoped to its payment
+    let (status, _, _) = app
+        .get_raw(
+            &format!(
+                "/api/v1/payment/{}/evidence/{}",
+                Uuid::new_v4(),
+                evidence_id
+            ),
+            &token,
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    assert_eq!(merchant_balance(&app, &token, &site).await, Value::Null);
+    let (status, body) = app
+        .post(
+            &format!("/api/v1/payment/{}/confirm", payment_id),
+            Some(&token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "paid");
+    assert!(body["data"]["dispute_until"].is_string());
+
+    // 100 less the 1.5% settlement fee
+    assert_eq!(merchant_balance(&app, &token, &site).await, "98.50");
+
+    // Confirming again does not settle twice, and paid payments take no more evidence
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/payment/{}/confirm", payment_id),
+            Some(&token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(merchant_balance(&app, &token, &site).await, "98.50");
+
+    let (content_type, body) = multipart_file("late.png", "image/png", b"png");
+    let (status, _) = app
+        .signed_raw(
+            &site,
+            "POST",
+            &format!("/api/v1/gateway/payment/{}/evidence", payment_id),
+            &content_type,
+            body,
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_rejected_payment_fails_without_settlement() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+    online_trader(&app, &token).await;
+    let payment_id = marked_payment(&app, &site, "order-1").await;
+
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/payment/{}/reject", payment_id),
+            Some(&token),
+            json!({ "reason": "" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = app
+        .post(
+            &format!("/api/v1/payment/{}/reject", payment_id),
+            Some(&token),
+            json!({ "reason": "No transfer received" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "failed");
+    assert_eq!(body["data"]["rejection_reason"], "No transfer received");
+    assert!(body["data"]["dispute_until"].is_string());
+    assert_eq!(merchant_balance(&app, &token, &site).await, Value::Null);
+
+    let (_, body) = app
+        .get(
+            &format!("/api/v1/audit?entity_type=payment&entity_id={}", payment_id),
+            Some(&token),
+        )
+        .await;
+    let entries = body["data"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["action"], "update");
+    assert_eq!(entries[0]["actor_id"], admin.id.to_string());
+    assert_eq!(entries[0]["changes"]["status"]["after"], "failed");
+
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/payment/{}/confirm", payment_id),
+            Some(&token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_merchant_cancels_payment_until_it_is_marked_paid() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+    let other_site = app.onboard_merchant(&token, "Globex").await;
+    online_trader(&app, &token).await;
+
+    let (_, body) = app
+        .signed(
+            &site,
+            "POST",
+            "/api/v1/gateway/payment",
+            Some(json!({ "external_order_id": "order-1", "amount": "100", "currency": "USD" })),
+        )
+        .await;
+    assert_eq!(body["data"]["status"], "pending");
+    let cancel_uri = format!(
+        "/api/v1/gateway/payment/{}/cancel",
+        body["data"]["id"].as_str().unwrap()
+    );
+
+    let (status, _) = app.signed(&other_site, "POST", &cancel_uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = app.signed(&site, "POST", &cancel_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "cancelled");
+    let payment_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, _) = app.signed(&site, "POST", &cancel_uri, None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, body) = app
+        .get(
+            &format!("/api/v1/webhook/delivery?payment_id={}", payment_id),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|delivery| delivery["event_type"] == "payment.cancelled"));
+
+    let payment_id = marked_payment(&app, &site, "order-2").await;
+    let (status, _) = app
+        .signed(
+            &site,
+            "POST",
+            &format!("/api/v1/gateway/payment/{}/cancel", payment_id),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_unconfirmed_payments_are_escalated_to_support() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let support = app.create_user("sam", support_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+    online_trader(&app, &token).await;
+    let overdue = marked_payment(&app, &site, "order-1").await;
+    let recent = marked_payment(&app, &site, "order-2").await;
+
+    {
+        let mut intents = app.payments.intents.lock().unwrap();
+        let intent = intents.get_mut(&overdue.parse().unwrap()).unwrap();
+        intent.marked_paid_at = Some(Utc::now() - Duration::hours(1));
+    }
+    let escalated = app
+        .state
+        .payment_confirm_use_case
+        .escalate_overdue()
+        .await
+        .unwrap();
+    assert_eq!(escalated, 1);
+
+    let support_token = app.token_for(&support);
+    let (status, body) = app
+        .get(
+            "/api/v1/payment?status=confirming&escalated=true",
+            Some(&support_token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let queue = body["data"].as_array().unwrap();
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue[0]["id"], overdue);
+    assert!(queue[0]["escalated_at"].is_string());
+
+    let (_, body) = app
+        .get(
+            "/api/v1/payment?status=confirming&escalated=false",
+            Some(&support_token),
+        )
+        .await;
+    assert_eq!(body["data"][0]["id"], recent);
+
+    // Support resolves the escalated payment
+    let (status, body) = app
+        .post(
+            &format!("/api/v1/payment/{}/confirm", overdue),
+            Some(&support_token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "paid");
+}
+
+#[tokio::test]
+async fn test_confirmation_requires_permission() {
+    let app = TestApp::new();
+    let finance = app.create_user("fiona", finance_role_id()).await;
+    let token = app.token_for(&finance);
+    let payment_uri = format!("/api/v1/payment/{}", Uuid::new_v4());
+
+    let (status, _) = app
+        .post(&format!("{}/confirm", payment_uri), Some(&token), json!({}))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = app
+        .post(
+            &format!("{}/reject", payment_uri),
+            Some(&token),
+            json!({ "reason": "No transfer received" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Finance can still read evidence
+    let (status, _) = app
+        .get(&format!("{}/evidence", payment_uri), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,266 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TEST_PASSWORD};
+use p2p_payment::common::hash_utils::hash_password;
+use p2p_payment::domains::backoffice::{
+    domain::user::User,
+    role::{admin_role_id, support_role_id, user_role_id},
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_list_roles_with_permissions() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let (status, body) = app.get("/api/v1/role", Some(&token)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let roles = body["data"].as_array().unwrap();
+    assert_eq!(roles.len(), 5);
+    let support = roles.iter().find(|r| r["role_name"] == "Support").unwrap();
+    assert_eq!(support["is_system"], true);
+    assert_eq!(
+        support["permissions"],
+        json!([
+            "disputes:read",
+            "disputes:write",
+            "merchants:read",
+            "payments:confirm",
+            "payments:read",
+            "users:read",
+            "webhooks:read",
+            "webhooks:redeliver"
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_role_routes_require_permission() {
+    let app = TestApp::new();
+    let support = app.create_user("sam", support_role_id()).await;
+    let token = app.token_for(&support);
+
+    let (status, _) = app.get("/api/v1/role", Some(&token)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = app
+        .post(
+            "/api/v1/role",
+            Some(&token),
+            json!({ "role_name": "Auditor" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_custom_role_lifecycle() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let (status, body) = app
+        .post(
+            "/api/v1/role",
+            Some(&token),
+            json!({
+                "role_name": "Auditor",
+                "role_description": "External auditors",
+                "permissions": ["users:read"]
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["is_system"], false);
+    let role_id = body["data"]["role_id"].as_str().unwrap().to_string();
+
+    let (status, body) = app
+        .patch(
+            &format!("/api/v1/role/{}", role_id),
+            Some(&token),
+            json!({ "role_name": "Compliance", "permissions": ["users:read", "merchants:read"] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["role_name"], "Compliance");
+    assert_eq!(
+        body["data"]["permissions"],
+        json!(["merchants:read", "users:read"])
+    );
+
+    let (status, _) = app
+        .delete(&format!("/api/v1/role/{}", role_id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .get(&format!("/api/v1/role/{}", role_id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = app
+        .get(
+            &format!("/api/v1/audit?entity_type=role&entity_id={}", role_id),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body["data"].as_array().unwrap();
+    let actions: Vec<&str> = entries
+        .iter()
+        .map(|e| e["action"].as_str().unwrap())
+        .collect();
+    assert_eq!(actions, ["delete", "update", "create"]);
+    assert!(entries
+        .iter()
+        .all(|e| e["actor_id"] == admin.id.to_string()));
+    assert_eq!(
+        entries[1]["changes"]["role_name"],
+        json!({ "before": "Auditor", "after": "Compliance" })
+    );
+    assert_eq!(
+        entries[1]["changes"]["permissions"]["before"],
+        json!(["users:read"])
+    );
+    assert_eq!(entries[2]["changes"]["role_name"]["after"], "Auditor");
+}
+
+#[tokio::test]
+async fn test_custom_role_permissions_apply_on_login() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let (_, body) = app
+        .post(
+            "/api/v1/role",
+            Some(&token),
+            json!({ "role_name": "Auditor", "permissions": ["roles:read"] }),
+        )
+        .await;
+    let role_id = body["data"]["role_id"].as_str().unwrap().parse().unwrap();
+
+    let role = app
+        .state
+        .role_repository
+        .find_by_id(role_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let auditor = app.users.insert(User::new(
+        "auditor".to_string(),
+        "auditor@example.com".to_string(),
+        hash_password(TEST_PASSWORD).unwrap(),
+        role,
+    ));
+
+    let tokens = app.login(&auditor).await;
+    let auditor_token = tokens["access_token"].as_str().unwrap();
+
+    let (status, _) = app.get("/api/v1/role", Some(auditor_token)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app.get("/api/v1/user", Some(auditor_token)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Role is in use now
+    let (status, body) = app
+        .delete(&format!("/api/v1/role/{}", role_id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["message"],
+        "Validation error: Role 'Auditor' is still assigned to 1 user(s)"
+    );
+
+    // Deleted users do not hold the role back; they fall back to the User role
+    let (status, _) = app
+        .delete(&format!("/api/v1/user/{}", auditor.id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .delete(&format!("/api/v1/role/{}", role_id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let stored = app.users.stored(auditor.id).unwrap();
+    assert_eq!(stored.role.role_id, user_role_id());
+}
+
+#[tokio::test]
+async fn test_system_roles_are_protected() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let (status, _) = app
+        .delete(&format!("/api/v1/role/{}", user_role_id()), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = app
+        .patch(
+            &format!("/api/v1/role/{}", user_role_id()),
+            Some(&token),
+            json!({ "role_name": "Member" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Permissions of system roles stay editable
+    let (status, body) = app
+        .patch(
+            &format!("/api/v1/role/{}", user_role_id()),
+            Some(&token),
+            json!({ "permissions": [] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["permissions"], json!([]));
+
+    // ...except that Admin cannot lose the permission to manage roles
+    let (status, body) = app
+        .patch(
+            &format!("/api/v1/role/{}", admin_role_id()),
+            Some(&token),
+            json!({ "permissions": ["users:write"] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(
+        body["message"],
+        "Forbidden: System role 'Admin' must keep the 'roles:write' permission"
+    );
+    let (status, body) = app.get("/api/v1/role", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    let roles = body["data"].as_array().unwrap();
+    let admin_role = roles.iter().find(|r| r["role_name"] == "Admin").unwrap();
+    assert!(admin_role["permissions"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("roles:write")));
+}
+
+#[tokio::test]
+async fn test_create_role_rejects_unknown_permission() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let (status, body) = app
+        .post(
+            "/api/v1/role",
+            Some(&token),
+            json!({ "role_name": "Auditor", "permissions": ["everything:all"] }),
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["message"],
+        "Validation error: Unknown permission 'everything:all'"
+    );
+}
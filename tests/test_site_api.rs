@@ -0,0 +1,141 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use p2p_payment::domains::backoffice::role::{admin_role_id, support_role_id};
+use serde_json::{json, Value};
+
+fn site_request(name: &str, url: &str) -> Value {
+    json!({
+        "name": name,
+        "url": url,
+        "callback_url": format!("{}/callback", url),
+        "redirect_success_url": format!("{}/ok", url),
+        "redirect_fail_url": format!("{}/fail", url),
+    })
+}
+
+async fn create_merchant(app: &TestApp, token: &str) -> String {
+    let (_, body) = app
+        .post("/api/v1/merchant", Some(token), json!({ "name": "Acme" }))
+        .await;
+    body["data"]["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_create_list_and_deactivate_site() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let merchant_id = create_merchant(&app, &token).await;
+    let sites_uri = format!("/api/v1/merchant/{}/site", merchant_id);
+
+    let (status, body) = app
+        .post(
+            &sites_uri,
+            Some(&token),
+            site_request("Acme Shop", "https://shop.acme.com"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "active");
+    assert_eq!(
+        body["data"]["callback_url"],
+        "https://shop.acme.com/callback"
+    );
+    let site_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, _) = app
+        .post(
+            &sites_uri,
+            Some(&token),
+            site_request("Acme Shop", "https://other.acme.com"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = app.get(&sites_uri, Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    let (status, body) = app
+        .patch(
+            &format!("{}/{}/status", sites_uri, site_id),
+            Some(&token),
+            json!({ "status": "inactive" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "inactive");
+}
+
+#[tokio::test]
+async fn test_site_urls_are_validated() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let merchant_id = create_merchant(&app, &token).await;
+    let sites_uri = format!("/api/v1/merchant/{}/site", merchant_id);
+
+    let mut request = site_request("Acme Shop", "https://shop.acme.com");
+    request["redirect_fail_url"] = json!("https://phishing.example/fail");
+    let (status, body) = app.post(&sites_uri, Some(&token), request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .contains("redirect_fail_url"));
+
+    let (status, _) = app
+        .post(&sites_uri, Some(&token), site_request("Acme Shop", "shop"))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, body) = app
+        .post(
+            &sites_uri,
+            Some(&token),
+            site_request("Acme Shop", "https://shop.acme.com"),
+        )
+        .await;
+    let site_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, _) = app
+        .patch(
+            &format!("{}/{}", sites_uri, site_id),
+            Some(&token),
+            json!({ "url": "https://acme.org" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_site_write_requires_permission() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let support = app.create_user("support", support_role_id()).await;
+    let merchant_id = create_merchant(&app, &app.token_for(&admin)).await;
+    let token = app.token_for(&support);
+    let sites_uri = format!("/api/v1/merchant/{}/site", merchant_id);
+
+    let (status, _) = app.get(&sites_uri, Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app
+        .post(
+            &sites_uri,
+            Some(&token),
+            site_request("Acme Shop", "https://shop.acme.com"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = app
+        .get(
+            &format!("/api/v1/merchant/{}/site", uuid::Uuid::new_v4()),
+            Some(&app.token_for(&admin)),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{build_request, TestApp};
+use p2p_payment::domains::backoffice::role::{admin_role_id, support_role_id};
+use serde_json::json;
+
+/// Creates a merchant with one site and returns the credentials base URI
+async fn site_credentials_uri(app: &TestApp, token: &str) -> String {
+    let (_, body) = app
+        .post("/api/v1/merchant", Some(token), json!({ "name": "Acme" }))
+        .await;
+    let merchant_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let (_, body) = app
+        .post(
+            &format!("/api/v1/merchant/{}/site", merchant_id),
+            Some(token),
+            json!({
+                "name": "Acme Shop",
+                "url": "https://shop.acme.com",
+                "callback_url": "https://shop.acme.com/callback",
+                "redirect_success_url": "https://shop.acme.com/ok",
+                "redirect_fail_url": "https://shop.acme.com/fail",
+            }),
+        )
+        .await;
+    let site_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    format!(
+        "/api/v1/merchant/{}/site/{}/credentials",
+        merchant_id, site_id
+    )
+}
+
+#[tokio::test]
+async fn test_issue_shows_secret_once() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let uri = site_credentials_uri(&app, &token).await;
+
+    let (status, body) = app.post(&uri, Some(&token), json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    let secret_key = body["data"]["secret_key"].as_str().unwrap().to_string();
+    assert!(secret_key.starts_with("sk_"));
+    assert!(body["data"]["credentials"]["public_key"]
+        .as_str()
+        .unwrap()
+        .starts_with("pk_"));
+
+    let (status, body) = app.get(&uri, Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert!(!body.to_string().contains(&secret_key));
+
+    // A second key must come from a rotation
+    let (status, _) = app.post(&uri, Some(&token), json!({})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_rotate_and_revoke() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let uri = site_credentials_uri(&app, &token).await;
+
+    let (_, body) = app.post(&uri, Some(&token), json!({})).await;
+    let old_id = body["data"]["credentials"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let (status, body) = app
+        .post(&format!("{}/rotate", uri), Some(&token), json!({}))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let new_id = body["data"]["credentials"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let (_, body) = app.get(&uri, Some(&token)).await;
+    let keys = body["data"].as_array().unwrap();
+    let old = keys.iter().find(|k| k["id"] == old_id.as_str()).unwrap();
+    let new = keys.iter().find(|k| k["id"] == new_id.as_str()).unwrap();
+    assert_eq!(old["is_active"], true);
+    assert!(old["expires_at"].is_string());
+    assert!(new["expires_at"].is_null());
+
+    let (status, body) = app
+        .post(
+            &format!("{}/{}/revoke", uri, old_id),
+            Some(&token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["is_active"], false);
+    assert!(body["data"]["revoked_at"].is_string());
+}
+
+#[tokio::test]
+async fn test_credentials_write_requires_permission() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let support = app.create_user("support", support_role_id()).await;
+    let uri = site_credentials_uri(&app, &app.token_for(&admin)).await;
+    let token = app.token_for(&support);
+
+    let (status, _) = app.post(&uri, Some(&token), json!({})).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = app.get(&uri, Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_update_allowed_ips_validates_entries() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let uri = site_credentials_uri(&app, &token).await;
+
+    let (_, body) = app.post(&uri, Some(&token), json!({})).await;
+    let allowed_ips_uri = format!(
+        "{}/{}/allowed-ips",
+        uri,
+        body["data"]["credentials"]["id"].as_str().unwrap()
+    );
+
+    let (status, _) = app
+        .send(build_request(
+            "PUT",
+            &allowed_ips_uri,
+            Some(&token),
+            Some(json!({ "allowed_ips": ["10.0.0.1", "not-an-ip"] })),
+        ))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = app
+        .send(build_request(
+            "PUT",
+            &allowed_ips_uri,
+            Some(&token),
+            Some(json!({ "allowed_ips": ["2001:db8::1", "10.1.2.3/8"] })),
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"]["allowed_ips"],
+        json!(["2001:db8::1/128", "10.0.0.0/8"])
+    );
+
+    let support = app.create_user("support", support_role_id()).await;
+    let (status, _) = app
+        .send(build_request(
+            "PUT",
+            &allowed_ips_uri,
+            Some(&app.token_for(&support)),
+            Some(json!({ "allowed_ips": [] })),
+        ))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
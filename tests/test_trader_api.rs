@@ -0,0 +1,368 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::TestApp;
+use p2p_payment::domains::backoffice::role::{
+    admin_role_id, finance_role_id, risk_role_id, support_role_id,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+async fn create_trader(app: &TestApp, token: &str, name: &str) -> String {
+    let (status, body) = app
+        .post(
+            "/api/v1/trader",
+            Some(token),
+            json!({ "name": name, "contact": "@trader" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    body["data"]["id"].as_str().unwrap().to_string()
+}
+
+async fn add_card(app: &TestApp, token: &str, trader_id: &str) -> Value {
+    let (status, body) = app
+        .post(
+            &format!("/api/v1/trader/{}/requisite", trader_id),
+            Some(token),
+            json!({
+                "kind": "card",
+                "number": "4242 4242 4242 4242",
+                "bank_name": "Example Bank",
+                "holder_name": "JANE DOE",
+                "currency": "USD",
+                "daily_limit": "5000",
+                "monthly_limit": "50000.00"
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    body["data"].clone()
+}
+
+#[tokio::test]
+async fn test_trader_lifecycle_with_requisites() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let trader_id = create_trader(&app, &token, "Alice").await;
+    let trader_uri = format!("/api/v1/trader/{}", trader_id);
+
+    let (status, _) = app
+        .post("/api/v1/trader", Some(&token), json!({ "name": " Alice " }))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Cannot go online without a requisite
+    let availability_uri = format!("{}/availability", trader_uri);
+    let (status, _) = app
+        .patch(
+            &availability_uri,
+            Some(&token),
+            json!({ "availability": "online" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let requisite = add_card(&app, &token, &trader_id).await;
+    assert_eq!(requisite["masked_number"], "**** 4242");
+    assert_eq!(requisite["currency"], "USD");
+    assert_eq!(
+        requisite["daily_limit"],
+        json!({ "amount": "5000.00", "currency": "USD" })
+    );
+    assert!(requisite.get("number").is_none());
+    assert!(requisite.get("encrypted_number").is_none());
+
+    // Only the ciphertext is stored
+    let stored = app.traders.requisites.lock().unwrap()[0].clone();
+    assert!(!stored.encrypted_number.contains("4242424242424242"));
+    assert_eq!(
+        app.state
+            .secret_cipher
+            .decrypt(&stored.encrypted_number)
+            .unwrap(),
+        "4242424242424242"
+    );
+
+    let (status, _) = app
+        .post(
+            &format!("{}/requisite", trader_uri),
+            Some(&token),
+            json!({
+                "kind": "card",
+                "number": "4242 4242 4242 4241",
+                "bank_name": "Example Bank",
+                "holder_name": "JANE DOE",
+                "currency": "USD",
+                "daily_limit": "5000",
+                "monthly_limit": "50000"
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = app
+        .patch(
+            &availability_uri,
+            Some(&token),
+            json!({ "availability": "online" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["availability"], "online");
+
+    create_trader(&app, &token, "Bob").await;
+    let (status, body) = app
+        .get("/api/v1/trader?availability=online", Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let traders = body["data"].as_array().unwrap();
+    assert_eq!(traders.len(), 1);
+    assert_eq!(traders[0]["name"], "Alice");
+    assert_eq!(traders[0]["requisites"].as_array().unwrap().len(), 1);
+
+    let requisite_uri = format!(
+        "{}/requisite/{}",
+        trader_uri,
+        requisite["id"].as_str().unwrap()
+    );
+    let (status, _) = app
+        .patch(
+            &requisite_uri,
+            Some(&token),
+            json!({ "daily_limit": "60000" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = app
+        .patch(
+            &requisite_uri,
+            Some(&token),
+            json!({ "monthly_limit": "80000.50", "is_active": false }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["monthly_limit"]["amount"], "80000.50");
+    assert_eq!(body["data"]["is_active"], false);
+
+    // Deactivating the only requisite takes the trader offline
+    let (status, body) = app.get(&trader_uri, Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["availability"], "offline");
+
+    let (status, _) = app.delete(&requisite_uri, Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = app.delete(&requisite_uri, Some(&token)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = app
+        .patch(
+            &trader_uri,
+            Some(&token),
+            json!({ "name": "Alice Smith", "contact": "" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["name"], "Alice Smith");
+    assert_eq!(body["data"]["contact"], Value::Null);
+
+    let (status, _) = app.delete(&trader_uri, Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = app.get(&trader_uri, Some(&token)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_trader_and_requisite_changes_are_audited() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+
+    let trader_id = create_trader(&app, &token, "Alice").await;
+    let requisite = add_card(&app, &token, &trader_id).await;
+    let requisite_id = requisite["id"].as_str().unwrap();
+    app.patch(
+        &format!("/api/v1/trader/{}/availability", trader_id),
+        Some(&token),
+        json!({ "availability": "online" }),
+    )
+    .await;
+    // Removing the only requisite also takes the trader offline
+    let (status, _) = app
+        .delete(
+            &format!("/api/v1/trader/{}/requisite/{}", trader_id, requisite_id),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app
+        .get(
+            &format!("/api/v1/audit?entity_type=trader&entity_id={}", trader_id),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body["data"].as_array().unwrap();
+    let actions: Vec<&str> = entries
+        .iter()
+        .map(|e| e["action"].as_str().unwrap())
+        .collect();
+    assert_eq!(actions, ["update", "update", "create"]);
+    assert!(entries
+        .iter()
+        .all(|e| e["actor_id"] == admin.id.to_string()));
+    assert_eq!(
+        entries[0]["changes"]["availability"],
+        json!({ "before": "online", "after": "offline" })
+    );
+
+    let (_, body) = app
+        .get(
+            &format!(
+                "/api/v1/audit?entity_type=requisite&entity_id={}",
+                requisite_id
+            ),
+            Some(&token),
+        )
+        .await;
+    let entries = body["data"].as_array().unwrap();
+    let actions: Vec<&str> = entries
+        .iter()
+        .map(|e| e["action"].as_str().unwrap())
+        .collect();
+    assert_eq!(actions, ["delete", "create"]);
+    assert_eq!(entries[1]["changes"]["masked_number"]["after"], "**** 4242");
+    assert!(!body.to_string().contains("4242424242424242"));
+
+    let (_, body) = app
+        .post(
+            &format!("/api/v1/trader/{}/deposit", trader_id),
+            Some(&token),
+            json!({ "amount": "1000", "currency": "USD" }),
+        )
+        .await;
+    let entry_id = body["data"]["id"].as_str().unwrap();
+    let (_, body) = app
+        .get(
+            &format!(
+                "/api/v1/audit?entity_type=journal_entry&entity_id={}",
+                entry_id
+            ),
+            Some(&token),
+        )
+        .await;
+    let entries = body["data"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["action"], "create");
+    assert_eq!(entries[0]["actor_id"], admin.id.to_string());
+    assert_eq!(entries[0]["changes"]["kind"]["after"], "deposit");
+}
+
+#[tokio::test]
+async fn test_deposits_are_posted_to_the_ledger() {
+    let app = TestApp::new();
+    let risk = app.create_user("rick", risk_role_id()).await;
+    let finance = app.create_user("fiona", finance_role_id()).await;
+    let token = app.token_for(&risk);
+
+    let trader_id = create_trader(&app, &token, "Alice").await;
+    let deposit_uri = format!("/api/v1/trader/{}/deposit", trader_id);
+
+    let (status, body) = app.get(&deposit_uri, Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!([]));
+
+    for amount in ["1000", "250.50"] {
+        let (status, body) = app
+            .post(
+                &deposit_uri,
+                Some(&token),
+                json!({ "amount": amount, "currency": "USD" }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["kind"], "deposit");
+        assert_eq!(body["data"]["created_by"], risk.id.to_string());
+    }
+
+    let (status, _) = app
+        .post(
+            &deposit_uri,
+            Some(&token),
+            json!({ "amount": "-5", "currency": "USD" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = app.get(&deposit_uri, Some(&app.token_for(&finance))).await;
+    assert_eq!(status, StatusCode::OK);
+    let balances = body["data"].as_array().unwrap();
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0]["account"]["kind"], "trader");
+    assert_eq!(balances[0]["account"]["owner_id"], trader_id);
+    assert_eq!(
+        balances[0]["balance"],
+        json!({ "amount": "1250.50", "currency": "USD" })
+    );
+
+    // A trader with ledger history is kept
+    let (status, _) = app
+        .delete(&format!("/api/v1/trader/{}", trader_id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/trader/{}/deposit", Uuid::new_v4()),
+            Some(&token),
+            json!({ "amount": "10", "currency": "USD" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_trader_permissions() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let finance = app.create_user("fiona", finance_role_id()).await;
+    let support = app.create_user("sam", support_role_id()).await;
+    let trader_id = create_trader(&app, &app.token_for(&admin), "Alice").await;
+
+    let finance_token = app.token_for(&finance);
+    let (status, _) = app
+        .get(
+            &format!("/api/v1/trader/{}", trader_id),
+            Some(&finance_token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = app
+        .post(
+            "/api/v1/trader",
+            Some(&finance_token),
+            json!({ "name": "Bob" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/trader/{}/deposit", trader_id),
+            Some(&finance_token),
+            json!({ "amount": "10", "currency": "USD" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = app
+        .get("/api/v1/trader", Some(&app.token_for(&support)))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = app.get("/api/v1/trader", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
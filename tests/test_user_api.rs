@@ -0,0 +1,204 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::{TestApp, TEST_PASSWORD};
+use p2p_payment::domains::backoffice::role::{admin_role_id, support_role_id, user_role_id};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_deactivation_signs_user_out_until_activated() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let admin_token = app.token_for(&admin);
+    let user = app.create_user("mallory", user_role_id()).await;
+    let tokens = app.login(&user).await;
+    let access_token = tokens["access_token"].as_str().unwrap();
+    let refresh_token = tokens["refresh_token"].as_str().unwrap();
+
+    let (status, body) = app
+        .post(
+            &format!("/api/v1/user/{}/deactivate", user.id),
+            Some(&admin_token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["is_active"], false);
+
+    let (status, _) = app.get("/api/v1/user/me", Some(access_token)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = app
+        .post(
+            "/api/v1/auth/refresh",
+            None,
+            json!({ "refresh_token": refresh_token }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let login = json!({ "login": "mallory", "password": TEST_PASSWORD });
+    let (status, _) = app.post("/api/v1/auth/login", None, login.clone()).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, body) = app
+        .post(
+            &format!("/api/v1/user/{}/activate", user.id),
+            Some(&admin_token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["is_active"], true);
+
+    // Sessions ended by the deactivation stay ended
+    let (status, _) = app.get("/api/v1/user/me", Some(access_token)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = app.post("/api/v1/auth/login", None, login).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = app
+        .get(
+            &format!("/api/v1/audit?entity_type=user&entity_id={}", user.id),
+            Some(&admin_token),
+        )
+        .await;
+    let changes: Vec<&serde_json::Value> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| &entry["changes"]["is_active"])
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            &json!({ "before": false, "after": true }),
+            &json!({ "before": true, "after": false })
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_token_of_deactivated_user_is_rejected() {
+    let app = TestApp::new();
+    let user = app.create_user("niaj", user_role_id()).await;
+    let token = app.token_for(&user);
+
+    // Deactivated outside the API, so no session was revoked
+    let mut stored = user.clone();
+    stored.deactivate();
+    app.users.insert(stored);
+
+    let (status, body) = app.get("/api/v1/user/me", Some(&token)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["message"], "Unauthorized: User is inactive");
+}
+
+#[tokio::test]
+async fn test_status_changes_are_validated() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let user = app.create_user("olivia", user_role_id()).await;
+
+    let (status, body) = app
+        .post(
+            &format!("/api/v1/user/{}/deactivate", admin.id),
+            Some(&token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["message"],
+        "Validation error: You cannot deactivate your own account"
+    );
+
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/user/{}/activate", user.id),
+            Some(&token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/user/{}/deactivate", uuid::Uuid::new_v4()),
+            Some(&token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let support = app.create_user("sam", support_role_id()).await;
+    let (status, _) = app
+        .post(
+            &format!("/api/v1/user/{}/deactivate", user.id),
+            Some(&app.token_for(&support)),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_delete_keeps_row_but_hides_user() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let user = app.create_user("peggy", user_role_id()).await;
+    let user_token = app.token_for(&user);
+
+    let (status, _) = app
+        .delete(&format!("/api/v1/user/{}", user.id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let stored = app.users.stored(user.id).expect("row is kept");
+    assert!(stored.deleted_at.is_some());
+    assert!(!stored.is_active);
+
+    let (status, _) = app
+        .get(&format!("/api/v1/user/{}", user.id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, body) = app.get("/api/v1/user", Some(&token)).await;
+    let usernames: Vec<&str> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|u| u["username"].as_str().unwrap())
+        .collect();
+    assert_eq!(usernames, ["admin"]);
+
+    let (status, _) = app.get("/api/v1/user/me", Some(&user_token)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = app
+        .delete(&format!("/api/v1/user/{}", user.id), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // The username stays taken by the deleted account
+    let (status, body) = app
+        .post(
+            "/api/v1/user",
+            Some(&token),
+            json!({
+                "username": "peggy",
+                "email": "peggy2@example.com",
+                "password": TEST_PASSWORD,
+                "role_id": user_role_id()
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["message"],
+        "Validation error: Username 'peggy' already exists"
+    );
+}
@@ -0,0 +1,329 @@
+mod common;
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+use chrono::{Duration, Utc};
+use common::{MerchantSite, TestApp};
+use p2p_payment::{
+    common::request_signature::{self, API_KEY_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER},
+    domains::backoffice::role::{admin_role_id, finance_role_id, support_role_id},
+};
+use serde_json::{json, Value};
+use std::sync::{
+    atomic::{AtomicU16, Ordering},
+    Arc, Mutex,
+};
+use uuid::Uuid;
+
+/// Merchant endpoint stub recording every webhook it receives
+#[derive(Clone)]
+struct Receiver {
+    url: String,
+    requests: Arc<Mutex<Vec<(HeaderMap, String)>>>,
+    status: Arc<AtomicU16>,
+}
+
+impl Receiver {
+    async fn start(status: StatusCode) -> Self {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let receiver = Self {
+            url: format!("http://{}", listener.local_addr().unwrap()),
+            requests: Arc::default(),
+            status: Arc::new(AtomicU16::new(status.as_u16())),
+        };
+
+        let router = Router::new()
+            .route("/callback", post(receive))
+            .with_state(receiver.clone());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        receiver
+    }
+
+    fn respond_with(&self, status: StatusCode) {
+        self.status.store(status.as_u16(), Ordering::SeqCst);
+    }
+
+    fn requests(&self) -> Vec<(HeaderMap, String)> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn receive(State(receiver): State<Receiver>, headers: HeaderMap, body: Bytes) -> StatusCode {
+    receiver
+        .requests
+        .lock()
+        .unwrap()
+        .push((headers, String::from_utf8(body.to_vec()).unwrap()));
+    StatusCode::from_u16(receiver.status.load(Ordering::SeqCst)).unwrap()
+}
+
+/// Points every URL of the site at `base_url`; the callback is `{base_url}/callback`
+async fn point_site_at(app: &TestApp, token: &str, site: &MerchantSite, base_url: &str) {
+    let (status, _) = app
+        .patch(
+            &format!(
+                "/api/v1/merchant/{}/site/{}",
+                site.merchant_id, site.site_id
+            ),
+            Some(token),
+            json!({
+                "url": base_url,
+                "callback_url": format!("{}/callback", base_url),
+                "redirect_success_url": format!("{}/ok", base_url),
+                "redirect_fail_url": format!("{}/fail", base_url),
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+/// Creates a payment and lets the expiry sweep expire it; returns its ID
+async fn expired_payment(app: &TestApp, site: &MerchantSite) -> String {
+    let (status, body) = app
+        .signed(
+            site,
+            "POST",
+            "/api/v1/gateway/payment",
+            Some(json!({ "external_order_id": "order-1", "amount": "100", "currency": "USD" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let payment_id = body["data"]["id"].as_str().unwrap().to_string();
+
+    app.payments
+        .intents
+        .lock()
+        .unwrap()
+        .get_mut(&Uuid::parse_str(&payment_id).unwrap())
+        .unwrap()
+        .expires_at = Utc::now() - Duration::minutes(1);
+    assert_eq!(
+        app.state
+            .payment_intent_expire_use_case
+            .execute()
+            .await
+            .unwrap(),
+        1
+    );
+
+    payment_id
+}
+
+async fn delivery_for(app: &TestApp, token: &str, payment_id: &str) -> Value {
+    let (status, body) = app
+        .get(
+            &format!("/api/v1/webhook/delivery?payment_id={}", payment_id),
+            Some(token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let deliveries = body["data"].as_array().unwrap();
+    assert_eq!(deliveries.len(), 1);
+    deliveries[0].clone()
+}
+
+#[tokio::test]
+async fn test_status_change_is_delivered_signed() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+    let receiver = Receiver::start(StatusCode::OK).await;
+    point_site_at(&app, &token, &site, &receiver.url).await;
+
+    let payment_id = expired_payment(&app, &site).await;
+    let delivered = app
+        .state
+        .webhook_deliver_use_case
+        .dispatch_due()
+        .await
+        .unwrap();
+    assert_eq!(delivered, 1);
+
+    let requests = receiver.requests();
+    assert_eq!(requests.len(), 1);
+    let (headers, body) = &requests[0];
+    let header = |name: &str| headers.get(name).unwrap().to_str().unwrap().to_string();
+    assert_eq!(header(API_KEY_HEADER), site.public_key);
+    assert_eq!(header("content-type"), "application/json");
+    let payload = request_signature::signing_payload(
+        header(TIMESTAMP_HEADER).parse().unwrap(),
+        "POST",
+        "/callback",
+        body.as_bytes(),
+    );
+    assert!(request_signature::verify(
+        &site.secret_key,
+        &payload,
+        &header(SIGNATURE_HEADER)
+    ));
+    assert_eq!(header("x-webhook-event"), "payment.expired");
+
+    let event: Value = serde_json::from_str(body).unwrap();
+    assert_eq!(event["type"], "payment.expired");
+    assert_eq!(event["data"]["id"], payment_id);
+    assert_eq!(event["data"]["status"], "expired");
+    assert_eq!(event["data"]["amount"], "100.00");
+
+    let delivery = delivery_for(&app, &token, &payment_id).await;
+    assert_eq!(delivery["status"], "delivered");
+    assert_eq!(delivery["attempts"], 1);
+    assert_eq!(delivery["last_response_status"], 200);
+    assert_eq!(header("x-webhook-id"), delivery["id"].as_str().unwrap());
+
+    // Delivered webhooks are not sent again
+    let delivered = app
+        .state
+        .webhook_deliver_use_case
+        .dispatch_due()
+        .await
+        .unwrap();
+    assert_eq!(delivered, 0);
+    assert_eq!(receiver.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_failed_webhook_is_retried_until_max_attempts_then_redelivered() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+    let receiver = Receiver::start(StatusCode::SERVICE_UNAVAILABLE).await;
+    point_site_at(&app, &token, &site, &receiver.url).await;
+    let payment_id = expired_payment(&app, &site).await;
+
+    // The test config allows 3 attempts with no backoff
+    for _ in 0..4 {
+        app.state
+            .webhook_deliver_use_case
+            .dispatch_due()
+            .await
+            .unwrap();
+    }
+    assert_eq!(receiver.requests().len(), 3);
+
+    let delivery = delivery_for(&app, &token, &payment_id).await;
+    assert_eq!(delivery["status"], "failed");
+    assert_eq!(delivery["attempts"], 3);
+    assert_eq!(delivery["last_response_status"], 503);
+    assert_eq!(delivery["next_attempt_at"], Value::Null);
+    let delivery_uri = format!(
+        "/api/v1/webhook/delivery/{}",
+        delivery["id"].as_str().unwrap()
+    );
+
+    let (status, body) = app
+        .get("/api/v1/webhook/delivery?status=failed", Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    receiver.respond_with(StatusCode::OK);
+    let (status, body) = app
+        .post(
+            &format!("{}/redeliver", delivery_uri),
+            Some(&token),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["status"], "delivered");
+    assert_eq!(body["data"]["attempts"], 4);
+    assert_eq!(receiver.requests().len(), 4);
+
+    let (status, body) = app
+        .get(&format!("{}/attempt", delivery_uri), Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let attempts = body["data"].as_array().unwrap();
+    assert_eq!(attempts.len(), 4);
+    assert_eq!(attempts[0]["attempt_number"], 1);
+    assert_eq!(attempts[0]["response_status"], 503);
+    assert_eq!(attempts[0]["triggered_by"], Value::Null);
+    assert_eq!(attempts[0]["url"], format!("{}/callback", receiver.url));
+    assert_eq!(attempts[3]["response_status"], 200);
+    assert_eq!(attempts[3]["triggered_by"], admin.id.to_string());
+}
+
+#[tokio::test]
+async fn test_unreachable_callback_is_logged_as_failed_attempt() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let token = app.token_for(&admin);
+    let site = app.onboard_merchant(&token, "Acme").await;
+
+    // Nothing listens on a port that was just released
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    point_site_at(&app, &token, &site, &base_url).await;
+    let payment_id = expired_payment(&app, &site).await;
+
+    app.state
+        .webhook_deliver_use_case
+        .dispatch_due()
+        .await
+        .unwrap();
+
+    let delivery = delivery_for(&app, &token, &payment_id).await;
+    assert_eq!(delivery["status"], "pending");
+    assert_eq!(delivery["attempts"], 1);
+    assert_eq!(delivery["last_response_status"], Value::Null);
+    assert!(delivery["last_error"].as_str().is_some());
+
+    let (_, body) = app
+        .get(
+            &format!(
+                "/api/v1/webhook/delivery/{}/attempt",
+                delivery["id"].as_str().unwrap()
+            ),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(body["data"][0]["response_status"], Value::Null);
+    assert!(body["data"][0]["error"].as_str().is_some());
+}
+
+#[tokio::test]
+async fn test_webhook_routes_require_permission() {
+    let app = TestApp::new();
+    let admin = app.create_user("admin", admin_role_id()).await;
+    let finance = app.create_user("finance", finance_role_id()).await;
+    let support = app.create_user("support", support_role_id()).await;
+    let site = app.onboard_merchant(&app.token_for(&admin), "Acme").await;
+    let payment_id = expired_payment(&app, &site).await;
+    let delivery = delivery_for(&app, &app.token_for(&admin), &payment_id).await;
+    let delivery_uri = format!(
+        "/api/v1/webhook/delivery/{}",
+        delivery["id"].as_str().unwrap()
+    );
+
+    let (status, _) = app.get(&delivery_uri, Some(&app.token_for(&finance))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = app
+        .post(
+            &format!("{}/redeliver", delivery_uri),
+            Some(&app.token_for(&finance)),
+            json!({}),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = app.get(&delivery_uri, Some(&app.token_for(&support))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["event_type"], "payment.expired");
+
+    let (status, _) = app
+        .get(
+            &format!("/api/v1/webhook/delivery/{}", Uuid::new_v4()),
+            Some(&app.token_for(&support)),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}